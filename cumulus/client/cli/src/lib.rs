@@ -304,6 +304,17 @@ pub struct RunCmd {
 	)]
 	pub relay_chain_rpc_urls: Vec<Url>,
 
+	/// Stay connected to all `--relay-chain-rpc-urls` at once and cross-check them.
+	///
+	/// Requests are served by the first endpoint which agrees with the others on the best and
+	/// finalized blocks. Endpoints lagging behind or reporting diverging finalized blocks are
+	/// demoted, and responses are verified against the relay chain headers where possible.
+	/// Endpoints serving responses which fail verification are demoted as well.
+	///
+	/// Needs at least three endpoints, so that a majority can tell which one diverges.
+	#[arg(long, requires = "relay_chain_rpc_urls")]
+	pub relay_chain_rpc_cross_check: bool,
+
 	/// EXPERIMENTAL: Embed a light client for the relay chain. Only supported for full-nodes.
 	/// Will use the specified relay chain chainspec.
 	#[arg(long, conflicts_with_all = ["relay_chain_rpc_urls", "collator"])]
@@ -334,6 +345,8 @@ impl RunCmd {
 		let relay_chain_mode =
 			match (self.relay_chain_light_client, !self.relay_chain_rpc_urls.is_empty()) {
 				(true, _) => RelayChainMode::LightClient,
+				(_, true) if self.relay_chain_rpc_cross_check =>
+					RelayChainMode::CrossCheckedRpc(self.relay_chain_rpc_urls.clone()),
 				(_, true) => RelayChainMode::ExternalRpc(self.relay_chain_rpc_urls.clone()),
 				_ => RelayChainMode::Embedded,
 			};
@@ -349,6 +362,8 @@ pub enum RelayChainMode {
 	Embedded,
 	/// Connect to remote relay chain node via websocket RPC
 	ExternalRpc(Vec<Url>),
+	/// Connect to all given remote relay chain nodes via websocket RPC and cross-check them
+	CrossCheckedRpc(Vec<Url>),
 	/// Spawn embedded relay chain light client
 	LightClient,
 }
@@ -289,6 +289,10 @@ impl RelayChainInterface for Relaychain {
 	async fn scheduling_lookahead(&self, _: PHash) -> RelayChainResult<u32> {
 		unimplemented!("Not needed for test")
 	}
+
+	async fn submit_extrinsic(&self, _: Vec<u8>) -> RelayChainResult<()> {
+		unimplemented!("Not needed for test")
+	}
 }
 
 fn sproof_with_best_parent(client: &Client) -> RelayStateSproofBuilder {
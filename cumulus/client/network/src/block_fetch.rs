@@ -0,0 +1,332 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
+
+//! Request-response protocol to fetch parachain blocks directly from other parachain nodes.
+//!
+//! Every parachain node serves the blocks it knows with the [`BlockFetchRequestHandler`]. The
+//! [`PeerBlockFetcher`] asks the connected peers for a block, it is used by the PoV recovery when a
+//! block can not be recovered from the relay chain availability store.
+
+use codec::{Decode, DecodeAll, Encode};
+use futures::{Future, StreamExt};
+use sc_client_api::BlockBackend;
+use sc_network::{
+	request_responses::{IncomingRequest, OutgoingResponse},
+	service::traits::NetworkService,
+	IfDisconnected, NetworkBackend, ProtocolName, ReputationChange, MAX_RESPONSE_SIZE,
+};
+use sc_network_sync::SyncingService;
+use sp_runtime::{
+	traits::{Block as BlockT, Hash as HashT, Header as HeaderT, NumberFor},
+	StateVersion,
+};
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+
+use crate::LOG_TARGET;
+
+/// Incoming block fetch requests bounded queue size.
+const MAX_REQUEST_QUEUE: usize = 20;
+
+/// Maximum size of a block fetch request, it only carries a block hash.
+const MAX_REQUEST_SIZE: u64 = 1024;
+
+/// Timeout of a single block fetch request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum number of peers asked for a block before giving up.
+const MAX_PEERS_PER_FETCH: usize = 5;
+
+/// Generate the block fetch protocol name from the genesis hash and fork id.
+pub fn block_fetch_protocol_name<Hash: AsRef<[u8]>>(
+	genesis_hash: Hash,
+	fork_id: Option<&str>,
+) -> ProtocolName {
+	let genesis_hash = genesis_hash.as_ref();
+	if let Some(fork_id) = fork_id {
+		format!("/{}/{}/parachain-block/1", array_bytes::bytes2hex("", genesis_hash), fork_id)
+	} else {
+		format!("/{}/parachain-block/1", array_bytes::bytes2hex("", genesis_hash))
+	}
+	.into()
+}
+
+/// A request for the block with the given hash.
+#[derive(Encode, Decode, Debug)]
+struct BlockFetchRequest<Hash> {
+	hash: Hash,
+}
+
+/// The response to a [`BlockFetchRequest`], `None` if the block is unknown to the peer.
+type BlockFetchResponse<Block> = Option<Block>;
+
+/// Handler for incoming block fetch requests from a remote peer.
+pub struct BlockFetchRequestHandler<Block, Client> {
+	request_receiver: async_channel::Receiver<IncomingRequest>,
+	client: Arc<Client>,
+	_block: PhantomData<Block>,
+}
+
+impl<Block, Client> BlockFetchRequestHandler<Block, Client>
+where
+	Block: BlockT,
+	Client: BlockBackend<Block> + Send + Sync + 'static,
+{
+	/// Create a new [`BlockFetchRequestHandler`] and the configuration of its protocol.
+	pub fn new<N: NetworkBackend<Block, Block::Hash>>(
+		client: Arc<Client>,
+		fork_id: Option<&str>,
+	) -> (Self, N::RequestResponseProtocolConfig) {
+		let (tx, request_receiver) = async_channel::bounded(MAX_REQUEST_QUEUE);
+		let genesis_hash = client
+			.block_hash(0u32.into())
+			.ok()
+			.flatten()
+			.expect("Genesis block exists; qed");
+
+		let protocol_config = N::request_response_config(
+			block_fetch_protocol_name(genesis_hash, fork_id),
+			Vec::new(),
+			MAX_REQUEST_SIZE,
+			MAX_RESPONSE_SIZE,
+			REQUEST_TIMEOUT,
+			Some(tx),
+		);
+
+		(Self { request_receiver, client, _block: PhantomData }, protocol_config)
+	}
+
+	/// Run [`BlockFetchRequestHandler`].
+	pub async fn run(mut self) {
+		while let Some(IncomingRequest { peer, payload, pending_response }) =
+			self.request_receiver.next().await
+		{
+			let response = match BlockFetchRequest::<Block::Hash>::decode_all(&mut &payload[..]) {
+				Ok(request) => OutgoingResponse {
+					result: Ok(self.block(request.hash).encode()),
+					reputation_changes: Vec::new(),
+					sent_feedback: None,
+				},
+				Err(error) => {
+					tracing::debug!(
+						target: LOG_TARGET,
+						%peer,
+						?error,
+						"Failed to decode block fetch request",
+					);
+					OutgoingResponse {
+						result: Err(()),
+						reputation_changes: vec![ReputationChange::new(-(1 << 12), "bad request")],
+						sent_feedback: None,
+					}
+				},
+			};
+
+			if pending_response.send(response).is_err() {
+				tracing::debug!(
+					target: LOG_TARGET,
+					%peer,
+					"Failed to send block fetch response",
+				);
+			}
+		}
+	}
+
+	/// The block with the given `hash`, if it is known.
+	fn block(&self, hash: Block::Hash) -> BlockFetchResponse<Block> {
+		match self.client.block(hash) {
+			Ok(block) => block.map(|signed_block| signed_block.block),
+			Err(error) => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					block_hash = ?hash,
+					?error,
+					"Failed to read block for block fetch request",
+				);
+				None
+			},
+		}
+	}
+}
+
+/// Fetches blocks from the connected parachain peers.
+pub struct PeerBlockFetcher<Block: BlockT> {
+	network: Arc<dyn NetworkService>,
+	sync_service: Arc<SyncingService<Block>>,
+	protocol_name: ProtocolName,
+}
+
+impl<Block: BlockT> PeerBlockFetcher<Block> {
+	/// Create a new [`PeerBlockFetcher`].
+	///
+	/// `genesis_hash` and `fork_id` must be the ones the [`BlockFetchRequestHandler`] of the
+	/// chain is created with.
+	pub fn new(
+		network: Arc<dyn NetworkService>,
+		sync_service: Arc<SyncingService<Block>>,
+		genesis_hash: Block::Hash,
+		fork_id: Option<&str>,
+	) -> Self {
+		Self {
+			network,
+			sync_service,
+			protocol_name: block_fetch_protocol_name(genesis_hash, fork_id),
+		}
+	}
+
+	/// Fetch the block with the given `hash` and `number`.
+	///
+	/// The peers which report a best block at or above `number` are asked in turn, until one of
+	/// them answers with the requested block. Responses carrying another header or a body not
+	/// matching the extrinsics root of the header are discarded and the next peer is asked.
+	/// Returns `None` if no peer answered with the requested block.
+	pub fn fetch(
+		&self,
+		hash: Block::Hash,
+		number: NumberFor<Block>,
+	) -> impl Future<Output = Option<Block>> + Send + 'static {
+		let network = self.network.clone();
+		let sync_service = self.sync_service.clone();
+		let protocol_name = self.protocol_name.clone();
+
+		async move {
+			let peers = sync_service.peers_info().await.ok()?;
+			let request = BlockFetchRequest { hash }.encode();
+
+			for (peer, _) in peers
+				.into_iter()
+				.filter(|(_, info)| info.best_number >= number)
+				.take(MAX_PEERS_PER_FETCH)
+			{
+				let response = match network
+					.request(
+						peer,
+						protocol_name.clone(),
+						request.clone(),
+						None,
+						IfDisconnected::ImmediateError,
+					)
+					.await
+				{
+					Ok((response, _)) => response,
+					Err(error) => {
+						tracing::debug!(
+							target: LOG_TARGET,
+							%peer,
+							block_hash = ?hash,
+							?error,
+							"Block fetch request failed",
+						);
+						continue
+					},
+				};
+
+				match decode_response::<Block>(hash, &response) {
+					Ok(Some(block)) => return Some(block),
+					Ok(None) => {
+						tracing::trace!(
+							target: LOG_TARGET,
+							%peer,
+							block_hash = ?hash,
+							"Peer does not know the requested block",
+						);
+					},
+					Err(error) => {
+						tracing::debug!(
+							target: LOG_TARGET,
+							%peer,
+							block_hash = ?hash,
+							error,
+							"Invalid block fetch response",
+						);
+					},
+				}
+			}
+
+			None
+		}
+	}
+}
+
+/// Decode a block fetch response and check that it carries the block with the given `hash`.
+///
+/// The hash only commits to the header, so the body is checked against the extrinsics root of
+/// the header. The root depends on the state version of the runtime, which is unknown here, so
+/// the body is accepted if it matches the root of either state version.
+fn decode_response<Block: BlockT>(
+	hash: Block::Hash,
+	mut response: &[u8],
+) -> Result<BlockFetchResponse<Block>, &'static str> {
+	let block = BlockFetchResponse::<Block>::decode_all(&mut response)
+		.map_err(|_| "Failed to decode the response")?;
+
+	let Some(block) = block else { return Ok(None) };
+	if block.header().hash() != hash {
+		return Err("Response carries another block")
+	}
+
+	let extrinsics = block.extrinsics().iter().map(Encode::encode).collect::<Vec<_>>();
+	let matches_root = [StateVersion::V0, StateVersion::V1].into_iter().any(|state_version| {
+		<<Block::Header as HeaderT>::Hashing as HashT>::ordered_trie_root(
+			extrinsics.clone(),
+			state_version,
+		) == *block.header().extrinsics_root()
+	});
+	if !matches_root {
+		return Err("Response body does not match the extrinsics root")
+	}
+
+	Ok(Some(block))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cumulus_test_service::runtime::{Block, Header};
+	use sp_runtime::traits::BlakeTwo256;
+
+	fn header(extrinsics_root: <Header as HeaderT>::Hash) -> Header {
+		Header::new(1, extrinsics_root, Default::default(), Default::default(), Default::default())
+	}
+
+	#[test]
+	fn responses_are_checked_against_the_requested_hash() {
+		let header = header(BlakeTwo256::ordered_trie_root(Vec::new(), StateVersion::V1));
+		let hash = header.hash();
+		let response = Some(Block::new(header, Vec::new())).encode();
+
+		assert_eq!(
+			decode_response::<Block>(hash, &response).unwrap().map(|block| block.hash()),
+			Some(hash),
+		);
+		assert!(decode_response::<Block>(Default::default(), &response).is_err());
+		assert!(decode_response::<Block>(hash, &response[1..]).is_err());
+		assert_eq!(decode_response::<Block>(hash, &None::<Block>.encode()), Ok(None));
+	}
+
+	#[test]
+	fn responses_are_checked_against_the_extrinsics_root() {
+		// the body does not match the extrinsics root committed to by the header
+		let header = header(Default::default());
+		let hash = header.hash();
+		let response = Some(Block::new(header, Vec::new())).encode();
+
+		assert_eq!(
+			decode_response::<Block>(hash, &response).map(|block| block.map(|block| block.hash())),
+			Err("Response body does not match the extrinsics root"),
+		);
+	}
+}
@@ -20,6 +20,9 @@
 //! Provides a custom block announcement implementation for parachains
 //! that use the relay chain provided consensus. See [`RequireSecondedInBlockAnnounce`]
 //! and [`WaitToAnnounce`] for more information about this implementation.
+//!
+//! It also provides a request-response protocol to fetch parachain blocks directly from other
+//! parachain nodes, see [`BlockFetchRequestHandler`] and [`PeerBlockFetcher`].
 
 use sp_api::RuntimeApiInfo;
 use sp_consensus::block_validation::{
@@ -44,6 +47,9 @@ use std::{fmt, marker::PhantomData, pin::Pin, sync::Arc};
 #[cfg(test)]
 mod tests;
 
+mod block_fetch;
+pub use block_fetch::{block_fetch_protocol_name, BlockFetchRequestHandler, PeerBlockFetcher};
+
 const LOG_TARGET: &str = "sync::cumulus";
 
 type BoxedError = Box<dyn std::error::Error + Send>;
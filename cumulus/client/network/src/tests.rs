@@ -352,6 +352,10 @@ impl RelayChainInterface for DummyRelayChainInterface {
 	async fn scheduling_lookahead(&self, _: PHash) -> RelayChainResult<u32> {
 		unimplemented!("Not needed for test")
 	}
+
+	async fn submit_extrinsic(&self, _: Vec<u8>) -> RelayChainResult<()> {
+		unimplemented!("Not needed for test")
+	}
 }
 
 fn make_validator_and_api() -> (
@@ -0,0 +1,68 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
+
+use sp_runtime::traits::Block as BlockT;
+
+use futures::{stream::FuturesUnordered, Future, FutureExt, StreamExt};
+
+use std::pin::Pin;
+
+use crate::BlockFetcher;
+
+/// The active block fetches.
+///
+/// This fetches blocks from parachain peers and tracks the fetches in progress.
+pub(crate) struct ActiveBlockFetch<Block: BlockT> {
+	/// The fetches that are currently being executed.
+	fetches: FuturesUnordered<Pin<Box<dyn Future<Output = (Block::Hash, Option<Block>)> + Send>>>,
+	/// `None` if fetching blocks from peers is disabled.
+	block_fetcher: Option<Box<dyn BlockFetcher<Block>>>,
+}
+
+impl<Block: BlockT> ActiveBlockFetch<Block> {
+	pub fn new(block_fetcher: Option<Box<dyn BlockFetcher<Block>>>) -> Self {
+		Self { fetches: Default::default(), block_fetcher }
+	}
+
+	/// Fetch the block of the given `candidate` from the parachain peers.
+	///
+	/// Returns `false` if fetching blocks from peers is disabled.
+	pub fn fetch_block(
+		&mut self,
+		block_hash: Block::Hash,
+		candidate: &crate::Candidate<Block>,
+	) -> bool {
+		let Some(block_fetcher) = &self.block_fetcher else { return false };
+
+		let fetch = block_fetcher.fetch_block(block_hash, candidate.block_number);
+		self.fetches.push(fetch.map(move |block| (block_hash, block)).boxed());
+		true
+	}
+
+	/// Waits for the next fetch.
+	///
+	/// If the returned block is `None`, it means that no peer provided it.
+	pub async fn wait_for_block(&mut self) -> (Block::Hash, Option<Block>) {
+		loop {
+			if let Some(res) = self.fetches.next().await {
+				return res
+			} else {
+				futures::pending!()
+			}
+		}
+	}
+}
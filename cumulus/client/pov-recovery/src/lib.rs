@@ -44,6 +44,10 @@
 //! 4b. Since we are trying to recover pending candidates, availability is not guaranteed. If the
 //! block     PoV is not yet available, we retry.
 //!
+//! 4c. If a [`BlockFetcher`] is configured, a failed recovery is first followed by fetching the
+//! block directly from the parachain peers. The fetched block is only imported if its header
+//! matches the head data hash of the candidate.
+//!
 //! If we need to recover multiple PoV blocks (which should hopefully not happen in real life), we
 //! make sure that the blocks are imported in the correct order.
 
@@ -51,7 +55,9 @@ use sc_client_api::{BlockBackend, BlockchainEvents, UsageProvider};
 use sc_consensus::import_queue::{ImportQueueService, IncomingBlock};
 use sp_api::RuntimeApiInfo;
 use sp_consensus::{BlockOrigin, BlockStatus, SyncOracle};
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
+use sp_runtime::traits::{
+	BlakeTwo256, Block as BlockT, Hash as HashT, Header as HeaderT, NumberFor,
+};
 
 use polkadot_node_primitives::{PoV, POV_BOMB_LIMIT};
 use polkadot_node_subsystem::messages::{AvailabilityRecoveryMessage, RuntimeApiRequest};
@@ -87,6 +93,9 @@ mod tests;
 mod active_candidate_recovery;
 use active_candidate_recovery::ActiveCandidateRecovery;
 
+mod active_block_fetch;
+use active_block_fetch::ActiveBlockFetch;
+
 const LOG_TARGET: &str = "cumulus-pov-recovery";
 
 /// Test-friendly wrapper trait for the overseer handle.
@@ -111,6 +120,28 @@ impl RecoveryHandle for OverseerHandle {
 	}
 }
 
+/// Fetches parachain blocks directly from other parachain nodes.
+///
+/// Used as a fallback when a block could not be recovered from the relay chain.
+pub trait BlockFetcher<Block: BlockT>: Send {
+	/// Fetch the block with the given `hash` and `number`, `None` if no peer provided it.
+	fn fetch_block(
+		&self,
+		hash: Block::Hash,
+		number: NumberFor<Block>,
+	) -> Pin<Box<dyn Future<Output = Option<Block>> + Send>>;
+}
+
+impl<Block: BlockT> BlockFetcher<Block> for cumulus_client_network::PeerBlockFetcher<Block> {
+	fn fetch_block(
+		&self,
+		hash: Block::Hash,
+		number: NumberFor<Block>,
+	) -> Pin<Box<dyn Future<Output = Option<Block>> + Send>> {
+		self.fetch(hash, number).boxed()
+	}
+}
+
 /// Type of recovery to trigger.
 #[derive(Debug, PartialEq)]
 pub enum RecoveryKind {
@@ -226,6 +257,8 @@ pub struct PoVRecovery<Block: BlockT, PC, RC> {
 	/// available anymore in this map, it means that it was already imported.
 	candidate_recovery_queue: RecoveryQueue<Block>,
 	active_candidate_recovery: ActiveCandidateRecovery<Block>,
+	/// Blocks fetched from the parachain peers after their recovery failed.
+	active_block_fetch: ActiveBlockFetch<Block>,
 	/// Blocks that wait that the parent is imported.
 	///
 	/// Uses parent -> blocks mapping.
@@ -261,6 +294,7 @@ where
 			candidates: HashMap::new(),
 			candidate_recovery_queue: RecoveryQueue::new(recovery_delay_range),
 			active_candidate_recovery: ActiveCandidateRecovery::new(recovery_handle),
+			active_block_fetch: ActiveBlockFetch::new(None),
 			waiting_for_parent: HashMap::new(),
 			parachain_client,
 			parachain_import_queue,
@@ -272,6 +306,13 @@ where
 		}
 	}
 
+	/// Fetch the blocks that could not be recovered from the relay chain from the parachain peers,
+	/// before retrying their recovery.
+	pub fn with_block_fetcher(mut self, block_fetcher: Box<dyn BlockFetcher<Block>>) -> Self {
+		self.active_block_fetch = ActiveBlockFetch::new(Some(block_fetcher));
+		self
+	}
+
 	/// Handle a new pending candidate.
 	fn handle_pending_candidate(
 		&mut self,
@@ -361,21 +402,21 @@ where
 				self.candidates_in_retry.remove(&block_hash);
 				pov
 			},
-			None =>
-				if self.candidates_in_retry.insert(block_hash) {
-					tracing::debug!(target: LOG_TARGET, ?block_hash, "Recovery failed, retrying.");
-					self.candidate_recovery_queue.push_recovery(block_hash);
-					return
-				} else {
-					tracing::warn!(
+			None => {
+				let fetching = self.candidates.get(&block_hash).is_some_and(|candidate| {
+					self.active_block_fetch.fetch_block(block_hash, candidate)
+				});
+				if fetching {
+					tracing::debug!(
 						target: LOG_TARGET,
 						?block_hash,
-						"Unable to recover block after retry.",
+						"Recovery failed, fetching block from peers.",
 					);
-					self.candidates_in_retry.remove(&block_hash);
-					self.reset_candidate(block_hash);
-					return
-				},
+				} else {
+					self.handle_recovery_failed(block_hash);
+				}
+				return
+			},
 		};
 
 		let raw_block_data =
@@ -403,8 +444,47 @@ where
 			},
 		};
 
-		let block = block_data.into_block();
+		self.handle_recovered_block(block_hash, block_data.into_block());
+	}
+
+	/// Handle a block fetched from the parachain peers.
+	fn handle_block_fetched(&mut self, block_hash: Block::Hash, block: Option<Block>) {
+		let Some(candidate) = self.candidates.get(&block_hash) else { return };
+
+		match block {
+			Some(block)
+				if BlakeTwo256::hash_of(block.header()) ==
+					candidate.receipt.descriptor.para_head() =>
+			{
+				self.candidates_in_retry.remove(&block_hash);
+				self.handle_recovered_block(block_hash, block);
+			},
+			Some(_) => {
+				tracing::warn!(
+					target: LOG_TARGET,
+					?block_hash,
+					"Fetched block does not match the candidate head data.",
+				);
+				self.handle_recovery_failed(block_hash);
+			},
+			None => self.handle_recovery_failed(block_hash),
+		}
+	}
+
+	/// Handle a block that could neither be recovered nor fetched: retry once, then give up.
+	fn handle_recovery_failed(&mut self, block_hash: Block::Hash) {
+		if self.candidates_in_retry.insert(block_hash) {
+			tracing::debug!(target: LOG_TARGET, ?block_hash, "Recovery failed, retrying.");
+			self.candidate_recovery_queue.push_recovery(block_hash);
+		} else {
+			tracing::warn!(target: LOG_TARGET, ?block_hash, "Unable to recover block after retry.");
+			self.candidates_in_retry.remove(&block_hash);
+			self.reset_candidate(block_hash);
+		}
+	}
 
+	/// Import the recovered `block` once its parent is known.
+	fn handle_recovered_block(&mut self, block_hash: Block::Hash, block: Block) {
 		let parent = *block.header().parent_hash();
 
 		match self.parachain_client.block_status(parent) {
@@ -616,6 +696,9 @@ where
 				{
 					self.handle_candidate_recovered(block_hash, pov.as_deref()).await;
 				},
+				(block_hash, block) = self.active_block_fetch.wait_for_block().fuse() => {
+					self.handle_block_fetched(block_hash, block);
+				},
 			}
 		}
 	}
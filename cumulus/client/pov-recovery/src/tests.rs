@@ -76,6 +76,19 @@ impl RecoveryHandle for AvailabilityRecoverySubsystemHandle {
 	}
 }
 
+/// Block fetcher serving the blocks known to the parachain peers.
+struct PeerBlocks(HashMap<PHash, Block>);
+
+impl BlockFetcher<Block> for PeerBlocks {
+	fn fetch_block(
+		&self,
+		hash: PHash,
+		_number: u32,
+	) -> Pin<Box<dyn Future<Output = Option<Block>> + Send>> {
+		futures::future::ready(self.0.get(&hash).cloned()).boxed()
+	}
+}
+
 struct ParachainClientInner<Block: BlockT> {
 	import_notifications_rx: Option<TracingUnboundedReceiver<BlockImportNotification<Block>>>,
 	finality_notifications_rx: Option<TracingUnboundedReceiver<FinalityNotification<Block>>>,
@@ -508,6 +521,10 @@ impl RelayChainInterface for Relaychain {
 	async fn scheduling_lookahead(&self, _: PHash) -> RelayChainResult<u32> {
 		unimplemented!("Not needed for test")
 	}
+
+	async fn submit_extrinsic(&self, _: Vec<u8>) -> RelayChainResult<()> {
+		unimplemented!("Not needed for test")
+	}
 }
 
 fn make_candidate_chain(candidate_number_range: Range<u32>) -> Vec<CommittedCandidateReceipt> {
@@ -535,7 +552,7 @@ fn make_candidate_chain(candidate_number_range: Range<u32>) -> Vec<CommittedCand
 				pov_hash: PHash::zero(),
 				erasure_root: PHash::zero(),
 				signature: collator.sign(&[0u8; 132]).into(),
-				para_head: PHash::zero(),
+				para_head: latest_parent_hash,
 				validation_code_hash: PHash::zero().into(),
 			}
 			.into(),
@@ -1433,3 +1450,101 @@ async fn chained_recovery_child_succeeds_before_parent() {
 	// No more import requests received
 	assert_matches!(import_requests_rx.next().timeout(Duration::from_millis(100)).await, None);
 }
+
+#[rstest]
+#[case(true)]
+#[case(false)]
+#[tokio::test]
+async fn failed_recovery_falls_back_to_peers(#[case] peer_block_matches_candidate: bool) {
+	sp_tracing::init_for_tests();
+
+	let (recovery_subsystem_tx, mut recovery_subsystem_rx) =
+		AvailabilityRecoverySubsystemHandle::new();
+	let recovery_delay_range =
+		RecoveryDelayRange { min: Duration::from_millis(0), max: Duration::from_millis(10) };
+	let (_explicit_recovery_chan_tx, explicit_recovery_chan_rx) = mpsc::channel(10);
+	let candidates = make_candidate_chain(1..2);
+	let header = Header::decode(&mut &candidates[0].commitments.head_data.0[..]).unwrap();
+	let candidate_hash = candidates[0].hash();
+
+	// A peer answering with a block that is not the one of the candidate is not trusted.
+	let mut peer_header = header.clone();
+	if !peer_block_matches_candidate {
+		peer_header.state_root = PHash::repeat_byte(1);
+	}
+	let peer_blocks =
+		PeerBlocks([(header.hash(), Block::new(peer_header, vec![]))].into_iter().collect());
+
+	let relay_chain_client = Relaychain::new(vec![(
+		PHeader {
+			parent_hash: PHash::from_low_u64_be(0),
+			number: 1,
+			state_root: PHash::random(),
+			extrinsics_root: PHash::random(),
+			digest: Default::default(),
+		},
+		candidates,
+	)]);
+	let mut known_blocks = HashMap::new();
+	known_blocks.insert(GENESIS_HASH, BlockStatus::InChainWithState);
+	let (parachain_client, _import_notifications_tx, _finality_notifications_tx) =
+		ParachainClient::new(vec![dummy_usage_info(0)], Arc::new(Mutex::new(known_blocks)));
+	let (parachain_import_queue, mut import_requests_rx) = ParachainImportQueue::new();
+
+	let pov_recovery = PoVRecovery::<Block, _, _>::new(
+		Box::new(recovery_subsystem_tx),
+		recovery_delay_range,
+		Arc::new(parachain_client),
+		Box::new(parachain_import_queue),
+		relay_chain_client,
+		ParaId::new(1000),
+		explicit_recovery_chan_rx,
+		Arc::new(DummySyncOracle::default()),
+	)
+	.with_block_fetcher(Box::new(peer_blocks));
+
+	task::spawn(pov_recovery.run());
+
+	// Availability recovery fails.
+	assert_matches!(
+		recovery_subsystem_rx.next().await,
+		Some(AvailabilityRecoveryMessage::RecoverAvailableData(
+			receipt,
+			session_index,
+			None,
+			None,
+			response_tx
+		)) => {
+			assert_eq!(receipt.hash(), candidate_hash);
+			assert_eq!(session_index, TEST_SESSION_INDEX);
+			response_tx.send(
+				Err(RecoveryError::Unavailable)
+			).unwrap()
+		}
+	);
+
+	if peer_block_matches_candidate {
+		// The block fetched from the peers is imported.
+		assert_matches!(import_requests_rx.next().await, Some(incoming_blocks) => {
+			assert_eq!(incoming_blocks.len(), 1);
+			assert_eq!(incoming_blocks[0].header, Some(header));
+		});
+
+		// No more recovery messages received.
+		assert_matches!(
+			recovery_subsystem_rx.next().timeout(Duration::from_millis(100)).await,
+			None
+		);
+	} else {
+		// The fetched block is rejected and the recovery is retried.
+		assert_matches!(
+			recovery_subsystem_rx.next().await,
+			Some(AvailabilityRecoveryMessage::RecoverAvailableData(receipt, _, None, None, _)) => {
+				assert_eq!(receipt.hash(), candidate_hash);
+			}
+		);
+	}
+
+	// No more import requests received.
+	assert_matches!(import_requests_rx.next().timeout(Duration::from_millis(100)).await, None);
+}
@@ -43,6 +43,7 @@ use sc_client_api::{
 	StorageProof,
 };
 use sc_telemetry::TelemetryWorkerHandle;
+use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::{CallApiAt, CallApiAtParams, CallContext, ProvideRuntimeApi};
 use sp_consensus::SyncOracle;
 use sp_core::Pair;
@@ -59,6 +60,7 @@ pub struct RelayChainInProcessInterface {
 	backend: Arc<FullBackend>,
 	sync_oracle: Arc<dyn SyncOracle + Send + Sync>,
 	overseer_handle: Handle,
+	transaction_pool: OffchainTransactionPoolFactory<PBlock>,
 }
 
 impl RelayChainInProcessInterface {
@@ -68,8 +70,9 @@ impl RelayChainInProcessInterface {
 		backend: Arc<FullBackend>,
 		sync_oracle: Arc<dyn SyncOracle + Send + Sync>,
 		overseer_handle: Handle,
+		transaction_pool: OffchainTransactionPoolFactory<PBlock>,
 	) -> Self {
-		Self { full_client, backend, sync_oracle, overseer_handle }
+		Self { full_client, backend, sync_oracle, overseer_handle, transaction_pool }
 	}
 }
 
@@ -321,6 +324,18 @@ impl RelayChainInterface for RelayChainInProcessInterface {
 	async fn scheduling_lookahead(&self, hash: PHash) -> RelayChainResult<u32> {
 		Ok(self.full_client.runtime_api().scheduling_lookahead(hash)?)
 	}
+
+	async fn submit_extrinsic(&self, extrinsic: Vec<u8>) -> RelayChainResult<()> {
+		let best_hash = self.backend.blockchain().info().best_hash;
+		self.transaction_pool
+			.offchain_transaction_pool(best_hash)
+			.submit_transaction(extrinsic)
+			.map_err(|_| {
+				RelayChainError::GenericError(
+					"Relay chain transaction pool rejected the extrinsic".to_string(),
+				)
+			})
+	}
 }
 
 pub enum BlockCheckStatus {
@@ -386,6 +401,9 @@ fn build_polkadot_full_node(
 			prepare_workers_soft_max_num: None,
 			enable_approval_voting_parallel: false,
 			keep_finalized_for: None,
+			keep_unavailable_for: None,
+			availability_archive_path: None,
+			overseer_record_path: None,
 		},
 	)?;
 
@@ -420,6 +438,7 @@ pub fn build_inprocess_relay_chain(
 		full_node.overseer_handle.clone().ok_or(RelayChainError::GenericError(
 			"Overseer not running in full node.".to_string(),
 		))?,
+		OffchainTransactionPoolFactory::new(full_node.transaction_pool),
 	));
 
 	task_manager.add_child(full_node.task_manager);
@@ -437,6 +456,7 @@ mod tests {
 		DefaultTestClientBuilderExt, InitPolkadotBlockBuilder, TestClientBuilder,
 		TestClientBuilderExt,
 	};
+	use sc_transaction_pool_api::{error::Error as PoolError, LocalTransactionPool};
 	use sp_consensus::{BlockOrigin, SyncOracle};
 	use sp_runtime::traits::Block as BlockT;
 	use std::sync::Arc;
@@ -455,6 +475,22 @@ mod tests {
 		}
 	}
 
+	struct DummyTransactionPool {}
+
+	impl LocalTransactionPool for DummyTransactionPool {
+		type Block = PBlock;
+		type Hash = PHash;
+		type Error = PoolError;
+
+		fn submit_local(
+			&self,
+			_: PHash,
+			_: <PBlock as BlockT>::Extrinsic,
+		) -> Result<PHash, PoolError> {
+			unimplemented!("Not needed for test")
+		}
+	}
+
 	fn build_client_backend_and_block() -> (Arc<Client>, PBlock, RelayChainInProcessInterface) {
 		let builder = TestClientBuilder::new();
 		let backend = builder.backend();
@@ -469,7 +505,13 @@ mod tests {
 		(
 			client.clone(),
 			block,
-			RelayChainInProcessInterface::new(client, backend, dummy_network, mock_handle),
+			RelayChainInProcessInterface::new(
+				client,
+				backend,
+				dummy_network,
+				mock_handle,
+				OffchainTransactionPoolFactory::new(DummyTransactionPool {}),
+			),
 		)
 	}
 
@@ -248,6 +248,9 @@ pub trait RelayChainInterface: Send + Sync {
 
 	/// Fetch the scheduling lookahead value.
 	async fn scheduling_lookahead(&self, relay_parent: PHash) -> RelayChainResult<u32>;
+
+	/// Submit a SCALE-encoded extrinsic to the transaction pool of the relay chain node.
+	async fn submit_extrinsic(&self, extrinsic: Vec<u8>) -> RelayChainResult<()>;
 }
 
 #[async_trait]
@@ -406,6 +409,10 @@ where
 	async fn scheduling_lookahead(&self, relay_parent: PHash) -> RelayChainResult<u32> {
 		(**self).scheduling_lookahead(relay_parent).await
 	}
+
+	async fn submit_extrinsic(&self, extrinsic: Vec<u8>) -> RelayChainResult<()> {
+		(**self).submit_extrinsic(extrinsic).await
+	}
 }
 
 /// Helper function to call an arbitrary runtime API using a `RelayChainInterface` client.
@@ -142,6 +142,24 @@ pub async fn build_minimal_relay_chain_node_with_rpc(
 	build_interface(relay_chain_config, task_manager, client).await
 }
 
+/// Same as [`build_minimal_relay_chain_node_with_rpc`], but stays connected to all given RPC
+/// endpoints and cross-checks them.
+pub async fn build_minimal_relay_chain_node_with_cross_checked_rpc(
+	relay_chain_config: Configuration,
+	parachain_prometheus_registry: Option<&Registry>,
+	task_manager: &mut TaskManager,
+	relay_chain_url: Vec<Url>,
+) -> RelayChainResult<(Arc<(dyn RelayChainInterface + 'static)>, Option<CollatorPair>)> {
+	let client = cumulus_relay_chain_rpc_interface::create_client_and_start_cross_checking_worker(
+		relay_chain_url,
+		task_manager,
+		parachain_prometheus_registry,
+	)
+	.await?;
+
+	build_interface(relay_chain_config, task_manager, client).await
+}
+
 pub async fn build_minimal_relay_chain_node_light_client(
 	polkadot_config: Configuration,
 	task_manager: &mut TaskManager,
@@ -0,0 +1,839 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
+
+//! Worker that stays connected to all relay chain RPC endpoints and cross-checks them.
+//!
+//! Unlike the
+//! [`ReconnectingWebsocketWorker`](crate::reconnecting_ws_client::ReconnectingWebsocketWorker),
+//! which only talks to a single endpoint and moves on to the next one once the connection dies,
+//! this worker follows the best and finalized heads of every endpoint. An endpoint is demoted
+//! when:
+//! - its best block lags more than [`MAX_BEST_BLOCK_LAG`] blocks behind the best block of another
+//!   endpoint which at least one more endpoint knows, until it catches up again, or
+//! - it reports a finalized block the majority of the endpoints disagrees with, or serves a
+//!   response which fails verification against the relay chain headers, for good.
+//!
+//! Telling which endpoint diverges on finality takes a majority, so at least three endpoints are
+//! needed.
+//!
+//! Requests and notifications are served by the first endpoint, in the order they were given, that
+//! is connected and not demoted. A new best block of that endpoint is only handed to the listeners
+//! once another endpoint reported the same block, unless no other endpoint reports best blocks.
+
+use cumulus_primitives_core::relay_chain::{
+	BlockNumber as RelayNumber, Hash as RelayHash, Header as RelayHeader,
+};
+use futures::{
+	channel::mpsc::Sender,
+	future::BoxFuture,
+	stream::{self, BoxStream, FuturesUnordered, SelectAll},
+	FutureExt, StreamExt,
+};
+use jsonrpsee::{
+	core::client::{Client as JsonRpcClient, Subscription},
+	ws_client::WsClientBuilder,
+};
+use prometheus::Registry;
+use schnellru::{ByLength, LruMap};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::mpsc::{
+	channel as tokio_channel, Receiver as TokioReceiver, Sender as TokioSender,
+};
+use url::Url;
+
+use crate::{
+	metrics::CrossCheckMetrics,
+	reconnecting_ws_client::{
+		create_request, subscribe_to_heads, url_to_string_with_port, RelayChainSubscriptions,
+		DEFAULT_EXTERNAL_RPC_CONN_RETRIES,
+	},
+	rpc_client::{distribute_header, RpcDispatcherMessage},
+};
+
+const LOG_TARGET: &str = "cross-checking-websocket-client";
+
+/// How many blocks the best block of an endpoint may lag behind the best block reported by the
+/// other endpoints.
+const MAX_BEST_BLOCK_LAG: RelayNumber = 2;
+
+/// The number of endpoints needed for a majority to tell which one diverges.
+const MIN_ENDPOINTS: usize = 3;
+
+/// How often to try to reconnect to endpoints whose connection died.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// For how many finalized block numbers the hashes reported by the endpoints are kept around.
+const FINALIZED_HISTORY: u32 = 64;
+
+/// For how many imported or best blocks the endpoints which reported them are kept around.
+const KNOWN_BLOCKS_HISTORY: u32 = 256;
+
+/// Why an endpoint doesn't serve requests anymore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Demotion {
+	/// The best block of the endpoint lags behind the other endpoints.
+	Lagging,
+	/// The endpoint reported a finalized block the majority of the endpoints disagrees with, or
+	/// served a response which could not be verified.
+	Diverged,
+}
+
+impl Demotion {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Demotion::Lagging => "lagging",
+			Demotion::Diverged => "diverged",
+		}
+	}
+}
+
+enum HeadKind {
+	Imported,
+	Best,
+	Finalized,
+}
+
+/// A head notification of one of the endpoints.
+struct HeadEvent {
+	endpoint: usize,
+	/// The connection the notification was received on, to ignore leftovers of dead connections.
+	connection: u64,
+	kind: HeadKind,
+	/// `None` once the subscription ended.
+	header: Option<RelayHeader>,
+}
+
+type ConnectionResult = (usize, Result<(Arc<JsonRpcClient>, RelayChainSubscriptions), String>);
+
+struct Endpoint {
+	url: String,
+	/// Label of the endpoint in the metrics, its position in the list of endpoints.
+	label: String,
+	client: Option<Arc<JsonRpcClient>>,
+	connection: u64,
+	connecting: bool,
+	best: Option<(RelayNumber, RelayHash)>,
+	demotion: Option<Demotion>,
+}
+
+/// Worker that should be used in combination with [`RelayChainRpcClient`] to cross-check several
+/// RPC endpoints.
+///
+/// Must be polled to distribute header notifications to listeners.
+///
+/// [`RelayChainRpcClient`]: crate::RelayChainRpcClient
+pub struct CrossCheckingWebsocketWorker {
+	endpoints: Vec<Endpoint>,
+	/// The endpoint serving requests and notifications.
+	active: Option<usize>,
+	/// The endpoints which reported a block as imported or best.
+	known_blocks: LruMap<RelayHash, Vec<usize>>,
+	/// The latest best block of the active endpoint, until another endpoint reported it too.
+	pending_best: Option<RelayHeader>,
+	/// Communication channel with the RPC client
+	client_receiver: TokioReceiver<RpcDispatcherMessage>,
+
+	/// Senders to distribute incoming header notifications to
+	imported_header_listeners: Vec<Sender<RelayHeader>>,
+	finalized_header_listeners: Vec<Sender<RelayHeader>>,
+	best_header_listeners: Vec<Sender<RelayHeader>>,
+
+	metrics: Option<CrossCheckMetrics>,
+}
+
+impl CrossCheckingWebsocketWorker {
+	/// Create new worker. Returns the worker and a channel to register new listeners.
+	pub fn new(
+		urls: Vec<Url>,
+		prometheus_registry: Option<&Registry>,
+	) -> (CrossCheckingWebsocketWorker, TokioSender<RpcDispatcherMessage>) {
+		let endpoints = urls
+			.into_iter()
+			.filter_map(url_to_string_with_port)
+			.enumerate()
+			.map(|(index, url)| Endpoint {
+				url,
+				label: index.to_string(),
+				client: None,
+				connection: 0,
+				connecting: false,
+				best: None,
+				demotion: None,
+			})
+			.collect::<Vec<_>>();
+
+		if endpoints.len() < MIN_ENDPOINTS {
+			tracing::warn!(
+				target: LOG_TARGET,
+				endpoints = endpoints.len(),
+				"Cross-checking needs at least {MIN_ENDPOINTS} relay chain RPC endpoints to tell which \
+				 one diverges on finality, only lagging endpoints and unverified responses are detected."
+			);
+		}
+
+		let metrics = prometheus_registry.and_then(|registry| {
+			CrossCheckMetrics::register(registry)
+				.map_err(|err| {
+					tracing::warn!(
+						target: LOG_TARGET,
+						error = %err,
+						"Unable to register the cross-checking metrics, continuing without them."
+					);
+				})
+				.ok()
+		});
+
+		let (tx, rx) = tokio_channel(100);
+		let worker = CrossCheckingWebsocketWorker {
+			endpoints,
+			active: None,
+			known_blocks: LruMap::new(ByLength::new(KNOWN_BLOCKS_HISTORY)),
+			pending_best: None,
+			client_receiver: rx,
+			imported_header_listeners: Vec::new(),
+			finalized_header_listeners: Vec::new(),
+			best_header_listeners: Vec::new(),
+			metrics,
+		};
+		(worker, tx)
+	}
+
+	/// Run this worker to drive notification streams.
+	/// The worker does the following:
+	/// - Keep a connection to every endpoint, reconnecting to the ones whose connection died.
+	/// - Listen for [`RpcDispatcherMessage`], perform requests on the active endpoint and register
+	///   new listeners for the notification streams.
+	/// - Compare the best and finalized heads reported by the endpoints, demote the ones diverging
+	///   from the others and distribute the notifications of the active endpoint to the listeners.
+	///
+	/// The worker shuts down if it isn't able to connect to any of the endpoints for
+	/// `DEFAULT_EXTERNAL_RPC_CONN_RETRIES` reconnection rounds in a row.
+	pub async fn run(mut self) {
+		if self.endpoints.is_empty() {
+			tracing::error!(target: LOG_TARGET, "No valid RPC url found. Stopping RPC worker.");
+			return
+		}
+
+		let mut pending_requests = FuturesUnordered::new();
+		let mut queued_requests = Vec::new();
+		let mut pending_connections: FuturesUnordered<BoxFuture<'static, ConnectionResult>> =
+			FuturesUnordered::new();
+		let mut head_events: SelectAll<BoxStream<'static, HeadEvent>> = SelectAll::new();
+
+		let mut imported_blocks_cache = LruMap::new(ByLength::new(40));
+		let mut finalized_hashes = LruMap::new(ByLength::new(FINALIZED_HISTORY));
+		let mut last_seen_finalized_num: RelayNumber = 0;
+		let mut failed_connection_rounds = 0;
+		let mut reconnect_interval = tokio::time::interval(RECONNECT_INTERVAL);
+
+		loop {
+			tokio::select! {
+				_ = reconnect_interval.tick() => {
+					if self.endpoints.iter().any(|endpoint| endpoint.client.is_some()) {
+						failed_connection_rounds = 0;
+					} else {
+						failed_connection_rounds += 1;
+						if failed_connection_rounds > DEFAULT_EXTERNAL_RPC_CONN_RETRIES {
+							tracing::error!(
+								target: LOG_TARGET,
+								"Unable to connect to any external relaychain node, stopping worker."
+							);
+							return
+						}
+					}
+
+					for (index, endpoint) in self.endpoints.iter_mut().enumerate() {
+						if endpoint.client.is_none() && !endpoint.connecting {
+							endpoint.connecting = true;
+							let url = endpoint.url.clone();
+							pending_connections.push(async move { (index, connect(url).await) }.boxed());
+						}
+					}
+				},
+				Some((index, result)) = pending_connections.next(), if !pending_connections.is_empty() => {
+					let endpoint = &mut self.endpoints[index];
+					endpoint.connecting = false;
+					match result {
+						Ok((client, subscriptions)) => {
+							tracing::info!(target: LOG_TARGET, index, url = %endpoint.url, "Connected to external relaychain node.");
+							endpoint.connection += 1;
+							endpoint.client = Some(client);
+							head_events.extend(head_event_streams(index, endpoint.connection, subscriptions));
+							self.select_active();
+
+							for request in std::mem::take(&mut queued_requests) {
+								self.dispatch(request, &mut pending_requests, &mut queued_requests);
+							}
+						},
+						Err(err) => tracing::debug!(target: LOG_TARGET, url = %endpoint.url, %err, "Unable to connect."),
+					}
+				},
+				evt = self.client_receiver.recv() => match evt {
+					Some(RpcDispatcherMessage::RegisterBestHeadListener(tx)) => {
+						self.best_header_listeners.push(tx);
+					},
+					Some(RpcDispatcherMessage::RegisterImportListener(tx)) => {
+						self.imported_header_listeners.push(tx)
+					},
+					Some(RpcDispatcherMessage::RegisterFinalizationListener(tx)) => {
+						self.finalized_header_listeners.push(tx)
+					},
+					Some(request @ RpcDispatcherMessage::Request(..)) => {
+						self.dispatch(request, &mut pending_requests, &mut queued_requests);
+					},
+					Some(RpcDispatcherMessage::ReportUnverifiedResponse(index, mismatch)) => {
+						self.on_unverified_response(index, &mismatch);
+					},
+					None => {
+						tracing::error!(target: LOG_TARGET, "RPC client receiver closed. Stopping RPC Worker.");
+						return;
+					}
+				},
+				Some((index, result)) = pending_requests.next(), if !pending_requests.is_empty() => {
+					if let Err(request) = result {
+						self.disconnect(index);
+						self.dispatch(request, &mut pending_requests, &mut queued_requests);
+					}
+				},
+				Some(event) = head_events.next(), if !head_events.is_empty() => {
+					if self.endpoints[event.endpoint].connection != event.connection {
+						continue
+					}
+					let index = event.endpoint;
+					let Some(header) = event.header else {
+						tracing::warn!(target: LOG_TARGET, index, url = %self.endpoints[index].url, "Subscription closed.");
+						self.disconnect(index);
+						continue
+					};
+
+					match event.kind {
+						HeadKind::Imported => {
+							let hash = header.hash();
+							self.on_block_known(index, hash);
+							self.distribute_best_header();
+							if self.active != Some(index) || imported_blocks_cache.peek(&hash).is_some() {
+								continue
+							}
+							imported_blocks_cache.insert(hash, ());
+							distribute_header(header, &mut self.imported_header_listeners);
+						},
+						HeadKind::Best => self.on_best_header(index, header),
+						HeadKind::Finalized => {
+							if let Some(metrics) = &self.metrics {
+								metrics.on_finalized_block(&self.endpoints[index].label, header.number);
+							}
+							let number = header.number;
+							let reports = finalized_hashes
+								.get_or_insert(number, Vec::new)
+								.expect("LRU map with a non-zero limit always inserts; qed");
+							reports.retain(|(endpoint, _)| *endpoint != index);
+							reports.push((index, header.hash()));
+							let reports = reports.clone();
+							self.check_finalized_block(number, &reports);
+
+							if self.active == Some(index) && number > last_seen_finalized_num {
+								last_seen_finalized_num = number;
+								distribute_header(header, &mut self.finalized_header_listeners);
+							}
+						},
+					}
+				},
+			}
+		}
+	}
+
+	/// Send the request to the active endpoint, or queue it until an endpoint is available.
+	fn dispatch(
+		&self,
+		request: RpcDispatcherMessage,
+		pending_requests: &mut FuturesUnordered<
+			BoxFuture<'static, (usize, Result<(), RpcDispatcherMessage>)>,
+		>,
+		queued_requests: &mut Vec<RpcDispatcherMessage>,
+	) {
+		let active = self
+			.active
+			.and_then(|index| Some((index, self.endpoints[index].client.clone()?)));
+		match (active, request) {
+			(Some((index, client)), RpcDispatcherMessage::Request(method, params, sender)) =>
+				pending_requests.push(
+					create_request(client, Some(index), method, params, sender)
+						.map(move |result| (index, result))
+						.boxed(),
+				),
+			(None, request) => queued_requests.push(request),
+			(Some(_), _) => {},
+		}
+	}
+
+	fn disconnect(&mut self, index: usize) {
+		let endpoint = &mut self.endpoints[index];
+		if endpoint.client.take().is_none() {
+			return
+		}
+
+		tracing::warn!(
+			target: LOG_TARGET,
+			index,
+			url = %endpoint.url,
+			"Lost connection to external relaychain node."
+		);
+		endpoint.best = None;
+		if endpoint.demotion == Some(Demotion::Lagging) {
+			self.set_demotion(index, None);
+		}
+		self.select_active();
+	}
+
+	/// Record a new best block of the endpoint `index`, and hand it to the listeners once
+	/// confirmed if the endpoint is the active one.
+	fn on_best_header(&mut self, index: usize, header: RelayHeader) {
+		let hash = header.hash();
+		self.endpoints[index].best = Some((header.number, hash));
+		self.on_block_known(index, hash);
+		if let Some(metrics) = &self.metrics {
+			metrics.on_best_block(&self.endpoints[index].label, header.number);
+		}
+		self.check_best_block_lag();
+
+		if self.active == Some(index) {
+			self.pending_best = Some(header);
+		}
+		self.distribute_best_header();
+	}
+
+	/// Record that the endpoint `index` reported the block `hash` as imported or best.
+	fn on_block_known(&mut self, index: usize, hash: RelayHash) {
+		let endpoints = self
+			.known_blocks
+			.get_or_insert(hash, Vec::new)
+			.expect("LRU map with a non-zero limit always inserts; qed");
+		if !endpoints.contains(&index) {
+			endpoints.push(index);
+		}
+	}
+
+	/// The number of endpoints which reported the block `hash` and did not diverge.
+	fn known_by(&self, hash: &RelayHash) -> usize {
+		self.known_blocks.peek(hash).map_or(0, |endpoints| {
+			endpoints
+				.iter()
+				.filter(|index| self.endpoints[**index].demotion != Some(Demotion::Diverged))
+				.count()
+		})
+	}
+
+	/// Hand the pending best block of the active endpoint to the listeners once another endpoint
+	/// reported it too, so that a single endpoint can't make up best blocks. If no other endpoint
+	/// reports best blocks, there is nothing to check against and the block is handed over right
+	/// away.
+	fn distribute_best_header(&mut self) {
+		let Some(hash) = self.pending_best.as_ref().map(|header| header.hash()) else { return };
+		let witnesses = self
+			.endpoints
+			.iter()
+			.filter(|e| e.best.is_some() && e.demotion != Some(Demotion::Diverged))
+			.count();
+		if witnesses >= 2 && self.known_by(&hash) < 2 {
+			return
+		}
+
+		if let Some(header) = self.pending_best.take() {
+			distribute_header(header, &mut self.best_header_listeners);
+		}
+	}
+
+	/// Demote endpoints whose best block lags behind, and restore the ones which caught up.
+	///
+	/// Only best blocks reported by at least two endpoints are compared against, so that a single
+	/// endpoint can't demote the others by reporting a made up best block.
+	fn check_best_block_lag(&mut self) {
+		let Some(max_best) = self
+			.endpoints
+			.iter()
+			.filter_map(|e| e.best)
+			.filter(|(_, hash)| self.known_by(hash) >= 2)
+			.map(|(number, _)| number)
+			.max()
+		else {
+			return
+		};
+
+		for index in 0..self.endpoints.len() {
+			let endpoint = &self.endpoints[index];
+			let Some((best_number, _)) = endpoint.best else { continue };
+			let lagging = best_number + MAX_BEST_BLOCK_LAG < max_best;
+
+			match endpoint.demotion {
+				None if lagging => {
+					tracing::warn!(
+						target: LOG_TARGET,
+						index,
+						url = %endpoint.url,
+						best_number,
+						max_best,
+						"Relay chain RPC endpoint lags behind the others, demoting it."
+					);
+					self.set_demotion(index, Some(Demotion::Lagging));
+				},
+				Some(Demotion::Lagging) if !lagging => {
+					tracing::info!(
+						target: LOG_TARGET,
+						index,
+						url = %endpoint.url,
+						best_number,
+						"Relay chain RPC endpoint caught up with the others."
+					);
+					self.set_demotion(index, None);
+				},
+				_ => {},
+			}
+		}
+
+		self.select_active();
+	}
+
+	/// Demote the endpoints which disagree with the majority on the finalized block `number`.
+	fn check_finalized_block(&mut self, number: RelayNumber, reports: &[(usize, RelayHash)]) {
+		let mut votes = HashMap::<RelayHash, usize>::new();
+		for (_, hash) in reports {
+			*votes.entry(*hash).or_default() += 1;
+		}
+		if votes.len() < 2 {
+			return
+		}
+
+		let max_votes = votes.values().copied().max().unwrap_or_default();
+		let mut majority = votes.iter().filter(|(_, count)| **count == max_votes);
+		let (Some((majority_hash, _)), None) = (majority.next(), majority.next()) else {
+			tracing::error!(
+				target: LOG_TARGET,
+				number,
+				?reports,
+				"Relay chain RPC endpoints disagree on the finalized block, unable to tell which one is right."
+			);
+			return
+		};
+
+		for (index, hash) in reports {
+			if hash == majority_hash || self.endpoints[*index].demotion == Some(Demotion::Diverged)
+			{
+				continue
+			}
+
+			tracing::error!(
+				target: LOG_TARGET,
+				index,
+				url = %self.endpoints[*index].url,
+				number,
+				?hash,
+				?majority_hash,
+				"Relay chain RPC endpoint finalized a block the others disagree with, demoting it."
+			);
+			self.set_demotion(*index, Some(Demotion::Diverged));
+		}
+
+		self.select_active();
+	}
+
+	/// Demote the endpoint `index` for good, as a response it served could not be verified.
+	fn on_unverified_response(&mut self, index: usize, mismatch: &str) {
+		if index >= self.endpoints.len() {
+			return
+		}
+
+		tracing::error!(
+			target: LOG_TARGET,
+			index,
+			url = %self.endpoints[index].url,
+			%mismatch,
+			"Relay chain RPC endpoint served a response which could not be verified, demoting it."
+		);
+		self.set_demotion(index, Some(Demotion::Diverged));
+		self.select_active();
+	}
+
+	fn set_demotion(&mut self, index: usize, demotion: Option<Demotion>) {
+		let endpoint = &mut self.endpoints[index];
+		endpoint.demotion = demotion;
+
+		if let Some(metrics) = &self.metrics {
+			metrics.set_demoted(&endpoint.label, demotion.is_some());
+			if let Some(demotion) = demotion {
+				metrics.on_divergence(&endpoint.label, demotion.as_str());
+			}
+		}
+	}
+
+	/// Pick the first connected endpoint which isn't demoted. Lagging endpoints are used as a last
+	/// resort, diverged ones never.
+	fn select_active(&mut self) {
+		let connected = || self.endpoints.iter().enumerate().filter(|(_, e)| e.client.is_some());
+		let active = connected()
+			.find(|(_, endpoint)| endpoint.demotion.is_none())
+			.or_else(|| {
+				connected().find(|(_, endpoint)| endpoint.demotion == Some(Demotion::Lagging))
+			})
+			.map(|(index, _)| index);
+
+		if active == self.active {
+			return
+		}
+
+		match active {
+			Some(index) => tracing::info!(
+				target: LOG_TARGET,
+				index,
+				url = %self.endpoints[index].url,
+				"Switching to external relaychain node."
+			),
+			None => tracing::warn!(
+				target: LOG_TARGET,
+				"No usable external relaychain node, waiting for one to become available."
+			),
+		}
+
+		if let Some(metrics) = &self.metrics {
+			for (index, endpoint) in self.endpoints.iter().enumerate() {
+				metrics.set_active(&endpoint.label, active == Some(index));
+			}
+		}
+		self.active = active;
+	}
+}
+
+async fn connect(url: String) -> Result<(Arc<JsonRpcClient>, RelayChainSubscriptions), String> {
+	let client = WsClientBuilder::default().build(&url).await.map_err(|err| err.to_string())?;
+	let subscriptions = subscribe_to_heads(&client).await.map_err(|err| err.to_string())?;
+	Ok((Arc::new(client), subscriptions))
+}
+
+/// Turn the subscriptions of an endpoint into streams of [`HeadEvent`]s, each terminated by an
+/// event without header.
+fn head_event_streams(
+	endpoint: usize,
+	connection: u64,
+	subscriptions: RelayChainSubscriptions,
+) -> [BoxStream<'static, HeadEvent>; 3] {
+	let into_events = move |subscription: Subscription<RelayHeader>, kind: fn() -> HeadKind| {
+		subscription
+			.map(move |item| match item {
+				Ok(header) => Some(header),
+				Err(error) => {
+					tracing::error!(target: LOG_TARGET, endpoint, ?error, "Error in RPC subscription.");
+					None
+				},
+			})
+			.take_while(|header| futures::future::ready(header.is_some()))
+			.chain(stream::once(futures::future::ready(None)))
+			.map(move |header| HeadEvent { endpoint, connection, kind: kind(), header })
+			.boxed()
+	};
+
+	[
+		into_events(subscriptions.import_subscription, || HeadKind::Imported),
+		into_events(subscriptions.best_subscription, || HeadKind::Best),
+		into_events(subscriptions.finalized_subscription, || HeadKind::Finalized),
+	]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use jsonrpsee::{core::params::ArrayParams, server::ServerHandle, RpcModule};
+
+	fn worker(endpoints: usize) -> CrossCheckingWebsocketWorker {
+		let urls = (0..endpoints)
+			.map(|i| Url::parse(&format!("ws://node{i}:9944")).unwrap())
+			.collect();
+		CrossCheckingWebsocketWorker::new(urls, None).0
+	}
+
+	fn demotions(worker: &CrossCheckingWebsocketWorker) -> Vec<Option<Demotion>> {
+		worker.endpoints.iter().map(|e| e.demotion).collect()
+	}
+
+	fn header(number: RelayNumber) -> RelayHeader {
+		RelayHeader {
+			parent_hash: Default::default(),
+			number,
+			state_root: Default::default(),
+			extrinsics_root: Default::default(),
+			digest: Default::default(),
+		}
+	}
+
+	#[test]
+	fn lagging_endpoint_is_demoted_until_it_catches_up() {
+		let mut worker = worker(3);
+		worker.on_best_header(0, header(10));
+		worker.on_best_header(1, header(10));
+		worker.on_best_header(2, header(7));
+		assert_eq!(demotions(&worker), vec![None, None, Some(Demotion::Lagging)]);
+
+		worker.on_best_header(2, header(8));
+		assert_eq!(demotions(&worker), vec![None, None, None]);
+	}
+
+	#[test]
+	fn made_up_best_block_does_not_demote_the_others() {
+		let mut worker = worker(3);
+		worker.on_best_header(0, header(10));
+		worker.on_best_header(1, header(10));
+		worker.on_best_header(2, header(100));
+		assert_eq!(demotions(&worker), vec![None, None, None]);
+	}
+
+	#[test]
+	fn best_block_of_the_active_endpoint_is_distributed_once_confirmed() {
+		let mut worker = worker(3);
+		let (tx, mut rx) = futures::channel::mpsc::channel(10);
+		worker.best_header_listeners.push(tx);
+		worker.active = Some(0);
+
+		worker.on_best_header(1, header(9));
+		worker.on_best_header(0, header(10));
+		assert!(rx.try_next().is_err());
+
+		worker.on_best_header(1, header(10));
+		assert_eq!(rx.try_next().unwrap().map(|header| header.number), Some(10));
+	}
+
+	#[test]
+	fn minority_finalized_block_is_demoted_for_good() {
+		let mut worker = worker(3);
+		let (good, bad) = (RelayHash::repeat_byte(1), RelayHash::repeat_byte(2));
+		worker.check_finalized_block(5, &[(0, bad), (1, good), (2, good)]);
+		assert_eq!(demotions(&worker), vec![Some(Demotion::Diverged), None, None]);
+
+		worker.on_best_header(0, header(10));
+		worker.on_best_header(1, header(10));
+		assert_eq!(worker.endpoints[0].demotion, Some(Demotion::Diverged));
+	}
+
+	#[test]
+	fn no_endpoint_is_demoted_on_a_tie() {
+		let mut worker = worker(2);
+		worker.check_finalized_block(
+			5,
+			&[(0, RelayHash::repeat_byte(1)), (1, RelayHash::repeat_byte(2))],
+		);
+		assert_eq!(demotions(&worker), vec![None, None]);
+	}
+
+	#[test]
+	fn unverified_response_demotes_the_serving_endpoint_for_good() {
+		let mut worker = worker(3);
+		worker.active = Some(0);
+		worker.on_unverified_response(1, "header hashes to something else");
+		assert_eq!(demotions(&worker), vec![None, Some(Demotion::Diverged), None]);
+
+		worker.on_best_header(1, header(10));
+		assert_eq!(worker.endpoints[1].demotion, Some(Demotion::Diverged));
+	}
+
+	/// Start a server answering `test_name` with `name`, and keeping head subscriptions open
+	/// without sending any heads.
+	async fn start_server(port: u16, name: &'static str) -> ServerHandle {
+		let mut module = RpcModule::new(());
+		module.register_method("test_name", move |_, _, _| name.to_string()).unwrap();
+		for (subscribe, notification, unsubscribe) in [
+			("chain_subscribeAllHeads", "chain_allHead", "chain_unsubscribeAllHeads"),
+			("chain_subscribeNewHeads", "chain_newHead", "chain_unsubscribeNewHeads"),
+			(
+				"chain_subscribeFinalizedHeads",
+				"chain_finalizedHead",
+				"chain_unsubscribeFinalizedHeads",
+			),
+		] {
+			module
+				.register_subscription(
+					subscribe,
+					notification,
+					unsubscribe,
+					|_, pending, _, _| async move {
+						let _sink = pending.accept().await;
+						futures::future::pending::<()>().await;
+					},
+				)
+				.unwrap();
+		}
+
+		let server = jsonrpsee::server::Server::builder()
+			.build(format!("127.0.0.1:{port}"))
+			.await
+			.unwrap();
+		server.start(module)
+	}
+
+	/// Send a request through the worker, returning the name of the endpoint which served it.
+	async fn served_by(sender: &TokioSender<RpcDispatcherMessage>) -> Option<String> {
+		let (tx, rx) = futures::channel::oneshot::channel();
+		let request = RpcDispatcherMessage::Request("test_name".into(), ArrayParams::new(), tx);
+		sender.send(request).await.unwrap();
+		serde_json::from_value(rx.await.ok()?.0.ok()?).ok()
+	}
+
+	/// Send requests through the worker until one is served by the endpoint called `name`.
+	async fn wait_for_endpoint(
+		sender: &TokioSender<RpcDispatcherMessage>,
+		name: &str,
+		timeout: Duration,
+	) {
+		tokio::time::timeout(timeout, async {
+			while served_by(sender).await.as_deref() != Some(name) {
+				tokio::time::sleep(Duration::from_millis(100)).await;
+			}
+		})
+		.await
+		.unwrap_or_else(|_| panic!("Requests weren't served by the {name} endpoint in time"));
+	}
+
+	#[tokio::test]
+	async fn requests_fail_over_and_return_after_reconnecting() {
+		let ports =
+			[portpicker::pick_unused_port().unwrap(), portpicker::pick_unused_port().unwrap()];
+		let first = start_server(ports[0], "first").await;
+		let _second = start_server(ports[1], "second").await;
+
+		let urls = ports
+			.iter()
+			.map(|port| Url::parse(&format!("ws://127.0.0.1:{port}")).unwrap())
+			.collect();
+		let (worker, sender) = CrossCheckingWebsocketWorker::new(urls, None);
+		tokio::spawn(worker.run());
+
+		// Requests are served by the first endpoint, in the order they were given.
+		wait_for_endpoint(&sender, "first", Duration::from_secs(5)).await;
+
+		// Once its connection dies, the next endpoint takes over.
+		first.stop().unwrap();
+		first.stopped().await;
+		wait_for_endpoint(&sender, "second", Duration::from_secs(5)).await;
+
+		// The first endpoint is used again once the worker reconnected to it.
+		let _first = start_server(ports[0], "first").await;
+		wait_for_endpoint(&sender, "first", RECONNECT_INTERVAL * 2).await;
+
+		// Unless it serves a response which can't be verified.
+		sender
+			.send(RpcDispatcherMessage::ReportUnverifiedResponse(0, "test".into()))
+			.await
+			.unwrap();
+		wait_for_endpoint(&sender, "second", Duration::from_secs(5)).await;
+	}
+}
@@ -16,12 +16,13 @@
 // along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
 
 use async_trait::async_trait;
+use codec::Encode;
 use core::time::Duration;
 use cumulus_primitives_core::{
 	relay_chain::{
-		vstaging::CommittedCandidateReceiptV2 as CommittedCandidateReceipt, Hash as RelayHash,
-		Header as RelayHeader, InboundHrmpMessage, OccupiedCoreAssumption, SessionIndex,
-		ValidationCodeHash, ValidatorId,
+		vstaging::CommittedCandidateReceiptV2 as CommittedCandidateReceipt, well_known_keys,
+		Hash as RelayHash, Header as RelayHeader, InboundHrmpMessage, OccupiedCoreAssumption,
+		SessionIndex, ValidationCodeHash, ValidatorId,
 	},
 	InboundDownwardMessage, ParaId, PersistedValidationData,
 };
@@ -32,14 +33,19 @@ use futures::{FutureExt, Stream, StreamExt};
 use polkadot_overseer::Handle;
 
 use sc_client_api::StorageProof;
+use sp_runtime::traits::BlakeTwo256;
 use sp_state_machine::StorageValue;
 use sp_storage::StorageKey;
 use sp_version::RuntimeVersion;
-use std::{collections::btree_map::BTreeMap, pin::Pin};
+use std::{
+	collections::{btree_map::BTreeMap, HashMap},
+	pin::Pin,
+};
 
 use cumulus_primitives_core::relay_chain::BlockId;
 pub use url::Url;
 
+mod cross_checking_ws_client;
 mod light_client_worker;
 mod metrics;
 mod reconnecting_ws_client;
@@ -47,8 +53,8 @@ mod rpc_client;
 mod tokio_platform;
 
 pub use rpc_client::{
-	create_client_and_start_light_client_worker, create_client_and_start_worker,
-	RelayChainRpcClient,
+	create_client_and_start_cross_checking_worker, create_client_and_start_light_client_worker,
+	create_client_and_start_worker, RelayChainRpcClient,
 };
 
 const TIMEOUT_IN_SECONDS: u64 = 6;
@@ -65,6 +71,61 @@ impl RelayChainRpcInterface {
 	pub fn new(rpc_client: RelayChainRpcClient, overseer_handle: Handle) -> Self {
 		Self { rpc_client, overseer_handle }
 	}
+
+	/// Fetch the header of the block `hash` and make sure it actually hashes to `hash`.
+	async fn verified_header(&self, hash: RelayHash) -> RelayChainResult<RelayHeader> {
+		let (header, endpoint) = self.rpc_client.served_chain_get_header(Some(hash)).await?;
+		let header = header.ok_or_else(|| {
+			RelayChainError::GenericError(format!("Relay chain header of {hash} not found"))
+		})?;
+		self.ensure_verified(endpoint, header.hash() == hash, || {
+			format!("header returned for {hash} hashes to {}", header.hash())
+		})?;
+		Ok(header)
+	}
+
+	/// Read `keys` at `relay_parent` from a storage proof checked against the block's state root.
+	async fn verified_storage(
+		&self,
+		relay_parent: RelayHash,
+		keys: Vec<Vec<u8>>,
+	) -> RelayChainResult<HashMap<Vec<u8>, Option<StorageValue>>> {
+		let header = self.verified_header(relay_parent).await?;
+		let storage_keys = keys.iter().cloned().map(StorageKey).collect();
+		let (read_proof, endpoint) = self
+			.rpc_client
+			.served_state_get_read_proof(storage_keys, Some(relay_parent))
+			.await?;
+		let proof = StorageProof::new(read_proof.proof.into_iter().map(|bytes| bytes.to_vec()));
+		sp_state_machine::read_proof_check::<BlakeTwo256, _>(header.state_root, proof, &keys)
+			.map_err(|err| {
+				self.rpc_client.report_unverified_response(
+					endpoint,
+					format!("storage proof at {relay_parent} doesn't match its state root: {err}"),
+				);
+				RelayChainError::StateMachineError(err)
+			})
+	}
+
+	/// Fail with a [`RelayChainError`] describing the mismatch if a response could not be
+	/// verified, and report the mismatch to the worker so it stops using the `endpoint` which
+	/// served the response.
+	fn ensure_verified(
+		&self,
+		endpoint: Option<usize>,
+		verified: bool,
+		mismatch: impl FnOnce() -> String,
+	) -> RelayChainResult<()> {
+		if verified {
+			return Ok(())
+		}
+
+		let mismatch = mismatch();
+		self.rpc_client.report_unverified_response(endpoint, mismatch.clone());
+		Err(RelayChainError::GenericError(format!(
+			"Unable to verify relay chain RPC response: {mismatch}"
+		)))
+	}
 }
 
 #[async_trait]
@@ -98,7 +159,18 @@ impl RelayChainInterface for RelayChainRpcInterface {
 				}
 			},
 		};
-		let header = self.rpc_client.chain_get_header(Some(hash)).await?;
+		let (header, endpoint) = self.rpc_client.served_chain_get_header(Some(hash)).await?;
+
+		if let (true, Some(header)) = (self.rpc_client.verifies_responses(), &header) {
+			self.ensure_verified(endpoint, header.hash() == hash, || {
+				format!("header returned for {hash} hashes to {}", header.hash())
+			})?;
+			if let BlockId::Number(number) = block_id {
+				self.ensure_verified(endpoint, header.number == number, || {
+					format!("header returned for #{number} is #{}", header.number)
+				})?;
+			}
+		}
 
 		Ok(header)
 	}
@@ -109,9 +181,45 @@ impl RelayChainInterface for RelayChainRpcInterface {
 		para_id: ParaId,
 		occupied_core_assumption: OccupiedCoreAssumption,
 	) -> RelayChainResult<Option<PersistedValidationData>> {
-		self.rpc_client
-			.parachain_host_persisted_validation_data(hash, para_id, occupied_core_assumption)
-			.await
+		let (validation_data, endpoint) = self
+			.rpc_client
+			.served_parachain_host_persisted_validation_data(
+				hash,
+				para_id,
+				occupied_core_assumption,
+			)
+			.await?;
+
+		let Some(validation_data) = validation_data else { return Ok(None) };
+		if !self.rpc_client.verifies_responses() {
+			return Ok(Some(validation_data))
+		}
+
+		let header = self.verified_header(hash).await?;
+		self.ensure_verified(
+			endpoint,
+			validation_data.relay_parent_number == header.number &&
+				validation_data.relay_parent_storage_root == header.state_root,
+			|| format!("persisted validation data of {para_id} doesn't match relay parent {hash}"),
+		)?;
+
+		// With a candidate assumed to be included the parent head isn't in the relay chain state
+		// yet, otherwise it has to match the head stored for the para.
+		if occupied_core_assumption != OccupiedCoreAssumption::Included {
+			let key = well_known_keys::para_head(para_id);
+			let head = self.verified_storage(hash, vec![key.clone()]).await?.remove(&key).flatten();
+			self.ensure_verified(
+				endpoint,
+				head == Some(validation_data.parent_head.encode()),
+				|| {
+					format!(
+						"parent head of {para_id} doesn't match the relay chain state at {hash}"
+					)
+				},
+			)?;
+		}
+
+		Ok(Some(validation_data))
 	}
 
 	async fn validation_code_hash(
@@ -192,6 +300,13 @@ impl RelayChainInterface for RelayChainRpcInterface {
 		relay_parent: RelayHash,
 		key: &[u8],
 	) -> RelayChainResult<Option<StorageValue>> {
+		if self.rpc_client.verifies_responses() {
+			return self
+				.verified_storage(relay_parent, vec![key.to_vec()])
+				.await
+				.map(|mut values| values.remove(key).flatten())
+		}
+
 		let storage_key = StorageKey(key.to_vec());
 		self.rpc_client
 			.state_get_storage(storage_key, Some(relay_parent))
@@ -287,4 +402,8 @@ impl RelayChainInterface for RelayChainRpcInterface {
 	async fn scheduling_lookahead(&self, relay_parent: RelayHash) -> RelayChainResult<u32> {
 		self.rpc_client.parachain_host_scheduling_lookahead(relay_parent).await
 	}
+
+	async fn submit_extrinsic(&self, extrinsic: Vec<u8>) -> RelayChainResult<()> {
+		self.rpc_client.author_submit_extrinsic(extrinsic).await.map(|_| ())
+	}
 }
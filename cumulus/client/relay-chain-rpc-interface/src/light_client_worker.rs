@@ -259,7 +259,7 @@ impl LightClientRpcWorker {
 									?response,
 									"Response"
 								);
-							if let Err(err) = response_sender.send(response) {
+							if let Err(err) = response_sender.send((response, None)) {
 								tracing::debug!(
 									target: LOG_TARGET,
 									?err,
@@ -268,6 +268,8 @@ impl LightClientRpcWorker {
 							};
 						});
 					},
+					// Responses are only verified when cross-checking several endpoints.
+					Some(RpcDispatcherMessage::ReportUnverifiedResponse(..)) => {},
 					None => {
 						tracing::error!(target: LOG_TARGET, "RPC client receiver closed. Stopping RPC Worker.");
 						return;
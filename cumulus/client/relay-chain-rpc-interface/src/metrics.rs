@@ -16,7 +16,7 @@
 // along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
 
 use prometheus::{Error as PrometheusError, HistogramTimer, Registry};
-use prometheus_endpoint::{HistogramOpts, HistogramVec, Opts};
+use prometheus_endpoint::{CounterVec, GaugeVec, HistogramOpts, HistogramVec, Opts, U64};
 
 /// Gathers metrics about the blockchain RPC client.
 #[derive(Clone)]
@@ -48,3 +48,90 @@ impl RelaychainRpcMetrics {
 		self.rpc_request.with_label_values(&[method]).start_timer()
 	}
 }
+
+/// Per-endpoint metrics of the cross-checking RPC worker.
+#[derive(Clone)]
+pub(crate) struct CrossCheckMetrics {
+	best_block: GaugeVec<U64>,
+	finalized_block: GaugeVec<U64>,
+	active: GaugeVec<U64>,
+	demoted: GaugeVec<U64>,
+	divergences: CounterVec<U64>,
+}
+
+impl CrossCheckMetrics {
+	pub(crate) fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			best_block: prometheus_endpoint::register(
+				GaugeVec::new(
+					Opts::new(
+						"relay_chain_rpc_endpoint_best_block",
+						"Best block number reported by each relay chain RPC endpoint",
+					),
+					&["endpoint"],
+				)?,
+				registry,
+			)?,
+			finalized_block: prometheus_endpoint::register(
+				GaugeVec::new(
+					Opts::new(
+						"relay_chain_rpc_endpoint_finalized_block",
+						"Finalized block number reported by each relay chain RPC endpoint",
+					),
+					&["endpoint"],
+				)?,
+				registry,
+			)?,
+			active: prometheus_endpoint::register(
+				GaugeVec::new(
+					Opts::new(
+						"relay_chain_rpc_endpoint_active",
+						"Whether the relay chain RPC endpoint serves requests and notifications",
+					),
+					&["endpoint"],
+				)?,
+				registry,
+			)?,
+			demoted: prometheus_endpoint::register(
+				GaugeVec::new(
+					Opts::new(
+						"relay_chain_rpc_endpoint_demoted",
+						"Whether the relay chain RPC endpoint is demoted for diverging from the others",
+					),
+					&["endpoint"],
+				)?,
+				registry,
+			)?,
+			divergences: prometheus_endpoint::register(
+				CounterVec::new(
+					Opts::new(
+						"relay_chain_rpc_endpoint_divergences_total",
+						"Number of times a relay chain RPC endpoint diverged from the others, by kind",
+					),
+					&["endpoint", "kind"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	pub(crate) fn on_best_block(&self, endpoint: &str, number: u32) {
+		self.best_block.with_label_values(&[endpoint]).set(number.into());
+	}
+
+	pub(crate) fn on_finalized_block(&self, endpoint: &str, number: u32) {
+		self.finalized_block.with_label_values(&[endpoint]).set(number.into());
+	}
+
+	pub(crate) fn set_active(&self, endpoint: &str, active: bool) {
+		self.active.with_label_values(&[endpoint]).set(active.into());
+	}
+
+	pub(crate) fn set_demoted(&self, endpoint: &str, demoted: bool) {
+		self.demoted.with_label_values(&[endpoint]).set(demoted.into());
+	}
+
+	pub(crate) fn on_divergence(&self, endpoint: &str, kind: &str) {
+		self.divergences.with_label_values(&[endpoint, kind]).inc();
+	}
+}
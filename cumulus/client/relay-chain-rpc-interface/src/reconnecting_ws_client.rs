@@ -28,7 +28,7 @@ use jsonrpsee::{
 	core::{
 		client::{Client as JsonRpcClient, ClientT, Subscription},
 		params::ArrayParams,
-		ClientError as JsonRpseeError, JsonValue,
+		ClientError as JsonRpseeError,
 	},
 	ws_client::WsClientBuilder,
 };
@@ -41,10 +41,10 @@ use tokio::sync::mpsc::{
 };
 use url::Url;
 
-use crate::rpc_client::{distribute_header, RpcDispatcherMessage};
+use crate::rpc_client::{distribute_header, RpcDispatcherMessage, RpcResponse};
 
 const LOG_TARGET: &str = "reconnecting-websocket-client";
-const DEFAULT_EXTERNAL_RPC_CONN_RETRIES: usize = 5;
+pub(crate) const DEFAULT_EXTERNAL_RPC_CONN_RETRIES: usize = 5;
 const DEFAULT_SLEEP_TIME_MS_BETWEEN_RETRIES: u64 = 1000;
 const DEFAULT_SLEEP_EXP_BACKOFF_BETWEEN_RETRIES: i32 = 2;
 
@@ -63,7 +63,7 @@ pub struct ReconnectingWebsocketWorker {
 }
 
 /// Format url and force addition of a port
-fn url_to_string_with_port(url: Url) -> Option<String> {
+pub(crate) fn url_to_string_with_port(url: Url) -> Option<String> {
 	// This is already validated on CLI side, just defensive here
 	if (url.scheme() != "ws" && url.scheme() != "wss") || url.host_str().is_none() {
 		tracing::warn!(target: LOG_TARGET, ?url, "Non-WebSocket URL or missing host.");
@@ -91,10 +91,97 @@ struct ClientManager {
 	active_index: usize,
 }
 
-struct RelayChainSubscriptions {
-	import_subscription: Subscription<RelayHeader>,
-	finalized_subscription: Subscription<RelayHeader>,
-	best_subscription: Subscription<RelayHeader>,
+pub(crate) struct RelayChainSubscriptions {
+	pub(crate) import_subscription: Subscription<RelayHeader>,
+	pub(crate) finalized_subscription: Subscription<RelayHeader>,
+	pub(crate) best_subscription: Subscription<RelayHeader>,
+}
+
+/// Open the import, best and finalized head subscriptions on the given client.
+pub(crate) async fn subscribe_to_heads(
+	client: &JsonRpcClient,
+) -> Result<RelayChainSubscriptions, JsonRpseeError> {
+	let import_subscription = <JsonRpcClient as ChainApiClient<
+		RelayNumber,
+		RelayHash,
+		RelayHeader,
+		SignedBlock<RelayBlock>,
+	>>::subscribe_all_heads(client)
+	.await
+	.map_err(|e| {
+		tracing::error!(
+			target: LOG_TARGET,
+			?e,
+			"Unable to open `chain_subscribeAllHeads` subscription."
+		);
+		e
+	})?;
+
+	let best_subscription = <JsonRpcClient as ChainApiClient<
+		RelayNumber,
+		RelayHash,
+		RelayHeader,
+		SignedBlock<RelayBlock>,
+	>>::subscribe_new_heads(client)
+	.await
+	.map_err(|e| {
+		tracing::error!(
+			target: LOG_TARGET,
+			?e,
+			"Unable to open `chain_subscribeNewHeads` subscription."
+		);
+		e
+	})?;
+
+	let finalized_subscription = <JsonRpcClient as ChainApiClient<
+		RelayNumber,
+		RelayHash,
+		RelayHeader,
+		SignedBlock<RelayBlock>,
+	>>::subscribe_finalized_heads(client)
+	.await
+	.map_err(|e| {
+		tracing::error!(
+			target: LOG_TARGET,
+			?e,
+			"Unable to open `chain_subscribeFinalizedHeads` subscription."
+		);
+		e
+	})?;
+
+	Ok(RelayChainSubscriptions { import_subscription, best_subscription, finalized_subscription })
+}
+
+/// Create a request future that performs an RPC request on the given client and sends the
+/// results to the caller, together with the index of the `endpoint` serving it. In case of a dead
+/// websocket connection, it returns the original request parameters to enable retries.
+pub(crate) fn create_request(
+	client: Arc<JsonRpcClient>,
+	endpoint: Option<usize>,
+	method: String,
+	params: ArrayParams,
+	response_sender: OneshotSender<RpcResponse>,
+) -> BoxFuture<'static, Result<(), RpcDispatcherMessage>> {
+	async move {
+		let resp = client.request(&method, params.clone()).await;
+
+		// We should only return the original request in case
+		// the websocket connection is dead and requires a restart.
+		// Other errors should be forwarded to the request caller.
+		if let Err(JsonRpseeError::RestartNeeded(_)) = resp {
+			return Err(RpcDispatcherMessage::Request(method, params, response_sender))
+		}
+
+		if let Err(err) = response_sender.send((resp, endpoint)) {
+			tracing::debug!(
+				target: LOG_TARGET,
+				?err,
+				"Recipient no longer interested in request result"
+			);
+		}
+		Ok(())
+	}
+	.boxed()
 }
 
 /// Try to find a new RPC server to connect to. Uses a naive retry
@@ -168,91 +255,17 @@ impl ClientManager {
 	}
 
 	async fn get_subscriptions(&self) -> Result<RelayChainSubscriptions, JsonRpseeError> {
-		let import_subscription = <JsonRpcClient as ChainApiClient<
-			RelayNumber,
-			RelayHash,
-			RelayHeader,
-			SignedBlock<RelayBlock>,
-		>>::subscribe_all_heads(&self.active_client)
-		.await
-		.map_err(|e| {
-			tracing::error!(
-				target: LOG_TARGET,
-				?e,
-				"Unable to open `chain_subscribeAllHeads` subscription."
-			);
-			e
-		})?;
-
-		let best_subscription = <JsonRpcClient as ChainApiClient<
-			RelayNumber,
-			RelayHash,
-			RelayHeader,
-			SignedBlock<RelayBlock>,
-		>>::subscribe_new_heads(&self.active_client)
-		.await
-		.map_err(|e| {
-			tracing::error!(
-				target: LOG_TARGET,
-				?e,
-				"Unable to open `chain_subscribeNewHeads` subscription."
-			);
-			e
-		})?;
-
-		let finalized_subscription = <JsonRpcClient as ChainApiClient<
-			RelayNumber,
-			RelayHash,
-			RelayHeader,
-			SignedBlock<RelayBlock>,
-		>>::subscribe_finalized_heads(&self.active_client)
-		.await
-		.map_err(|e| {
-			tracing::error!(
-				target: LOG_TARGET,
-				?e,
-				"Unable to open `chain_subscribeFinalizedHeads` subscription."
-			);
-			e
-		})?;
-
-		Ok(RelayChainSubscriptions {
-			import_subscription,
-			best_subscription,
-			finalized_subscription,
-		})
+		subscribe_to_heads(&self.active_client).await
 	}
 
-	/// Create a request future that performs an RPC request and sends the results to the caller.
-	/// In case of a dead websocket connection, it returns the original request parameters to
-	/// enable retries.
+	/// Create a request future that performs an RPC request on the active client.
 	fn create_request(
 		&self,
 		method: String,
 		params: ArrayParams,
-		response_sender: OneshotSender<Result<JsonValue, JsonRpseeError>>,
+		response_sender: OneshotSender<RpcResponse>,
 	) -> BoxFuture<'static, Result<(), RpcDispatcherMessage>> {
-		let future_client = self.active_client.clone();
-		async move {
-			let resp = future_client.request(&method, params.clone()).await;
-
-			// We should only return the original request in case
-			// the websocket connection is dead and requires a restart.
-			// Other errors should be forwarded to the request caller.
-			if let Err(JsonRpseeError::RestartNeeded(_)) = resp {
-				return Err(RpcDispatcherMessage::Request(method, params, response_sender))
-			}
-
-			if let Err(err) = response_sender.send(resp) {
-				tracing::debug!(
-					target: LOG_TARGET,
-					?err,
-					"Recipient no longer interested in request result"
-				);
-			}
-			Ok(())
-		}
-		.boxed()
+		create_request(self.active_client.clone(), None, method, params, response_sender)
 	}
 }
 
@@ -386,6 +399,8 @@ impl ReconnectingWebsocketWorker {
 					Some(RpcDispatcherMessage::Request(method, params, response_sender)) => {
 						pending_requests.push(client_manager.create_request(method, params, response_sender));
 					},
+					// Responses are only verified when cross-checking several endpoints.
+					Some(RpcDispatcherMessage::ReportUnverifiedResponse(..)) => {},
 					None => {
 						tracing::error!(target: LOG_TARGET, "RPC client receiver closed. Stopping RPC Worker.");
 						return;
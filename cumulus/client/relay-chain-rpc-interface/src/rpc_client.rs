@@ -58,6 +58,7 @@ use sp_storage::StorageKey;
 use sp_version::RuntimeVersion;
 
 use crate::{
+	cross_checking_ws_client::CrossCheckingWebsocketWorker,
 	light_client_worker::{build_smoldot_client, LightClientRpcWorker},
 	metrics::RelaychainRpcMetrics,
 	reconnecting_ws_client::ReconnectingWebsocketWorker,
@@ -67,6 +68,10 @@ pub use url::Url;
 const LOG_TARGET: &str = "relay-chain-rpc-client";
 const NOTIFICATION_CHANNEL_SIZE_LIMIT: usize = 20;
 
+/// The result of a [`RpcDispatcherMessage::Request`], together with the index of the endpoint
+/// which served it if the worker talks to several endpoints at once.
+pub type RpcResponse = (Result<JsonValue, JsonRpseeError>, Option<usize>);
+
 /// Messages for communication between [`RelayChainRpcClient`] and the RPC workers.
 #[derive(Debug)]
 pub enum RpcDispatcherMessage {
@@ -87,7 +92,11 @@ pub enum RpcDispatcherMessage {
 	/// - [`String`] representing the RPC method to be called
 	/// - [`ArrayParams`] for the parameters to the RPC call
 	/// - [`OneshotSender`] for the return value of the request
-	Request(String, ArrayParams, OneshotSender<Result<JsonValue, JsonRpseeError>>),
+	Request(String, ArrayParams, OneshotSender<RpcResponse>),
+
+	/// Report that a response could not be verified against the relay chain headers. Contains the
+	/// index of the endpoint which served the response and a description of the mismatch.
+	ReportUnverifiedResponse(usize, String),
 }
 
 /// Entry point to create [`RelayChainRpcClient`] and start a worker that communicates
@@ -108,6 +117,26 @@ pub async fn create_client_and_start_worker(
 	Ok(client)
 }
 
+/// Entry point to create [`RelayChainRpcClient`] and start a worker that stays connected to all
+/// given JsonRPC servers and cross-checks them.
+///
+/// The returned client verifies the responses it can check against the relay chain headers.
+pub async fn create_client_and_start_cross_checking_worker(
+	urls: Vec<Url>,
+	task_manager: &mut TaskManager,
+	prometheus_registry: Option<&Registry>,
+) -> RelayChainResult<RelayChainRpcClient> {
+	let (worker, sender) = CrossCheckingWebsocketWorker::new(urls, prometheus_registry);
+
+	task_manager
+		.spawn_essential_handle()
+		.spawn("relay-chain-rpc-worker", None, worker.run());
+
+	let client = RelayChainRpcClient::new(sender, prometheus_registry).with_response_verification();
+
+	Ok(client)
+}
+
 /// Entry point to create [`RelayChainRpcClient`] and start a worker that communicates
 /// with an embedded smoldot instance.
 pub async fn create_client_and_start_light_client_worker(
@@ -137,6 +166,8 @@ pub struct RelayChainRpcClient {
 	/// Sender to send messages to the worker.
 	worker_channel: TokioSender<RpcDispatcherMessage>,
 	metrics: Option<RelaychainRpcMetrics>,
+	/// Whether responses should be verified against the relay chain headers.
+	verify_responses: bool,
 }
 
 impl RelayChainRpcClient {
@@ -154,9 +185,21 @@ impl RelayChainRpcClient {
 				.and_then(|inner| RelaychainRpcMetrics::register(inner).map_err(|err| {
 					tracing::warn!(target: LOG_TARGET, error = %err, "Unable to instantiate the RPC client metrics, continuing w/o metrics setup.");
 				}).ok()),
+			verify_responses: false,
 		}
 	}
 
+	/// Verify the responses against the relay chain headers where possible.
+	pub(crate) fn with_response_verification(mut self) -> Self {
+		self.verify_responses = true;
+		self
+	}
+
+	/// Whether responses should be verified against the relay chain headers.
+	pub(crate) fn verifies_responses(&self) -> bool {
+		self.verify_responses
+	}
+
 	/// Same as `call_remote_runtime_function` but work on encoded data
 	pub async fn call_remote_runtime_function_encoded(
 		&self,
@@ -164,6 +207,19 @@ impl RelayChainRpcClient {
 		hash: RelayHash,
 		payload: &[u8],
 	) -> RelayChainResult<sp_core::Bytes> {
+		self.served_call_remote_runtime_function_encoded(method_name, hash, payload)
+			.await
+			.map(|(bytes, _)| bytes)
+	}
+
+	/// Same as [`Self::call_remote_runtime_function_encoded`], but also returns the index of the
+	/// endpoint which served the call, if known.
+	async fn served_call_remote_runtime_function_encoded(
+		&self,
+		method_name: &str,
+		hash: RelayHash,
+		payload: &[u8],
+	) -> RelayChainResult<(sp_core::Bytes, Option<usize>)> {
 		let payload = PayloadToHex(payload);
 
 		let params = rpc_params! {
@@ -172,7 +228,7 @@ impl RelayChainRpcClient {
 			hash
 		};
 
-		self.request_tracing::<sp_core::Bytes, _>("state_call", params, |err| {
+		self.served_request_tracing::<sp_core::Bytes, _>("state_call", params, |err| {
 			tracing::trace!(
 				target: LOG_TARGET,
 				%method_name,
@@ -216,6 +272,24 @@ impl RelayChainRpcClient {
 		.await
 	}
 
+	/// Same as [`Self::request`], but also returns the index of the endpoint which served the
+	/// request, if known.
+	async fn served_request<'a, R>(
+		&self,
+		method: &'a str,
+		params: ArrayParams,
+	) -> Result<(R, Option<usize>), RelayChainError>
+	where
+		R: DeserializeOwned + std::fmt::Debug,
+	{
+		self.served_request_tracing(
+			method,
+			params,
+			|e| tracing::trace!(target:LOG_TARGET, error = %e, %method, "Unable to complete RPC request"),
+		)
+		.await
+	}
+
 	/// Perform RPC request
 	async fn request_tracing<'a, R, OR>(
 		&self,
@@ -223,6 +297,23 @@ impl RelayChainRpcClient {
 		params: ArrayParams,
 		trace_error: OR,
 	) -> Result<R, RelayChainError>
+	where
+		R: DeserializeOwned + std::fmt::Debug,
+		OR: Fn(&RelayChainError),
+	{
+		self.served_request_tracing(method, params, trace_error)
+			.await
+			.map(|(value, _)| value)
+	}
+
+	/// Perform RPC request, returning the response together with the index of the endpoint which
+	/// served it, if known.
+	async fn served_request_tracing<'a, R, OR>(
+		&self,
+		method: &'a str,
+		params: ArrayParams,
+		trace_error: OR,
+	) -> Result<(R, Option<usize>), RelayChainError>
 	where
 		R: DeserializeOwned + std::fmt::Debug,
 		OR: Fn(&RelayChainError),
@@ -239,17 +330,57 @@ impl RelayChainRpcClient {
 			))
 		})?;
 
-		let value = rx.await.map_err(|err| {
+		let (value, endpoint) = rx.await.map_err(|err| {
 			RelayChainError::WorkerCommunicationError(format!(
 				"RPC worker channel closed. This can hint and connectivity issues with the supplied RPC endpoints. Message: {}",
 				err
 			))
-		})??;
+		})?;
 
-		serde_json::from_value(value).map_err(|_| {
+		let value = serde_json::from_value(value?).map_err(|_| {
 			trace_error(&RelayChainError::GenericError("Unable to deserialize value".to_string()));
 			RelayChainError::RpcCallError(method.to_string())
-		})
+		})?;
+		Ok((value, endpoint))
+	}
+
+	/// Same as [`Self::chain_get_header`], but also returns the index of the endpoint which
+	/// served the header, if known.
+	pub(crate) async fn served_chain_get_header(
+		&self,
+		hash: Option<RelayHash>,
+	) -> Result<(Option<RelayHeader>, Option<usize>), RelayChainError> {
+		let params = rpc_params![hash];
+		self.served_request("chain_getHeader", params).await
+	}
+
+	/// Same as [`Self::state_get_read_proof`], but also returns the index of the endpoint which
+	/// served the proof, if known.
+	pub(crate) async fn served_state_get_read_proof(
+		&self,
+		storage_keys: Vec<StorageKey>,
+		at: Option<RelayHash>,
+	) -> Result<(ReadProof<RelayHash>, Option<usize>), RelayChainError> {
+		let params = rpc_params![storage_keys, at];
+		self.served_request("state_getReadProof", params).await
+	}
+
+	/// Same as [`Self::parachain_host_persisted_validation_data`], but also returns the index of
+	/// the endpoint which served the data, if known.
+	pub(crate) async fn served_parachain_host_persisted_validation_data(
+		&self,
+		at: RelayHash,
+		para_id: ParaId,
+		occupied_core_assumption: OccupiedCoreAssumption,
+	) -> Result<(Option<PersistedValidationData>, Option<usize>), RelayChainError> {
+		let (bytes, endpoint) = self
+			.served_call_remote_runtime_function_encoded(
+				"ParachainHost_persisted_validation_data",
+				at,
+				&(para_id, occupied_core_assumption).encode(),
+			)
+			.await?;
+		Ok((Decode::decode(&mut &*bytes.0)?, endpoint))
 	}
 
 	/// Returns information regarding the current epoch.
@@ -315,6 +446,15 @@ impl RelayChainRpcClient {
 		self.request("state_getStorage", params).await
 	}
 
+	/// Submit a SCALE-encoded extrinsic to the transaction pool of the node.
+	pub async fn author_submit_extrinsic(
+		&self,
+		extrinsic: Vec<u8>,
+	) -> Result<RelayHash, RelayChainError> {
+		let params = rpc_params![sp_core::Bytes(extrinsic)];
+		self.request("author_submitExtrinsic", params).await
+	}
+
 	/// Get hash of the n-th block in the canon chain.
 	///
 	/// By default returns latest block hash.
@@ -759,6 +899,21 @@ impl RelayChainRpcClient {
 			.map_err(|e| RelayChainError::WorkerCommunicationError(e.to_string()))
 	}
 
+	/// Tell the worker that a response served by `endpoint` could not be verified, so it stops
+	/// using the endpoint.
+	///
+	/// Nothing is reported if the endpoint is unknown, i.e. the worker only talks to a single
+	/// endpoint at a time.
+	pub(crate) fn report_unverified_response(&self, endpoint: Option<usize>, mismatch: String) {
+		let Some(endpoint) = endpoint else { return };
+		if let Err(err) = self
+			.worker_channel
+			.try_send(RpcDispatcherMessage::ReportUnverifiedResponse(endpoint, mismatch))
+		{
+			tracing::debug!(target: LOG_TARGET, %err, "Unable to report an unverified response.");
+		}
+	}
+
 	/// Get a stream of all imported relay chain headers
 	pub fn get_imported_heads_stream(&self) -> Result<Receiver<RelayHeader>, RelayChainError> {
 		let (tx, rx) =
@@ -21,18 +21,22 @@
 
 use cumulus_client_cli::CollatorOptions;
 use cumulus_client_consensus_common::ParachainConsensus;
-use cumulus_client_network::{AssumeSybilResistance, RequireSecondedInBlockAnnounce};
-use cumulus_client_pov_recovery::{PoVRecovery, RecoveryDelayRange, RecoveryHandle};
+use cumulus_client_network::{
+	AssumeSybilResistance, BlockFetchRequestHandler, RequireSecondedInBlockAnnounce,
+};
+use cumulus_client_pov_recovery::{BlockFetcher, PoVRecovery, RecoveryDelayRange, RecoveryHandle};
 use cumulus_primitives_core::{CollectCollationInfo, ParaId};
 use cumulus_relay_chain_inprocess_interface::build_inprocess_relay_chain;
 use cumulus_relay_chain_interface::{RelayChainInterface, RelayChainResult};
 use cumulus_relay_chain_minimal_node::{
-	build_minimal_relay_chain_node_light_client, build_minimal_relay_chain_node_with_rpc,
+	build_minimal_relay_chain_node_light_client,
+	build_minimal_relay_chain_node_with_cross_checked_rpc, build_minimal_relay_chain_node_with_rpc,
 };
 use futures::{channel::mpsc, StreamExt};
 use polkadot_primitives::{CollatorPair, OccupiedCoreAssumption};
 use sc_client_api::{
-	Backend as BackendT, BlockBackend, BlockchainEvents, Finalizer, ProofProvider, UsageProvider,
+	AuxStore, Backend as BackendT, BlockBackend, BlockchainEvents, Finalizer, ProofProvider,
+	UsageProvider,
 };
 use sc_consensus::{
 	import_queue::{ImportQueue, ImportQueueService},
@@ -50,7 +54,13 @@ use sp_core::{traits::SpawnNamed, Decode};
 use sp_runtime::traits::{Block as BlockT, BlockIdTo, Header};
 use std::{sync::Arc, time::Duration};
 
+mod on_demand;
+
+pub use cumulus_client_network::PeerBlockFetcher;
 pub use cumulus_primitives_proof_size_hostfunction::storage_proof_size;
+pub use on_demand::{
+	start_on_demand_order_service, OnDemandOrderConfig, StartOnDemandOrderServiceParams,
+};
 
 /// Host functions that should be used in parachain nodes.
 ///
@@ -107,6 +117,9 @@ pub struct StartRelayChainTasksParams<'a, Block: BlockT, Client, RCInterface> {
 	pub relay_chain_slot_duration: Duration,
 	pub recovery_handle: Box<dyn RecoveryHandle>,
 	pub sync_service: Arc<SyncingService<Block>>,
+	/// Fetches the blocks that could not be recovered from the relay chain from the parachain
+	/// peers, see [`PeerBlockFetcher`].
+	pub block_fetcher: Option<Box<dyn BlockFetcher<Block>>>,
 }
 
 /// Parameters given to [`start_full_node`].
@@ -178,6 +191,7 @@ where
 		relay_chain_slot_duration,
 		recovery_handle,
 		sync_service,
+		block_fetcher: None,
 	})?;
 
 	#[allow(deprecated)]
@@ -215,6 +229,7 @@ pub fn start_relay_chain_tasks<Block, Client, Backend, RCInterface>(
 		relay_chain_slot_duration,
 		recovery_handle,
 		sync_service,
+		block_fetcher,
 	}: StartRelayChainTasksParams<Block, Client, RCInterface>,
 ) -> sc_service::error::Result<()>
 where
@@ -268,7 +283,7 @@ where
 		DARecoveryProfile::Other(profile) => profile,
 	};
 
-	let pov_recovery = PoVRecovery::new(
+	let mut pov_recovery = PoVRecovery::new(
 		recovery_handle,
 		da_recovery_profile,
 		client.clone(),
@@ -278,6 +293,9 @@ where
 		recovery_chan_rx,
 		sync_service,
 	);
+	if let Some(block_fetcher) = block_fetcher {
+		pov_recovery = pov_recovery.with_block_fetcher(block_fetcher);
+	}
 
 	task_manager
 		.spawn_essential_handle()
@@ -328,6 +346,7 @@ where
 		recovery_handle,
 		sync_service,
 		da_recovery_profile: DARecoveryProfile::FullNode,
+		block_fetcher: None,
 	})
 }
 
@@ -376,6 +395,14 @@ pub async fn build_relay_chain_interface(
 				rpc_target_urls,
 			)
 			.await,
+		cumulus_client_cli::RelayChainMode::CrossCheckedRpc(rpc_target_urls) =>
+			build_minimal_relay_chain_node_with_cross_checked_rpc(
+				relay_chain_config,
+				parachain_config.prometheus_registry(),
+				task_manager,
+				rpc_target_urls,
+			)
+			.await,
 		cumulus_client_cli::RelayChainMode::LightClient =>
 			build_minimal_relay_chain_node_light_client(relay_chain_config, task_manager).await,
 	}
@@ -427,7 +454,7 @@ pub struct BuildNetworkParams<
 pub async fn build_network<'a, Block, Client, RCInterface, IQ, Network>(
 	BuildNetworkParams {
 		parachain_config,
-		net_config,
+		mut net_config,
 		client,
 		transaction_pool,
 		para_id,
@@ -455,6 +482,7 @@ where
 		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
 		+ BlockIdTo<Block, Error = sp_blockchain::Error>
 		+ ProofProvider<Block>
+		+ AuxStore
 		+ 'static,
 	Client::Api: CollectCollationInfo<Block>
 		+ sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
@@ -493,6 +521,19 @@ where
 			Box::new(block_announce_validator) as Box<_>
 		},
 	};
+
+	// Serve the blocks of this node to the peers that could not recover them from the relay chain.
+	let (block_fetch_handler, block_fetch_protocol_config) = BlockFetchRequestHandler::new::<Network>(
+		client.clone(),
+		parachain_config.chain_spec.fork_id(),
+	);
+	net_config.add_request_response_protocol(block_fetch_protocol_config);
+	spawn_handle.spawn(
+		"cumulus-block-fetch-request-handler",
+		Some("networking"),
+		block_fetch_handler.run(),
+	);
+
 	let metrics = Network::register_notification_metrics(
 		parachain_config.prometheus_config.as_ref().map(|config| &config.registry),
 	);
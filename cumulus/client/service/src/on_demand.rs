@@ -0,0 +1,1200 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus. If not, see <https://www.gnu.org/licenses/>.
+
+//! Automatic ordering of on-demand coretime.
+//!
+//! Parachains running on on-demand coretime only get a core when someone places an order on the
+//! relay chain. The service in this module does that for the collator: whenever the transaction
+//! pool holds enough ready transactions and the para has nothing in the claim queue, it signs and
+//! submits an `on_demand::place_order_keep_alive` extrinsic to the relay chain, as long as the
+//! spot price is acceptable and the configured budget isn't exhausted. The amount spent is only
+//! accounted for once the order shows up in the events of the relay chain.
+//!
+//! The extrinsic is built according to the metadata of the relay chain runtime, which is read
+//! again whenever the runtime is upgraded. Relay chains whose transaction extensions aren't known
+//! to the service are rejected when it starts.
+
+use codec::{Compact, Decode, Encode};
+use cumulus_primitives_core::{relay_chain::BlockId, ParaId};
+use cumulus_relay_chain_interface::{
+	call_runtime_api, BlockNumber, PHash, PHeader, RelayChainError, RelayChainInterface,
+	RelayChainResult,
+};
+use frame_metadata::{
+	v15::{RuntimeMetadataV15, StorageEntryType},
+	RuntimeMetadata, RuntimeMetadataPrefixed,
+};
+use futures::StreamExt;
+use polkadot_primitives::{well_known_keys, Balance};
+use polkadot_runtime_parachains::configuration::HostConfiguration;
+use sc_client_api::AuxStore;
+use sc_service::TaskManager;
+use sc_transaction_pool_api::TransactionPool;
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+use sp_core::{
+	crypto::Pair as _,
+	hashing::{blake2_128, blake2_256, twox_128},
+	sr25519,
+};
+use sp_runtime::{
+	generic::Era, traits::Header as _, AccountId32, FixedPointNumber, FixedU128, MultiAddress,
+	MultiSignature,
+};
+use std::sync::Arc;
+
+const LOG_TARGET: &str = "cumulus-on-demand";
+
+/// Name of the on-demand pallet in the relay chain runtimes.
+const ON_DEMAND_PALLET: &str = "OnDemandAssignmentProvider";
+
+/// Name of the call placing an order in the on-demand pallet.
+const PLACE_ORDER_KEEP_ALIVE: &str = "place_order_keep_alive";
+
+/// Name of the event emitted by the on-demand pallet when an order is placed.
+const ORDER_PLACED: &str = "OnDemandOrderPlaced";
+
+/// Version of the metadata the extrinsic is built from.
+const METADATA_VERSION: u32 = 15;
+
+/// Version of the extrinsic format the extrinsic is built in.
+const EXTRINSIC_VERSION: u8 = 4;
+
+/// Version byte of a signed extrinsic in the [`EXTRINSIC_VERSION`] format.
+const SIGNED_EXTRINSIC: u8 = 0b1000_0000 | EXTRINSIC_VERSION;
+
+/// Payloads longer than this are hashed before being signed.
+const MAX_UNHASHED_PAYLOAD_LEN: usize = 256;
+
+/// How many relay chain blocks a placed order is valid for.
+const ORDER_MORTALITY: BlockNumber = 16;
+
+/// How many relay chain blocks to wait for a placed order to show up in the claim queue, before
+/// another one is placed.
+///
+/// No order is placed while the last one may still be included, as it would reuse its nonce.
+const ORDER_TIMEOUT: BlockNumber = ORDER_MORTALITY;
+
+/// Prefix of the aux storage key the amount spent by an account is stored under.
+const SPENT_KEY_PREFIX: &[u8] = b"cumulus_on_demand_spent";
+
+/// Configuration of the on-demand order service.
+pub struct OnDemandOrderConfig {
+	/// The para to order coretime for.
+	pub para_id: ParaId,
+	/// The relay chain account paying for the orders.
+	pub account: sr25519::Pair,
+	/// The total amount the service may spend on orders.
+	///
+	/// Orders are accounted for at the spot price they were placed at, once they are seen on
+	/// chain. The amount spent is kept across restarts of the node, per account. Transaction fees
+	/// are not accounted for.
+	pub budget: Balance,
+	/// The maximum spot price to pay for a single order.
+	pub max_spot_price: Balance,
+	/// The number of ready transactions in the pool needed to place an order.
+	pub min_backlog: usize,
+}
+
+/// Parameters given to [`start_on_demand_order_service`].
+pub struct StartOnDemandOrderServiceParams<'a, Pool, Client, RCInterface> {
+	pub config: OnDemandOrderConfig,
+	pub transaction_pool: Arc<Pool>,
+	/// Where the amount spent on orders is kept.
+	pub client: Arc<Client>,
+	pub relay_chain_interface: RCInterface,
+	pub task_manager: &'a TaskManager,
+}
+
+/// Start the service placing on-demand coretime orders on the relay chain when the transaction
+/// pool has a backlog.
+pub fn start_on_demand_order_service<Pool, Client, RCInterface>(
+	StartOnDemandOrderServiceParams {
+		config,
+		transaction_pool,
+		client,
+		relay_chain_interface,
+		task_manager,
+	}: StartOnDemandOrderServiceParams<Pool, Client, RCInterface>,
+) where
+	Pool: TransactionPool + 'static,
+	Client: AuxStore + Send + Sync + 'static,
+	RCInterface: RelayChainInterface + Clone + 'static,
+{
+	let spent = match load_spent(&*client, &config.account.public()) {
+		Ok(spent) => spent,
+		Err(err) => {
+			log::error!(
+				target: LOG_TARGET,
+				"Unable to load the amount spent on orders, no on-demand orders will be placed: {}",
+				err,
+			);
+			return
+		},
+	};
+
+	log::info!(
+		target: LOG_TARGET,
+		"Placing on-demand orders from {} with a budget of {}, of which {} is spent, and a maximum \
+		 spot price of {}",
+		AccountId32::from(config.account.public()),
+		config.budget,
+		spent,
+		config.max_spot_price,
+	);
+
+	let service = OnDemandOrderService {
+		config,
+		transaction_pool,
+		client,
+		relay_chain_interface,
+		genesis_hash: None,
+		layout: None,
+		state: OrderState { spent, last_order: None, pending: None },
+	};
+	task_manager
+		.spawn_handle()
+		.spawn("cumulus-on-demand-orders", None, service.run());
+}
+
+fn spent_key(account: &sr25519::Public) -> Vec<u8> {
+	(SPENT_KEY_PREFIX, account).encode()
+}
+
+fn load_spent(client: &impl AuxStore, account: &sr25519::Public) -> sp_blockchain::Result<Balance> {
+	match client.get_aux(&spent_key(account))? {
+		None => Ok(0),
+		Some(raw) => Balance::decode(&mut &raw[..])
+			.map_err(|err| sp_blockchain::Error::Backend(format!("Invalid amount spent: {err}"))),
+	}
+}
+
+fn store_spent(
+	client: &impl AuxStore,
+	account: &sr25519::Public,
+	spent: Balance,
+) -> sp_blockchain::Result<()> {
+	client.insert_aux(&[(&spent_key(account)[..], &spent.encode()[..])], &[])
+}
+
+/// An order submitted to the relay chain, which wasn't seen on chain yet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PendingOrder {
+	/// The nonce of the extrinsic placing the order.
+	nonce: u32,
+	/// The relay chain block number the order was placed at.
+	placed_at: BlockNumber,
+}
+
+/// Book-keeping of the orders, deciding when to place one.
+#[derive(Debug, Clone, PartialEq)]
+struct OrderState {
+	/// The amount spent on orders so far.
+	spent: Balance,
+	/// The relay chain block number at which the last order was placed, until it is served.
+	last_order: Option<BlockNumber>,
+	/// The last order, until it is seen on chain or expires.
+	pending: Option<PendingOrder>,
+}
+
+impl OrderState {
+	/// Whether an order is wanted at the relay chain block with the given number.
+	///
+	/// `scheduled` tells whether the para is in the claim queue, `backlog` is the number of ready
+	/// transactions in the pool.
+	fn wants_order(
+		&mut self,
+		config: &OnDemandOrderConfig,
+		number: BlockNumber,
+		scheduled: bool,
+		backlog: usize,
+	) -> bool {
+		if scheduled {
+			// Blocks can be built already, or the last order was served.
+			self.last_order = None;
+			return false
+		}
+
+		if self.pending.is_some() ||
+			self.last_order.map_or(false, |placed_at| number < placed_at + ORDER_TIMEOUT)
+		{
+			return false
+		}
+
+		backlog >= config.min_backlog
+	}
+
+	/// Whether an order at the given spot price is within the limits of the config.
+	fn can_afford(&self, config: &OnDemandOrderConfig, spot_price: Balance) -> bool {
+		if spot_price > config.max_spot_price {
+			log::debug!(
+				target: LOG_TARGET,
+				"Spot price {} is above the maximum of {}, not placing an on-demand order",
+				spot_price,
+				config.max_spot_price,
+			);
+			return false
+		}
+		if spot_price > self.max_amount(config) {
+			log::warn!(
+				target: LOG_TARGET,
+				"On-demand budget exhausted, spent {} of {} and the spot price is {}",
+				self.spent,
+				config.budget,
+				spot_price,
+			);
+			return false
+		}
+
+		true
+	}
+
+	/// The maximum amount an order may cost, so that the budget isn't exceeded even if the spot
+	/// price goes up before the order is included.
+	fn max_amount(&self, config: &OnDemandOrderConfig) -> Balance {
+		config.max_spot_price.min(config.budget.saturating_sub(self.spent))
+	}
+
+	fn on_order_placed(&mut self, number: BlockNumber, nonce: u32) {
+		self.last_order = Some(number);
+		self.pending = Some(PendingOrder { nonce, placed_at: number });
+	}
+
+	/// The pending order was included at the given spot price, or it didn't place any order.
+	fn on_order_settled(&mut self, spot_price: Option<Balance>) {
+		self.pending = None;
+		self.spent += spot_price.unwrap_or_default();
+	}
+}
+
+/// The transaction extensions of the relay chain runtimes the service knows how to fill in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TxExtension {
+	/// `CheckSpecVersion`, signing the spec version.
+	SpecVersion,
+	/// `CheckTxVersion`, signing the transaction version.
+	TxVersion,
+	/// `CheckGenesis`, signing the genesis hash.
+	Genesis,
+	/// `CheckMortality`, with the era and signing the hash of the block it is born at.
+	Mortality,
+	/// `CheckNonce`, with the nonce.
+	Nonce,
+	/// `ChargeTransactionPayment`, with no tip.
+	Payment,
+	/// `CheckMetadataHash`, in disabled mode and signing no metadata hash.
+	MetadataHash,
+}
+
+impl TxExtension {
+	fn from_identifier(identifier: &str) -> Option<Self> {
+		Some(match identifier {
+			"CheckSpecVersion" => Self::SpecVersion,
+			"CheckTxVersion" => Self::TxVersion,
+			"CheckGenesis" => Self::Genesis,
+			"CheckMortality" => Self::Mortality,
+			"CheckNonce" => Self::Nonce,
+			"ChargeTransactionPayment" => Self::Payment,
+			"CheckMetadataHash" => Self::MetadataHash,
+			_ => return None,
+		})
+	}
+}
+
+/// What the transaction extensions are filled in with.
+struct TxParams {
+	nonce: u32,
+	era: Era,
+	spec_version: u32,
+	transaction_version: u32,
+	genesis_hash: PHash,
+	/// Hash of the block the era is born at.
+	checkpoint_hash: PHash,
+}
+
+/// The shape of a `place_order_keep_alive` extrinsic and of the events telling that an order was
+/// placed, as read from the relay chain metadata.
+#[derive(Debug, Clone, PartialEq)]
+struct PlaceOrderLayout {
+	/// Index of the on-demand pallet.
+	pallet_index: u8,
+	/// Index of `place_order_keep_alive` among the calls of the on-demand pallet.
+	call_index: u8,
+	/// The transaction extensions carrying data, in order.
+	///
+	/// Extensions carrying no data don't need to be filled in, so they are left out.
+	extensions: Vec<TxExtension>,
+	/// Index of the events of the on-demand pallet among the events of the runtime.
+	event_index: u8,
+	/// Index of `OnDemandOrderPlaced` among the events of the on-demand pallet.
+	order_placed_index: u8,
+	/// The type of the `System::Events` storage.
+	events_ty: u32,
+	/// The types of the relay chain runtime, to skip over the other events.
+	registry: PortableRegistry,
+}
+
+impl PlaceOrderLayout {
+	fn from_metadata(metadata: &RuntimeMetadataV15) -> Result<Self, String> {
+		let registry = &metadata.types;
+		let extrinsic = &metadata.extrinsic;
+		if extrinsic.version != EXTRINSIC_VERSION {
+			return Err(format!("Unsupported extrinsic version {}", extrinsic.version))
+		}
+		for (ty, expected) in [
+			(extrinsic.address_ty.id, "MultiAddress"),
+			(extrinsic.signature_ty.id, "MultiSignature"),
+		] {
+			let name = registry.resolve(ty).and_then(|ty| ty.path.segments.last());
+			if name.map_or(true, |name| name != expected) {
+				return Err(format!("Unsupported extrinsic type {name:?}, expected `{expected}`"))
+			}
+		}
+
+		let pallet = metadata
+			.pallets
+			.iter()
+			.find(|pallet| pallet.name == ON_DEMAND_PALLET)
+			.ok_or_else(|| format!("The relay chain has no `{ON_DEMAND_PALLET}` pallet"))?;
+		let call_index = pallet
+			.calls
+			.as_ref()
+			.and_then(|calls| match &registry.resolve(calls.ty.id)?.type_def {
+				TypeDef::Variant(calls) => calls
+					.variants
+					.iter()
+					.find(|call| call.name == PLACE_ORDER_KEEP_ALIVE)
+					.map(|call| call.index),
+				_ => None,
+			})
+			.ok_or_else(|| {
+				format!("The `{ON_DEMAND_PALLET}` pallet has no `{PLACE_ORDER_KEEP_ALIVE}` call")
+			})?;
+
+		let event_index = registry
+			.resolve(metadata.outer_enums.event_enum_ty.id)
+			.and_then(|events| match &events.type_def {
+				TypeDef::Variant(events) => events
+					.variants
+					.iter()
+					.find(|events| events.name == ON_DEMAND_PALLET)
+					.map(|events| events.index),
+				_ => None,
+			})
+			.ok_or_else(|| format!("The `{ON_DEMAND_PALLET}` pallet emits no events"))?;
+		let order_placed_index = pallet
+			.event
+			.as_ref()
+			.and_then(|events| match &registry.resolve(events.ty.id)?.type_def {
+				TypeDef::Variant(events) => events
+					.variants
+					.iter()
+					.find(|event| {
+						event.name == ORDER_PLACED &&
+							event.fields.iter().map(|field| field.name.as_deref()).eq([
+								Some("para_id"),
+								Some("spot_price"),
+								Some("ordered_by"),
+							])
+					})
+					.map(|event| event.index),
+				_ => None,
+			})
+			.ok_or_else(|| {
+				format!("The `{ON_DEMAND_PALLET}` pallet has no `{ORDER_PLACED}` event")
+			})?;
+		let events_ty = metadata
+			.pallets
+			.iter()
+			.find(|pallet| pallet.name == "System")
+			.and_then(|pallet| pallet.storage.as_ref())
+			.and_then(|storage| storage.entries.iter().find(|entry| entry.name == "Events"))
+			.and_then(|entry| match &entry.ty {
+				StorageEntryType::Plain(ty) => Some(ty.id),
+				_ => None,
+			})
+			.ok_or_else(|| "The relay chain has no `System::Events` storage".to_string())?;
+
+		let extensions = extrinsic
+			.signed_extensions
+			.iter()
+			.filter_map(|extension| match TxExtension::from_identifier(&extension.identifier) {
+				Some(known) => Some(Ok(known)),
+				None if is_empty_type(registry, extension.ty.id) &&
+					is_empty_type(registry, extension.additional_signed.id) =>
+					None,
+				None => Some(Err(format!(
+					"Unsupported transaction extension `{}`",
+					extension.identifier
+				))),
+			})
+			.collect::<Result<_, _>>()?;
+
+		Ok(Self {
+			pallet_index: pallet.index,
+			call_index,
+			extensions,
+			event_index,
+			order_placed_index,
+			events_ty,
+			registry: registry.clone(),
+		})
+	}
+
+	/// The orders placed according to the encoded `System::Events` of a block, as the para they
+	/// were placed for, the spot price paid and the account that paid it.
+	fn orders_placed(
+		&self,
+		mut input: &[u8],
+	) -> Result<Vec<(ParaId, Balance, AccountId32)>, String> {
+		let type_def = |id| {
+			self.registry
+				.resolve(id)
+				.map(|ty| &ty.type_def)
+				.ok_or_else(|| format!("Unknown type {id}"))
+		};
+		let TypeDef::Sequence(records) = type_def(self.events_ty)? else {
+			return Err("`System::Events` is not a sequence".to_string())
+		};
+		let TypeDef::Composite(record) = type_def(records.type_param.id)? else {
+			return Err("The event records are not structs".to_string())
+		};
+
+		let count = Compact::<u32>::decode(&mut input).map_err(|err| err.to_string())?.0;
+		let mut orders = Vec::new();
+		for _ in 0..count {
+			for field in &record.fields {
+				if field.name.as_deref() == Some("event") &&
+					input.starts_with(&[self.event_index, self.order_placed_index])
+				{
+					input = &input[2..];
+					orders.push(Decode::decode(&mut input).map_err(|err| err.to_string())?);
+				} else {
+					skip_value(&self.registry, field.ty.id, &mut input)?;
+				}
+			}
+		}
+
+		Ok(orders)
+	}
+
+	/// Builds a signed `place_order_keep_alive` extrinsic.
+	fn signed_extrinsic(
+		&self,
+		account: &sr25519::Pair,
+		params: &TxParams,
+		max_amount: Balance,
+		para_id: ParaId,
+	) -> Vec<u8> {
+		let mut call = vec![self.pallet_index, self.call_index];
+		(max_amount, para_id).encode_to(&mut call);
+
+		let mut explicit = Vec::new();
+		let mut implicit = Vec::new();
+		for extension in &self.extensions {
+			match extension {
+				TxExtension::SpecVersion => params.spec_version.encode_to(&mut implicit),
+				TxExtension::TxVersion => params.transaction_version.encode_to(&mut implicit),
+				TxExtension::Genesis => params.genesis_hash.encode_to(&mut implicit),
+				TxExtension::Mortality => {
+					params.era.encode_to(&mut explicit);
+					params.checkpoint_hash.encode_to(&mut implicit);
+				},
+				TxExtension::Nonce => Compact(params.nonce).encode_to(&mut explicit),
+				TxExtension::Payment => Compact::<Balance>(0).encode_to(&mut explicit),
+				TxExtension::MetadataHash => {
+					0u8.encode_to(&mut explicit);
+					None::<[u8; 32]>.encode_to(&mut implicit);
+				},
+			}
+		}
+
+		let payload = [&call[..], &explicit[..], &implicit[..]].concat();
+		let signature = if payload.len() > MAX_UNHASHED_PAYLOAD_LEN {
+			account.sign(&blake2_256(&payload))
+		} else {
+			account.sign(&payload)
+		};
+
+		let mut extrinsic = vec![SIGNED_EXTRINSIC];
+		MultiAddress::<AccountId32, ()>::Id(account.public().into()).encode_to(&mut extrinsic);
+		MultiSignature::Sr25519(signature).encode_to(&mut extrinsic);
+		extrinsic.extend(explicit);
+		extrinsic.extend(call);
+
+		extrinsic.encode()
+	}
+}
+
+/// Whether values of the type don't take any space when encoded.
+fn is_empty_type(registry: &PortableRegistry, id: u32) -> bool {
+	let Some(ty) = registry.resolve(id) else { return false };
+	match &ty.type_def {
+		TypeDef::Composite(composite) =>
+			composite.fields.iter().all(|field| is_empty_type(registry, field.ty.id)),
+		TypeDef::Tuple(tuple) => tuple.fields.iter().all(|field| is_empty_type(registry, field.id)),
+		TypeDef::Array(array) => array.len == 0 || is_empty_type(registry, array.type_param.id),
+		_ => false,
+	}
+}
+
+/// Skips over a value of the type with the given id.
+fn skip_value(registry: &PortableRegistry, id: u32, input: &mut &[u8]) -> Result<(), String> {
+	fn advance(input: &mut &[u8], len: usize) -> Result<(), String> {
+		*input = input.get(len..).ok_or_else(|| "Unexpected end of input".to_string())?;
+		Ok(())
+	}
+	fn decode_len(input: &mut &[u8]) -> Result<usize, String> {
+		Ok(Compact::<u32>::decode(input).map_err(|err| err.to_string())?.0 as usize)
+	}
+
+	let ty = registry.resolve(id).ok_or_else(|| format!("Unknown type {id}"))?;
+	match &ty.type_def {
+		TypeDef::Composite(composite) =>
+			for field in &composite.fields {
+				skip_value(registry, field.ty.id, input)?;
+			},
+		TypeDef::Variant(variants) => {
+			let index = u8::decode(input).map_err(|err| err.to_string())?;
+			let variant = variants
+				.variants
+				.iter()
+				.find(|variant| variant.index == index)
+				.ok_or_else(|| format!("Unknown variant {index} of type {id}"))?;
+			for field in &variant.fields {
+				skip_value(registry, field.ty.id, input)?;
+			}
+		},
+		TypeDef::Sequence(sequence) =>
+			for _ in 0..decode_len(input)? {
+				skip_value(registry, sequence.type_param.id, input)?;
+			},
+		TypeDef::Array(array) =>
+			for _ in 0..array.len {
+				skip_value(registry, array.type_param.id, input)?;
+			},
+		TypeDef::Tuple(tuple) =>
+			for field in &tuple.fields {
+				skip_value(registry, field.id, input)?;
+			},
+		TypeDef::Primitive(primitive) => {
+			let len = match primitive {
+				TypeDefPrimitive::Bool | TypeDefPrimitive::U8 | TypeDefPrimitive::I8 => 1,
+				TypeDefPrimitive::U16 | TypeDefPrimitive::I16 => 2,
+				TypeDefPrimitive::Char | TypeDefPrimitive::U32 | TypeDefPrimitive::I32 => 4,
+				TypeDefPrimitive::U64 | TypeDefPrimitive::I64 => 8,
+				TypeDefPrimitive::U128 | TypeDefPrimitive::I128 => 16,
+				TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => 32,
+				TypeDefPrimitive::Str => decode_len(input)?,
+			};
+			advance(input, len)?;
+		},
+		TypeDef::Compact(_) => {
+			Compact::<u128>::decode(input).map_err(|err| err.to_string())?;
+		},
+		TypeDef::BitSequence(bits) => {
+			let store_len = match registry.resolve(bits.bit_store_type.id).map(|ty| &ty.type_def) {
+				Some(TypeDef::Primitive(TypeDefPrimitive::U8)) => 1,
+				Some(TypeDef::Primitive(TypeDefPrimitive::U16)) => 2,
+				Some(TypeDef::Primitive(TypeDefPrimitive::U32)) => 4,
+				Some(TypeDef::Primitive(TypeDefPrimitive::U64)) => 8,
+				_ => return Err(format!("Unsupported bit store of type {id}")),
+			};
+			let bits = decode_len(input)?;
+			advance(input, bits.div_ceil(store_len * 8) * store_len)?;
+		},
+	}
+
+	Ok(())
+}
+
+struct OnDemandOrderService<Pool, Client, RCInterface> {
+	config: OnDemandOrderConfig,
+	transaction_pool: Arc<Pool>,
+	client: Arc<Client>,
+	relay_chain_interface: RCInterface,
+	genesis_hash: Option<PHash>,
+	/// The layout of the extrinsic, along with the spec version it was read at.
+	layout: Option<(u32, Arc<PlaceOrderLayout>)>,
+	state: OrderState,
+}
+
+impl<Pool, Client, RCInterface> OnDemandOrderService<Pool, Client, RCInterface>
+where
+	Pool: TransactionPool,
+	Client: AuxStore,
+	RCInterface: RelayChainInterface,
+{
+	async fn run(mut self) {
+		let startup_check = async {
+			let best_hash = self.relay_chain_interface.best_block_hash().await?;
+			let version = self.relay_chain_interface.version(best_hash).await?;
+			self.layout(best_hash, version.spec_version).await
+		};
+		if let Err(err) = startup_check.await {
+			log::error!(
+				target: LOG_TARGET,
+				"The relay chain doesn't support on-demand orders, none will be placed: {}",
+				err,
+			);
+			return
+		}
+
+		let mut best_heads = match self.relay_chain_interface.new_best_notification_stream().await {
+			Ok(best_heads) => best_heads,
+			Err(err) => {
+				log::error!(
+					target: LOG_TARGET,
+					"Unable to follow the relay chain, no on-demand orders will be placed: {}",
+					err,
+				);
+				return
+			},
+		};
+
+		while let Some(header) = best_heads.next().await {
+			if let Err(err) = self.on_new_best(&header).await {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to place an on-demand order at relay parent {:?}: {}",
+					header.hash(),
+					err,
+				);
+			}
+		}
+	}
+
+	async fn on_new_best(&mut self, header: &PHeader) -> RelayChainResult<()> {
+		let relay_parent = header.hash();
+		let para_id = self.config.para_id;
+		let account = AccountId32::from(self.config.account.public());
+		let nonce = self.account_nonce(relay_parent, &account).await?;
+		self.settle_pending_order(header, nonce).await?;
+
+		let claim_queue = self.relay_chain_interface.claim_queue(relay_parent).await?;
+		let scheduled = claim_queue.values().any(|paras| paras.contains(&para_id));
+		let backlog = self.transaction_pool.status().ready;
+		if !self.state.wants_order(&self.config, header.number, scheduled, backlog) {
+			return Ok(())
+		}
+
+		let spot_price = self.spot_price(relay_parent).await?;
+		if !self.state.can_afford(&self.config, spot_price) {
+			return Ok(())
+		}
+
+		// The order is rejected if the spot price goes above the maximum amount before it is
+		// included, so it never costs more than what the limits allow.
+		let max_amount = self.state.max_amount(&self.config);
+		let extrinsic = self.place_order_extrinsic(header, nonce, max_amount).await?;
+		self.relay_chain_interface.submit_extrinsic(extrinsic).await?;
+
+		self.state.on_order_placed(header.number, nonce);
+		log::info!(
+			target: LOG_TARGET,
+			"Placed an on-demand order for at most {} at relay parent {:?} with {} ready \
+			 transactions, the spot price is {}",
+			max_amount,
+			relay_parent,
+			backlog,
+			spot_price,
+		);
+
+		Ok(())
+	}
+
+	/// Accounts for the pending order once the nonce of the account tells that it was included,
+	/// or forgets about it once it expired.
+	async fn settle_pending_order(&mut self, header: &PHeader, nonce: u32) -> RelayChainResult<()> {
+		let Some(pending) = self.state.pending else { return Ok(()) };
+
+		if nonce <= pending.nonce {
+			if header.number >= pending.placed_at + ORDER_MORTALITY {
+				log::debug!(
+					target: LOG_TARGET,
+					"The on-demand order placed at relay block #{} expired",
+					pending.placed_at,
+				);
+				self.state.on_order_settled(None);
+			}
+			return Ok(())
+		}
+
+		let spot_price = self.placed_order_price(header, pending).await?;
+		self.state.on_order_settled(spot_price);
+		let Some(spot_price) = spot_price else {
+			log::warn!(
+				target: LOG_TARGET,
+				"The on-demand order placed at relay block #{} was included, but no order was \
+				 placed",
+				pending.placed_at,
+			);
+			return Ok(())
+		};
+
+		if let Err(err) =
+			store_spent(&*self.client, &self.config.account.public(), self.state.spent)
+		{
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to store the amount spent on orders, it will be lost on restart: {}",
+				err,
+			);
+		}
+		log::info!(
+			target: LOG_TARGET,
+			"The on-demand order placed at relay block #{} was placed for {}, spent {} of {}",
+			pending.placed_at,
+			spot_price,
+			self.state.spent,
+			self.config.budget,
+		);
+
+		Ok(())
+	}
+
+	/// The spot price paid for the pending order, according to the events of the blocks since it
+	/// was placed, up to the given header.
+	async fn placed_order_price(
+		&mut self,
+		header: &PHeader,
+		pending: PendingOrder,
+	) -> RelayChainResult<Option<Balance>> {
+		let version = self.relay_chain_interface.version(header.hash()).await?;
+		let layout = self.layout(header.hash(), version.spec_version).await?;
+		let account = AccountId32::from(self.config.account.public());
+		let events_key = [twox_128(b"System"), twox_128(b"Events")].concat();
+
+		let mut block = header.clone();
+		while block.number > pending.placed_at {
+			let events = self
+				.relay_chain_interface
+				.get_storage_by_key(block.hash(), &events_key)
+				.await?
+				.unwrap_or_default();
+			let orders = layout.orders_placed(&events).map_err(RelayChainError::GenericError)?;
+			let spot_price = orders.into_iter().find_map(|(para_id, spot_price, ordered_by)| {
+				(para_id == self.config.para_id && ordered_by == account).then_some(spot_price)
+			});
+			if spot_price.is_some() {
+				return Ok(spot_price)
+			}
+
+			block = self
+				.relay_chain_interface
+				.header(BlockId::Hash(block.parent_hash))
+				.await?
+				.ok_or_else(|| {
+					RelayChainError::GenericError(format!(
+						"Relay chain header {:?} is missing",
+						block.parent_hash
+					))
+				})?;
+		}
+
+		Ok(None)
+	}
+
+	/// The price of an order, as calculated by the on-demand pallet.
+	async fn spot_price(&self, relay_parent: PHash) -> RelayChainResult<Balance> {
+		let queue_status_key =
+			[twox_128(ON_DEMAND_PALLET.as_bytes()), twox_128(b"QueueStatus")].concat();
+		// The traffic is the first field of the queue status.
+		let traffic = self
+			.relay_chain_interface
+			.get_storage_by_key(relay_parent, &queue_status_key)
+			.await?
+			.ok_or_else(|| {
+				RelayChainError::GenericError(
+					"The relay chain has no on-demand queue status".to_string(),
+				)
+			})
+			.and_then(|raw| {
+				FixedU128::decode(&mut &raw[..]).map_err(RelayChainError::DeserializationError)
+			})?;
+
+		let host_config = self
+			.relay_chain_interface
+			.get_storage_by_key(relay_parent, well_known_keys::ACTIVE_CONFIG)
+			.await?
+			.ok_or_else(|| {
+				RelayChainError::GenericError("The relay chain has no active config".to_string())
+			})
+			.and_then(|raw| {
+				HostConfiguration::<BlockNumber>::decode(&mut &raw[..])
+					.map_err(RelayChainError::DeserializationError)
+			})?;
+
+		Ok(traffic.saturating_mul_int(host_config.scheduler_params.on_demand_base_fee))
+	}
+
+	/// Builds a signed `place_order_keep_alive` extrinsic, valid for [`ORDER_MORTALITY`] blocks
+	/// after the relay parent.
+	async fn place_order_extrinsic(
+		&mut self,
+		relay_parent: &PHeader,
+		nonce: u32,
+		max_amount: Balance,
+	) -> RelayChainResult<Vec<u8>> {
+		let relay_parent_hash = relay_parent.hash();
+		let genesis_hash = self.genesis_hash().await?;
+		let version = self.relay_chain_interface.version(relay_parent_hash).await?;
+		let layout = self.layout(relay_parent_hash, version.spec_version).await?;
+
+		// The era is born at the relay parent, as the period is too short for its birth to be
+		// quantized.
+		let params = TxParams {
+			nonce,
+			era: Era::mortal(ORDER_MORTALITY.into(), relay_parent.number.into()),
+			spec_version: version.spec_version,
+			transaction_version: version.transaction_version,
+			genesis_hash,
+			checkpoint_hash: relay_parent_hash,
+		};
+
+		Ok(layout.signed_extrinsic(&self.config.account, &params, max_amount, self.config.para_id))
+	}
+
+	/// The layout of the extrinsic, read from the metadata again whenever the spec version of the
+	/// relay chain runtime changes.
+	async fn layout(
+		&mut self,
+		relay_parent: PHash,
+		spec_version: u32,
+	) -> RelayChainResult<Arc<PlaceOrderLayout>> {
+		if let Some((layout_version, layout)) = &self.layout {
+			if *layout_version == spec_version {
+				return Ok(layout.clone())
+			}
+		}
+
+		let metadata = call_runtime_api::<Option<Vec<u8>>>(
+			&self.relay_chain_interface,
+			"Metadata_metadata_at_version",
+			relay_parent,
+			METADATA_VERSION,
+		)
+		.await?
+		.ok_or_else(|| {
+			RelayChainError::GenericError(format!(
+				"The relay chain doesn't provide metadata version {METADATA_VERSION}"
+			))
+		})?;
+		let RuntimeMetadata::V15(metadata) = RuntimeMetadataPrefixed::decode(&mut &metadata[..])?.1
+		else {
+			return Err(RelayChainError::GenericError(format!(
+				"The relay chain provided metadata other than version {METADATA_VERSION}"
+			)))
+		};
+
+		let layout = Arc::new(
+			PlaceOrderLayout::from_metadata(&metadata).map_err(RelayChainError::GenericError)?,
+		);
+		self.layout = Some((spec_version, layout.clone()));
+
+		Ok(layout)
+	}
+
+	async fn account_nonce(
+		&self,
+		relay_parent: PHash,
+		account: &AccountId32,
+	) -> RelayChainResult<u32> {
+		let account_key = [
+			&twox_128(b"System")[..],
+			&twox_128(b"Account")[..],
+			&blake2_128(account.as_ref())[..],
+			account.as_ref(),
+		]
+		.concat();
+
+		// The nonce is the first field of the account info.
+		self.relay_chain_interface
+			.get_storage_by_key(relay_parent, &account_key)
+			.await?
+			.ok_or_else(|| {
+				RelayChainError::GenericError(format!(
+					"Account {account} doesn't exist on the relay chain"
+				))
+			})
+			.and_then(|raw| {
+				u32::decode(&mut &raw[..]).map_err(RelayChainError::DeserializationError)
+			})
+	}
+
+	async fn genesis_hash(&mut self) -> RelayChainResult<PHash> {
+		if let Some(genesis_hash) = self.genesis_hash {
+			return Ok(genesis_hash)
+		}
+
+		let genesis_hash = self
+			.relay_chain_interface
+			.header(BlockId::Number(0))
+			.await?
+			.ok_or_else(|| {
+				RelayChainError::GenericError("Relay chain genesis header is missing".to_string())
+			})?
+			.hash();
+		self.genesis_hash = Some(genesis_hash);
+
+		Ok(genesis_hash)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_runtime_parachains::on_demand;
+	use sp_runtime::{generic::ExtrinsicFormat, traits::Checkable};
+	use std::{collections::HashMap, sync::Mutex};
+	use westend_runtime::{Runtime, RuntimeCall, RuntimeEvent, UncheckedExtrinsic};
+
+	#[derive(Default)]
+	struct TestAuxStore(Mutex<HashMap<Vec<u8>, Vec<u8>>>);
+
+	impl AuxStore for TestAuxStore {
+		fn insert_aux<
+			'a,
+			'b: 'a,
+			'c: 'a,
+			I: IntoIterator<Item = &'a (&'c [u8], &'c [u8])>,
+			D: IntoIterator<Item = &'a &'b [u8]>,
+		>(
+			&self,
+			insert: I,
+			delete: D,
+		) -> sp_blockchain::Result<()> {
+			let mut aux = self.0.lock().unwrap();
+			for (key, value) in insert {
+				aux.insert(key.to_vec(), value.to_vec());
+			}
+			for key in delete {
+				aux.remove(*key);
+			}
+			Ok(())
+		}
+
+		fn get_aux(&self, key: &[u8]) -> sp_blockchain::Result<Option<Vec<u8>>> {
+			Ok(self.0.lock().unwrap().get(key).cloned())
+		}
+	}
+
+	fn config() -> OnDemandOrderConfig {
+		OnDemandOrderConfig {
+			para_id: ParaId::from(2000),
+			account: sr25519::Pair::from_string("//Alice", None).unwrap(),
+			budget: 1_000,
+			max_spot_price: 300,
+			min_backlog: 2,
+		}
+	}
+
+	fn westend_layout() -> PlaceOrderLayout {
+		let metadata = Runtime::metadata_at_version(METADATA_VERSION).unwrap();
+		let RuntimeMetadata::V15(metadata) =
+			RuntimeMetadataPrefixed::decode(&mut &metadata[..]).unwrap().1
+		else {
+			panic!("Metadata version {METADATA_VERSION} was requested")
+		};
+
+		PlaceOrderLayout::from_metadata(&metadata).unwrap()
+	}
+
+	#[test]
+	fn orders_are_placed_when_not_scheduled_and_with_a_backlog() {
+		let config = config();
+		let mut state = OrderState { spent: 0, last_order: None, pending: None };
+
+		assert!(!state.wants_order(&config, 10, false, 1));
+		assert!(!state.wants_order(&config, 10, true, 2));
+		assert!(state.wants_order(&config, 10, false, 2));
+
+		// The order has some time to show up in the claim queue.
+		state.on_order_placed(10, 0);
+		state.on_order_settled(Some(100));
+		assert!(!state.wants_order(&config, 10 + ORDER_TIMEOUT - 1, false, 2));
+		assert!(state.wants_order(&config, 10 + ORDER_TIMEOUT, false, 2));
+
+		// Once served, the next order doesn't wait for the last one.
+		state.on_order_placed(30, 1);
+		state.on_order_settled(Some(100));
+		assert!(!state.wants_order(&config, 31, true, 2));
+		assert!(state.wants_order(&config, 32, false, 2));
+		assert_eq!(state.spent, 200);
+	}
+
+	#[test]
+	fn orders_are_accounted_for_once_seen_on_chain() {
+		let config = config();
+		let mut state = OrderState { spent: 0, last_order: None, pending: None };
+
+		state.on_order_placed(10, 0);
+		assert_eq!(state.pending, Some(PendingOrder { nonce: 0, placed_at: 10 }));
+		assert_eq!(state.spent, 0);
+
+		// No order reuses the nonce of a pending one, even once the last one timed out.
+		assert!(!state.wants_order(&config, 10 + ORDER_TIMEOUT, false, 2));
+		assert!(!state.wants_order(&config, 11, true, 2));
+
+		// Orders which didn't make it on chain aren't accounted for.
+		state.on_order_settled(None);
+		assert_eq!(state.spent, 0);
+		assert!(state.wants_order(&config, 12, false, 2));
+
+		state.on_order_placed(12, 1);
+		state.on_order_settled(Some(120));
+		assert_eq!((state.spent, state.pending), (120, None));
+	}
+
+	#[test]
+	fn orders_stay_within_the_spot_price_and_the_budget() {
+		let config = config();
+		let mut state = OrderState { spent: 0, last_order: None, pending: None };
+
+		assert!(state.can_afford(&config, 300));
+		assert!(!state.can_afford(&config, 301));
+		assert_eq!(state.max_amount(&config), 300);
+
+		state.spent = 800;
+		assert!(state.can_afford(&config, 200));
+		assert!(!state.can_afford(&config, 201));
+		assert_eq!(state.max_amount(&config), 200);
+
+		state.spent = 1_000;
+		assert_eq!(state.max_amount(&config), 0);
+	}
+
+	#[test]
+	fn spent_is_kept_per_account() {
+		let store = TestAuxStore::default();
+		let alice = sr25519::Pair::from_string("//Alice", None).unwrap().public();
+		let bob = sr25519::Pair::from_string("//Bob", None).unwrap().public();
+
+		assert_eq!(load_spent(&store, &alice).unwrap(), 0);
+		store_spent(&store, &alice, 500).unwrap();
+		assert_eq!(load_spent(&store, &alice).unwrap(), 500);
+		assert_eq!(load_spent(&store, &bob).unwrap(), 0);
+	}
+
+	#[test]
+	fn layout_is_read_from_the_metadata() {
+		let layout = westend_layout();
+
+		let call =
+			RuntimeCall::OnDemandAssignmentProvider(on_demand::Call::place_order_keep_alive {
+				max_amount: 1,
+				para_id: 2000.into(),
+			})
+			.encode();
+		assert_eq!((layout.pallet_index, layout.call_index), (call[0], call[1]));
+		assert_eq!(
+			layout.extensions,
+			vec![
+				TxExtension::SpecVersion,
+				TxExtension::TxVersion,
+				TxExtension::Genesis,
+				TxExtension::Mortality,
+				TxExtension::Nonce,
+				TxExtension::Payment,
+				TxExtension::MetadataHash,
+			],
+		);
+	}
+
+	#[test]
+	fn orders_placed_are_read_from_the_events() {
+		let config = config();
+		let account = AccountId32::from(config.account.public());
+		let record = |phase, event| frame_system::EventRecord::<RuntimeEvent, PHash> {
+			phase,
+			event,
+			topics: vec![PHash::repeat_byte(3)],
+		};
+		let events = vec![
+			record(
+				frame_system::Phase::Initialization,
+				RuntimeEvent::System(frame_system::Event::NewAccount { account: account.clone() }),
+			),
+			record(
+				frame_system::Phase::ApplyExtrinsic(1),
+				RuntimeEvent::OnDemandAssignmentProvider(on_demand::Event::OnDemandOrderPlaced {
+					para_id: config.para_id,
+					spot_price: 250,
+					ordered_by: account.clone(),
+				}),
+			),
+			record(
+				frame_system::Phase::ApplyExtrinsic(1),
+				RuntimeEvent::System(frame_system::Event::ExtrinsicSuccess {
+					dispatch_info: Default::default(),
+				}),
+			),
+			record(
+				frame_system::Phase::Finalization,
+				RuntimeEvent::OnDemandAssignmentProvider(on_demand::Event::SpotPriceSet {
+					spot_price: 300,
+				}),
+			),
+		];
+
+		assert_eq!(
+			westend_layout().orders_placed(&events.encode()).unwrap(),
+			vec![(config.para_id, 250, account)],
+		);
+		assert!(westend_layout().orders_placed(&events.encode()[..40]).is_err());
+	}
+
+	#[test]
+	fn place_order_extrinsic_is_accepted_by_the_runtime() {
+		let config = config();
+		let relay_parent_number: BlockNumber = 100;
+		let genesis_hash = PHash::repeat_byte(1);
+		let relay_parent_hash = PHash::repeat_byte(2);
+		let params = TxParams {
+			nonce: 3,
+			era: Era::mortal(ORDER_MORTALITY.into(), relay_parent_number.into()),
+			spec_version: westend_runtime::VERSION.spec_version,
+			transaction_version: westend_runtime::VERSION.transaction_version,
+			genesis_hash,
+			checkpoint_hash: relay_parent_hash,
+		};
+
+		let extrinsic =
+			westend_layout().signed_extrinsic(&config.account, &params, 250, config.para_id);
+		let extrinsic = UncheckedExtrinsic::decode(&mut &extrinsic[..]).unwrap();
+		assert_eq!(
+			extrinsic.function,
+			RuntimeCall::OnDemandAssignmentProvider(on_demand::Call::place_order_keep_alive {
+				max_amount: 250,
+				para_id: config.para_id,
+			}),
+		);
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			frame_system::Pallet::<Runtime>::set_block_number(relay_parent_number + 1);
+			frame_system::BlockHash::<Runtime>::insert(0, genesis_hash);
+			frame_system::BlockHash::<Runtime>::insert(relay_parent_number, relay_parent_hash);
+
+			let checked = extrinsic
+				.check(&frame_system::ChainContext::<Runtime>::default())
+				.expect("The signature covers the implicit data of the runtime");
+			assert!(matches!(
+				checked.format,
+				ExtrinsicFormat::Signed(who, _) if who == AccountId32::from(config.account.public())
+			));
+		});
+	}
+}
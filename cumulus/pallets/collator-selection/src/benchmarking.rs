@@ -25,13 +25,20 @@ use alloc::vec::Vec;
 use codec::Decode;
 use core::cmp;
 use frame_benchmarking::{account, v2::*, whitelisted_caller, BenchmarkError};
-use frame_support::traits::{Currency, EnsureOrigin, Get, ReservableCurrency};
+use frame_support::traits::{
+	fungible::{Inspect, Mutate, MutateHold},
+	EnsureOrigin, Get,
+};
 use frame_system::{pallet_prelude::BlockNumberFor, EventRecord, RawOrigin};
 use pallet_authorship::EventHandler;
 use pallet_session::{self as session, SessionManager};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	Perbill,
+};
 
 pub type BalanceOf<T> =
-	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
 const SEED: u32 = 0;
 
@@ -50,7 +57,7 @@ fn create_funded_user<T: Config>(
 ) -> T::AccountId {
 	let user = account(string, n, SEED);
 	let balance = T::Currency::minimum_balance() * balance_factor.into();
-	let _ = T::Currency::make_free_balance_be(&user, balance);
+	let _ = T::Currency::set_balance(&user, balance);
 	user
 }
 
@@ -90,11 +97,26 @@ fn register_candidates<T: Config>(count: u32) {
 	assert!(CandidacyBond::<T>::get() > 0u32.into(), "Bond cannot be zero!");
 
 	for who in candidates {
-		T::Currency::make_free_balance_be(&who, CandidacyBond::<T>::get() * 3u32.into());
+		T::Currency::set_balance(&who, CandidacyBond::<T>::get() * 3u32.into());
 		<CollatorSelection<T>>::register_as_candidate(RawOrigin::Signed(who).into()).unwrap();
 	}
 }
 
+fn delegation_amount<T: Config>() -> BalanceOf<T> {
+	T::MinDelegation::get().max(T::Currency::minimum_balance())
+}
+
+fn register_delegators<T: Config>(candidate: &T::AccountId, count: u32) {
+	let delegators = (0..count).map(|d| account("delegator", d, SEED)).collect::<Vec<_>>();
+	let amount = delegation_amount::<T>();
+
+	for who in delegators {
+		T::Currency::set_balance(&who, amount * 3u32.into());
+		<CollatorSelection<T>>::delegate(RawOrigin::Signed(who).into(), candidate.clone(), amount)
+			.unwrap();
+	}
+}
+
 fn min_candidates<T: Config>() -> u32 {
 	let min_collators = T::MinEligibleCollators::get();
 	let invulnerable_length = Invulnerables::<T>::get().len();
@@ -161,13 +183,14 @@ mod benchmarks {
 		// ... and register them.
 		for (who, _) in candidates.iter() {
 			let deposit = CandidacyBond::<T>::get();
-			T::Currency::make_free_balance_be(who, deposit * 1000_u32.into());
+			T::Currency::set_balance(who, deposit * 1000_u32.into());
 			CandidateList::<T>::try_mutate(|list| {
-				list.try_push(CandidateInfo { who: who.clone(), deposit }).unwrap();
+				list.try_push(CandidateInfo { who: who.clone(), deposit, backing: Zero::zero() })
+					.unwrap();
 				Ok::<(), BenchmarkError>(())
 			})
 			.unwrap();
-			T::Currency::reserve(who, deposit)?;
+			T::Currency::hold(&HoldReason::CandidacyBond.into(), who, deposit)?;
 			LastAuthoredBlock::<T>::insert(
 				who.clone(),
 				frame_system::Pallet::<T>::block_number() + T::KickThreshold::get(),
@@ -292,7 +315,7 @@ mod benchmarks {
 
 		let caller: T::AccountId = whitelisted_caller();
 		let bond: BalanceOf<T> = T::Currency::minimum_balance() * 2u32.into();
-		T::Currency::make_free_balance_be(&caller, bond);
+		T::Currency::set_balance(&caller, bond);
 
 		<session::Pallet<T>>::set_keys(
 			RawOrigin::Signed(caller.clone()).into(),
@@ -319,7 +342,7 @@ mod benchmarks {
 
 		let caller: T::AccountId = whitelisted_caller();
 		let bond: BalanceOf<T> = T::Currency::minimum_balance() * 10u32.into();
-		T::Currency::make_free_balance_be(&caller, bond);
+		T::Currency::set_balance(&caller, bond);
 
 		<session::Pallet<T>>::set_keys(
 			RawOrigin::Signed(caller.clone()).into(),
@@ -357,26 +380,112 @@ mod benchmarks {
 		assert_last_event::<T>(Event::CandidateRemoved { account_id: leaving }.into());
 	}
 
-	// worse case is paying a non-existing candidate account.
+	// worst case is delegating to a candidate with all but one delegator slot taken.
+	#[benchmark]
+	fn delegate(c: Linear<1, { T::MaxCandidates::get() }>) {
+		CandidacyBond::<T>::put(T::Currency::minimum_balance());
+		DesiredCandidates::<T>::put(c);
+
+		register_validators::<T>(c);
+		register_candidates::<T>(c);
+
+		let candidate = CandidateList::<T>::get()[0].who.clone();
+		register_delegators::<T>(&candidate, T::MaxDelegatorsPerCandidate::get().saturating_sub(1));
+
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = delegation_amount::<T>() * 10u32.into();
+		T::Currency::set_balance(&caller, amount * 2u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), candidate.clone(), amount);
+
+		assert_last_event::<T>(
+			Event::Delegated { candidate: candidate.clone(), delegator: caller, amount }.into(),
+		);
+		assert!(CandidateList::<T>::get().iter().last().unwrap().who == candidate);
+	}
+
+	// worst case is the last delegator of a candidate with all delegator slots taken leaving.
+	#[benchmark]
+	fn undelegate(c: Linear<1, { T::MaxCandidates::get() }>) {
+		CandidacyBond::<T>::put(T::Currency::minimum_balance());
+		// no candidate occupies a top slot, so any delegation can be withdrawn.
+		DesiredCandidates::<T>::put(0);
+
+		register_validators::<T>(c);
+		register_candidates::<T>(c);
+
+		let candidate = CandidateList::<T>::get()[0].who.clone();
+		register_delegators::<T>(&candidate, T::MaxDelegatorsPerCandidate::get().saturating_sub(1));
+
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = delegation_amount::<T>() * 10u32.into();
+		T::Currency::set_balance(&caller, amount * 2u32.into());
+		<CollatorSelection<T>>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			candidate.clone(),
+			amount,
+		)
+		.unwrap();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), candidate.clone());
+
+		assert_last_event::<T>(Event::Undelegated { candidate, delegator: caller, amount }.into());
+	}
+
+	#[benchmark]
+	fn set_commission(c: Linear<1, { T::MaxCandidates::get() }>) {
+		CandidacyBond::<T>::put(T::Currency::minimum_balance());
+		DesiredCandidates::<T>::put(c);
+
+		register_validators::<T>(c);
+		register_candidates::<T>(c);
+
+		let caller = CandidateList::<T>::get().iter().last().unwrap().who.clone();
+		v2::whitelist!(caller);
+		// increasing the commission is the worst case, as it is scheduled.
+		let commission = Perbill::from_percent(10);
+		let at = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::CommissionIncreaseDelay::get());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), commission);
+
+		assert_last_event::<T>(
+			Event::CommissionIncreaseScheduled { account_id: caller, commission, at }.into(),
+		);
+	}
+
+	// worst case is paying an author backed by the maximum number of delegators.
 	#[benchmark]
-	fn note_author() {
+	fn note_author(d: Linear<0, { T::MaxDelegatorsPerCandidate::get() }>) {
 		CandidacyBond::<T>::put(T::Currency::minimum_balance());
-		T::Currency::make_free_balance_be(
+		T::Currency::set_balance(
 			&<CollatorSelection<T>>::account_id(),
-			T::Currency::minimum_balance() * 4u32.into(),
+			T::Currency::minimum_balance() * 1_000_000u32.into(),
 		);
-		let author = account("author", 0, SEED);
+
+		// the author is looked up in a full candidate list.
+		register_validators::<T>(T::MaxCandidates::get());
+		register_candidates::<T>(T::MaxCandidates::get());
+
+		let author = CandidateList::<T>::get()[0].who.clone();
+		register_delegators::<T>(&author, d);
+		Commission::<T>::insert(&author, Perbill::from_percent(10));
+		// a commission increase which is in effect is looked up too.
+		PendingCommission::<T>::insert(&author, (Zero::zero(), Perbill::from_percent(20)));
 		let new_block: BlockNumberFor<T> = 10u32.into();
 
 		frame_system::Pallet::<T>::set_block_number(new_block);
-		assert!(T::Currency::free_balance(&author) == 0u32.into());
+		let balance_before = T::Currency::balance(&author);
 
 		#[block]
 		{
 			<CollatorSelection<T> as EventHandler<_, _>>::note_author(author.clone())
 		}
 
-		assert!(T::Currency::free_balance(&author) > 0u32.into());
+		assert!(T::Currency::balance(&author) > balance_before);
 		assert_eq!(frame_system::Pallet::<T>::block_number(), new_block);
 	}
 
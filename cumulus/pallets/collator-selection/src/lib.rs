@@ -26,7 +26,8 @@
 //! ## Terminology
 //!
 //! - Collator: A parachain block producer.
-//! - Bond: An amount of `Balance` _reserved_ for candidate registration.
+//! - Bond: An amount of `Balance` _held_ for candidate registration.
+//! - Delegation: An amount of `Balance` _held_ from a third party to back a candidate.
 //! - Invulnerable: An account guaranteed to be in the collator set.
 //!
 //! ## Implementation
@@ -57,13 +58,19 @@
 //! `update_bond`, but candidates who are on top slots and try to decrease their deposits will fail
 //! in order to enforce auction mechanics and have meaningful bids.
 //!
+//! Candidates can also be backed by other accounts through `delegate`. The delegations backing a
+//! candidate add up to its own deposit, and candidates are ranked in the auction by this total
+//! backing. Delegations can be withdrawn through `undelegate`, following the same rules as lowering
+//! a deposit through `update_bond`, or at any time while an increase of the commission of the
+//! candidate is pending.
+//!
 //! Candidates will not be allowed to get kicked or `leave_intent` if the total number of collators
 //! would fall below `MinEligibleCollators`. This is to ensure that some collators will always
 //! exist, i.e. someone is eligible to produce a block.
 //!
-//! When a new session starts, candidates with the highest deposits will be selected in order until
-//! the desired number of collators is reached. Candidates can increase or decrease their deposits
-//! between sessions in order to ensure they receive a slot in the collator list.
+//! When a new session starts, candidates with the highest total backing will be selected in order
+//! until the desired number of collators is reached. Candidates can increase or decrease their
+//! deposits between sessions in order to ensure they receive a slot in the collator list.
 //!
 //! ### Rewards
 //!
@@ -74,6 +81,11 @@
 //! - Half the value of the transaction fees within the block. The other half of the transaction
 //!   fees are deposited into the Pot.
 //!
+//! If the author is a candidate backed by delegations, it first keeps its commission (see
+//! `set_commission`, increases only apply after `CommissionIncreaseDelay`) out of its reward. The
+//! rest of the reward is split pro rata between the candidate's own deposit and the delegations
+//! backing it.
+//!
 //! To initiate rewards, an ED needs to be transferred to the pot address.
 //!
 //! Note: Eventually the Pot distribution may be modified as discussed in [this
@@ -109,24 +121,25 @@ pub mod pallet {
 		dispatch::{DispatchClass, DispatchResultWithPostInfo},
 		pallet_prelude::*,
 		traits::{
-			Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, ReservableCurrency,
-			ValidatorRegistration,
+			fungible::{Inspect, Mutate, MutateHold},
+			tokens::{Precision, Preservation},
+			EnsureOrigin, ValidatorRegistration,
 		},
 		BoundedVec, DefaultNoBound, PalletId,
 	};
 	use frame_system::{pallet_prelude::*, Config as SystemConfig};
 	use pallet_session::SessionManager;
 	use sp_runtime::{
-		traits::{AccountIdConversion, CheckedSub, Convert, Saturating, Zero},
-		RuntimeDebug,
+		traits::{AccountIdConversion, Convert, Saturating, Zero},
+		Perbill, RuntimeDebug,
 	};
 	use sp_staking::SessionIndex;
 
 	/// The in-code storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 	type BalanceOf<T> =
-		<<T as Config>::Currency as Currency<<T as SystemConfig>::AccountId>>::Balance;
+		<<T as Config>::Currency as Inspect<<T as SystemConfig>::AccountId>>::Balance;
 
 	/// A convertor from collators id. Since this pallet does not have stash/controller, this is
 	/// just identity.
@@ -143,8 +156,12 @@ pub mod pallet {
 		/// Overarching event type.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-		/// The currency mechanism.
-		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The currency mechanism, holding the candidacy bonds and delegations.
+		type Currency: Mutate<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
 
 		/// Origin that can dictate updating parameters of this pallet.
 		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
@@ -169,6 +186,21 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxInvulnerables: Get<u32>;
 
+		/// Minimum amount an account has to delegate to a candidate.
+		#[pallet::constant]
+		type MinDelegation: Get<BalanceOf<Self>>;
+
+		/// Maximum number of delegators backing a single candidate.
+		#[pallet::constant]
+		type MaxDelegatorsPerCandidate: Get<u32>;
+
+		/// Number of blocks after which an increase of the commission of a candidate takes effect.
+		///
+		/// Meanwhile, the delegators backing the candidate can withdraw their delegations even if
+		/// it occupies a top-`DesiredCandidates` slot.
+		#[pallet::constant]
+		type CommissionIncreaseDelay: Get<BlockNumberFor<Self>>;
+
 		// Will be kicked if block is not produced in threshold.
 		#[pallet::constant]
 		type KickThreshold: Get<BlockNumberFor<Self>>;
@@ -196,6 +228,15 @@ pub mod pallet {
 		}
 	}
 
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Funds bonded by a candidate for its candidacy.
+		CandidacyBond,
+		/// Funds delegated to back a candidate.
+		Delegation,
+	}
+
 	/// Basic information about a collation candidate.
 	#[derive(
 		PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
@@ -203,8 +244,28 @@ pub mod pallet {
 	pub struct CandidateInfo<AccountId, Balance> {
 		/// Account identifier.
 		pub who: AccountId,
-		/// Reserved deposit.
+		/// Deposit held from the candidate itself.
 		pub deposit: Balance,
+		/// Sum of the delegations backing the candidate.
+		pub backing: Balance,
+	}
+
+	impl<AccountId, Balance: Saturating + Copy> CandidateInfo<AccountId, Balance> {
+		/// The total backing of the candidate, which determines its position in the candidate list.
+		pub fn total(&self) -> Balance {
+			self.deposit.saturating_add(self.backing)
+		}
+	}
+
+	/// A delegation backing a collation candidate.
+	#[derive(
+		PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+	)]
+	pub struct Delegation<AccountId, Balance> {
+		/// The delegating account.
+		pub delegator: AccountId,
+		/// Amount held from the delegator.
+		pub amount: Balance,
 	}
 
 	#[pallet::pallet]
@@ -219,8 +280,8 @@ pub mod pallet {
 	/// The (community, limited) collation candidates. `Candidates` and `Invulnerables` should be
 	/// mutually exclusive.
 	///
-	/// This list is sorted in ascending order by deposit and when the deposits are equal, the least
-	/// recently updated is considered greater.
+	/// This list is sorted in ascending order by total backing (deposit plus delegations) and when
+	/// the totals are equal, the least recently updated is considered greater.
 	#[pallet::storage]
 	pub type CandidateList<T: Config> = StorageValue<
 		_,
@@ -245,6 +306,28 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type CandidacyBond<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+	/// The delegations backing each candidate.
+	///
+	/// Delegations are kept when their candidate leaves the candidate list, and count again should
+	/// it register again. Meanwhile, delegators can withdraw them at any time.
+	#[pallet::storage]
+	pub type Delegations<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<Delegation<T::AccountId, BalanceOf<T>>, T::MaxDelegatorsPerCandidate>,
+		ValueQuery,
+	>;
+
+	/// Share of its block rewards a candidate keeps before splitting the rest with its delegators.
+	#[pallet::storage]
+	pub type Commission<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Perbill, ValueQuery>;
+
+	/// Commission increases which are not in effect yet, with the block from which they apply.
+	#[pallet::storage]
+	pub type PendingCommission<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (BlockNumberFor<T>, Perbill), OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -305,6 +388,18 @@ pub mod pallet {
 		/// An account was unable to be added to the Invulnerables because they did not have keys
 		/// registered. Other Invulnerables may have been set.
 		InvalidInvulnerableSkipped { account_id: T::AccountId },
+		/// A delegator backed a candidate.
+		Delegated { candidate: T::AccountId, delegator: T::AccountId, amount: BalanceOf<T> },
+		/// A delegator withdrew its delegation from a candidate.
+		Undelegated { candidate: T::AccountId, delegator: T::AccountId, amount: BalanceOf<T> },
+		/// The commission of a candidate was set.
+		CommissionSet { account_id: T::AccountId, commission: Perbill },
+		/// An increase of the commission of a candidate was scheduled.
+		CommissionIncreaseScheduled {
+			account_id: T::AccountId,
+			commission: Perbill,
+			at: BlockNumberFor<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -341,8 +436,17 @@ pub mod pallet {
 		TargetIsNotCandidate,
 		/// The updated deposit amount is equal to the amount already reserved.
 		IdenticalDeposit,
-		/// Cannot lower candidacy bond while occupying a future collator slot in the list.
+		/// Cannot lower candidacy bond or backing while occupying a future collator slot in the
+		/// list.
 		InvalidUnreserve,
+		/// A candidate cannot delegate to itself.
+		SelfDelegation,
+		/// The delegation would be below the minimum delegation.
+		DelegationTooLow,
+		/// The candidate has too many delegators.
+		TooManyDelegators,
+		/// Account has no delegation to the candidate.
+		NotDelegator,
 	}
 
 	#[pallet::hooks]
@@ -491,18 +595,20 @@ pub mod pallet {
 			let kicked = (bond_increased && initial_len > 0)
 				.then(|| {
 					// Closure below returns the number of candidates which were kicked because
-					// their deposits were lower than the new candidacy bond.
+					// their deposits were lower than the new candidacy bond. The list is sorted by
+					// total backing, so they can be anywhere in the list.
 					CandidateList::<T>::mutate(|candidates| -> usize {
-						let first_safe_candidate = candidates
-							.iter()
-							.position(|candidate| candidate.deposit >= bond)
-							.unwrap_or(initial_len);
-						let kicked_candidates = candidates.drain(..first_safe_candidate);
-						for candidate in kicked_candidates {
-							T::Currency::unreserve(&candidate.who, candidate.deposit);
-							LastAuthoredBlock::<T>::remove(candidate.who);
-						}
-						first_safe_candidate
+						let mut kicked = 0;
+						candidates.retain(|candidate| {
+							if candidate.deposit >= bond {
+								return true
+							}
+							Self::release_bond(&candidate.who, candidate.deposit);
+							LastAuthoredBlock::<T>::remove(&candidate.who);
+							kicked += 1;
+							false
+						});
+						kicked
 					})
 				})
 				.unwrap_or_default();
@@ -515,7 +621,9 @@ pub mod pallet {
 		}
 
 		/// Register this account as a collator candidate. The account must (a) already have
-		/// registered session keys and (b) be able to reserve the `CandidacyBond`.
+		/// registered session keys and (b) be able to hold the `CandidacyBond`.
+		///
+		/// Delegations which backed the account in a previous candidacy count again.
 		///
 		/// This call is not available to `Invulnerable` collators.
 		#[pallet::call_index(3)]
@@ -539,19 +647,22 @@ pub mod pallet {
 			);
 
 			let deposit = CandidacyBond::<T>::get();
+			let info =
+				CandidateInfo { who: who.clone(), deposit, backing: Self::delegated_to(&who) };
 			// First authored block is current block plus kick threshold to handle session delay
 			CandidateList::<T>::try_mutate(|candidates| -> Result<(), DispatchError> {
 				ensure!(
 					!candidates.iter().any(|candidate_info| candidate_info.who == who),
 					Error::<T>::AlreadyCandidate
 				);
-				T::Currency::reserve(&who, deposit)?;
+				T::Currency::hold(&HoldReason::CandidacyBond.into(), &who, deposit)?;
 				LastAuthoredBlock::<T>::insert(
 					who.clone(),
 					frame_system::Pallet::<T>::block_number() + T::KickThreshold::get(),
 				);
+				let pos = Self::insert_position(candidates, info.total());
 				candidates
-					.try_insert(0, CandidateInfo { who: who.clone(), deposit })
+					.try_insert(pos, info)
 					.map_err(|_| Error::<T>::InsertToCandidateListFailed)?;
 				Ok(())
 			})?;
@@ -564,7 +675,7 @@ pub mod pallet {
 		}
 
 		/// Deregister `origin` as a collator candidate. Note that the collator can only leave on
-		/// session change. The `CandidacyBond` will be unreserved immediately.
+		/// session change. The `CandidacyBond` will be released immediately.
 		///
 		/// This call will fail if the total number of candidates would drop below
 		/// `MinEligibleCollators`.
@@ -667,7 +778,7 @@ pub mod pallet {
 		/// occupying a top-`DesiredCandidates` slot is not allowed.
 		///
 		/// This call will fail if `origin` is not a collator candidate, the updated bond is lower
-		/// than the minimum candidacy bond, and/or the amount cannot be reserved.
+		/// than the minimum candidacy bond, and/or the amount cannot be held.
 		#[pallet::call_index(7)]
 		#[pallet::weight(T::WeightInfo::update_bond(T::MaxCandidates::get()))]
 		pub fn update_bond(
@@ -690,7 +801,11 @@ pub mod pallet {
 					let mut info = candidates.remove(idx);
 					let old_deposit = info.deposit;
 					if new_deposit > old_deposit {
-						T::Currency::reserve(&who, new_deposit - old_deposit)?;
+						T::Currency::hold(
+							&HoldReason::CandidacyBond.into(),
+							&who,
+							new_deposit - old_deposit,
+						)?;
 					} else if new_deposit < old_deposit {
 						// Casting `u32` to `usize` should be safe on all machines running this.
 						ensure!(
@@ -698,17 +813,14 @@ pub mod pallet {
 								candidate_count,
 							Error::<T>::InvalidUnreserve
 						);
-						T::Currency::unreserve(&who, old_deposit - new_deposit);
+						Self::release_bond(&who, old_deposit - new_deposit);
 					} else {
 						return Err(Error::<T>::IdenticalDeposit.into())
 					}
 
 					// Update the deposit and insert the candidate in the correct spot in the list.
 					info.deposit = new_deposit;
-					let new_pos = candidates
-						.iter()
-						.position(|candidate| candidate.deposit >= new_deposit)
-						.unwrap_or_else(|| candidates.len());
+					let new_pos = Self::insert_position(candidates, info.total());
 					candidates
 						.try_insert(new_pos, info)
 						.map_err(|_| Error::<T>::InsertToCandidateListFailed)?;
@@ -724,12 +836,13 @@ pub mod pallet {
 		}
 
 		/// The caller `origin` replaces a candidate `target` in the collator candidate list by
-		/// reserving `deposit`. The amount `deposit` reserved by the caller must be greater than
-		/// the existing bond of the target it is trying to replace.
+		/// holding `deposit`. The amount `deposit` held from the caller, together with the
+		/// delegations already backing it, must be greater than the total backing of the target it
+		/// is trying to replace.
 		///
 		/// This call will fail if the caller is already a collator candidate or invulnerable, the
 		/// caller does not have registered session keys, the target is not a collator candidate,
-		/// and/or the `deposit` amount cannot be reserved.
+		/// and/or the `deposit` amount cannot be held.
 		#[pallet::call_index(8)]
 		#[pallet::weight(T::WeightInfo::take_candidate_slot(T::MaxCandidates::get()))]
 		pub fn take_candidate_slot(
@@ -749,6 +862,8 @@ pub mod pallet {
 				Error::<T>::ValidatorNotRegistered
 			);
 
+			let backing = Self::delegated_to(&who);
+			let total = deposit.saturating_add(backing);
 			let length = CandidateList::<T>::decode_len().unwrap_or_default();
 			// The closure below iterates through all elements of the candidate list to ensure that
 			// the caller isn't already a candidate and to find the target it's trying to replace in
@@ -772,7 +887,7 @@ pub mod pallet {
 						}
 						// Find the spot where the new candidate would be inserted in the current
 						// version of the list.
-						if new_info_idx.is_none() && candidate_info.deposit >= deposit {
+						if new_info_idx.is_none() && candidate_info.total() >= total {
 							new_info_idx = Some(idx);
 						}
 					}
@@ -781,14 +896,14 @@ pub mod pallet {
 
 					// Remove the old candidate from the list.
 					let target_info = candidates.remove(target_info_idx);
-					ensure!(deposit > target_info.deposit, Error::<T>::InsufficientBond);
+					ensure!(total > target_info.total(), Error::<T>::InsufficientBond);
 
 					// We have removed one element before `new_info_idx`, so the position we have to
 					// insert to is reduced by 1.
 					let new_pos = new_info_idx
 						.map(|i| i.saturating_sub(1))
 						.unwrap_or_else(|| candidates.len());
-					let new_info = CandidateInfo { who: who.clone(), deposit };
+					let new_info = CandidateInfo { who: who.clone(), deposit, backing };
 					// Insert the new candidate in the correct spot in the list.
 					candidates
 						.try_insert(new_pos, new_info)
@@ -797,8 +912,8 @@ pub mod pallet {
 					Ok(target_info)
 				},
 			)?;
-			T::Currency::reserve(&who, deposit)?;
-			T::Currency::unreserve(&target_info.who, target_info.deposit);
+			T::Currency::hold(&HoldReason::CandidacyBond.into(), &who, deposit)?;
+			Self::release_bond(&target_info.who, target_info.deposit);
 			LastAuthoredBlock::<T>::remove(target_info.who.clone());
 			LastAuthoredBlock::<T>::insert(
 				who.clone(),
@@ -808,6 +923,174 @@ pub mod pallet {
 			Self::deposit_event(Event::CandidateReplaced { old: target, new: who, deposit });
 			Ok(Some(T::WeightInfo::take_candidate_slot(length as u32)).into())
 		}
+
+		/// Back the collator candidate `candidate` with `amount` held from the caller `origin`.
+		///
+		/// Delegating to the same candidate again increases the existing delegation. Delegations
+		/// count towards the total backing of the candidate and earn a share of its rewards.
+		///
+		/// This call will fail if `candidate` is not a collator candidate or is the caller, the
+		/// delegation would be lower than `MinDelegation`, the candidate already has
+		/// `MaxDelegatorsPerCandidate` delegators, and/or the amount cannot be held.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::delegate(T::MaxCandidates::get()))]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(who != candidate, Error::<T>::SelfDelegation);
+
+			let delegated = Delegations::<T>::try_mutate(
+				&candidate,
+				|delegations| -> Result<BalanceOf<T>, DispatchError> {
+					match delegations.iter_mut().find(|delegation| delegation.delegator == who) {
+						Some(delegation) => {
+							delegation.amount.saturating_accrue(amount);
+							Ok(delegation.amount)
+						},
+						None => {
+							delegations
+								.try_push(Delegation { delegator: who.clone(), amount })
+								.map_err(|_| Error::<T>::TooManyDelegators)?;
+							Ok(amount)
+						},
+					}
+				},
+			)?;
+			ensure!(delegated >= T::MinDelegation::get(), Error::<T>::DelegationTooLow);
+
+			// Update the backing of the candidate and move it to its new spot in the list. The
+			// return value is the length of the list, used for weight calculation.
+			let length =
+				CandidateList::<T>::try_mutate(|candidates| -> Result<usize, DispatchError> {
+					let idx = candidates
+						.iter()
+						.position(|candidate_info| candidate_info.who == candidate)
+						.ok_or(Error::<T>::NotCandidate)?;
+					let candidate_count = candidates.len();
+					let mut info = candidates.remove(idx);
+					info.backing.saturating_accrue(amount);
+					let new_pos = Self::insert_position(candidates, info.total());
+					candidates
+						.try_insert(new_pos, info)
+						.map_err(|_| Error::<T>::InsertToCandidateListFailed)?;
+					Ok(candidate_count)
+				})?;
+			T::Currency::hold(&HoldReason::Delegation.into(), &who, amount)?;
+
+			Self::deposit_event(Event::Delegated { candidate, delegator: who, amount });
+			Ok(Some(T::WeightInfo::delegate(length as u32)).into())
+		}
+
+		/// Withdraw the delegation of the caller `origin` to `candidate` and release the held
+		/// funds.
+		///
+		/// Like lowering a candidacy bond, withdrawing a delegation from a candidate occupying a
+		/// top-`DesiredCandidates` slot is not allowed, unless an increase of its commission is
+		/// pending. Delegations to accounts which are not candidates anymore can always be
+		/// withdrawn.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::undelegate(T::MaxCandidates::get()))]
+		pub fn undelegate(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let amount = Delegations::<T>::try_mutate_exists(
+				&candidate,
+				|maybe_delegations| -> Result<BalanceOf<T>, DispatchError> {
+					let delegations = maybe_delegations.as_mut().ok_or(Error::<T>::NotDelegator)?;
+					let idx = delegations
+						.iter()
+						.position(|delegation| delegation.delegator == who)
+						.ok_or(Error::<T>::NotDelegator)?;
+					let amount = delegations.remove(idx).amount;
+					if delegations.is_empty() {
+						*maybe_delegations = None;
+					}
+					Ok(amount)
+				},
+			)?;
+
+			let commission_increase_pending = Self::commission_increase_pending(&candidate);
+			let length =
+				CandidateList::<T>::try_mutate(|candidates| -> Result<usize, DispatchError> {
+					let candidate_count = candidates.len();
+					let Some(idx) = candidates
+						.iter()
+						.position(|candidate_info| candidate_info.who == candidate)
+					else {
+						return Ok(candidate_count)
+					};
+					// Casting `u32` to `usize` should be safe on all machines running this.
+					ensure!(
+						commission_increase_pending ||
+							idx.saturating_add(DesiredCandidates::<T>::get() as usize) <
+								candidate_count,
+						Error::<T>::InvalidUnreserve
+					);
+					let mut info = candidates.remove(idx);
+					info.backing.saturating_reduce(amount);
+					let new_pos = Self::insert_position(candidates, info.total());
+					candidates
+						.try_insert(new_pos, info)
+						.map_err(|_| Error::<T>::InsertToCandidateListFailed)?;
+					Ok(candidate_count)
+				})?;
+			let _ = T::Currency::release(
+				&HoldReason::Delegation.into(),
+				&who,
+				amount,
+				Precision::BestEffort,
+			);
+
+			Self::deposit_event(Event::Undelegated { candidate, delegator: who, amount });
+			Ok(Some(T::WeightInfo::undelegate(length as u32)).into())
+		}
+
+		/// Set the share of its block rewards collator candidate `origin` keeps before the rest is
+		/// split pro rata between its deposit and the delegations backing it.
+		///
+		/// Lowering the commission takes effect immediately, and cancels any pending increase.
+		/// Increasing it only takes effect after `CommissionIncreaseDelay` blocks, which gives the
+		/// delegators backing the candidate the chance to withdraw their delegations.
+		///
+		/// This call will fail if `origin` is not a collator candidate.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::set_commission(T::MaxCandidates::get()))]
+		pub fn set_commission(
+			origin: OriginFor<T>,
+			commission: Perbill,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let candidates = CandidateList::<T>::get();
+			ensure!(
+				candidates.iter().any(|candidate_info| candidate_info.who == who),
+				Error::<T>::NotCandidate
+			);
+
+			let current = Self::commission_of(&who);
+			if commission > current {
+				let at = frame_system::Pallet::<T>::block_number()
+					.saturating_add(T::CommissionIncreaseDelay::get());
+				// A pending increase which is already in effect is not pending anymore.
+				Commission::<T>::insert(&who, current);
+				PendingCommission::<T>::insert(&who, (at, commission));
+				Self::deposit_event(Event::CommissionIncreaseScheduled {
+					account_id: who,
+					commission,
+					at,
+				});
+			} else {
+				Commission::<T>::insert(&who, commission);
+				PendingCommission::<T>::remove(&who);
+				Self::deposit_event(Event::CommissionSet { account_id: who, commission });
+			}
+			Ok(Some(T::WeightInfo::set_commission(candidates.len() as u32)).into())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -826,6 +1109,84 @@ pub mod pallet {
 				.unwrap_or(u32::MAX)
 		}
 
+		/// Sum of the delegations backing `who`.
+		fn delegated_to(who: &T::AccountId) -> BalanceOf<T> {
+			Delegations::<T>::get(who)
+				.iter()
+				.fold(Zero::zero(), |total, delegation| total.saturating_add(delegation.amount))
+		}
+
+		/// Position at which a candidate with a total backing of `total` is inserted in the
+		/// candidate list, after which the list is still sorted.
+		fn insert_position(
+			candidates: &[CandidateInfo<T::AccountId, BalanceOf<T>>],
+			total: BalanceOf<T>,
+		) -> usize {
+			candidates
+				.iter()
+				.position(|candidate| candidate.total() >= total)
+				.unwrap_or(candidates.len())
+		}
+
+		/// The commission `who` currently keeps out of its block rewards.
+		pub fn commission_of(who: &T::AccountId) -> Perbill {
+			match PendingCommission::<T>::get(who) {
+				Some((at, commission)) if frame_system::Pallet::<T>::block_number() >= at =>
+					commission,
+				_ => Commission::<T>::get(who),
+			}
+		}
+
+		/// Whether an increase of the commission of `who` is scheduled but not in effect yet.
+		fn commission_increase_pending(who: &T::AccountId) -> bool {
+			PendingCommission::<T>::get(who)
+				.is_some_and(|(at, _)| frame_system::Pallet::<T>::block_number() < at)
+		}
+
+		/// Release `amount` of the candidacy bond of `who`.
+		fn release_bond(who: &T::AccountId, amount: BalanceOf<T>) {
+			let _ = T::Currency::release(
+				&HoldReason::CandidacyBond.into(),
+				who,
+				amount,
+				Precision::BestEffort,
+			);
+		}
+
+		/// Pay the delegators backing `author` their share of the block `reward`.
+		///
+		/// The author keeps its commission, and the rest is split pro rata between its deposit and
+		/// the delegations backing it. Returns what is left for the author, and the number of
+		/// delegators, used for weight calculation.
+		fn pay_delegators(
+			pot: &T::AccountId,
+			author: &T::AccountId,
+			reward: BalanceOf<T>,
+		) -> (BalanceOf<T>, u32) {
+			let Some(info) = CandidateList::<T>::get().into_iter().find(|candidate_info| {
+				candidate_info.who == *author && !candidate_info.backing.is_zero()
+			}) else {
+				return (reward, 0)
+			};
+
+			let total = info.total();
+			let shared = reward.saturating_sub(Self::commission_of(author) * reward);
+			let delegations = Delegations::<T>::get(author);
+			let delegators = delegations.len() as u32;
+			let mut author_reward = reward;
+			for Delegation { delegator, amount } in delegations {
+				let share = Perbill::from_rational(amount, total) * shared;
+				// Shares which can't be paid out, e.g. because they are below the existential
+				// deposit of a reaped delegator, are left to the author.
+				if !share.is_zero() &&
+					T::Currency::transfer(pot, &delegator, share, Preservation::Preserve).is_ok()
+				{
+					author_reward.saturating_reduce(share);
+				}
+			}
+			(author_reward, delegators)
+		}
+
 		/// Removes a candidate if they exist and sends them back their deposit.
 		fn try_remove_candidate(
 			who: &T::AccountId,
@@ -837,7 +1198,7 @@ pub mod pallet {
 					.position(|candidate_info| candidate_info.who == *who)
 					.ok_or(Error::<T>::NotCandidate)?;
 				let deposit = candidates[idx].deposit;
-				T::Currency::unreserve(who, deposit);
+				Self::release_bond(who, deposit);
 				candidates.remove(idx);
 				if remove_last_authored {
 					LastAuthoredBlock::<T>::remove(who.clone())
@@ -917,6 +1278,11 @@ pub mod pallet {
 		/// * The current desired candidate count should not exceed the candidate list capacity.
 		/// * The number of selected candidates together with the invulnerables must be greater than
 		///   or equal to the minimum number of eligible collators.
+		///
+		/// ## `CandidateList`
+		///
+		/// * The candidates should be sorted by total backing.
+		/// * The backing of each candidate should be the sum of the delegations to it.
 		#[cfg(any(test, feature = "try-runtime"))]
 		pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
 			let desired_candidates = DesiredCandidates::<T>::get();
@@ -932,6 +1298,18 @@ pub mod pallet {
 				"Invulnerable set together with desired candidates should be able to meet the collator quota."
 			);
 
+			let candidates = CandidateList::<T>::get();
+			frame_support::ensure!(
+				candidates.windows(2).all(|pair| pair[0].total() <= pair[1].total()),
+				"Candidates should be sorted by total backing."
+			);
+			frame_support::ensure!(
+				candidates
+					.iter()
+					.all(|candidate| candidate.backing == Self::delegated_to(&candidate.who)),
+				"The backing of a candidate should match its delegations."
+			);
+
 			Ok(())
 		}
 	}
@@ -944,17 +1322,18 @@ pub mod pallet {
 		fn note_author(author: T::AccountId) {
 			let pot = Self::account_id();
 			// assumes an ED will be sent to pot.
-			let reward = T::Currency::free_balance(&pot)
-				.checked_sub(&T::Currency::minimum_balance())
-				.unwrap_or_else(Zero::zero)
+			let reward = T::Currency::balance(&pot)
+				.saturating_sub(T::Currency::minimum_balance())
 				.div(2u32.into());
+			let (author_reward, delegators) = Self::pay_delegators(&pot, &author, reward);
 			// `reward` is half of pot account minus ED, this should never fail.
-			let _success = T::Currency::transfer(&pot, &author, reward, KeepAlive);
+			let _success =
+				T::Currency::transfer(&pot, &author, author_reward, Preservation::Preserve);
 			debug_assert!(_success.is_ok());
 			LastAuthoredBlock::<T>::insert(author, frame_system::Pallet::<T>::block_number());
 
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::WeightInfo::note_author(),
+				T::WeightInfo::note_author(delegators),
 				DispatchClass::Mandatory,
 			);
 		}
@@ -22,37 +22,186 @@ use alloc::vec::Vec;
 use frame_support::traits::{OnRuntimeUpgrade, UncheckedOnRuntimeUpgrade};
 use log;
 
+/// Migrate to v3. Moves the candidacy bonds from reserves to holds, and extends the candidate list
+/// with the backing of the candidates.
+pub mod v3 {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{fungible::MutateHold, ReservableCurrency},
+	};
+	use sp_runtime::traits::{Saturating, Zero};
+
+	/// [`UncheckedMigrationToV3`] wrapped in a
+	/// [`VersionedMigration`](frame_support::migrations::VersionedMigration), ensuring the
+	/// migration is only performed when on-chain version is 2.
+	pub type MigrationToV3<T, OldCurrency> = frame_support::migrations::VersionedMigration<
+		2,
+		3,
+		UncheckedMigrationToV3<T, OldCurrency>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+
+	/// Migrate to V3.
+	///
+	/// `OldCurrency` is the [`ReservableCurrency`] which reserved the candidacy bonds so far.
+	pub struct UncheckedMigrationToV3<T, OldCurrency>(PhantomData<(T, OldCurrency)>);
+	impl<T, OldCurrency> UncheckedOnRuntimeUpgrade for UncheckedMigrationToV3<T, OldCurrency>
+	where
+		T: Config,
+		OldCurrency: ReservableCurrency<T::AccountId, Balance = v2::BalanceOf<T>>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			let old_candidates = v2::CandidateList::<T>::take();
+			let count = old_candidates.len() as u64;
+
+			let mut candidates = BoundedVec::<_, T::MaxCandidates>::new();
+			for candidate in old_candidates {
+				let missing = OldCurrency::unreserve(&candidate.who, candidate.deposit);
+				let deposit = candidate.deposit.saturating_sub(missing);
+				if !missing.is_zero() {
+					log::error!(
+						target: LOG_TARGET,
+						"{:?} balance was unable to be unreserved from {:?}",
+						missing, &candidate.who,
+					);
+				}
+
+				if let Err(err) =
+					T::Currency::hold(&HoldReason::CandidacyBond.into(), &candidate.who, deposit)
+				{
+					// The deposit is back in the free balance of the candidate, so it's as if it
+					// left the candidate list.
+					log::error!(
+						target: LOG_TARGET,
+						"Unable to hold the candidacy bond of {:?}, removing it from the candidates: {:?}",
+						&candidate.who, err,
+					);
+					continue
+				}
+
+				// The list can only shrink, so this never fails.
+				let _ = candidates.try_push(CandidateInfo {
+					who: candidate.who,
+					deposit,
+					backing: Zero::zero(),
+				});
+			}
+			let migrated = candidates.len();
+			CandidateList::<T>::put(candidates);
+
+			log::info!(
+				target: LOG_TARGET,
+				"Moved the bond of {} out of {} candidates to holds, upgraded storage to version 3",
+				migrated,
+				count,
+			);
+
+			// 1 read and write for the list, and for each candidate the account and holds are read
+			// and written by the unreserve and hold.
+			T::DbWeight::get()
+				.reads_writes(1, 1)
+				.saturating_add(T::DbWeight::get().reads_writes(2, 2).saturating_mul(count))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::DispatchError> {
+			let number_of_candidates = v2::CandidateList::<T>::get().len() as u32;
+			Ok(number_of_candidates.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(number_of_candidates: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+			use frame_support::traits::fungible::InspectHold;
+
+			let number_of_candidates: u32 = Decode::decode(&mut number_of_candidates.as_slice())
+				.expect(
+					"the state parameter should be something that was generated by pre_upgrade",
+				);
+			let candidates = CandidateList::<T>::get();
+			frame_support::ensure!(
+				candidates.len() as u32 <= number_of_candidates,
+				"after migration, there should not be more candidates"
+			);
+			for candidate in candidates {
+				frame_support::ensure!(
+					T::Currency::balance_on_hold(&HoldReason::CandidacyBond.into(), &candidate.who) >=
+						candidate.deposit,
+					"after migration, the candidacy bonds should be held"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
 /// Migrate to v2. Should have been part of <https://github.com/paritytech/polkadot-sdk/pull/1340>.
 pub mod v2 {
 	use super::*;
 	use frame_support::{
 		pallet_prelude::*,
 		storage_alias,
-		traits::{Currency, ReservableCurrency},
+		traits::{fungible::Inspect, ReservableCurrency},
 	};
 	use sp_runtime::traits::{Saturating, Zero};
 
 	/// [`UncheckedMigrationToV2`] wrapped in a
 	/// [`VersionedMigration`](frame_support::migrations::VersionedMigration), ensuring the
 	/// migration is only performed when on-chain version is 1.
-	pub type MigrationToV2<T> = frame_support::migrations::VersionedMigration<
+	pub type MigrationToV2<T, OldCurrency> = frame_support::migrations::VersionedMigration<
 		1,
 		2,
-		UncheckedMigrationToV2<T>,
+		UncheckedMigrationToV2<T, OldCurrency>,
 		Pallet<T>,
 		<T as frame_system::Config>::DbWeight,
 	>;
 
+	/// The balance type of the pallet's currency.
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Information about a collation candidate up to storage version 2.
+	#[derive(
+		PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+	)]
+	pub struct CandidateInfo<AccountId, Balance> {
+		/// Account identifier.
+		pub who: AccountId,
+		/// Reserved deposit.
+		pub deposit: Balance,
+	}
+
 	#[storage_alias]
 	pub type Candidates<T: Config> = StorageValue<
 		Pallet<T>,
-		BoundedVec<CandidateInfo<<T as frame_system::Config>::AccountId, <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance>, <T as Config>::MaxCandidates>,
+		BoundedVec<
+			CandidateInfo<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
+			<T as Config>::MaxCandidates,
+		>,
+		ValueQuery,
+	>;
+
+	/// The candidate list up to storage version 2.
+	#[storage_alias]
+	pub type CandidateList<T: Config> = StorageValue<
+		Pallet<T>,
+		BoundedVec<
+			CandidateInfo<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
+			<T as Config>::MaxCandidates,
+		>,
 		ValueQuery,
 	>;
 
 	/// Migrate to V2.
-	pub struct UncheckedMigrationToV2<T>(PhantomData<T>);
-	impl<T: Config + pallet_balances::Config> UncheckedOnRuntimeUpgrade for UncheckedMigrationToV2<T> {
+	///
+	/// `OldCurrency` is the [`ReservableCurrency`] which reserved the candidacy bonds.
+	pub struct UncheckedMigrationToV2<T, OldCurrency>(PhantomData<(T, OldCurrency)>);
+	impl<T, OldCurrency> UncheckedOnRuntimeUpgrade for UncheckedMigrationToV2<T, OldCurrency>
+	where
+		T: Config + pallet_balances::Config,
+		OldCurrency: ReservableCurrency<T::AccountId, Balance = BalanceOf<T>>,
+	{
 		fn on_runtime_upgrade() -> Weight {
 			let mut weight = Weight::zero();
 			let mut count: u64 = 0;
@@ -75,7 +224,7 @@ pub mod v2 {
 				// `CandidateList`. So, let's just refund the old ones and assume they have already
 				// started participating in the new system.
 				for candidate in candidates {
-					let err = T::Currency::unreserve(&candidate.who, candidate.deposit);
+					let err = OldCurrency::unreserve(&candidate.who, candidate.deposit);
 					if err > Zero::zero() {
 						log::error!(
 							target: LOG_TARGET,
@@ -191,11 +340,11 @@ pub mod v1 {
 mod tests {
 	use super::*;
 	use crate::{
-		migration::v2::Candidates,
-		mock::{new_test_ext, Balances, Test},
+		migration::v2::{CandidateInfo, Candidates},
+		mock::{new_test_ext, Balances, RuntimeHoldReason, Test},
 	};
 	use frame_support::{
-		traits::{Currency, ReservableCurrency, StorageVersion},
+		traits::{fungible::InspectHold, Currency, ReservableCurrency, StorageVersion},
 		BoundedVec,
 	};
 	use sp_runtime::traits::ConstU32;
@@ -242,7 +391,7 @@ mod tests {
 
 			// Set storage
 			Candidates::<Test>::put(bounded_candidates);
-			CandidateList::<Test>::put(bounded_candidate_list.clone());
+			v2::CandidateList::<Test>::put(bounded_candidate_list.clone());
 
 			// Sanity check
 			assert_eq!(Balances::free_balance(one), 90);
@@ -250,7 +399,7 @@ mod tests {
 			assert_eq!(Balances::free_balance(three), 90);
 
 			// Run migration
-			v2::MigrationToV2::<Test>::on_runtime_upgrade();
+			v2::MigrationToV2::<Test, Balances>::on_runtime_upgrade();
 
 			let new_storage_version = StorageVersion::get::<Pallet<Test>>();
 			assert_eq!(new_storage_version, 2);
@@ -262,7 +411,7 @@ mod tests {
 			// The storage item should be gone
 			assert!(Candidates::<Test>::get().is_empty());
 			// The new storage item should be preserved
-			assert_eq!(CandidateList::<Test>::get(), bounded_candidate_list);
+			assert_eq!(v2::CandidateList::<Test>::get(), bounded_candidate_list);
 		});
 	}
 
@@ -304,7 +453,7 @@ mod tests {
 			assert_eq!(Balances::free_balance(two), 90);
 
 			// Run migration
-			v2::MigrationToV2::<Test>::on_runtime_upgrade();
+			v2::MigrationToV2::<Test, Balances>::on_runtime_upgrade();
 
 			let new_storage_version = StorageVersion::get::<Pallet<Test>>();
 			assert_eq!(new_storage_version, 2);
@@ -315,7 +464,52 @@ mod tests {
 			// The storage item should be gone
 			assert!(Candidates::<Test>::get().is_empty());
 			// The new storage item should have the info now
-			assert_eq!(CandidateList::<Test>::get(), bounded_candidates);
+			assert_eq!(v2::CandidateList::<Test>::get(), bounded_candidates);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v3_moves_bonds_to_holds() {
+		new_test_ext().execute_with(|| {
+			let storage_version = StorageVersion::new(2);
+			storage_version.put::<Pallet<Test>>();
+
+			let one = 1u64;
+			let two = 2u64;
+
+			Balances::make_free_balance_be(&one, 100u64);
+			Balances::make_free_balance_be(&two, 100u64);
+			Balances::reserve(&one, 10u64).unwrap();
+			Balances::reserve(&two, 20u64).unwrap();
+
+			let bounded_candidates =
+				BoundedVec::<CandidateInfo<u64, u64>, ConstU32<20>>::try_from(vec![
+					CandidateInfo { who: one, deposit: 10 },
+					CandidateInfo { who: two, deposit: 20 },
+				])
+				.expect("it works");
+			v2::CandidateList::<Test>::put(bounded_candidates);
+
+			// Run migration
+			v3::MigrationToV3::<Test, Balances>::on_runtime_upgrade();
+
+			let new_storage_version = StorageVersion::get::<Pallet<Test>>();
+			assert_eq!(new_storage_version, 3);
+
+			// The bonds are held instead of reserved now
+			let reason: RuntimeHoldReason = HoldReason::CandidacyBond.into();
+			assert_eq!(Balances::balance_on_hold(&reason, &one), 10);
+			assert_eq!(Balances::balance_on_hold(&reason, &two), 20);
+			assert_eq!(Balances::free_balance(one), 90);
+			assert_eq!(Balances::free_balance(two), 80);
+			// The candidates are kept, without backing
+			assert_eq!(
+				CandidateList::<Test>::get().to_vec(),
+				vec![
+					crate::CandidateInfo { who: one, deposit: 10, backing: 0 },
+					crate::CandidateInfo { who: two, deposit: 20, backing: 0 },
+				]
+			);
 		});
 	}
 }
@@ -161,6 +161,7 @@ impl ValidatorRegistration<u64> for IsRegistered {
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type UpdateOrigin = EnsureSignedBy<RootAccount, u64>;
 	type PotId = PotId;
 	type MaxCandidates = ConstU32<20>;
@@ -170,6 +171,9 @@ impl Config for Test {
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	type ValidatorIdOf = IdentityCollator;
 	type ValidatorRegistration = IsRegistered;
+	type MinDelegation = ConstU64<5>;
+	type MaxDelegatorsPerCandidate = ConstU32<3>;
+	type CommissionIncreaseDelay = ConstU64<10>;
 	type WeightInfo = ();
 }
 
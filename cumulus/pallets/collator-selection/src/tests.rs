@@ -15,15 +15,14 @@
 
 use crate as collator_selection;
 use crate::{
-	mock::*, CandidacyBond, CandidateInfo, CandidateList, DesiredCandidates, Error, Invulnerables,
-	LastAuthoredBlock,
+	mock::*, CandidacyBond, CandidateInfo, CandidateList, Commission, Delegation, Delegations,
+	DesiredCandidates, Error, HoldReason, Invulnerables, LastAuthoredBlock, PendingCommission,
 };
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::{Currency, OnInitialize},
+	traits::{fungible::InspectHold, Currency, OnInitialize},
 };
-use pallet_balances::Error as BalancesError;
-use sp_runtime::{testing::UintAuthorityId, traits::BadOrigin, BuildStorage};
+use sp_runtime::{testing::UintAuthorityId, traits::BadOrigin, BuildStorage, Perbill, TokenError};
 
 #[test]
 fn basic_setup_works() {
@@ -305,7 +304,7 @@ fn set_candidacy_bond_with_one_candidate() {
 		assert_eq!(CandidacyBond::<Test>::get(), 10);
 		assert!(CandidateList::<Test>::get().is_empty());
 
-		let candidate_3 = CandidateInfo { who: 3, deposit: 10 };
+		let candidate_3 = CandidateInfo { who: 3, deposit: 10, backing: 0 };
 
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
 		assert_eq!(CandidateList::<Test>::get(), vec![candidate_3.clone()]);
@@ -342,9 +341,9 @@ fn set_candidacy_bond_with_many_candidates_same_deposit() {
 		assert_eq!(CandidacyBond::<Test>::get(), 10);
 		assert!(CandidateList::<Test>::get().is_empty());
 
-		let candidate_3 = CandidateInfo { who: 3, deposit: 10 };
-		let candidate_4 = CandidateInfo { who: 4, deposit: 10 };
-		let candidate_5 = CandidateInfo { who: 5, deposit: 10 };
+		let candidate_3 = CandidateInfo { who: 3, deposit: 10, backing: 0 };
+		let candidate_4 = CandidateInfo { who: 4, deposit: 10, backing: 0 };
+		let candidate_5 = CandidateInfo { who: 5, deposit: 10, backing: 0 };
 
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
@@ -392,9 +391,9 @@ fn set_candidacy_bond_with_many_candidates_different_deposits() {
 		assert_eq!(CandidacyBond::<Test>::get(), 10);
 		assert!(CandidateList::<Test>::get().is_empty());
 
-		let candidate_3 = CandidateInfo { who: 3, deposit: 10 };
-		let candidate_4 = CandidateInfo { who: 4, deposit: 20 };
-		let candidate_5 = CandidateInfo { who: 5, deposit: 30 };
+		let candidate_3 = CandidateInfo { who: 3, deposit: 10, backing: 0 };
+		let candidate_4 = CandidateInfo { who: 4, deposit: 20, backing: 0 };
+		let candidate_5 = CandidateInfo { who: 5, deposit: 30, backing: 0 };
 
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
@@ -559,7 +558,7 @@ fn cannot_register_dupe_candidate() {
 		// can add 3 as candidate
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
 		// tuple of (id, deposit).
-		let addition = CandidateInfo { who: 3, deposit: 10 };
+		let addition = CandidateInfo { who: 3, deposit: 10, backing: 0 };
 		assert_eq!(
 			CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>(),
 			vec![addition]
@@ -587,7 +586,7 @@ fn cannot_register_as_candidate_if_poor() {
 		// poor
 		assert_noop!(
 			CollatorSelection::register_as_candidate(RuntimeOrigin::signed(33)),
-			BalancesError::<Test>::InsufficientBalance,
+			TokenError::FundsUnavailable,
 		);
 	});
 }
@@ -647,8 +646,8 @@ fn cannot_take_candidate_slot_if_duplicate() {
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
 		// tuple of (id, deposit).
-		let candidate_3 = CandidateInfo { who: 3, deposit: 10 };
-		let candidate_4 = CandidateInfo { who: 4, deposit: 10 };
+		let candidate_3 = CandidateInfo { who: 3, deposit: 10, backing: 0 };
+		let candidate_4 = CandidateInfo { who: 4, deposit: 10, backing: 0 };
 		let actual_candidates = CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>();
 		assert_eq!(actual_candidates, vec![candidate_4, candidate_3]);
 		assert_eq!(LastAuthoredBlock::<Test>::get(3), 10);
@@ -669,7 +668,7 @@ fn cannot_take_candidate_slot_if_target_invalid() {
 		// can add 3 as candidate
 		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
 		// tuple of (id, deposit).
-		let candidate_3 = CandidateInfo { who: 3, deposit: 10 };
+		let candidate_3 = CandidateInfo { who: 3, deposit: 10, backing: 0 };
 		assert_eq!(
 			CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>(),
 			vec![candidate_3]
@@ -702,7 +701,7 @@ fn cannot_take_candidate_slot_if_poor() {
 		// poor
 		assert_noop!(
 			CollatorSelection::take_candidate_slot(RuntimeOrigin::signed(33), 30u64.into(), 3),
-			BalancesError::<Test>::InsufficientBalance,
+			TokenError::FundsUnavailable,
 		);
 	});
 }
@@ -780,9 +779,9 @@ fn take_candidate_slot_works() {
 		assert_eq!(Balances::free_balance(6), 50);
 
 		// tuple of (id, deposit).
-		let candidate_3 = CandidateInfo { who: 3, deposit: 10 };
-		let candidate_6 = CandidateInfo { who: 6, deposit: 50 };
-		let candidate_5 = CandidateInfo { who: 5, deposit: 10 };
+		let candidate_3 = CandidateInfo { who: 3, deposit: 10, backing: 0 };
+		let candidate_6 = CandidateInfo { who: 6, deposit: 50, backing: 0 };
+		let candidate_5 = CandidateInfo { who: 5, deposit: 10, backing: 0 };
 		let mut actual_candidates =
 			CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>();
 		actual_candidates.sort_by(|info_1, info_2| info_1.deposit.cmp(&info_2.deposit));
@@ -838,7 +837,7 @@ fn increase_candidacy_bond_insufficient_balance() {
 
 		assert_noop!(
 			CollatorSelection::update_bond(RuntimeOrigin::signed(3), 110),
-			BalancesError::<Test>::InsufficientBalance
+			TokenError::FundsUnavailable
 		);
 
 		assert_eq!(Balances::free_balance(3), 90);
@@ -971,9 +970,9 @@ fn decrease_candidacy_bond_occupying_top_slot() {
 		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(5), 60u64.into()));
 
 		// tuple of (id, deposit).
-		let candidate_3 = CandidateInfo { who: 3, deposit: 30 };
-		let candidate_4 = CandidateInfo { who: 4, deposit: 30 };
-		let candidate_5 = CandidateInfo { who: 5, deposit: 60 };
+		let candidate_3 = CandidateInfo { who: 3, deposit: 30, backing: 0 };
+		let candidate_4 = CandidateInfo { who: 4, deposit: 30, backing: 0 };
+		let candidate_5 = CandidateInfo { who: 5, deposit: 60, backing: 0 };
 		assert_eq!(
 			CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>(),
 			vec![candidate_4, candidate_3, candidate_5]
@@ -995,9 +994,9 @@ fn decrease_candidacy_bond_occupying_top_slot() {
 		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(4), 35u64.into()));
 
 		// tuple of (id, deposit).
-		let candidate_3 = CandidateInfo { who: 3, deposit: 30 };
-		let candidate_4 = CandidateInfo { who: 4, deposit: 35 };
-		let candidate_5 = CandidateInfo { who: 5, deposit: 60 };
+		let candidate_3 = CandidateInfo { who: 3, deposit: 30, backing: 0 };
+		let candidate_4 = CandidateInfo { who: 4, deposit: 35, backing: 0 };
+		let candidate_5 = CandidateInfo { who: 5, deposit: 60, backing: 0 };
 		assert_eq!(
 			CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>(),
 			vec![candidate_3, candidate_4, candidate_5]
@@ -1126,9 +1125,9 @@ fn candidate_list_works() {
 
 		assert_ok!(CollatorSelection::update_bond(RuntimeOrigin::signed(5), 10));
 
-		let candidate_3 = CandidateInfo { who: 3, deposit: 30 };
-		let candidate_4 = CandidateInfo { who: 4, deposit: 25 };
-		let candidate_5 = CandidateInfo { who: 5, deposit: 10 };
+		let candidate_3 = CandidateInfo { who: 3, deposit: 30, backing: 0 };
+		let candidate_4 = CandidateInfo { who: 4, deposit: 25, backing: 0 };
+		let candidate_5 = CandidateInfo { who: 5, deposit: 10, backing: 0 };
 		assert_eq!(
 			CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>(),
 			vec![candidate_5, candidate_4, candidate_3]
@@ -1173,7 +1172,7 @@ fn authorship_event_handler() {
 		Authorship::on_initialize(1);
 
 		// tuple of (id, deposit).
-		let collator = CandidateInfo { who: 4, deposit: 10 };
+		let collator = CandidateInfo { who: 4, deposit: 10, backing: 0 };
 
 		assert_eq!(
 			CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>(),
@@ -1202,7 +1201,7 @@ fn fees_edgecases() {
 		Authorship::on_initialize(1);
 
 		// tuple of (id, deposit).
-		let collator = CandidateInfo { who: 4, deposit: 10 };
+		let collator = CandidateInfo { who: 4, deposit: 10, backing: 0 };
 
 		assert_eq!(
 			CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>(),
@@ -1216,6 +1215,243 @@ fn fees_edgecases() {
 	});
 }
 
+#[test]
+fn delegate_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_eq!(Balances::free_balance(5), 100);
+
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(5), 3, 20));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(crate::Event::Delegated {
+			candidate: 3,
+			delegator: 5,
+			amount: 20,
+		}));
+
+		// the delegation is held from the delegator
+		assert_eq!(Balances::free_balance(5), 80);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Delegation.into(), &5), 20);
+		assert_eq!(
+			Delegations::<Test>::get(3).to_vec(),
+			vec![Delegation { delegator: 5, amount: 20 }]
+		);
+
+		// and 3 is sorted by its total backing now
+		let candidate_3 = CandidateInfo { who: 3, deposit: 10, backing: 20 };
+		let candidate_4 = CandidateInfo { who: 4, deposit: 10, backing: 0 };
+		assert_eq!(
+			CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>(),
+			vec![candidate_4, candidate_3]
+		);
+
+		// delegating again tops up the existing delegation
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(5), 3, 10));
+		assert_eq!(Balances::free_balance(5), 70);
+		assert_eq!(
+			Delegations::<Test>::get(3).to_vec(),
+			vec![Delegation { delegator: 5, amount: 30 }]
+		);
+		assert_eq!(CandidateList::<Test>::get()[1].backing, 30);
+	});
+}
+
+#[test]
+fn cannot_delegate_invalid() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+
+		// can't back oneself
+		assert_noop!(
+			CollatorSelection::delegate(RuntimeOrigin::signed(3), 3, 20),
+			Error::<Test>::SelfDelegation,
+		);
+		// only candidates can be backed
+		assert_noop!(
+			CollatorSelection::delegate(RuntimeOrigin::signed(5), 4, 20),
+			Error::<Test>::NotCandidate,
+		);
+		// below `MinDelegation`
+		assert_noop!(
+			CollatorSelection::delegate(RuntimeOrigin::signed(5), 3, 4),
+			Error::<Test>::DelegationTooLow,
+		);
+		// poor
+		assert_noop!(
+			CollatorSelection::delegate(RuntimeOrigin::signed(33), 3, 20),
+			TokenError::FundsUnavailable,
+		);
+
+		// `MaxDelegatorsPerCandidate` is 3 in tests
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(1), 3, 10));
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(2), 3, 10));
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(4), 3, 10));
+		assert_noop!(
+			CollatorSelection::delegate(RuntimeOrigin::signed(5), 3, 10),
+			Error::<Test>::TooManyDelegators,
+		);
+	});
+}
+
+#[test]
+fn undelegate_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(5)));
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(1), 3, 20));
+		assert_eq!(Balances::free_balance(1), 80);
+
+		// not a delegator
+		assert_noop!(
+			CollatorSelection::undelegate(RuntimeOrigin::signed(2), 3),
+			Error::<Test>::NotDelegator,
+		);
+		// 3 occupies a top slot, like lowering its bond the backing can't be withdrawn
+		assert_noop!(
+			CollatorSelection::undelegate(RuntimeOrigin::signed(1), 3),
+			Error::<Test>::InvalidUnreserve,
+		);
+
+		assert_ok!(CollatorSelection::set_desired_candidates(
+			RuntimeOrigin::signed(RootAccount::get()),
+			0
+		));
+		assert_ok!(CollatorSelection::undelegate(RuntimeOrigin::signed(1), 3));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(crate::Event::Undelegated {
+			candidate: 3,
+			delegator: 1,
+			amount: 20,
+		}));
+
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Delegation.into(), &1), 0);
+		assert!(Delegations::<Test>::get(3).is_empty());
+		assert!(CandidateList::<Test>::get().iter().all(|info| info.backing == 0));
+	});
+}
+
+#[test]
+fn undelegate_from_former_candidate() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(5), 3, 20));
+		assert_ok!(CollatorSelection::leave_intent(RuntimeOrigin::signed(3)));
+		assert_eq!(Balances::free_balance(5), 80);
+
+		// the delegation can always be withdrawn once 3 isn't a candidate anymore
+		assert_ok!(CollatorSelection::undelegate(RuntimeOrigin::signed(5), 3));
+		assert_eq!(Balances::free_balance(5), 100);
+		assert!(Delegations::<Test>::get(3).is_empty());
+	});
+}
+
+#[test]
+fn set_commission_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CollatorSelection::set_commission(RuntimeOrigin::signed(3), Perbill::from_percent(20)),
+			Error::<Test>::NotCandidate,
+		);
+
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		// an increase only takes effect after `CommissionIncreaseDelay`, which is 10 in tests
+		let now = System::block_number();
+		assert_ok!(CollatorSelection::set_commission(
+			RuntimeOrigin::signed(3),
+			Perbill::from_percent(20)
+		));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(
+			crate::Event::CommissionIncreaseScheduled {
+				account_id: 3,
+				commission: Perbill::from_percent(20),
+				at: now + 10,
+			},
+		));
+		assert_eq!(CollatorSelection::commission_of(&3), Perbill::zero());
+		System::set_block_number(now + 9);
+		assert_eq!(CollatorSelection::commission_of(&3), Perbill::zero());
+		System::set_block_number(now + 10);
+		assert_eq!(CollatorSelection::commission_of(&3), Perbill::from_percent(20));
+
+		// a decrease takes effect immediately
+		assert_ok!(CollatorSelection::set_commission(
+			RuntimeOrigin::signed(3),
+			Perbill::from_percent(10)
+		));
+		System::assert_last_event(RuntimeEvent::CollatorSelection(crate::Event::CommissionSet {
+			account_id: 3,
+			commission: Perbill::from_percent(10),
+		}));
+		assert_eq!(Commission::<Test>::get(3), Perbill::from_percent(10));
+		assert_eq!(PendingCommission::<Test>::get(3), None);
+		assert_eq!(CollatorSelection::commission_of(&3), Perbill::from_percent(10));
+	});
+}
+
+#[test]
+fn delegators_can_leave_while_commission_increase_is_pending() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(5)));
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(1), 3, 20));
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(2), 3, 20));
+
+		// 3 occupies a top slot, so its backing can't be withdrawn
+		assert_noop!(
+			CollatorSelection::undelegate(RuntimeOrigin::signed(1), 3),
+			Error::<Test>::InvalidUnreserve,
+		);
+
+		// until it schedules an increase of its commission
+		let now = System::block_number();
+		assert_ok!(CollatorSelection::set_commission(
+			RuntimeOrigin::signed(3),
+			Perbill::from_percent(100)
+		));
+		assert_ok!(CollatorSelection::undelegate(RuntimeOrigin::signed(1), 3));
+		assert_eq!(Balances::free_balance(1), 100);
+
+		// once the increase is in effect, the delegation is locked again
+		System::set_block_number(now + 10);
+		assert_eq!(CollatorSelection::commission_of(&3), Perbill::from_percent(100));
+		assert_noop!(
+			CollatorSelection::undelegate(RuntimeOrigin::signed(2), 3),
+			Error::<Test>::InvalidUnreserve,
+		);
+	});
+}
+
+#[test]
+fn delegators_share_rewards() {
+	new_test_ext().execute_with(|| {
+		// put 100 in the pot + 5 for ED
+		Balances::make_free_balance_be(&CollatorSelection::account_id(), 105);
+
+		// 4 is the default author, backed by 10 of its own and 30 of 5.
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(4)));
+		assert_ok!(CollatorSelection::delegate(RuntimeOrigin::signed(5), 4, 30));
+		assert_ok!(CollatorSelection::set_commission(
+			RuntimeOrigin::signed(4),
+			Perbill::from_percent(20)
+		));
+		// let the commission increase take effect
+		System::set_block_number(System::block_number() + 10);
+		assert_eq!(Balances::free_balance(4), 90);
+		assert_eq!(Balances::free_balance(5), 70);
+
+		// triggers `note_author`
+		Authorship::on_initialize(1);
+
+		// the reward is 50, of which 4 keeps 10 as commission. The remaining 40 are split 1:3.
+		assert_eq!(Balances::free_balance(4), 110);
+		assert_eq!(Balances::free_balance(5), 100);
+		// half + ED stays.
+		assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 55);
+	});
+}
+
 #[test]
 fn session_management_single_candidate() {
 	new_test_ext().execute_with(|| {
@@ -1483,7 +1719,7 @@ fn kick_mechanism() {
 		// 3 will be kicked after 1 session delay
 		assert_eq!(SessionHandlerCollators::get(), vec![1, 2, 3, 4]);
 		// tuple of (id, deposit).
-		let collator = CandidateInfo { who: 4, deposit: 10 };
+		let collator = CandidateInfo { who: 4, deposit: 10, backing: 0 };
 		assert_eq!(
 			CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>(),
 			vec![collator]
@@ -1525,7 +1761,7 @@ fn should_not_kick_mechanism_too_few() {
 		// 3 will be kicked after 1 session delay
 		assert_eq!(SessionHandlerCollators::get(), vec![3, 5]);
 		// tuple of (id, deposit).
-		let collator = CandidateInfo { who: 3, deposit: 10 };
+		let collator = CandidateInfo { who: 3, deposit: 10, backing: 0 };
 		assert_eq!(
 			CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>(),
 			vec![collator]
@@ -1554,8 +1790,8 @@ fn should_kick_invulnerables_from_candidates_on_session_change() {
 		));
 
 		// tuple of (id, deposit).
-		let collator_3 = CandidateInfo { who: 3, deposit: 10 };
-		let collator_4 = CandidateInfo { who: 4, deposit: 10 };
+		let collator_3 = CandidateInfo { who: 3, deposit: 10, backing: 0 };
+		let collator_4 = CandidateInfo { who: 4, deposit: 10, backing: 0 };
 
 		let actual_candidates = CandidateList::<Test>::get().iter().cloned().collect::<Vec<_>>();
 		assert_eq!(actual_candidates, vec![collator_4.clone(), collator_3]);
@@ -35,7 +35,10 @@ pub trait WeightInfo {
 	fn leave_intent(_c: u32) -> Weight;
 	fn update_bond(_c: u32) -> Weight;
 	fn take_candidate_slot(_c: u32) -> Weight;
-	fn note_author() -> Weight;
+	fn delegate(_c: u32) -> Weight;
+	fn undelegate(_c: u32) -> Weight;
+	fn set_commission(_c: u32) -> Weight;
+	fn note_author(_d: u32) -> Weight;
 	fn new_session(_c: u32, _r: u32) -> Weight;
 }
 
@@ -82,10 +85,35 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
-	fn note_author() -> Weight {
-		Weight::from_parts(71_461_000_u64, 0)
+	fn delegate(c: u32) -> Weight {
+		Weight::from_parts(62_714_000_u64, 0)
+			// Standard Error: 0
+			.saturating_add(Weight::from_parts(163_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn undelegate(c: u32) -> Weight {
+		Weight::from_parts(58_902_000_u64, 0)
+			// Standard Error: 0
+			.saturating_add(Weight::from_parts(157_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn set_commission(c: u32) -> Weight {
+		Weight::from_parts(17_204_000_u64, 0)
+			// Standard Error: 0
+			.saturating_add(Weight::from_parts(41_000_u64, 0).saturating_mul(c as u64))
 			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn note_author(d: u32) -> Weight {
+		Weight::from_parts(71_461_000_u64, 0)
+			// Standard Error: 0
+			.saturating_add(Weight::from_parts(38_176_000_u64, 0).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads(1_u64.saturating_mul(d as u64)))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64.saturating_mul(d as u64)))
 	}
 	fn new_session(r: u32, c: u32) -> Weight {
 		Weight::from_parts(0_u64, 0)
@@ -169,10 +197,14 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
-	fn note_author() -> Weight {
+	fn note_author(d: u32) -> Weight {
 		Weight::from_parts(71_461_000_u64, 0)
-			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			// Standard Error: 0
+			.saturating_add(Weight::from_parts(38_176_000_u64, 0).saturating_mul(d as u64))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads(1_u64.saturating_mul(d as u64)))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64.saturating_mul(d as u64)))
 	}
 	fn update_bond(c: u32) -> Weight {
 		Weight::from_parts(55_336_000_u64, 0)
@@ -188,6 +220,27 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
+	fn delegate(c: u32) -> Weight {
+		Weight::from_parts(62_714_000_u64, 0)
+			// Standard Error: 0
+			.saturating_add(Weight::from_parts(163_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn undelegate(c: u32) -> Weight {
+		Weight::from_parts(58_902_000_u64, 0)
+			// Standard Error: 0
+			.saturating_add(Weight::from_parts(157_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn set_commission(c: u32) -> Weight {
+		Weight::from_parts(17_204_000_u64, 0)
+			// Standard Error: 0
+			.saturating_add(Weight::from_parts(41_000_u64, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 	fn new_session(r: u32, c: u32) -> Weight {
 		Weight::from_parts(0_u64, 0)
 			// Standard Error: 1_010_000
@@ -540,9 +540,12 @@ pub mod pallet {
 			<AnnouncedHrmpMessagesPerCandidate<T>>::put(hrmp_max_message_num_per_candidate);
 
 			// NOTE that the actual weight consumed by `on_finalize` may turn out lower.
+			//
+			// Every message taken from the outbound queue may also empty the queue of its
+			// recipient, in which case the message source clears its bookkeeping for the channel.
 			weight += T::DbWeight::get().reads_writes(
 				3 + hrmp_max_message_num_per_candidate as u64,
-				4 + hrmp_max_message_num_per_candidate as u64,
+				4 + 2 * hrmp_max_message_num_per_candidate as u64,
 			);
 
 			// Weight for updating the last relay chain block number in `on_finalize`.
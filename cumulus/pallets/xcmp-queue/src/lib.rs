@@ -32,6 +32,8 @@
 //! It is defined in the channel configuration.
 //! - `THRESHOLD_FACTOR` just declares which percentage of the max size is the actual threshold.
 //! If it's 2, then the threshold is half of the max size, if it's 4, it's a quarter, and so on.
+//!
+//! The state of the channels can be inspected with the [`runtime_api::XcmpQueueApi`].
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -47,6 +49,7 @@ mod tests;
 mod benchmarking;
 #[cfg(feature = "bridging")]
 pub mod bridging;
+pub mod runtime_api;
 pub mod weights;
 pub mod weights_ext;
 
@@ -55,7 +58,7 @@ pub use weights_ext::WeightInfoExt;
 
 extern crate alloc;
 
-use alloc::vec::Vec;
+use alloc::{collections::BTreeSet, vec::Vec};
 use bounded_collections::BoundedBTreeSet;
 use codec::{Decode, DecodeLimit, Encode, MaxEncodedLen};
 use cumulus_primitives_core::{
@@ -69,13 +72,17 @@ use frame_support::{
 	weights::{Weight, WeightMeter},
 	BoundedVec,
 };
+use frame_system::pallet_prelude::BlockNumberFor;
 use pallet_message_queue::OnQueueChanged;
 use polkadot_runtime_common::xcm_sender::PriceForMessageDelivery;
 use polkadot_runtime_parachains::FeeTracker;
 use scale_info::TypeInfo;
 use sp_core::MAX_POSSIBLE_ALLOCATION;
 use sp_runtime::{FixedU128, RuntimeDebug, Saturating, WeakBoundedVec};
-use xcm::{latest::prelude::*, VersionedLocation, VersionedXcm, WrapVersion, MAX_XCM_DECODE_DEPTH};
+use xcm::{
+	latest::prelude::*, IdentifyVersion, Version, VersionedLocation, VersionedXcm, WrapVersion,
+	MAX_XCM_DECODE_DEPTH,
+};
 use xcm_builder::InspectMessageQueues;
 use xcm_executor::traits::ConvertOrigin;
 
@@ -366,6 +373,26 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type DeliveryFeeFactor<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, FixedU128, ValueQuery, InitialFactor>;
+
+	/// The block in which the inbound queue of a sibling last became non-empty.
+	///
+	/// This is not the block in which its oldest queued message was received: the entry is only
+	/// reset once the queue is empty, so a queue that is never drained keeps the block in which
+	/// it started to fill up. Only contains the siblings with messages in their inbound queue.
+	/// Queues that were not empty before this was introduced are only tracked once they were
+	/// empty again.
+	#[pallet::storage]
+	pub(super) type InboundXcmpNonEmptySince<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, BlockNumberFor<T>, OptionQuery>;
+
+	/// The block in which the outbound queue to a sibling last became non-empty.
+	///
+	/// Like [`InboundXcmpNonEmptySince`], this is not the block in which the oldest queued page was
+	/// created, but a lower bound for it. Signals are not tracked since they are sent in the block
+	/// they are queued in.
+	#[pallet::storage]
+	pub(super) type OutboundXcmpNonEmptySince<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, BlockNumberFor<T>, OptionQuery>;
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -459,6 +486,67 @@ pub enum ChannelSignal {
 	Resume,
 }
 
+/// State of an inbound XCMP channel, as reported by the [`runtime_api::XcmpQueueApi`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct InboundChannelStatus<BlockNumber> {
+	/// The sibling sending on this channel.
+	pub sender: ParaId,
+	/// Whether the sender was told to suspend the channel because its queue is too long.
+	pub suspended: bool,
+	/// The number of pages in the queue, including overweight pages.
+	pub pages: u32,
+	/// The number of pages that are ready to be processed.
+	pub ready_pages: u32,
+	/// The number of messages in the queue, including overweight messages.
+	pub messages: u64,
+	/// The total size of the messages in the queue, in bytes.
+	pub size: u64,
+	/// The block in which the queue last became non-empty, if known.
+	///
+	/// The queue has not been empty since, but its oldest message may have been received later.
+	pub non_empty_since: Option<BlockNumber>,
+	/// The XCM versions of the queued messages.
+	pub xcm_versions: Vec<Version>,
+}
+
+/// State of an outbound XCMP channel, as reported by the [`runtime_api::XcmpQueueApi`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct OutboundChannelStatus<BlockNumber> {
+	/// The sibling receiving on this channel.
+	pub recipient: ParaId,
+	/// Whether the recipient told us to suspend the channel.
+	pub suspended: bool,
+	/// Whether a signal is waiting to be sent.
+	pub signals_queued: bool,
+	/// The number of pages waiting to be sent.
+	pub pages: u32,
+	/// The total size of the pages waiting to be sent, in bytes.
+	pub size: u64,
+	/// The block in which the queue last became non-empty, if known.
+	///
+	/// The queue has not been empty since, but its oldest message may have been received later.
+	pub non_empty_since: Option<BlockNumber>,
+	/// The XCM versions of the queued messages.
+	pub xcm_versions: Vec<Version>,
+}
+
+/// State of the XCMP queue, as reported by the [`runtime_api::XcmpQueueApi`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct XcmpQueueStatus<BlockNumber> {
+	/// Whether the execution of inbound messages is suspended.
+	pub suspended: bool,
+	/// The number of queued pages above which inbound channels are suspended.
+	pub suspend_threshold: u32,
+	/// The number of queued pages above which inbound messages are dropped.
+	pub drop_threshold: u32,
+	/// The number of queued pages below which suspended inbound channels are resumed.
+	pub resume_threshold: u32,
+	/// The inbound channels that are suspended or have messages queued.
+	pub inbound: Vec<InboundChannelStatus<BlockNumber>>,
+	/// The outbound channels that are suspended or have messages or signals queued.
+	pub outbound: Vec<OutboundChannelStatus<BlockNumber>>,
+}
+
 impl<T: Config> Pallet<T> {
 	/// Place a message `fragment` on the outgoing XCMP queue for `recipient`.
 	///
@@ -555,6 +643,12 @@ impl<T: Config> Pallet<T> {
 			// Need to add a new page.
 			let page_index = channel_details.last_index;
 			channel_details.last_index += 1;
+			if !have_active {
+				<OutboundXcmpNonEmptySince<T>>::insert(
+					recipient,
+					frame_system::Pallet::<T>::block_number(),
+				);
+			}
 			let mut new_page = format.encode();
 			new_page.extend_from_slice(&encoded_fragment[..]);
 			let last_page_size = new_page.len();
@@ -684,6 +778,95 @@ impl<T: Config> Pallet<T> {
 			.max(<T as crate::Config>::WeightInfo::on_idle_large_msg())
 	}
 
+	/// The state of the XCMP queue and of its suspended or non-empty channels.
+	///
+	/// `inbound_messages` returns the messages queued for a sibling by [`Config::XcmpQueue`],
+	/// which are decoded to report their XCM versions.
+	pub fn status(
+		inbound_messages: impl Fn(ParaId) -> Vec<Vec<u8>>,
+	) -> XcmpQueueStatus<BlockNumberFor<T>> {
+		let QueueConfigData { suspend_threshold, drop_threshold, resume_threshold } =
+			<QueueConfig<T>>::get();
+		let suspended_senders = <InboundXcmpSuspended<T>>::get();
+
+		let mut senders = suspended_senders.iter().copied().collect::<BTreeSet<_>>();
+		senders.extend(<InboundXcmpNonEmptySince<T>>::iter_keys());
+		let inbound = senders
+			.into_iter()
+			.map(|sender| {
+				let footprint = T::XcmpQueue::footprint(sender);
+				let xcm_versions = inbound_messages(sender)
+					.iter()
+					.flat_map(|message| Self::xcm_versions(message))
+					.collect::<BTreeSet<_>>();
+				InboundChannelStatus {
+					sender,
+					suspended: suspended_senders.contains(&sender),
+					pages: footprint.pages,
+					ready_pages: footprint.ready_pages,
+					messages: footprint.storage.count,
+					size: footprint.storage.size,
+					non_empty_since: <InboundXcmpNonEmptySince<T>>::get(sender),
+					xcm_versions: xcm_versions.into_iter().collect(),
+				}
+			})
+			.collect();
+
+		let outbound = <OutboundXcmpStatus<T>>::get()
+			.into_iter()
+			.map(|details| {
+				let mut size = 0;
+				let mut xcm_versions = BTreeSet::new();
+				for index in details.first_index..details.last_index {
+					let page = <OutboundXcmpMessages<T>>::get(details.recipient, index);
+					size += page.len() as u64;
+					xcm_versions.extend(Self::page_xcm_versions(&page));
+				}
+
+				OutboundChannelStatus {
+					recipient: details.recipient,
+					suspended: details.state == OutboundState::Suspended,
+					signals_queued: details.signals_exist,
+					pages: details.last_index.saturating_sub(details.first_index).into(),
+					size,
+					non_empty_since: <OutboundXcmpNonEmptySince<T>>::get(details.recipient),
+					xcm_versions: xcm_versions.into_iter().collect(),
+				}
+			})
+			.collect();
+
+		XcmpQueueStatus {
+			suspended: <QueueSuspended<T>>::get(),
+			suspend_threshold,
+			drop_threshold,
+			resume_threshold,
+			inbound,
+			outbound,
+		}
+	}
+
+	/// The XCM versions of the messages of an outbound page.
+	fn page_xcm_versions(mut page: &[u8]) -> BTreeSet<Version> {
+		if XcmpMessageFormat::decode(&mut page) != Ok(XcmpMessageFormat::ConcatenatedVersionedXcm) {
+			return BTreeSet::new()
+		}
+		Self::xcm_versions(page)
+	}
+
+	/// The XCM versions of concatenated encoded `VersionedXcm`s.
+	///
+	/// Stops at the first message that cannot be decoded.
+	fn xcm_versions(mut data: &[u8]) -> BTreeSet<Version> {
+		let mut versions = BTreeSet::new();
+		while !data.is_empty() {
+			match VersionedXcm::<()>::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut data) {
+				Ok(xcm) => versions.insert(xcm.identify_version()),
+				Err(_) => break,
+			};
+		}
+		versions
+	}
+
 	#[cfg(feature = "bridging")]
 	fn is_inbound_channel_suspended(sender: ParaId) -> bool {
 		<InboundXcmpSuspended<T>>::get().iter().any(|c| c == &sender)
@@ -702,6 +885,15 @@ impl<T: Config> Pallet<T> {
 impl<T: Config> OnQueueChanged<ParaId> for Pallet<T> {
 	// Suspends/Resumes the queue when certain thresholds are reached.
 	fn on_queue_changed(para: ParaId, fp: QueueFootprint) {
+		match (<InboundXcmpNonEmptySince<T>>::contains_key(para), fp.storage.count == 0) {
+			(true, true) => <InboundXcmpNonEmptySince<T>>::remove(para),
+			(false, false) => <InboundXcmpNonEmptySince<T>>::insert(
+				para,
+				frame_system::Pallet::<T>::block_number(),
+			),
+			_ => {},
+		}
+
 		let QueueConfigData { resume_threshold, suspend_threshold, .. } = <QueueConfig<T>>::get();
 
 		let mut suspended_channels = <InboundXcmpSuspended<T>>::get();
@@ -862,6 +1054,9 @@ impl<T: Config> XcmpMessageSource for Pallet<T> {
 					for i in first_index..last_index {
 						<OutboundXcmpMessages<T>>::remove(para_id, i);
 					}
+					if last_index > first_index {
+						<OutboundXcmpNonEmptySince<T>>::remove(para_id);
+					}
 					if signals_exist {
 						<SignalMessages<T>>::remove(para_id);
 					}
@@ -899,6 +1094,9 @@ impl<T: Config> XcmpMessageSource for Pallet<T> {
 				if page.len() < max_size_now {
 					<OutboundXcmpMessages<T>>::remove(para_id, first_index);
 					first_index += 1;
+					if first_index == last_index {
+						<OutboundXcmpNonEmptySince<T>>::remove(para_id);
+					}
 					page
 				} else {
 					continue
@@ -1028,6 +1226,7 @@ impl<T: Config> InspectMessageQueues for Pallet<T> {
 	fn clear_messages() {
 		// Best effort.
 		let _ = OutboundXcmpMessages::<T>::clear(u32::MAX, None);
+		let _ = OutboundXcmpNonEmptySince::<T>::clear(u32::MAX, None);
 		OutboundXcmpStatus::<T>::mutate(|details_vec| {
 			for details in details_vec {
 				details.first_index = 0;
@@ -143,6 +143,16 @@ parameter_types! {
 /// An `EnqueueMessage` implementation that puts all messages in thread-local storage.
 pub struct EnqueueToLocalStorage<T>(PhantomData<T>);
 
+impl<T> EnqueueToLocalStorage<T> {
+	/// The messages enqueued for `origin`.
+	pub fn queued_messages(origin: ParaId) -> Vec<Vec<u8>> {
+		EnqueuedMessages::get()
+			.into_iter()
+			.filter_map(|(o, m)| (o == origin).then_some(m))
+			.collect()
+	}
+}
+
 impl<T: OnQueueChanged<ParaId>> EnqueueMessage<ParaId> for EnqueueToLocalStorage<T> {
 	type MaxMessageLen = sp_core::ConstU32<256>;
 
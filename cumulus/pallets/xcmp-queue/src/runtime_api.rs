@@ -0,0 +1,27 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the XCMP queue pallet.
+
+use crate::XcmpQueueStatus;
+use sp_runtime::traits::NumberFor;
+
+sp_api::decl_runtime_apis! {
+	/// API to inspect the XCMP channels, for example to find out why messages are stuck.
+	pub trait XcmpQueueApi {
+		/// The state of the queue and of the channels that are suspended or have messages queued.
+		fn status() -> XcmpQueueStatus<NumberFor<Block>>;
+	}
+}
@@ -14,7 +14,7 @@
 // limitations under the License.
 
 use super::{
-	mock::{mk_page, versioned_xcm, EnqueuedMessages, HRMP_PARA_ID},
+	mock::{mk_page, versioned_xcm, EnqueueToLocalStorage, EnqueuedMessages, HRMP_PARA_ID},
 	*,
 };
 use XcmpMessageFormat::*;
@@ -927,3 +927,72 @@ fn page_not_modified_when_fragment_does_not_fit() {
 		}
 	});
 }
+
+#[test]
+fn status_reports_inbound_channels() {
+	new_test_ext().execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(3);
+		let xcm = VersionedXcm::<Test>::from(Xcm::<Test>(vec![ClearOrigin])).encode();
+		let data = [ConcatenatedVersionedXcm.encode(), xcm.clone(), xcm.clone()].concat();
+
+		XcmpQueue::handle_xcmp_messages(once((1000.into(), 1, data.as_slice())), Weight::MAX);
+
+		assert_eq!(
+			XcmpQueue::status(EnqueueToLocalStorage::<XcmpQueue>::queued_messages).inbound,
+			vec![InboundChannelStatus {
+				sender: 1000.into(),
+				suspended: false,
+				pages: 1,
+				ready_pages: 1,
+				messages: 2,
+				size: 2 * xcm.len() as u64,
+				non_empty_since: Some(3),
+				xcm_versions: vec![xcm::latest::VERSION],
+			}],
+		);
+
+		// The queue is not tracked anymore once it is empty.
+		EnqueueToLocalStorage::<XcmpQueue>::sweep_queue(1000.into());
+		assert_eq!(InboundXcmpNonEmptySince::<Test>::get(ParaId::from(1000)), None);
+		assert!(XcmpQueue::status(EnqueueToLocalStorage::<XcmpQueue>::queued_messages)
+			.inbound
+			.is_empty());
+	})
+}
+
+#[test]
+fn status_reports_outbound_channels() {
+	new_test_ext().execute_with(|| {
+		let sibling_para_id = ParaId::from(2001);
+		ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(sibling_para_id);
+		let destination: Location = (Parent, Parachain(sibling_para_id.into())).into();
+
+		frame_system::Pallet::<Test>::set_block_number(5);
+		assert_ok!(send_xcm::<XcmpQueue>(destination.clone(), Xcm(vec![ClearOrigin])));
+		frame_system::Pallet::<Test>::set_block_number(6);
+		assert_ok!(send_xcm::<XcmpQueue>(destination, Xcm(vec![ClearOrigin])));
+
+		let status = XcmpQueue::status(|_| Vec::new());
+		assert_eq!(status.suspend_threshold, 32);
+		assert_eq!(
+			status.outbound,
+			vec![OutboundChannelStatus {
+				recipient: sibling_para_id,
+				suspended: false,
+				signals_queued: false,
+				pages: 1,
+				size: OutboundXcmpMessages::<Test>::get(sibling_para_id, 0).len() as u64,
+				non_empty_since: Some(5),
+				xcm_versions: vec![xcm::latest::VERSION],
+			}],
+		);
+
+		// Sending the page empties the queue.
+		XcmpQueue::take_outbound_messages(usize::MAX);
+		assert_eq!(OutboundXcmpNonEmptySince::<Test>::get(sibling_para_id), None);
+		assert!(XcmpQueue::status(|_| Vec::new())
+			.outbound
+			.iter()
+			.all(|channel| channel.pages == 0));
+	})
+}
@@ -197,7 +197,7 @@ mod tests {
 	use super::*;
 	use frame_support::{
 		derive_impl, parameter_types,
-		traits::{ConstU32, FindAuthor, ValidatorRegistration},
+		traits::{ConstU32, ConstU64, FindAuthor, ValidatorRegistration},
 		PalletId,
 	};
 	use frame_system::{limits, EnsureRoot};
@@ -218,7 +218,7 @@ mod tests {
 		{
 			System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
 			Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-			CollatorSelection: pallet_collator_selection::{Pallet, Call, Storage, Event<T>},
+			CollatorSelection: pallet_collator_selection::{Pallet, Call, Storage, Event<T>, HoldReason},
 		}
 	);
 
@@ -282,6 +282,7 @@ mod tests {
 	impl pallet_collator_selection::Config for Test {
 		type RuntimeEvent = RuntimeEvent;
 		type Currency = Balances;
+		type RuntimeHoldReason = RuntimeHoldReason;
 		type UpdateOrigin = EnsureRoot<AccountId>;
 		type PotId = PotId;
 		type MaxCandidates = ConstU32<20>;
@@ -291,6 +292,9 @@ mod tests {
 		type ValidatorIdOf = IdentityCollator;
 		type ValidatorRegistration = IsRegistered;
 		type KickThreshold = ();
+		type MinDelegation = ConstU64<1>;
+		type MaxDelegatorsPerCandidate = ConstU32<20>;
+		type CommissionIncreaseDelay = ();
 		type WeightInfo = ();
 	}
 
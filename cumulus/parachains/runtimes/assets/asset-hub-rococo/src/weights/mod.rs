@@ -29,7 +29,6 @@ pub mod pallet_assets_foreign;
 pub mod pallet_assets_local;
 pub mod pallet_assets_pool;
 pub mod pallet_balances;
-pub mod pallet_collator_selection;
 pub mod pallet_message_queue;
 pub mod pallet_multisig;
 pub mod pallet_nft_fractionalization;
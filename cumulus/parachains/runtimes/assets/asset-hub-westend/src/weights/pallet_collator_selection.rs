@@ -1,280 +0,0 @@
-// Copyright (C) Parity Technologies (UK) Ltd.
-// SPDX-License-Identifier: Apache-2.0
-
-// Licensed under the Apache License, Version 2.0 (the "License");
-// you may not use this file except in compliance with the License.
-// You may obtain a copy of the License at
-//
-// 	http://www.apache.org/licenses/LICENSE-2.0
-//
-// Unless required by applicable law or agreed to in writing, software
-// distributed under the License is distributed on an "AS IS" BASIS,
-// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
-// See the License for the specific language governing permissions and
-// limitations under the License.
-
-//! Autogenerated weights for `pallet_collator_selection`
-//!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 32.0.0
-//! DATE: 2025-02-21, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `73b9817d6032`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
-//! WASM-EXECUTION: `Compiled`, CHAIN: `None`, DB CACHE: 1024
-
-// Executed Command:
-// frame-omni-bencher
-// v1
-// benchmark
-// pallet
-// --extrinsic=*
-// --runtime=target/production/wbuild/asset-hub-westend-runtime/asset_hub_westend_runtime.wasm
-// --pallet=pallet_collator_selection
-// --header=/__w/polkadot-sdk/polkadot-sdk/cumulus/file_header.txt
-// --output=./cumulus/parachains/runtimes/assets/asset-hub-westend/src/weights
-// --wasm-execution=compiled
-// --steps=50
-// --repeat=20
-// --heap-pages=4096
-// --no-storage-info
-// --no-min-squares
-// --no-median-slopes
-
-#![cfg_attr(rustfmt, rustfmt_skip)]
-#![allow(unused_parens)]
-#![allow(unused_imports)]
-#![allow(missing_docs)]
-
-use frame_support::{traits::Get, weights::Weight};
-use core::marker::PhantomData;
-
-/// Weight functions for `pallet_collator_selection`.
-pub struct WeightInfo<T>(PhantomData<T>);
-impl<T: frame_system::Config> pallet_collator_selection::WeightInfo for WeightInfo<T> {
-	/// Storage: `Session::NextKeys` (r:20 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:0 w:1)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[1, 20]`.
-	fn set_invulnerables(b: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `164 + b * (79 ±0)`
-		//  Estimated: `1155 + b * (2555 ±0)`
-		// Minimum execution time: 12_971_000 picoseconds.
-		Weight::from_parts(10_915_736, 0)
-			.saturating_add(Weight::from_parts(0, 1155))
-			// Standard Error: 10_601
-			.saturating_add(Weight::from_parts(4_079_577, 0).saturating_mul(b.into()))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
-			.saturating_add(T::DbWeight::get().writes(1))
-			.saturating_add(Weight::from_parts(0, 2555).saturating_mul(b.into()))
-	}
-	/// Storage: `Session::NextKeys` (r:1 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:1)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[1, 19]`.
-	/// The range of component `c` is `[1, 99]`.
-	fn add_invulnerable(b: u32, c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `791 + b * (32 ±0) + c * (53 ±0)`
-		//  Estimated: `6287 + b * (37 ±0) + c * (53 ±0)`
-		// Minimum execution time: 50_487_000 picoseconds.
-		Weight::from_parts(50_631_813, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 11_165
-			.saturating_add(Weight::from_parts(148_146, 0).saturating_mul(b.into()))
-			// Standard Error: 2_116
-			.saturating_add(Weight::from_parts(142_897, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().writes(3))
-			.saturating_add(Weight::from_parts(0, 37).saturating_mul(b.into()))
-			.saturating_add(Weight::from_parts(0, 53).saturating_mul(c.into()))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:1)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[5, 20]`.
-	fn remove_invulnerable(b: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `153 + b * (32 ±0)`
-		//  Estimated: `6287`
-		// Minimum execution time: 13_373_000 picoseconds.
-		Weight::from_parts(13_452_303, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 2_220
-			.saturating_add(Weight::from_parts(157_264, 0).saturating_mul(b.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
-	}
-	/// Storage: `CollatorSelection::DesiredCandidates` (r:0 w:1)
-	/// Proof: `CollatorSelection::DesiredCandidates` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
-	fn set_desired_candidates() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 5_211_000 picoseconds.
-		Weight::from_parts(5_480_000, 0)
-			.saturating_add(Weight::from_parts(0, 0))
-			.saturating_add(T::DbWeight::get().writes(1))
-	}
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:100 w:100)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:100)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[0, 100]`.
-	/// The range of component `k` is `[0, 100]`.
-	fn set_candidacy_bond(c: u32, k: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0 + c * (180 ±0) + k * (112 ±0)`
-		//  Estimated: `6287 + c * (901 ±29) + k * (901 ±29)`
-		// Minimum execution time: 11_494_000 picoseconds.
-		Weight::from_parts(11_703_000, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 178_455
-			.saturating_add(Weight::from_parts(6_093_663, 0).saturating_mul(c.into()))
-			// Standard Error: 178_455
-			.saturating_add(Weight::from_parts(5_683_693, 0).saturating_mul(k.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(k.into())))
-			.saturating_add(Weight::from_parts(0, 901).saturating_mul(c.into()))
-			.saturating_add(Weight::from_parts(0, 901).saturating_mul(k.into()))
-	}
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[4, 100]`.
-	fn update_bond(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `321 + c * (50 ±0)`
-		//  Estimated: `6287`
-		// Minimum execution time: 30_106_000 picoseconds.
-		Weight::from_parts(32_731_556, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 3_574
-			.saturating_add(Weight::from_parts(137_212, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `Session::NextKeys` (r:1 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[1, 99]`.
-	fn register_as_candidate(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `758 + c * (52 ±0)`
-		//  Estimated: `6287 + c * (54 ±0)`
-		// Minimum execution time: 43_366_000 picoseconds.
-		Weight::from_parts(48_635_091, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 2_931
-			.saturating_add(Weight::from_parts(163_848, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().writes(2))
-			.saturating_add(Weight::from_parts(0, 54).saturating_mul(c.into()))
-	}
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `Session::NextKeys` (r:1 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:2)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[4, 100]`.
-	fn take_candidate_slot(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `926 + c * (52 ±0)`
-		//  Estimated: `6287 + c * (55 ±0)`
-		// Minimum execution time: 60_407_000 picoseconds.
-		Weight::from_parts(66_549_367, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 3_685
-			.saturating_add(Weight::from_parts(186_945, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(5))
-			.saturating_add(T::DbWeight::get().writes(4))
-			.saturating_add(Weight::from_parts(0, 55).saturating_mul(c.into()))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[4, 100]`.
-	fn leave_intent(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `348 + c * (48 ±0)`
-		//  Estimated: `6287`
-		// Minimum execution time: 32_931_000 picoseconds.
-		Weight::from_parts(37_172_964, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 4_054
-			.saturating_add(Weight::from_parts(189_751, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(2))
-	}
-	/// Storage: `System::Account` (r:2 w:2)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	fn note_author() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `103`
-		//  Estimated: `6196`
-		// Minimum execution time: 43_692_000 picoseconds.
-		Weight::from_parts(44_666_000, 0)
-			.saturating_add(Weight::from_parts(0, 6196))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(3))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:100 w:0)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::DesiredCandidates` (r:1 w:0)
-	/// Proof: `CollatorSelection::DesiredCandidates` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:97 w:97)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// The range of component `r` is `[1, 100]`.
-	/// The range of component `c` is `[1, 100]`.
-	fn new_session(r: u32, c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `2214 + c * (97 ±0) + r * (112 ±0)`
-		//  Estimated: `6287 + c * (2519 ±0) + r * (2603 ±0)`
-		// Minimum execution time: 20_277_000 picoseconds.
-		Weight::from_parts(20_618_000, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 313_955
-			.saturating_add(Weight::from_parts(14_412_840, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
-			.saturating_add(Weight::from_parts(0, 2519).saturating_mul(c.into()))
-			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
-	}
-}
@@ -145,7 +145,8 @@ pub type UncheckedExtrinsic =
 
 /// Migrations to apply on runtime upgrade.
 pub type Migrations = (
-	pallet_collator_selection::migration::v2::MigrationToV2<Runtime>,
+	pallet_collator_selection::migration::v2::MigrationToV2<Runtime, Balances>,
+	pallet_collator_selection::migration::v3::MigrationToV3<Runtime, Balances>,
 	pallet_multisig::migrations::v1::MigrateToV1<Runtime>,
 	InitStorageVersions,
 	// unreleased
@@ -522,6 +523,7 @@ pub type CollatorSelectionUpdateOrigin = EnsureRoot<AccountId>;
 impl pallet_collator_selection::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type UpdateOrigin = CollatorSelectionUpdateOrigin;
 	type PotId = PotId;
 	type MaxCandidates = ConstU32<100>;
@@ -532,7 +534,11 @@ impl pallet_collator_selection::Config for Runtime {
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
 	type ValidatorRegistration = Session;
-	type WeightInfo = weights::pallet_collator_selection::WeightInfo<Runtime>;
+	type MinDelegation = ExistentialDeposit;
+	type MaxDelegatorsPerCandidate = ConstU32<64>;
+	type CommissionIncreaseDelay = ConstU32<PERIOD>;
+	// Replace this with the weights benchmarked for this runtime.
+	type WeightInfo = pallet_collator_selection::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -922,6 +928,14 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl cumulus_pallet_xcmp_queue::runtime_api::XcmpQueueApi<Block> for Runtime {
+		fn status() -> cumulus_pallet_xcmp_queue::XcmpQueueStatus<BlockNumber> {
+			XcmpQueue::status(|para| {
+				MessageQueue::queued_messages(&AggregateMessageOrigin::Sibling(para))
+			})
+		}
+	}
+
 	impl bp_westend::WestendFinalityApi<Block> for Runtime {
 		fn best_finalized() -> Option<HeaderId<bp_westend::Hash, bp_westend::BlockNumber>> {
 			BridgeWestendGrandpa::best_finalized()
@@ -36,7 +36,6 @@ pub mod pallet_bridge_messages_rococo_to_westend;
 pub mod pallet_bridge_parachains;
 pub mod pallet_bridge_relayers_legacy;
 pub mod pallet_bridge_relayers_permissionless_lanes;
-pub mod pallet_collator_selection;
 pub mod pallet_message_queue;
 pub mod pallet_multisig;
 pub mod pallet_session;
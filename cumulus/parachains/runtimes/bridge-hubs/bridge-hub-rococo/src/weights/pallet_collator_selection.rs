@@ -1,280 +0,0 @@
-// Copyright (C) Parity Technologies (UK) Ltd.
-// SPDX-License-Identifier: Apache-2.0
-
-// Licensed under the Apache License, Version 2.0 (the "License");
-// you may not use this file except in compliance with the License.
-// You may obtain a copy of the License at
-//
-// 	http://www.apache.org/licenses/LICENSE-2.0
-//
-// Unless required by applicable law or agreed to in writing, software
-// distributed under the License is distributed on an "AS IS" BASIS,
-// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
-// See the License for the specific language governing permissions and
-// limitations under the License.
-
-//! Autogenerated weights for `pallet_collator_selection`
-//!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 32.0.0
-//! DATE: 2025-02-21, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `814af52b0d43`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
-//! WASM-EXECUTION: `Compiled`, CHAIN: `None`, DB CACHE: 1024
-
-// Executed Command:
-// frame-omni-bencher
-// v1
-// benchmark
-// pallet
-// --extrinsic=*
-// --runtime=target/production/wbuild/bridge-hub-rococo-runtime/bridge_hub_rococo_runtime.wasm
-// --pallet=pallet_collator_selection
-// --header=/__w/polkadot-sdk/polkadot-sdk/cumulus/file_header.txt
-// --output=./cumulus/parachains/runtimes/bridge-hubs/bridge-hub-rococo/src/weights
-// --wasm-execution=compiled
-// --steps=50
-// --repeat=20
-// --heap-pages=4096
-// --no-storage-info
-// --no-min-squares
-// --no-median-slopes
-
-#![cfg_attr(rustfmt, rustfmt_skip)]
-#![allow(unused_parens)]
-#![allow(unused_imports)]
-#![allow(missing_docs)]
-
-use frame_support::{traits::Get, weights::Weight};
-use core::marker::PhantomData;
-
-/// Weight functions for `pallet_collator_selection`.
-pub struct WeightInfo<T>(PhantomData<T>);
-impl<T: frame_system::Config> pallet_collator_selection::WeightInfo for WeightInfo<T> {
-	/// Storage: `Session::NextKeys` (r:20 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:0 w:1)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[1, 20]`.
-	fn set_invulnerables(b: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `196 + b * (79 ±0)`
-		//  Estimated: `1187 + b * (2555 ±0)`
-		// Minimum execution time: 13_338_000 picoseconds.
-		Weight::from_parts(11_986_525, 0)
-			.saturating_add(Weight::from_parts(0, 1187))
-			// Standard Error: 10_072
-			.saturating_add(Weight::from_parts(4_110_140, 0).saturating_mul(b.into()))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
-			.saturating_add(T::DbWeight::get().writes(1))
-			.saturating_add(Weight::from_parts(0, 2555).saturating_mul(b.into()))
-	}
-	/// Storage: `Session::NextKeys` (r:1 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:1)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[1, 19]`.
-	/// The range of component `c` is `[1, 99]`.
-	fn add_invulnerable(b: u32, c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `757 + b * (32 ±0) + c * (53 ±0)`
-		//  Estimated: `6287 + b * (37 ±0) + c * (53 ±0)`
-		// Minimum execution time: 50_026_000 picoseconds.
-		Weight::from_parts(46_794_223, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 16_127
-			.saturating_add(Weight::from_parts(268_509, 0).saturating_mul(b.into()))
-			// Standard Error: 3_056
-			.saturating_add(Weight::from_parts(159_092, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().writes(3))
-			.saturating_add(Weight::from_parts(0, 37).saturating_mul(b.into()))
-			.saturating_add(Weight::from_parts(0, 53).saturating_mul(c.into()))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:1)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[5, 20]`.
-	fn remove_invulnerable(b: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `82 + b * (32 ±0)`
-		//  Estimated: `6287`
-		// Minimum execution time: 12_569_000 picoseconds.
-		Weight::from_parts(12_395_889, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 2_405
-			.saturating_add(Weight::from_parts(198_537, 0).saturating_mul(b.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
-	}
-	/// Storage: `CollatorSelection::DesiredCandidates` (r:0 w:1)
-	/// Proof: `CollatorSelection::DesiredCandidates` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
-	fn set_desired_candidates() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 5_392_000 picoseconds.
-		Weight::from_parts(5_704_000, 0)
-			.saturating_add(Weight::from_parts(0, 0))
-			.saturating_add(T::DbWeight::get().writes(1))
-	}
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:100 w:100)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:100)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[0, 100]`.
-	/// The range of component `k` is `[0, 100]`.
-	fn set_candidacy_bond(c: u32, k: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0 + c * (182 ±0) + k * (115 ±0)`
-		//  Estimated: `6287 + c * (901 ±29) + k * (901 ±29)`
-		// Minimum execution time: 10_728_000 picoseconds.
-		Weight::from_parts(11_051_000, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 178_671
-			.saturating_add(Weight::from_parts(6_119_294, 0).saturating_mul(c.into()))
-			// Standard Error: 178_671
-			.saturating_add(Weight::from_parts(5_697_217, 0).saturating_mul(k.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(k.into())))
-			.saturating_add(Weight::from_parts(0, 901).saturating_mul(c.into()))
-			.saturating_add(Weight::from_parts(0, 901).saturating_mul(k.into()))
-	}
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[3, 100]`.
-	fn update_bond(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `282 + c * (49 ±0)`
-		//  Estimated: `6287`
-		// Minimum execution time: 32_598_000 picoseconds.
-		Weight::from_parts(35_209_960, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 2_669
-			.saturating_add(Weight::from_parts(195_321, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `Session::NextKeys` (r:1 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[1, 99]`.
-	fn register_as_candidate(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `727 + c * (52 ±0)`
-		//  Estimated: `6287 + c * (54 ±0)`
-		// Minimum execution time: 43_069_000 picoseconds.
-		Weight::from_parts(46_532_260, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 2_354
-			.saturating_add(Weight::from_parts(232_318, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().writes(2))
-			.saturating_add(Weight::from_parts(0, 54).saturating_mul(c.into()))
-	}
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `Session::NextKeys` (r:1 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:2)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[3, 100]`.
-	fn take_candidate_slot(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `867 + c * (53 ±0)`
-		//  Estimated: `6287 + c * (54 ±0)`
-		// Minimum execution time: 60_755_000 picoseconds.
-		Weight::from_parts(66_976_829, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 3_178
-			.saturating_add(Weight::from_parts(168_913, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(5))
-			.saturating_add(T::DbWeight::get().writes(4))
-			.saturating_add(Weight::from_parts(0, 54).saturating_mul(c.into()))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[3, 100]`.
-	fn leave_intent(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `310 + c * (48 ±0)`
-		//  Estimated: `6287`
-		// Minimum execution time: 35_003_000 picoseconds.
-		Weight::from_parts(37_953_436, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 3_452
-			.saturating_add(Weight::from_parts(198_637, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(2))
-	}
-	/// Storage: `System::Account` (r:2 w:2)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	fn note_author() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `155`
-		//  Estimated: `6196`
-		// Minimum execution time: 43_916_000 picoseconds.
-		Weight::from_parts(44_865_000, 0)
-			.saturating_add(Weight::from_parts(0, 6196))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(3))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:100 w:0)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::DesiredCandidates` (r:1 w:0)
-	/// Proof: `CollatorSelection::DesiredCandidates` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:97 w:97)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// The range of component `r` is `[1, 100]`.
-	/// The range of component `c` is `[1, 100]`.
-	fn new_session(r: u32, c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `2265 + c * (97 ±0) + r * (114 ±0)`
-		//  Estimated: `6287 + c * (2519 ±0) + r * (2603 ±0)`
-		// Minimum execution time: 22_586_000 picoseconds.
-		Weight::from_parts(23_013_000, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 312_023
-			.saturating_add(Weight::from_parts(14_508_118, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
-			.saturating_add(Weight::from_parts(0, 2519).saturating_mul(c.into()))
-			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
-	}
-}
@@ -34,7 +34,6 @@ pub mod pallet_bridge_grandpa;
 pub mod pallet_bridge_messages;
 pub mod pallet_bridge_parachains;
 pub mod pallet_bridge_relayers;
-pub mod pallet_collator_selection;
 pub mod pallet_message_queue;
 pub mod pallet_multisig;
 pub mod pallet_session;
@@ -531,6 +531,7 @@ pub type CollatorSelectionUpdateOrigin = EitherOfDiverse<
 impl pallet_collator_selection::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type UpdateOrigin = CollatorSelectionUpdateOrigin;
 	type PotId = PotId;
 	type MaxCandidates = ConstU32<100>;
@@ -541,7 +542,11 @@ impl pallet_collator_selection::Config for Runtime {
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
 	type ValidatorRegistration = Session;
-	type WeightInfo = weights::pallet_collator_selection::WeightInfo<Runtime>;
+	type MinDelegation = ExistentialDeposit;
+	type MaxDelegatorsPerCandidate = ConstU32<64>;
+	type CommissionIncreaseDelay = ConstU32<PERIOD>;
+	// Replace this with the weights benchmarked for this runtime.
+	type WeightInfo = pallet_collator_selection::weights::SubstrateWeight<Runtime>;
 }
 
 pub const ALLIANCE_MOTION_DURATION: BlockNumber = 5 * DAYS;
@@ -763,7 +768,8 @@ pub type UncheckedExtrinsic =
 /// `OnRuntimeUpgrade`. Included migrations must be idempotent.
 type Migrations = (
 	// unreleased
-	pallet_collator_selection::migration::v2::MigrationToV2<Runtime>,
+	pallet_collator_selection::migration::v2::MigrationToV2<Runtime, Balances>,
+	pallet_collator_selection::migration::v3::MigrationToV3<Runtime, Balances>,
 	// unreleased
 	cumulus_pallet_xcmp_queue::migration::v4::MigrationToV4<Runtime>,
 	cumulus_pallet_xcmp_queue::migration::v5::MigrateV4ToV5<Runtime>,
@@ -1068,6 +1074,14 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl cumulus_pallet_xcmp_queue::runtime_api::XcmpQueueApi<Block> for Runtime {
+		fn status() -> cumulus_pallet_xcmp_queue::XcmpQueueStatus<BlockNumber> {
+			XcmpQueue::status(|para| {
+				MessageQueue::queued_messages(&AggregateMessageOrigin::Sibling(para))
+			})
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
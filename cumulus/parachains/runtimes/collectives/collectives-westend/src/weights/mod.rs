@@ -23,7 +23,6 @@ pub mod frame_system_extensions;
 pub mod pallet_alliance;
 pub mod pallet_asset_rate;
 pub mod pallet_balances;
-pub mod pallet_collator_selection;
 pub mod pallet_collective;
 pub mod pallet_collective_content;
 pub mod pallet_core_fellowship_ambassador_core;
@@ -1,280 +0,0 @@
-// Copyright (C) Parity Technologies (UK) Ltd.
-// SPDX-License-Identifier: Apache-2.0
-
-// Licensed under the Apache License, Version 2.0 (the "License");
-// you may not use this file except in compliance with the License.
-// You may obtain a copy of the License at
-//
-// 	http://www.apache.org/licenses/LICENSE-2.0
-//
-// Unless required by applicable law or agreed to in writing, software
-// distributed under the License is distributed on an "AS IS" BASIS,
-// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
-// See the License for the specific language governing permissions and
-// limitations under the License.
-
-//! Autogenerated weights for `pallet_collator_selection`
-//!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 32.0.0
-//! DATE: 2025-02-21, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `e0f303704c84`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
-//! WASM-EXECUTION: `Compiled`, CHAIN: `None`, DB CACHE: 1024
-
-// Executed Command:
-// frame-omni-bencher
-// v1
-// benchmark
-// pallet
-// --extrinsic=*
-// --runtime=target/production/wbuild/collectives-westend-runtime/collectives_westend_runtime.wasm
-// --pallet=pallet_collator_selection
-// --header=/__w/polkadot-sdk/polkadot-sdk/cumulus/file_header.txt
-// --output=./cumulus/parachains/runtimes/collectives/collectives-westend/src/weights
-// --wasm-execution=compiled
-// --steps=50
-// --repeat=20
-// --heap-pages=4096
-// --no-storage-info
-// --no-min-squares
-// --no-median-slopes
-
-#![cfg_attr(rustfmt, rustfmt_skip)]
-#![allow(unused_parens)]
-#![allow(unused_imports)]
-#![allow(missing_docs)]
-
-use frame_support::{traits::Get, weights::Weight};
-use core::marker::PhantomData;
-
-/// Weight functions for `pallet_collator_selection`.
-pub struct WeightInfo<T>(PhantomData<T>);
-impl<T: frame_system::Config> pallet_collator_selection::WeightInfo for WeightInfo<T> {
-	/// Storage: `Session::NextKeys` (r:20 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:0 w:1)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[1, 20]`.
-	fn set_invulnerables(b: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `164 + b * (79 ±0)`
-		//  Estimated: `1155 + b * (2555 ±0)`
-		// Minimum execution time: 13_764_000 picoseconds.
-		Weight::from_parts(11_523_628, 0)
-			.saturating_add(Weight::from_parts(0, 1155))
-			// Standard Error: 10_118
-			.saturating_add(Weight::from_parts(4_062_086, 0).saturating_mul(b.into()))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
-			.saturating_add(T::DbWeight::get().writes(1))
-			.saturating_add(Weight::from_parts(0, 2555).saturating_mul(b.into()))
-	}
-	/// Storage: `Session::NextKeys` (r:1 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:1)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[1, 19]`.
-	/// The range of component `c` is `[1, 99]`.
-	fn add_invulnerable(b: u32, c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `793 + b * (32 ±0) + c * (53 ±0)`
-		//  Estimated: `6287 + b * (37 ±0) + c * (53 ±0)`
-		// Minimum execution time: 51_019_000 picoseconds.
-		Weight::from_parts(52_207_693, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 9_905
-			.saturating_add(Weight::from_parts(87_209, 0).saturating_mul(b.into()))
-			// Standard Error: 1_877
-			.saturating_add(Weight::from_parts(136_234, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().writes(3))
-			.saturating_add(Weight::from_parts(0, 37).saturating_mul(b.into()))
-			.saturating_add(Weight::from_parts(0, 53).saturating_mul(c.into()))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:1)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[5, 20]`.
-	fn remove_invulnerable(b: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `153 + b * (32 ±0)`
-		//  Estimated: `6287`
-		// Minimum execution time: 13_569_000 picoseconds.
-		Weight::from_parts(13_828_814, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 2_278
-			.saturating_add(Weight::from_parts(153_633, 0).saturating_mul(b.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
-	}
-	/// Storage: `CollatorSelection::DesiredCandidates` (r:0 w:1)
-	/// Proof: `CollatorSelection::DesiredCandidates` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
-	fn set_desired_candidates() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 5_650_000 picoseconds.
-		Weight::from_parts(5_863_000, 0)
-			.saturating_add(Weight::from_parts(0, 0))
-			.saturating_add(T::DbWeight::get().writes(1))
-	}
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:100 w:100)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:100)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[0, 100]`.
-	/// The range of component `k` is `[0, 100]`.
-	fn set_candidacy_bond(c: u32, k: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0 + c * (180 ±0) + k * (113 ±0)`
-		//  Estimated: `6287 + c * (901 ±29) + k * (901 ±29)`
-		// Minimum execution time: 11_764_000 picoseconds.
-		Weight::from_parts(12_075_000, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 182_131
-			.saturating_add(Weight::from_parts(6_205_013, 0).saturating_mul(c.into()))
-			// Standard Error: 182_131
-			.saturating_add(Weight::from_parts(5_799_911, 0).saturating_mul(k.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(k.into())))
-			.saturating_add(Weight::from_parts(0, 901).saturating_mul(c.into()))
-			.saturating_add(Weight::from_parts(0, 901).saturating_mul(k.into()))
-	}
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[4, 100]`.
-	fn update_bond(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `323 + c * (50 ±0)`
-		//  Estimated: `6287`
-		// Minimum execution time: 30_863_000 picoseconds.
-		Weight::from_parts(34_693_079, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 3_366
-			.saturating_add(Weight::from_parts(196_136, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `Session::NextKeys` (r:1 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[1, 99]`.
-	fn register_as_candidate(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `758 + c * (52 ±0)`
-		//  Estimated: `6287 + c * (54 ±0)`
-		// Minimum execution time: 43_791_000 picoseconds.
-		Weight::from_parts(48_850_114, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 2_295
-			.saturating_add(Weight::from_parts(177_217, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().writes(2))
-			.saturating_add(Weight::from_parts(0, 54).saturating_mul(c.into()))
-	}
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidacyBond` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `Session::NextKeys` (r:1 w:0)
-	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:2)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[4, 100]`.
-	fn take_candidate_slot(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `926 + c * (52 ±0)`
-		//  Estimated: `6287 + c * (55 ±0)`
-		// Minimum execution time: 61_972_000 picoseconds.
-		Weight::from_parts(65_916_131, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 4_193
-			.saturating_add(Weight::from_parts(272_995, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(5))
-			.saturating_add(T::DbWeight::get().writes(4))
-			.saturating_add(Weight::from_parts(0, 55).saturating_mul(c.into()))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// The range of component `c` is `[4, 100]`.
-	fn leave_intent(c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `348 + c * (48 ±0)`
-		//  Estimated: `6287`
-		// Minimum execution time: 37_663_000 picoseconds.
-		Weight::from_parts(37_217_061, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 3_895
-			.saturating_add(Weight::from_parts(325_751, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(2))
-	}
-	/// Storage: `System::Account` (r:2 w:2)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	fn note_author() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `103`
-		//  Estimated: `6196`
-		// Minimum execution time: 44_444_000 picoseconds.
-		Weight::from_parts(45_661_000, 0)
-			.saturating_add(Weight::from_parts(0, 6196))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(3))
-	}
-	/// Storage: `CollatorSelection::CandidateList` (r:1 w:0)
-	/// Proof: `CollatorSelection::CandidateList` (`max_values`: Some(1), `max_size`: Some(4802), added: 5297, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:100 w:0)
-	/// Proof: `CollatorSelection::LastAuthoredBlock` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
-	/// Proof: `CollatorSelection::Invulnerables` (`max_values`: Some(1), `max_size`: Some(641), added: 1136, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorSelection::DesiredCandidates` (r:1 w:0)
-	/// Proof: `CollatorSelection::DesiredCandidates` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:97 w:97)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// The range of component `r` is `[1, 100]`.
-	/// The range of component `c` is `[1, 100]`.
-	fn new_session(r: u32, c: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `2251 + c * (97 ±0) + r * (112 ±0)`
-		//  Estimated: `6287 + c * (2519 ±0) + r * (2603 ±0)`
-		// Minimum execution time: 23_760_000 picoseconds.
-		Weight::from_parts(24_070_000, 0)
-			.saturating_add(Weight::from_parts(0, 6287))
-			// Standard Error: 324_546
-			.saturating_add(Weight::from_parts(15_228_943, 0).saturating_mul(c.into()))
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
-			.saturating_add(Weight::from_parts(0, 2519).saturating_mul(c.into()))
-			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
-	}
-}
@@ -26,7 +26,6 @@ pub mod frame_system;
 pub mod frame_system_extensions;
 pub mod pallet_balances;
 pub mod pallet_broker;
-pub mod pallet_collator_selection;
 pub mod pallet_message_queue;
 pub mod pallet_multisig;
 pub mod pallet_proxy;
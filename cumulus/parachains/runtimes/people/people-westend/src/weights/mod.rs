@@ -23,7 +23,6 @@ pub mod extrinsic_weights;
 pub mod frame_system;
 pub mod frame_system_extensions;
 pub mod pallet_balances;
-pub mod pallet_collator_selection;
 pub mod pallet_identity;
 pub mod pallet_message_queue;
 pub mod pallet_migrations;
@@ -100,12 +100,35 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export a binary snapshot of the state of a given block.
+	ExportSnapshot(sc_cli::ExportSnapshotCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Bootstrap the database from a state snapshot.
+	ImportSnapshot(sc_cli::ImportSnapshotCmd),
+
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Dry-run a runtime upgrade against the local database or a state snapshot.
+	///
+	/// Executes `TryRuntime_on_runtime_upgrade` of the given runtime and re-executes the
+	/// follow-up blocks on top of the upgraded state, reporting their weight, PoV size and events.
+	#[cfg(feature = "try-runtime")]
+	DryRunUpgrade(crate::common::dry_run::DryRunUpgradeCmd),
+
+	/// Break down the PoV of a block stored in the local database.
+	///
+	/// Re-executes the block on top of the state of its parent and reports the storage proof
+	/// bytes per extrinsic and per storage item, along with the weight reclaim correction.
+	ProfilePov(crate::common::pov_profile::ProfilePovCmd),
+
+	/// Inspect the multi-block migrations of a running node.
+	#[command(subcommand)]
+	Migrations(crate::common::migrations::MigrationsSubcommand),
+
 	/// Subcommand for generating and managing chain specifications.
 	///
 	/// A `chain-spec-builder` subcommand corresponds to the existing `chain-spec-builder` tool
@@ -166,6 +189,14 @@ pub struct Cli<Config: CliConfig> {
 	#[arg(long)]
 	pub dev_block_time: Option<u64>,
 
+	/// Script of relay chain events injected into the blocks of the dev node.
+	///
+	/// A JSON or YAML file (by extension) listing, per parachain block, the downward messages,
+	/// horizontal messages by sender para ID, relay parent number and upgrade go-ahead signal
+	/// provided by the mocked relay chain. Requires `--dev` or `--dev-block-time`.
+	#[arg(long, value_name = "PATH")]
+	pub dev_relay_script: Option<PathBuf>,
+
 	/// DEPRECATED: This feature has been stabilized, pLease use `--authoring slot-based` instead.
 	///
 	/// Use slot-based collator which can handle elastic scaling.
@@ -194,6 +225,10 @@ pub struct Cli<Config: CliConfig> {
 	#[arg(long)]
 	pub export_pov_to_path: Option<PathBuf>,
 
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub on_demand_orders: OnDemandOrderParams,
+
 	/// Relay chain arguments
 	#[arg(raw = true)]
 	pub relay_chain_args: Vec<String>,
@@ -202,6 +237,34 @@ pub struct Cli<Config: CliConfig> {
 	pub(crate) _phantom: PhantomData<Config>,
 }
 
+/// Options of the collator service placing on-demand coretime orders on the relay chain.
+#[derive(Debug, Clone, clap::Args)]
+pub struct OnDemandOrderParams {
+	/// File containing the secret URI of the relay chain account paying for on-demand coretime
+	/// orders.
+	///
+	/// Setting it makes the collator place an order whenever its transaction pool has a backlog
+	/// and the parachain has no core scheduled on the relay chain. Requires `--on-demand-budget`.
+	#[arg(long, value_name = "PATH", requires = "on_demand_budget")]
+	pub on_demand_order_key_file: Option<PathBuf>,
+
+	/// The total amount, in plancks, the collator may spend on on-demand orders.
+	///
+	/// The amount spent is kept across restarts of the collator.
+	#[arg(long, requires = "on_demand_order_key_file")]
+	pub on_demand_budget: Option<u128>,
+
+	/// The maximum spot price, in plancks, to pay for a single on-demand order.
+	///
+	/// Defaults to the budget.
+	#[arg(long, requires = "on_demand_order_key_file")]
+	pub on_demand_max_spot_price: Option<u128>,
+
+	/// The number of ready transactions in the pool needed to place an on-demand order.
+	#[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+	pub on_demand_min_backlog: u64,
+}
+
 /// Collator implementation to use.
 #[derive(PartialEq, Debug, ValueEnum, Clone, Copy)]
 pub enum AuthoringPolicy {
@@ -232,6 +295,11 @@ impl<Config: CliConfig> Cli<Config> {
 				.unwrap_or(self.authoring),
 			export_pov: self.export_pov_to_path.clone(),
 			max_pov_percentage: self.run.experimental_max_pov_percentage,
+			on_demand_orders: self
+				.on_demand_orders
+				.on_demand_order_key_file
+				.is_some()
+				.then(|| self.on_demand_orders.clone()),
 		}
 	}
 }
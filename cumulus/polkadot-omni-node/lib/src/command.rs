@@ -26,7 +26,7 @@ use crate::{
 		NodeBlock, NodeExtraArgs,
 	},
 	fake_runtime_api,
-	nodes::DynNodeSpecExt,
+	nodes::{mock_relay::MockRelayScript, DynNodeSpecExt},
 	runtime::BlockNumber,
 };
 #[cfg(feature = "runtime-benchmarks")]
@@ -133,6 +133,14 @@ pub fn run<CliConfig: crate::cli::CliConfig>(cmd_config: RunConfig) -> Result<()
 				node.prepare_export_state_cmd(config, cmd)
 			})
 		},
+		Some(Subcommand::ExportSnapshot(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let node =
+					new_node_spec(&config, &cmd_config.runtime_resolver, &cli.node_extra_args())?;
+				node.prepare_export_snapshot_cmd(config, cmd)
+			})
+		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -141,6 +149,14 @@ pub fn run<CliConfig: crate::cli::CliConfig>(cmd_config: RunConfig) -> Result<()
 				node.prepare_import_blocks_cmd(config, cmd)
 			})
 		},
+		Some(Subcommand::ImportSnapshot(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let node =
+					new_node_spec(&config, &cmd_config.runtime_resolver, &cli.node_extra_args())?;
+				node.prepare_import_snapshot_cmd(config, cmd)
+			})
+		},
 		Some(Subcommand::Revert(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -149,6 +165,24 @@ pub fn run<CliConfig: crate::cli::CliConfig>(cmd_config: RunConfig) -> Result<()
 				node.prepare_revert_cmd(config, cmd)
 			})
 		},
+		#[cfg(feature = "try-runtime")]
+		Some(Subcommand::DryRunUpgrade(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let node =
+					new_node_spec(&config, &cmd_config.runtime_resolver, &cli.node_extra_args())?;
+				node.run_dry_run_upgrade_cmd(config, cmd)
+			})
+		},
+		Some(Subcommand::ProfilePov(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let node =
+					new_node_spec(&config, &cmd_config.runtime_resolver, &cli.node_extra_args())?;
+				node.run_profile_pov_cmd(config, cmd)
+			})
+		},
+		Some(Subcommand::Migrations(cmd)) => cmd.run(),
 		Some(Subcommand::ChainSpecBuilder(cmd)) =>
 			cmd.run().map_err(|err| sc_cli::Error::Application(err.into())),
 
@@ -244,21 +278,32 @@ pub fn run<CliConfig: crate::cli::CliConfig>(cmd_config: RunConfig) -> Result<()
 						.ok_or("Could not find parachain extension in chain-spec.")?,
 				);
 
+				let relay_script = cli
+					.dev_relay_script
+					.as_deref()
+					.map(MockRelayScript::from_file)
+					.transpose()?
+					.unwrap_or_default();
+
 				if cli.run.base.is_dev()? {
 					// Set default dev block time to 3000ms if not set.
 					// TODO: take block time from AURA config if set.
 					let dev_block_time = cli.dev_block_time.unwrap_or(DEFAULT_DEV_BLOCK_TIME_MS);
 					return node_spec
-						.start_manual_seal_node(config, para_id, dev_block_time)
+						.start_manual_seal_node(config, para_id, dev_block_time, relay_script)
 						.map_err(Into::into);
 				}
 
 				if let Some(dev_block_time) = cli.dev_block_time {
 					return node_spec
-						.start_manual_seal_node(config, para_id, dev_block_time)
+						.start_manual_seal_node(config, para_id, dev_block_time, relay_script)
 						.map_err(Into::into);
 				}
 
+				if cli.dev_relay_script.is_some() {
+					return Err("`--dev-relay-script` requires `--dev` or `--dev-block-time`".into())
+				}
+
 				// If Statemint (Statemine, Westmint, Rockmine) DB exists and we're using the
 				// asset-hub chain spec, then rename the base path to the new chain ID. In the case
 				// that both file paths exist, the node will exit, as the user must decide (by
@@ -14,12 +14,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::common::spec::BaseNodeSpec;
+#[cfg(feature = "try-runtime")]
+use crate::common::dry_run::DryRunUpgradeCmd;
+use crate::common::{pov_profile::ProfilePovCmd, spec::BaseNodeSpec};
 use cumulus_client_cli::ExportGenesisHeadCommand;
 use frame_benchmarking_cli::BlockCmd;
 #[cfg(any(feature = "runtime-benchmarks"))]
 use frame_benchmarking_cli::StorageCmd;
-use sc_cli::{CheckBlockCmd, ExportBlocksCmd, ExportStateCmd, ImportBlocksCmd, RevertCmd};
+use sc_cli::{
+	CheckBlockCmd, ExportBlocksCmd, ExportSnapshotCmd, ExportStateCmd, ImportBlocksCmd,
+	ImportSnapshotCmd, RevertCmd,
+};
 use sc_service::{Configuration, TaskManager};
 use std::{future::Future, pin::Pin};
 
@@ -47,12 +52,24 @@ pub trait NodeCommandRunner {
 		cmd: &ExportStateCmd,
 	) -> AsyncCmdResult<'_>;
 
+	fn prepare_export_snapshot_cmd(
+		self: Box<Self>,
+		config: Configuration,
+		cmd: &ExportSnapshotCmd,
+	) -> AsyncCmdResult<'_>;
+
 	fn prepare_import_blocks_cmd(
 		self: Box<Self>,
 		config: Configuration,
 		cmd: &ImportBlocksCmd,
 	) -> AsyncCmdResult<'_>;
 
+	fn prepare_import_snapshot_cmd(
+		self: Box<Self>,
+		config: Configuration,
+		cmd: &ImportSnapshotCmd,
+	) -> AsyncCmdResult<'_>;
+
 	fn prepare_revert_cmd(
 		self: Box<Self>,
 		config: Configuration,
@@ -77,6 +94,19 @@ pub trait NodeCommandRunner {
 		config: Configuration,
 		cmd: &StorageCmd,
 	) -> SyncCmdResult;
+
+	#[cfg(feature = "try-runtime")]
+	fn run_dry_run_upgrade_cmd(
+		self: Box<Self>,
+		config: Configuration,
+		cmd: &DryRunUpgradeCmd,
+	) -> SyncCmdResult;
+
+	fn run_profile_pov_cmd(
+		self: Box<Self>,
+		config: Configuration,
+		cmd: &ProfilePovCmd,
+	) -> SyncCmdResult;
 }
 
 impl<T> NodeCommandRunner for T
@@ -110,6 +140,15 @@ where
 		Ok((Box::pin(cmd.run(partial.client, config.chain_spec)), partial.task_manager))
 	}
 
+	fn prepare_export_snapshot_cmd(
+		self: Box<Self>,
+		config: Configuration,
+		cmd: &ExportSnapshotCmd,
+	) -> AsyncCmdResult<'_> {
+		let partial = T::new_partial(&config).map_err(sc_cli::Error::Service)?;
+		Ok((Box::pin(cmd.run(partial.client)), partial.task_manager))
+	}
+
 	fn prepare_import_blocks_cmd(
 		self: Box<Self>,
 		config: Configuration,
@@ -119,6 +158,15 @@ where
 		Ok((Box::pin(cmd.run(partial.client, partial.import_queue)), partial.task_manager))
 	}
 
+	fn prepare_import_snapshot_cmd(
+		self: Box<Self>,
+		config: Configuration,
+		cmd: &ImportSnapshotCmd,
+	) -> AsyncCmdResult<'_> {
+		let partial = T::new_partial(&config).map_err(sc_cli::Error::Service)?;
+		Ok((Box::pin(cmd.run(partial.client, partial.import_queue)), partial.task_manager))
+	}
+
 	fn prepare_revert_cmd(
 		self: Box<Self>,
 		config: Configuration,
@@ -158,4 +206,25 @@ where
 
 		cmd.run(config, partial.client, db, storage)
 	}
+
+	#[cfg(feature = "try-runtime")]
+	fn run_dry_run_upgrade_cmd(
+		self: Box<Self>,
+		config: Configuration,
+		cmd: &DryRunUpgradeCmd,
+	) -> SyncCmdResult {
+		let partial = T::new_partial(&config).map_err(sc_cli::Error::Service)?;
+		let executor = sc_service::new_wasm_executor(&config.executor);
+		cmd.run(partial.client, partial.backend, executor)
+	}
+
+	fn run_profile_pov_cmd(
+		self: Box<Self>,
+		config: Configuration,
+		cmd: &ProfilePovCmd,
+	) -> SyncCmdResult {
+		let partial = T::new_partial(&config).map_err(sc_cli::Error::Service)?;
+		let executor = sc_service::new_wasm_executor(&config.executor);
+		cmd.run(partial.client, partial.backend, executor)
+	}
 }
@@ -0,0 +1,523 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dry-run of a runtime upgrade against the local state.
+//!
+//! The new runtime code is written over the state of the upgrade block, and
+//! `TryRuntime_on_runtime_upgrade` is executed on top of it. The blocks following the upgrade
+//! block in the local database are then re-executed with `TryRuntime_execute_block` on top of the
+//! upgraded state, to exercise the multi-block migrations and the `try_state` checks.
+//!
+//! Every step is executed with a proof recorder, so the compact proof of each step approximates
+//! the PoV of the corresponding parachain block.
+
+use crate::common::{types::ParachainHostFunctions, NodeBlock};
+use clap::Parser;
+use codec::{Decode, Encode};
+use frame_try_runtime::{TryStateSelect, UpgradeCheckSelect};
+use log::{info, warn};
+use polkadot_primitives::MAX_POV_SIZE;
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, BlockBackend, HeaderBackend};
+use sc_executor::WasmExecutor;
+use sc_runtime_utilities::fetch_latest_metadata_from_code_blob;
+use scale_value::{At, ValueDef};
+use sp_core::{
+	storage::{well_known_keys, StateVersion},
+	traits::{ReadRuntimeVersion, ReadRuntimeVersionExt, RuntimeCode, WrappedRuntimeCode},
+	Hasher,
+};
+use sp_externalities::Extensions;
+use sp_runtime::{
+	traits::{Block as BlockT, HashingFor, Header as HeaderT, NumberFor},
+	DigestItem,
+};
+use sp_state_machine::{
+	backend::AsTrieBackend, prove_execution_on_trie_backend, Backend as StateBackend,
+	BasicExternalities, DBValue, DefaultError, OverlayedChanges, TrieBackend, TrieBackendBuilder,
+	TrieBackendStorage,
+};
+use sp_trie::{HashDBT, PrefixedMemoryDB};
+use sp_version::RuntimeVersion;
+use sp_weights::Weight;
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+use subxt_metadata::{Metadata, StorageEntryType};
+
+/// The `dry-run-upgrade` command used to test a runtime upgrade against the local state.
+#[derive(Debug, Clone, Parser)]
+pub struct DryRunUpgradeCmd {
+	/// Path to the wasm blob of the new runtime.
+	///
+	/// The runtime must be built with the `try-runtime` feature.
+	#[arg(long, value_name = "PATH")]
+	pub runtime: PathBuf,
+
+	/// Block hash or number whose state is upgraded.
+	///
+	/// Defaults to the block `--blocks` blocks behind the best block, so that all the follow-up
+	/// blocks are available in the database.
+	#[arg(long, value_name = "HASH or NUMBER", conflicts_with = "snapshot")]
+	pub at: Option<BlockNumberOrHash>,
+
+	/// Take the state to upgrade from a snapshot created by the `export-snapshot` command
+	/// instead of the database.
+	#[arg(long, value_name = "PATH")]
+	pub snapshot: Option<PathBuf>,
+
+	/// Number of blocks following the upgrade block to re-execute on top of the upgraded state.
+	///
+	/// The blocks are read from the database.
+	#[arg(long, value_name = "COUNT", default_value_t = 0)]
+	pub blocks: u32,
+
+	/// The checks to run during the upgrade: `none`, `all`, `pre-and-post` or `try-state`.
+	///
+	/// Note that the `pre_upgrade` and `post_upgrade` hooks make the reported PoV size of the
+	/// upgrade inaccurate.
+	#[arg(long, value_name = "CHECKS", default_value = "pre-and-post")]
+	pub checks: UpgradeCheckSelect,
+
+	/// The `try_state` checks to run after every follow-up block: `none`, `all`,
+	/// `rr-<count>` or a comma separated list of pallet names.
+	#[arg(long, value_name = "SELECT", default_value = "all")]
+	pub try_state: TryStateSelect,
+
+	/// The maximal PoV size of a parachain block, in bytes.
+	#[arg(long, value_name = "BYTES", default_value_t = MAX_POV_SIZE)]
+	pub max_pov_size: u32,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// Trie nodes of the dry-run state: the nodes written by the previous steps on top of the nodes
+/// of the initial state.
+struct DryRunStorage<'a, S, H: Hasher> {
+	base: &'a S,
+	changes: &'a PrefixedMemoryDB<H>,
+}
+
+impl<'a, S: TrieBackendStorage<H>, H: Hasher> TrieBackendStorage<H> for DryRunStorage<'a, S, H> {
+	fn get(
+		&self,
+		key: &H::Out,
+		prefix: (&[u8], Option<u8>),
+	) -> Result<Option<DBValue>, DefaultError> {
+		match HashDBT::get(self.changes, key, prefix) {
+			Some(value) => Ok(Some(value)),
+			None => self.base.get(key, prefix),
+		}
+	}
+}
+
+/// Outcome of a dry-run step.
+struct StepReport {
+	/// Weight consumed by the step.
+	weight: Weight,
+	/// Size of the compact storage proof of the step.
+	pov_size: usize,
+	/// Number of events emitted by the step, by event name.
+	events: BTreeMap<String, usize>,
+}
+
+/// Runs the steps of the dry-run one after the other, on top of each other's state.
+struct DryRun<'a, S, Block: BlockT> {
+	base: &'a S,
+	changes: PrefixedMemoryDB<HashingFor<Block>>,
+	root: Block::Hash,
+	executor: &'a WasmExecutor<ParachainHostFunctions>,
+	runtime_code: RuntimeCode<'a>,
+	state_version: StateVersion,
+	metadata: Option<Metadata>,
+}
+
+impl<'a, S, Block> DryRun<'a, S, Block>
+where
+	S: TrieBackendStorage<HashingFor<Block>>,
+	Block: NodeBlock,
+{
+	/// Execute `method` of the new runtime on the current state and apply its changes.
+	///
+	/// `overlay` can hold changes to apply to the state before the execution.
+	fn execute(
+		&mut self,
+		method: &str,
+		call_data: &[u8],
+		mut overlay: OverlayedChanges<HashingFor<Block>>,
+	) -> sc_cli::Result<(Vec<u8>, usize, BTreeMap<String, usize>)> {
+		let backend = self.backend();
+		let mut extensions = Extensions::default();
+		extensions.register(ReadRuntimeVersionExt::new(self.executor.clone()));
+
+		let (result, proof) = prove_execution_on_trie_backend(
+			&backend,
+			&mut overlay,
+			self.executor,
+			method,
+			call_data,
+			&self.runtime_code,
+			&mut extensions,
+		)
+		.map_err(|e| format!("Failed to execute `{method}`: {e}"))?;
+		let pov_size = proof
+			.into_compact_proof::<HashingFor<Block>>(self.root)
+			.map_err(|e| format!("Failed to compact the proof of `{method}`: {e:?}"))?
+			.encoded_size();
+
+		let changes = overlay.drain_storage_changes(&backend, self.state_version)?;
+		drop(backend);
+		self.root = changes.transaction_storage_root;
+		self.changes.consolidate(changes.transaction);
+
+		let events = self.events();
+		Ok((result, pov_size, events))
+	}
+
+	/// The current state.
+	fn backend(&self) -> TrieBackend<DryRunStorage<'_, S, HashingFor<Block>>, HashingFor<Block>> {
+		TrieBackendBuilder::new(
+			DryRunStorage { base: self.base, changes: &self.changes },
+			self.root,
+		)
+		.build()
+	}
+
+	/// Count the events of the current state by name.
+	fn events(&self) -> BTreeMap<String, usize> {
+		let Some(metadata) = &self.metadata else { return Default::default() };
+		let events = match self.backend().storage(&system_storage_key("Events")) {
+			Ok(events) => events.unwrap_or_default(),
+			Err(e) => {
+				warn!("Failed to read the events: {e}");
+				return Default::default()
+			},
+		};
+
+		let mut counts = BTreeMap::new();
+		match event_names(metadata, &events) {
+			Ok(names) => names.into_iter().for_each(|name| *counts.entry(name).or_default() += 1),
+			Err(e) => warn!("Failed to decode the events: {e}"),
+		}
+		counts
+	}
+
+	/// Apply the upgrade and run `TryRuntime_on_runtime_upgrade`.
+	///
+	/// Returns the report of the upgrade and the maximal weight of a block.
+	fn upgrade(
+		&mut self,
+		code: &[u8],
+		checks: UpgradeCheckSelect,
+	) -> sc_cli::Result<(StepReport, Weight)> {
+		let mut overlay = OverlayedChanges::default();
+		overlay.set_storage(well_known_keys::CODE.to_vec(), Some(code.to_vec()));
+		// Only keep the events emitted by the migrations.
+		overlay.set_storage(system_storage_key("Events"), None);
+		overlay.set_storage(system_storage_key("EventCount"), None);
+
+		let (result, pov_size, events) =
+			self.execute("TryRuntime_on_runtime_upgrade", &checks.encode(), overlay)?;
+		let (weight, max_block_weight) = <(Weight, Weight)>::decode(&mut &result[..])
+			.map_err(|e| format!("Failed to decode the upgrade weight: {e}"))?;
+
+		Ok((StepReport { weight, pov_size, events }, max_block_weight))
+	}
+
+	/// Run `TryRuntime_execute_block` with the given block.
+	fn execute_block(
+		&mut self,
+		block: Block,
+		try_state: TryStateSelect,
+	) -> sc_cli::Result<StepReport> {
+		let (mut header, extrinsics) = block.deconstruct();
+		// The seal is added by the block author after the execution.
+		if matches!(header.digest().logs().last(), Some(DigestItem::Seal(..))) {
+			header.digest_mut().pop();
+		}
+
+		let call_data = (Block::new(header, extrinsics), false, true, try_state).encode();
+		let (result, pov_size, events) =
+			self.execute("TryRuntime_execute_block", &call_data, OverlayedChanges::default())?;
+		let weight = Weight::decode(&mut &result[..])
+			.map_err(|e| format!("Failed to decode the block weight: {e}"))?;
+
+		Ok(StepReport { weight, pov_size, events })
+	}
+}
+
+/// Storage key of a storage value of the `System` pallet.
+fn system_storage_key(name: &str) -> Vec<u8> {
+	[sp_crypto_hashing::twox_128(b"System"), sp_crypto_hashing::twox_128(name.as_bytes())].concat()
+}
+
+/// Decode the names of the events stored in the `System::Events` storage value.
+fn event_names(metadata: &Metadata, mut events: &[u8]) -> Result<Vec<String>, String> {
+	let events_ty = metadata
+		.pallet_by_name("System")
+		.and_then(|pallet| pallet.storage())
+		.and_then(|storage| storage.entry_by_name("Events"))
+		.and_then(|entry| match entry.entry_type() {
+			StorageEntryType::Plain(ty) => Some(*ty),
+			_ => None,
+		})
+		.ok_or("Missing `System::Events` in the metadata")?;
+
+	let records = scale_value::scale::decode_as_type(&mut events, events_ty, metadata.types())
+		.map_err(|e| e.to_string())?;
+	if !events.is_empty() {
+		return Err("Unexpected data after the events".into())
+	}
+	let ValueDef::Composite(records) = records.value else {
+		return Err("`System::Events` is not a sequence".into())
+	};
+
+	records
+		.values()
+		.map(|record| {
+			// The runtime event is a variant per pallet, wrapping the event of the pallet.
+			let Some(ValueDef::Variant(pallet)) = record.at("event").map(|event| &event.value)
+			else {
+				return Err("Event record without an event".to_string())
+			};
+			let event = match pallet.values.values().next().map(|event| &event.value) {
+				Some(ValueDef::Variant(event)) => event.name.as_str(),
+				_ => "<unknown>",
+			};
+			Ok(format!("{}::{}", pallet.name, event))
+		})
+		.collect()
+}
+
+impl DryRunUpgradeCmd {
+	/// Run the `dry-run-upgrade` command.
+	pub fn run<Block, BA, C>(
+		&self,
+		client: Arc<C>,
+		backend: Arc<BA>,
+		executor: WasmExecutor<ParachainHostFunctions>,
+	) -> sc_cli::Result<()>
+	where
+		Block: NodeBlock,
+		BA: Backend<Block>,
+		C: HeaderBackend<Block> + BlockBackend<Block>,
+	{
+		let code = std::fs::read(&self.runtime)?;
+
+		if let Some(path) = &self.snapshot {
+			let (header, storage) = sc_service::chain_ops::read_state_snapshot::<Block>(path)?;
+			// The state version of the snapshot is unknown, pick the one matching the header.
+			let state = [StateVersion::V1, StateVersion::V0]
+				.into_iter()
+				.map(|state_version| {
+					TrieBackend::<PrefixedMemoryDB<HashingFor<Block>>, _>::from((
+						storage.clone(),
+						state_version,
+					))
+				})
+				.find(|state| state.root() == header.state_root())
+				.ok_or("The state snapshot does not match the state root of its header")?;
+
+			self.dry_run(&*client, header, state.backend_storage(), &executor, &code)
+		} else {
+			let hash = match &self.at {
+				Some(block) => client.expect_block_hash_from_id(&block.parse::<Block>()?)?,
+				None => {
+					let best_number = client.info().best_number;
+					let number = best_number.saturating_sub(self.blocks.into());
+					client
+						.hash(number)?
+						.ok_or_else(|| format!("Block #{number} is not in the database"))?
+				},
+			};
+			let header = client
+				.header(hash)?
+				.ok_or_else(|| format!("Block {hash:?} is not in the database"))?;
+			let state = backend.state_at(hash)?;
+
+			self.dry_run(
+				&*client,
+				header,
+				state.as_trie_backend().backend_storage(),
+				&executor,
+				&code,
+			)
+		}
+	}
+
+	/// Upgrade the state of the block with the given header and execute the follow-up blocks.
+	fn dry_run<Block, C, S>(
+		&self,
+		client: &C,
+		header: Block::Header,
+		storage: &S,
+		executor: &WasmExecutor<ParachainHostFunctions>,
+		code: &[u8],
+	) -> sc_cli::Result<()>
+	where
+		Block: NodeBlock,
+		C: HeaderBackend<Block> + BlockBackend<Block>,
+		S: TrieBackendStorage<HashingFor<Block>>,
+	{
+		let version = executor
+			.read_runtime_version(code, &mut BasicExternalities::default())
+			.map_err(|e| format!("Failed to read the version of the new runtime: {e}"))?;
+		let version = RuntimeVersion::decode(&mut &version[..])
+			.map_err(|e| format!("Failed to decode the version of the new runtime: {e}"))?;
+		info!("Dry-running the upgrade to {version}");
+
+		let metadata = fetch_latest_metadata_from_code_blob(executor, code.into())
+			.map_err(|e| e.to_string())
+			.and_then(|metadata| {
+				Metadata::decode(&mut (*metadata).as_slice()).map_err(|e| e.to_string())
+			})
+			.inspect_err(|e| warn!("Failed to read the metadata, events are not reported: {e}"))
+			.ok();
+
+		let code_fetcher = WrappedRuntimeCode(code.into());
+		let mut dry_run = DryRun::<_, Block> {
+			base: storage,
+			changes: Default::default(),
+			root: *header.state_root(),
+			executor,
+			runtime_code: RuntimeCode {
+				code_fetcher: &code_fetcher,
+				heap_pages: None,
+				hash: sp_crypto_hashing::blake2_256(code).to_vec(),
+			},
+			state_version: version.state_version(),
+			metadata,
+		};
+
+		info!("Upgrading the state of block #{} ({:?})", header.number(), header.hash());
+		let (report, max_block_weight) = dry_run.upgrade(code, self.checks)?;
+		let mut within_limits = self.report("Upgrade", &report, max_block_weight);
+
+		for offset in 1..=self.blocks {
+			let number = *header.number() + NumberFor::<Block>::from(offset);
+			let block = client
+				.hash(number)?
+				.map(|hash| client.block(hash))
+				.transpose()?
+				.flatten()
+				.ok_or_else(|| format!("Follow-up block #{number} is not in the database"))?
+				.block;
+
+			let report = dry_run.execute_block(block, self.try_state.clone())?;
+			within_limits &= self.report(&format!("Block #{number}"), &report, max_block_weight);
+		}
+
+		if !within_limits {
+			return Err("The upgrade exceeds the block weight or PoV size limits".into())
+		}
+		info!("✅ Runtime upgrade dry-run succeeded");
+		Ok(())
+	}
+
+	/// Log the report of a step, returns `false` if the step exceeds the limits of a block.
+	fn report(&self, step: &str, report: &StepReport, max_block_weight: Weight) -> bool {
+		let percent = |used: u64, max: u64| used as f64 * 100.0 / max.max(1) as f64;
+		info!(
+			"{step}: ref time {} ({:.2}% of the block), proof size {} ({:.2}% of the block), \
+			 PoV size {} bytes ({:.2}% of {})",
+			report.weight.ref_time(),
+			percent(report.weight.ref_time(), max_block_weight.ref_time()),
+			report.weight.proof_size(),
+			percent(report.weight.proof_size(), max_block_weight.proof_size()),
+			report.pov_size,
+			percent(report.pov_size as u64, self.max_pov_size as u64),
+			self.max_pov_size,
+		);
+		for (event, count) in &report.events {
+			info!("{step}: event {event} x{count}");
+		}
+
+		let mut within_limits = true;
+		if report.weight.any_gt(max_block_weight) {
+			warn!("{step}: weight exceeds the maximal block weight {max_block_weight}");
+			within_limits = false;
+		}
+		if report.pov_size > self.max_pov_size as usize {
+			warn!("{step}: PoV size exceeds the maximal PoV size");
+			within_limits = false;
+		}
+		within_limits
+	}
+}
+
+impl CliConfiguration for DryRunUpgradeCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cumulus_test_runtime::RuntimeEvent;
+	use frame_system::{EventRecord, Phase};
+	use sp_core::H256;
+
+	#[test]
+	fn event_names_are_decoded() {
+		let executor = WasmExecutor::<ParachainHostFunctions>::builder()
+			.with_allow_missing_host_functions(true)
+			.build();
+		let metadata = fetch_latest_metadata_from_code_blob(
+			&executor,
+			cumulus_test_runtime::WASM_BINARY.unwrap().into(),
+		)
+		.unwrap();
+		let metadata = Metadata::decode(&mut (*metadata).as_slice()).unwrap();
+
+		let events: Vec<EventRecord<RuntimeEvent, H256>> = vec![
+			EventRecord {
+				phase: Phase::Initialization,
+				event: RuntimeEvent::System(frame_system::Event::CodeUpdated),
+				topics: vec![],
+			},
+			EventRecord {
+				phase: Phase::ApplyExtrinsic(1),
+				event: RuntimeEvent::System(frame_system::Event::NewAccount {
+					account: [1u8; 32].into(),
+				}),
+				topics: vec![H256::repeat_byte(2)],
+			},
+		];
+
+		assert_eq!(
+			event_names(&metadata, &events.encode()).unwrap(),
+			vec!["System::CodeUpdated", "System::NewAccount"]
+		);
+		assert!(event_names(&metadata, &events.encode()[..10]).is_err());
+	}
+}
@@ -0,0 +1,198 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Inspection of the multi-block migrations of a running node.
+
+use jsonrpsee::ws_client::WsClientBuilder;
+use pallet_migrations_rpc::{MigrationsApiClient, MigrationsStatus};
+use sp_core::{hexdisplay::HexDisplay, Bytes, H256};
+use std::time::Duration;
+
+/// Sub-commands concerned with the multi-block migrations of `pallet-migrations`.
+#[derive(Debug, clap::Subcommand)]
+pub enum MigrationsSubcommand {
+	/// Show the progress of the multi-block migrations of a running node.
+	Status(MigrationsStatusCmd),
+}
+
+impl MigrationsSubcommand {
+	/// Run the sub-command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		match self {
+			Self::Status(cmd) => cmd.run(),
+		}
+	}
+}
+
+/// The `migrations status` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct MigrationsStatusCmd {
+	/// The RPC endpoint of the node to query.
+	#[arg(long, value_name = "URI", default_value = "ws://127.0.0.1:9944")]
+	pub uri: String,
+
+	/// Hash of the block to query, defaults to the best block.
+	#[arg(long, value_name = "HASH")]
+	pub at: Option<H256>,
+
+	/// Keep querying the best block and print the status whenever it changes.
+	#[arg(long, conflicts_with = "at")]
+	pub watch: bool,
+
+	/// Seconds between two queries when watching.
+	#[arg(long, value_name = "SECONDS", default_value_t = 6, requires = "watch")]
+	pub interval: u64,
+}
+
+impl MigrationsStatusCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		sc_cli::build_runtime()?.block_on(self.query())
+	}
+
+	async fn query(&self) -> sc_cli::Result<()> {
+		let client = WsClientBuilder::default()
+			.build(&self.uri)
+			.await
+			.map_err(|e| format!("Failed to connect to {}: {e}", self.uri))?;
+
+		let mut last_printed = None;
+		loop {
+			let status = client
+				.status(self.at)
+				.await
+				.map_err(|e| format!("Failed to query the migrations status: {e}"))?;
+
+			let unchanged = (status.active.clone(), status.stuck, status.failures.clone());
+			if last_printed.as_ref() != Some(&unchanged) {
+				print!("{}", render(&status));
+				last_printed = Some(unchanged);
+			}
+
+			if !self.watch {
+				return Ok(())
+			}
+			futures_timer::Delay::new(Duration::from_secs(self.interval)).await;
+		}
+	}
+}
+
+/// Renders the identifier as text if it is printable, as hex otherwise.
+fn identifier(id: &Option<Bytes>) -> String {
+	match id {
+		Some(id) => match std::str::from_utf8(&id.0) {
+			Ok(text) if text.chars().all(|c| c.is_ascii_graphic() || c == ' ') =>
+				format!("`{text}`"),
+			_ => format!("0x{}", HexDisplay::from(&id.0)),
+		},
+		None => "<unknown>".into(),
+	}
+}
+
+/// Human readable representation of the status.
+fn render(status: &MigrationsStatus) -> String {
+	let mut out = format!("Multi-block migrations at block #{}:\n", status.block_number);
+
+	match &status.active {
+		Some(active) => {
+			out += &format!(
+				"  active: migration {} of {} {}, started at block #{}\n",
+				active.index + 1,
+				active.total,
+				identifier(&active.identifier),
+				active.started_at,
+			);
+			out += &match active.max_steps {
+				Some(max) => format!("    steps: {} of at most {max}\n", active.steps),
+				None => format!("    steps: {}\n", active.steps),
+			};
+			if let Some(cursor) = &active.inner_cursor {
+				out += &format!("    inner cursor: 0x{}\n", HexDisplay::from(&cursor.0));
+			}
+			out += &match active.estimated_blocks_remaining {
+				Some(blocks) => format!("    estimated blocks remaining: at most {blocks}\n"),
+				None => "    estimated blocks remaining: unknown\n".into(),
+			};
+		},
+		None if status.stuck =>
+			out += "  STUCK: the migrations failed, governance intervention is required\n",
+		None => out += "  no migrations are running\n",
+	}
+
+	if !status.failures.is_empty() {
+		out += "  recent failures:\n";
+	}
+	for failure in &status.failures {
+		out += &match failure.index {
+			Some(index) => format!(
+				"    block #{}: migration {} {}\n",
+				failure.failed_at,
+				index + 1,
+				identifier(&failure.identifier),
+			),
+			None => format!(
+				"    block #{}: a new upgrade interrupted the unfinished one\n",
+				failure.failed_at
+			),
+		};
+	}
+
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_migrations_rpc::{ActiveMigration, FailedMigration};
+
+	#[test]
+	fn status_is_rendered() {
+		let status = MigrationsStatus {
+			block_number: 120,
+			active: Some(ActiveMigration {
+				index: 1,
+				total: 3,
+				identifier: Some(b"pallet-foo-v2".to_vec().into()),
+				inner_cursor: Some(vec![0x2a, 0x00].into()),
+				started_at: 100,
+				steps: 20,
+				max_steps: Some(50),
+				estimated_blocks_remaining: None,
+			}),
+			stuck: false,
+			failures: vec![
+				FailedMigration {
+					index: Some(0),
+					identifier: Some(vec![0xff, 0x01].into()),
+					failed_at: 90,
+				},
+				FailedMigration { index: None, identifier: None, failed_at: 80 },
+			],
+		};
+
+		assert_eq!(
+			render(&status),
+			"Multi-block migrations at block #120:\n\
+			\x20 active: migration 2 of 3 `pallet-foo-v2`, started at block #100\n\
+			\x20   steps: 20 of at most 50\n\
+			\x20   inner cursor: 0x2a00\n\
+			\x20   estimated blocks remaining: unknown\n\
+			\x20 recent failures:\n\
+			\x20   block #90: migration 1 0xff01\n\
+			\x20   block #80: a new upgrade interrupted the unfinished one\n"
+		);
+	}
+}
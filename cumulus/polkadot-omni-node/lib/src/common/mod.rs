@@ -21,12 +21,16 @@
 pub(crate) mod aura;
 pub mod chain_spec;
 pub mod command;
+#[cfg(feature = "try-runtime")]
+pub mod dry_run;
+pub mod migrations;
+pub mod pov_profile;
 pub mod rpc;
 pub mod runtime;
 pub mod spec;
 pub mod types;
 
-use crate::cli::AuthoringPolicy;
+use crate::cli::{AuthoringPolicy, OnDemandOrderParams};
 use cumulus_primitives_core::{CollectCollationInfo, GetCoreSelectorApi};
 use sc_client_db::DbHash;
 use sc_offchain::OffchainWorkerApi;
@@ -116,4 +120,7 @@ pub struct NodeExtraArgs {
 	/// The maximum percentage of the maximum PoV size that the collator can use.
 	/// It will be removed once <https://github.com/paritytech/polkadot-sdk/issues/6020> is fixed.
 	pub max_pov_percentage: Option<u32>,
+
+	/// If set, the collator places on-demand coretime orders when it has a transaction backlog.
+	pub on_demand_orders: Option<OnDemandOrderParams>,
 }
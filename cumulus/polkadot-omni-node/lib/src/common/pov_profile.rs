@@ -0,0 +1,818 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Breakdown of the PoV of a parachain block.
+//!
+//! The block is re-executed on top of the state of its parent with a proof recorder, one runtime
+//! call at a time: `Core_initialize_block`, `BlockBuilder_apply_extrinsic` for every extrinsic
+//! and `BlockBuilder_finalize_block`. The growth of the storage proof is attributed to the call
+//! that caused it, and the trie nodes of the proof are attributed to the storage items whose
+//! keys were read.
+//!
+//! For every extrinsic, the proof size it was benchmarked with is compared to the proof size
+//! accounted in `System::BlockWeight`, which shows the correction applied by the storage weight
+//! reclaim. `CheckWeight` also accounts the encoded length and the base weight of the extrinsic as
+//! proof size, which are not part of the benchmarked weight and are left out of the comparison.
+
+use crate::common::{types::ParachainHostFunctions, NodeBlock};
+use clap::Parser;
+use codec::{Decode, Encode};
+use log::{debug, info, warn};
+use polkadot_primitives::MAX_POV_SIZE;
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, BlockBackend, HeaderBackend};
+use sc_executor::WasmExecutor;
+use sc_runtime_utilities::fetch_latest_metadata_from_code_blob;
+use serde::Serialize;
+use sp_core::{
+	hexdisplay::HexDisplay,
+	storage::well_known_keys,
+	traits::{CallContext, ReadRuntimeVersionExt, RuntimeCode},
+};
+use sp_externalities::Extensions;
+use sp_runtime::{
+	traits::{Block as BlockT, Hash as HashT, HashingFor, Header as HeaderT, Zero},
+	DigestItem,
+};
+use sp_state_machine::{
+	backend::{AsTrieBackend, BackendRuntimeCode},
+	Backend as StateBackend, OverlayedChanges, StateMachine, TrieBackend, TrieBackendBuilder,
+	TrieBackendStorage,
+};
+use sp_trie::{proof_size_extension::ProofSizeExt, recorder::Recorder};
+use sp_weights::Weight;
+use std::{
+	cmp::Reverse,
+	collections::{BTreeMap, HashMap, HashSet},
+	fs::File,
+	path::PathBuf,
+	sync::Arc,
+};
+use subxt_metadata::Metadata;
+
+/// The `profile-pov` command used to break down the PoV of a block.
+#[derive(Debug, Clone, Parser)]
+pub struct ProfilePovCmd {
+	/// Block hash or number to profile.
+	///
+	/// Defaults to the best block. The state of its parent must be available in the database.
+	#[arg(value_name = "HASH or NUMBER")]
+	pub block: Option<BlockNumberOrHash>,
+
+	/// Number of storage items to report, overall and per extrinsic.
+	#[arg(long, value_name = "COUNT", default_value_t = 10)]
+	pub top: usize,
+
+	/// Also write the full report as JSON to the given file.
+	#[arg(long, value_name = "PATH")]
+	pub json: Option<PathBuf>,
+
+	/// The maximal PoV size of a parachain block, in bytes.
+	#[arg(long, value_name = "BYTES", default_value_t = MAX_POV_SIZE)]
+	pub max_pov_size: u32,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// PoV breakdown of a block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PovReport {
+	/// Hash of the block.
+	block: String,
+	/// Number of the block.
+	number: String,
+	/// Encoded size of the header, seal included.
+	header_size: usize,
+	/// Encoded size of the extrinsics.
+	extrinsics_size: usize,
+	/// Size of the storage proof, as estimated by the recorder during the execution.
+	proof_size: usize,
+	/// Size of the compact storage proof shipped in the PoV.
+	compact_proof_size: usize,
+	/// Estimated size of the PoV: header, extrinsics and compact storage proof.
+	pov_size: usize,
+	/// Runtime calls the block was executed with, in order.
+	steps: Vec<StepProfile>,
+	/// Proof bytes per storage item over the whole block, largest first.
+	storage: Vec<StorageItemProfile>,
+	/// Proof bytes that could not be attributed to a storage read: nodes of child tries,
+	/// iterated nodes and nodes only needed to compute the storage root.
+	unattributed: usize,
+}
+
+/// PoV breakdown of a runtime call of the block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StepProfile {
+	/// Name of the step.
+	name: String,
+	/// Hash of the extrinsic.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	hash: Option<String>,
+	/// Encoded size of the extrinsic.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	encoded_size: Option<usize>,
+	/// Outcome of the extrinsic: `success`, `failed` or `invalid`.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	outcome: Option<&'static str>,
+	/// Growth of the storage proof during the step.
+	proof_size: usize,
+	/// Weight reclaim applied to the extrinsic.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	reclaim: Option<ReclaimProfile>,
+	/// Proof bytes per storage item first read by the step, largest first.
+	storage: Vec<StorageItemProfile>,
+}
+
+/// Proof size of an extrinsic as benchmarked and as accounted after the execution.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReclaimProfile {
+	/// Proof size of the dispatch info of the extrinsic, if the runtime could report it.
+	benchmarked: Option<u64>,
+	/// Growth of the proof size of `System::BlockWeight` during the extrinsic.
+	accounted: u64,
+	/// Part of `accounted` that is not benchmarked: the encoded length of the extrinsic and the
+	/// proof size of the base weight of its dispatch class, if known.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	overhead: Option<u64>,
+	/// `benchmarked - (accounted - overhead)`: positive when proof size was reclaimed.
+	correction: Option<i64>,
+}
+
+/// `frame_system::limits::WeightsPerClass`, of which only the base weight is used.
+#[derive(Decode)]
+struct WeightsPerClass {
+	base_extrinsic: Weight,
+	_max_extrinsic: Option<Weight>,
+	_max_total: Option<Weight>,
+	_reserved: Option<Weight>,
+}
+
+/// `frame_system::limits::BlockWeights`, with the weights of the `Normal`, `Operational` and
+/// `Mandatory` dispatch classes.
+#[derive(Decode)]
+struct BlockWeights {
+	_base_block: Weight,
+	_max_block: Weight,
+	per_class: [WeightsPerClass; 3],
+}
+
+impl BlockWeights {
+	/// The `System::BlockWeights` constant of the runtime.
+	fn new(metadata: &Metadata) -> Option<Self> {
+		let constant = metadata.pallet_by_name("System")?.constant_by_name("BlockWeights")?;
+		Self::decode(&mut constant.value())
+			.inspect_err(|e| warn!("Failed to decode `System::BlockWeights`: {e}"))
+			.ok()
+	}
+
+	/// Proof size of the base weight of an extrinsic of the dispatch class with the given index.
+	fn base_extrinsic_proof_size(&self, class: u8) -> Option<u64> {
+		self.per_class
+			.get(class as usize)
+			.map(|class| class.base_extrinsic.proof_size())
+	}
+}
+
+/// Proof bytes attributed to a storage item.
+#[derive(Debug, Default, Serialize)]
+struct StorageItemProfile {
+	/// Name of the storage item.
+	item: String,
+	/// Number of keys of the item that were read.
+	keys: usize,
+	/// Proof bytes of the trie nodes first needed to read those keys.
+	bytes: usize,
+}
+
+/// Re-executes a block call by call on top of the state of its parent, recording every trie node
+/// that is accessed.
+struct Profiler<'a, S, Block: BlockT> {
+	storage: &'a S,
+	parent_root: Block::Hash,
+	executor: &'a WasmExecutor<ParachainHostFunctions>,
+	runtime_code: &'a RuntimeCode<'a>,
+	recorder: Recorder<HashingFor<Block>>,
+	overlay: OverlayedChanges<HashingFor<Block>>,
+	/// Keys already read by a previous step.
+	read_keys: HashSet<Arc<[u8]>>,
+}
+
+impl<'a, S, Block> Profiler<'a, S, Block>
+where
+	S: TrieBackendStorage<HashingFor<Block>>,
+	Block: NodeBlock,
+{
+	/// The state of the parent block.
+	fn backend(
+		&self,
+		recorder: Option<Recorder<HashingFor<Block>>>,
+	) -> TrieBackend<&'a S, HashingFor<Block>> {
+		TrieBackendBuilder::new(self.storage, self.parent_root)
+			.with_optional_recorder(recorder)
+			.build()
+	}
+
+	/// Execute `method` on top of the changes of the previous steps, recording the accessed trie
+	/// nodes.
+	///
+	/// Returns the result of the call, the growth of the storage proof and the keys read for the
+	/// first time, sorted.
+	fn execute(
+		&mut self,
+		method: &str,
+		call_data: &[u8],
+	) -> sc_cli::Result<(Vec<u8>, usize, Vec<Arc<[u8]>>)> {
+		let backend = self.backend(Some(self.recorder.clone()));
+		let mut extensions = Extensions::default();
+		extensions.register(ReadRuntimeVersionExt::new(self.executor.clone()));
+		extensions.register(ProofSizeExt::new(self.recorder.clone()));
+
+		let before = self.recorder.estimate_encoded_size();
+		let result = StateMachine::new(
+			&backend,
+			&mut self.overlay,
+			self.executor,
+			method,
+			call_data,
+			&mut extensions,
+			self.runtime_code,
+			CallContext::Onchain,
+		)
+		.execute()
+		.map_err(|e| format!("Failed to execute `{method}`: {e}"))?;
+		let proof_size = self.recorder.estimate_encoded_size().saturating_sub(before);
+
+		// Keys of child tries are recorded under their own root and stay unattributed.
+		let mut keys = self
+			.recorder
+			.recorded_keys()
+			.remove(&self.parent_root)
+			.unwrap_or_default()
+			.into_keys()
+			.filter(|key| self.read_keys.insert(key.clone()))
+			.collect::<Vec<_>>();
+		keys.sort();
+
+		Ok((result, proof_size, keys))
+	}
+
+	/// Total proof size accounted in `System::BlockWeight` so far.
+	fn accounted_proof_size(&mut self) -> sc_cli::Result<u64> {
+		let key = system_storage_key("BlockWeight");
+		let encoded = match self.overlay.storage(&key) {
+			Some(value) => value.map(|value| value.to_vec()),
+			None => self
+				.backend(None)
+				.storage(&key)
+				.map_err(|e| format!("Failed to read `System::BlockWeight`: {e}"))?,
+		};
+		let Some(encoded) = encoded else { return Ok(0) };
+
+		// One weight per dispatch class.
+		let weights = <[Weight; 3]>::decode(&mut &encoded[..])
+			.map_err(|e| format!("Failed to decode `System::BlockWeight`: {e}"))?;
+		Ok(weights.iter().map(|weight| weight.proof_size()).sum())
+	}
+
+	/// Proof size the extrinsic was benchmarked with and the index of its dispatch class,
+	/// according to `TransactionPaymentApi_query_info`.
+	fn dispatch_info(&self, extrinsic: &[u8]) -> Option<(u64, u8)> {
+		// The extrinsic is already encoded, only its length is appended.
+		let call_data = [extrinsic, &(extrinsic.len() as u32).encode()].concat();
+		let mut extensions = Extensions::default();
+		let result = StateMachine::new(
+			&self.backend(None),
+			&mut OverlayedChanges::default(),
+			self.executor,
+			"TransactionPaymentApi_query_info",
+			&call_data,
+			&mut extensions,
+			self.runtime_code,
+			CallContext::Offchain,
+		)
+		.execute()
+		.inspect_err(|e| debug!("Failed to query the dispatch info of an extrinsic: {e}"))
+		.ok()?;
+
+		// `RuntimeDispatchInfo` starts with the weight, followed by the dispatch class.
+		let (weight, class) = <(Weight, u8)>::decode(&mut &result[..]).ok()?;
+		Some((weight.proof_size(), class))
+	}
+
+	/// Attribute the nodes of the storage proof to the storage items whose keys were read.
+	///
+	/// The keys of every step are read again, in order, on the state of the parent block. Every
+	/// node of the proof is attributed to the first key that needs it. Returns the number of
+	/// unattributed proof bytes.
+	fn attribute(
+		&self,
+		steps: &mut [StepProfile],
+		keys: &[Vec<Arc<[u8]>>],
+		names: &StorageNames,
+	) -> sc_cli::Result<usize> {
+		let proof_nodes =
+			self.recorder.to_storage_proof().into_iter_nodes().collect::<HashSet<_>>();
+		let mut attributed = HashSet::new();
+
+		for (step, keys) in steps.iter_mut().zip(keys) {
+			let mut items = BTreeMap::<String, StorageItemProfile>::new();
+			for key in keys {
+				let recorder = Recorder::default();
+				self.backend(Some(recorder.clone())).storage(key).map_err(|e| {
+					format!("Failed to read key 0x{}: {e}", HexDisplay::from(&&key[..]))
+				})?;
+
+				let name = names.name(key);
+				let item = items
+					.entry(name.clone())
+					.or_insert_with(|| StorageItemProfile { item: name, ..Default::default() });
+				item.keys += 1;
+				for node in recorder.drain_storage_proof().into_iter_nodes() {
+					if proof_nodes.contains(&node) && !attributed.contains(&node) {
+						item.bytes += node.encoded_size();
+						attributed.insert(node);
+					}
+				}
+			}
+			step.storage = items.into_values().collect();
+			step.storage.sort_by_key(|item| Reverse(item.bytes));
+		}
+
+		Ok(proof_nodes
+			.iter()
+			.filter(|node| !attributed.contains(*node))
+			.map(|node| node.encoded_size())
+			.sum())
+	}
+}
+
+/// Names of the storage items of the runtime, by the hashed prefix of their keys.
+#[derive(Default)]
+struct StorageNames(HashMap<Vec<u8>, String>);
+
+impl StorageNames {
+	fn new(metadata: &Metadata) -> Self {
+		let mut names = HashMap::new();
+		for pallet in metadata.pallets() {
+			let Some(storage) = pallet.storage() else { continue };
+			let prefix = sp_crypto_hashing::twox_128(storage.prefix().as_bytes());
+			names.insert(prefix.to_vec(), format!("{}::<unknown>", pallet.name()));
+			for entry in storage.entries() {
+				names.insert(
+					[prefix, sp_crypto_hashing::twox_128(entry.name().as_bytes())].concat(),
+					format!("{}::{}", pallet.name(), entry.name()),
+				);
+			}
+		}
+		Self(names)
+	}
+
+	/// Name of the storage item of `key`.
+	///
+	/// Falls back to the well-known key itself or to the hex encoded prefix of the key.
+	fn name(&self, key: &[u8]) -> String {
+		if let Some(child) = key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			return format!(":child_storage:default:0x{}", HexDisplay::from(&child))
+		}
+		if key.starts_with(b":") {
+			return String::from_utf8_lossy(key).into_owned()
+		}
+
+		[32, 16]
+			.into_iter()
+			.find_map(|len| key.get(..len).and_then(|prefix| self.0.get(prefix)))
+			.cloned()
+			.unwrap_or_else(|| format!("0x{}", HexDisplay::from(&&key[..key.len().min(32)])))
+	}
+}
+
+/// Storage key of a storage value of the `System` pallet.
+fn system_storage_key(name: &str) -> Vec<u8> {
+	[sp_crypto_hashing::twox_128(b"System"), sp_crypto_hashing::twox_128(name.as_bytes())].concat()
+}
+
+/// Describe an encoded extrinsic by its position, its version and, for bare extrinsics, its call.
+fn extrinsic_name(metadata: Option<&Metadata>, index: usize, extrinsic: &[u8]) -> String {
+	let Ok(body) = Vec::<u8>::decode(&mut &extrinsic[..]) else {
+		return format!("Extrinsic #{index}")
+	};
+	let Some(version) = body.first() else { return format!("Extrinsic #{index}") };
+
+	match version & 0b1100_0000 {
+		0b1000_0000 => format!("Extrinsic #{index} (signed)"),
+		0b0100_0000 => format!("Extrinsic #{index} (general)"),
+		_ => {
+			let call = metadata.zip(body.get(1..3)).and_then(|(metadata, call)| {
+				let pallet = metadata.pallet_by_index(call[0])?;
+				let variant = pallet.call_variant_by_index(call[1])?;
+				Some(format!("{}::{}", pallet.name(), variant.name))
+			});
+			match call {
+				Some(call) => format!("Extrinsic #{index} (bare {call})"),
+				None => format!("Extrinsic #{index} (bare)"),
+			}
+		},
+	}
+}
+
+impl ProfilePovCmd {
+	/// Run the `profile-pov` command.
+	pub fn run<Block, BA, C>(
+		&self,
+		client: Arc<C>,
+		backend: Arc<BA>,
+		executor: WasmExecutor<ParachainHostFunctions>,
+	) -> sc_cli::Result<()>
+	where
+		Block: NodeBlock,
+		BA: Backend<Block>,
+		C: HeaderBackend<Block> + BlockBackend<Block>,
+	{
+		let hash = match &self.block {
+			Some(block) => client.expect_block_hash_from_id(&block.parse::<Block>()?)?,
+			None => client.info().best_hash,
+		};
+		let block = client
+			.block(hash)?
+			.ok_or_else(|| format!("Block {hash:?} is not in the database"))?
+			.block;
+		if block.header().number().is_zero() {
+			return Err("The genesis block has no PoV".into())
+		}
+
+		let state = backend.state_at(*block.header().parent_hash())?;
+		let trie = state.as_trie_backend();
+		let report = self.profile(block, trie.backend_storage(), *trie.root(), &executor)?;
+
+		self.report(&report);
+		if let Some(path) = &self.json {
+			serde_json::to_writer_pretty(File::create(path)?, &report)
+				.map_err(|e| format!("Failed to write the report: {e}"))?;
+			info!("Report written to {}", path.display());
+		}
+		Ok(())
+	}
+
+	/// Re-execute `block` on the state with the given storage and root, and break down its PoV.
+	fn profile<Block, S>(
+		&self,
+		block: Block,
+		storage: &S,
+		parent_root: Block::Hash,
+		executor: &WasmExecutor<ParachainHostFunctions>,
+	) -> sc_cli::Result<PovReport>
+	where
+		Block: NodeBlock,
+		S: TrieBackendStorage<HashingFor<Block>>,
+	{
+		let (mut header, extrinsics) = block.deconstruct();
+		let hash = header.hash();
+		let header_size = header.encoded_size();
+		// The seal is added by the block author after the execution.
+		if matches!(header.digest().logs().last(), Some(DigestItem::Seal(..))) {
+			header.digest_mut().pop();
+		}
+
+		let parent_state = TrieBackendBuilder::new(storage, parent_root).build();
+		let backend_code = BackendRuntimeCode::new(&parent_state);
+		let runtime_code = backend_code.runtime_code()?;
+		let metadata = parent_state
+			.storage(well_known_keys::CODE)
+			.map_err(|e| e.to_string())
+			.and_then(|code| code.ok_or_else(|| "Missing runtime code".to_string()))
+			.and_then(|code| {
+				fetch_latest_metadata_from_code_blob(executor, code.into())
+					.map_err(|e| e.to_string())
+			})
+			.and_then(|metadata| {
+				Metadata::decode(&mut (*metadata).as_slice()).map_err(|e| e.to_string())
+			})
+			.inspect_err(|e| warn!("Failed to read the metadata, calls are not named: {e}"))
+			.ok();
+		let names = metadata.as_ref().map(StorageNames::new).unwrap_or_default();
+		let block_weights = metadata.as_ref().and_then(BlockWeights::new);
+
+		let mut profiler = Profiler::<_, Block> {
+			storage,
+			parent_root,
+			executor,
+			runtime_code: &runtime_code,
+			recorder: Default::default(),
+			overlay: Default::default(),
+			read_keys: Default::default(),
+		};
+		let mut steps = Vec::with_capacity(extrinsics.len() + 2);
+		let mut keys = Vec::with_capacity(extrinsics.len() + 2);
+		let step = |name: String, proof_size| StepProfile {
+			name,
+			hash: None,
+			encoded_size: None,
+			outcome: None,
+			proof_size,
+			reclaim: None,
+			storage: Vec::new(),
+		};
+
+		let (_, proof_size, step_keys) =
+			profiler.execute("Core_initialize_block", &header.encode())?;
+		steps.push(step("Initialization".into(), proof_size));
+		keys.push(step_keys);
+
+		let mut extrinsics_size = 0;
+		for (index, extrinsic) in extrinsics.iter().enumerate() {
+			let extrinsic = extrinsic.encode();
+			extrinsics_size += extrinsic.len();
+
+			let accounted_before = profiler.accounted_proof_size()?;
+			let (result, proof_size, step_keys) =
+				profiler.execute("BlockBuilder_apply_extrinsic", &extrinsic)?;
+			let accounted = profiler.accounted_proof_size()?.saturating_sub(accounted_before);
+			let dispatch_info = profiler.dispatch_info(&extrinsic);
+			let benchmarked = dispatch_info.map(|(proof_size, _)| proof_size);
+			// `CheckWeight` accounts the length of the extrinsic as proof size, on top of its
+			// weight and of the base weight of its dispatch class.
+			let overhead = dispatch_info
+				.zip(block_weights.as_ref())
+				.and_then(|((_, class), weights)| weights.base_extrinsic_proof_size(class))
+				.map(|base| base + extrinsic.len() as u64);
+
+			// `ApplyExtrinsicResult`: the validity first, then the dispatch outcome.
+			let outcome = match &result[..] {
+				[0, 0, ..] => "success",
+				[0, ..] => "failed",
+				_ => "invalid",
+			};
+			let name = extrinsic_name(metadata.as_ref(), index, &extrinsic);
+			if outcome == "invalid" {
+				warn!("{name} is invalid, the profile does not match the imported block");
+			}
+
+			steps.push(StepProfile {
+				hash: Some(format!("{:?}", HashingFor::<Block>::hash(&extrinsic))),
+				encoded_size: Some(extrinsic.len()),
+				outcome: Some(outcome),
+				reclaim: Some(ReclaimProfile {
+					benchmarked,
+					accounted,
+					overhead,
+					correction: benchmarked.zip(overhead).map(|(benchmarked, overhead)| {
+						benchmarked as i64 - (accounted as i64 - overhead as i64)
+					}),
+				}),
+				..step(name, proof_size)
+			});
+			keys.push(step_keys);
+		}
+
+		let (result, proof_size, step_keys) =
+			profiler.execute("BlockBuilder_finalize_block", &[])?;
+		steps.push(step("Finalization".into(), proof_size));
+		keys.push(step_keys);
+
+		let finalized = Block::Header::decode(&mut &result[..])
+			.map_err(|e| format!("Failed to decode the finalized header: {e}"))?;
+		if finalized.state_root() != header.state_root() {
+			warn!(
+				"The re-executed block has state root {:?} instead of {:?}, the profile may not \
+				 match the imported block",
+				finalized.state_root(),
+				header.state_root(),
+			);
+		}
+
+		let unattributed = profiler.attribute(&mut steps, &keys, &names)?;
+		let mut storage = BTreeMap::<String, StorageItemProfile>::new();
+		for item in steps.iter().flat_map(|step| &step.storage) {
+			let total = storage.entry(item.item.clone()).or_insert_with(|| StorageItemProfile {
+				item: item.item.clone(),
+				..Default::default()
+			});
+			total.keys += item.keys;
+			total.bytes += item.bytes;
+		}
+		let mut storage = storage.into_values().collect::<Vec<_>>();
+		storage.sort_by_key(|item| Reverse(item.bytes));
+
+		let proof = profiler.recorder.to_storage_proof();
+		let proof_size = proof.encoded_size();
+		let compact_proof_size = proof
+			.into_compact_proof::<HashingFor<Block>>(parent_root)
+			.map_err(|e| format!("Failed to compact the storage proof: {e:?}"))?
+			.encoded_size();
+		// The block data also encodes the number of extrinsics.
+		let extrinsics_size =
+			extrinsics_size + codec::Compact(extrinsics.len() as u32).encoded_size();
+
+		Ok(PovReport {
+			block: format!("{hash:?}"),
+			number: header.number().to_string(),
+			header_size,
+			extrinsics_size,
+			proof_size,
+			compact_proof_size,
+			pov_size: header_size + extrinsics_size + compact_proof_size,
+			steps,
+			storage,
+			unattributed,
+		})
+	}
+
+	/// Log the report.
+	fn report(&self, report: &PovReport) {
+		let percent = |used: usize| used as f64 * 100.0 / self.max_pov_size.max(1) as f64;
+		info!(
+			"PoV of block #{} ({}): {} bytes ({:.2}% of {}), header {} bytes, extrinsics {} bytes, \
+			 storage proof {} bytes ({} bytes compact)",
+			report.number,
+			report.block,
+			report.pov_size,
+			percent(report.pov_size),
+			self.max_pov_size,
+			report.header_size,
+			report.extrinsics_size,
+			report.proof_size,
+			report.compact_proof_size,
+		);
+
+		for step in &report.steps {
+			let mut line = format!("{}: {} bytes of storage proof", step.name, step.proof_size);
+			if let (Some(size), Some(outcome)) = (step.encoded_size, step.outcome) {
+				line += &format!(", {size} bytes encoded, {outcome}");
+			}
+			if let Some(reclaim) = &step.reclaim {
+				match (reclaim.benchmarked, reclaim.overhead, reclaim.correction) {
+					(Some(benchmarked), Some(overhead), Some(correction)) =>
+						line += &format!(
+							", proof size benchmarked {benchmarked}, accounted {} including {overhead} \
+							 for the length and base weight (reclaimed {correction})",
+							reclaim.accounted,
+						),
+					_ => line += &format!(", proof size accounted {}", reclaim.accounted),
+				}
+			}
+			info!("{line}");
+			for item in step.storage.iter().take(self.top) {
+				info!("{}:   {}: {} bytes ({} keys)", step.name, item.item, item.bytes, item.keys);
+			}
+		}
+
+		info!("Storage items:");
+		for item in report.storage.iter().take(self.top) {
+			info!(
+				"  {}: {} bytes ({:.2}% of the proof, {} keys)",
+				item.item,
+				item.bytes,
+				item.bytes as f64 * 100.0 / report.proof_size.max(1) as f64,
+				item.keys,
+			);
+		}
+		info!(
+			"  <unattributed>: {} bytes (child tries, iterations and storage root computation)",
+			report.unattributed,
+		);
+
+		if report.pov_size > self.max_pov_size as usize {
+			warn!("The PoV size exceeds the maximal PoV size");
+		}
+	}
+}
+
+impl CliConfiguration for ProfilePovCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cumulus_test_client::{
+		runtime::RuntimeBlockWeights, transfer, DefaultTestClientBuilderExt, InitBlockBuilder,
+		Sr25519Keyring, TestClientBuilder, TestClientBuilderExt,
+	};
+	use frame_support::{
+		dispatch::{DispatchClass, GetDispatchInfo},
+		traits::Get,
+	};
+
+	#[test]
+	fn storage_items_are_named() {
+		let executor = WasmExecutor::<ParachainHostFunctions>::builder()
+			.with_allow_missing_host_functions(true)
+			.build();
+		let metadata = fetch_latest_metadata_from_code_blob(
+			&executor,
+			cumulus_test_runtime::WASM_BINARY.unwrap().into(),
+		)
+		.unwrap();
+		let names = StorageNames::new(&Metadata::decode(&mut (*metadata).as_slice()).unwrap());
+
+		let account = [system_storage_key("Account"), vec![1; 48]].concat();
+		assert_eq!(names.name(&account), "System::Account");
+		assert_eq!(names.name(&system_storage_key("Number")), "System::Number");
+		let unknown = [sp_crypto_hashing::twox_128(b"System").to_vec(), vec![2; 16]].concat();
+		assert_eq!(names.name(&unknown), "System::<unknown>");
+		assert_eq!(names.name(well_known_keys::CODE), ":code");
+		assert_eq!(
+			names.name(&[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, &[0xab]].concat()),
+			":child_storage:default:0xab",
+		);
+		assert_eq!(names.name(&[7; 40]), format!("0x{}", HexDisplay::from(&[7u8; 32].as_ref())));
+	}
+
+	#[test]
+	fn block_of_the_test_runtime_is_profiled() {
+		let builder = TestClientBuilder::new();
+		let backend = builder.backend();
+		let client = builder.build();
+
+		let transfer = transfer(&client, Sr25519Keyring::Alice, Sr25519Keyring::Bob, 1_000_000);
+		let mut block_builder = client.init_block_builder(None, Default::default()).block_builder;
+		block_builder.push(transfer.clone()).unwrap();
+		let block = block_builder.build().unwrap().block;
+		let block = crate::common::types::Block::<u32>::decode(&mut &block.encode()[..]).unwrap();
+		let extrinsics = block.extrinsics().len();
+
+		let state = backend.state_at(client.info().genesis_hash).unwrap();
+		let trie = state.as_trie_backend();
+		let executor = WasmExecutor::<ParachainHostFunctions>::builder()
+			.with_allow_missing_host_functions(true)
+			.build();
+		let report = ProfilePovCmd::parse_from(["profile-pov"])
+			.profile(block, trie.backend_storage(), *trie.root(), &executor)
+			.unwrap();
+
+		// Every proof byte is attributed to a storage item or reported as unattributed, only the
+		// number of nodes encoded in front of them is left.
+		assert_eq!(report.steps.len(), extrinsics + 2);
+		let attributed = report.storage.iter().map(|item| item.bytes).sum::<usize>();
+		assert!((1..=5).contains(&(report.proof_size - attributed - report.unattributed)));
+		for item in &report.storage {
+			let bytes = report
+				.steps
+				.iter()
+				.flat_map(|step| &step.storage)
+				.filter(|step_item| step_item.item == item.item)
+				.map(|step_item| step_item.bytes)
+				.sum::<usize>();
+			assert_eq!(bytes, item.bytes);
+		}
+
+		let step = &report.steps[extrinsics];
+		assert!(step.name.ends_with("(signed)"));
+		assert_eq!(step.outcome, Some("success"));
+		assert_eq!(step.encoded_size, Some(transfer.encoded_size()));
+		let accounts = step.storage.iter().find(|item| item.item == "System::Account").unwrap();
+		assert!(accounts.keys >= 2 && accounts.bytes > 0);
+
+		// The length and the base weight are accounted on top of the benchmarked weight, and the
+		// storage weight reclaim only accounts the proof size used by the transfer.
+		let reclaim = step.reclaim.as_ref().unwrap();
+		let benchmarked = transfer.get_dispatch_info().total_weight().proof_size();
+		let base_extrinsic = RuntimeBlockWeights::get()
+			.get(DispatchClass::Normal)
+			.base_extrinsic
+			.proof_size();
+		let overhead = transfer.encoded_size() as u64 + base_extrinsic;
+		assert_eq!(reclaim.benchmarked, Some(benchmarked));
+		assert_eq!(reclaim.overhead, Some(overhead));
+		let used = reclaim.accounted - overhead;
+		assert!(used <= step.proof_size as u64);
+		assert_eq!(reclaim.correction, Some(benchmarked as i64 - used as i64));
+		assert!(reclaim.correction > Some(0));
+	}
+}
@@ -22,6 +22,7 @@ use crate::common::{
 	types::{AccountId, Balance, Nonce, ParachainBackend, ParachainClient},
 	ConstructNodeRuntimeApi,
 };
+use pallet_migrations_rpc::{Migrations, MigrationsApiServer};
 use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 use sc_rpc::dev::{Dev, DevApiServer};
 use sp_runtime::traits::Block as BlockT;
@@ -52,7 +53,8 @@ where
 	RuntimeApi:
 		ConstructNodeRuntimeApi<Block, ParachainClient<Block, RuntimeApi>> + Send + Sync + 'static,
 	RuntimeApi::RuntimeApi: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
-		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+		+ pallet_migrations_rpc::MigrationsRuntimeApi<Block>,
 {
 	fn build_rpc_extensions(
 		client: Arc<ParachainClient<Block, RuntimeApi>>,
@@ -66,6 +68,7 @@ where
 
 			module.merge(System::new(client.clone(), pool).into_rpc())?;
 			module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+			module.merge(Migrations::new(client.clone()).into_rpc())?;
 			module.merge(StateMigration::new(client.clone(), backend).into_rpc())?;
 			module.merge(Dev::new(client).into_rpc())?;
 
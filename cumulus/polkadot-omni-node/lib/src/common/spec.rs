@@ -25,8 +25,10 @@ use crate::common::{
 };
 use cumulus_client_cli::CollatorOptions;
 use cumulus_client_service::{
-	build_network, build_relay_chain_interface, prepare_node_config, start_relay_chain_tasks,
-	BuildNetworkParams, CollatorSybilResistance, DARecoveryProfile, StartRelayChainTasksParams,
+	build_network, build_relay_chain_interface, prepare_node_config, start_on_demand_order_service,
+	start_relay_chain_tasks, BuildNetworkParams, CollatorSybilResistance, DARecoveryProfile,
+	OnDemandOrderConfig, PeerBlockFetcher, StartOnDemandOrderServiceParams,
+	StartRelayChainTasksParams,
 };
 use cumulus_primitives_core::{BlockT, ParaId};
 use cumulus_relay_chain_interface::{OverseerHandle, RelayChainInterface};
@@ -35,7 +37,7 @@ use parachains_common::Hash;
 use polkadot_cli::service::IdentifyNetworkBackend;
 use polkadot_primitives::CollatorPair;
 use prometheus_endpoint::Registry;
-use sc_client_api::Backend;
+use sc_client_api::{Backend, HeaderBackend};
 use sc_consensus::DefaultImportQueue;
 use sc_executor::{HeapAllocStrategy, DEFAULT_HEAP_ALLOC_STRATEGY};
 use sc_network::{config::FullNetworkConfiguration, NetworkBackend, NetworkBlock};
@@ -45,6 +47,7 @@ use sc_telemetry::{TelemetryHandle, TelemetryWorker};
 use sc_tracing::tracing::Instrument;
 use sc_transaction_pool::TransactionPoolHandle;
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sp_core::{sr25519, Pair};
 use sp_keystore::KeystorePtr;
 use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
 
@@ -307,6 +310,13 @@ pub(crate) trait NodeSpec: BaseNodeSpec {
 				})
 				.await?;
 
+			let block_fetcher = PeerBlockFetcher::new(
+				network.clone(),
+				sync_service.clone(),
+				client.chain_info().genesis_hash,
+				parachain_config.chain_spec.fork_id(),
+			);
+
 			if parachain_config.offchain_worker.enabled {
 				let offchain_workers =
 					sc_offchain::OffchainWorkers::new(sc_offchain::OffchainWorkerOptions {
@@ -399,8 +409,43 @@ pub(crate) trait NodeSpec: BaseNodeSpec {
 				relay_chain_slot_duration,
 				recovery_handle: Box::new(overseer_handle.clone()),
 				sync_service,
+				block_fetcher: Some(Box::new(block_fetcher)),
 			})?;
 
+			if let Some(params) = node_extra_args.on_demand_orders.as_ref() {
+				if validator {
+					let key_file = params.on_demand_order_key_file.clone().unwrap_or_default();
+					let key = std::fs::read_to_string(&key_file).map_err(|e| {
+						sc_service::Error::Other(format!(
+							"Unable to read the on-demand order key from {}: {e}",
+							key_file.display(),
+						))
+					})?;
+					let account = sr25519::Pair::from_string(key.trim(), None).map_err(|e| {
+						sc_service::Error::Other(format!("Invalid on-demand order key: {e:?}"))
+					})?;
+					let budget = params.on_demand_budget.unwrap_or_default();
+
+					start_on_demand_order_service(StartOnDemandOrderServiceParams {
+						config: OnDemandOrderConfig {
+							para_id,
+							account,
+							budget,
+							max_spot_price: params.on_demand_max_spot_price.unwrap_or(budget),
+							min_backlog: params.on_demand_min_backlog as usize,
+						},
+						transaction_pool: transaction_pool.clone(),
+						client: client.clone(),
+						relay_chain_interface: relay_chain_interface.clone(),
+						task_manager: &task_manager,
+					});
+				} else {
+					log::warn!(
+						"On-demand orders are only placed by collators, ignoring the order key"
+					);
+				}
+			}
+
 			if validator {
 				Self::StartConsensus::start_consensus(
 					client.clone(),
@@ -197,6 +197,13 @@ macro_rules! impl_node_runtime_apis {
 				}
 			}
 
+			impl pallet_migrations::runtime_api::MigrationsApi<$block> for $runtime {
+				fn status(
+				) -> pallet_migrations::MigrationStatus<sp_runtime::traits::NumberFor<$block>> {
+					unimplemented!()
+				}
+			}
+
 			#[cfg(feature = "runtime-benchmarks")]
 			impl frame_benchmarking::Benchmark<$block> for $runtime {
 				fn benchmark_metadata(_: bool) -> (
@@ -14,10 +14,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::common::{
-	rpc::BuildRpcExtensions as BuildRpcExtensionsT,
-	spec::{BaseNodeSpec, BuildImportQueue, ClientBlockImport, NodeSpec as NodeSpecT},
-	types::{Hash, ParachainBlockImport, ParachainClient},
+use crate::{
+	common::{
+		rpc::BuildRpcExtensions as BuildRpcExtensionsT,
+		spec::{BaseNodeSpec, BuildImportQueue, ClientBlockImport, NodeSpec as NodeSpecT},
+		types::{Hash, ParachainBlockImport, ParachainClient},
+	},
+	nodes::mock_relay::MockRelayScript,
 };
 use codec::Encode;
 use cumulus_client_parachain_inherent::{MockValidationDataInherentDataProvider, MockXcmConfig};
@@ -33,7 +36,7 @@ use sc_service::{Configuration, PartialComponents, TaskManager};
 use sc_telemetry::TelemetryHandle;
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::{ApiExt, ProvideRuntimeApi};
-use sp_runtime::traits::Header;
+use sp_runtime::traits::{Header, UniqueSaturatedInto};
 use std::{marker::PhantomData, sync::Arc};
 
 pub struct ManualSealNode<NodeSpec>(PhantomData<NodeSpec>);
@@ -80,6 +83,7 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 		mut config: Configuration,
 		para_id: ParaId,
 		block_time: u64,
+		relay_script: MockRelayScript,
 	) -> sc_service::error::Result<TaskManager>
 	where
 		Net: NetworkBackend<NodeSpec::Block, Hash>,
@@ -169,6 +173,7 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 			});
 
 		let client_for_cidp = client.clone();
+		let relay_script = Arc::new(relay_script);
 		let params = sc_consensus_manual_seal::ManualSealParams {
 			block_import: client.clone(),
 			env: proposer,
@@ -205,33 +210,57 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 				let current_para_block_head =
 					Some(polkadot_primitives::HeadData(current_para_head.encode()));
 				let client_for_xcm = client_for_cidp.clone();
+				let relay_script = relay_script.clone();
 				async move {
-					use sp_runtime::traits::UniqueSaturatedInto;
+					// When using manual seal we start from block 0, and it's very unlikely to
+					// reach a block number > u32::MAX.
+					let current_para_block = UniqueSaturatedInto::<u32>::unique_saturated_into(
+						*current_para_head.number(),
+					);
+					let new_para_block = current_para_block.saturating_add(1);
+					let relay_blocks_per_para_block =
+						requires_relay_progress.then(|| 1).unwrap_or_default();
+					let relay_offset = match relay_script
+						.relay_offset(new_para_block, relay_blocks_per_para_block)
+					{
+						Ok(relay_offset) => relay_offset,
+						Err(e) =>
+							return Err::<_, Box<dyn std::error::Error + Send + Sync>>(e.into()),
+					};
+
+					let raw_downward_messages = relay_script.downward_messages(new_para_block);
+					let raw_horizontal_messages = relay_script.horizontal_messages(new_para_block);
+					if !raw_downward_messages.is_empty() || !raw_horizontal_messages.is_empty() {
+						log::info!(
+							"Injecting {} downward and {} horizontal messages into block #{}.",
+							raw_downward_messages.len(),
+							raw_horizontal_messages.len(),
+							new_para_block,
+						);
+					}
+					let upgrade_go_ahead =
+						relay_script.upgrade_go_ahead(new_para_block).or_else(|| {
+							should_send_go_ahead.then(|| {
+								log::info!(
+									"Detected pending validation code, sending go-ahead signal."
+								);
+								UpgradeGoAhead::GoAhead
+							})
+						});
 
 					let mocked_parachain = MockValidationDataInherentDataProvider {
-						// When using manual seal we start from block 0, and it's very unlikely to
-						// reach a block number > u32::MAX.
-						current_para_block: UniqueSaturatedInto::<u32>::unique_saturated_into(
-							*current_para_head.number(),
-						),
+						current_para_block,
 						para_id,
 						current_para_block_head,
-						relay_offset: 0,
-						relay_blocks_per_para_block: requires_relay_progress
-							.then(|| 1)
-							.unwrap_or_default(),
+						relay_offset,
+						relay_blocks_per_para_block,
 						para_blocks_per_relay_epoch: 10,
 						relay_randomness_config: (),
 						xcm_config: MockXcmConfig::new(&*client_for_xcm, block, Default::default()),
-						raw_downward_messages: vec![],
-						raw_horizontal_messages: vec![],
+						raw_downward_messages,
+						raw_horizontal_messages,
 						additional_key_values: None,
-						upgrade_go_ahead: should_send_go_ahead.then(|| {
-							log::info!(
-								"Detected pending validation code, sending go-ahead signal."
-							);
-							UpgradeGoAhead::GoAhead
-						}),
+						upgrade_go_ahead,
 					};
 					Ok((
 						// This is intentional, as the runtime that we expect to run against this
@@ -0,0 +1,312 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scripted relay chain events for the manual seal dev node.
+//!
+//! A script lists, per parachain block, the relay chain events the mocked parachain inherent
+//! should carry into that block. It is read from a JSON or YAML file, for example:
+//!
+//! ```yaml
+//! blocks:
+//!   - block: 3
+//!     downwardMessages: ["0x0408..."]
+//!   - block: 5
+//!     relayBlockNumber: 1000
+//!     horizontalMessages:
+//!       - sender: 2000
+//!         data: "0x0408..."
+//!   - block: 8
+//!     upgradeGoAhead: abort
+//! ```
+
+use cumulus_primitives_core::ParaId;
+use polkadot_primitives::UpgradeGoAhead;
+use serde::Deserialize;
+use sp_core::Bytes;
+use std::path::Path;
+
+/// Relay chain events injected into the blocks of a manual seal dev node.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MockRelayScript {
+	/// Events of the parachain blocks, by increasing block number.
+	blocks: Vec<MockRelayBlock>,
+}
+
+/// Relay chain events injected into a parachain block.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MockRelayBlock {
+	/// Number of the parachain block.
+	block: u32,
+	/// Relay parent number of the block.
+	///
+	/// The relay parent numbers of the following blocks continue from it. It can't be lower than
+	/// the relay parent number the block would have otherwise, so if the runtime requires relay
+	/// chain progress, it must be greater than the relay parent number of the previous block.
+	#[serde(default)]
+	relay_block_number: Option<u32>,
+	/// Downward messages sent by the relay chain.
+	#[serde(default)]
+	downward_messages: Vec<Bytes>,
+	/// Horizontal messages sent by other parachains.
+	#[serde(default)]
+	horizontal_messages: Vec<MockHrmpMessage>,
+	/// Upgrade go-ahead signal, replacing the one sent when a validation code upgrade is pending.
+	#[serde(default)]
+	upgrade_go_ahead: Option<MockUpgradeGoAhead>,
+}
+
+/// A horizontal message sent by another parachain.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MockHrmpMessage {
+	/// The sending parachain.
+	sender: u32,
+	/// The encoded message.
+	data: Bytes,
+}
+
+/// The upgrade go-ahead signal of the relay chain.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum MockUpgradeGoAhead {
+	/// Apply the pending validation code upgrade.
+	GoAhead,
+	/// Abort the pending validation code upgrade.
+	Abort,
+}
+
+impl From<MockUpgradeGoAhead> for UpgradeGoAhead {
+	fn from(signal: MockUpgradeGoAhead) -> Self {
+		match signal {
+			MockUpgradeGoAhead::GoAhead => UpgradeGoAhead::GoAhead,
+			MockUpgradeGoAhead::Abort => UpgradeGoAhead::Abort,
+		}
+	}
+}
+
+impl MockRelayScript {
+	/// Read a script from a YAML file, if its extension is `yaml` or `yml`, or from a JSON file.
+	pub fn from_file(path: &Path) -> Result<Self, String> {
+		let content = std::fs::read_to_string(path)
+			.map_err(|e| format!("Failed to read the relay script {}: {e}", path.display()))?;
+		let script: Self = match path.extension().and_then(|extension| extension.to_str()) {
+			Some("yaml" | "yml") => serde_yaml::from_str(&content).map_err(|e| e.to_string()),
+			_ => serde_json::from_str(&content).map_err(|e| e.to_string()),
+		}
+		.map_err(|e| format!("Failed to parse the relay script {}: {e}", path.display()))?;
+
+		script.validate()?;
+		Ok(script)
+	}
+
+	/// Check that the blocks are ordered and that the relay chain does not go backwards.
+	fn validate(&self) -> Result<(), String> {
+		let mut previous_block = 0;
+		let mut previous_relay_block_number = 0;
+		for block in &self.blocks {
+			if block.block <= previous_block {
+				return Err(format!(
+					"Relay script block #{} is not after block #{previous_block}",
+					block.block
+				))
+			}
+			previous_block = block.block;
+
+			if let Some(number) = block.relay_block_number {
+				if number < previous_relay_block_number {
+					return Err(format!(
+						"Relay script block #{} goes back to relay block #{number}",
+						block.block
+					))
+				}
+				previous_relay_block_number = number;
+			}
+		}
+		Ok(())
+	}
+
+	/// The events of the given parachain block, if any.
+	fn block(&self, para_block: u32) -> Option<&MockRelayBlock> {
+		self.blocks
+			.binary_search_by_key(&para_block, |block| block.block)
+			.ok()
+			.map(|index| &self.blocks[index])
+	}
+
+	/// Relay offset of the mocked inherent of the given parachain block.
+	///
+	/// The mocked relay parent number of a block is
+	/// `relay_offset + relay_blocks_per_para_block * parent_number`, the offset is chosen so that
+	/// the last relay block number set by the script, at or before `para_block`, is honoured.
+	///
+	/// Fails if a relay block number set by the script is below the relay parent number the block
+	/// would have anyway, as the relay parent would then not advance by
+	/// `relay_blocks_per_para_block` from the previous block.
+	pub fn relay_offset(
+		&self,
+		para_block: u32,
+		relay_blocks_per_para_block: u32,
+	) -> Result<u32, String> {
+		let mut offset = 0u32;
+		for block in self.blocks.iter().take_while(|block| block.block <= para_block) {
+			let Some(number) = block.relay_block_number else { continue };
+			let progress =
+				relay_blocks_per_para_block.saturating_mul(block.block.saturating_sub(1));
+			let minimum = offset.saturating_add(progress);
+			if number < minimum {
+				return Err(format!(
+					"Relay script block #{} sets relay block #{number}, but the relay chain is at \
+					 least at block #{minimum} by then, as it advances by \
+					 {relay_blocks_per_para_block} block(s) per parachain block",
+					block.block,
+				))
+			}
+			offset = number - progress;
+		}
+		Ok(offset)
+	}
+
+	/// Downward messages to inject into the given parachain block.
+	pub fn downward_messages(&self, para_block: u32) -> Vec<Vec<u8>> {
+		self.block(para_block)
+			.map(|block| block.downward_messages.iter().map(|message| message.0.clone()).collect())
+			.unwrap_or_default()
+	}
+
+	/// Horizontal messages to inject into the given parachain block, with their sender.
+	pub fn horizontal_messages(&self, para_block: u32) -> Vec<(ParaId, Vec<u8>)> {
+		self.block(para_block)
+			.map(|block| {
+				block
+					.horizontal_messages
+					.iter()
+					.map(|message| (message.sender.into(), message.data.0.clone()))
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Upgrade go-ahead signal to send in the given parachain block, if scripted.
+	pub fn upgrade_go_ahead(&self, para_block: u32) -> Option<UpgradeGoAhead> {
+		self.block(para_block)?.upgrade_go_ahead.map(Into::into)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn script(yaml: &str) -> Result<MockRelayScript, String> {
+		let script: MockRelayScript = serde_yaml::from_str(yaml).map_err(|e| e.to_string())?;
+		script.validate().map(|_| script)
+	}
+
+	#[test]
+	fn events_are_injected_at_their_block() {
+		let script = script(
+			r#"
+blocks:
+  - block: 2
+    downwardMessages: ["0x0102"]
+    horizontalMessages:
+      - sender: 2000
+        data: "0x03"
+      - sender: 1000
+        data: "0x04"
+  - block: 4
+    upgradeGoAhead: abort
+"#,
+		)
+		.unwrap();
+
+		assert_eq!(script.downward_messages(2), vec![vec![1, 2]]);
+		assert_eq!(
+			script.horizontal_messages(2),
+			vec![(ParaId::from(2000), vec![3]), (ParaId::from(1000), vec![4])],
+		);
+		assert_eq!(script.upgrade_go_ahead(2), None);
+		assert_eq!(script.upgrade_go_ahead(4), Some(UpgradeGoAhead::Abort));
+		assert!(script.downward_messages(3).is_empty());
+		assert!(script.horizontal_messages(4).is_empty());
+	}
+
+	#[test]
+	fn relay_block_numbers_continue_from_the_script() {
+		let script = script(
+			r#"
+blocks:
+  - block: 5
+    relayBlockNumber: 100
+  - block: 7
+  - block: 10
+    relayBlockNumber: 200
+"#,
+		)
+		.unwrap();
+
+		// Relay parent number of a block: `offset + relay_blocks_per_para_block * (block - 1)`.
+		assert_eq!(script.relay_offset(4, 1), Ok(0));
+		assert_eq!(script.relay_offset(5, 1), Ok(100 - 4));
+		assert_eq!(script.relay_offset(9, 1), Ok(104 - 8));
+		assert_eq!(script.relay_offset(10, 1), Ok(200 - 9));
+		assert_eq!(script.relay_offset(12, 0), Ok(200));
+	}
+
+	#[test]
+	fn relay_block_numbers_must_keep_up_with_the_relay_chain() {
+		let going_back = script("blocks:\n  - block: 5\n    relayBlockNumber: 3\n").unwrap();
+		// Without relay chain progress, any non-decreasing number is honoured.
+		assert_eq!(going_back.relay_offset(5, 0), Ok(3));
+		// Otherwise, block 5 would go back from relay parent 3 of block 4.
+		let error = going_back.relay_offset(5, 1).unwrap_err();
+		assert!(error.contains("block #5 sets relay block #3"), "{error}");
+		assert!(error.contains("at least at block #4"), "{error}");
+
+		let standing_still = script(
+			r#"
+blocks:
+  - block: 7
+    relayBlockNumber: 10
+  - block: 8
+    relayBlockNumber: 10
+"#,
+		)
+		.unwrap();
+		assert_eq!(standing_still.relay_offset(8, 0), Ok(10));
+		assert_eq!(standing_still.relay_offset(7, 1), Ok(10 - 6));
+		// Block 8 would not advance from relay parent 10 of block 7.
+		let error = standing_still.relay_offset(8, 1).unwrap_err();
+		assert!(error.contains("block #8 sets relay block #10"), "{error}");
+		assert!(error.contains("at least at block #11"), "{error}");
+	}
+
+	#[test]
+	fn invalid_scripts_are_rejected() {
+		assert!(script("blocks:\n  - block: 3\n  - block: 3\n").is_err());
+		assert!(script("blocks:\n  - block: 0\n").is_err());
+		assert!(script(
+			"blocks:\n  - block: 1\n    relayBlockNumber: 10\n  - block: 2\n    relayBlockNumber: 9\n"
+		)
+		.is_err());
+		assert!(script("blocks:\n  - block: 1\n    unknown: 1\n").is_err());
+
+		let json = r#"{ "blocks": [{ "block": 1, "downwardMessages": ["0x00"] }] }"#;
+		let script: MockRelayScript = serde_json::from_str(json).unwrap();
+		assert_eq!(script.downward_messages(1), vec![vec![0]]);
+	}
+}
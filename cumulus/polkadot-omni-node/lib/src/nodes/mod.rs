@@ -16,10 +16,12 @@
 
 pub mod aura;
 mod manual_seal;
+pub mod mock_relay;
 
 use crate::common::spec::{DynNodeSpec, NodeSpec as NodeSpecT};
 use cumulus_primitives_core::ParaId;
 use manual_seal::ManualSealNode;
+use mock_relay::MockRelayScript;
 use polkadot_cli::service::IdentifyNetworkBackend;
 use sc_service::{Configuration, TaskManager};
 
@@ -38,6 +40,7 @@ pub trait DynNodeSpecExt: DynNodeSpec {
 		config: Configuration,
 		para_id: ParaId,
 		block_time: u64,
+		relay_script: MockRelayScript,
 	) -> sc_service::error::Result<TaskManager>;
 }
 
@@ -51,6 +54,7 @@ where
 		config: Configuration,
 		para_id: ParaId,
 		block_time: u64,
+		relay_script: MockRelayScript,
 	) -> sc_service::error::Result<TaskManager> {
 		let node = ManualSealNode::<T>::new();
 
@@ -58,10 +62,20 @@ where
 		let default_backend = config.chain_spec.network_backend();
 		let network_backend = config.network.network_backend.unwrap_or(default_backend);
 		match network_backend {
-			sc_network::config::NetworkBackendType::Libp2p =>
-				node.start_node::<sc_network::NetworkWorker<_, _>>(config, para_id, block_time),
-			sc_network::config::NetworkBackendType::Litep2p =>
-				node.start_node::<sc_network::Litep2pNetworkBackend>(config, para_id, block_time),
+			sc_network::config::NetworkBackendType::Libp2p => node
+				.start_node::<sc_network::NetworkWorker<_, _>>(
+					config,
+					para_id,
+					block_time,
+					relay_script,
+				),
+			sc_network::config::NetworkBackendType::Litep2p => node
+				.start_node::<sc_network::Litep2pNetworkBackend>(
+				config,
+				para_id,
+				block_time,
+				relay_script,
+			),
 		}
 	}
 }
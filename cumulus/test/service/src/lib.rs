@@ -63,7 +63,8 @@ use cumulus_primitives_core::{relay_chain::ValidationCode, ParaId};
 use cumulus_relay_chain_inprocess_interface::RelayChainInProcessInterface;
 use cumulus_relay_chain_interface::{RelayChainError, RelayChainInterface, RelayChainResult};
 use cumulus_relay_chain_minimal_node::{
-	build_minimal_relay_chain_node_light_client, build_minimal_relay_chain_node_with_rpc,
+	build_minimal_relay_chain_node_light_client,
+	build_minimal_relay_chain_node_with_cross_checked_rpc, build_minimal_relay_chain_node_with_rpc,
 };
 
 use cumulus_test_runtime::{Hash, Header, NodeBlock as Block, RuntimeApi};
@@ -300,6 +301,15 @@ async fn build_relay_chain_interface(
 			)
 			.await
 			.map(|r| r.0),
+		cumulus_client_cli::RelayChainMode::CrossCheckedRpc(rpc_target_urls) =>
+			return build_minimal_relay_chain_node_with_cross_checked_rpc(
+				relay_chain_config,
+				parachain_prometheus_registry,
+				task_manager,
+				rpc_target_urls,
+			)
+			.await
+			.map(|r| r.0),
 		cumulus_client_cli::RelayChainMode::LightClient =>
 			return build_minimal_relay_chain_node_light_client(relay_chain_config, task_manager)
 				.await
@@ -315,6 +325,9 @@ async fn build_relay_chain_interface(
 		relay_chain_node.overseer_handle.ok_or(RelayChainError::GenericError(
 			"Overseer should be running in full node.".to_string(),
 		))?,
+		sc_transaction_pool_api::OffchainTransactionPoolFactory::new(
+			relay_chain_node.transaction_pool,
+		),
 	)))
 }
 
@@ -447,6 +460,7 @@ where
 		relay_chain_slot_duration,
 		recovery_handle,
 		sync_service: sync_service.clone(),
+		block_fetcher: None,
 	})?;
 
 	if let Some(collator_key) = collator_key {
@@ -158,6 +158,28 @@ pub struct RunCmd {
 	/// networks.
 	#[arg(long)]
 	pub keep_finalized_for: Option<u32>,
+
+	/// How long data which is not available in any block should be kept in the availability
+	/// store (in hours). If not specified, set to 1 hour.
+	#[arg(long)]
+	pub keep_unavailable_for: Option<u32>,
+
+	/// Archive the available data of finalized candidates to the given directory before it is
+	/// pruned from the availability store.
+	///
+	/// The archive is append-only and grows without bounds, it is meant for archival nodes and
+	/// indexers that need the PoVs of past candidates for debugging or research. Archived
+	/// candidates can be re-validated with `polkadot-revalidate-candidate --archive-path`.
+	#[arg(long, value_name = "PATH")]
+	pub availability_archive_path: Option<PathBuf>,
+
+	/// Record all the messages and signals delivered to the subsystems to the given file.
+	///
+	/// The trace can be replayed into a single subsystem under test, to reproduce an incident
+	/// deterministically. Recording slows the node down and the trace grows quickly, only use it
+	/// when debugging.
+	#[arg(long, value_name = "PATH")]
+	pub overseer_record_path: Option<PathBuf>,
 }
 
 #[allow(missing_docs)]
@@ -236,6 +236,9 @@ where
 				prepare_workers_soft_max_num: cli.run.prepare_workers_soft_max_num,
 				enable_approval_voting_parallel: cli.run.enable_approval_voting_parallel,
 				keep_finalized_for: cli.run.keep_finalized_for,
+				keep_unavailable_for: cli.run.keep_unavailable_for,
+				availability_archive_path: cli.run.availability_archive_path,
+				overseer_record_path: cli.run.overseer_record_path,
 			},
 		)
 		.map(|full| full.task_manager)?;
@@ -39,8 +39,8 @@ use polkadot_node_subsystem::{
 		ApprovalVotingMessage, AssignmentCheckError, AssignmentCheckResult,
 		AvailabilityRecoveryMessage, BlockDescription, CandidateValidationMessage, ChainApiMessage,
 		ChainSelectionMessage, CheckedIndirectAssignment, CheckedIndirectSignedApprovalVote,
-		DisputeCoordinatorMessage, HighestApprovedAncestorBlock, PvfExecKind, RuntimeApiMessage,
-		RuntimeApiRequest,
+		DisputeCoordinatorMessage, HighestApprovedAncestorBlock, PendingApproval, PvfExecKind,
+		RuntimeApiMessage, RuntimeApiRequest,
 	},
 	overseer, FromOrchestra, OverseerSignal, SpawnedSubsystem, SubsystemError, SubsystemResult,
 	SubsystemSender,
@@ -2163,12 +2163,51 @@ async fn handle_from_overseer<
 				.await?;
 				Vec::new()
 			},
+			ApprovalVotingMessage::GetPendingApprovals(tx) => {
+				let _ = tx.send(get_pending_approvals(db)?);
+				Vec::new()
+			},
 		},
 	};
 
 	Ok(actions)
 }
 
+/// Collect the approval status of the candidates of all the unapproved blocks.
+fn get_pending_approvals(
+	db: &OverlayedBackend<'_, impl Backend>,
+) -> SubsystemResult<Vec<PendingApproval>> {
+	let mut pending = Vec::new();
+	for block_hash in db.load_all_blocks()? {
+		let Some(block_entry) = db.load_block_entry(&block_hash)? else { continue };
+		for candidate_hash in block_entry.unapproved_candidates() {
+			let Some(candidate_entry) = db.load_candidate_entry(&candidate_hash)? else { continue };
+			let Some(approval_entry) = candidate_entry.approval_entry(&block_hash) else {
+				continue
+			};
+
+			pending.push(PendingApproval {
+				block_hash,
+				block_number: block_entry.block_number(),
+				candidate_hash,
+				para_id: candidate_entry.candidate_receipt().descriptor.para_id(),
+				tranches: approval_entry
+					.tranches()
+					.iter()
+					.map(|entry| (entry.tranche(), entry.assignments().len()))
+					.collect(),
+				our_assignment: approval_entry
+					.our_assignment()
+					.map(|assignment| (assignment.tranche(), assignment.triggered())),
+				approvals: candidate_entry.approvals().count_ones(),
+				n_validators: approval_entry.n_validators(),
+			});
+		}
+	}
+
+	Ok(pending)
+}
+
 /// Retrieve approval signatures.
 ///
 /// This involves an unbounded message send to approval-distribution, the caller has to ensure that
@@ -2840,6 +2840,90 @@ fn subsystem_approved_ancestor_missing_approval() {
 	approved_ancestor_test(|i| i == 3, 2);
 }
 
+#[test]
+fn subsystem_reports_pending_approvals() {
+	test_harness(HarnessConfig::default(), |test_harness| async move {
+		let TestHarness { mut virtual_overseer, sync_oracle_handle: _sync_oracle_handle, .. } =
+			test_harness;
+
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::ChainApi(ChainApiMessage::FinalizedBlockNumber(rx)) => {
+				rx.send(Ok(0)).unwrap();
+			}
+		);
+
+		let block_hashes = vec![Hash::repeat_byte(0x01), Hash::repeat_byte(0x02)];
+		let candidate_receipts: Vec<_> = block_hashes
+			.iter()
+			.enumerate()
+			.map(|(i, hash)| {
+				let mut candidate_receipt = dummy_candidate_receipt_v2(*hash);
+				candidate_receipt.descriptor.set_para_id(i.into());
+				candidate_receipt
+			})
+			.collect();
+		let candidate_hashes: Vec<_> = candidate_receipts.iter().map(|r| r.hash()).collect();
+
+		let validator = ValidatorIndex(0);
+		let mut builder = ChainBuilder::new();
+		for (i, (block_hash, candidate_receipt)) in
+			block_hashes.iter().zip(candidate_receipts).enumerate()
+		{
+			let parent_hash = if i == 0 { ChainBuilder::GENESIS_HASH } else { block_hashes[i - 1] };
+			builder.add_block(
+				*block_hash,
+				parent_hash,
+				i as u32 + 1,
+				BlockConfig {
+					slot: Slot::from(i as u64),
+					candidates: Some(vec![(candidate_receipt, CoreIndex(0), GroupIndex(0))]),
+					session_info: None,
+					end_syncing: false,
+				},
+			);
+		}
+		builder.build(&mut virtual_overseer).await;
+
+		for block_hash in &block_hashes {
+			let rx = import_assignment(&mut virtual_overseer, *block_hash, 0, validator, 0).await;
+			assert_eq!(rx.await, Ok(AssignmentCheckResult::Accepted));
+		}
+
+		// Only the candidate of the first block gets approved.
+		let rx = import_approval(
+			&mut virtual_overseer,
+			block_hashes[0],
+			0,
+			validator,
+			candidate_hashes[0],
+			1,
+			true,
+			None,
+		)
+		.await;
+		assert_eq!(rx.await, Ok(ApprovalCheckResult::Accepted));
+
+		let (tx, rx) = oneshot::channel();
+		overseer_send(
+			&mut virtual_overseer,
+			FromOrchestra::Communication { msg: ApprovalVotingMessage::GetPendingApprovals(tx) },
+		)
+		.await;
+
+		let pending = rx.await.unwrap();
+		assert_eq!(pending.len(), 1);
+		assert_eq!(pending[0].block_hash, block_hashes[1]);
+		assert_eq!(pending[0].block_number, 2);
+		assert_eq!(pending[0].candidate_hash, candidate_hashes[1]);
+		assert_eq!(pending[0].para_id, ParaId::from(1_u32));
+		assert_eq!(pending[0].tranches, vec![(0, 1)]);
+		assert_eq!(pending[0].approvals, 0);
+
+		virtual_overseer
+	});
+}
+
 #[test]
 fn subsystem_validate_approvals_cache() {
 	let assignment_criteria = Box::new(MockAssignmentCriteria(
@@ -0,0 +1,466 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Append-only archive of the available data of finalized candidates.
+//!
+//! The archive is a directory of segment files, named after their sequence number. A segment is a
+//! list of records, each made of the candidate hash, the little-endian `u32` length of the
+//! SCALE-encoded [`AvailableData`], the checksum of the record and the encoded data itself. The
+//! checksum is the BLAKE2-256 hash of the candidate hash and the encoded data, it's checked
+//! whenever a record is read. Records are only ever appended to the last segment, a new segment is
+//! started once it grows past [`MAX_SEGMENT_SIZE`].
+//!
+//! The location of every record is kept in an in-memory index, which is rebuilt by scanning the
+//! record headers of all segments when the archive is opened.
+//!
+//! An archive can also be opened read-only, e.g. to look up candidates while the node keeps
+//! appending to it. Such an archive never modifies the segments, and ignores a record still being
+//! written at the end of the last one.
+
+use std::{
+	collections::HashMap,
+	fs::{self, File, OpenOptions},
+	io::{self, Read, Seek, SeekFrom, Write},
+	path::{Path, PathBuf},
+	sync::{Mutex, MutexGuard},
+};
+
+use codec::{Decode, Encode};
+use polkadot_node_primitives::AvailableData;
+use polkadot_primitives::{BlakeTwo256, CandidateHash, Hash, HashT};
+
+use crate::LOG_TARGET;
+
+const SEGMENT_EXTENSION: &str = "seg";
+
+/// Segments are rolled over once they reach this size.
+const MAX_SEGMENT_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// The candidate hash, the length of the encoded data and the checksum.
+const RECORD_HEADER_SIZE: usize = 32 + 4 + 32;
+
+/// Where a record's encoded data lives.
+#[derive(Debug, Clone, Copy)]
+struct Location {
+	segment: u32,
+	offset: u64,
+	len: u32,
+}
+
+/// The segment records are appended to.
+struct Writer {
+	segment: u32,
+	segment_size: u64,
+	file: File,
+}
+
+struct Inner {
+	index: HashMap<CandidateHash, Location>,
+	/// `None` if the archive was opened read-only.
+	writer: Option<Writer>,
+}
+
+/// A file-backed, append-only store of the available data of finalized candidates, keyed by
+/// candidate hash.
+///
+/// Once configured, the availability store appends the available data of a finalized candidate
+/// to the archive right before pruning it. Only candidates the availability store holds the full
+/// available data for are archived, candidates it only stored chunks of are not recovered.
+pub struct Archive {
+	path: PathBuf,
+	inner: Mutex<Inner>,
+}
+
+impl Archive {
+	/// Opens the archive in the given directory, creating it if it doesn't exist.
+	///
+	/// A record cut short at the end of the last segment, e.g. because the node was killed while
+	/// writing it, is discarded.
+	pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+		let path = path.as_ref().to_path_buf();
+		fs::create_dir_all(&path)?;
+
+		let (index, segments, segment_size) = scan_segments(&path, true)?;
+		let segment = segments.last().copied().unwrap_or_default();
+		let file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(segment_path(&path, segment))?;
+
+		gum::info!(
+			target: LOG_TARGET,
+			path = %path.display(),
+			segments = segments.len(),
+			candidates = index.len(),
+			"Opened the availability archive",
+		);
+
+		let writer = Writer { segment, segment_size, file };
+		Ok(Self { path, inner: Mutex::new(Inner { index, writer: Some(writer) }) })
+	}
+
+	/// Opens an existing archive for lookups only.
+	///
+	/// Nothing is created or truncated, appending to the returned archive fails.
+	pub fn open_read_only(path: impl AsRef<Path>) -> io::Result<Self> {
+		let path = path.as_ref().to_path_buf();
+		if !path.is_dir() {
+			return Err(io::Error::new(
+				io::ErrorKind::NotFound,
+				format!("no availability archive at {}", path.display()),
+			))
+		}
+
+		let (index, _, _) = scan_segments(&path, false)?;
+		Ok(Self { path, inner: Mutex::new(Inner { index, writer: None }) })
+	}
+
+	/// Whether the available data of the candidate is archived.
+	pub fn contains(&self, candidate_hash: &CandidateHash) -> bool {
+		self.inner().index.contains_key(candidate_hash)
+	}
+
+	/// The number of archived candidates.
+	pub fn len(&self) -> usize {
+		self.inner().index.len()
+	}
+
+	/// Whether the archive is empty.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Looks up the archived available data of the candidate.
+	pub fn get(&self, candidate_hash: &CandidateHash) -> io::Result<Option<AvailableData>> {
+		let Some(location) = self.inner().index.get(candidate_hash).copied() else {
+			return Ok(None)
+		};
+
+		let mut file = File::open(segment_path(&self.path, location.segment))?;
+		file.seek(SeekFrom::Start(location.offset - Hash::len_bytes() as u64))?;
+		let mut expected = Hash::zero();
+		file.read_exact(expected.as_bytes_mut())?;
+		let mut encoded = vec![0; location.len as usize];
+		file.read_exact(&mut encoded)?;
+
+		if checksum(candidate_hash, &encoded) != expected {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				format!(
+					"corrupted record of candidate {:?} in {}",
+					candidate_hash,
+					segment_path(&self.path, location.segment).display(),
+				),
+			))
+		}
+
+		AvailableData::decode(&mut &encoded[..])
+			.map(Some)
+			.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+	}
+
+	/// Appends the available data of the candidate, unless it is archived already.
+	///
+	/// The record is not guaranteed to be persisted until [`Archive::sync`] is called.
+	pub fn append(
+		&self,
+		candidate_hash: &CandidateHash,
+		available_data: &AvailableData,
+	) -> io::Result<()> {
+		let mut inner = self.inner();
+		if inner.index.contains_key(candidate_hash) {
+			return Ok(())
+		}
+		let writer = inner.writer.as_mut().ok_or_else(read_only)?;
+
+		if writer.segment_size >= MAX_SEGMENT_SIZE {
+			writer.file.sync_data()?;
+			let segment = writer.segment + 1;
+			writer.file = OpenOptions::new()
+				.create(true)
+				.append(true)
+				.open(segment_path(&self.path, segment))?;
+			writer.segment = segment;
+			writer.segment_size = 0;
+		}
+
+		let encoded = available_data.encode();
+		let len = u32::try_from(encoded.len())
+			.map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "available data too large"))?;
+		let mut record = Vec::with_capacity(RECORD_HEADER_SIZE + encoded.len());
+		record.extend_from_slice(candidate_hash.0.as_bytes());
+		record.extend_from_slice(&len.to_le_bytes());
+		record.extend_from_slice(checksum(candidate_hash, &encoded).as_bytes());
+		record.extend_from_slice(&encoded);
+		if let Err(err) = writer.file.write_all(&record) {
+			// Drop whatever part of the record made it to the file, so offsets stay valid.
+			let _ = writer.file.set_len(writer.segment_size);
+			return Err(err)
+		}
+
+		let location = Location {
+			segment: writer.segment,
+			offset: writer.segment_size + RECORD_HEADER_SIZE as u64,
+			len,
+		};
+		writer.segment_size += record.len() as u64;
+		inner.index.insert(*candidate_hash, location);
+
+		Ok(())
+	}
+
+	/// Persists all the records appended so far to disk.
+	pub fn sync(&self) -> io::Result<()> {
+		self.inner().writer.as_ref().ok_or_else(read_only)?.file.sync_data()
+	}
+
+	fn inner(&self) -> MutexGuard<'_, Inner> {
+		// The index is only updated once a record is fully written, so it stays consistent.
+		self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+/// The checksum of the record of a candidate, given its encoded available data.
+fn checksum(candidate_hash: &CandidateHash, encoded: &[u8]) -> Hash {
+	BlakeTwo256::hash_of(&(candidate_hash, encoded))
+}
+
+fn read_only() -> io::Error {
+	io::Error::new(io::ErrorKind::PermissionDenied, "the availability archive is read-only")
+}
+
+fn segment_path(path: &Path, segment: u32) -> PathBuf {
+	path.join(format!("{:010}.{}", segment, SEGMENT_EXTENSION))
+}
+
+/// Indexes the records of all the segments in the given directory.
+///
+/// Returns the index, the sorted segment numbers and the size of the valid part of the last
+/// segment. A truncated record at the end of the last segment is cut off if `truncate` is set,
+/// and ignored otherwise.
+fn scan_segments(
+	path: &Path,
+	truncate: bool,
+) -> io::Result<(HashMap<CandidateHash, Location>, Vec<u32>, u64)> {
+	let mut segments = Vec::new();
+	for entry in fs::read_dir(path)? {
+		let entry_path = entry?.path();
+		if entry_path.extension().map_or(true, |ext| ext != SEGMENT_EXTENSION) {
+			continue
+		}
+		if let Some(segment) =
+			entry_path.file_stem().and_then(|s| s.to_str()).and_then(|s| s.parse().ok())
+		{
+			segments.push(segment);
+		}
+	}
+	segments.sort_unstable();
+
+	let mut index = HashMap::new();
+	let mut segment_size = 0;
+	for (i, segment) in segments.iter().enumerate() {
+		let is_last = i + 1 == segments.len();
+		segment_size = scan_segment(path, *segment, is_last, truncate, &mut index)?;
+	}
+
+	Ok((index, segments, segment_size))
+}
+
+/// Indexes the records of a segment and returns the size of its valid part.
+fn scan_segment(
+	path: &Path,
+	segment: u32,
+	is_last: bool,
+	truncate: bool,
+	index: &mut HashMap<CandidateHash, Location>,
+) -> io::Result<u64> {
+	let segment_path = segment_path(path, segment);
+	let mut file = File::open(&segment_path)?;
+	let file_size = file.metadata()?.len();
+
+	let mut offset = 0;
+	while offset < file_size {
+		let mut header = [0u8; RECORD_HEADER_SIZE];
+		let record_end = match file.read_exact(&mut header) {
+			Ok(()) => {
+				let len = u32::from_le_bytes(header[32..36].try_into().expect("4 bytes; qed"));
+				let record_end = offset + RECORD_HEADER_SIZE as u64 + len as u64;
+				(record_end <= file_size).then(|| {
+					let candidate_hash = CandidateHash(Hash::from_slice(&header[..32]));
+					let location =
+						Location { segment, offset: offset + RECORD_HEADER_SIZE as u64, len };
+					index.insert(candidate_hash, location);
+					record_end
+				})
+			},
+			Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => None,
+			Err(err) => return Err(err),
+		};
+
+		match record_end {
+			Some(record_end) => {
+				file.seek(SeekFrom::Start(record_end))?;
+				offset = record_end;
+			},
+			// The node may still be writing the record.
+			None if is_last && !truncate => return Ok(offset),
+			None if is_last => {
+				gum::warn!(
+					target: LOG_TARGET,
+					segment = %segment_path.display(),
+					offset,
+					"Discarding a truncated record at the end of the availability archive",
+				);
+				OpenOptions::new().write(true).open(&segment_path)?.set_len(offset)?;
+				return Ok(offset)
+			},
+			None =>
+				return Err(io::Error::new(
+					io::ErrorKind::InvalidData,
+					format!("truncated record in {}", segment_path.display()),
+				)),
+		}
+	}
+
+	Ok(offset)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_node_primitives::{BlockData, PoV};
+	use polkadot_primitives::PersistedValidationData;
+	use std::sync::Arc;
+
+	fn available_data(byte: u8) -> AvailableData {
+		AvailableData {
+			pov: Arc::new(PoV { block_data: BlockData(vec![byte; 64]) }),
+			validation_data: PersistedValidationData::default(),
+		}
+	}
+
+	#[test]
+	fn records_survive_reopening() {
+		let dir = tempfile::tempdir().unwrap();
+		let (hash_a, hash_b) =
+			(CandidateHash(Hash::repeat_byte(1)), CandidateHash(Hash::repeat_byte(2)));
+
+		{
+			let archive = Archive::open(dir.path()).unwrap();
+			archive.append(&hash_a, &available_data(1)).unwrap();
+			archive.append(&hash_b, &available_data(2)).unwrap();
+			// Already archived candidates are not appended twice.
+			archive.append(&hash_a, &available_data(3)).unwrap();
+			archive.sync().unwrap();
+
+			assert_eq!(archive.len(), 2);
+			assert_eq!(archive.get(&hash_a).unwrap(), Some(available_data(1)));
+		}
+
+		let archive = Archive::open(dir.path()).unwrap();
+		assert_eq!(archive.len(), 2);
+		assert_eq!(archive.get(&hash_a).unwrap(), Some(available_data(1)));
+		assert_eq!(archive.get(&hash_b).unwrap(), Some(available_data(2)));
+		assert_eq!(archive.get(&CandidateHash(Hash::repeat_byte(3))).unwrap(), None);
+	}
+
+	#[test]
+	fn truncated_record_is_discarded() {
+		let dir = tempfile::tempdir().unwrap();
+		let (hash_a, hash_b) =
+			(CandidateHash(Hash::repeat_byte(1)), CandidateHash(Hash::repeat_byte(2)));
+
+		let valid_size = {
+			let archive = Archive::open(dir.path()).unwrap();
+			archive.append(&hash_a, &available_data(1)).unwrap();
+			archive.sync().unwrap();
+			let valid_size = archive.inner().writer.as_ref().unwrap().segment_size;
+			archive.append(&hash_b, &available_data(2)).unwrap();
+			valid_size
+		};
+
+		// Cut the second record short.
+		let segment = OpenOptions::new().write(true).open(segment_path(dir.path(), 0)).unwrap();
+		segment.set_len(valid_size + 10).unwrap();
+
+		let archive = Archive::open(dir.path()).unwrap();
+		assert_eq!(archive.len(), 1);
+		assert!(!archive.contains(&hash_b));
+
+		// Appending picks up right after the last valid record.
+		archive.append(&hash_b, &available_data(2)).unwrap();
+		assert_eq!(archive.get(&hash_a).unwrap(), Some(available_data(1)));
+		assert_eq!(archive.get(&hash_b).unwrap(), Some(available_data(2)));
+	}
+
+	#[test]
+	fn read_only_archive_never_modifies_segments() {
+		let dir = tempfile::tempdir().unwrap();
+		let (hash_a, hash_b) =
+			(CandidateHash(Hash::repeat_byte(1)), CandidateHash(Hash::repeat_byte(2)));
+
+		assert!(Archive::open_read_only(dir.path().join("missing")).is_err());
+		assert!(!dir.path().join("missing").exists());
+
+		let archive = Archive::open(dir.path()).unwrap();
+		archive.append(&hash_a, &available_data(1)).unwrap();
+		archive.sync().unwrap();
+		let valid_size = archive.inner().writer.as_ref().unwrap().segment_size;
+
+		// A record still being written by the node is ignored, but left untouched.
+		let segment_path = segment_path(dir.path(), 0);
+		let segment = OpenOptions::new().write(true).open(&segment_path).unwrap();
+		segment.set_len(valid_size + 10).unwrap();
+
+		let read_only = Archive::open_read_only(dir.path()).unwrap();
+		assert_eq!(read_only.len(), 1);
+		assert_eq!(read_only.get(&hash_a).unwrap(), Some(available_data(1)));
+		assert_eq!(fs::metadata(&segment_path).unwrap().len(), valid_size + 10);
+
+		let err = read_only.append(&hash_b, &available_data(2)).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+		assert!(read_only.sync().is_err());
+	}
+
+	#[test]
+	fn corrupted_record_is_detected() {
+		let dir = tempfile::tempdir().unwrap();
+		let (hash_a, hash_b) =
+			(CandidateHash(Hash::repeat_byte(1)), CandidateHash(Hash::repeat_byte(2)));
+
+		let archive = Archive::open(dir.path()).unwrap();
+		archive.append(&hash_a, &available_data(1)).unwrap();
+		archive.append(&hash_b, &available_data(2)).unwrap();
+		archive.sync().unwrap();
+
+		// Flip a byte of the PoV of the first record.
+		let location = archive.inner().index[&hash_a];
+		let mut segment = OpenOptions::new()
+			.read(true)
+			.write(true)
+			.open(segment_path(dir.path(), location.segment))
+			.unwrap();
+		let mut byte = [0u8];
+		segment.seek(SeekFrom::Start(location.offset + 10)).unwrap();
+		segment.read_exact(&mut byte).unwrap();
+		segment.seek(SeekFrom::Start(location.offset + 10)).unwrap();
+		segment.write_all(&[!byte[0]]).unwrap();
+
+		let err = archive.get(&hash_a).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(archive.get(&hash_b).unwrap(), Some(available_data(2)));
+	}
+}
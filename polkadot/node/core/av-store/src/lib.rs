@@ -52,8 +52,9 @@ use polkadot_primitives::{
 };
 use util::availability_chunks::availability_chunk_indices;
 
+mod archive;
 mod metrics;
-pub use self::metrics::*;
+pub use self::{archive::Archive, metrics::*};
 
 #[cfg(test)]
 mod tests;
@@ -72,8 +73,8 @@ const PRUNE_BY_TIME_PREFIX: &[u8; 13] = b"prune_by_time";
 // this because rocksdb doesn't support empty values.
 const TOMBSTONE_VALUE: &[u8] = b" ";
 
-/// Unavailable blocks are kept for 1 hour.
-const KEEP_UNAVAILABLE_FOR: Duration = Duration::from_secs(60 * 60);
+/// How long data which is not available in any block is kept by default (in hours).
+pub const KEEP_UNAVAILABLE_FOR: u32 = 1;
 
 /// The pruning interval.
 const PRUNING_INTERVAL: Duration = Duration::from_secs(60 * 5);
@@ -186,7 +187,10 @@ fn write_available_data(
 	tx.put_vec(config.col_data, &key[..], available_data.encode());
 }
 
-fn load_available_data(
+/// Loads the full available data of a candidate from the availability store database.
+///
+/// Exposed for offline tooling, the subsystem is the only user of the database in a running node.
+pub fn load_available_data(
 	db: &Arc<dyn Database>,
 	config: &Config,
 	hash: &CandidateHash,
@@ -429,6 +433,8 @@ pub struct Config {
 	pub col_meta: u32,
 	/// How long finalized data should be kept (in hours).
 	pub keep_finalized_for: u32,
+	/// How long data which is not available in any block should be kept (in hours).
+	pub keep_unavailable_for: u32,
 }
 
 trait Clock: Send + Sync {
@@ -454,6 +460,7 @@ pub struct AvailabilityStoreSubsystem {
 	metrics: Metrics,
 	clock: Box<dyn Clock>,
 	sync_oracle: Box<dyn SyncOracle + Send + Sync>,
+	archive: Option<Arc<Archive>>,
 }
 
 impl AvailabilityStoreSubsystem {
@@ -465,7 +472,7 @@ impl AvailabilityStoreSubsystem {
 		metrics: Metrics,
 	) -> Self {
 		let pruning_config = PruningConfig {
-			keep_unavailable_for: KEEP_UNAVAILABLE_FOR,
+			keep_unavailable_for: Duration::from_secs(config.keep_unavailable_for as u64 * 3600),
 			keep_finalized_for: Duration::from_secs(config.keep_finalized_for as u64 * 3600),
 			pruning_interval: PRUNING_INTERVAL,
 		};
@@ -498,8 +505,15 @@ impl AvailabilityStoreSubsystem {
 			known_blocks: KnownUnfinalizedBlocks::default(),
 			sync_oracle,
 			finalized_number: None,
+			archive: None,
 		}
 	}
+
+	/// Export the available data of finalized candidates to the given archive before pruning it.
+	pub fn with_archive(mut self, archive: Option<Archive>) -> Self {
+		self.archive = archive.map(Arc::new);
+		self
+	}
 }
 
 /// We keep the hashes and numbers of all unfinalized
@@ -657,6 +671,7 @@ async fn start_prune_all<Context>(
 	let metrics = subsystem.metrics.clone();
 	let db = subsystem.db.clone();
 	let config = subsystem.config;
+	let archive = subsystem.archive.clone();
 	let time_now = subsystem.clock.now()?;
 
 	ctx.spawn_blocking(
@@ -665,7 +680,7 @@ async fn start_prune_all<Context>(
 			let _timer = metrics.time_pruning();
 
 			gum::debug!(target: LOG_TARGET, "Prunning started");
-			let result = prune_all(&db, &config, time_now);
+			let result = prune_all(&db, &config, archive.as_deref(), time_now);
 
 			if let Err(err) = pruning_result_tx.send(result).await {
 				// This usually means that the node is closing down, log it just in case
@@ -1353,7 +1368,12 @@ fn store_available_data(
 	Ok(())
 }
 
-fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(), Error> {
+fn prune_all(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	archive: Option<&Archive>,
+	now: Duration,
+) -> Result<(), Error> {
 	let (range_start, range_end) = pruning_range(now);
 
 	let mut tx = DBTransaction::new();
@@ -1374,8 +1394,11 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(
 
 		// Clean up all attached data of the candidate.
 		if let Some(meta) = load_meta(db, config, &candidate_hash)? {
-			// delete available data.
+			// delete available data, archiving it first if the candidate was finalized.
 			if meta.data_available {
+				if let (Some(archive), State::Finalized(_)) = (archive, &meta.state) {
+					archive_available_data(db, config, archive, &candidate_hash)?;
+				}
 				delete_available_data(&mut tx, config, &candidate_hash)
 			}
 
@@ -1402,6 +1425,33 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(
 		}
 	}
 
+	// Archiving is best effort, failing to do so must not keep the store from being pruned.
+	if let Some(Err(err)) = archive.map(Archive::sync) {
+		gum::warn!(target: LOG_TARGET, ?err, "Failed to sync the availability archive");
+	}
+
 	db.write(tx)?;
 	Ok(())
 }
+
+fn archive_available_data(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	archive: &Archive,
+	candidate_hash: &CandidateHash,
+) -> Result<(), Error> {
+	let Some(available_data) = load_available_data(db, config, candidate_hash)? else {
+		gum::warn!(
+			target: LOG_TARGET,
+			?candidate_hash,
+			"Available data marked as stored but missing, not archiving it",
+		);
+		return Ok(())
+	};
+
+	// The data is pruned anyway, retrying would only keep the store growing.
+	if let Err(err) = archive.append(candidate_hash, &available_data) {
+		gum::warn!(target: LOG_TARGET, ?candidate_hash, ?err, "Failed to archive available data");
+	}
+	Ok(())
+}
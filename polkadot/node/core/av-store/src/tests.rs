@@ -43,8 +43,12 @@ mod columns {
 	pub const NUM_COLUMNS: u32 = 2;
 }
 
-const TEST_CONFIG: Config =
-	Config { col_data: columns::DATA, col_meta: columns::META, keep_finalized_for: 1 };
+const TEST_CONFIG: Config = Config {
+	col_data: columns::DATA,
+	col_meta: columns::META,
+	keep_finalized_for: 1,
+	keep_unavailable_for: KEEP_UNAVAILABLE_FOR,
+};
 
 type VirtualOverseer =
 	polkadot_node_subsystem_test_helpers::TestSubsystemContextHandle<AvailabilityStoreMessage>;
@@ -1384,3 +1388,95 @@ fn query_chunk_size_works() {
 		virtual_overseer
 	});
 }
+
+#[test]
+fn finalized_data_is_archived_before_pruning() {
+	let store = test_store();
+	let archive_dir = tempfile::tempdir().unwrap();
+	let archive = Archive::open(archive_dir.path()).unwrap();
+
+	let finalized = CandidateHash(Hash::repeat_byte(1));
+	let unavailable = CandidateHash(Hash::repeat_byte(2));
+	let available_data = AvailableData {
+		pov: Arc::new(PoV { block_data: BlockData(vec![4, 5, 6]) }),
+		validation_data: TestState::default().persisted_validation_data,
+	};
+
+	with_tx(&store, |tx| {
+		for (candidate_hash, state) in [
+			(finalized, State::Finalized(BETimestamp(0))),
+			(unavailable, State::Unavailable(BETimestamp(0))),
+		] {
+			super::write_meta(
+				tx,
+				&TEST_CONFIG,
+				&candidate_hash,
+				&CandidateMeta {
+					data_available: true,
+					chunks_stored: bitvec::bitvec![u8, BitOrderLsb0; 0; 10],
+					state,
+				},
+			);
+			super::write_available_data(tx, &TEST_CONFIG, &candidate_hash, &available_data);
+			super::write_pruning_key(tx, &TEST_CONFIG, Duration::from_secs(1), &candidate_hash);
+		}
+	});
+
+	prune_all(&store, &TEST_CONFIG, Some(&archive), Duration::from_secs(2)).unwrap();
+
+	for candidate_hash in [finalized, unavailable] {
+		assert!(load_available_data(&store, &TEST_CONFIG, &candidate_hash).unwrap().is_none());
+	}
+	assert_eq!(archive.get(&finalized).unwrap(), Some(available_data));
+	assert!(!archive.contains(&unavailable));
+}
+
+#[test]
+fn failing_to_archive_does_not_block_pruning() {
+	let store = test_store();
+	let archive_dir = tempfile::tempdir().unwrap();
+	drop(Archive::open(archive_dir.path()).unwrap());
+	// Appending to a read-only archive always fails.
+	let archive = Archive::open_read_only(archive_dir.path()).unwrap();
+
+	let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+	let available_data = AvailableData {
+		pov: Arc::new(PoV { block_data: BlockData(vec![4, 5, 6]) }),
+		validation_data: TestState::default().persisted_validation_data,
+	};
+
+	with_tx(&store, |tx| {
+		super::write_meta(
+			tx,
+			&TEST_CONFIG,
+			&candidate_hash,
+			&CandidateMeta {
+				data_available: true,
+				chunks_stored: bitvec::bitvec![u8, BitOrderLsb0; 0; 10],
+				state: State::Finalized(BETimestamp(0)),
+			},
+		);
+		super::write_available_data(tx, &TEST_CONFIG, &candidate_hash, &available_data);
+		super::write_pruning_key(tx, &TEST_CONFIG, Duration::from_secs(1), &candidate_hash);
+	});
+
+	prune_all(&store, &TEST_CONFIG, Some(&archive), Duration::from_secs(2)).unwrap();
+
+	assert!(load_available_data(&store, &TEST_CONFIG, &candidate_hash).unwrap().is_none());
+	assert!(load_meta(&store, &TEST_CONFIG, &candidate_hash).unwrap().is_none());
+	assert!(!archive.contains(&candidate_hash));
+}
+
+#[test]
+fn retention_is_configurable() {
+	let config = Config { keep_finalized_for: 25, keep_unavailable_for: 3, ..TEST_CONFIG };
+	let subsystem = AvailabilityStoreSubsystem::new(
+		test_store(),
+		config,
+		Box::new(NoSyncOracle),
+		Metrics::default(),
+	);
+
+	assert_eq!(subsystem.pruning_config.keep_unavailable_for, Duration::from_secs(3 * 3600));
+	assert_eq!(subsystem.pruning_config.keep_finalized_for, Duration::from_secs(25 * 3600));
+}
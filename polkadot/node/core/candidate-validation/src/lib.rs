@@ -1057,6 +1057,37 @@ async fn validate_candidate_exhaustive(
 	}
 }
 
+/// Re-validates a candidate outside of the subsystem, as it would be validated when participating
+/// in a dispute.
+///
+/// This is meant for offline tooling reproducing the validation of a candidate: the checks which
+/// depend on the state of the relay chain at the time of backing are skipped.
+pub async fn revalidate_candidate(
+	validation_host: ValidationHost,
+	persisted_validation_data: PersistedValidationData,
+	validation_code: ValidationCode,
+	candidate_receipt: CandidateReceipt,
+	pov: Arc<PoV>,
+	executor_params: ExecutorParams,
+	validation_code_bomb_limit: u32,
+) -> Result<ValidationResult, ValidationFailed> {
+	validate_candidate_exhaustive(
+		// The session index is only checked for backing.
+		0,
+		validation_host,
+		persisted_validation_data,
+		validation_code,
+		candidate_receipt,
+		pov,
+		executor_params,
+		PvfExecKind::Dispute,
+		&Metrics::default(),
+		None,
+		validation_code_bomb_limit,
+	)
+	.await
+}
+
 #[async_trait]
 trait ValidationBackend {
 	/// Tries executing a PVF a single time (no retries).
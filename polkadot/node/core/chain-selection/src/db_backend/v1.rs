@@ -82,9 +82,41 @@ impl From<Approval> for crate::Approval {
 	}
 }
 
+// Encodes like the `bool` which used to record reversions, so reversions stored before their
+// trigger was tracked are read back as disputes.
+#[derive(Debug, Encode, Decode, Clone, Copy, PartialEq)]
+enum Reversion {
+	#[codec(index = 0)]
+	NotReverted,
+	#[codec(index = 1)]
+	Dispute,
+	#[codec(index = 2)]
+	Log,
+}
+
+impl From<Option<crate::Reversion>> for Reversion {
+	fn from(x: Option<crate::Reversion>) -> Self {
+		match x {
+			None => Reversion::NotReverted,
+			Some(crate::Reversion::Dispute) => Reversion::Dispute,
+			Some(crate::Reversion::Log) => Reversion::Log,
+		}
+	}
+}
+
+impl From<Reversion> for Option<crate::Reversion> {
+	fn from(x: Reversion) -> Option<crate::Reversion> {
+		match x {
+			Reversion::NotReverted => None,
+			Reversion::Dispute => Some(crate::Reversion::Dispute),
+			Reversion::Log => Some(crate::Reversion::Log),
+		}
+	}
+}
+
 #[derive(Debug, Encode, Decode, Clone, PartialEq)]
 struct ViabilityCriteria {
-	explicitly_reverted: bool,
+	explicitly_reverted: Reversion,
 	approval: Approval,
 	earliest_unviable_ancestor: Option<Hash>,
 }
@@ -92,7 +124,7 @@ struct ViabilityCriteria {
 impl From<crate::ViabilityCriteria> for ViabilityCriteria {
 	fn from(x: crate::ViabilityCriteria) -> Self {
 		ViabilityCriteria {
-			explicitly_reverted: x.explicitly_reverted,
+			explicitly_reverted: x.explicitly_reverted.into(),
 			approval: x.approval.into(),
 			earliest_unviable_ancestor: x.earliest_unviable_ancestor,
 		}
@@ -102,7 +134,7 @@ impl From<crate::ViabilityCriteria> for ViabilityCriteria {
 impl From<ViabilityCriteria> for crate::ViabilityCriteria {
 	fn from(x: ViabilityCriteria) -> crate::ViabilityCriteria {
 		crate::ViabilityCriteria {
-			explicitly_reverted: x.explicitly_reverted,
+			explicitly_reverted: x.explicitly_reverted.into(),
 			approval: x.approval.into(),
 			earliest_unviable_ancestor: x.earliest_unviable_ancestor,
 		}
@@ -193,6 +225,7 @@ pub struct Config {
 }
 
 /// The database backend.
+#[derive(Clone)]
 pub struct DbBackend {
 	inner: Arc<dyn Database>,
 	config: Config,
@@ -417,6 +450,12 @@ mod tests {
 		assert_eq!(decode_stagnant_at_key(&key), Some(5));
 	}
 
+	#[test]
+	fn reversion_decodes_from_legacy_bool() {
+		assert_eq!(Reversion::decode(&mut &false.encode()[..]).unwrap(), Reversion::NotReverted);
+		assert_eq!(Reversion::decode(&mut &true.encode()[..]).unwrap(), Reversion::Dispute);
+	}
+
 	#[test]
 	fn lower_block_height_key_lesser() {
 		for i in 0..256 {
@@ -455,7 +494,7 @@ mod tests {
 			children: vec![],
 			viability: ViabilityCriteria {
 				earliest_unviable_ancestor: None,
-				explicitly_reverted: false,
+				explicitly_reverted: Reversion::NotReverted,
 				approval: Approval::Unapproved,
 			},
 			weight: 100,
@@ -471,6 +510,74 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn non_viable_leaves_metrics_are_computed_on_scrape() {
+		use polkadot_node_subsystem_util::metrics::{prometheus, Metrics as _};
+
+		let gather = |registry: &prometheus::Registry, name: &str| {
+			let mut values: Vec<(Vec<String>, u64)> = registry
+				.gather()
+				.iter()
+				.find(|family| family.get_name() == name)
+				.expect("metric is registered; qed")
+				.get_metric()
+				.iter()
+				.map(|metric| {
+					let labels =
+						metric.get_label().iter().map(|l| l.get_value().to_owned()).collect();
+					(labels, metric.get_gauge().get_value() as u64)
+				})
+				.collect();
+			values.sort();
+			values
+		};
+		let non_viable = "polkadot_parachain_chain_selection_non_viable_leaves";
+		let longest_chain = "polkadot_parachain_chain_selection_longest_non_viable_chain";
+
+		let registry = prometheus::Registry::new();
+		let metrics = crate::Metrics::register(Some(&registry)).unwrap();
+		let mut backend = DbBackend::new(test_db(), Config { col_data: 0 });
+
+		let block_entry = BlockEntry {
+			block_hash: Hash::repeat_byte(1),
+			block_number: 1,
+			parent_hash: Hash::repeat_byte(0),
+			children: vec![],
+			viability: ViabilityCriteria {
+				earliest_unviable_ancestor: None,
+				explicitly_reverted: Reversion::NotReverted,
+				approval: Approval::Stagnant,
+			},
+			weight: 100,
+		};
+		backend
+			.write(vec![
+				BackendWriteOp::WriteBlockEntry(block_entry.clone().into()),
+				BackendWriteOp::WriteBlocksByNumber(1, vec![block_entry.block_hash]),
+			])
+			.unwrap();
+
+		// Nothing is loaded until the subsystem provides its backend.
+		assert_eq!(gather(&registry, longest_chain), vec![(vec![], 0)]);
+
+		metrics.set_backend(&backend);
+		assert_eq!(
+			gather(&registry, non_viable),
+			vec![
+				(vec!["dispute-revert".to_owned()], 0),
+				(vec!["explicit-revert".to_owned()], 0),
+				(vec!["stagnant".to_owned()], 1),
+			],
+		);
+		assert_eq!(gather(&registry, longest_chain), vec![(vec![], 1)]);
+
+		// Later changes are picked up by the next scrape.
+		backend
+			.write(vec![BackendWriteOp::DeleteBlockEntry(block_entry.block_hash)])
+			.unwrap();
+		assert_eq!(gather(&registry, longest_chain), vec![(vec![], 0)]);
+	}
+
 	#[test]
 	fn delete_block_entry() {
 		let db = test_db();
@@ -485,7 +592,7 @@ mod tests {
 			children: vec![],
 			viability: ViabilityCriteria {
 				earliest_unviable_ancestor: None,
-				explicitly_reverted: false,
+				explicitly_reverted: Reversion::NotReverted,
 				approval: Approval::Unapproved,
 			},
 			weight: 100,
@@ -38,8 +38,11 @@ use crate::backend::{Backend, BackendWriteOp, OverlayedBackend};
 
 mod backend;
 mod db_backend;
+mod metrics;
 mod tree;
 
+pub use self::metrics::Metrics;
+
 #[cfg(test)]
 mod tests;
 
@@ -73,10 +76,19 @@ impl Approval {
 	}
 }
 
+// What triggered the reversion of a block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Reversion {
+	// A dispute concluded against a candidate included in the block.
+	Dispute,
+	// A reversion log in the header of a descendant.
+	Log,
+}
+
 #[derive(Debug, Clone)]
 struct ViabilityCriteria {
-	// Whether this block has been explicitly reverted by one of its descendants.
-	explicitly_reverted: bool,
+	// Whether this block has been explicitly reverted, and what triggered it.
+	explicitly_reverted: Option<Reversion>,
 	// The approval state of this block specifically.
 	approval: Approval,
 	// The earliest unviable ancestor - the hash of the earliest unfinalized
@@ -92,7 +104,7 @@ impl ViabilityCriteria {
 	// Whether the current block is explicitly viable.
 	// That is, whether the current block is neither reverted nor stagnant.
 	fn is_explicitly_viable(&self) -> bool {
-		!self.explicitly_reverted && !self.approval.is_stagnant()
+		self.explicitly_reverted.is_none() && !self.approval.is_stagnant()
 	}
 
 	// Whether the parent is viable. This assumes that the parent
@@ -329,13 +341,14 @@ pub struct Config {
 pub struct ChainSelectionSubsystem {
 	config: Config,
 	db: Arc<dyn Database>,
+	metrics: Metrics,
 }
 
 impl ChainSelectionSubsystem {
 	/// Create a new instance of the subsystem with the given config
 	/// and key-value store.
-	pub fn new(config: Config, db: Arc<dyn Database>) -> Self {
-		ChainSelectionSubsystem { config, db }
+	pub fn new(config: Config, db: Arc<dyn Database>, metrics: Metrics) -> Self {
+		ChainSelectionSubsystem { config, db, metrics }
 	}
 
 	/// Revert to the block corresponding to the specified `hash`.
@@ -357,6 +370,7 @@ impl<Context> ChainSelectionSubsystem {
 			self.db,
 			db_backend::v1::Config { col_data: self.config.col_data },
 		);
+		self.metrics.set_backend(&backend);
 
 		SpawnedSubsystem {
 			future: run(
@@ -445,7 +459,7 @@ where
 						}
 					}
 					FromOrchestra::Signal(OverseerSignal::BlockFinalized(h, n)) => {
-						handle_finalized_block(backend, h, n)?
+						handle_finalized_block(backend, h, n)?;
 					}
 					FromOrchestra::Communication { msg } => match msg {
 						ChainSelectionMessage::Approved(hash) => {
@@ -472,17 +486,22 @@ where
 							let write_ops = handle_revert_blocks(backend, blocks_to_revert)?;
 							backend.write(write_ops)?;
 						}
+						ChainSelectionMessage::NonViableLeaves(tx) => {
+							let _ = tx.send(tree::non_viable_leaves(&*backend)?);
+						}
 					}
 				}
 			}
 			_ = stagnant_check_stream.next().fuse() => {
 				match stagnant_check_mode {
-					StagnantCheckMode::CheckAndPrune => detect_stagnant(backend, clock.timestamp_now(), MAX_STAGNANT_ENTRIES),
+					StagnantCheckMode::CheckAndPrune => {
+						detect_stagnant(backend, clock.timestamp_now(), MAX_STAGNANT_ENTRIES)?;
+					},
 					StagnantCheckMode::PruneOnly => {
 						let now_timestamp = clock.timestamp_now();
-						prune_only_stagnant(backend, now_timestamp - STAGNANT_PRUNE_DELAY, MAX_STAGNANT_ENTRIES)
+						prune_only_stagnant(backend, now_timestamp - STAGNANT_PRUNE_DELAY, MAX_STAGNANT_ENTRIES)?;
 					},
-				}?;
+				}
 			}
 		}
 	}
@@ -0,0 +1,132 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, OnceLock};
+
+use polkadot_node_subsystem::messages::{NonViabilityReason, NonViableLeaf};
+use polkadot_node_subsystem_util::metrics::{self, prometheus};
+
+use crate::{db_backend::v1::DbBackend, tree, LOG_TARGET};
+
+/// The backend the non-viable leaves are loaded from, once the subsystem has started.
+type SharedBackend = Arc<OnceLock<DbBackend>>;
+
+#[derive(Clone)]
+pub(crate) struct MetricsInner {
+	backend: SharedBackend,
+}
+
+/// Chain selection metrics.
+///
+/// The non-viable leaves are only collected when the metrics are scraped, so that the subsystem
+/// doesn't walk the unfinalized tree on every change to it.
+#[derive(Default, Clone)]
+pub struct Metrics(Option<MetricsInner>);
+
+impl Metrics {
+	/// Provide the backend the metrics are collected from.
+	pub(crate) fn set_backend(&self, backend: &DbBackend) {
+		if let Some(metrics) = &self.0 {
+			let _ = metrics.backend.set(backend.clone());
+		}
+	}
+}
+
+// Load the non-viable leaves for a scrape.
+//
+// Failing to do so isn't worth more than a debug log, the metrics are reported as empty.
+fn load_non_viable_leaves(backend: &SharedBackend) -> Vec<NonViableLeaf> {
+	let Some(backend) = backend.get() else { return Vec::new() };
+
+	tree::non_viable_leaves(backend).unwrap_or_else(|err| {
+		gum::debug!(target: LOG_TARGET, ?err, "Failed to load the non-viable leaves");
+		Vec::new()
+	})
+}
+
+/// Number of non-viable leaves, by reason.
+#[derive(Clone)]
+struct NonViableLeavesCount(SharedBackend);
+
+impl prometheus::MetricSource for NonViableLeavesCount {
+	type N = u64;
+
+	fn collect(&self, mut set: impl FnMut(&[&str], Self::N)) {
+		let leaves = load_non_viable_leaves(&self.0);
+		for reason in [
+			NonViabilityReason::Stagnant,
+			NonViabilityReason::DisputeRevert,
+			NonViabilityReason::ExplicitRevert,
+		] {
+			let count = leaves.iter().filter(|leaf| leaf.reason == reason).count();
+			set(&[reason_label(reason)], count as u64);
+		}
+	}
+}
+
+/// Length of the longest non-viable chain.
+#[derive(Clone)]
+struct LongestNonViableChain(SharedBackend);
+
+impl prometheus::MetricSource for LongestNonViableChain {
+	type N = u64;
+
+	fn collect(&self, mut set: impl FnMut(&[&str], Self::N)) {
+		let longest_chain = load_non_viable_leaves(&self.0)
+			.iter()
+			.map(|leaf| leaf.block_number.saturating_sub(leaf.earliest_non_viable_number) + 1)
+			.max()
+			.unwrap_or_default();
+		set(&[], longest_chain as u64);
+	}
+}
+
+fn reason_label(reason: NonViabilityReason) -> &'static str {
+	match reason {
+		NonViabilityReason::Stagnant => "stagnant",
+		NonViabilityReason::DisputeRevert => "dispute-revert",
+		NonViabilityReason::ExplicitRevert => "explicit-revert",
+	}
+}
+
+impl metrics::Metrics for Metrics {
+	fn try_register(registry: &prometheus::Registry) -> Result<Self, prometheus::PrometheusError> {
+		let backend = SharedBackend::default();
+		prometheus::register(
+			prometheus::SourcedGauge::new(
+				&prometheus::Opts::new(
+					"polkadot_parachain_chain_selection_non_viable_leaves",
+					"Number of leaves which aren't viable to build on, by reason.",
+				)
+				.variable_label("reason"),
+				NonViableLeavesCount(backend.clone()),
+			)?,
+			registry,
+		)?;
+		prometheus::register(
+			prometheus::SourcedGauge::new(
+				&prometheus::Opts::new(
+					"polkadot_parachain_chain_selection_longest_non_viable_chain",
+					"Number of blocks from the earliest non-viable block up to the leaf, for the \
+					 longest non-viable chain.",
+				),
+				LongestNonViableChain(backend.clone()),
+			)?,
+			registry,
+		)?;
+		Ok(Metrics(Some(MetricsInner { backend })))
+	}
+}
@@ -35,7 +35,10 @@ use futures::channel::oneshot;
 use parking_lot::Mutex;
 use sp_core::testing::TaskExecutor;
 
-use polkadot_node_subsystem::{messages::AllMessages, ActiveLeavesUpdate};
+use polkadot_node_subsystem::{
+	messages::{AllMessages, NonViabilityReason, NonViableLeaf},
+	ActiveLeavesUpdate,
+};
 use polkadot_node_subsystem_test_helpers as test_helpers;
 use polkadot_primitives::{BlakeTwo256, ConsensusLog, HashT};
 use test_helpers::mock::new_leaf;
@@ -2092,7 +2095,7 @@ fn revert_blocks_message_triggers_proper_reversion() {
 				.unwrap()
 				.viability
 				.explicitly_reverted,
-			true
+			Some(Reversion::Dispute)
 		);
 		// Block 3 should be non-viable, with 2 as its earliest unviable ancestor
 		assert_eq!(
@@ -2149,3 +2152,58 @@ fn revert_blocks_against_finalized_is_ignored() {
 		virtual_overseer
 	})
 }
+
+#[test]
+fn non_viable_leaves_report_reason_and_range() {
+	test_harness(|backend, _, mut virtual_overseer| async move {
+		// Building mini chain with 1 finalized block and 3 unfinalized blocks
+		let finalized_number = 0;
+		let finalized_hash = Hash::repeat_byte(0);
+
+		let (head_hash, built_chain) =
+			construct_chain_on_base(vec![1, 2, 3], finalized_number, finalized_hash, |_| {});
+
+		import_blocks_into(
+			&mut virtual_overseer,
+			&backend,
+			Some((finalized_number, finalized_hash)),
+			built_chain.clone(),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+		virtual_overseer
+			.send(FromOrchestra::Communication { msg: ChainSelectionMessage::NonViableLeaves(tx) })
+			.await;
+		assert!(rx.await.unwrap().is_empty());
+
+		let block_2_hash = *backend.load_blocks_by_number(2).unwrap().get(0).unwrap();
+
+		let (_, write_rx) = backend.await_next_write();
+		virtual_overseer
+			.send(FromOrchestra::Communication {
+				msg: ChainSelectionMessage::RevertBlocks(Vec::from([(2, block_2_hash)])),
+			})
+			.await;
+
+		write_rx.await.unwrap();
+
+		// The head is non-viable because of the reverted block 2.
+		let (tx, rx) = oneshot::channel();
+		virtual_overseer
+			.send(FromOrchestra::Communication { msg: ChainSelectionMessage::NonViableLeaves(tx) })
+			.await;
+		assert_eq!(
+			rx.await.unwrap(),
+			vec![NonViableLeaf {
+				block_hash: head_hash,
+				block_number: 3,
+				earliest_non_viable_hash: block_2_hash,
+				earliest_non_viable_number: 2,
+				reason: NonViabilityReason::DisputeRevert,
+			}],
+		);
+
+		virtual_overseer
+	})
+}
@@ -24,12 +24,17 @@
 //! and as the finalized block advances, orphaned sub-trees are entirely pruned.
 
 use polkadot_node_primitives::BlockWeight;
-use polkadot_node_subsystem::ChainApiError;
+use polkadot_node_subsystem::{
+	messages::{NonViabilityReason, NonViableLeaf},
+	ChainApiError,
+};
 use polkadot_primitives::{BlockNumber, Hash};
 
 use std::collections::HashMap;
 
-use super::{Approval, BlockEntry, Error, LeafEntry, Timestamp, ViabilityCriteria, LOG_TARGET};
+use super::{
+	Approval, BlockEntry, Error, LeafEntry, Reversion, Timestamp, ViabilityCriteria, LOG_TARGET,
+};
 use crate::backend::{Backend, OverlayedBackend};
 
 // A viability update to be applied to a block.
@@ -319,7 +324,7 @@ fn add_block(
 		children: Vec::new(),
 		viability: ViabilityCriteria {
 			earliest_unviable_ancestor: inherited_viability,
-			explicitly_reverted: false,
+			explicitly_reverted: None,
 			approval: Approval::Unapproved,
 		},
 		weight,
@@ -378,6 +383,7 @@ fn apply_reversions(
 			revert_number,
 			Some(block_entry.block_hash),
 			Some(block_entry.block_number),
+			Reversion::Log,
 		)?;
 	}
 
@@ -406,6 +412,7 @@ pub(crate) fn apply_single_reversion(
 		revert_number,
 		None,
 		None,
+		Reversion::Dispute,
 	)?;
 	Ok(())
 }
@@ -417,6 +424,7 @@ fn revert_single_block_entry_if_present(
 	revert_number: BlockNumber,
 	maybe_reporting_hash: Option<Hash>,
 	maybe_reporting_number: Option<BlockNumber>,
+	reversion: Reversion,
 ) -> Result<(), Error> {
 	match maybe_block_entry {
 		None => {
@@ -441,7 +449,7 @@ fn revert_single_block_entry_if_present(
 				"Unfinalized block reverted due to a bad parachain block.",
 			);
 
-			block_entry.viability.explicitly_reverted = true;
+			block_entry.viability.explicitly_reverted = Some(reversion);
 			// Marks children of reverted block as non-viable
 			propagate_viability_update(backend, block_entry)?;
 		},
@@ -729,7 +737,7 @@ pub(super) fn revert_to<'a, B: Backend + 'a>(
 			// the last finalized block, i.e. this is the best and only choice.
 			let block_number = first_number.saturating_sub(1);
 			let viability = ViabilityCriteria {
-				explicitly_reverted: false,
+				explicitly_reverted: None,
 				approval: Approval::Approved,
 				earliest_unviable_ancestor: None,
 			};
@@ -780,3 +788,59 @@ pub(super) fn revert_to<'a, B: Backend + 'a>(
 
 	Ok(backend)
 }
+
+/// Collect the leaves of the tree which aren't viable, along with the earliest non-viable block in
+/// their ancestry, in ascending order by number.
+pub(super) fn non_viable_leaves(backend: &impl Backend) -> Result<Vec<NonViableLeaf>, Error> {
+	let mut non_viable_leaves = Vec::new();
+	let Some(mut number) = backend.load_first_block_number()? else { return Ok(non_viable_leaves) };
+
+	loop {
+		let blocks_at = backend.load_blocks_by_number(number)?;
+		if blocks_at.is_empty() {
+			break
+		}
+
+		for block_hash in blocks_at {
+			let Some(entry) = backend.load_block_entry(&block_hash)? else { continue };
+			if !entry.children.is_empty() || entry.viability.is_viable() {
+				continue
+			}
+
+			let earliest_non_viable = match entry.viability.earliest_unviable_ancestor {
+				None => entry.clone(),
+				Some(ancestor) => match backend.load_block_entry(&ancestor)? {
+					Some(ancestor) => ancestor,
+					None => {
+						gum::debug!(
+							target: LOG_TARGET,
+							?block_hash,
+							?ancestor,
+							"Missing entry for the earliest unviable ancestor"
+						);
+
+						continue
+					},
+				},
+			};
+
+			let reason = match earliest_non_viable.viability.explicitly_reverted {
+				Some(Reversion::Dispute) => NonViabilityReason::DisputeRevert,
+				Some(Reversion::Log) => NonViabilityReason::ExplicitRevert,
+				None => NonViabilityReason::Stagnant,
+			};
+
+			non_viable_leaves.push(NonViableLeaf {
+				block_hash,
+				block_number: entry.block_number,
+				earliest_non_viable_hash: earliest_non_viable.block_hash,
+				earliest_non_viable_number: earliest_non_viable.block_number,
+				reason,
+			});
+		}
+
+		number += 1;
+	}
+
+	Ok(non_viable_leaves)
+}
@@ -21,6 +21,12 @@ use polkadot_node_subsystem::{
 		AllMessages, HypotheticalMembershipRequest, ParentHeadData, ProspectiveParachainsMessage,
 		ProspectiveValidationDataRequest,
 	},
+	overseer::{
+		recorder::{
+			read_trace, RecordedDelivery, RecordedResponses, RecordedSignal, RecordingContext,
+		},
+		MessageRecorder,
+	},
 	RuntimeApiError,
 };
 use polkadot_node_subsystem_test_helpers as test_helpers;
@@ -2817,3 +2823,100 @@ fn uses_ancestry_only_within_session() {
 		virtual_overseer
 	});
 }
+
+// The deliveries recorded while the subsystem runs, replayed along with the recorded responses to
+// its requests, lead a fresh subsystem to the same backable candidates.
+#[test]
+fn recorded_deliveries_are_replayed() {
+	let test_state = TestState::default();
+	let trace_dir = tempfile::tempdir().unwrap();
+	let trace_path = trace_dir.path().join("trace");
+	let leaf_a = TestLeaf {
+		number: 100,
+		hash: Hash::from_low_u64_be(130),
+		para_data: vec![
+			(1.into(), PerParaData::new(97, HeadData(vec![1, 2, 3]))),
+			(2.into(), PerParaData::new(100, HeadData(vec![2, 3, 4]))),
+		],
+	};
+	let (candidate_a, pvd_a) = make_candidate(
+		leaf_a.hash,
+		leaf_a.number,
+		1.into(),
+		HeadData(vec![1, 2, 3]),
+		HeadData(vec![1]),
+		test_state.validation_code_hash,
+	);
+	let backable = vec![(candidate_a.hash(), leaf_a.hash)];
+
+	let pool = sp_core::testing::TaskExecutor::new();
+	let (context, mut virtual_overseer) =
+		polkadot_node_subsystem_test_helpers::make_subsystem_context(pool.clone());
+	let recorder = MessageRecorder::create(&trace_path).unwrap();
+	virtual_overseer.record_responses(recorder.clone(), pool.clone());
+	let mut context = RecordingContext::new(context, recorder.clone());
+	let subsystem = async move {
+		run_iteration(&mut context, &mut View::new(), &Metrics(None)).await.unwrap();
+	};
+	let test = async {
+		activate_leaf(&mut virtual_overseer, &leaf_a, &test_state).await;
+		introduce_seconded_candidate(&mut virtual_overseer, candidate_a.clone(), pvd_a).await;
+		back_candidate(&mut virtual_overseer, &candidate_a, candidate_a.hash()).await;
+		get_backable_candidates(
+			&mut virtual_overseer,
+			&leaf_a,
+			1.into(),
+			Ancestors::default(),
+			5,
+			backable.clone(),
+		)
+		.await;
+		virtual_overseer.send(FromOrchestra::Signal(OverseerSignal::Conclude)).await;
+	};
+	futures::executor::block_on(future::join(test, subsystem));
+
+	recorder.flush();
+	let trace: Vec<_> = read_trace(&trace_path)
+		.unwrap()
+		.into_iter()
+		.filter(|entry| entry.delivery != RecordedDelivery::Signal(RecordedSignal::Conclude))
+		.collect();
+	let responses = RecordedResponses::new(&trace);
+
+	let (mut context, mut virtual_overseer) =
+		polkadot_node_subsystem_test_helpers::make_subsystem_context(pool);
+	let mut view = View::new();
+	let subsystem = async {
+		run_iteration(&mut context, &mut view, &Metrics(None)).await.unwrap();
+	};
+	let test = async {
+		let mut tx = virtual_overseer.tx.clone();
+		let replay = async {
+			let replayed = test_helpers::replay_trace(tx.clone(), trace).await;
+			let (response_tx, response_rx) = oneshot::channel();
+			tx.send(FromOrchestra::Communication {
+				msg: ProspectiveParachainsMessage::GetBackableCandidates(
+					leaf_a.hash,
+					1.into(),
+					5,
+					Ancestors::default(),
+					response_tx,
+				),
+			})
+			.await
+			.unwrap();
+			(replayed, response_rx.await.unwrap())
+		};
+		let ((replayed, replayed_backable), unanswered) =
+			test_helpers::answer_recorded_requests(&mut virtual_overseer, &responses, replay).await;
+
+		// The leaf activation, the introduction of candidate A and its backing.
+		assert_eq!(replayed, 3);
+		assert_eq!(replayed_backable, backable);
+		assert!(unanswered.is_empty());
+		virtual_overseer.send(FromOrchestra::Signal(OverseerSignal::Conclude)).await;
+	};
+	futures::executor::block_on(future::join(test, subsystem));
+
+	assert_eq!(view.active_leaves.len(), 1);
+}
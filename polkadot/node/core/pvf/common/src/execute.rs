@@ -37,6 +37,11 @@ pub struct WorkerResponse {
 	pub duration: Duration,
 	/// The uncompressed PoV size.
 	pub pov_size: u32,
+	/// The maximum resident set size of the job processes of the worker so far, in kilobytes.
+	///
+	/// As reported by `getrusage` for the terminated children of the worker, so this is the peak
+	/// of the largest job the worker ran. `None` if no job was run.
+	pub max_rss: Option<i64>,
 }
 
 /// An error occurred in the worker process.
@@ -225,6 +225,7 @@ pub fn worker_entrypoint(
 									job_response: JobResponse::PoVDecompressionFailure,
 									duration: Duration::ZERO,
 									pov_size: 0,
+									max_rss: None,
 								}),
 								worker_info,
 							)?;
@@ -629,7 +630,12 @@ fn handle_parent_process(
 						))));
 					}
 
-					Ok(Ok(WorkerResponse { job_response, pov_size, duration: cpu_tv }))
+					Ok(Ok(WorkerResponse {
+						job_response,
+						pov_size,
+						duration: cpu_tv,
+						max_rss: Some(usage_after.max_rss() as i64),
+					}))
 				},
 				Err(job_error) => {
 					gum::warn!(
@@ -18,7 +18,11 @@
 //!
 //! # Lifecycle of an artifact
 //!
-//! 1. During node start-up, we prune all the cached artifacts, if any.
+//! 1. During node start-up, we load the cached artifacts prepared by the same node version, if any,
+//!    and prune the others. Every artifact is accompanied by a metadata file binding it to its
+//!    [`ArtifactId`], its checksum and the node version. The checksum of a loaded artifact is only
+//!    verified when it is first needed for execution; on mismatch the artifact is removed and
+//!    prepared again.
 //!
 //! 2. In order to be executed, a PVF should be prepared first. This means that artifacts should
 //!    have an [`ArtifactState::Prepared`] entry for that artifact in the table. If not, the
@@ -34,8 +38,9 @@
 //!
 //! 3. The pool gets an available worker and instructs it to work on the given PVF. The worker
 //!    starts compilation. When the worker finishes successfully, it writes the serialized artifact
-//!    into a temporary file and notifies the host that it's done. The host atomically moves
-//!    (renames) the temporary file to the destination filename of the artifact.
+//!    into a temporary file and notifies the host that it's done, together with the checksum of the
+//!    artifact. The host atomically moves (renames) the temporary file to the destination filename
+//!    of the artifact, and then writes the metadata file of the artifact.
 //!
 //! 4. If the worker concluded successfully or returned an error, then the pool notifies the queue.
 //!    In both cases, the queue reports to the host that the result is ready.
@@ -54,14 +59,16 @@
 //!    older by a predefined parameter. This process is run very rarely (say, once a day). Once the
 //!    artifact is expired it is removed from disk eagerly atomically.
 
-use crate::{host::PrecheckResultSender, worker_interface::WORKER_DIR_PREFIX};
+use crate::{host::PrecheckResultSender, worker_interface::WORKER_DIR_PREFIX, LOG_TARGET};
 use always_assert::always;
+use codec::{Decode, Encode};
 use polkadot_node_core_pvf_common::{error::PrepareError, pvf::PvfPrepData};
+use polkadot_node_primitives::NODE_VERSION;
 use polkadot_parachain_primitives::primitives::ValidationCodeHash;
 use polkadot_primitives::ExecutorParamsPrepHash;
 use std::{
 	collections::HashMap,
-	fs,
+	fs, io,
 	path::{Path, PathBuf},
 	time::{Duration, SystemTime},
 };
@@ -69,6 +76,9 @@ use std::{
 /// The extension to use for cached artifacts.
 const ARTIFACT_EXTENSION: &str = "pvf";
 
+/// The extension to use for the metadata files of cached artifacts.
+const ARTIFACT_METADATA_EXTENSION: &str = "meta";
+
 /// The prefix that artifacts used to start with under the old naming scheme.
 const ARTIFACT_OLD_PREFIX: &str = "wasmtime_";
 
@@ -85,9 +95,54 @@ pub fn generate_artifact_path(cache_path: &Path) -> PathBuf {
 	artifact_path
 }
 
+/// Returns the path of the metadata file of the artifact at `artifact_path`.
+pub fn artifact_metadata_path(artifact_path: &Path) -> PathBuf {
+	artifact_path.with_extension(ARTIFACT_METADATA_EXTENSION)
+}
+
+/// Computes the checksum of a compiled artifact, the same way the prepare worker does.
+pub fn artifact_checksum(artifact: &[u8]) -> String {
+	blake3::hash(artifact).to_hex().to_string()
+}
+
+/// The content of the metadata file kept next to a cached artifact.
+///
+/// Binds the artifact to the PVF and the preparation-related executor parameters it was prepared
+/// with, as well as to the node version. The latter pins the wasmtime version and the executor
+/// configuration, so artifacts prepared by another version are never loaded.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct ArtifactMetadata {
+	/// The identifier of the artifact.
+	pub id: ArtifactId,
+	/// The checksum of the artifact.
+	pub checksum: String,
+	/// The version of the node which prepared the artifact.
+	pub node_version: String,
+}
+
+impl ArtifactMetadata {
+	/// Creates the metadata of an artifact prepared by this node.
+	pub fn new(id: ArtifactId, checksum: String) -> Self {
+		Self { id, checksum, node_version: NODE_VERSION.into() }
+	}
+
+	/// Writes the metadata file of the artifact at `artifact_path`.
+	///
+	/// A partially written file fails to decode on startup, in which case the artifact is pruned.
+	pub fn write(&self, artifact_path: &Path) -> io::Result<()> {
+		fs::write(artifact_metadata_path(artifact_path), self.encode())
+	}
+
+	/// Reads the metadata file of the artifact at `artifact_path`.
+	fn read(artifact_path: &Path) -> Option<Self> {
+		let encoded = fs::read(artifact_metadata_path(artifact_path)).ok()?;
+		Self::decode(&mut &encoded[..]).ok()
+	}
+}
+
 /// Identifier of an artifact. Encodes a code hash of the PVF and a hash of preparation-related
 ///  executor parameter set.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode)]
 pub struct ArtifactId {
 	pub(crate) code_hash: ValidationCodeHash,
 	pub(crate) executor_params_prep_hash: ExecutorParamsPrepHash,
@@ -144,6 +199,10 @@ pub enum ArtifactState {
 		last_time_needed: SystemTime,
 		/// Size in bytes
 		size: u64,
+		/// The checksum the artifact must match before it is used for the first time.
+		///
+		/// Only set for artifacts loaded from the cache on startup, until they are verified.
+		unverified_checksum: Option<String>,
 	},
 	/// A task to prepare this artifact is scheduled.
 	Preparing {
@@ -212,27 +271,71 @@ impl Artifacts {
 		self.inner.keys().cloned().collect()
 	}
 
-	/// Create an empty table and the cache directory on-disk if it doesn't exist.
+	/// Create the table of the artifacts cached by this node version, and the cache directory
+	/// on-disk if it doesn't exist.
+	///
+	/// The checksums of the loaded artifacts are not verified here, see
+	/// [`ArtifactState::Prepared::unverified_checksum`].
 	pub async fn new(cache_path: &Path) -> Self {
 		// Make sure that the cache path directory and all its parents are created.
 		let _ = tokio::fs::create_dir_all(cache_path).await;
 
-		// Delete any leftover artifacts and worker dirs from previous runs. We don't delete the
-		// entire cache directory in case the user made a mistake and set it to e.g. their home
-		// directory. This is a best-effort to do clean-up, so ignore any errors.
+		let mut inner = HashMap::new();
+		let now = SystemTime::now();
+
+		// Load the artifacts prepared by this node version and delete any other leftover artifacts
+		// and worker dirs from previous runs. We don't delete the entire cache directory in case
+		// the user made a mistake and set it to e.g. their home directory. This is a best-effort to
+		// do clean-up, so ignore any errors.
 		for entry in fs::read_dir(cache_path).into_iter().flatten().flatten() {
 			let path = entry.path();
 			let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else { continue };
 			if path.is_dir() && file_name.starts_with(WORKER_DIR_PREFIX) {
 				let _ = fs::remove_dir_all(path);
-			} else if path.extension().map_or(false, |ext| ext == ARTIFACT_EXTENSION) ||
-				file_name.starts_with(ARTIFACT_OLD_PREFIX)
+			} else if path.extension().map_or(false, |ext| ext == ARTIFACT_EXTENSION) {
+				let size = entry.metadata().map(|metadata| metadata.len());
+				match (ArtifactMetadata::read(&path), size) {
+					(Some(metadata), Ok(size))
+						if metadata.node_version == NODE_VERSION &&
+							!inner.contains_key(&metadata.id) =>
+					{
+						gum::debug!(
+							target: LOG_TARGET,
+							artifact_id = ?metadata.id,
+							"loaded cached artifact {}",
+							path.display(),
+						);
+						inner.insert(
+							metadata.id,
+							ArtifactState::Prepared {
+								path,
+								last_time_needed: now,
+								size,
+								unverified_checksum: Some(metadata.checksum),
+							},
+						);
+					},
+					_ => {
+						let _ = fs::remove_file(artifact_metadata_path(&path));
+						let _ = fs::remove_file(path);
+					},
+				}
+			} else if file_name.starts_with(ARTIFACT_OLD_PREFIX) {
+				let _ = fs::remove_file(path);
+			}
+		}
+
+		// Delete the metadata files left without their artifacts.
+		for entry in fs::read_dir(cache_path).into_iter().flatten().flatten() {
+			let path = entry.path();
+			if path.extension().map_or(false, |ext| ext == ARTIFACT_METADATA_EXTENSION) &&
+				!path.with_extension(ARTIFACT_EXTENSION).exists()
 			{
 				let _ = fs::remove_file(path);
 			}
 		}
 
-		Self { inner: HashMap::new() }
+		Self { inner }
 	}
 
 	/// Returns the state of the given artifact by its ID.
@@ -271,7 +374,10 @@ impl Artifacts {
 		// See the precondition.
 		always!(self
 			.inner
-			.insert(artifact_id, ArtifactState::Prepared { path, last_time_needed, size })
+			.insert(
+				artifact_id,
+				ArtifactState::Prepared { path, last_time_needed, size, unverified_checksum: None }
+			)
 			.is_none());
 	}
 
@@ -326,6 +432,7 @@ impl Artifacts {
 #[cfg(test)]
 mod tests {
 	use crate::testing::artifact_id;
+	use assert_matches::assert_matches;
 
 	use super::*;
 
@@ -357,6 +464,54 @@ mod tests {
 		assert_eq!(artifacts.len(), 0);
 	}
 
+	#[tokio::test]
+	async fn cached_artifacts_loaded_on_startup() {
+		let tempdir = tempfile::tempdir().unwrap();
+		let cache_path = tempdir.path();
+
+		// Prepared by this node version, should be loaded.
+		let current = generate_artifact_path(cache_path);
+		fs::write(&current, "current").unwrap();
+		let checksum = artifact_checksum(b"current");
+		ArtifactMetadata::new(artifact_id(1), checksum.clone()).write(&current).unwrap();
+
+		// Prepared by another node version, should be cleared along with its metadata.
+		let outdated = generate_artifact_path(cache_path);
+		fs::write(&outdated, "outdated").unwrap();
+		let mut metadata = ArtifactMetadata::new(artifact_id(2), artifact_checksum(b"outdated"));
+		metadata.node_version = "0.0.0".into();
+		metadata.write(&outdated).unwrap();
+
+		// A second artifact with the same ID, should be cleared.
+		let duplicate = generate_artifact_path(cache_path);
+		fs::write(&duplicate, "current").unwrap();
+		ArtifactMetadata::new(artifact_id(1), checksum.clone())
+			.write(&duplicate)
+			.unwrap();
+
+		// Orphan metadata, should be cleared.
+		fs::write(cache_path.join("abcd.meta"), "test").unwrap();
+
+		let artifacts = Artifacts::new(cache_path).await;
+
+		let entries: Vec<PathBuf> =
+			fs::read_dir(&cache_path).unwrap().map(|entry| entry.unwrap().path()).collect();
+		assert_eq!(artifacts.len(), 1);
+		assert_eq!(entries.len(), 2);
+		assert!(!outdated.exists());
+		assert!(!artifact_metadata_path(&outdated).exists());
+		assert!(!cache_path.join("abcd.meta").exists());
+
+		// Only one of the artifacts sharing the ID is kept, depending on the directory order.
+		let path = if current.exists() { &current } else { &duplicate };
+		assert!(artifact_metadata_path(path).exists());
+		assert_matches!(
+			artifacts.inner.get(&artifact_id(1)),
+			Some(ArtifactState::Prepared { path: loaded, unverified_checksum: Some(c), .. })
+				if loaded == path && *c == checksum
+		);
+	}
+
 	#[tokio::test]
 	async fn test_pruned_by_cache_size() {
 		let mock_now = SystemTime::now();
@@ -450,11 +450,16 @@ async fn handle_job_finish(
 					job_response: JobResponse::Ok { result_descriptor },
 					duration,
 					pov_size,
+					max_rss,
 				},
 			idle_worker,
 		}) => {
 			// TODO: propagate the soft timeout
 
+			if let Some(max_rss) = max_rss {
+				queue.metrics.observe_execution_max_rss(max_rss);
+			}
+
 			(Some(idle_worker), Ok(result_descriptor), Some(duration), None, Some(pov_size))
 		},
 		Ok(WorkerInterfaceResponse {
@@ -21,7 +21,10 @@
 //! [`ValidationHost`], that allows communication with that event-loop.
 
 use crate::{
-	artifacts::{ArtifactId, ArtifactPathId, ArtifactState, Artifacts, ArtifactsCleanupConfig},
+	artifacts::{
+		artifact_checksum, artifact_metadata_path, ArtifactId, ArtifactMetadata, ArtifactPathId,
+		ArtifactState, Artifacts, ArtifactsCleanupConfig,
+	},
 	execute::{self, PendingExecutionRequest},
 	metrics::Metrics,
 	prepare, Priority, SecurityStatus, ValidationError, LOG_TARGET,
@@ -328,6 +331,7 @@ pub async fn start(
 			from_execute_queue_rx,
 			to_sweeper_tx,
 			awaiting_prepare: AwaitingPrepare::default(),
+			awaiting_verification: AwaitingVerification::default(),
 		})
 		.await
 	};
@@ -361,6 +365,50 @@ impl AwaitingPrepare {
 	}
 }
 
+/// The execution requests waiting for the checksum of a cached artifact to be verified, together
+/// with the PVF to prepare again if the artifact turns out to be corrupted.
+struct PendingVerification {
+	pvf: PvfPrepData,
+	priority: Priority,
+	requests: Vec<PendingExecutionRequest>,
+}
+
+/// A mapping from an artifact ID which is being verified to the execution requests waiting for it.
+#[derive(Default)]
+struct AwaitingVerification(HashMap<ArtifactId, PendingVerification>);
+
+impl AwaitingVerification {
+	/// Adds the request, returning true if it is the first one, i.e. the verification has to be
+	/// started.
+	fn add(
+		&mut self,
+		artifact_id: ArtifactId,
+		pvf: PvfPrepData,
+		priority: Priority,
+		pending_execution_request: PendingExecutionRequest,
+	) -> bool {
+		let mut first = false;
+		let pending = self.0.entry(artifact_id).or_insert_with(|| {
+			first = true;
+			PendingVerification { pvf, priority, requests: Vec::new() }
+		});
+		pending.requests.push(pending_execution_request);
+		first
+	}
+
+	fn take(&mut self, artifact_id: &ArtifactId) -> Option<PendingVerification> {
+		self.0.remove(artifact_id)
+	}
+}
+
+/// The outcome of the verification of a cached artifact against its checksum.
+#[derive(Debug)]
+struct ArtifactVerified {
+	artifact_id: ArtifactId,
+	path: PathBuf,
+	matches: bool,
+}
+
 struct Inner {
 	cleanup_pulse_interval: Duration,
 	cleanup_config: ArtifactsCleanupConfig,
@@ -377,6 +425,7 @@ struct Inner {
 	to_sweeper_tx: mpsc::Sender<PathBuf>,
 
 	awaiting_prepare: AwaitingPrepare,
+	awaiting_verification: AwaitingVerification,
 }
 
 #[derive(Debug)]
@@ -394,6 +443,7 @@ async fn run(
 		mut to_execute_queue_tx,
 		mut to_sweeper_tx,
 		mut awaiting_prepare,
+		mut awaiting_verification,
 	}: Inner,
 ) {
 	macro_rules! break_if_fatal {
@@ -419,6 +469,11 @@ async fn run(
 	let mut from_prepare_queue_rx = from_prepare_queue_rx.fuse();
 	let mut from_execute_queue_rx = from_execute_queue_rx.fuse();
 
+	// The checksums of the cached artifacts are verified off the event loop, the outcomes are fed
+	// back through this channel.
+	let (verified_tx, verified_rx) = mpsc::unbounded();
+	let mut verified_rx = verified_rx.fuse();
+
 	loop {
 		// biased to make it behave deterministically for tests.
 		futures::select_biased! {
@@ -461,10 +516,24 @@ async fn run(
 					&mut to_prepare_queue_tx,
 					&mut to_execute_queue_tx,
 					&mut awaiting_prepare,
+					&mut awaiting_verification,
+					&verified_tx,
 					to_host,
 				)
 				.await);
 			},
+			verified = verified_rx.select_next_some() => {
+				break_if_fatal!(handle_artifact_verified(
+					&mut artifacts,
+					&mut to_prepare_queue_tx,
+					&mut to_execute_queue_tx,
+					&mut to_sweeper_tx,
+					&mut awaiting_prepare,
+					&mut awaiting_verification,
+					verified,
+				)
+				.await);
+			},
 			from_prepare_queue = from_prepare_queue_rx.next() => {
 				let from_queue = break_if_fatal!(from_prepare_queue.ok_or(Fatal));
 
@@ -493,6 +562,8 @@ async fn handle_to_host(
 	prepare_queue: &mut mpsc::Sender<prepare::ToQueue>,
 	execute_queue: &mut mpsc::Sender<execute::ToQueue>,
 	awaiting_prepare: &mut AwaitingPrepare,
+	awaiting_verification: &mut AwaitingVerification,
+	verified_tx: &mpsc::UnboundedSender<ArtifactVerified>,
 	to_host: ToHost,
 ) -> Result<(), Fatal> {
 	match to_host {
@@ -500,8 +571,16 @@ async fn handle_to_host(
 			handle_precheck_pvf(artifacts, prepare_queue, pvf, result_tx).await?;
 		},
 		ToHost::ExecutePvf(inputs) => {
-			handle_execute_pvf(artifacts, prepare_queue, execute_queue, awaiting_prepare, inputs)
-				.await?;
+			handle_execute_pvf(
+				artifacts,
+				prepare_queue,
+				execute_queue,
+				awaiting_prepare,
+				awaiting_verification,
+				verified_tx,
+				inputs,
+			)
+			.await?;
 		},
 		ToHost::HeadsUp { active_pvfs } =>
 			handle_heads_up(artifacts, prepare_queue, active_pvfs).await?,
@@ -553,7 +632,8 @@ async fn handle_precheck_pvf(
 /// This will try to prepare the PVF, if a prepared artifact does not already exist. If there is
 /// already a preparation job, we coalesce the two preparation jobs.
 ///
-/// If the prepare job succeeded previously, we will enqueue an execute job right away.
+/// If the prepare job succeeded previously, we will enqueue an execute job right away. Artifacts
+/// loaded from the cache on startup are first verified against their checksum, off the event loop.
 ///
 /// If the prepare job failed previously, we may retry it under certain conditions.
 ///
@@ -565,6 +645,8 @@ async fn handle_execute_pvf(
 	prepare_queue: &mut mpsc::Sender<prepare::ToQueue>,
 	execute_queue: &mut mpsc::Sender<execute::ToQueue>,
 	awaiting_prepare: &mut AwaitingPrepare,
+	awaiting_verification: &mut AwaitingVerification,
+	verified_tx: &mpsc::UnboundedSender<ArtifactVerified>,
 	inputs: ExecutePvfInputs,
 ) -> Result<(), Fatal> {
 	let ExecutePvfInputs { pvf, exec_timeout, pvd, pov, priority, exec_kind, result_tx } = inputs;
@@ -573,10 +655,38 @@ async fn handle_execute_pvf(
 
 	if let Some(state) = artifacts.artifact_state_mut(&artifact_id) {
 		match state {
-			ArtifactState::Prepared { ref path, last_time_needed, .. } => {
+			ArtifactState::Prepared { ref path, last_time_needed, unverified_checksum, .. } => {
 				let file_metadata = std::fs::metadata(path);
 
-				if file_metadata.is_ok() {
+				if let (Some(checksum), Ok(_)) = (unverified_checksum, &file_metadata) {
+					*last_time_needed = SystemTime::now();
+
+					// Artifacts loaded from the cache on startup are checked against the checksum
+					// recorded at preparation time the first time they are needed. Reading and
+					// hashing the whole artifact may take a while, so the execution waits for the
+					// outcome of the verification.
+					let first = awaiting_verification.add(
+						artifact_id.clone(),
+						pvf,
+						priority,
+						PendingExecutionRequest {
+							exec_timeout,
+							pvd,
+							pov,
+							executor_params,
+							exec_kind,
+							result_tx,
+						},
+					);
+					if first {
+						spawn_artifact_verification(
+							verified_tx.clone(),
+							artifact_id,
+							path.clone(),
+							checksum.clone(),
+						);
+					}
+				} else if file_metadata.is_ok() {
 					*last_time_needed = SystemTime::now();
 
 					// This artifact has already been prepared, send it to the execute queue.
@@ -853,8 +963,22 @@ async fn handle_prepare_done(
 	}
 
 	*state = match result {
-		Ok(PrepareSuccess { path, size, .. }) =>
-			ArtifactState::Prepared { path, last_time_needed: SystemTime::now(), size },
+		Ok(PrepareSuccess { path, size, checksum, .. }) => {
+			if let Err(err) = ArtifactMetadata::new(artifact_id.clone(), checksum).write(&path) {
+				gum::warn!(
+					target: LOG_TARGET,
+					?artifact_id,
+					"failed to write the artifact metadata, the artifact won't be reused after a restart: {}",
+					err,
+				);
+			}
+			ArtifactState::Prepared {
+				path,
+				last_time_needed: SystemTime::now(),
+				size,
+				unverified_checksum: None,
+			}
+		},
 		Err(error) => {
 			let last_time_failed = SystemTime::now();
 			let num_failures = *num_failures + 1;
@@ -874,6 +998,102 @@ async fn handle_prepare_done(
 	Ok(())
 }
 
+/// Verifies the cached artifact at `path` against its checksum on a blocking thread, and reports
+/// the outcome to the host.
+fn spawn_artifact_verification(
+	verified_tx: mpsc::UnboundedSender<ArtifactVerified>,
+	artifact_id: ArtifactId,
+	path: PathBuf,
+	checksum: String,
+) {
+	tokio::task::spawn_blocking(move || {
+		let matches =
+			std::fs::read(&path).map_or(false, |artifact| artifact_checksum(&artifact) == checksum);
+		let _ = verified_tx.unbounded_send(ArtifactVerified { artifact_id, path, matches });
+	});
+}
+
+/// Handles the outcome of the verification of a cached artifact.
+///
+/// The execution requests waiting for the artifact are enqueued if it matches its checksum.
+/// Otherwise the artifact is removed and prepared again, and the requests wait for the
+/// preparation.
+async fn handle_artifact_verified(
+	artifacts: &mut Artifacts,
+	prepare_queue: &mut mpsc::Sender<prepare::ToQueue>,
+	execute_queue: &mut mpsc::Sender<execute::ToQueue>,
+	sweeper_tx: &mut mpsc::Sender<PathBuf>,
+	awaiting_prepare: &mut AwaitingPrepare,
+	awaiting_verification: &mut AwaitingVerification,
+	verified: ArtifactVerified,
+) -> Result<(), Fatal> {
+	let ArtifactVerified { artifact_id, path, matches } = verified;
+	let Some(PendingVerification { pvf, priority, requests }) =
+		awaiting_verification.take(&artifact_id)
+	else {
+		never!("an artifact was verified without pending requests: {:?}", artifact_id);
+		return Ok(())
+	};
+
+	match artifacts.artifact_state_mut(&artifact_id) {
+		Some(ArtifactState::Prepared { path: current_path, unverified_checksum, .. })
+			if *current_path == path =>
+		{
+			if matches {
+				*unverified_checksum = None;
+				for pending_execution_request in requests {
+					if pending_execution_request.result_tx.is_canceled() {
+						continue
+					}
+					send_execute(
+						execute_queue,
+						execute::ToQueue::Enqueue {
+							artifact: ArtifactPathId::new(artifact_id.clone(), &path),
+							pending_execution_request,
+						},
+					)
+					.await?;
+				}
+				return Ok(())
+			}
+
+			gum::warn!(
+				target: LOG_TARGET,
+				?pvf,
+				?artifact_id,
+				"handle_artifact_verified: Cached artifact doesn't match its checksum, re-queuing PVF preparation."
+			);
+			// The sweeper removes the metadata file along with the artifact.
+			artifacts.remove(artifact_id.clone());
+			sweeper_tx.send(path).await.map_err(|_| Fatal)?;
+		},
+		Some(ArtifactState::Preparing { .. }) => {
+			for pending_execution_request in requests {
+				awaiting_prepare.add(artifact_id.clone(), pending_execution_request);
+			}
+			return Ok(())
+		},
+		Some(ArtifactState::FailedToProcess { error, .. }) => {
+			for PendingExecutionRequest { result_tx, .. } in requests {
+				let _ = result_tx.send(Err(ValidationError::from(error.clone())));
+			}
+			return Ok(())
+		},
+		// The artifact was pruned in the meantime.
+		_ => {
+			artifacts.remove(artifact_id.clone());
+		},
+	}
+
+	artifacts.insert_preparing(artifact_id.clone(), Vec::new());
+	send_prepare(prepare_queue, prepare::ToQueue::Enqueue { priority, pvf }).await?;
+	for pending_execution_request in requests {
+		awaiting_prepare.add(artifact_id.clone(), pending_execution_request);
+	}
+
+	Ok(())
+}
+
 async fn handle_update_active_leaves(
 	execute_queue: &mut mpsc::Sender<execute::ToQueue>,
 	update: ActiveLeavesUpdate,
@@ -981,6 +1201,7 @@ async fn sweeper_task(mut sweeper_rx: mpsc::Receiver<PathBuf>) {
 					"Swept the artifact file {}",
 					condemned.display(),
 				);
+				let _ = tokio::fs::remove_file(artifact_metadata_path(&condemned)).await;
 			},
 		}
 	}
@@ -1094,6 +1315,7 @@ pub(crate) mod tests {
 				from_execute_queue_rx,
 				to_sweeper_tx,
 				awaiting_prepare: AwaitingPrepare::default(),
+				awaiting_verification: AwaitingVerification::default(),
 			})
 			.boxed();
 
@@ -1376,6 +1598,96 @@ pub(crate) mod tests {
 		);
 	}
 
+	#[tokio::test]
+	async fn cached_artifacts_are_verified_before_execution() {
+		let tempdir = tempfile::tempdir().unwrap();
+		let cache_path = tempdir.path();
+
+		// A cached artifact which matches its checksum, and a corrupted one.
+		let intact = generate_artifact_path(cache_path);
+		std::fs::write(&intact, "intact").unwrap();
+		ArtifactMetadata::new(artifact_id(1), artifact_checksum(b"intact"))
+			.write(&intact)
+			.unwrap();
+		let corrupted = generate_artifact_path(cache_path);
+		std::fs::write(&corrupted, "corrupted").unwrap();
+		ArtifactMetadata::new(artifact_id(2), artifact_checksum(b"original"))
+			.write(&corrupted)
+			.unwrap();
+
+		let mut builder = Builder::default();
+		builder.artifacts = Artifacts::new(cache_path).await;
+		let mut test = builder.build();
+		let mut host = test.host_handle();
+		let pvd = Arc::new(PersistedValidationData {
+			parent_head: Default::default(),
+			relay_parent_number: 1u32,
+			relay_parent_storage_root: H256::default(),
+			max_pov_size: 4096 * 1024,
+		});
+		let pov = Arc::new(PoV { block_data: BlockData(b"pov".to_vec()) });
+
+		// The intact artifact is executed without being prepared again.
+		let (result_tx, _result_rx) = oneshot::channel();
+		host.execute_pvf(
+			PvfPrepData::from_discriminator(1),
+			TEST_EXECUTION_TIMEOUT,
+			pvd.clone(),
+			pov.clone(),
+			Priority::Normal,
+			PvfExecKind::Backing(H256::default()),
+			result_tx,
+		)
+		.await
+		.unwrap();
+		assert_matches!(
+			test.poll_and_recv_to_execute_queue().await,
+			execute::ToQueue::Enqueue { artifact, .. } => assert_eq!(artifact.path, intact)
+		);
+		test.poll_ensure_to_prepare_queue_is_empty().await;
+
+		// The corrupted artifact is removed and prepared again.
+		let (result_tx, _result_rx) = oneshot::channel();
+		host.execute_pvf(
+			PvfPrepData::from_discriminator(2),
+			TEST_EXECUTION_TIMEOUT,
+			pvd,
+			pov,
+			Priority::Normal,
+			PvfExecKind::Backing(H256::default()),
+			result_tx,
+		)
+		.await
+		.unwrap();
+		let to_sweeper_rx = &mut test.to_sweeper_rx;
+		run_until(
+			&mut test.run,
+			async {
+				assert_eq!(to_sweeper_rx.next().await.unwrap(), corrupted);
+			}
+			.boxed(),
+		)
+		.await;
+		assert_matches!(
+			test.poll_and_recv_to_prepare_queue().await,
+			prepare::ToQueue::Enqueue { pvf, .. } => assert_eq!(ArtifactId::from_pvf_prep_data(&pvf), artifact_id(2))
+		);
+		test.poll_ensure_to_execute_queue_is_empty().await;
+
+		// The execution waits for the preparation.
+		test.from_prepare_queue_tx
+			.send(prepare::FromQueue {
+				artifact_id: artifact_id(2),
+				result: Ok(PrepareSuccess::default()),
+			})
+			.await
+			.unwrap();
+		assert_matches!(
+			test.poll_and_recv_to_execute_queue().await,
+			execute::ToQueue::Enqueue { .. }
+		);
+	}
+
 	#[tokio::test]
 	async fn precheck_pvf() {
 		let mut test = Builder::default().build();
@@ -107,6 +107,15 @@ impl Metrics {
 		}
 	}
 
+	/// Observe the maximum resident set size of an execution.
+	#[allow(unused_variables)]
+	pub(crate) fn observe_execution_max_rss(&self, max_rss: i64) {
+		#[cfg(target_os = "linux")]
+		if let Some(metrics) = &self.0 {
+			metrics.execution_max_rss.observe(max_rss as f64);
+		}
+	}
+
 	pub(crate) fn observe_code_size(&self, code_size: usize) {
 		if let Some(metrics) = &self.0 {
 			metrics.code_size.observe(code_size as f64);
@@ -143,6 +152,8 @@ struct MetricsInner {
 	execution_time: prometheus::Histogram,
 	execution_queued_time: prometheus::Histogram,
 	#[cfg(target_os = "linux")]
+	execution_max_rss: prometheus::Histogram,
+	#[cfg(target_os = "linux")]
 	preparation_max_rss: prometheus::Histogram,
 	// Max. allocated memory, tracked by Jemallocator, polling-based
 	#[cfg(any(target_os = "linux", feature = "jemalloc-allocator"))]
@@ -299,6 +310,19 @@ impl metrics::Metrics for Metrics {
 				registry,
 			)?,
 			#[cfg(target_os = "linux")]
+			execution_max_rss: prometheus::register(
+				prometheus::Histogram::with_opts(
+					prometheus::HistogramOpts::new(
+						"polkadot_pvf_execution_max_rss",
+						"ru_maxrss (maximum resident set size) observed for execution (in kilobytes)",
+					).buckets(
+						prometheus::exponential_buckets(8192.0, 2.0, 10)
+							.expect("arguments are always valid; qed"),
+					),
+				)?,
+				registry,
+			)?,
+			#[cfg(target_os = "linux")]
 			preparation_max_rss: prometheus::register(
 				prometheus::Histogram::with_opts(
 					prometheus::HistogramOpts::new(
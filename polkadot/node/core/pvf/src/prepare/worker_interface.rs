@@ -206,10 +206,8 @@ async fn handle_response(
 	cache_path: &Path,
 	preparation_timeout: Duration,
 ) -> Outcome {
-	// TODO: Add `checksum` to `ArtifactPathId`. See:
-	//       https://github.com/paritytech/polkadot-sdk/issues/2399
 	let PrepareWorkerSuccess {
-		checksum: _,
+		checksum,
 		stats: PrepareStats { cpu_time_elapsed, memory_stats, observed_wasm_code_len },
 	} = match result.clone() {
 		Ok(result) => result,
@@ -268,6 +266,7 @@ async fn handle_response(
 			result: Ok(PrepareSuccess {
 				path: artifact_path,
 				size,
+				checksum,
 				stats: PrepareStats {
 					cpu_time_elapsed,
 					memory_stats: memory_stats.clone(),
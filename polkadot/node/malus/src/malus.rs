@@ -42,6 +42,10 @@ enum NemesisVariant {
 	DisputeFinalizedCandidates(DisputeFinalizedCandidatesOptions),
 	/// Spam many request statements instead of sending a single one.
 	SpamStatementRequests(SpamStatementRequestsOptions),
+	/// Sign availability bitfields but withhold erasure chunks from other validators.
+	WithholdChunks(WithholdChunksOptions),
+	/// Spam approval assignments claiming candidates the validator was not assigned to.
+	SpamApprovalAssignments(SpamApprovalAssignmentsOptions),
 }
 
 #[derive(Debug, Parser)]
@@ -105,6 +109,20 @@ impl MalusCli {
 
 				polkadot_cli::run_node(cli, SpamStatementRequests { spam_factor }, finality_delay)?
 			},
+			NemesisVariant::WithholdChunks(opts) => {
+				let WithholdChunksOptions { percentage, cli } = opts;
+
+				polkadot_cli::run_node(cli, WithholdChunks { percentage }, finality_delay)?
+			},
+			NemesisVariant::SpamApprovalAssignments(opts) => {
+				let SpamApprovalAssignmentsOptions { spam_factor, cli } = opts;
+
+				polkadot_cli::run_node(
+					cli,
+					SpamApprovalAssignments { spam_factor },
+					finality_delay,
+				)?
+			},
 		}
 		Ok(())
 	}
@@ -244,4 +262,42 @@ mod tests {
 			assert!(opts.cli.run.base.bob);
 		});
 	}
+
+	#[test]
+	fn percentage_works_withhold_chunks() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"withhold-chunks",
+			"--percentage",
+			"50",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::WithholdChunks(opts),
+			..
+		} => {
+			assert_eq!(opts.percentage, 50);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
+
+	#[test]
+	fn spam_factor_works_spam_approval_assignments() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"spam-approval-assignments",
+			"--spam-factor",
+			"20",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::SpamApprovalAssignments(opts),
+			..
+		} => {
+			assert_eq!(opts.spam_factor, 20);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
 }
@@ -20,16 +20,20 @@ mod back_garbage_candidate;
 mod common;
 mod dispute_finalized_candidates;
 mod dispute_valid_candidates;
+mod spam_approval_assignments;
 mod spam_statement_requests;
 mod suggest_garbage_candidate;
 mod support_disabled;
+mod withhold_chunks;
 
 pub(crate) use self::{
 	back_garbage_candidate::{BackGarbageCandidateOptions, BackGarbageCandidates},
 	dispute_finalized_candidates::{DisputeFinalizedCandidates, DisputeFinalizedCandidatesOptions},
 	dispute_valid_candidates::{DisputeAncestorOptions, DisputeValidCandidates},
+	spam_approval_assignments::{SpamApprovalAssignments, SpamApprovalAssignmentsOptions},
 	spam_statement_requests::{SpamStatementRequests, SpamStatementRequestsOptions},
 	suggest_garbage_candidate::{SuggestGarbageCandidateOptions, SuggestGarbageCandidates},
 	support_disabled::{SupportDisabled, SupportDisabledOptions},
+	withhold_chunks::{WithholdChunks, WithholdChunksOptions},
 };
 pub(crate) use common::*;
@@ -0,0 +1,179 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node variant that spams invalid approval assignments.
+//!
+//! This malus variant behaves honestly in everything except when propagating approval assignments
+//! through the network bridge subsystem. Next to every genuine assignment it also sends copies of
+//! the certificate claiming candidates the validator was not assigned to.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	service::{
+		AuxStore, Error, ExtendedOverseerGenArgs, Overseer, OverseerConnector, OverseerGen,
+		OverseerGenArgs, OverseerHandle,
+	},
+	validator_overseer_builder, Cli,
+};
+use polkadot_node_network_protocol::{v3, PeerId, Versioned, VersionedValidationProtocol};
+use polkadot_node_primitives::approval::v2::{
+	AsBitIndex, CandidateBitfield, IndirectAssignmentCertV2,
+};
+use polkadot_node_subsystem::SpawnGlue;
+use polkadot_node_subsystem_types::{ChainApiBackend, RuntimeApiSubsystemClient};
+use polkadot_primitives::CandidateIndex;
+use sp_core::traits::SpawnNamed;
+
+// Filter wrapping related types.
+use crate::{interceptor::*, shared::MALUS};
+
+use std::sync::Arc;
+
+/// Wraps around network bridge and replaces it.
+#[derive(Clone)]
+struct AssignmentSpammer {
+	spam_factor: u32, // How many candidates to claim next to the assigned ones.
+}
+
+impl AssignmentSpammer {
+	/// Returns one message per genuine assignment, each claiming the first `spam_factor`
+	/// candidates of the block which weren't assigned to us.
+	fn spam_assignments(
+		&self,
+		peers: &[PeerId],
+		assignments: &[(IndirectAssignmentCertV2, CandidateBitfield)],
+	) -> Vec<(Vec<PeerId>, VersionedValidationProtocol)> {
+		assignments
+			.iter()
+			.map(|(cert, claimed)| {
+				let spam = (0..self.spam_factor as CandidateIndex)
+					.filter(|candidate_index| !claimed.bit_at(candidate_index.as_bit_index()))
+					.map(|candidate_index| (cert.clone(), CandidateBitfield::from(candidate_index)))
+					.collect::<Vec<_>>();
+
+				gum::info!(
+					target: MALUS,
+					block_hash = ?cert.block_hash,
+					"😈 Claiming {} unassigned candidates with an assignment certificate.",
+					spam.len(),
+				);
+
+				(
+					peers.to_vec(),
+					Versioned::V3(v3::ValidationProtocol::ApprovalDistribution(
+						v3::ApprovalDistributionMessage::Assignments(spam),
+					)),
+				)
+			})
+			.collect()
+	}
+}
+
+impl<Sender> MessageInterceptor<Sender> for AssignmentSpammer
+where
+	Sender: overseer::NetworkBridgeTxSenderTrait + Clone + Send + 'static,
+{
+	type Message = NetworkBridgeTxMessage;
+
+	/// Intercept NetworkBridgeTxMessage::SendValidationMessage with approval assignments inside
+	/// and append assignments for candidates we were not assigned to.
+	fn intercept_incoming(
+		&self,
+		_subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg:
+					NetworkBridgeTxMessage::SendValidationMessage(
+						peers,
+						Versioned::V3(v3::ValidationProtocol::ApprovalDistribution(
+							v3::ApprovalDistributionMessage::Assignments(assignments),
+						)),
+					),
+			} => {
+				let spam = self.spam_assignments(&peers, &assignments);
+				let original = (
+					peers,
+					Versioned::V3(v3::ValidationProtocol::ApprovalDistribution(
+						v3::ApprovalDistributionMessage::Assignments(assignments),
+					)),
+				);
+
+				// Passthrough the genuine assignments followed by the spam
+				Some(FromOrchestra::Communication {
+					msg: NetworkBridgeTxMessage::SendValidationMessages(
+						std::iter::once(original).chain(spam).collect(),
+					),
+				})
+			},
+			FromOrchestra::Communication { msg } => Some(FromOrchestra::Communication { msg }),
+			FromOrchestra::Signal(signal) => Some(FromOrchestra::Signal(signal)),
+		}
+	}
+}
+
+//----------------------------------------------------------------------------------
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct SpamApprovalAssignmentsOptions {
+	/// How many candidates of a block to claim with each genuine assignment.
+	#[clap(long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..=500))]
+	pub spam_factor: u32,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// SpamApprovalAssignments implementation wrapper which implements `OverseerGen` glue.
+pub(crate) struct SpamApprovalAssignments {
+	/// How many candidates of a block to claim with each genuine assignment.
+	pub spam_factor: u32,
+}
+
+impl OverseerGen for SpamApprovalAssignments {
+	fn generate<Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'_, Spawner, RuntimeClient>,
+		ext_args: Option<ExtendedOverseerGenArgs>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		gum::info!(
+			target: MALUS,
+			"😈 Started Malus node that claims spam_factor = {:?} candidates with each assignment.",
+			&self.spam_factor,
+		);
+
+		let assignment_spammer = AssignmentSpammer { spam_factor: self.spam_factor };
+
+		validator_overseer_builder(
+			args,
+			ext_args.expect("Extended arguments required to build validator overseer are provided"),
+		)?
+		.replace_network_bridge_tx(move |cb| InterceptedSubsystem::new(cb, assignment_spammer))
+		.build_with_connector(connector)
+		.map_err(|e| e.into())
+	}
+}
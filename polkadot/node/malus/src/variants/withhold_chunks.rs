@@ -0,0 +1,136 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node variant that withholds erasure chunks.
+//!
+//! This malus variant behaves honestly in everything except when serving erasure chunks. The
+//! availability store still reports chunks as available, so bitfields are signed as usual, but
+//! chunk queries coming from `availability-distribution` are answered as if the chunk was
+//! missing.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	service::{
+		AuxStore, Error, ExtendedOverseerGenArgs, Overseer, OverseerConnector, OverseerGen,
+		OverseerGenArgs, OverseerHandle,
+	},
+	validator_overseer_builder, Cli,
+};
+use polkadot_node_subsystem::SpawnGlue;
+use polkadot_node_subsystem_types::{ChainApiBackend, RuntimeApiSubsystemClient};
+use rand::distributions::{Bernoulli, Distribution};
+use sp_core::traits::SpawnNamed;
+
+// Filter wrapping related types.
+use crate::{interceptor::*, shared::MALUS};
+
+use std::sync::Arc;
+
+/// Wraps around the availability store and hides chunks from it.
+#[derive(Clone)]
+struct ChunkWithholder {
+	/// Probability of withholding a requested chunk.
+	distribution: Bernoulli,
+}
+
+impl<Sender> MessageInterceptor<Sender> for ChunkWithholder
+where
+	Sender: overseer::AvailabilityStoreSenderTrait + Clone + Send + 'static,
+{
+	type Message = AvailabilityStoreMessage;
+
+	/// Intercept `AvailabilityStoreMessage::QueryChunk`, which is only sent when serving chunk
+	/// requests, and pretend we don't have the chunk.
+	fn intercept_incoming(
+		&self,
+		_subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryChunk(candidate_hash, validator_index, tx),
+			} if self.distribution.sample(&mut rand::thread_rng()) => {
+				gum::info!(
+					target: MALUS,
+					?candidate_hash,
+					?validator_index,
+					"😈 Withholding erasure chunk.",
+				);
+				let _ = tx.send(None);
+				None
+			},
+			FromOrchestra::Communication { msg } => Some(FromOrchestra::Communication { msg }),
+			FromOrchestra::Signal(signal) => Some(FromOrchestra::Signal(signal)),
+		}
+	}
+}
+
+//----------------------------------------------------------------------------------
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct WithholdChunksOptions {
+	/// Determines the percentage of requested chunks that are withheld.
+	/// Defaults to 100% of chunks being withheld.
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// WithholdChunks implementation wrapper which implements `OverseerGen` glue.
+pub(crate) struct WithholdChunks {
+	/// The probability of withholding a chunk.
+	pub percentage: u8,
+}
+
+impl OverseerGen for WithholdChunks {
+	fn generate<Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'_, Spawner, RuntimeClient>,
+		ext_args: Option<ExtendedOverseerGenArgs>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		gum::info!(
+			target: MALUS,
+			"😈 Started Malus node that withholds {}% of the requested erasure chunks.",
+			&self.percentage,
+		);
+
+		let distribution = Bernoulli::new(f64::from(self.percentage) / 100.0)
+			.expect("Invalid probability! Percentage must be in range [0..=100].");
+		let chunk_withholder = ChunkWithholder { distribution };
+
+		validator_overseer_builder(
+			args,
+			ext_args.expect("Extended arguments required to build validator overseer are provided"),
+		)?
+		.replace_availability_store(move |av_store| {
+			InterceptedSubsystem::new(av_store, chunk_withholder)
+		})
+		.build_with_connector(connector)
+		.map_err(|e| e.into())
+	}
+}
@@ -100,6 +100,10 @@ pub use self::metrics::Metrics as OverseerMetrics;
 pub mod dummy;
 pub use self::dummy::DummySubsystem;
 
+/// Opt-in recording of the deliveries made to subsystems.
+pub mod recorder;
+pub use self::recorder::{MessageRecorder, RecordingSubsystem};
+
 pub use polkadot_node_metrics::{
 	metrics::{prometheus, Metrics as MetricsTrait},
 	Metronome,
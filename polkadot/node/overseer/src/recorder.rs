@@ -0,0 +1,1123 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Opt-in recording of the signals and messages delivered to subsystems.
+//!
+//! Subsystems wrapped in a [`RecordingSubsystem`] append a [`TraceEntry`] to the trace file of a
+//! [`MessageRecorder`] for every [`FromOrchestra`] delivery they receive. Signals are always
+//! recorded in a replayable form. Messages are recorded by their debug representation, and
+//! SCALE-encoded as well if their type supports replaying them, see [`RecordableMessage`].
+//!
+//! The `RuntimeApi` and `ChainApi` subsystems also record the responses to the requests they
+//! receive, so that the requests of a replayed subsystem can be answered the way they were when
+//! the trace was recorded, see [`RecordedResponses`].
+//!
+//! Entries are written to the trace file by a dedicated thread, so that recording never blocks the
+//! subsystems on file I/O.
+//!
+//! Traces are read back with [`read_trace`], e.g. to feed them into a single subsystem under test.
+
+use std::{
+	collections::HashMap,
+	fs::File,
+	io::{self, BufWriter, Write},
+	path::Path,
+	pin::Pin,
+	sync::mpsc::{self as std_mpsc, Receiver, Sender},
+	thread,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use codec::{Decode, Encode};
+use futures::{channel::oneshot, future::BoxFuture, Future, FutureExt};
+use orchestra::{FromOrchestra, SpawnedSubsystem, Subsystem, SubsystemContext};
+use polkadot_node_network_protocol::{self as net_protocol, PeerId, Versioned, View};
+use polkadot_node_primitives::{
+	approval::v2::{CandidateBitfield, IndirectAssignmentCertV2, IndirectSignedApprovalVoteV2},
+	PoV, SignedFullStatementWithPVD, StatementWithPVD,
+};
+use polkadot_node_subsystem_types::{
+	errors::{ChainApiError, RuntimeApiError, SubsystemError, SubsystemResult},
+	messages::*,
+	ActivatedLeaf, ActiveLeavesUpdate, OverseerSignal, UnpinHandle,
+};
+use polkadot_primitives::{
+	vstaging::{
+		CandidateReceiptV2 as CandidateReceipt,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt,
+	},
+	BlockNumber, CandidateHash, Hash, Id as ParaId, PersistedValidationData, SessionIndex,
+	SigningContext, UncheckedSigned, ValidatorId, ValidatorIndex, ValidatorSignature,
+};
+
+// Generated dummy messages
+use crate::{
+	messages::{BitfieldSigningMessage, PvfCheckerMessage},
+	AllMessages,
+};
+
+const LOG_TARGET: &str = "parachain::overseer-recorder";
+
+/// A signal recorded in a trace.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum RecordedSignal {
+	/// An [`OverseerSignal::ActiveLeaves`] signal.
+	ActiveLeaves {
+		/// The hash and number of the activated leaf, if any.
+		activated: Option<(Hash, BlockNumber)>,
+		/// The hashes of the deactivated leaves.
+		deactivated: Vec<Hash>,
+	},
+	/// An [`OverseerSignal::BlockFinalized`] signal.
+	BlockFinalized(Hash, BlockNumber),
+	/// An [`OverseerSignal::Conclude`] signal.
+	Conclude,
+}
+
+impl From<&OverseerSignal> for RecordedSignal {
+	fn from(signal: &OverseerSignal) -> Self {
+		match signal {
+			OverseerSignal::ActiveLeaves(update) => RecordedSignal::ActiveLeaves {
+				activated: update.activated.as_ref().map(|leaf| (leaf.hash, leaf.number)),
+				deactivated: update.deactivated.to_vec(),
+			},
+			OverseerSignal::BlockFinalized(hash, number) =>
+				RecordedSignal::BlockFinalized(*hash, *number),
+			OverseerSignal::Conclude => RecordedSignal::Conclude,
+		}
+	}
+}
+
+impl RecordedSignal {
+	/// Turn the recorded signal back into an [`OverseerSignal`].
+	///
+	/// The unpin handle of the activated leaf, if any, is created by `unpin_handle`.
+	pub fn into_signal(self, unpin_handle: impl FnOnce(Hash) -> UnpinHandle) -> OverseerSignal {
+		match self {
+			RecordedSignal::ActiveLeaves { activated, deactivated } =>
+				OverseerSignal::ActiveLeaves(ActiveLeavesUpdate {
+					activated: activated.map(|(hash, number)| ActivatedLeaf {
+						hash,
+						number,
+						unpin_handle: unpin_handle(hash),
+					}),
+					deactivated: deactivated.into(),
+				}),
+			RecordedSignal::BlockFinalized(hash, number) =>
+				OverseerSignal::BlockFinalized(hash, number),
+			RecordedSignal::Conclude => OverseerSignal::Conclude,
+		}
+	}
+}
+
+/// A delivery recorded in a trace.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum RecordedDelivery {
+	/// A signal.
+	Signal(RecordedSignal),
+	/// A message.
+	Message {
+		/// The debug representation of the message.
+		debug: String,
+		/// The message encoded by [`RecordableMessage::encode_for_replay`], if it can be
+		/// replayed.
+		encoded: Option<Vec<u8>>,
+	},
+	/// The response to a request received by the subsystem.
+	Response {
+		/// The request, encoded as the key it is looked up by in [`RecordedResponses`].
+		request: Vec<u8>,
+		/// The encoded response.
+		response: Vec<u8>,
+	},
+}
+
+/// A single entry of a trace.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct TraceEntry {
+	/// The time of the delivery, in microseconds since the UNIX epoch.
+	pub timestamp: u64,
+	/// The subsystem the delivery was made to, as returned by [`subsystem_name`].
+	pub subsystem: String,
+	/// The recorded delivery.
+	pub delivery: RecordedDelivery,
+}
+
+/// The name a subsystem is identified by in traces: the name of the message type it receives.
+pub fn subsystem_name<M>() -> &'static str {
+	let name = std::any::type_name::<M>();
+	name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Read all the entries of a trace file, in the order they were recorded.
+pub fn read_trace(path: impl AsRef<Path>) -> io::Result<Vec<TraceEntry>> {
+	let encoded = std::fs::read(path)?;
+	let mut input = &encoded[..];
+	let mut entries = Vec::new();
+	while !input.is_empty() {
+		let entry = TraceEntry::decode(&mut input)
+			.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+		entries.push(entry);
+	}
+
+	Ok(entries)
+}
+
+/// A command sent to the thread writing the trace file.
+enum WriterCommand {
+	/// Append an entry to the trace.
+	Entry(TraceEntry),
+	/// Flush the trace file, then signal the sender.
+	Flush(std_mpsc::SyncSender<()>),
+}
+
+/// Writes the deliveries made to subsystems to a trace file.
+///
+/// The entries are queued and written by a background thread, which flushes the file whenever the
+/// queue runs empty. Cheap to clone, all the clones write to the same file. The thread exits once
+/// all the clones are dropped.
+#[derive(Clone)]
+pub struct MessageRecorder {
+	commands: Sender<WriterCommand>,
+}
+
+impl MessageRecorder {
+	/// Create a recorder writing to a new trace file at `path`, truncating any existing file.
+	pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+		let writer = BufWriter::new(File::create(path)?);
+		let (commands, rx) = std_mpsc::channel();
+		thread::Builder::new()
+			.name("overseer-recorder".into())
+			.spawn(move || write_trace(writer, rx))?;
+		Ok(Self { commands })
+	}
+
+	/// Append a delivery made to `subsystem` to the trace.
+	///
+	/// Never blocks, the entry is written by the background thread.
+	pub fn record(&self, subsystem: &str, delivery: RecordedDelivery) {
+		let timestamp = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|elapsed| elapsed.as_micros() as u64)
+			.unwrap_or_default();
+		let entry = TraceEntry { timestamp, subsystem: subsystem.to_owned(), delivery };
+
+		// The writer thread only stops once all the recorders are dropped.
+		let _ = self.commands.send(WriterCommand::Entry(entry));
+	}
+
+	/// Block until all the deliveries recorded so far are written to the trace file.
+	pub fn flush(&self) {
+		let (tx, rx) = std_mpsc::sync_channel(1);
+		if self.commands.send(WriterCommand::Flush(tx)).is_ok() {
+			let _ = rx.recv();
+		}
+	}
+
+	fn record_delivery<M: RecordableMessage + std::fmt::Debug>(
+		&self,
+		delivery: &FromOrchestra<M, OverseerSignal>,
+	) {
+		let delivery = match delivery {
+			FromOrchestra::Signal(signal) => RecordedDelivery::Signal(signal.into()),
+			FromOrchestra::Communication { msg } => RecordedDelivery::Message {
+				debug: format!("{:?}", msg),
+				encoded: msg.encode_for_replay(),
+			},
+		};
+		self.record(subsystem_name::<M>(), delivery);
+	}
+}
+
+/// Write the entries received on `commands` to the trace file, until all the senders are dropped.
+fn write_trace(mut writer: BufWriter<File>, commands: Receiver<WriterCommand>) {
+	let mut failed = false;
+	let mut check = |result: io::Result<()>| {
+		// Only report the first failure, the following ones are most likely the same.
+		if let Err(err) = result {
+			if !std::mem::replace(&mut failed, true) {
+				gum::warn!(target: LOG_TARGET, ?err, "Failed to write to the trace file");
+			}
+		}
+	};
+
+	while let Ok(command) = commands.recv() {
+		let mut flushed = Vec::new();
+		for command in std::iter::once(command).chain(commands.try_iter()) {
+			match command {
+				WriterCommand::Entry(entry) => check(writer.write_all(&entry.encode())),
+				WriterCommand::Flush(done) => flushed.push(done),
+			}
+		}
+
+		// Flush whenever the queue runs empty, so that the trace is complete if the node crashes.
+		check(writer.flush());
+		for done in flushed {
+			let _ = done.send(());
+		}
+	}
+}
+
+/// Messages which can be recorded in a replayable form.
+///
+/// Only some variants of a few message types are supported, the default implementation supports
+/// none. Requests whose response is only used by the sender, like
+/// [`CandidateBackingMessage::CanSecond`], are replayed with a fresh response channel, whose
+/// receiver is dropped. Queries are never replayed.
+///
+/// Signed statements, as carried by [`CandidateBackingMessage::Statement`] and
+/// [`StatementDistributionMessage::Share`], can only be rebuilt by checking their signature against
+/// the signing context and the key of the validator. Both are taken from the responses to the
+/// `SessionIndexForChild` and `Validators` runtime API requests recorded in the same trace.
+pub trait RecordableMessage: Sized {
+	/// Encode the message, if it's of a variant which can be replayed.
+	fn encode_for_replay(&self) -> Option<Vec<u8>> {
+		None
+	}
+
+	/// Decode a message encoded by [`Self::encode_for_replay`].
+	fn decode_for_replay(_encoded: &[u8]) -> Option<Self> {
+		None
+	}
+
+	/// Decode a message encoded by [`Self::encode_for_replay`], with the responses recorded in
+	/// the same trace at hand.
+	fn decode_for_replay_with(encoded: &[u8], _responses: &RecordedResponses) -> Option<Self> {
+		Self::decode_for_replay(encoded)
+	}
+
+	/// Replace the response channel of a request with one recording the response in `recorder`.
+	///
+	/// Returns the future forwarding the response to the original channel, which must be polled
+	/// to completion. Only requests answered by the `RuntimeApi` and `ChainApi` subsystems are
+	/// recorded, the default implementation records none.
+	fn record_response(
+		self,
+		_recorder: &MessageRecorder,
+	) -> (Self, Option<BoxFuture<'static, ()>>) {
+		(self, None)
+	}
+}
+
+macro_rules! impl_not_replayable {
+	($($message:ty),* $(,)?) => {
+		$(impl RecordableMessage for $message {})*
+	};
+}
+
+impl_not_replayable!(
+	CandidateValidationMessage,
+	PvfCheckerMessage,
+	AvailabilityDistributionMessage,
+	AvailabilityRecoveryMessage,
+	BitfieldSigningMessage,
+	ProvisionerMessage,
+	AvailabilityStoreMessage,
+	NetworkBridgeRxMessage,
+	NetworkBridgeTxMessage,
+	CollationGenerationMessage,
+	CollatorProtocolMessage,
+	ApprovalVotingMessage,
+	GossipSupportMessage,
+	DisputeCoordinatorMessage,
+	DisputeDistributionMessage,
+);
+
+/// A [`StatementWithPVD`], in an encodable form.
+#[derive(Encode, Decode)]
+enum RecordedStatement {
+	Seconded(CommittedCandidateReceipt, PersistedValidationData),
+	Valid(CandidateHash),
+}
+
+/// A [`SignedFullStatementWithPVD`], in an encodable form.
+#[derive(Encode, Decode)]
+struct RecordedSignedStatement {
+	statement: RecordedStatement,
+	validator_index: ValidatorIndex,
+	signature: ValidatorSignature,
+}
+
+impl RecordedSignedStatement {
+	fn new(signed: &SignedFullStatementWithPVD) -> Self {
+		let statement = match signed.payload() {
+			StatementWithPVD::Seconded(candidate, pvd) =>
+				RecordedStatement::Seconded(candidate.clone(), pvd.clone()),
+			StatementWithPVD::Valid(candidate_hash) => RecordedStatement::Valid(*candidate_hash),
+		};
+		Self {
+			statement,
+			validator_index: signed.validator_index(),
+			signature: signed.signature().clone(),
+		}
+	}
+
+	/// Check the signature of the statement, made at `relay_parent`.
+	fn into_signed(
+		self,
+		relay_parent: Hash,
+		responses: &RecordedResponses,
+	) -> Option<SignedFullStatementWithPVD> {
+		let (context, key) = responses.signing_context(relay_parent, self.validator_index)?;
+		let statement = match self.statement {
+			RecordedStatement::Seconded(candidate, pvd) =>
+				StatementWithPVD::Seconded(candidate, pvd),
+			RecordedStatement::Valid(candidate_hash) => StatementWithPVD::Valid(candidate_hash),
+		};
+		UncheckedSigned::new(statement, self.validator_index, self.signature)
+			.try_into_checked(&context, &key)
+			.ok()
+	}
+}
+
+#[derive(Encode, Decode)]
+enum RecordedCandidateBacking {
+	Second(Hash, CandidateReceipt, PersistedValidationData, PoV),
+	CanSecond {
+		candidate_para_id: ParaId,
+		candidate_relay_parent: Hash,
+		candidate_hash: CandidateHash,
+		parent_head_data_hash: Hash,
+	},
+	Statement(Hash, RecordedSignedStatement),
+}
+
+impl RecordableMessage for CandidateBackingMessage {
+	fn encode_for_replay(&self) -> Option<Vec<u8>> {
+		let recorded = match self {
+			CandidateBackingMessage::Second(relay_parent, candidate, pvd, pov) =>
+				RecordedCandidateBacking::Second(
+					*relay_parent,
+					candidate.clone(),
+					pvd.clone(),
+					pov.clone(),
+				),
+			CandidateBackingMessage::CanSecond(request, _) => RecordedCandidateBacking::CanSecond {
+				candidate_para_id: request.candidate_para_id,
+				candidate_relay_parent: request.candidate_relay_parent,
+				candidate_hash: request.candidate_hash,
+				parent_head_data_hash: request.parent_head_data_hash,
+			},
+			CandidateBackingMessage::Statement(relay_parent, statement) =>
+				RecordedCandidateBacking::Statement(
+					*relay_parent,
+					RecordedSignedStatement::new(statement),
+				),
+			CandidateBackingMessage::GetBackableCandidates(..) => return None,
+		};
+		Some(recorded.encode())
+	}
+
+	fn decode_for_replay(encoded: &[u8]) -> Option<Self> {
+		Self::decode_for_replay_with(encoded, &RecordedResponses::default())
+	}
+
+	fn decode_for_replay_with(mut encoded: &[u8], responses: &RecordedResponses) -> Option<Self> {
+		Some(match RecordedCandidateBacking::decode(&mut encoded).ok()? {
+			RecordedCandidateBacking::Second(relay_parent, candidate, pvd, pov) =>
+				CandidateBackingMessage::Second(relay_parent, candidate, pvd, pov),
+			RecordedCandidateBacking::CanSecond {
+				candidate_para_id,
+				candidate_relay_parent,
+				candidate_hash,
+				parent_head_data_hash,
+			} => CandidateBackingMessage::CanSecond(
+				CanSecondRequest {
+					candidate_para_id,
+					candidate_relay_parent,
+					candidate_hash,
+					parent_head_data_hash,
+				},
+				oneshot::channel().0,
+			),
+			RecordedCandidateBacking::Statement(relay_parent, statement) =>
+				CandidateBackingMessage::Statement(
+					relay_parent,
+					statement.into_signed(relay_parent, responses)?,
+				),
+		})
+	}
+}
+
+#[derive(Encode, Decode)]
+enum RecordedProspectiveParachains {
+	CandidateBacked(ParaId, CandidateHash),
+	IntroduceSecondedCandidate(ParaId, CommittedCandidateReceipt, PersistedValidationData),
+}
+
+impl RecordableMessage for ProspectiveParachainsMessage {
+	fn encode_for_replay(&self) -> Option<Vec<u8>> {
+		let recorded = match self {
+			ProspectiveParachainsMessage::CandidateBacked(para_id, candidate_hash) =>
+				RecordedProspectiveParachains::CandidateBacked(*para_id, *candidate_hash),
+			ProspectiveParachainsMessage::IntroduceSecondedCandidate(request, _) =>
+				RecordedProspectiveParachains::IntroduceSecondedCandidate(
+					request.candidate_para,
+					request.candidate_receipt.clone(),
+					request.persisted_validation_data.clone(),
+				),
+			ProspectiveParachainsMessage::GetBackableCandidates(..) |
+			ProspectiveParachainsMessage::GetHypotheticalMembership(..) |
+			ProspectiveParachainsMessage::GetMinimumRelayParents(..) |
+			ProspectiveParachainsMessage::GetProspectiveValidationData(..) => return None,
+		};
+		Some(recorded.encode())
+	}
+
+	fn decode_for_replay(mut encoded: &[u8]) -> Option<Self> {
+		Some(match RecordedProspectiveParachains::decode(&mut encoded).ok()? {
+			RecordedProspectiveParachains::CandidateBacked(para_id, candidate_hash) =>
+				ProspectiveParachainsMessage::CandidateBacked(para_id, candidate_hash),
+			RecordedProspectiveParachains::IntroduceSecondedCandidate(
+				candidate_para,
+				candidate_receipt,
+				persisted_validation_data,
+			) => ProspectiveParachainsMessage::IntroduceSecondedCandidate(
+				IntroduceSecondedCandidateRequest {
+					candidate_para,
+					candidate_receipt,
+					persisted_validation_data,
+				},
+				oneshot::channel().0,
+			),
+		})
+	}
+}
+
+/// A versioned network message, in an encodable form.
+#[derive(Encode, Decode)]
+enum RecordedVersioned<V1, V2, V3> {
+	V1(V1),
+	V2(V2),
+	V3(V3),
+}
+
+/// A network bridge event, in an encodable form. Peers are stored as the bytes of their id.
+#[derive(Encode, Decode)]
+enum RecordedNetworkEvent<V1, V2, V3> {
+	PeerDisconnected(Vec<u8>),
+	PeerMessage(Vec<u8>, RecordedVersioned<V1, V2, V3>),
+	PeerViewChange(Vec<u8>, View),
+}
+
+impl<V1: Clone, V2: Clone, V3: Clone> RecordedNetworkEvent<V1, V2, V3> {
+	fn new(event: &NetworkBridgeEvent<Versioned<V1, V2, V3>>) -> Option<Self> {
+		Some(match event {
+			NetworkBridgeEvent::PeerDisconnected(peer) =>
+				RecordedNetworkEvent::PeerDisconnected(peer.to_bytes()),
+			NetworkBridgeEvent::PeerMessage(peer, message) => {
+				let message = match message {
+					Versioned::V1(message) => RecordedVersioned::V1(message.clone()),
+					Versioned::V2(message) => RecordedVersioned::V2(message.clone()),
+					Versioned::V3(message) => RecordedVersioned::V3(message.clone()),
+				};
+				RecordedNetworkEvent::PeerMessage(peer.to_bytes(), message)
+			},
+			NetworkBridgeEvent::PeerViewChange(peer, view) =>
+				RecordedNetworkEvent::PeerViewChange(peer.to_bytes(), view.clone()),
+			NetworkBridgeEvent::PeerConnected(..) |
+			NetworkBridgeEvent::NewGossipTopology(_) |
+			NetworkBridgeEvent::OurViewChange(_) |
+			NetworkBridgeEvent::UpdatedAuthorityIds(..) => return None,
+		})
+	}
+
+	fn into_event(self) -> Option<NetworkBridgeEvent<Versioned<V1, V2, V3>>> {
+		let peer = |bytes: Vec<u8>| PeerId::from_bytes(&bytes).ok();
+		Some(match self {
+			RecordedNetworkEvent::PeerDisconnected(id) =>
+				NetworkBridgeEvent::PeerDisconnected(peer(id)?),
+			RecordedNetworkEvent::PeerMessage(id, message) => {
+				let message = match message {
+					RecordedVersioned::V1(message) => Versioned::V1(message),
+					RecordedVersioned::V2(message) => Versioned::V2(message),
+					RecordedVersioned::V3(message) => Versioned::V3(message),
+				};
+				NetworkBridgeEvent::PeerMessage(peer(id)?, message)
+			},
+			RecordedNetworkEvent::PeerViewChange(id, view) =>
+				NetworkBridgeEvent::PeerViewChange(peer(id)?, view),
+		})
+	}
+}
+
+type RecordedBitfieldDistributionEvent = RecordedNetworkEvent<
+	net_protocol::v1::BitfieldDistributionMessage,
+	net_protocol::v2::BitfieldDistributionMessage,
+	net_protocol::v3::BitfieldDistributionMessage,
+>;
+
+impl RecordableMessage for BitfieldDistributionMessage {
+	fn encode_for_replay(&self) -> Option<Vec<u8>> {
+		match self {
+			BitfieldDistributionMessage::NetworkBridgeUpdate(event) =>
+				RecordedBitfieldDistributionEvent::new(event).map(|event| event.encode()),
+			BitfieldDistributionMessage::DistributeBitfield(..) => None,
+		}
+	}
+
+	fn decode_for_replay(mut encoded: &[u8]) -> Option<Self> {
+		let event = RecordedBitfieldDistributionEvent::decode(&mut encoded).ok()?;
+		Some(BitfieldDistributionMessage::NetworkBridgeUpdate(event.into_event()?))
+	}
+}
+
+#[derive(Encode, Decode)]
+enum RecordedStatementDistribution {
+	Backed(CandidateHash),
+	NetworkBridgeUpdate(
+		RecordedNetworkEvent<
+			net_protocol::v1::StatementDistributionMessage,
+			net_protocol::v2::StatementDistributionMessage,
+			net_protocol::v3::StatementDistributionMessage,
+		>,
+	),
+	Share(Hash, RecordedSignedStatement),
+}
+
+impl RecordableMessage for StatementDistributionMessage {
+	fn encode_for_replay(&self) -> Option<Vec<u8>> {
+		let recorded = match self {
+			StatementDistributionMessage::Backed(candidate_hash) =>
+				RecordedStatementDistribution::Backed(*candidate_hash),
+			StatementDistributionMessage::NetworkBridgeUpdate(event) =>
+				RecordedStatementDistribution::NetworkBridgeUpdate(RecordedNetworkEvent::new(
+					event,
+				)?),
+			StatementDistributionMessage::Share(relay_parent, statement) =>
+				RecordedStatementDistribution::Share(
+					*relay_parent,
+					RecordedSignedStatement::new(statement),
+				),
+		};
+		Some(recorded.encode())
+	}
+
+	fn decode_for_replay(encoded: &[u8]) -> Option<Self> {
+		Self::decode_for_replay_with(encoded, &RecordedResponses::default())
+	}
+
+	fn decode_for_replay_with(mut encoded: &[u8], responses: &RecordedResponses) -> Option<Self> {
+		Some(match RecordedStatementDistribution::decode(&mut encoded).ok()? {
+			RecordedStatementDistribution::Backed(candidate_hash) =>
+				StatementDistributionMessage::Backed(candidate_hash),
+			RecordedStatementDistribution::NetworkBridgeUpdate(event) =>
+				StatementDistributionMessage::NetworkBridgeUpdate(event.into_event()?),
+			RecordedStatementDistribution::Share(relay_parent, statement) =>
+				StatementDistributionMessage::Share(
+					relay_parent,
+					statement.into_signed(relay_parent, responses)?,
+				),
+		})
+	}
+}
+
+#[derive(Encode, Decode)]
+enum RecordedApprovalDistribution {
+	DistributeAssignment(IndirectAssignmentCertV2, CandidateBitfield),
+	DistributeApproval(IndirectSignedApprovalVoteV2),
+	NetworkBridgeUpdate(
+		RecordedNetworkEvent<
+			net_protocol::v1::ApprovalDistributionMessage,
+			net_protocol::v2::ApprovalDistributionMessage,
+			net_protocol::v3::ApprovalDistributionMessage,
+		>,
+	),
+	ApprovalCheckingLagUpdate(BlockNumber),
+}
+
+impl RecordedApprovalDistribution {
+	fn into_message(self) -> Option<ApprovalDistributionMessage> {
+		Some(match self {
+			RecordedApprovalDistribution::DistributeAssignment(cert, candidates) =>
+				ApprovalDistributionMessage::DistributeAssignment(cert, candidates),
+			RecordedApprovalDistribution::DistributeApproval(vote) =>
+				ApprovalDistributionMessage::DistributeApproval(vote),
+			RecordedApprovalDistribution::NetworkBridgeUpdate(event) =>
+				ApprovalDistributionMessage::NetworkBridgeUpdate(event.into_event()?),
+			RecordedApprovalDistribution::ApprovalCheckingLagUpdate(lag) =>
+				ApprovalDistributionMessage::ApprovalCheckingLagUpdate(lag),
+		})
+	}
+}
+
+impl RecordableMessage for ApprovalDistributionMessage {
+	fn encode_for_replay(&self) -> Option<Vec<u8>> {
+		let recorded = match self {
+			ApprovalDistributionMessage::DistributeAssignment(cert, candidates) =>
+				RecordedApprovalDistribution::DistributeAssignment(cert.clone(), candidates.clone()),
+			ApprovalDistributionMessage::DistributeApproval(vote) =>
+				RecordedApprovalDistribution::DistributeApproval(vote.clone()),
+			ApprovalDistributionMessage::NetworkBridgeUpdate(event) =>
+				RecordedApprovalDistribution::NetworkBridgeUpdate(RecordedNetworkEvent::new(event)?),
+			ApprovalDistributionMessage::ApprovalCheckingLagUpdate(lag) =>
+				RecordedApprovalDistribution::ApprovalCheckingLagUpdate(*lag),
+			ApprovalDistributionMessage::NewBlocks(_) |
+			ApprovalDistributionMessage::GetApprovalSignatures(..) => return None,
+		};
+		Some(recorded.encode())
+	}
+
+	fn decode_for_replay(mut encoded: &[u8]) -> Option<Self> {
+		RecordedApprovalDistribution::decode(&mut encoded).ok()?.into_message()
+	}
+}
+
+impl RecordableMessage for ApprovalVotingParallelMessage {
+	fn encode_for_replay(&self) -> Option<Vec<u8>> {
+		let recorded = match self {
+			ApprovalVotingParallelMessage::DistributeAssignment(cert, candidates) =>
+				RecordedApprovalDistribution::DistributeAssignment(cert.clone(), candidates.clone()),
+			ApprovalVotingParallelMessage::DistributeApproval(vote) =>
+				RecordedApprovalDistribution::DistributeApproval(vote.clone()),
+			ApprovalVotingParallelMessage::NetworkBridgeUpdate(event) =>
+				RecordedApprovalDistribution::NetworkBridgeUpdate(RecordedNetworkEvent::new(event)?),
+			ApprovalVotingParallelMessage::ApprovalCheckingLagUpdate(lag) =>
+				RecordedApprovalDistribution::ApprovalCheckingLagUpdate(*lag),
+			_ => return None,
+		};
+		Some(recorded.encode())
+	}
+
+	fn decode_for_replay(mut encoded: &[u8]) -> Option<Self> {
+		RecordedApprovalDistribution::decode(&mut encoded)
+			.ok()?
+			.into_message()
+			.map(Into::into)
+	}
+}
+
+#[derive(Encode, Decode)]
+enum RecordedChainSelection {
+	Approved(Hash),
+	RevertBlocks(Vec<(BlockNumber, Hash)>),
+}
+
+impl RecordableMessage for ChainSelectionMessage {
+	fn encode_for_replay(&self) -> Option<Vec<u8>> {
+		let recorded = match self {
+			ChainSelectionMessage::Approved(hash) => RecordedChainSelection::Approved(*hash),
+			ChainSelectionMessage::RevertBlocks(blocks) =>
+				RecordedChainSelection::RevertBlocks(blocks.clone()),
+			ChainSelectionMessage::Leaves(_) |
+			ChainSelectionMessage::BestLeafContaining(..) |
+			ChainSelectionMessage::NonViableLeaves(_) => return None,
+		};
+		Some(recorded.encode())
+	}
+
+	fn decode_for_replay(mut encoded: &[u8]) -> Option<Self> {
+		Some(match RecordedChainSelection::decode(&mut encoded).ok()? {
+			RecordedChainSelection::Approved(hash) => ChainSelectionMessage::Approved(hash),
+			RecordedChainSelection::RevertBlocks(blocks) =>
+				ChainSelectionMessage::RevertBlocks(blocks),
+		})
+	}
+}
+
+/// Replace the response channel `tx` of a request with one recording the response under the
+/// `request` key, see [`RecordableMessage::record_response`].
+///
+/// Errors can't be encoded, so only successful responses are recorded.
+fn recording_channel<T, E>(
+	recorder: &MessageRecorder,
+	subsystem: &'static str,
+	request: Vec<u8>,
+	tx: oneshot::Sender<Result<T, E>>,
+) -> (oneshot::Sender<Result<T, E>>, BoxFuture<'static, ()>)
+where
+	T: Encode + Send + 'static,
+	E: Send + 'static,
+{
+	let recorder = recorder.clone();
+	let (response_tx, response_rx) = oneshot::channel();
+	let forward = async move {
+		let Ok(response) = response_rx.await else { return };
+		if let Ok(value) = &response {
+			recorder.record(
+				subsystem,
+				RecordedDelivery::Response { request, response: value.encode() },
+			);
+		}
+		let _ = tx.send(response);
+	};
+
+	(response_tx, forward.boxed())
+}
+
+/// Answer a request through its response channel `tx` with a recorded response, or with `error`
+/// if there is none.
+fn answer_request<T: Decode, E>(
+	tx: oneshot::Sender<Result<T, E>>,
+	response: Option<&Vec<u8>>,
+	error: E,
+) {
+	let response = response.and_then(|response| T::decode(&mut &response[..]).ok());
+	let _ = tx.send(response.ok_or(error));
+}
+
+macro_rules! runtime_api_requests {
+	($($request:ident($($arg:ident),*)),* $(,)?) => {
+		/// The key a runtime API request is recorded under: the block it is made at, the name of
+		/// the request and its arguments.
+		fn runtime_api_request_key(relay_parent: &Hash, request: &RuntimeApiRequest) -> Vec<u8> {
+			match request {
+				$(RuntimeApiRequest::$request($($arg,)* _) =>
+					(relay_parent, stringify!($request), $($arg,)*).encode(),)*
+			}
+		}
+
+		fn record_runtime_api_response(
+			recorder: &MessageRecorder,
+			relay_parent: Hash,
+			request: RuntimeApiRequest,
+		) -> (RuntimeApiRequest, BoxFuture<'static, ()>) {
+			let key = runtime_api_request_key(&relay_parent, &request);
+			let subsystem = subsystem_name::<RuntimeApiMessage>();
+			match request {
+				$(RuntimeApiRequest::$request($($arg,)* tx) => {
+					let (tx, forward) = recording_channel(recorder, subsystem, key, tx);
+					(RuntimeApiRequest::$request($($arg,)* tx), forward)
+				},)*
+			}
+		}
+
+		fn answer_runtime_api_request(
+			responses: &RecordedResponses,
+			relay_parent: Hash,
+			request: RuntimeApiRequest,
+		) {
+			let key = runtime_api_request_key(&relay_parent, &request);
+			let response = responses.get(subsystem_name::<RuntimeApiMessage>(), &key);
+			match request {
+				$(RuntimeApiRequest::$request(.., tx) => answer_request(
+					tx,
+					response,
+					RuntimeApiError::NotSupported { runtime_api_name: stringify!($request) },
+				),)*
+			}
+		}
+	};
+}
+
+runtime_api_requests!(
+	Version(),
+	Authorities(),
+	Validators(),
+	ValidatorGroups(),
+	AvailabilityCores(),
+	PersistedValidationData(para_id, assumption),
+	AssumedValidationData(para_id, parent_head_hash),
+	CheckValidationOutputs(para_id, commitments),
+	SessionIndexForChild(),
+	ValidationCode(para_id, assumption),
+	ValidationCodeByHash(code_hash),
+	CandidatePendingAvailability(para_id),
+	CandidateEvents(),
+	SessionExecutorParams(session_index),
+	SessionInfo(session_index),
+	DmqContents(para_id),
+	InboundHrmpChannelsContents(para_id),
+	CurrentBabeEpoch(),
+	FetchOnChainVotes(),
+	SubmitPvfCheckStatement(statement, signature),
+	PvfsRequirePrecheck(),
+	ValidationCodeHash(para_id, assumption),
+	Disputes(),
+	UnappliedSlashes(),
+	KeyOwnershipProof(validator_id),
+	SubmitReportDisputeLost(dispute_proof, key_ownership_proof),
+	MinimumBackingVotes(session_index),
+	DisabledValidators(),
+	ParaBackingState(para_id),
+	AsyncBackingParams(),
+	NodeFeatures(session_index),
+	ApprovalVotingParams(session_index),
+	ClaimQueue(),
+	CandidatesPendingAvailability(para_id),
+	BackingConstraints(para_id),
+	SchedulingLookahead(session_index),
+	ValidationCodeBombLimit(session_index),
+);
+
+impl RecordableMessage for RuntimeApiMessage {
+	fn record_response(self, recorder: &MessageRecorder) -> (Self, Option<BoxFuture<'static, ()>>) {
+		let RuntimeApiMessage::Request(relay_parent, request) = self;
+		let (request, forward) = record_runtime_api_response(recorder, relay_parent, request);
+		(RuntimeApiMessage::Request(relay_parent, request), Some(forward))
+	}
+}
+
+/// The key a chain API request is recorded under: the name of the request and its arguments.
+fn chain_api_request_key(request: &ChainApiMessage) -> Vec<u8> {
+	match request {
+		ChainApiMessage::BlockNumber(hash, _) => ("BlockNumber", hash).encode(),
+		ChainApiMessage::BlockHeader(hash, _) => ("BlockHeader", hash).encode(),
+		ChainApiMessage::BlockWeight(hash, _) => ("BlockWeight", hash).encode(),
+		ChainApiMessage::FinalizedBlockHash(number, _) => ("FinalizedBlockHash", number).encode(),
+		ChainApiMessage::FinalizedBlockNumber(_) => ("FinalizedBlockNumber",).encode(),
+		ChainApiMessage::Ancestors { hash, k, .. } => ("Ancestors", hash, *k as u64).encode(),
+	}
+}
+
+impl RecordableMessage for ChainApiMessage {
+	fn record_response(self, recorder: &MessageRecorder) -> (Self, Option<BoxFuture<'static, ()>>) {
+		let key = chain_api_request_key(&self);
+		let subsystem = subsystem_name::<ChainApiMessage>();
+		let (message, forward) = match self {
+			ChainApiMessage::BlockNumber(hash, tx) => {
+				let (tx, forward) = recording_channel(recorder, subsystem, key, tx);
+				(ChainApiMessage::BlockNumber(hash, tx), forward)
+			},
+			ChainApiMessage::BlockHeader(hash, tx) => {
+				let (tx, forward) = recording_channel(recorder, subsystem, key, tx);
+				(ChainApiMessage::BlockHeader(hash, tx), forward)
+			},
+			ChainApiMessage::BlockWeight(hash, tx) => {
+				let (tx, forward) = recording_channel(recorder, subsystem, key, tx);
+				(ChainApiMessage::BlockWeight(hash, tx), forward)
+			},
+			ChainApiMessage::FinalizedBlockHash(number, tx) => {
+				let (tx, forward) = recording_channel(recorder, subsystem, key, tx);
+				(ChainApiMessage::FinalizedBlockHash(number, tx), forward)
+			},
+			ChainApiMessage::FinalizedBlockNumber(tx) => {
+				let (tx, forward) = recording_channel(recorder, subsystem, key, tx);
+				(ChainApiMessage::FinalizedBlockNumber(tx), forward)
+			},
+			ChainApiMessage::Ancestors { hash, k, response_channel } => {
+				let (response_channel, forward) =
+					recording_channel(recorder, subsystem, key, response_channel);
+				(ChainApiMessage::Ancestors { hash, k, response_channel }, forward)
+			},
+		};
+		(message, Some(forward))
+	}
+}
+
+/// The responses recorded in a trace, to answer the runtime API and chain API requests of a
+/// replayed subsystem the way they were answered when the trace was recorded.
+///
+/// The responses are looked up by the request, along with the block it is made at. Requests
+/// which weren't recorded, or failed when they were, are answered with an error.
+#[derive(Debug, Clone, Default)]
+pub struct RecordedResponses {
+	/// The encoded responses, by subsystem and encoded request.
+	responses: HashMap<String, HashMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl RecordedResponses {
+	/// Collect the responses recorded in a trace. Later responses to the same request override
+	/// the earlier ones.
+	pub fn new<'a>(trace: impl IntoIterator<Item = &'a TraceEntry>) -> Self {
+		let mut responses = HashMap::<_, HashMap<_, _>>::new();
+		for entry in trace {
+			if let RecordedDelivery::Response { request, response } = &entry.delivery {
+				responses
+					.entry(entry.subsystem.clone())
+					.or_default()
+					.insert(request.clone(), response.clone());
+			}
+		}
+
+		Self { responses }
+	}
+
+	/// Answer `message` with the recorded response if it is a runtime API or chain API request,
+	/// otherwise give it back.
+	pub fn answer(&self, message: AllMessages) -> Option<AllMessages> {
+		match message {
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(relay_parent, request)) =>
+				answer_runtime_api_request(self, relay_parent, request),
+			AllMessages::ChainApi(request) => {
+				let key = chain_api_request_key(&request);
+				let response = self.get(subsystem_name::<ChainApiMessage>(), &key);
+				let error = || ChainApiError::from("No response recorded");
+				match request {
+					ChainApiMessage::BlockNumber(_, tx) => answer_request(tx, response, error()),
+					ChainApiMessage::BlockHeader(_, tx) => answer_request(tx, response, error()),
+					ChainApiMessage::BlockWeight(_, tx) => answer_request(tx, response, error()),
+					ChainApiMessage::FinalizedBlockHash(_, tx) =>
+						answer_request(tx, response, error()),
+					ChainApiMessage::FinalizedBlockNumber(tx) =>
+						answer_request(tx, response, error()),
+					ChainApiMessage::Ancestors { response_channel, .. } =>
+						answer_request(response_channel, response, error()),
+				}
+			},
+			message => return Some(message),
+		}
+
+		None
+	}
+
+	fn get(&self, subsystem: &str, request: &[u8]) -> Option<&Vec<u8>> {
+		self.responses.get(subsystem)?.get(request)
+	}
+
+	/// The recorded response to a runtime API request without arguments made at `relay_parent`.
+	fn runtime_api<T: Decode>(&self, relay_parent: Hash, request: &str) -> Option<T> {
+		let response =
+			self.get(subsystem_name::<RuntimeApiMessage>(), &(relay_parent, request).encode())?;
+		T::decode(&mut &response[..]).ok()
+	}
+
+	/// The context of the statements signed at `relay_parent` and the key of the validator with
+	/// the given index.
+	///
+	/// The validators are usually requested once per session, so they are looked up at any
+	/// block of the session if they weren't requested at `relay_parent`.
+	fn signing_context(
+		&self,
+		relay_parent: Hash,
+		validator_index: ValidatorIndex,
+	) -> Option<(SigningContext, ValidatorId)> {
+		let session_index: SessionIndex = self.runtime_api(relay_parent, "SessionIndexForChild")?;
+		let validators =
+			self.runtime_api::<Vec<ValidatorId>>(relay_parent, "Validators").or_else(|| {
+				self.responses.get(subsystem_name::<RuntimeApiMessage>())?.keys().find_map(
+					|request| {
+						let (block, name) = <(Hash, String)>::decode(&mut &request[..]).ok()?;
+						if name != "Validators" ||
+							self.runtime_api(block, "SessionIndexForChild") !=
+								Some(session_index)
+						{
+							return None
+						}
+						self.runtime_api(block, "Validators")
+					},
+				)
+			})?;
+		let key = validators.get(validator_index.0 as usize)?.clone();
+
+		Some((SigningContext { session_index, parent_hash: relay_parent }, key))
+	}
+}
+
+/// A subsystem context recording all the deliveries made to the subsystem.
+pub struct RecordingContext<Context> {
+	inner: Context,
+	recorder: MessageRecorder,
+}
+
+impl<Context> RecordingContext<Context> {
+	/// Wrap `inner`, recording its deliveries with `recorder`.
+	pub fn new(inner: Context, recorder: MessageRecorder) -> Self {
+		Self { inner, recorder }
+	}
+}
+
+impl<Context> RecordingContext<Context>
+where
+	Context: SubsystemContext<Signal = OverseerSignal, Error = SubsystemError>,
+	Context::Message: RecordableMessage,
+{
+	/// Record `delivery`, and the response to it if it is a request.
+	fn record(
+		&mut self,
+		delivery: FromOrchestra<Context::Message, OverseerSignal>,
+	) -> SubsystemResult<FromOrchestra<Context::Message, OverseerSignal>> {
+		self.recorder.record_delivery(&delivery);
+		let msg = match delivery {
+			FromOrchestra::Communication { msg } => msg,
+			signal => return Ok(signal),
+		};
+
+		let (msg, forward) = msg.record_response(&self.recorder);
+		if let Some(forward) = forward {
+			self.inner.spawn("overseer-recorder-response", forward)?;
+		}
+		Ok(FromOrchestra::Communication { msg })
+	}
+}
+
+#[async_trait::async_trait]
+impl<Context> SubsystemContext for RecordingContext<Context>
+where
+	Context: SubsystemContext<Signal = OverseerSignal, Error = SubsystemError>,
+	Context::Message: RecordableMessage,
+{
+	type Message = Context::Message;
+	type Signal = OverseerSignal;
+	type OutgoingMessages = Context::OutgoingMessages;
+	type Sender = Context::Sender;
+	type Error = SubsystemError;
+
+	async fn try_recv(&mut self) -> Result<Option<FromOrchestra<Self::Message, Self::Signal>>, ()> {
+		match self.inner.try_recv().await? {
+			Some(delivery) => self.record(delivery).map(Some).map_err(|_| ()),
+			None => Ok(None),
+		}
+	}
+
+	async fn recv(&mut self) -> SubsystemResult<FromOrchestra<Self::Message, Self::Signal>> {
+		let delivery = self.inner.recv().await?;
+		self.record(delivery)
+	}
+
+	async fn recv_signal(&mut self) -> SubsystemResult<Self::Signal> {
+		let signal = self.inner.recv_signal().await?;
+		self.recorder
+			.record(subsystem_name::<Self::Message>(), RecordedDelivery::Signal((&signal).into()));
+		Ok(signal)
+	}
+
+	fn spawn(
+		&mut self,
+		name: &'static str,
+		s: Pin<Box<dyn Future<Output = ()> + Send>>,
+	) -> SubsystemResult<()> {
+		self.inner.spawn(name, s)
+	}
+
+	fn spawn_blocking(
+		&mut self,
+		name: &'static str,
+		s: Pin<Box<dyn Future<Output = ()> + Send>>,
+	) -> SubsystemResult<()> {
+		self.inner.spawn_blocking(name, s)
+	}
+
+	fn sender(&mut self) -> &mut Self::Sender {
+		self.inner.sender()
+	}
+}
+
+/// A subsystem wrapper recording all the deliveries made to the wrapped subsystem.
+///
+/// Usually set up through the `replace_*` methods of the overseer builder.
+pub struct RecordingSubsystem<Sub> {
+	subsystem: Sub,
+	recorder: MessageRecorder,
+}
+
+impl<Sub> RecordingSubsystem<Sub> {
+	/// Wrap `subsystem`, recording its deliveries with `recorder`.
+	pub fn new(subsystem: Sub, recorder: MessageRecorder) -> Self {
+		Self { subsystem, recorder }
+	}
+}
+
+impl<Context, Sub> Subsystem<Context, SubsystemError> for RecordingSubsystem<Sub>
+where
+	Context: SubsystemContext<Signal = OverseerSignal, Error = SubsystemError>,
+	Context::Message: RecordableMessage,
+	Sub: Subsystem<RecordingContext<Context>, SubsystemError>,
+{
+	fn start(self, ctx: Context) -> SpawnedSubsystem<SubsystemError> {
+		self.subsystem.start(RecordingContext::new(ctx, self.recorder))
+	}
+}
@@ -0,0 +1,347 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Re-validates a parachain candidate outside of a running node, the same way a validator
+//! participating in a dispute about it would.
+//!
+//! The candidate receipt, the validation code and the executor parameters are read from
+//! SCALE-encoded files. The executor parameters change the outcome of the validation, so the
+//! defaults are only used when explicitly asked for with `--default-executor-params`. The PoV and
+//! the persisted validation data are read either from files as well, from the availability store of
+//! a stopped node, or from the availability archive of a node. Both are opened read-only, the
+//! archive can be read while the node keeps appending to it.
+//!
+//! The PVF is always prepared from scratch in a temporary artifacts cache, and the time and memory
+//! stats of the preparation and execution recorded by the validation host are printed along with
+//! the result.
+
+use clap::{Parser, ValueEnum};
+use codec::Decode;
+use color_eyre::eyre::{self, eyre, WrapErr};
+use polkadot_node_core_av_store as av_store;
+use polkadot_node_core_candidate_validation::revalidate_candidate;
+use polkadot_node_core_pvf::{Config, Metrics};
+use polkadot_node_metrics::metrics::{prometheus, Metrics as _};
+use polkadot_node_primitives::{PoV, ValidationResult, NODE_VERSION, VALIDATION_CODE_BOMB_LIMIT};
+use polkadot_primitives::{
+	vstaging::CandidateReceiptV2 as CandidateReceipt, ExecutorParams, PersistedValidationData,
+	ValidationCode,
+};
+use polkadot_service::{open_availability_store_read_only, workers, DatabaseSource};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	sync::Arc,
+	time::Instant,
+};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Database {
+	RocksDb,
+	ParityDb,
+}
+
+#[derive(Debug, Parser)]
+#[command(about, version)]
+struct Cli {
+	/// The SCALE-encoded candidate receipt.
+	#[arg(long)]
+	candidate: PathBuf,
+
+	/// The validation code of the parachain, as stored on chain.
+	#[arg(long)]
+	validation_code: PathBuf,
+
+	/// The SCALE-encoded executor parameters of the session the candidate was validated in.
+	#[arg(long, required_unless_present = "default_executor_params")]
+	executor_params: Option<PathBuf>,
+
+	/// Validate with the default executor parameters instead of the ones of a session. The
+	/// result may differ from the one of the validators if the session had other parameters.
+	#[arg(long, conflicts_with = "executor_params")]
+	default_executor_params: bool,
+
+	/// The SCALE-encoded PoV.
+	#[arg(long, requires = "pvd", conflicts_with_all = ["db_path", "archive_path"])]
+	pov: Option<PathBuf>,
+
+	/// The SCALE-encoded persisted validation data.
+	#[arg(long, requires = "pov", conflicts_with_all = ["db_path", "archive_path"])]
+	pvd: Option<PathBuf>,
+
+	/// The availability archive directory of a node, as passed to its
+	/// `--availability-archive-path`, to read the PoV and the persisted validation data from.
+	#[arg(long, conflicts_with = "db_path")]
+	archive_path: Option<PathBuf>,
+
+	/// The database directory of a stopped node to read the PoV and the persisted validation
+	/// data from, e.g. `<base-path>/chains/<chain>/db/full` for RocksDB or
+	/// `<base-path>/chains/<chain>/paritydb/full` for ParityDB.
+	#[arg(long, required_unless_present_any = ["pov", "archive_path"])]
+	db_path: Option<PathBuf>,
+
+	/// The database backend of the node at `--db-path`.
+	#[arg(long, value_enum, default_value_t = Database::RocksDb)]
+	database: Database,
+
+	/// The directory of the prepare and execute worker binaries. Looked up next to this binary
+	/// and in the usual install locations if not given.
+	#[arg(long)]
+	workers_path: Option<PathBuf>,
+
+	/// Run the PVF workers without the security features of Secure Validator Mode.
+	#[arg(long)]
+	insecure_validator_i_know_what_i_do: bool,
+
+	/// The maximum decompressed size of the validation code.
+	#[arg(long, default_value_t = VALIDATION_CODE_BOMB_LIMIT as u32)]
+	validation_code_bomb_limit: u32,
+}
+
+impl Cli {
+	/// Reads the PoV and the persisted validation data of the candidate.
+	fn load_pov_and_pvd(
+		&self,
+		candidate: &CandidateReceipt,
+	) -> eyre::Result<(Arc<PoV>, PersistedValidationData)> {
+		if let Some(path) = &self.archive_path {
+			let archive = av_store::Archive::open_read_only(path)
+				.wrap_err("cannot open the availability archive")?;
+			let candidate_hash = candidate.hash();
+			let available_data = archive
+				.get(&candidate_hash)
+				.wrap_err("cannot read the availability archive")?
+				.ok_or_else(|| eyre!("candidate {candidate_hash:?} is not archived"))?;
+			return Ok((available_data.pov, available_data.validation_data))
+		}
+
+		let (Some(pov), Some(pvd)) = (&self.pov, &self.pvd) else {
+			let path = self.db_path.clone().ok_or_else(|| eyre!("no PoV source given"))?;
+			if !path.is_dir() {
+				return Err(eyre!("no database found at {}", path.display()))
+			}
+			let db_source = match self.database {
+				Database::RocksDb => DatabaseSource::RocksDb { path, cache_size: 0 },
+				Database::ParityDb => DatabaseSource::ParityDb { path },
+			};
+			let scratch = tempfile::tempdir()?;
+			let (db, config) =
+				open_availability_store_read_only(&db_source, scratch.path().to_owned())
+					.wrap_err("cannot open the parachains database")?;
+
+			let candidate_hash = candidate.hash();
+			let available_data = av_store::load_available_data(&db, &config, &candidate_hash)
+				.map_err(|err| eyre!("cannot read the availability store: {err}"))?
+				.ok_or_else(|| {
+					eyre!("no available data stored for candidate {candidate_hash:?}")
+				})?;
+			return Ok((available_data.pov, available_data.validation_data))
+		};
+
+		Ok((Arc::new(decode_file(pov)?), decode_file(pvd)?))
+	}
+}
+
+fn decode_file<T: Decode>(path: &Path) -> eyre::Result<T> {
+	let encoded = fs::read(path).wrap_err_with(|| format!("cannot read {}", path.display()))?;
+	T::decode(&mut &encoded[..]).wrap_err_with(|| format!("cannot decode {}", path.display()))
+}
+
+/// The metrics of the validation host describing the preparation and the execution of a PVF.
+const STATS: &[&str] = &["polkadot_pvf_execution_time", "polkadot_pvf_execution_max_rss"];
+
+/// Prints the preparation and execution stats recorded by the validation host.
+fn print_stats(registry: &prometheus::Registry) {
+	for family in registry.gather() {
+		let name = family.get_name();
+		if !name.starts_with("polkadot_pvf_preparation_") && !STATS.contains(&name) {
+			continue
+		}
+
+		for metric in family.get_metric() {
+			let histogram = metric.get_histogram();
+			if histogram.get_sample_count() > 0 {
+				println!("{}: {}", family.get_help(), histogram.get_sample_sum());
+			}
+		}
+	}
+}
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+	color_eyre::install()?;
+	sp_tracing::try_init_simple();
+
+	let cli = Cli::parse();
+
+	let candidate: CandidateReceipt = decode_file(&cli.candidate)?;
+	let validation_code =
+		ValidationCode(fs::read(&cli.validation_code).wrap_err("cannot read the validation code")?);
+	let executor_params = match (&cli.executor_params, cli.default_executor_params) {
+		(Some(path), _) => decode_file::<ExecutorParams>(path)?,
+		(None, true) => {
+			eprintln!("Warning: validating with the default executor parameters");
+			ExecutorParams::default()
+		},
+		(None, false) => return Err(eyre!("no executor parameters given")),
+	};
+	let (pov, pvd) = cli.load_pov_and_pvd(&candidate)?;
+
+	let (prep_worker_path, exec_worker_path) = workers::determine_workers_paths(
+		cli.workers_path.clone(),
+		None,
+		Some(NODE_VERSION.into()),
+	)?;
+
+	// Use a fresh artifacts cache, so that the PVF is always prepared again.
+	let cache = tempfile::tempdir()?;
+	let registry = prometheus::Registry::new();
+	let config = Config::new(
+		cache.path().to_owned(),
+		Some(NODE_VERSION.into()),
+		!cli.insecure_validator_i_know_what_i_do,
+		prep_worker_path,
+		exec_worker_path,
+		1,
+		1,
+		1,
+	);
+	let (validation_host, task) =
+		polkadot_node_core_pvf::start(config, Metrics::register(Some(&registry))?).await?;
+	tokio::spawn(task);
+
+	let started = Instant::now();
+	let result = revalidate_candidate(
+		validation_host,
+		pvd,
+		validation_code,
+		candidate,
+		pov,
+		executor_params,
+		cli.validation_code_bomb_limit,
+	)
+	.await;
+	let elapsed = started.elapsed();
+
+	match result {
+		Ok(ValidationResult::Valid(commitments, _)) => println!("Valid: {commitments:?}"),
+		Ok(ValidationResult::Invalid(reason)) => println!("Invalid: {reason:?}"),
+		Err(err) => println!("Validation failed: {}", err.0),
+	}
+	println!("Total validation time: {elapsed:?}");
+	print_stats(&registry);
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_node_primitives::{AvailableData, BlockData};
+	use polkadot_primitives_test_helpers::dummy_candidate_receipt_v2;
+
+	fn cli(args: &[&str]) -> Result<Cli, clap::Error> {
+		Cli::try_parse_from(
+			[
+				"polkadot-revalidate-candidate",
+				"--candidate",
+				"c",
+				"--validation-code",
+				"v",
+				"--executor-params",
+				"e",
+			]
+			.iter()
+			.chain(args),
+		)
+	}
+
+	#[test]
+	fn pov_source_is_required() {
+		assert!(cli(&[]).is_err());
+		assert!(cli(&["--pov", "p"]).is_err());
+		assert!(cli(&["--pov", "p", "--pvd", "d", "--db-path", "db"]).is_err());
+		assert!(cli(&["--pov", "p", "--pvd", "d"]).is_ok());
+		assert!(cli(&["--db-path", "db", "--database", "parity-db"]).is_ok());
+		assert!(cli(&["--archive-path", "archive"]).is_ok());
+		assert!(cli(&["--archive-path", "archive", "--db-path", "db"]).is_err());
+		assert!(cli(&["--archive-path", "archive", "--pov", "p", "--pvd", "d"]).is_err());
+	}
+
+	#[test]
+	fn executor_params_are_required() {
+		let parse = |args: &[&str]| {
+			Cli::try_parse_from(
+				["polkadot-revalidate-candidate", "--candidate", "c", "--validation-code", "v"]
+					.iter()
+					.chain(["--pov", "p", "--pvd", "d"].iter())
+					.chain(args),
+			)
+		};
+
+		assert!(parse(&[]).is_err());
+		assert!(parse(&["--executor-params", "e"]).unwrap().executor_params.is_some());
+		let cli = parse(&["--default-executor-params"]).unwrap();
+		assert!(cli.default_executor_params && cli.executor_params.is_none());
+		assert!(parse(&["--executor-params", "e", "--default-executor-params"]).is_err());
+	}
+
+	#[test]
+	fn pov_is_read_from_the_archive() {
+		let dir = tempfile::tempdir().unwrap();
+		let candidate = dummy_candidate_receipt_v2(Default::default());
+		let available_data = AvailableData {
+			pov: Arc::new(PoV { block_data: BlockData(vec![1, 2, 3]) }),
+			validation_data: PersistedValidationData::default(),
+		};
+
+		let archive_path = dir.path().join("archive");
+		let cli = cli(&["--archive-path", archive_path.to_str().unwrap()]).unwrap();
+		// The archive is not created if missing.
+		assert!(cli.load_pov_and_pvd(&candidate).is_err());
+		assert!(!archive_path.exists());
+
+		let archive = av_store::Archive::open(&archive_path).unwrap();
+		assert!(cli.load_pov_and_pvd(&candidate).is_err());
+		archive.append(&candidate.hash(), &available_data).unwrap();
+		archive.sync().unwrap();
+
+		let (pov, pvd) = cli.load_pov_and_pvd(&candidate).unwrap();
+		assert_eq!(pov, available_data.pov);
+		assert_eq!(pvd, available_data.validation_data);
+	}
+
+	#[test]
+	fn missing_database_is_not_created() {
+		let dir = tempfile::tempdir().unwrap();
+		let candidate = dummy_candidate_receipt_v2(Default::default());
+
+		// The database directory doesn't exist.
+		let db_path = dir.path().join("db").join("full");
+		let rocksdb = cli(&["--db-path", db_path.to_str().unwrap()]).unwrap();
+		assert!(rocksdb.load_pov_and_pvd(&candidate).is_err());
+		assert!(!db_path.exists());
+
+		// The database directory exists, but there is no parachains database in it.
+		fs::create_dir_all(&db_path).unwrap();
+		for database in ["rocks-db", "parity-db"] {
+			let cli =
+				cli(&["--db-path", db_path.to_str().unwrap(), "--database", database]).unwrap();
+			assert!(cli.load_pov_and_pvd(&candidate).is_err());
+			assert_eq!(fs::read_dir(&db_path).unwrap().count(), 0);
+		}
+	}
+}
@@ -35,7 +35,9 @@ use gum::info;
 use mmr_gadget::MmrGadget;
 use polkadot_availability_recovery::FETCH_CHUNKS_THRESHOLD;
 use polkadot_node_core_approval_voting::Config as ApprovalVotingConfig;
-use polkadot_node_core_av_store::Config as AvailabilityConfig;
+use polkadot_node_core_av_store::{
+	Archive as AvailabilityArchive, Config as AvailabilityConfig, KEEP_UNAVAILABLE_FOR,
+};
 use polkadot_node_core_candidate_validation::Config as CandidateValidationConfig;
 use polkadot_node_core_chain_selection::{
 	self as chain_selection_subsystem, Config as ChainSelectionConfig,
@@ -46,7 +48,7 @@ use polkadot_node_network_protocol::{
 	request_response::{IncomingRequest, ReqProtocolNames},
 };
 use polkadot_node_subsystem_types::DefaultSubsystemClient;
-use polkadot_overseer::{Handle, OverseerConnector};
+use polkadot_overseer::{Handle, MessageRecorder, OverseerConnector};
 use polkadot_primitives::Block;
 use sc_client_api::Backend;
 use sc_network::config::FullNetworkConfiguration;
@@ -85,6 +87,12 @@ pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	pub prepare_workers_hard_max_num: Option<usize>,
 	/// How long finalized data should be kept in the availability store (in hours)
 	pub keep_finalized_for: Option<u32>,
+	/// How long data which is not available in any block should be kept in the availability
+	/// store (in hours)
+	pub keep_unavailable_for: Option<u32>,
+	/// An optional path to a directory to archive the available data of finalized candidates to,
+	/// before it's pruned from the availability store.
+	pub availability_archive_path: Option<std::path::PathBuf>,
 	pub overseer_gen: OverseerGenerator,
 	pub overseer_message_channel_capacity_override: Option<usize>,
 	#[allow(dead_code)]
@@ -92,6 +100,8 @@ pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	pub hwbench: Option<sc_sysinfo::HwBench>,
 	/// Enable approval voting processing in parallel.
 	pub enable_approval_voting_parallel: bool,
+	/// An optional path to record the messages and signals delivered to the subsystems to.
+	pub overseer_record_path: Option<std::path::PathBuf>,
 }
 
 /// Completely built polkadot node service.
@@ -103,6 +113,7 @@ pub struct NewFull {
 	pub sync_service: Arc<sc_network_sync::SyncingService<Block>>,
 	pub rpc_handlers: RpcHandlers,
 	pub backend: Arc<FullBackend>,
+	pub transaction_pool: Arc<sc_transaction_pool::TransactionPoolHandle<Block, FullClient>>,
 }
 
 pub struct PolkadotServiceBuilder<OverseerGenerator, Network>
@@ -150,8 +161,16 @@ where
 			SelectRelayChain::new_longest_chain(basics.backend.clone())
 		};
 
+		// The parachain consensus introspection RPCs need the subsystems of a validator.
+		let parachains_rpc = (config.role.is_authority() &&
+			!params.is_parachain_node.is_running_alongside_parachain_node())
+		.then(|| polkadot_rpc::ParachainsDeps {
+			overseer_handle: overseer_handle.clone(),
+			approval_voting_parallel: params.enable_approval_voting_parallel,
+		});
+
 		let partial_components =
-			new_partial::<SelectRelayChain<_>>(&mut config, basics, select_chain)?;
+			new_partial::<SelectRelayChain<_>>(&mut config, basics, select_chain, parachains_rpc)?;
 
 		let net_config = sc_network::config::FullNetworkConfiguration::<_, _, Network>::new(
 			&config.network,
@@ -202,7 +221,10 @@ where
 					prepare_workers_soft_max_num,
 					prepare_workers_hard_max_num,
 					keep_finalized_for,
+					keep_unavailable_for,
+					availability_archive_path,
 					enable_approval_voting_parallel,
+					overseer_record_path,
 				},
 			overseer_connector,
 			partial_components:
@@ -428,6 +450,7 @@ where
 				} else {
 					KEEP_FINALIZED_FOR_LIVE_NETWORKS
 				},
+				keep_unavailable_for: keep_unavailable_for.unwrap_or(KEEP_UNAVAILABLE_FOR),
 			};
 
 			Some(ExtendedOverseerGenArgs {
@@ -435,6 +458,9 @@ where
 				parachains_db,
 				candidate_validation_config,
 				availability_config,
+				availability_archive: availability_archive_path
+					.map(AvailabilityArchive::open)
+					.transpose()?,
 				pov_req_receiver,
 				chunk_req_v1_receiver,
 				chunk_req_v2_receiver,
@@ -445,6 +471,7 @@ where
 				chain_selection_config,
 				fetch_chunks_threshold,
 				enable_approval_voting_parallel,
+				message_recorder: overseer_record_path.map(MessageRecorder::create).transpose()?,
 			})
 		};
 
@@ -841,6 +868,7 @@ where
 			sync_service,
 			rpc_handlers,
 			backend,
+			transaction_pool,
 		})
 	}
 }
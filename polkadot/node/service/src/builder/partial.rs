@@ -143,6 +143,7 @@ pub(crate) fn new_partial<ChainSelection>(
 	config: &mut Configuration,
 	Basics { task_manager, backend, client, keystore_container, telemetry }: Basics,
 	select_chain: ChainSelection,
+	parachains_rpc: Option<polkadot_rpc::ParachainsDeps>,
 ) -> Result<PolkadotPartialComponents<ChainSelection>, Error>
 where
 	ChainSelection: 'static + SelectChain<Block>,
@@ -255,6 +256,7 @@ where
 					subscription_executor,
 				},
 				backend: backend.clone(),
+				parachains: parachains_rpc.clone(),
 			};
 
 			polkadot_rpc::create_full(deps).map_err(Into::into)
@@ -362,6 +362,45 @@ pub fn open_database(db_source: &DatabaseSource) -> Result<Arc<dyn Database>, Er
 	Ok(parachains_db)
 }
 
+/// Opens the availability store of a stopped node read-only, e.g. for offline tooling.
+///
+/// `scratch_path` is a directory the RocksDB secondary instance keeps its own logs in. The
+/// database is never created nor migrated, this fails if it doesn't exist or has another version
+/// than the one this node would use.
+#[cfg(feature = "full-node")]
+pub fn open_availability_store_read_only(
+	db_source: &DatabaseSource,
+	scratch_path: PathBuf,
+) -> Result<(Arc<dyn Database>, polkadot_node_core_av_store::Config), Error> {
+	let parachains_db = match db_source {
+		DatabaseSource::RocksDb { path, .. } =>
+			parachains_db::open_read_only_rocksdb(path.clone(), scratch_path)?,
+		DatabaseSource::ParityDb { path, .. } => parachains_db::open_read_only_paritydb(
+			path.parent().ok_or(Error::DatabasePathRequired)?.into(),
+		)?,
+		DatabaseSource::Auto { paritydb_path, rocksdb_path, .. } => {
+			if paritydb_path.is_dir() && paritydb_path.exists() {
+				parachains_db::open_read_only_paritydb(
+					paritydb_path.parent().ok_or(Error::DatabasePathRequired)?.into(),
+				)?
+			} else {
+				parachains_db::open_read_only_rocksdb(rocksdb_path.clone(), scratch_path)?
+			}
+		},
+		DatabaseSource::Custom { .. } => {
+			unimplemented!("No polkadot subsystem db for custom source.");
+		},
+	};
+	let config = polkadot_node_core_av_store::Config {
+		col_data: parachains_db::REAL_COLUMNS.col_availability_data,
+		col_meta: parachains_db::REAL_COLUMNS.col_availability_meta,
+		// Nothing is ever pruned through a read-only database.
+		keep_finalized_for: 0,
+		keep_unavailable_for: 0,
+	};
+	Ok((parachains_db, config))
+}
+
 /// Is this node running as in-process node for a parachain node?
 #[cfg(feature = "full-node")]
 #[derive(Clone)]
@@ -417,7 +456,7 @@ macro_rules! chain_ops {
 		let chain_selection = LongestChain::new(basics.backend.clone());
 
 		let sc_service::PartialComponents { client, backend, import_queue, task_manager, .. } =
-			new_partial::<LongestChain<_, Block>>(&mut config, basics, chain_selection)?;
+			new_partial::<LongestChain<_, Block>>(&mut config, basics, chain_selection, None)?;
 		Ok((client, backend, import_queue, task_manager))
 	}};
 }
@@ -521,7 +560,8 @@ fn revert_chain_selection(db: Arc<dyn Database>, hash: Hash) -> sp_blockchain::R
 		stagnant_check_mode: chain_selection_subsystem::StagnantCheckMode::PruneOnly,
 	};
 
-	let chain_selection = chain_selection_subsystem::ChainSelectionSubsystem::new(config, db);
+	let chain_selection =
+		chain_selection_subsystem::ChainSelectionSubsystem::new(config, db, Default::default());
 
 	chain_selection
 		.revert_to(hash)
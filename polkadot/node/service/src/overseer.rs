@@ -21,7 +21,7 @@ use sp_core::traits::SpawnNamed;
 
 use polkadot_availability_distribution::IncomingRequestReceivers;
 use polkadot_node_core_approval_voting::{Config as ApprovalVotingConfig, RealAssignmentCriteria};
-use polkadot_node_core_av_store::Config as AvailabilityConfig;
+use polkadot_node_core_av_store::{Archive as AvailabilityArchive, Config as AvailabilityConfig};
 use polkadot_node_core_candidate_validation::Config as CandidateValidationConfig;
 use polkadot_node_core_chain_selection::Config as ChainSelectionConfig;
 use polkadot_node_core_dispute_coordinator::Config as DisputeCoordinatorConfig;
@@ -34,8 +34,8 @@ use polkadot_node_network_protocol::{
 #[cfg(any(feature = "malus", test))]
 pub use polkadot_overseer::{dummy::dummy_overseer_builder, HeadSupportsParachains};
 use polkadot_overseer::{
-	metrics::Metrics as OverseerMetrics, MetricsTrait, Overseer, OverseerConnector, OverseerHandle,
-	SpawnGlue,
+	metrics::Metrics as OverseerMetrics, MessageRecorder, MetricsTrait, Overseer,
+	OverseerConnector, OverseerHandle, RecordingSubsystem, SpawnGlue,
 };
 
 use parking_lot::Mutex;
@@ -119,6 +119,8 @@ pub struct ExtendedOverseerGenArgs {
 	pub candidate_validation_config: Option<CandidateValidationConfig>,
 	/// Configuration for the availability store subsystem.
 	pub availability_config: AvailabilityConfig,
+	/// Archive to export finalized available data to before it's pruned, if any.
+	pub availability_archive: Option<AvailabilityArchive>,
 	/// POV request receiver.
 	pub pov_req_receiver: IncomingRequestReceiver<request_v1::PoVFetchingRequest>,
 	/// Erasure chunk request v1 receiver.
@@ -142,6 +144,8 @@ pub struct ExtendedOverseerGenArgs {
 	/// Enable approval-voting-parallel subsystem and disable the standalone approval-voting and
 	/// approval-distribution subsystems.
 	pub enable_approval_voting_parallel: bool,
+	/// Record the deliveries made to all the subsystems, if set.
+	pub message_recorder: Option<MessageRecorder>,
 }
 
 /// Obtain a prepared validator `Overseer`, that is initialized with all default values.
@@ -171,6 +175,7 @@ pub fn validator_overseer_builder<Spawner, RuntimeClient>(
 		parachains_db,
 		candidate_validation_config,
 		availability_config,
+		availability_archive,
 		pov_req_receiver,
 		chunk_req_v1_receiver,
 		chunk_req_v2_receiver,
@@ -181,6 +186,7 @@ pub fn validator_overseer_builder<Spawner, RuntimeClient>(
 		chain_selection_config,
 		fetch_chunks_threshold,
 		enable_approval_voting_parallel,
+		message_recorder: _,
 	}: ExtendedOverseerGenArgs,
 ) -> Result<
 	InitializedOverseerBuilder<
@@ -269,12 +275,15 @@ where
 			&req_protocol_names,
 			Metrics::register(registry)?,
 		))
-		.availability_store(AvailabilityStoreSubsystem::new(
-			parachains_db.clone(),
-			availability_config,
-			Box::new(sync_service.clone()),
-			Metrics::register(registry)?,
-		))
+		.availability_store(
+			AvailabilityStoreSubsystem::new(
+				parachains_db.clone(),
+				availability_config,
+				Box::new(sync_service.clone()),
+				Metrics::register(registry)?,
+			)
+			.with_archive(availability_archive),
+		)
 		.bitfield_distribution(BitfieldDistributionSubsystem::new(Metrics::register(registry)?))
 		.bitfield_signing(BitfieldSigningSubsystem::new(
 			keystore.clone(),
@@ -350,7 +359,11 @@ where
 			authority_discovery_service.clone(),
 			Metrics::register(registry)?,
 		))
-		.chain_selection(ChainSelectionSubsystem::new(chain_selection_config, parachains_db))
+		.chain_selection(ChainSelectionSubsystem::new(
+			chain_selection_config,
+			parachains_db,
+			Metrics::register(registry)?,
+		))
 		.prospective_parachains(ProspectiveParachainsSubsystem::new(Metrics::register(registry)?))
 		.activation_external_listeners(Default::default())
 		.active_leaves(Default::default())
@@ -393,6 +406,7 @@ pub fn validator_with_parallel_overseer_builder<Spawner, RuntimeClient>(
 		parachains_db,
 		candidate_validation_config,
 		availability_config,
+		availability_archive,
 		pov_req_receiver,
 		chunk_req_v1_receiver,
 		chunk_req_v2_receiver,
@@ -403,6 +417,7 @@ pub fn validator_with_parallel_overseer_builder<Spawner, RuntimeClient>(
 		chain_selection_config,
 		fetch_chunks_threshold,
 		enable_approval_voting_parallel,
+		message_recorder: _,
 	}: ExtendedOverseerGenArgs,
 ) -> Result<
 	InitializedOverseerBuilder<
@@ -490,12 +505,15 @@ where
 			&req_protocol_names,
 			Metrics::register(registry)?,
 		))
-		.availability_store(AvailabilityStoreSubsystem::new(
-			parachains_db.clone(),
-			availability_config,
-			Box::new(sync_service.clone()),
-			Metrics::register(registry)?,
-		))
+		.availability_store(
+			AvailabilityStoreSubsystem::new(
+				parachains_db.clone(),
+				availability_config,
+				Box::new(sync_service.clone()),
+				Metrics::register(registry)?,
+			)
+			.with_archive(availability_archive),
+		)
 		.bitfield_distribution(BitfieldDistributionSubsystem::new(Metrics::register(registry)?))
 		.bitfield_signing(BitfieldSigningSubsystem::new(
 			keystore.clone(),
@@ -568,7 +586,11 @@ where
 			authority_discovery_service.clone(),
 			Metrics::register(registry)?,
 		))
-		.chain_selection(ChainSelectionSubsystem::new(chain_selection_config, parachains_db))
+		.chain_selection(ChainSelectionSubsystem::new(
+			chain_selection_config,
+			parachains_db,
+			Metrics::register(registry)?,
+		))
 		.prospective_parachains(ProspectiveParachainsSubsystem::new(Metrics::register(registry)?))
 		.activation_external_listeners(Default::default())
 		.active_leaves(Default::default())
@@ -730,6 +752,39 @@ where
 	Ok(builder)
 }
 
+/// Wrap all the subsystems of an overseer builder, recording their deliveries with the given
+/// `MessageRecorder`.
+macro_rules! record_messages {
+	($builder:expr, $recorder:expr) => {{
+		let recorder: MessageRecorder = $recorder;
+		$builder
+			.replace_candidate_validation(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_pvf_checker(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_candidate_backing(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_statement_distribution(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_availability_distribution(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_availability_recovery(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_bitfield_signing(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_bitfield_distribution(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_provisioner(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_runtime_api(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_availability_store(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_network_bridge_rx(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_network_bridge_tx(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_chain_api(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_collation_generation(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_collator_protocol(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_approval_distribution(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_approval_voting(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_approval_voting_parallel(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_gossip_support(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_dispute_coordinator(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_dispute_distribution(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_chain_selection(|s| RecordingSubsystem::new(s, recorder.clone()))
+			.replace_prospective_parachains(|s| RecordingSubsystem::new(s, recorder))
+	}};
+}
+
 /// Trait for the `fn` generating the overseer.
 pub trait OverseerGen {
 	/// Overwrite the full generation of the overseer, including the subsystems.
@@ -766,14 +821,23 @@ impl OverseerGen for ValidatorOverseerGen {
 			"create validator overseer as mandatory extended arguments were not provided"
 				.to_owned(),
 		)))?;
-		if ext_args.enable_approval_voting_parallel {
-			validator_with_parallel_overseer_builder(args, ext_args)?
+		match (ext_args.enable_approval_voting_parallel, ext_args.message_recorder.clone()) {
+			(true, None) => validator_with_parallel_overseer_builder(args, ext_args)?
 				.build_with_connector(connector)
-				.map_err(|e| e.into())
-		} else {
-			validator_overseer_builder(args, ext_args)?
+				.map_err(|e| e.into()),
+			(true, Some(recorder)) => record_messages!(
+				validator_with_parallel_overseer_builder(args, ext_args)?,
+				recorder
+			)
+			.build_with_connector(connector)
+			.map_err(|e| e.into()),
+			(false, None) => validator_overseer_builder(args, ext_args)?
 				.build_with_connector(connector)
-				.map_err(|e| e.into())
+				.map_err(|e| e.into()),
+			(false, Some(recorder)) =>
+				record_messages!(validator_overseer_builder(args, ext_args)?, recorder)
+					.build_with_connector(connector)
+					.map_err(|e| e.into()),
 		}
 	}
 }
@@ -150,6 +150,57 @@ pub fn open_creating_rocksdb(
 	Ok(Arc::new(db))
 }
 
+/// Open the database of a stopped node on disk read-only, as a secondary instance keeping its own
+/// logs at `secondary_path`.
+///
+/// Unlike [`open_creating_rocksdb`], the database is never created nor migrated: this fails if it
+/// doesn't exist or doesn't have the current version.
+#[cfg(feature = "full-node")]
+pub fn open_read_only_rocksdb(
+	root: PathBuf,
+	secondary_path: PathBuf,
+) -> io::Result<Arc<dyn Database>> {
+	use kvdb_rocksdb::{Database, DatabaseConfig};
+
+	let path = root.join("parachains").join("db");
+	upgrade::ensure_current_version(&path)?;
+
+	let mut db_config = DatabaseConfig::with_columns(columns::v4::NUM_COLUMNS);
+	db_config.create_if_missing = false;
+	db_config.secondary = Some(secondary_path);
+
+	let path_str = path
+		.to_str()
+		.ok_or_else(|| other_io_error(format!("Bad database path: {:?}", path)))?;
+
+	let db = Database::open(&db_config, &path_str)?;
+	let db = polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(
+		db,
+		columns::v4::ORDERED_COL,
+	);
+
+	Ok(Arc::new(db))
+}
+
+/// Open the parity db database of a stopped node read-only.
+///
+/// Unlike [`open_creating_paritydb`], the database is never created nor migrated: this fails if it
+/// doesn't exist or doesn't have the current version.
+#[cfg(feature = "full-node")]
+pub fn open_read_only_paritydb(root: PathBuf) -> io::Result<Arc<dyn Database>> {
+	let path = root.join("parachains");
+	upgrade::ensure_current_version(&path)?;
+
+	let db = parity_db::Db::open_read_only(&upgrade::paritydb_version_3_config(&path))
+		.map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{:?}", err)))?;
+
+	let db = polkadot_node_subsystem_util::database::paritydb_impl::DbAdapter::new(
+		db,
+		columns::v4::ORDERED_COL,
+	);
+	Ok(Arc::new(db))
+}
+
 /// Open a parity db database.
 #[cfg(feature = "full-node")]
 pub fn open_creating_paritydb(
@@ -173,3 +224,63 @@ pub fn open_creating_paritydb(
 	);
 	Ok(Arc::new(db))
 }
+
+#[cfg(all(test, feature = "full-node"))]
+mod tests {
+	use super::*;
+	use kvdb::DBTransaction;
+
+	const KEY: &[u8] = b"candidate";
+	const VALUE: &[u8] = b"available data";
+
+	fn write_value(db: Arc<dyn Database>) {
+		let mut tx = DBTransaction::new();
+		tx.put(columns::v4::COL_AVAILABILITY_DATA, KEY, VALUE);
+		db.write(tx).unwrap();
+	}
+
+	#[test]
+	fn read_only_rocksdb_reads_existing_database() {
+		let root = tempfile::tempdir().unwrap();
+		let secondary = tempfile::tempdir().unwrap();
+
+		// The database is never created.
+		assert!(open_read_only_rocksdb(root.path().into(), secondary.path().into()).is_err());
+		assert!(!root.path().join("parachains").exists());
+
+		write_value(open_creating_rocksdb(root.path().into(), Default::default()).unwrap());
+
+		let db = open_read_only_rocksdb(root.path().into(), secondary.path().into()).unwrap();
+		assert_eq!(
+			db.get(columns::v4::COL_AVAILABILITY_DATA, KEY).unwrap().as_deref(),
+			Some(VALUE)
+		);
+	}
+
+	#[test]
+	fn read_only_paritydb_reads_existing_database() {
+		let root = tempfile::tempdir().unwrap();
+
+		// The database is never created.
+		assert!(open_read_only_paritydb(root.path().into()).is_err());
+		assert!(!root.path().join("parachains").exists());
+
+		write_value(open_creating_paritydb(root.path().into(), Default::default()).unwrap());
+
+		let db = open_read_only_paritydb(root.path().into()).unwrap();
+		assert_eq!(
+			db.get(columns::v4::COL_AVAILABILITY_DATA, KEY).unwrap().as_deref(),
+			Some(VALUE)
+		);
+	}
+
+	#[test]
+	fn read_only_open_rejects_other_versions() {
+		let root = tempfile::tempdir().unwrap();
+		drop(open_creating_paritydb(root.path().into(), Default::default()).unwrap());
+		std::fs::write(root.path().join("parachains").join("parachain_db_version"), "4").unwrap();
+
+		let err = open_read_only_paritydb(root.path().into()).err().unwrap();
+		assert!(err.to_string().contains("another version"), "{err}");
+	}
+}
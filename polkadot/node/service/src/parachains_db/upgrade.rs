@@ -54,6 +54,10 @@ pub enum Error {
 	MigrationFailed,
 	#[error("Parachain DB migration would take forever")]
 	MigrationLoop,
+	#[error("Parachains DB or its version file is missing")]
+	MissingVersion,
+	#[error("Parachains DB has another version (expected {current:?}, found {got:?})")]
+	UnexpectedVersion { current: Version, got: Version },
 }
 
 impl From<Error> for io::Error {
@@ -131,6 +135,15 @@ pub(crate) fn try_upgrade_db_to_next_version(
 	Ok(new_version)
 }
 
+/// Checks that the database at the given path has the current version, without migrating it.
+pub(crate) fn ensure_current_version(db_path: &Path) -> Result<(), Error> {
+	match get_db_version(db_path)? {
+		Some(CURRENT_VERSION) => Ok(()),
+		Some(got) => Err(Error::UnexpectedVersion { current: CURRENT_VERSION, got }),
+		None => Err(Error::MissingVersion),
+	}
+}
+
 /// Reads current database version from the file at given path.
 /// If the file does not exist returns `None`, otherwise the version stored in the file.
 fn get_db_version(path: &Path) -> Result<Option<Version>, Error> {
@@ -662,6 +675,24 @@ mod tests {
 			.unwrap();
 	}
 
+	#[test]
+	fn test_ensure_current_version() {
+		let db_dir = tempfile::tempdir().unwrap();
+		let path = db_dir.path();
+
+		assert!(matches!(ensure_current_version(path), Err(Error::MissingVersion)));
+
+		fs::write(version_file_path(path), "4").expect("Failed to write DB version");
+		assert!(matches!(
+			ensure_current_version(path),
+			Err(Error::UnexpectedVersion { current: CURRENT_VERSION, got: 4 })
+		));
+
+		fs::write(version_file_path(path), CURRENT_VERSION.to_string())
+			.expect("Failed to write DB version");
+		assert!(ensure_current_version(path).is_ok());
+	}
+
 	#[test]
 	fn test_rocksdb_migrate_0_to_5() {
 		use kvdb_rocksdb::{Database, DatabaseConfig};
@@ -26,8 +26,12 @@ mod columns {
 	pub const NUM_COLUMNS: u32 = 2;
 }
 
-const TEST_CONFIG: Config =
-	Config { col_data: columns::DATA, col_meta: columns::META, keep_finalized_for: 1 };
+const TEST_CONFIG: Config = Config {
+	col_data: columns::DATA,
+	col_meta: columns::META,
+	keep_finalized_for: 1,
+	keep_unavailable_for: polkadot_node_core_av_store::KEEP_UNAVAILABLE_FOR,
+};
 
 pub fn new_av_store(dependencies: &TestEnvironmentDependencies) -> AvailabilityStoreSubsystem {
 	let metrics = Metrics::try_register(&dependencies.registry).unwrap();
@@ -0,0 +1,291 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	dummy_builder,
+	environment::{TestEnvironment, TestEnvironmentDependencies},
+	mock::{
+		candidate_backing::MockCandidateBacking,
+		chain_api::{ChainApiState, MockChainApi},
+		network_bridge::{MockNetworkBridgeRx, MockNetworkBridgeTx},
+		prospective_parachains::MockProspectiveParachains,
+		runtime_api::{MockRuntimeApi, MockRuntimeApiCoreState},
+		AlwaysSupportsParachains,
+	},
+	network::{new_network, NetworkEmulatorHandle, NetworkInterface, NetworkInterfaceReceiver},
+	statement::make_keystore,
+	usage::BenchmarkUsage,
+	NODE_UNDER_TEST,
+};
+use colored::Colorize;
+use polkadot_collator_protocol::{CollatorProtocolSubsystem, ProtocolSide};
+use polkadot_node_metrics::metrics::Metrics;
+use polkadot_node_network_protocol::{
+	our_view, peer_set::CollationVersion, v2, ObservedRole, Versioned,
+};
+use polkadot_node_subsystem::{
+	messages::{AllMessages, CollatorProtocolMessage, NetworkBridgeEvent},
+	TimeoutExt,
+};
+use polkadot_overseer::{
+	Handle as OverseerHandle, Overseer, OverseerConnector, OverseerMetrics, SpawnGlue,
+};
+use polkadot_primitives::Id;
+use sc_service::SpawnTaskHandle;
+use serde::{Deserialize, Serialize};
+use sp_core::Pair;
+use std::{
+	sync::{atomic::Ordering, Arc},
+	time::{Duration, Instant},
+};
+pub use test_state::TestState;
+
+mod test_state;
+
+const LOG_TARGET: &str = "subsystem-bench::collation";
+
+/// How long the node under test may take to fetch the collations of a block.
+const MAX_FETCH_TIME: Duration = Duration::from_secs(60);
+
+/// Parameters specific to the collator protocol benchmark
+#[derive(Debug, Clone, Serialize, Deserialize, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct CollatorProtocolOptions {
+	#[clap(long, default_value_t = 10)]
+	/// The number of collators advertising collations for the para on our core.
+	pub n_collators: usize,
+	#[clap(long, default_value_t = 3)]
+	/// The number of cores assigned to each para.
+	pub cores_per_para: usize,
+	#[clap(long, default_value_t = 3)]
+	/// The number of claims a para has in the claim queue of each of its cores.
+	pub claim_queue_depth: usize,
+}
+
+fn build_overseer(
+	state: &TestState,
+	options: &CollatorProtocolOptions,
+	network: NetworkEmulatorHandle,
+	network_interface: NetworkInterface,
+	network_receiver: NetworkInterfaceReceiver,
+	dependencies: &TestEnvironmentDependencies,
+) -> (Overseer<SpawnGlue<SpawnTaskHandle>, AlwaysSupportsParachains>, OverseerHandle) {
+	let overseer_connector = OverseerConnector::with_event_capacity(64000);
+	let overseer_metrics = OverseerMetrics::try_register(&dependencies.registry).unwrap();
+	let spawn_task_handle = dependencies.task_manager.spawn_handle();
+	let mock_runtime_api = MockRuntimeApi::new(
+		state.config.clone(),
+		state.test_authorities.clone(),
+		state.candidate_receipts.clone(),
+		Default::default(),
+		Default::default(),
+		0,
+		MockRuntimeApiCoreState::Scheduled,
+	)
+	.with_claim_queue_depth(options.claim_queue_depth);
+	let chain_api_state = ChainApiState { block_headers: state.block_headers.clone() };
+	let mock_chain_api = MockChainApi::new(chain_api_state);
+	let mock_prospective_parachains = MockProspectiveParachains::new(state.pvd.clone());
+	let mock_candidate_backing = MockCandidateBacking::new(
+		state.config.clone(),
+		state
+			.test_authorities
+			.validator_pairs
+			.get(NODE_UNDER_TEST as usize)
+			.unwrap()
+			.clone(),
+		state.pvd.clone(),
+		state.own_backing_group.clone(),
+		state.committed_receipts(),
+	);
+	let subsystem = CollatorProtocolSubsystem::new(ProtocolSide::Validator {
+		keystore: make_keystore(),
+		eviction_policy: Default::default(),
+		metrics: Metrics::try_register(&dependencies.registry).unwrap(),
+	});
+	let network_bridge_tx = MockNetworkBridgeTx::new(
+		network,
+		network_interface.subsystem_sender(),
+		state.test_authorities.clone(),
+	);
+	let network_bridge_rx = MockNetworkBridgeRx::new(network_receiver, None, false);
+
+	let dummy = dummy_builder!(spawn_task_handle, overseer_metrics)
+		.replace_runtime_api(|_| mock_runtime_api)
+		.replace_chain_api(|_| mock_chain_api)
+		.replace_prospective_parachains(|_| mock_prospective_parachains)
+		.replace_candidate_backing(|_| mock_candidate_backing)
+		.replace_collator_protocol(|_| subsystem)
+		.replace_network_bridge_tx(|_| network_bridge_tx)
+		.replace_network_bridge_rx(|_| network_bridge_rx);
+	let (overseer, raw_handle) = dummy.build_with_connector(overseer_connector).unwrap();
+	let overseer_handle = OverseerHandle::new(raw_handle);
+
+	(overseer, overseer_handle)
+}
+
+pub fn prepare_test(
+	state: &TestState,
+	options: &CollatorProtocolOptions,
+	with_prometheus_endpoint: bool,
+) -> TestEnvironment {
+	let dependencies = TestEnvironmentDependencies::default();
+	let (network, network_interface, network_receiver) = new_network(
+		&state.config,
+		&dependencies,
+		&state.test_authorities,
+		vec![Arc::new(state.clone())],
+	);
+	let (overseer, overseer_handle) = build_overseer(
+		state,
+		options,
+		network.clone(),
+		network_interface,
+		network_receiver,
+		&dependencies,
+	);
+
+	TestEnvironment::new(
+		dependencies,
+		state.config.clone(),
+		network,
+		overseer,
+		overseer_handle,
+		state.test_authorities.clone(),
+		with_prometheus_endpoint,
+	)
+}
+
+fn collator_protocol_update(
+	event: NetworkBridgeEvent<polkadot_node_network_protocol::CollatorProtocolMessage>,
+) -> AllMessages {
+	AllMessages::CollatorProtocol(CollatorProtocolMessage::NetworkBridgeUpdate(event))
+}
+
+pub async fn benchmark_collator_protocol(
+	env: &mut TestEnvironment,
+	state: &TestState,
+	options: &CollatorProtocolOptions,
+) -> BenchmarkUsage {
+	state.reset_trackers();
+
+	let config = env.config().clone();
+	env.metrics().set_n_validators(config.n_validators);
+	env.metrics().set_n_cores(config.n_cores);
+
+	// Collators are only reachable if the emulated peer they run on is connected.
+	let connected_collators = state
+		.collators
+		.iter()
+		.filter(|(peer_id, _)| {
+			state
+				.test_authorities
+				.peer_id_to_authority
+				.get(peer_id)
+				.map_or(false, |authority_id| env.network().is_peer_connected(authority_id))
+		})
+		.collect::<Vec<_>>();
+	gum::info!(target: LOG_TARGET, "{}/{} collators connected", connected_collators.len(), state.collators.len());
+
+	for (peer_id, _) in connected_collators.iter() {
+		env.send_message(collator_protocol_update(NetworkBridgeEvent::PeerConnected(
+			*peer_id,
+			ObservedRole::Full,
+			CollationVersion::V2.into(),
+			None,
+		)))
+		.await;
+	}
+
+	let test_start = Instant::now();
+	for block_info in state.block_infos.iter() {
+		let block_num = block_info.number as usize;
+		gum::info!(target: LOG_TARGET, "Current block {}/{} {:?}", block_num, config.num_blocks, block_info.hash);
+		env.metrics().set_current_block(block_num);
+		env.import_block(block_info.clone()).await;
+		env.send_message(collator_protocol_update(NetworkBridgeEvent::OurViewChange(our_view![
+			block_info.hash
+		])))
+		.await;
+
+		// Collators can only declare once our para is assigned to the core of the node under
+		// test.
+		if block_num == 1 {
+			for (peer_id, collator_pair) in connected_collators.iter() {
+				let signature = collator_pair.sign(&v2::declare_signature_payload(peer_id));
+				let message = v2::CollatorProtocolMessage::Declare(
+					collator_pair.public(),
+					Id::new(test_state::OWN_PARA_ID),
+					signature,
+				);
+				env.send_message(collator_protocol_update(NetworkBridgeEvent::PeerMessage(
+					*peer_id,
+					Versioned::V2(message),
+				)))
+				.await;
+			}
+		}
+
+		for (peer_id, candidate_hash) in state.advertisements.get(&block_info.hash).unwrap() {
+			if !connected_collators.iter().any(|(collator, _)| collator == peer_id) {
+				continue
+			}
+
+			let message = v2::CollatorProtocolMessage::AdvertiseCollation {
+				relay_parent: block_info.hash,
+				candidate_hash: *candidate_hash,
+				parent_head_data_hash: state.pvd.parent_head.hash(),
+			};
+			env.send_message(collator_protocol_update(NetworkBridgeEvent::PeerMessage(
+				*peer_id,
+				Versioned::V2(message),
+			)))
+			.await;
+		}
+
+		// The node under test seconds as many collations as our para has claims on its core.
+		let fetched_tracker = state.fetched_tracker.get(&block_info.hash).unwrap();
+		let fetched_all = async {
+			loop {
+				let fetched = fetched_tracker.load(Ordering::SeqCst);
+				gum::debug!(target: LOG_TARGET, "{}/{} collations fetched", fetched, options.claim_queue_depth);
+
+				if fetched >= options.claim_queue_depth {
+					break;
+				}
+				tokio::time::sleep(Duration::from_millis(50)).await;
+			}
+		};
+		fetched_all.timeout(MAX_FETCH_TIME).await.unwrap_or_else(|| {
+			panic!(
+				"Collations of block {} not fetched within {}s",
+				block_num,
+				MAX_FETCH_TIME.as_secs()
+			)
+		});
+	}
+
+	let duration: u128 = test_start.elapsed().as_millis();
+	gum::info!(target: LOG_TARGET, "All blocks processed in {}", format!("{:?}ms", duration).cyan());
+	gum::info!(target: LOG_TARGET,
+		"Avg block time: {}",
+		format!("{} ms", test_start.elapsed().as_millis() / env.config().num_blocks as u128).red()
+	);
+
+	env.stop().await;
+	env.collect_resource_usage(&["collator-protocol"], false)
+}
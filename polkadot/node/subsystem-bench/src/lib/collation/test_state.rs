@@ -0,0 +1,227 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	collation::CollatorProtocolOptions,
+	configuration::{TestAuthorities, TestConfiguration},
+	mock::runtime_api::session_info_for_peers,
+	network::{HandleNetworkMessage, NetworkMessage},
+	statement::{generate_block_header, generate_block_info},
+	NODE_UNDER_TEST,
+};
+use codec::Encode;
+use polkadot_node_network_protocol::request_response::{v2::CollationFetchingResponse, Requests};
+use polkadot_node_primitives::{BlockData, PoV};
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	vstaging::{
+		CandidateReceiptV2 as CandidateReceipt,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, MutateDescriptorV2,
+	},
+	CandidateHash, CollatorPair, CoreIndex, Header, Id, PersistedValidationData, ValidatorIndex,
+};
+use polkadot_primitives_test_helpers::{
+	dummy_committed_candidate_receipt_v2, dummy_hash, dummy_head_data, dummy_pvd,
+};
+use sc_network::ProtocolName;
+use sc_network_types::PeerId;
+use sp_core::{Pair, H256};
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+};
+
+const SESSION_INDEX: u32 = 0;
+
+/// The para assigned to the core of the node under test.
+pub const OWN_PARA_ID: u32 = 1;
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config
+	pub config: TestConfiguration,
+	// Authority keys for the network emulation.
+	pub test_authorities: TestAuthorities,
+	// Relay chain block infos
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers
+	pub block_headers: HashMap<H256, Header>,
+	// One candidate per core, used to derive the claim queue
+	pub candidate_receipts: HashMap<H256, Vec<CandidateReceipt>>,
+	// PersistedValidationData, we use one for all candidates
+	pub pvd: PersistedValidationData,
+	// Indices in the backing group where the node under test is
+	pub own_backing_group: Vec<ValidatorIndex>,
+	// The collators of our para, each one reusing the peer id of an emulated validator
+	pub collators: Vec<(PeerId, CollatorPair)>,
+	// Collations advertised by each collator peer, by relay parent
+	pub advertisements: HashMap<H256, Vec<(PeerId, CandidateHash)>>,
+	// Collations served to the node under test, by candidate hash
+	pub collations: HashMap<CandidateHash, (CommittedCandidateReceipt, PoV)>,
+	// Tracks how many collations were fetched at each relay parent
+	pub fetched_tracker: HashMap<H256, Arc<AtomicUsize>>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &CollatorProtocolOptions) -> Self {
+		assert!(
+			options.cores_per_para <= config.n_cores,
+			"Our para can't be assigned more cores than available"
+		);
+		assert!(
+			options.n_collators < config.n_validators,
+			"Every collator needs an emulated peer besides us"
+		);
+
+		let test_authorities = config.generate_authorities();
+		let session_info = session_info_for_peers(config, &test_authorities);
+		let own_backing_group = session_info
+			.validator_groups
+			.iter()
+			.find(|g| g.contains(&ValidatorIndex(NODE_UNDER_TEST)))
+			.unwrap()
+			.clone();
+		let collators = test_authorities
+			.peer_ids
+			.iter()
+			.skip(NODE_UNDER_TEST as usize + 1)
+			.take(options.n_collators)
+			.map(|peer_id| (*peer_id, CollatorPair::generate().0))
+			.collect();
+		let mut state = Self {
+			config: config.clone(),
+			test_authorities,
+			block_infos: (1..=config.num_blocks).map(generate_block_info).collect(),
+			block_headers: Default::default(),
+			candidate_receipts: Default::default(),
+			pvd: dummy_pvd(dummy_head_data(), 0),
+			own_backing_group,
+			collators,
+			advertisements: Default::default(),
+			collations: Default::default(),
+			fetched_tracker: Default::default(),
+		};
+
+		state.block_headers = state.block_infos.iter().map(generate_block_header).collect();
+
+		let pov_sizes = config.pov_sizes();
+		for block_info in state.block_infos.iter() {
+			// Consecutive cores are assigned to the same para to emulate elastic scaling.
+			for core_idx in 0..config.n_cores {
+				let para_id = (core_idx / options.cores_per_para) as u32 + OWN_PARA_ID;
+				let mut receipt = dummy_committed_candidate_receipt_v2(dummy_hash());
+				receipt.descriptor.set_para_id(Id::new(para_id));
+				receipt.descriptor.set_relay_parent(block_info.hash);
+				receipt.descriptor.set_core_index(CoreIndex(core_idx as u32));
+				receipt.descriptor.set_session_index(SESSION_INDEX);
+				state
+					.candidate_receipts
+					.entry(block_info.hash)
+					.or_default()
+					.push(receipt.to_plain());
+			}
+
+			for (collator_index, (peer_id, _)) in state.collators.iter().enumerate() {
+				for index in 0..options.claim_queue_depth {
+					let pov_size = pov_sizes[(collator_index + index) % pov_sizes.len()];
+					let (receipt, pov) = generate_collation(
+						block_info.hash,
+						&state.pvd,
+						(collator_index as u32, index as u32).encode(),
+						pov_size,
+					);
+					let candidate_hash = receipt.hash();
+					state
+						.advertisements
+						.entry(block_info.hash)
+						.or_default()
+						.push((*peer_id, candidate_hash));
+					state.collations.insert(candidate_hash, (receipt, pov));
+				}
+			}
+
+			state.fetched_tracker.insert(block_info.hash, Arc::new(AtomicUsize::new(0)));
+		}
+
+		state
+	}
+
+	pub fn committed_receipts(&self) -> HashMap<CandidateHash, CommittedCandidateReceipt> {
+		self.collations
+			.iter()
+			.map(|(candidate_hash, (receipt, _))| (*candidate_hash, receipt.clone()))
+			.collect()
+	}
+
+	pub fn reset_trackers(&self) {
+		self.fetched_tracker
+			.values()
+			.for_each(|v| v.as_ref().store(0, Ordering::SeqCst));
+	}
+}
+
+/// Generates a collation for our para, made unique by the `seed` written into its PoV.
+fn generate_collation(
+	relay_parent: H256,
+	pvd: &PersistedValidationData,
+	seed: Vec<u8>,
+	pov_size: usize,
+) -> (CommittedCandidateReceipt, PoV) {
+	let mut block_data = seed;
+	block_data.resize(pov_size.max(block_data.len()), 0);
+	let pov = PoV { block_data: BlockData(block_data) };
+
+	let mut receipt = dummy_committed_candidate_receipt_v2(dummy_hash());
+	receipt.descriptor.set_para_id(Id::new(OWN_PARA_ID));
+	receipt.descriptor.set_relay_parent(relay_parent);
+	receipt.descriptor.set_core_index(CoreIndex(0));
+	receipt.descriptor.set_session_index(SESSION_INDEX);
+	receipt.descriptor.set_persisted_validation_data_hash(pvd.hash());
+	receipt.descriptor.set_pov_hash(pov.hash());
+
+	(receipt, pov)
+}
+
+#[async_trait::async_trait]
+impl HandleNetworkMessage for TestState {
+	async fn handle(
+		&self,
+		message: NetworkMessage,
+		_node_sender: &mut futures::channel::mpsc::UnboundedSender<NetworkMessage>,
+	) -> Option<NetworkMessage> {
+		match message {
+			NetworkMessage::RequestFromNode(_authority_id, Requests::CollationFetchingV2(req)) => {
+				let payload = req.payload;
+				let (receipt, pov) = self
+					.collations
+					.get(&payload.candidate_hash)
+					.expect("Only advertised collations are requested")
+					.clone();
+				if let Some(tracker) = self.fetched_tracker.get(&payload.relay_parent) {
+					tracker.as_ref().fetch_add(1, Ordering::SeqCst);
+				}
+
+				let response = CollationFetchingResponse::Collation(receipt.to_plain(), pov);
+				let _ = req.pending_response.send(Ok((response.encode(), ProtocolName::from(""))));
+				None
+			},
+			_ => Some(message),
+		}
+	}
+}
@@ -0,0 +1,258 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	dummy_builder,
+	environment::{TestEnvironment, TestEnvironmentDependencies, GENESIS_HASH},
+	mock::{
+		authority_discovery::MockAuthorityDiscovery,
+		availability_recovery::MockAvailabilityRecovery,
+		candidate_validation::MockCandidateValidation,
+		chain_api::{ChainApiState, MockChainApi},
+		network_bridge::{MockNetworkBridgeRx, MockNetworkBridgeTx},
+		runtime_api::{MockRuntimeApi, MockRuntimeApiCoreState},
+		AlwaysSupportsParachains,
+	},
+	network::{new_network, NetworkEmulatorHandle, NetworkInterface, NetworkInterfaceReceiver},
+	statement::make_keystore,
+	usage::BenchmarkUsage,
+};
+use codec::Encode;
+use colored::Colorize;
+use futures::{channel::oneshot, future::join_all};
+use polkadot_dispute_distribution::DisputeDistributionSubsystem;
+use polkadot_node_core_dispute_coordinator::{
+	Config as DisputeCoordinatorConfig, DisputeCoordinatorSubsystem,
+};
+use polkadot_node_metrics::metrics::Metrics;
+use polkadot_node_network_protocol::request_response::{IncomingRequest, ReqProtocolNames};
+use polkadot_node_subsystem::{
+	messages::{AllMessages, DisputeCoordinatorMessage},
+	TimeoutExt,
+};
+use polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter;
+use polkadot_overseer::{
+	Handle as OverseerHandle, Overseer, OverseerConnector, OverseerMetrics, SpawnGlue,
+};
+use polkadot_primitives::{Block, Hash};
+use sc_network::config::{IncomingRequest as RawIncomingRequest, OutgoingResponse};
+use sc_service::SpawnTaskHandle;
+use serde::{Deserialize, Serialize};
+use std::{
+	sync::{atomic::Ordering, Arc},
+	time::{Duration, Instant},
+};
+pub use test_state::TestState;
+
+mod test_state;
+
+const LOG_TARGET: &str = "subsystem-bench::disputes";
+
+/// The dispute coordinator stores everything in a single column.
+const NUM_COLUMNS: u32 = 1;
+
+/// How long the node under test may take to participate in the disputes of a block.
+const MAX_PARTICIPATION_TIME: Duration = Duration::from_secs(60);
+
+/// Parameters specific to the disputes benchmark
+#[derive(Debug, Clone, Serialize, Deserialize, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct DisputesOptions {
+	#[clap(short, long, default_value_t = 10)]
+	/// The number of candidates disputed in each block, at most `n_cores`.
+	pub n_disputes: usize,
+}
+
+fn build_overseer(
+	state: &TestState,
+	network: NetworkEmulatorHandle,
+	network_interface: NetworkInterface,
+	network_receiver: NetworkInterfaceReceiver,
+	dependencies: &TestEnvironmentDependencies,
+) -> (Overseer<SpawnGlue<SpawnTaskHandle>, AlwaysSupportsParachains>, OverseerHandle) {
+	let overseer_connector = OverseerConnector::with_event_capacity(64000);
+	let overseer_metrics = OverseerMetrics::try_register(&dependencies.registry).unwrap();
+	let spawn_task_handle = dependencies.task_manager.spawn_handle();
+	let mock_runtime_api = MockRuntimeApi::new(
+		state.config.clone(),
+		state.test_authorities.clone(),
+		state.candidate_receipts.clone(),
+		state.candidate_events.clone(),
+		Default::default(),
+		0,
+		MockRuntimeApiCoreState::Occupied,
+	);
+	let chain_api_state = ChainApiState { block_headers: state.block_headers.clone() };
+	let mock_chain_api = MockChainApi::new(chain_api_state);
+	let mock_availability_recovery = MockAvailabilityRecovery::new();
+	let mock_candidate_validation = MockCandidateValidation::new();
+	let (dispute_req_receiver, dispute_req_cfg) = IncomingRequest::get_config_receiver::<
+		Block,
+		sc_network::NetworkWorker<Block, Hash>,
+	>(&ReqProtocolNames::new(GENESIS_HASH, None));
+	let keystore = make_keystore();
+	let db = DbAdapter::new(kvdb_memorydb::create(NUM_COLUMNS), &[]);
+	let dispute_coordinator = DisputeCoordinatorSubsystem::new(
+		Arc::new(db),
+		DisputeCoordinatorConfig { col_dispute_data: 0 },
+		keystore.clone(),
+		Metrics::try_register(&dependencies.registry).unwrap(),
+		true,
+	);
+	let dispute_distribution = DisputeDistributionSubsystem::new(
+		keystore,
+		dispute_req_receiver,
+		MockAuthorityDiscovery::new(&state.test_authorities),
+		Metrics::try_register(&dependencies.registry).unwrap(),
+	);
+	let network_bridge_tx = MockNetworkBridgeTx::new(
+		network,
+		network_interface.subsystem_sender(),
+		state.test_authorities.clone(),
+	);
+	let network_bridge_rx =
+		MockNetworkBridgeRx::new(network_receiver, Some(dispute_req_cfg), false);
+
+	let dummy = dummy_builder!(spawn_task_handle, overseer_metrics)
+		.replace_runtime_api(|_| mock_runtime_api)
+		.replace_chain_api(|_| mock_chain_api)
+		.replace_availability_recovery(|_| mock_availability_recovery)
+		.replace_candidate_validation(|_| mock_candidate_validation)
+		.replace_dispute_coordinator(|_| dispute_coordinator)
+		.replace_dispute_distribution(|_| dispute_distribution)
+		.replace_network_bridge_tx(|_| network_bridge_tx)
+		.replace_network_bridge_rx(|_| network_bridge_rx);
+	let (overseer, raw_handle) = dummy.build_with_connector(overseer_connector).unwrap();
+	let overseer_handle = OverseerHandle::new(raw_handle);
+
+	(overseer, overseer_handle)
+}
+
+pub fn prepare_test(state: &TestState, with_prometheus_endpoint: bool) -> TestEnvironment {
+	let dependencies = TestEnvironmentDependencies::default();
+	let (network, network_interface, network_receiver) = new_network(
+		&state.config,
+		&dependencies,
+		&state.test_authorities,
+		vec![Arc::new(state.clone())],
+	);
+	let (overseer, overseer_handle) =
+		build_overseer(state, network.clone(), network_interface, network_receiver, &dependencies);
+
+	TestEnvironment::new(
+		dependencies,
+		state.config.clone(),
+		network,
+		overseer,
+		overseer_handle,
+		state.test_authorities.clone(),
+		with_prometheus_endpoint,
+	)
+}
+
+pub async fn benchmark_dispute_coordinator(
+	env: &mut TestEnvironment,
+	state: &TestState,
+) -> BenchmarkUsage {
+	state.reset_trackers();
+
+	let config = env.config().clone();
+	env.metrics().set_n_validators(config.n_validators);
+	env.metrics().set_n_cores(config.n_cores);
+
+	let test_start = Instant::now();
+	for block_info in state.block_infos.iter() {
+		let block_num = block_info.number as usize;
+		gum::info!(target: LOG_TARGET, "Current block {}/{} {:?}", block_num, config.num_blocks, block_info.hash);
+		env.metrics().set_current_block(block_num);
+		env.import_block(block_info.clone()).await;
+
+		let mut pending_responses = Vec::new();
+		for (validator_index, request) in state.dispute_requests.get(&block_info.hash).unwrap() {
+			let index = validator_index.0 as usize;
+			let authority_id = state.test_authorities.validator_authority_id.get(index).unwrap();
+			if !env.network().is_peer_connected(authority_id) {
+				continue
+			}
+
+			let (pending_response, response_receiver) = oneshot::channel();
+			let request = RawIncomingRequest {
+				peer: *state.test_authorities.peer_ids.get(index).unwrap(),
+				payload: request.encode(),
+				pending_response,
+			};
+			if env.network().send_request_from_peer(authority_id, request).is_ok() {
+				pending_responses.push(response_receiver);
+			}
+		}
+
+		let requests_sent = pending_responses.len();
+		let requests_confirmed = join_all(pending_responses)
+			.await
+			.into_iter()
+			.filter(|response| matches!(response, Ok(OutgoingResponse { result: Ok(_), .. })))
+			.count();
+		gum::info!(target: LOG_TARGET, "{}/{} dispute requests confirmed", requests_confirmed, requests_sent);
+
+		let disputed_candidates = state.disputed_candidates.get(&block_info.hash).unwrap();
+		let participated = async {
+			loop {
+				let votes_count = disputed_candidates
+					.iter()
+					.filter(|candidate| {
+						state.votes_tracker.get(&candidate.hash()).unwrap().load(Ordering::SeqCst)
+					})
+					.count();
+				gum::debug!(target: LOG_TARGET, "{}/{} disputes participated", votes_count, disputed_candidates.len());
+
+				if votes_count == disputed_candidates.len() {
+					break;
+				}
+				tokio::time::sleep(Duration::from_millis(50)).await;
+			}
+		};
+		participated.timeout(MAX_PARTICIPATION_TIME).await.unwrap_or_else(|| {
+			panic!(
+				"Disputes of block {} not participated in within {}s",
+				block_num,
+				MAX_PARTICIPATION_TIME.as_secs()
+			)
+		});
+	}
+
+	let duration: u128 = test_start.elapsed().as_millis();
+	gum::info!(target: LOG_TARGET, "All blocks processed in {}", format!("{:?}ms", duration).cyan());
+	gum::info!(target: LOG_TARGET,
+		"Avg block time: {}",
+		format!("{} ms", test_start.elapsed().as_millis() / env.config().num_blocks as u128).red()
+	);
+
+	let (tx, rx) = oneshot::channel();
+	env.send_message(AllMessages::DisputeCoordinator(DisputeCoordinatorMessage::RecentDisputes(
+		tx,
+	)))
+	.await;
+	let recent_disputes = rx.await.expect("Dispute coordinator never fails us");
+	let concluded = recent_disputes
+		.iter()
+		.filter(|(_, _, status)| status.has_concluded_for())
+		.count();
+	gum::info!(target: LOG_TARGET, "{}/{} disputes concluded valid", concluded, recent_disputes.len());
+
+	env.stop().await;
+	env.collect_resource_usage(&["dispute-coordinator", "dispute-distribution"], false)
+}
@@ -0,0 +1,224 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	configuration::{TestAuthorities, TestConfiguration},
+	disputes::DisputesOptions,
+	network::{HandleNetworkMessage, NetworkMessage},
+	statement::{generate_block_header, generate_block_info},
+	NODE_UNDER_TEST,
+};
+use codec::Encode;
+use polkadot_node_network_protocol::request_response::{
+	v1::{DisputeRequest, DisputeResponse},
+	Requests,
+};
+use polkadot_node_primitives::{InvalidDisputeVote, UncheckedDisputeMessage, ValidDisputeVote};
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	vstaging::{CandidateEvent, CandidateReceiptV2 as CandidateReceipt, MutateDescriptorV2},
+	CandidateHash, CoreIndex, ExplicitDisputeStatement, GroupIndex, Header, Id,
+	InvalidDisputeStatementKind, ValidDisputeStatementKind, ValidatorIndex, ValidatorPair,
+	ValidatorSignature,
+};
+use polkadot_primitives_test_helpers::{
+	dummy_committed_candidate_receipt_v2, dummy_hash, dummy_head_data,
+};
+use sc_network::ProtocolName;
+use sp_core::{Pair, H256};
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
+
+const SESSION_INDEX: u32 = 0;
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config
+	pub config: TestConfiguration,
+	// Authority keys for the network emulation.
+	pub test_authorities: TestAuthorities,
+	// Relay chain block infos
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers
+	pub block_headers: HashMap<H256, Header>,
+	// Map from generated candidate receipts
+	pub candidate_receipts: HashMap<H256, Vec<CandidateReceipt>>,
+	// Inclusion events of all generated candidates
+	pub candidate_events: HashMap<H256, Vec<CandidateEvent>>,
+	// Candidates disputed in each block
+	pub disputed_candidates: HashMap<H256, Vec<CandidateReceipt>>,
+	// Pregenerated dispute requests, with the index of the sending validator
+	pub dispute_requests: HashMap<H256, Vec<(ValidatorIndex, DisputeRequest)>>,
+	// Tracks if the node under test sent out its own vote for a disputed candidate
+	pub votes_tracker: HashMap<CandidateHash, Arc<AtomicBool>>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &DisputesOptions) -> Self {
+		assert!(
+			options.n_disputes <= config.n_cores,
+			"Can't dispute more candidates than there are cores"
+		);
+		assert!(config.n_validators > 2, "Disputes need at least two voters besides us");
+
+		let test_authorities = config.generate_authorities();
+		let mut state = Self {
+			config: config.clone(),
+			test_authorities,
+			block_infos: (1..=config.num_blocks).map(generate_block_info).collect(),
+			block_headers: Default::default(),
+			candidate_receipts: Default::default(),
+			candidate_events: Default::default(),
+			disputed_candidates: Default::default(),
+			dispute_requests: Default::default(),
+			votes_tracker: Default::default(),
+		};
+
+		state.block_headers = state.block_infos.iter().map(generate_block_header).collect();
+
+		for block_info in state.block_infos.iter() {
+			for core_idx in 0..config.n_cores {
+				let mut receipt = dummy_committed_candidate_receipt_v2(dummy_hash());
+				receipt.descriptor.set_para_id(Id::new(core_idx as u32 + 1));
+				receipt.descriptor.set_relay_parent(block_info.hash);
+				receipt.descriptor.set_core_index(CoreIndex(core_idx as u32));
+				receipt.descriptor.set_session_index(SESSION_INDEX);
+				let receipt = receipt.to_plain();
+
+				state.candidate_events.entry(block_info.hash).or_default().push(
+					CandidateEvent::CandidateIncluded(
+						receipt.clone(),
+						dummy_head_data(),
+						CoreIndex(core_idx as u32),
+						GroupIndex(core_idx as u32),
+					),
+				);
+				state.candidate_receipts.entry(block_info.hash).or_default().push(receipt);
+			}
+
+			let disputed = state.candidate_receipts.get(&block_info.hash).unwrap()
+				[..options.n_disputes]
+				.to_vec();
+			let requests = disputed
+				.iter()
+				.enumerate()
+				.flat_map(|(index, candidate)| {
+					generate_dispute_requests(
+						candidate,
+						// Spread the disputes over all validators except the node under test.
+						ValidatorIndex(1 + (index % (config.n_validators - 1)) as u32),
+						&state.test_authorities,
+					)
+				})
+				.collect();
+			for candidate in disputed.iter() {
+				state.votes_tracker.insert(candidate.hash(), Arc::new(AtomicBool::new(false)));
+			}
+			state.dispute_requests.insert(block_info.hash, requests);
+			state.disputed_candidates.insert(block_info.hash, disputed);
+		}
+
+		state
+	}
+
+	pub fn reset_trackers(&self) {
+		self.votes_tracker
+			.values()
+			.for_each(|v| v.as_ref().store(false, Ordering::SeqCst));
+	}
+}
+
+/// Every validator except the disputer and the node under test backs up the invalid vote of the
+/// disputer with an explicit valid vote, so the dispute concludes in favor of the candidate.
+fn generate_dispute_requests(
+	candidate: &CandidateReceipt,
+	disputer: ValidatorIndex,
+	test_authorities: &TestAuthorities,
+) -> Vec<(ValidatorIndex, DisputeRequest)> {
+	let candidate_hash = candidate.hash();
+	let invalid_vote = InvalidDisputeVote {
+		validator_index: disputer,
+		signature: sign_dispute_statement(
+			false,
+			candidate_hash,
+			test_authorities.validator_pairs.get(disputer.0 as usize).unwrap(),
+		),
+		kind: InvalidDisputeStatementKind::Explicit,
+	};
+
+	test_authorities
+		.validator_pairs
+		.iter()
+		.enumerate()
+		.map(|(index, pair)| (ValidatorIndex(index as u32), pair))
+		.filter(|(index, _)| index.0 != NODE_UNDER_TEST && *index != disputer)
+		.map(|(validator_index, pair)| {
+			let valid_vote = ValidDisputeVote {
+				validator_index,
+				signature: sign_dispute_statement(true, candidate_hash, pair),
+				kind: ValidDisputeStatementKind::Explicit,
+			};
+			let request = DisputeRequest(UncheckedDisputeMessage {
+				candidate_receipt: candidate.clone(),
+				session_index: SESSION_INDEX,
+				invalid_vote: invalid_vote.clone(),
+				valid_vote,
+			});
+
+			(validator_index, request)
+		})
+		.collect()
+}
+
+fn sign_dispute_statement(
+	valid: bool,
+	candidate_hash: CandidateHash,
+	pair: &ValidatorPair,
+) -> ValidatorSignature {
+	let payload = ExplicitDisputeStatement { valid, candidate_hash, session: SESSION_INDEX }
+		.signing_payload();
+
+	pair.sign(&payload[..])
+}
+
+#[async_trait::async_trait]
+impl HandleNetworkMessage for TestState {
+	async fn handle(
+		&self,
+		message: NetworkMessage,
+		_node_sender: &mut futures::channel::mpsc::UnboundedSender<NetworkMessage>,
+	) -> Option<NetworkMessage> {
+		match message {
+			NetworkMessage::RequestFromNode(_authority_id, Requests::DisputeSendingV1(req)) => {
+				let candidate_hash = req.payload.0.candidate_receipt.hash();
+				if let Some(tracker) = self.votes_tracker.get(&candidate_hash) {
+					tracker.as_ref().store(true, Ordering::SeqCst);
+				}
+
+				let _ = req
+					.pending_response
+					.send(Ok((DisputeResponse::Confirmed.encode(), ProtocolName::from(""))));
+				None
+			},
+			_ => Some(message),
+		}
+	}
+}
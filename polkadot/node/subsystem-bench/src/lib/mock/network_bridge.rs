@@ -39,6 +39,7 @@ const LOG_TARGET: &str = "subsystem-bench::network-bridge";
 const ALLOWED_PROTOCOLS: &[&str] = &[
 	"/ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff/req_chunk/2",
 	"/ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff/req_attested_candidate/2",
+	"/ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff/send_dispute/1",
 ];
 
 /// A mock of the network bridge tx subsystem.
@@ -142,6 +143,13 @@ impl MockNetworkBridgeTx {
 					NetworkBridgeTxMessage::ReportPeer(_) => {
 						// ignore rep changes
 					},
+					NetworkBridgeTxMessage::DisconnectPeer(_, _) => {
+						// ignore disconnects, emulated peers stay connected
+					},
+					NetworkBridgeTxMessage::SendCollationMessage(_, _) |
+					NetworkBridgeTxMessage::SendCollationMessages(_) => {
+						// collators are not emulated on the collation peer set
+					},
 					NetworkBridgeTxMessage::SendValidationMessage(peers, message) => {
 						for peer in peers {
 							self.to_network_interface
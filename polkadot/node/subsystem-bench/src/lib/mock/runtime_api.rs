@@ -118,6 +118,18 @@ impl MockRuntimeApi {
 		}
 	}
 
+	/// Repeats every claim in the claim queue `depth` times, so a para keeps its core for the
+	/// whole scheduling lookahead.
+	pub fn with_claim_queue_depth(mut self, depth: usize) -> Self {
+		for claims in self.state.claim_queue.values_mut() {
+			*claims = claims
+				.iter()
+				.flat_map(|para_id| std::iter::repeat(*para_id).take(depth))
+				.collect();
+		}
+		self
+	}
+
 	fn session_info(&self) -> SessionInfo {
 		session_info_for_peers(&self.config, &self.state.authorities)
 	}
@@ -330,6 +342,19 @@ impl MockRuntimeApi {
 						RuntimeApiMessage::Request(_parent, RuntimeApiRequest::ClaimQueue(tx)) => {
 							tx.send(Ok(self.state.claim_queue.clone())).unwrap();
 						},
+						RuntimeApiMessage::Request(
+							_parent,
+							RuntimeApiRequest::FetchOnChainVotes(tx),
+						) => {
+							// Disputes are never included on chain in the test.
+							tx.send(Ok(None)).unwrap();
+						},
+						RuntimeApiMessage::Request(
+							_parent,
+							RuntimeApiRequest::UnappliedSlashes(tx),
+						) => {
+							tx.send(Ok(Vec::new())).unwrap();
+						},
 						// Long term TODO: implement more as needed.
 						message => {
 							unimplemented!("Unexpected runtime-api message: {:?}", message)
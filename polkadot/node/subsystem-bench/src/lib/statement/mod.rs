@@ -54,20 +54,21 @@ use polkadot_statement_distribution::StatementDistributionSubsystem;
 use sc_keystore::LocalKeystore;
 use sc_network_types::PeerId;
 use sc_service::SpawnTaskHandle;
-use sp_keystore::{Keystore, KeystorePtr};
+use sp_keystore::Keystore;
 use sp_runtime::RuntimeAppPublic;
 use std::{
 	sync::{atomic::Ordering, Arc},
 	time::{Duration, Instant},
 };
 pub use test_state::TestState;
+pub(crate) use test_state::{generate_block_header, generate_block_info};
 
 mod test_state;
 
 const LOG_TARGET: &str = "subsystem-bench::statement";
 
-pub fn make_keystore() -> KeystorePtr {
-	let keystore: KeystorePtr = Arc::new(LocalKeystore::in_memory());
+pub fn make_keystore() -> Arc<LocalKeystore> {
+	let keystore = Arc::new(LocalKeystore::in_memory());
 	Keystore::sr25519_generate_new(&*keystore, ValidatorId::ID, Some("//Node0"))
 		.expect("Insert key into keystore");
 	Keystore::sr25519_generate_new(&*keystore, AuthorityDiscoveryId::ID, Some("//Node0"))
@@ -96,7 +97,7 @@ fn build_overseer(
 	);
 	let chain_api_state = ChainApiState { block_headers: state.block_headers.clone() };
 	let mock_chain_api = MockChainApi::new(chain_api_state);
-	let mock_prospective_parachains = MockProspectiveParachains::new();
+	let mock_prospective_parachains = MockProspectiveParachains::new(state.pvd.clone());
 	let mock_candidate_backing = MockCandidateBacking::new(
 		state.config.clone(),
 		state
@@ -107,6 +108,7 @@ fn build_overseer(
 			.clone(),
 		state.pvd.clone(),
 		state.own_backing_group.clone(),
+		Default::default(),
 	);
 	let (candidate_req_receiver, candidate_req_cfg) =
 		IncomingRequest::get_config_receiver::<Block, sc_network::NetworkWorker<Block, Hash>>(
@@ -213,11 +213,11 @@ fn sign_statement(
 	.to_owned()
 }
 
-fn generate_block_info(block_num: usize) -> BlockInfo {
+pub(crate) fn generate_block_info(block_num: usize) -> BlockInfo {
 	new_block_import_info(Hash::repeat_byte(block_num as u8), block_num as BlockNumber)
 }
 
-fn generate_block_header(info: &BlockInfo) -> (H256, Header) {
+pub(crate) fn generate_block_header(info: &BlockInfo) -> (H256, Header) {
 	(
 		info.hash,
 		Header {
@@ -21,15 +21,23 @@
 use polkadot_erasure_coding::{branches, obtain_chunks_v1 as obtain_chunks};
 use polkadot_node_primitives::{AvailableData, ErasureChunk, Proof};
 use polkadot_node_subsystem::{
-	messages::AllMessages, overseer, FromOrchestra, OverseerSignal, SpawnGlue, SpawnedSubsystem,
-	SubsystemError, SubsystemResult, TrySendError,
+	messages::AllMessages,
+	overseer::{
+		self,
+		recorder::{
+			subsystem_name, RecordableMessage, RecordedDelivery, RecordedResponses, TraceEntry,
+		},
+		MessageRecorder,
+	},
+	FromOrchestra, OverseerSignal, SpawnGlue, SpawnedSubsystem, SubsystemError, SubsystemResult,
+	TrySendError,
 };
 use polkadot_node_subsystem_util::TimeoutExt;
 use polkadot_primitives::{ChunkIndex, Hash};
 
-use futures::{channel::mpsc, poll, prelude::*};
+use futures::{channel::mpsc, poll, prelude::*, stream::FuturesUnordered};
 use parking_lot::Mutex;
-use sp_core::testing::TaskExecutor;
+use sp_core::{testing::TaskExecutor, traits::SpawnNamed};
 
 use std::{
 	collections::VecDeque,
@@ -350,6 +358,46 @@ impl<M> TestSubsystemContextHandle<M> {
 
 		self.message_buffer.as_ref()
 	}
+
+	/// Record the responses to the runtime API and chain API requests of the subsystem with
+	/// `recorder`, the way the overseer records them when the `RuntimeApi` and `ChainApi`
+	/// subsystems are recorded. The requests are received through [`Self::rx`] as before.
+	///
+	/// Used along with a `RecordingContext` to record a trace which can be replayed by
+	/// [`replay_trace`] and [`answer_recorded_requests`].
+	pub fn record_responses(&mut self, recorder: MessageRecorder, spawner: impl SpawnNamed) {
+		let (tx, rx) = mpsc::unbounded();
+		let mut requests = std::mem::replace(&mut self.rx, rx).fuse();
+		let record = async move {
+			let mut forwards = FuturesUnordered::new();
+			loop {
+				futures::select! {
+					msg = requests.next() => {
+						let msg = match msg {
+							Some(AllMessages::RuntimeApi(msg)) => {
+								let (msg, forward) = msg.record_response(&recorder);
+								forwards.extend(forward);
+								AllMessages::RuntimeApi(msg)
+							},
+							Some(AllMessages::ChainApi(msg)) => {
+								let (msg, forward) = msg.record_response(&recorder);
+								forwards.extend(forward);
+								AllMessages::ChainApi(msg)
+							},
+							Some(msg) => msg,
+							None => break,
+						};
+						if tx.unbounded_send(msg).is_err() {
+							break
+						}
+					},
+					_ = forwards.select_next_some() => {},
+				}
+			}
+			forwards.collect::<()>().await;
+		};
+		spawner.spawn("record-responses", None, record.boxed());
+	}
 }
 
 /// Make a test subsystem context with `buffer_size == 0`. This is used by most
@@ -448,6 +496,68 @@ pub fn subsystem_test_harness<M, OverseerFactory, Overseer, TestFactory, Test>(
 	});
 }
 
+/// Replay a trace recorded by an overseer `MessageRecorder` into the subsystem under test.
+///
+/// Only the deliveries made to the subsystem handling `M` are replayed, in the order they were
+/// recorded. Messages which were not recorded in a replayable form are skipped, see
+/// [`RecordableMessage`]. Signed statements are checked against the responses recorded in the
+/// trace. Deliveries are sent through `tx`, usually a clone of [`TestSubsystemContextHandle::tx`],
+/// and each of them only resolves once the subsystem has read it, so the requests sent by the
+/// subsystem must be answered concurrently, see [`answer_recorded_requests`].
+///
+/// Returns the number of replayed deliveries.
+pub async fn replay_trace<M: RecordableMessage>(
+	mut tx: mpsc::Sender<FromOrchestra<M>>,
+	trace: impl IntoIterator<Item = TraceEntry>,
+) -> usize {
+	let trace = trace.into_iter().collect::<Vec<_>>();
+	let responses = RecordedResponses::new(&trace);
+	let subsystem = subsystem_name::<M>();
+	let mut replayed = 0;
+	for entry in trace.into_iter().filter(|entry| entry.subsystem == subsystem) {
+		let delivery = match entry.delivery {
+			RecordedDelivery::Signal(signal) =>
+				FromOrchestra::Signal(signal.into_signal(mock::dummy_unpin_handle)),
+			RecordedDelivery::Message { encoded: Some(encoded), .. } =>
+				match M::decode_for_replay_with(&encoded, &responses) {
+					Some(msg) => FromOrchestra::Communication { msg },
+					None => continue,
+				},
+			RecordedDelivery::Message { encoded: None, .. } | RecordedDelivery::Response { .. } =>
+				continue,
+		};
+		tx.send(delivery)
+			.timeout(TestSubsystemContextHandle::<M>::TIMEOUT)
+			.await
+			.expect("replaying a delivery does not timeout")
+			.expect("Test subsystem no longer live");
+		replayed += 1;
+	}
+
+	replayed
+}
+
+/// Answer the runtime API and chain API requests of the subsystem under test with the `responses`
+/// recorded in a trace until `until` completes, usually while it is replayed by [`replay_trace`].
+///
+/// Requests which weren't recorded are answered with an error. Returns the output of `until`,
+/// along with all the other messages sent by the subsystem in the meantime.
+pub async fn answer_recorded_requests<M, T>(
+	handle: &mut TestSubsystemContextHandle<M>,
+	responses: &RecordedResponses,
+	until: impl Future<Output = T>,
+) -> (T, Vec<AllMessages>) {
+	futures::pin_mut!(until);
+	let mut unanswered = Vec::new();
+	loop {
+		match future::select(until.as_mut(), handle.rx.next()).await {
+			future::Either::Left((output, _)) => return (output, unanswered),
+			future::Either::Right((Some(msg), _)) => unanswered.extend(responses.answer(msg)),
+			future::Either::Right((None, _)) => return (until.await, unanswered),
+		}
+	}
+}
+
 /// A forward subsystem that implements [`Subsystem`](overseer::Subsystem).
 ///
 /// It forwards all communication from the overseer to the internal message
@@ -573,6 +683,24 @@ pub fn derive_erasure_chunks_with_proofs_and_root(
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use futures::channel::oneshot;
+	use overseer::{
+		recorder::{read_trace, RecordingContext},
+		SubsystemContext, SubsystemSender,
+	};
+	use polkadot_node_primitives::{BlockData, PoV, SignedFullStatementWithPVD, StatementWithPVD};
+	use polkadot_node_subsystem::messages::{
+		CandidateBackingMessage, ChainSelectionMessage, IntroduceSecondedCandidateRequest,
+		ProspectiveParachainsMessage, RuntimeApiMessage, RuntimeApiRequest,
+		StatementDistributionMessage,
+	};
+	use polkadot_primitives::{
+		CandidateHash, Id as ParaId, SigningContext, ValidatorId, ValidatorIndex,
+	};
+	use polkadot_primitives_test_helpers::{
+		dummy_candidate_receipt_v2, dummy_head_data, dummy_pvd, make_candidate,
+	};
+	use sp_keyring::Sr25519Keyring;
 
 	#[test]
 	fn macro_arbitrary_order() {
@@ -589,4 +717,237 @@ mod tests {
 		assert_eq!(first, 11_usize);
 		assert_eq!(second, 0);
 	}
+
+	#[test]
+	fn recorded_trace_is_replayed() {
+		let trace_dir = tempfile::tempdir().unwrap();
+		let trace_path = trace_dir.path().join("trace");
+		let hash = Hash::repeat_byte(1);
+
+		let (ctx, mut handle) =
+			make_subsystem_context::<ChainSelectionMessage, _>(TaskExecutor::new());
+		let recorder = MessageRecorder::create(&trace_path).unwrap();
+		let mut ctx = RecordingContext::new(ctx, recorder.clone());
+		futures::executor::block_on(async {
+			let deliver = async {
+				handle
+					.send(FromOrchestra::Signal(OverseerSignal::BlockFinalized(hash, 1)))
+					.await;
+				handle
+					.send(FromOrchestra::Communication {
+						msg: ChainSelectionMessage::Approved(hash),
+					})
+					.await;
+				let (tx, _rx) = oneshot::channel();
+				handle
+					.send(FromOrchestra::Communication { msg: ChainSelectionMessage::Leaves(tx) })
+					.await;
+			};
+			let receive = async {
+				for _ in 0..3 {
+					ctx.recv().await.unwrap();
+				}
+			};
+			future::join(deliver, receive).await;
+		});
+
+		recorder.flush();
+		let trace = read_trace(&trace_path).unwrap();
+		assert_eq!(trace.len(), 3);
+		assert!(trace.iter().all(|entry| entry.subsystem == "ChainSelectionMessage"));
+		assert!(matches!(
+			&trace[2].delivery,
+			RecordedDelivery::Message { encoded: None, debug } if debug.starts_with("Leaves")
+		));
+
+		let (mut ctx, handle) =
+			make_subsystem_context::<ChainSelectionMessage, _>(TaskExecutor::new());
+		futures::executor::block_on(async {
+			let replay = replay_trace(handle.tx.clone(), trace);
+			let receive = async {
+				assert!(matches!(
+					ctx.recv().await.unwrap(),
+					FromOrchestra::Signal(OverseerSignal::BlockFinalized(h, 1)) if h == hash
+				));
+				assert!(matches!(
+					ctx.recv().await.unwrap(),
+					FromOrchestra::Communication { msg: ChainSelectionMessage::Approved(h) }
+						if h == hash
+				));
+			};
+			let (replayed, _) = future::join(replay, receive).await;
+			assert_eq!(replayed, 2);
+		});
+	}
+
+	fn replayed<M: RecordableMessage>(msg: &M) -> Option<M> {
+		M::decode_for_replay(&msg.encode_for_replay()?)
+	}
+
+	#[test]
+	fn candidate_backing_second_is_replayable() {
+		let relay_parent = Hash::repeat_byte(1);
+		let candidate = dummy_candidate_receipt_v2(relay_parent);
+		let pvd = dummy_pvd(dummy_head_data(), 1);
+		let pov = PoV { block_data: BlockData(vec![1, 2, 3]) };
+
+		let msg = CandidateBackingMessage::Second(
+			relay_parent,
+			candidate.clone(),
+			pvd.clone(),
+			pov.clone(),
+		);
+		assert!(matches!(
+			replayed(&msg),
+			Some(CandidateBackingMessage::Second(r, c, v, p))
+				if r == relay_parent && c == candidate && v == pvd && p == pov
+		));
+	}
+
+	#[test]
+	fn prospective_parachains_candidate_backed_is_replayable() {
+		let candidate_hash = CandidateHash(Hash::repeat_byte(2));
+		let msg = ProspectiveParachainsMessage::CandidateBacked(ParaId::from(100), candidate_hash);
+		assert!(matches!(
+			replayed(&msg),
+			Some(ProspectiveParachainsMessage::CandidateBacked(p, c))
+				if p == ParaId::from(100) && c == candidate_hash
+		));
+
+		let (tx, _rx) = oneshot::channel();
+		let msg = ProspectiveParachainsMessage::GetMinimumRelayParents(Hash::zero(), tx);
+		assert!(msg.encode_for_replay().is_none());
+	}
+
+	#[test]
+	fn requests_are_replayed_with_a_fresh_response_channel() {
+		let (candidate, pvd) = make_candidate(
+			Hash::repeat_byte(1),
+			1,
+			ParaId::from(100),
+			dummy_head_data(),
+			dummy_head_data(),
+			Hash::repeat_byte(2).into(),
+		);
+		let request = IntroduceSecondedCandidateRequest {
+			candidate_para: ParaId::from(100),
+			candidate_receipt: candidate.clone(),
+			persisted_validation_data: pvd.clone(),
+		};
+		let (tx, _rx) = oneshot::channel();
+		let msg = ProspectiveParachainsMessage::IntroduceSecondedCandidate(request, tx);
+		match replayed(&msg) {
+			Some(ProspectiveParachainsMessage::IntroduceSecondedCandidate(request, tx)) => {
+				assert_eq!(request.candidate_receipt, candidate);
+				assert_eq!(request.persisted_validation_data, pvd);
+				assert!(tx.is_canceled());
+			},
+			_ => panic!("`IntroduceSecondedCandidate` is replayable"),
+		}
+	}
+
+	// Signed statements are checked against the validator keys and the session index recorded at
+	// the relay parent, so they can only be replayed along with these responses.
+	#[test]
+	fn signed_statements_are_replayed_with_the_recorded_signing_context() {
+		let trace_dir = tempfile::tempdir().unwrap();
+		let trace_path = trace_dir.path().join("trace");
+		let keystore = mock::make_ferdie_keystore();
+		let relay_parent = Hash::repeat_byte(1);
+		let signing_context = SigningContext { session_index: 1, parent_hash: relay_parent };
+		let validators: Vec<ValidatorId> =
+			vec![Sr25519Keyring::Alice.public().into(), Sr25519Keyring::Ferdie.public().into()];
+		let statement = SignedFullStatementWithPVD::sign(
+			&keystore,
+			StatementWithPVD::Valid(CandidateHash(Hash::repeat_byte(2))),
+			&signing_context,
+			ValidatorIndex(1),
+			&validators[1],
+		)
+		.unwrap()
+		.unwrap();
+
+		let msg = StatementDistributionMessage::Share(relay_parent, statement.clone());
+		assert!(msg.encode_for_replay().is_some());
+		assert!(replayed(&msg).is_none());
+
+		// Record the delivery of the statement, along with the responses to the requests made
+		// by the subsystem to check it.
+		let (ctx, mut handle) =
+			make_subsystem_context::<CandidateBackingMessage, _>(TaskExecutor::new());
+		let recorder = MessageRecorder::create(&trace_path).unwrap();
+		handle.record_responses(recorder.clone(), TaskExecutor::new());
+		let mut ctx = RecordingContext::new(ctx, recorder.clone());
+		futures::executor::block_on(async {
+			let subsystem = async {
+				let (tx, rx) = oneshot::channel();
+				ctx.sender()
+					.send_message(RuntimeApiMessage::Request(
+						relay_parent,
+						RuntimeApiRequest::SessionIndexForChild(tx),
+					))
+					.await;
+				assert_eq!(rx.await.unwrap().unwrap(), 1);
+				let (tx, rx) = oneshot::channel();
+				ctx.sender()
+					.send_message(RuntimeApiMessage::Request(
+						relay_parent,
+						RuntimeApiRequest::Validators(tx),
+					))
+					.await;
+				assert_eq!(rx.await.unwrap().unwrap(), validators);
+				ctx.recv().await.unwrap();
+			};
+			let overseer = async {
+				match handle.recv().await {
+					AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+						_,
+						RuntimeApiRequest::SessionIndexForChild(tx),
+					)) => tx.send(Ok(1)).unwrap(),
+					msg => panic!("unexpected message {:?}", msg),
+				}
+				match handle.recv().await {
+					AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+						_,
+						RuntimeApiRequest::Validators(tx),
+					)) => tx.send(Ok(validators.clone())).unwrap(),
+					msg => panic!("unexpected message {:?}", msg),
+				}
+				handle
+					.send(FromOrchestra::Communication {
+						msg: CandidateBackingMessage::Statement(relay_parent, statement.clone()),
+					})
+					.await;
+			};
+			future::join(subsystem, overseer).await;
+		});
+
+		recorder.flush();
+		let trace = read_trace(&trace_path).unwrap();
+		assert_eq!(trace.len(), 3);
+		let responses = RecordedResponses::new(&trace);
+		let (mut ctx, handle) =
+			make_subsystem_context::<CandidateBackingMessage, _>(TaskExecutor::new());
+		futures::executor::block_on(async {
+			let replay = replay_trace(handle.tx.clone(), trace);
+			let receive = async {
+				assert!(matches!(
+					ctx.recv().await.unwrap(),
+					FromOrchestra::Communication { msg: CandidateBackingMessage::Statement(r, s) }
+						if r == relay_parent && s == statement
+				));
+			};
+			let (replayed, _) = future::join(replay, receive).await;
+			assert_eq!(replayed, 1);
+		});
+
+		// Requests which weren't recorded are answered with an error.
+		let (tx, rx) = oneshot::channel();
+		let request = AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+			Hash::repeat_byte(3),
+			RuntimeApiRequest::SessionIndexForChild(tx),
+		));
+		assert!(responses.answer(request).is_none());
+		assert!(futures::executor::block_on(rx).unwrap().is_err());
+	}
 }
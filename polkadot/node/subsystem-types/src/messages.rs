@@ -639,6 +639,36 @@ pub enum ChainApiMessage {
 	},
 }
 
+/// Why a block isn't viable to build on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonViabilityReason {
+	/// The block wasn't approved in time.
+	Stagnant,
+	/// The block was reverted because a dispute concluded against one of its candidates.
+	DisputeRevert,
+	/// The block was reverted by a reversion log in the header of one of its descendants.
+	ExplicitRevert,
+}
+
+/// Response type to `ChainSelectionMessage::NonViableLeaves`.
+///
+/// A leaf of the unfinalized block tree which isn't viable to build on, because either itself or
+/// one of its ancestors isn't viable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonViableLeaf {
+	/// The hash of the leaf.
+	pub block_hash: Hash,
+	/// The number of the leaf.
+	pub block_number: BlockNumber,
+	/// The hash of the earliest non-viable block in the ancestry of the leaf, the leaf included.
+	/// All the blocks from it up to the leaf are affected.
+	pub earliest_non_viable_hash: Hash,
+	/// The number of the earliest non-viable block in the ancestry of the leaf.
+	pub earliest_non_viable_number: BlockNumber,
+	/// Why the earliest non-viable block isn't viable.
+	pub reason: NonViabilityReason,
+}
+
 /// Chain selection subsystem messages
 #[derive(Debug)]
 pub enum ChainSelectionMessage {
@@ -652,6 +682,10 @@ pub enum ChainSelectionMessage {
 	/// The passed blocks must be marked as reverted, and their children must be marked
 	/// as non-viable.
 	RevertBlocks(Vec<(BlockNumber, Hash)>),
+	/// Request the leaves which aren't viable to build on, in ascending order by number.
+	///
+	/// This walks the whole unfinalized block tree: Calls should be infrequent.
+	NonViableLeaves(oneshot::Sender<Vec<NonViableLeaf>>),
 }
 
 /// A sender for the result of a runtime API request.
@@ -1008,6 +1042,8 @@ pub enum ApprovalVotingParallelMessage {
 		CandidateHash,
 		oneshot::Sender<HashMap<ValidatorIndex, (Vec<CandidateHash>, ValidatorSignature)>>,
 	),
+	/// Gets mapped into `ApprovalVotingMessage::GetPendingApprovals`
+	GetPendingApprovals(oneshot::Sender<Vec<PendingApproval>>),
 	/// Gets mapped into `ApprovalDistributionMessage::NewBlocks`
 	NewBlocks(Vec<BlockApprovalMeta>),
 	/// Gets mapped into `ApprovalDistributionMessage::DistributeAssignment`
@@ -1037,6 +1073,8 @@ impl TryFrom<ApprovalVotingParallelMessage> for ApprovalVotingMessage {
 				Ok(ApprovalVotingMessage::ApprovedAncestor(hash, number, tx)),
 			ApprovalVotingParallelMessage::GetApprovalSignaturesForCandidate(candidate, tx) =>
 				Ok(ApprovalVotingMessage::GetApprovalSignaturesForCandidate(candidate, tx)),
+			ApprovalVotingParallelMessage::GetPendingApprovals(tx) =>
+				Ok(ApprovalVotingMessage::GetPendingApprovals(tx)),
 			_ => Err(()),
 		}
 	}
@@ -1147,6 +1185,30 @@ impl CheckedIndirectSignedApprovalVote {
 	}
 }
 
+/// Response type to `ApprovalVotingMessage::GetPendingApprovals`.
+///
+/// The approval status of a candidate in the context of an unapproved block including it.
+#[derive(Clone, Debug)]
+pub struct PendingApproval {
+	/// The hash of the block including the candidate.
+	pub block_hash: Hash,
+	/// The number of the block including the candidate.
+	pub block_number: BlockNumber,
+	/// The hash of the candidate.
+	pub candidate_hash: CandidateHash,
+	/// The parachain the candidate belongs to.
+	pub para_id: ParaId,
+	/// The tranches assignments were received for, along with the number of assignments in
+	/// each of them.
+	pub tranches: Vec<(DelayTranche, usize)>,
+	/// The tranche of our own assignment and whether it has been triggered, if we are assigned.
+	pub our_assignment: Option<(DelayTranche, bool)>,
+	/// The number of validators which approved the candidate.
+	pub approvals: usize,
+	/// The number of validators in the session of the candidate.
+	pub n_validators: usize,
+}
+
 /// Message to the Approval Voting subsystem.
 #[derive(Debug)]
 pub enum ApprovalVotingMessage {
@@ -1176,6 +1238,12 @@ pub enum ApprovalVotingMessage {
 		CandidateHash,
 		oneshot::Sender<HashMap<ValidatorIndex, (Vec<CandidateHash>, ValidatorSignature)>>,
 	),
+
+	/// Retrieve the approval status of all the candidates of the unapproved blocks.
+	///
+	/// This involves loading all the unapproved blocks and their candidates from the database:
+	/// Calls should be infrequent.
+	GetPendingApprovals(oneshot::Sender<Vec<PendingApproval>>),
 }
 
 /// Message to the Approval Distribution subsystem.
@@ -107,6 +107,9 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 					prepare_workers_soft_max_num: None,
 					enable_approval_voting_parallel: false,
 					keep_finalized_for: None,
+					keep_unavailable_for: None,
+					availability_archive_path: None,
+					overseer_record_path: None,
 				},
 			),
 		sc_network::config::NetworkBackendType::Litep2p =>
@@ -130,6 +133,9 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 					prepare_workers_soft_max_num: None,
 					enable_approval_voting_parallel: false,
 					keep_finalized_for: None,
+					keep_unavailable_for: None,
+					availability_archive_path: None,
+					overseer_record_path: None,
 				},
 			),
 	}
@@ -99,6 +99,9 @@ fn main() -> Result<()> {
 						prepare_workers_soft_max_num: None,
 						enable_approval_voting_parallel: false,
 						keep_finalized_for: None,
+						keep_unavailable_for: None,
+						availability_archive_path: None,
+						overseer_record_path: None,
 					},
 				)
 				.map_err(|e| e.to_string())?;
@@ -38,6 +38,8 @@ use sp_consensus_babe::BabeApi;
 use sp_consensus_beefy::AuthorityIdBound;
 use sp_keystore::KeystorePtr;
 
+pub mod parachains;
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = RpcModule<()>;
 
@@ -73,6 +75,15 @@ pub struct BeefyDeps<AuthorityId: AuthorityIdBound> {
 	pub subscription_executor: sc_rpc::SubscriptionTaskExecutor,
 }
 
+/// Dependencies for the parachain consensus introspection RPCs.
+#[derive(Clone)]
+pub struct ParachainsDeps {
+	/// A handle to the overseer of the validator node.
+	pub overseer_handle: polkadot_overseer::Handle,
+	/// Whether approval voting runs as part of the approval-voting-parallel subsystem.
+	pub approval_voting_parallel: bool,
+}
+
 /// Full client dependencies
 pub struct FullDeps<C, P, SC, B, AuthorityId: AuthorityIdBound> {
 	/// The client instance to use.
@@ -91,11 +102,23 @@ pub struct FullDeps<C, P, SC, B, AuthorityId: AuthorityIdBound> {
 	pub beefy: BeefyDeps<AuthorityId>,
 	/// Backend used by the node.
 	pub backend: Arc<B>,
+	/// Parachain consensus introspection dependencies, only available on validator nodes.
+	pub parachains: Option<ParachainsDeps>,
 }
 
 /// Instantiate all RPC extensions.
 pub fn create_full<C, P, SC, B, AuthorityId>(
-	FullDeps { client, pool, select_chain, chain_spec, babe, grandpa, beefy, backend }: FullDeps<
+	FullDeps {
+		client,
+		pool,
+		select_chain,
+		chain_spec,
+		babe,
+		grandpa,
+		beefy,
+		backend,
+		parachains,
+	}: FullDeps<
 		C,
 		P,
 		SC,
@@ -125,6 +148,7 @@ where
 {
 	use mmr_rpc::{Mmr, MmrApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use parachains::{Parachains, ParachainsApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
 	use sc_consensus_beefy_rpc::{Beefy, BeefyApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
@@ -180,5 +204,9 @@ where
 		.into_rpc(),
 	)?;
 
+	if let Some(ParachainsDeps { overseer_handle, approval_voting_parallel }) = parachains {
+		io.merge(Parachains::new(overseer_handle, approval_voting_parallel).into_rpc())?;
+	}
+
 	Ok(io)
 }
@@ -0,0 +1,350 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPCs exposing the parachain consensus state of a validator node.
+//!
+//! The state is queried from the approval voting, availability store, dispute coordinator and
+//! chain selection subsystems through the overseer. All the methods are unsafe, as they put load on
+//! subsystems which are critical to the node.
+
+use futures::channel::oneshot;
+use jsonrpsee::{
+	core::async_trait,
+	proc_macros::rpc,
+	types::{ErrorObject, ErrorObjectOwned},
+	Extensions,
+};
+use polkadot_node_primitives::DisputeStatus as NodeDisputeStatus;
+use polkadot_node_subsystem_types::messages::{
+	ApprovalVotingMessage, ApprovalVotingParallelMessage, AvailabilityStoreMessage,
+	ChainSelectionMessage, DisputeCoordinatorMessage, NonViabilityReason as NodeNonViabilityReason,
+	NonViableLeaf as NodeNonViableLeaf, PendingApproval as NodePendingApproval,
+};
+use polkadot_overseer::Handle;
+use polkadot_primitives::{BlockNumber, CandidateHash, Hash, SessionIndex};
+use sc_rpc_api::{check_if_safe, UnsafeRpcError};
+use serde::{Deserialize, Serialize};
+
+const PARACHAINS_ERROR: i32 = 9500;
+
+/// The origin of the messages sent to the overseer.
+const MESSAGE_ORIGIN: &str = "parachains-rpc";
+
+/// Provides RPC methods inspecting the parachain consensus state of a validator node.
+#[rpc(client, server)]
+pub trait ParachainsApi {
+	/// Returns the approval status of the candidates included in the unapproved blocks.
+	#[method(name = "parachains_pendingApprovals", with_extensions)]
+	async fn pending_approvals(&self) -> Result<Vec<PendingApproval>, Error>;
+
+	/// Returns the availability data of the given candidate held by the node.
+	#[method(name = "parachains_availability", with_extensions)]
+	async fn availability(&self, candidate_hash: Hash) -> Result<Availability, Error>;
+
+	/// Returns the disputes which are either not concluded yet or recently concluded.
+	#[method(name = "parachains_activeDisputes", with_extensions)]
+	async fn active_disputes(&self) -> Result<Vec<Dispute>, Error>;
+
+	/// Returns the leaves which aren't viable to build on, along with the reason and the range of
+	/// blocks affected.
+	#[method(name = "parachains_nonViableLeaves", with_extensions)]
+	async fn non_viable_leaves(&self) -> Result<Vec<NonViableLeaf>, Error>;
+}
+
+/// The assignments received for a candidate in a tranche.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrancheAssignments {
+	/// The tranche of the assignments.
+	pub tranche: u32,
+	/// The number of assignments received in the tranche.
+	pub assignments: u32,
+}
+
+/// The assignment of the node to check a candidate.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OurAssignment {
+	/// The tranche of the assignment.
+	pub tranche: u32,
+	/// Whether the assignment has been triggered.
+	pub triggered: bool,
+}
+
+/// The approval status of a candidate in the context of an unapproved block including it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingApproval {
+	/// The hash of the block including the candidate.
+	pub block_hash: Hash,
+	/// The number of the block including the candidate.
+	pub block_number: BlockNumber,
+	/// The hash of the candidate.
+	pub candidate_hash: Hash,
+	/// The parachain the candidate belongs to.
+	pub para_id: u32,
+	/// The tranches assignments were received for.
+	pub tranches: Vec<TrancheAssignments>,
+	/// The assignment of the node, if any.
+	pub our_assignment: Option<OurAssignment>,
+	/// The number of validators which approved the candidate.
+	pub approvals: u32,
+	/// The number of validators in the session of the candidate.
+	pub n_validators: u32,
+}
+
+impl From<NodePendingApproval> for PendingApproval {
+	fn from(pending: NodePendingApproval) -> Self {
+		Self {
+			block_hash: pending.block_hash,
+			block_number: pending.block_number,
+			candidate_hash: pending.candidate_hash.0,
+			para_id: pending.para_id.into(),
+			tranches: pending
+				.tranches
+				.into_iter()
+				.map(|(tranche, assignments)| TrancheAssignments {
+					tranche,
+					assignments: assignments as u32,
+				})
+				.collect(),
+			our_assignment: pending
+				.our_assignment
+				.map(|(tranche, triggered)| OurAssignment { tranche, triggered }),
+			approvals: pending.approvals as u32,
+			n_validators: pending.n_validators as u32,
+		}
+	}
+}
+
+/// An erasure chunk held by the node.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Chunk {
+	/// The index of the validator the chunk is assigned to.
+	pub validator_index: u32,
+	/// The index of the chunk.
+	pub chunk_index: u32,
+}
+
+/// The availability data of a candidate held by the node.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Availability {
+	/// Whether the full available data of the candidate is held.
+	pub available_data: bool,
+	/// The erasure chunks of the candidate held.
+	pub chunks: Vec<Chunk>,
+}
+
+/// The status of a dispute.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DisputeStatus {
+	/// The dispute is active and unconcluded.
+	Active,
+	/// The dispute has been confirmed, but is not concluded yet.
+	Confirmed,
+	/// The dispute concluded in favor of the candidate, at the given timestamp.
+	ConcludedFor(u64),
+	/// The dispute concluded against the candidate, at the given timestamp.
+	ConcludedAgainst(u64),
+}
+
+impl From<NodeDisputeStatus> for DisputeStatus {
+	fn from(status: NodeDisputeStatus) -> Self {
+		match status {
+			NodeDisputeStatus::Active => Self::Active,
+			NodeDisputeStatus::Confirmed => Self::Confirmed,
+			NodeDisputeStatus::ConcludedFor(at) => Self::ConcludedFor(at),
+			NodeDisputeStatus::ConcludedAgainst(at) => Self::ConcludedAgainst(at),
+		}
+	}
+}
+
+/// A dispute known to the node.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dispute {
+	/// The session the disputed candidate was included in.
+	pub session: SessionIndex,
+	/// The hash of the disputed candidate.
+	pub candidate_hash: Hash,
+	/// The status of the dispute.
+	pub status: DisputeStatus,
+}
+
+/// Why a block isn't viable to build on.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NonViabilityReason {
+	/// The block wasn't approved in time.
+	Stagnant,
+	/// The block was reverted because a dispute concluded against one of its candidates.
+	DisputeRevert,
+	/// The block was reverted by a reversion log in the header of one of its descendants.
+	ExplicitRevert,
+}
+
+impl From<NodeNonViabilityReason> for NonViabilityReason {
+	fn from(reason: NodeNonViabilityReason) -> Self {
+		match reason {
+			NodeNonViabilityReason::Stagnant => Self::Stagnant,
+			NodeNonViabilityReason::DisputeRevert => Self::DisputeRevert,
+			NodeNonViabilityReason::ExplicitRevert => Self::ExplicitRevert,
+		}
+	}
+}
+
+/// A leaf which isn't viable to build on.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NonViableLeaf {
+	/// The hash of the leaf.
+	pub block_hash: Hash,
+	/// The number of the leaf.
+	pub block_number: BlockNumber,
+	/// The hash of the earliest non-viable block in the ancestry of the leaf, the leaf included.
+	pub earliest_non_viable_hash: Hash,
+	/// The number of the earliest non-viable block, all the blocks from it up to the leaf are
+	/// affected.
+	pub earliest_non_viable_number: BlockNumber,
+	/// Why the earliest non-viable block isn't viable.
+	pub reason: NonViabilityReason,
+}
+
+impl From<NodeNonViableLeaf> for NonViableLeaf {
+	fn from(leaf: NodeNonViableLeaf) -> Self {
+		Self {
+			block_hash: leaf.block_hash,
+			block_number: leaf.block_number,
+			earliest_non_viable_hash: leaf.earliest_non_viable_hash,
+			earliest_non_viable_number: leaf.earliest_non_viable_number,
+			reason: leaf.reason.into(),
+		}
+	}
+}
+
+/// Provides RPC methods inspecting the parachain consensus state of a validator node.
+pub struct Parachains {
+	/// A handle to the overseer of the node.
+	overseer_handle: Handle,
+	/// Whether approval voting runs as part of the approval-voting-parallel subsystem.
+	approval_voting_parallel: bool,
+}
+
+impl Parachains {
+	/// Creates a new instance of the parachains RPC handler.
+	pub fn new(overseer_handle: Handle, approval_voting_parallel: bool) -> Self {
+		Self { overseer_handle, approval_voting_parallel }
+	}
+
+	async fn request<T>(
+		&self,
+		message: impl FnOnce(oneshot::Sender<T>) -> polkadot_overseer::AllMessages,
+	) -> Result<T, Error> {
+		let (tx, rx) = oneshot::channel();
+		self.overseer_handle.clone().send_msg(message(tx), MESSAGE_ORIGIN).await;
+		rx.await.map_err(|_| Error::SubsystemUnavailable)
+	}
+}
+
+#[async_trait]
+impl ParachainsApiServer for Parachains {
+	async fn pending_approvals(&self, ext: &Extensions) -> Result<Vec<PendingApproval>, Error> {
+		check_if_safe(ext)?;
+
+		let pending = if self.approval_voting_parallel {
+			self.request(|tx| ApprovalVotingParallelMessage::GetPendingApprovals(tx).into())
+				.await?
+		} else {
+			self.request(|tx| ApprovalVotingMessage::GetPendingApprovals(tx).into()).await?
+		};
+
+		Ok(pending.into_iter().map(Into::into).collect())
+	}
+
+	async fn availability(
+		&self,
+		ext: &Extensions,
+		candidate_hash: Hash,
+	) -> Result<Availability, Error> {
+		check_if_safe(ext)?;
+
+		let candidate_hash = CandidateHash(candidate_hash);
+		let available_data = self
+			.request(|tx| {
+				AvailabilityStoreMessage::QueryDataAvailability(candidate_hash, tx).into()
+			})
+			.await?;
+		let chunks = self
+			.request(|tx| AvailabilityStoreMessage::QueryAllChunks(candidate_hash, tx).into())
+			.await?
+			.into_iter()
+			.map(|(validator_index, chunk)| Chunk {
+				validator_index: validator_index.0,
+				chunk_index: chunk.index.0,
+			})
+			.collect();
+
+		Ok(Availability { available_data, chunks })
+	}
+
+	async fn active_disputes(&self, ext: &Extensions) -> Result<Vec<Dispute>, Error> {
+		check_if_safe(ext)?;
+
+		let disputes =
+			self.request(|tx| DisputeCoordinatorMessage::ActiveDisputes(tx).into()).await?;
+
+		Ok(disputes
+			.into_iter()
+			.map(|(session, candidate_hash, status)| Dispute {
+				session,
+				candidate_hash: candidate_hash.0,
+				status: status.into(),
+			})
+			.collect())
+	}
+
+	async fn non_viable_leaves(&self, ext: &Extensions) -> Result<Vec<NonViableLeaf>, Error> {
+		check_if_safe(ext)?;
+
+		let leaves = self.request(|tx| ChainSelectionMessage::NonViableLeaves(tx).into()).await?;
+
+		Ok(leaves.into_iter().map(Into::into).collect())
+	}
+}
+
+/// Top-level error type for the RPC handler.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The subsystem didn't answer the request.
+	#[error("The subsystem didn't answer the request, it might not be running")]
+	SubsystemUnavailable,
+	/// Call to an unsafe RPC was denied.
+	#[error(transparent)]
+	UnsafeRpcCalled(#[from] UnsafeRpcError),
+}
+
+impl From<Error> for ErrorObjectOwned {
+	fn from(error: Error) -> Self {
+		match error {
+			Error::SubsystemUnavailable =>
+				ErrorObject::owned(PARACHAINS_ERROR + 1, error.to_string(), None::<()>),
+			Error::UnsafeRpcCalled(e) => e.into(),
+		}
+	}
+}
@@ -2633,6 +2633,12 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_migrations::runtime_api::MigrationsApi<Block> for Runtime {
+		fn status() -> pallet_migrations::MigrationStatus<BlockNumber> {
+			MultiBlockMigrations::status()
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
@@ -3,5 +3,7 @@
 
 mod async_backing_6_seconds_rate;
 mod duplicate_collations;
+mod spam_approval_assignments;
 mod spam_statement_distribution_requests;
 mod sync_backing;
+mod withhold_chunks;
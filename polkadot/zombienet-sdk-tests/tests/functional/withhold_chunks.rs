@@ -0,0 +1,161 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Test if parachains progress and finality doesn't stall when a backer signs availability
+// bitfields but withholds its erasure chunks from the other validators.
+
+use anyhow::anyhow;
+
+use cumulus_zombienet_sdk_helpers::{assert_blocks_are_being_finalized, assert_para_throughput};
+use polkadot_primitives::Id as ParaId;
+use serde_json::json;
+use subxt::{OnlineClient, PolkadotConfig};
+use zombienet_sdk::NetworkConfigBuilder;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn withhold_chunks_test() -> Result<(), anyhow::Error> {
+	let _ = env_logger::try_init_from_env(
+		env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, "info"),
+	);
+
+	let images = zombienet_sdk::environment::get_images_from_env();
+
+	let config = NetworkConfigBuilder::new()
+		.with_relaychain(|r| {
+			r.with_chain("rococo-local")
+				.with_default_command("polkadot")
+				.with_default_image(images.polkadot.as_str())
+				.with_default_args(vec![("-lparachain=debug").into()])
+				.with_genesis_overrides(json!({
+					"configuration": {
+						"config": {
+							"needed_approvals": 2,
+							"scheduler_params": {
+								"max_validators_per_core": 2
+							}
+						}
+					}
+				}))
+				.with_node(|node| node.with_name("honest-0"))
+				.with_node(|node| node.with_name("honest-1"))
+				.with_node(|node| node.with_name("honest-2"))
+				.with_node(|node| node.with_name("honest-3"))
+				.with_node(|node| {
+					node.with_name("malus")
+						.with_image(
+							std::env::var("MALUS_IMAGE")
+								.unwrap_or("docker.io/paritypr/malus".to_string())
+								.as_str(),
+						)
+						.with_command("malus")
+						.with_subcommand("withhold-chunks")
+						.with_args(vec![
+							"--alice".into(),
+							"--percentage=100".into(),
+							"--insecure-validator-i-know-what-i-do".into(),
+							"-lMALUS=trace,parachain=debug".into(),
+						])
+				})
+		})
+		.with_parachain(|p| {
+			p.with_id(2000)
+				.with_default_command("undying-collator")
+				.cumulus_based(false)
+				.with_default_image(
+					std::env::var("COL_IMAGE")
+						.unwrap_or("docker.io/paritypr/colander:latest".to_string())
+						.as_str(),
+				)
+				.with_default_args(vec![("-lparachain=debug").into()])
+				.with_collator(|n| n.with_name("collator-2000"))
+		})
+		.with_parachain(|p| {
+			p.with_id(2001)
+				.with_default_command("undying-collator")
+				.cumulus_based(false)
+				.with_default_image(
+					std::env::var("COL_IMAGE")
+						.unwrap_or("docker.io/paritypr/colander:latest".to_string())
+						.as_str(),
+				)
+				.with_default_args(vec![("-lparachain=debug").into()])
+				.with_collator(|n| n.with_name("collator-2001"))
+		})
+		.build()
+		.map_err(|e| {
+			let errs = e.into_iter().map(|e| e.to_string()).collect::<Vec<_>>().join(" ");
+			anyhow!("config errs: {errs}")
+		})?;
+
+	let spawn_fn = zombienet_sdk::environment::get_spawn_fn();
+	let network = spawn_fn(config).await?;
+
+	let malus = network.get_node("malus")?;
+	let honest = network.get_node("honest-0")?;
+	let relay_client: OnlineClient<PolkadotConfig> = honest.wait_client().await?;
+	let _malus_client: OnlineClient<PolkadotConfig> = malus.wait_client().await?;
+
+	// Check authority status.
+	malus.assert("node_roles", 4.0).await?;
+	honest.assert("node_roles", 4.0).await?;
+
+	// Ensure parachains are registered.
+	assert_para_throughput(
+		&relay_client,
+		2,
+		[(ParaId::from(2000), 2..3), (ParaId::from(2001), 2..3)].into_iter().collect(),
+	)
+	.await?;
+
+	// Ensure that malus is already withholding chunks.
+	malus
+		.wait_log_line_count_with_timeout("*Withholding erasure chunk*", true, 1, 90u64)
+		.await?;
+
+	// Chunks can still be fetched from the honest validators, so parachains keep making progress.
+	assert_para_throughput(
+		&relay_client,
+		10,
+		[(ParaId::from(2000), 7..11), (ParaId::from(2001), 7..11)].into_iter().collect(),
+	)
+	.await?;
+
+	assert_blocks_are_being_finalized(&relay_client).await?;
+
+	let timeout_secs: u64 = 120;
+	// Honest validators notice the missing chunks and fetch them from the other backers.
+	honest
+		.wait_metric_with_timeout(
+			"polkadot_parachain_fetch_retries_total",
+			|r| r > 0.0,
+			timeout_secs,
+		)
+		.await?;
+
+	// Withholding chunks can't be proven, so no dispute is raised against the candidates malus
+	// backed and it doesn't get slashed.
+	honest
+		.wait_metric_with_timeout(
+			"polkadot_parachain_candidate_dispute_concluded{validity=\"invalid\"}",
+			|d| d < 1.0,
+			timeout_secs,
+		)
+		.await?;
+
+	// Check lag - approval
+	honest
+		.assert(
+			"polkadot_parachain_approval_checking_finality_lag{chain=\"rococo_local_testnet\"}",
+			0.0,
+		)
+		.await?;
+
+	// Check lag - dispute conclusion
+	honest
+		.assert("polkadot_parachain_disputes_finality_lag{chain=\"rococo_local_testnet\"}", 0.0)
+		.await?;
+
+	log::info!("Test finished successfully");
+
+	Ok(())
+}
@@ -89,9 +89,15 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export a binary snapshot of the state of a given block.
+	ExportSnapshot(sc_cli::ExportSnapshotCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Bootstrap the database from a state snapshot.
+	ImportSnapshot(sc_cli::ImportSnapshotCmd),
+
 	/// Remove the whole chain.
 	PurgeChain(sc_cli::PurgeChainCmd),
 
@@ -26,7 +26,7 @@ use polkadot_sdk::{
 };
 
 use crate::Cli;
-use codec::Encode;
+use codec::{Decode, Encode};
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
 use frame_system_rpc_runtime_api::AccountNonceApi;
 use futures::prelude::*;
@@ -97,6 +97,27 @@ pub fn fetch_nonce(client: &FullClient, account: sp_core::sr25519::Pair) -> u32
 		.expect("Fetching account nonce works; qed")
 }
 
+/// Classifies the transactions of the transaction pool, so that pool lanes can be matched on the
+/// dispatch class (`normal`, `operational` or `mandatory`) and on the pallet of their call.
+pub fn transaction_classifier() -> sc_transaction_pool::TransactionClassifier {
+	use frame_support::{
+		dispatch::{DispatchClass, GetDispatchInfo},
+		traits::GetCallMetadata,
+	};
+
+	sc_transaction_pool::TransactionClassifier::new(|mut encoded| {
+		let Ok(xt) = kitchensink_runtime::UncheckedExtrinsic::decode(&mut encoded) else {
+			return vec![]
+		};
+		let class = match xt.function.get_dispatch_info().class {
+			DispatchClass::Normal => "normal",
+			DispatchClass::Operational => "operational",
+			DispatchClass::Mandatory => "mandatory",
+		};
+		vec![class.into(), xt.function.get_call_metadata().pallet_name.into()]
+	})
+}
+
 /// Create a transaction using the given `call`.
 ///
 /// The transaction will be signed by `sender`. If `nonce` is `None` it will be fetched from the
@@ -235,7 +256,7 @@ pub fn new_partial(
 			client.clone(),
 			config.role.is_authority().into(),
 		)
-		.with_options(config.transaction_pool.clone())
+		.with_options(config.transaction_pool.clone().with_classifier(transaction_classifier()))
 		.with_prometheus(config.prometheus_registry())
 		.build(),
 	);
@@ -0,0 +1,96 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{BlockNumberOrHash, DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use log::info;
+use sc_client_api::{BlockBackend, HeaderBackend, ProofProvider};
+use sc_service::chain_ops::export_state_snapshot;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{fmt::Debug, path::PathBuf, str::FromStr, sync::Arc};
+
+/// The `export-snapshot` command used to export a chunked binary snapshot of the state of a
+/// given block.
+#[derive(Debug, Clone, Parser)]
+pub struct ExportSnapshotCmd {
+	/// Output file.
+	#[arg(value_name = "FILE")]
+	pub output: PathBuf,
+
+	/// Block hash or number to export the state of.
+	///
+	/// Defaults to the finalized block, or to the block of the snapshot being resumed.
+	#[arg(long, value_name = "HASH or NUMBER")]
+	pub block: Option<BlockNumberOrHash>,
+
+	/// Maximal size of a snapshot chunk in KiB.
+	#[arg(long, value_name = "KiB", default_value_t = 8 * 1024)]
+	pub chunk_size: usize,
+
+	/// Continue an interrupted export from the last valid chunk of the output file.
+	#[arg(long)]
+	pub resume: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExportSnapshotCmd {
+	/// Run the `export-snapshot` command
+	pub async fn run<B, C>(&self, client: Arc<C>) -> error::Result<()>
+	where
+		B: BlockT,
+		C: HeaderBackend<B> + BlockBackend<B> + ProofProvider<B>,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		info!("Exporting state snapshot to {}...", self.output.display());
+		let block_id = self.block.as_ref().map(|b| b.parse()).transpose()?;
+		let hash = block_id.map(|id| client.expect_block_hash_from_id(&id)).transpose()?;
+
+		export_state_snapshot(client, hash, &self.output, self.chunk_size * 1024, self.resume)
+			.map_err(Into::into)
+	}
+}
+
+impl CliConfiguration for ExportSnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,71 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{ImportParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_client_api::HeaderBackend;
+use sc_service::chain_ops::import_state_snapshot;
+use sp_runtime::traits::Block as BlockT;
+use std::{path::PathBuf, sync::Arc};
+
+/// The `import-snapshot` command used to bootstrap the database from a state snapshot.
+///
+/// The snapshot block is imported as finalized together with its state and justifications, the
+/// same way it is done at the end of a warp or state sync.
+#[derive(Debug, Parser)]
+pub struct ImportSnapshotCmd {
+	/// Snapshot file created by the `export-snapshot` command.
+	#[arg(value_name = "FILE")]
+	pub input: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: ImportParams,
+}
+
+impl ImportSnapshotCmd {
+	/// Run the `import-snapshot` command
+	pub async fn run<B, C, IQ>(&self, client: Arc<C>, import_queue: IQ) -> error::Result<()>
+	where
+		C: HeaderBackend<B> + Send + Sync + 'static,
+		B: BlockT,
+		IQ: sc_service::ImportQueue<B> + 'static,
+	{
+		import_state_snapshot(client, import_queue, &self.input)
+			.await
+			.map_err(Into::into)
+	}
+}
+
+impl CliConfiguration for ImportSnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
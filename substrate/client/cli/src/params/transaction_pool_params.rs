@@ -17,7 +17,9 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use clap::{Args, ValueEnum};
-use sc_transaction_pool::TransactionPoolOptions;
+use sc_transaction_pool::{PoolLane, PoolQuotas, TransactionPoolOptions};
+use sp_runtime::transaction_validity::TransactionSource;
+use std::path::PathBuf;
 
 /// Type of transaction pool to be used
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -59,6 +61,36 @@ pub struct TransactionPoolParams {
 	/// The type of transaction pool to be instantiated.
 	#[arg(long, value_enum, default_value_t = TransactionPoolType::SingleState)]
 	pub pool_type: TransactionPoolType,
+
+	/// Path to the on-disk journal of the pending transactions.
+	///
+	/// If set, pending transactions are persisted and restored after the node restart. Only
+	/// supported by the fork-aware transaction pool.
+	#[arg(long, value_name = "PATH")]
+	pub pool_journal_path: Option<PathBuf>,
+
+	/// Maximum number of transactions of a single sender in the transaction pool.
+	///
+	/// Transactions depending on each other, like subsequent nonces of an account, are
+	/// attributed to the same sender. Nonce based transactions of an account are attributed to
+	/// the same sender also when their nonces are not contiguous. Transactions of a lane are not
+	/// limited. The limit is enforced both in the views and in the mempool of the fork-aware
+	/// transaction pool.
+	#[arg(long, value_name = "COUNT")]
+	pub pool_sender_limit: Option<usize>,
+
+	/// Reserve capacity of the ready queue for the matching transactions.
+	///
+	/// Format:
+	/// `NAME=RESERVED[,priority=N][,source=local|external|in-block][,sender=0xHEX][,class=NAME]`.
+	/// A transaction belongs to the lane if its priority is at least `N`, if it was submitted from
+	/// the given source, if it provides a tag starting with the given bytes, which for nonce
+	/// based transactions is the SCALE encoded account of the sender, or if the node assigns it
+	/// the given class, like the dispatch class or the origin of its call. Which classes are
+	/// available depends on the node. The criteria can be repeated. Can be passed multiple
+	/// times, a transaction belongs to the first matching lane.
+	#[arg(long, value_name = "LANE", value_parser = parse_pool_lane)]
+	pub pool_lane: Vec<PoolLane>,
 }
 
 impl TransactionPoolParams {
@@ -71,5 +103,78 @@ impl TransactionPoolParams {
 			self.pool_type.into(),
 			is_dev,
 		)
+		.with_journal_path(self.pool_journal_path.clone())
+		.with_quotas(PoolQuotas {
+			max_per_sender: self.pool_sender_limit,
+			lanes: self.pool_lane.clone(),
+			classifier: None,
+		})
+	}
+}
+
+fn parse_pool_lane(s: &str) -> Result<PoolLane, String> {
+	let mut parts = s.split(',');
+	let (name, reserved) = parts
+		.next()
+		.and_then(|lane| lane.split_once('='))
+		.ok_or_else(|| format!("Expected `NAME=RESERVED`, got `{s}`"))?;
+	let mut lane = PoolLane {
+		name: name.into(),
+		reserved: reserved
+			.parse()
+			.map_err(|e| format!("Invalid reserved capacity `{reserved}`: {e}"))?,
+		..Default::default()
+	};
+
+	for criterion in parts {
+		match criterion.split_once('=') {
+			Some(("priority", priority)) => {
+				let priority =
+					priority.parse().map_err(|e| format!("Invalid priority `{priority}`: {e}"))?;
+				lane.min_priority = Some(lane.min_priority.map_or(priority, |p| p.min(priority)));
+			},
+			Some(("source", "local")) => lane.sources.push(TransactionSource::Local),
+			Some(("source", "external")) => lane.sources.push(TransactionSource::External),
+			Some(("source", "in-block")) => lane.sources.push(TransactionSource::InBlock),
+			Some(("sender", sender)) => lane.tag_prefixes.push(
+				array_bytes::hex2bytes(sender)
+					.map_err(|e| format!("Invalid sender `{sender}`: {e:?}"))?,
+			),
+			Some(("class", class)) if !class.is_empty() => lane.classes.push(class.into()),
+			_ => return Err(format!("Unknown lane criterion `{criterion}`")),
+		}
+	}
+
+	Ok(lane)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_pool_lane_works() {
+		assert_eq!(
+			parse_pool_lane(
+				"governance=32,priority=1000,source=local,sender=0x0102,class=operational"
+			)
+			.unwrap(),
+			PoolLane {
+				name: "governance".into(),
+				reserved: 32,
+				min_priority: Some(1000),
+				sources: vec![TransactionSource::Local],
+				tag_prefixes: vec![vec![1, 2]],
+				classes: vec!["operational".into()],
+			}
+		);
+		assert_eq!(
+			parse_pool_lane("ops=8").unwrap(),
+			PoolLane { name: "ops".into(), reserved: 8, ..Default::default() }
+		);
+		assert!(parse_pool_lane("ops").is_err());
+		assert!(parse_pool_lane("ops=8,source=nowhere").is_err());
+		assert!(parse_pool_lane("ops=8,sender=0xzz").is_err());
+		assert!(parse_pool_lane("ops=8,class=").is_err());
 	}
 }
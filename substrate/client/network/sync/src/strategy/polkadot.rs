@@ -26,6 +26,7 @@ use crate::{
 	strategy::{
 		chain_sync::{ChainSync, ChainSyncMode},
 		state::StateStrategy,
+		state_sync::{self, StateSync},
 		warp::{WarpSync, WarpSyncConfig},
 		StrategyKey, SyncingAction, SyncingStrategy,
 	},
@@ -34,13 +35,16 @@ use crate::{
 };
 use log::{debug, error, info, warn};
 use prometheus_endpoint::Registry;
-use sc_client_api::{BlockBackend, ProofProvider};
+use sc_client_api::{AuxStore, BlockBackend, ProofProvider};
 use sc_consensus::{BlockImportError, BlockImportStatus};
 use sc_network::ProtocolName;
 use sc_network_common::sync::{message::BlockAnnounce, SyncMode};
 use sc_network_types::PeerId;
 use sp_blockchain::{Error as ClientError, HeaderBackend, HeaderMetadata};
-use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
+use sp_runtime::{
+	traits::{Block as BlockT, Header, NumberFor},
+	Justifications,
+};
 use std::{any::Any, collections::HashMap, sync::Arc};
 
 /// Corresponding `ChainSync` mode.
@@ -97,6 +101,7 @@ where
 		+ BlockBackend<B>
 		+ HeaderMetadata<B, Error = sp_blockchain::Error>
 		+ ProofProvider<B>
+		+ AuxStore
 		+ Send
 		+ Sync
 		+ 'static,
@@ -333,6 +338,7 @@ where
 		+ BlockBackend<B>
 		+ HeaderMetadata<B, Error = sp_blockchain::Error>
 		+ ProofProvider<B>
+		+ AuxStore
 		+ Send
 		+ Sync
 		+ 'static,
@@ -353,6 +359,32 @@ where
 		}
 
 		if let SyncMode::Warp = config.mode {
+			if let Some((target_header, target_body, target_justifications)) =
+				state_sync::resumable_target::<B>(&*client)
+			{
+				info!(
+					target: LOG_TARGET,
+					"Resuming the interrupted state sync of #{}, skipping warp sync.",
+					target_header.number(),
+				);
+				let state_sync = Self::state_strategy(
+					&config,
+					&client,
+					target_header,
+					target_body,
+					target_justifications,
+					std::iter::empty(),
+				);
+				return Ok(Self {
+					config,
+					client,
+					warp: None,
+					state: Some(state_sync),
+					chain_sync: None,
+					peer_best_blocks: Default::default(),
+				})
+			}
+
 			let warp_sync_config = warp_sync_config
 				.expect("Warp sync configuration must be supplied in warp sync mode.");
 			let warp_sync = WarpSync::new(
@@ -391,6 +423,30 @@ where
 		}
 	}
 
+	/// Create the state strategy persisting its progress to the client's auxiliary storage.
+	fn state_strategy(
+		config: &PolkadotSyncingStrategyConfig<B>,
+		client: &Arc<Client>,
+		target_header: B::Header,
+		target_body: Option<Vec<B::Extrinsic>>,
+		target_justifications: Option<Justifications>,
+		initial_peers: impl Iterator<Item = (PeerId, NumberFor<B>)>,
+	) -> StateStrategy<B> {
+		let state_sync = StateSync::new(
+			client.clone(),
+			target_header,
+			target_body,
+			target_justifications,
+			false,
+		)
+		.with_journal(client.clone());
+		StateStrategy::new_with_provider(
+			Box::new(state_sync),
+			initial_peers,
+			config.state_request_protocol_name.clone(),
+		)
+	}
+
 	/// Proceed with the next strategy if the active one finished.
 	pub fn proceed_to_next(&mut self) -> Result<(), ClientError> {
 		// The strategies are switched as `WarpSync` -> `StateStrategy` -> `ChainSync`.
@@ -401,16 +457,15 @@ where
 						target: LOG_TARGET,
 						"Warp sync is complete, continuing with state sync."
 					);
-					let state_sync = StateStrategy::new(
-						self.client.clone(),
+					let state_sync = Self::state_strategy(
+						&self.config,
+						&self.client,
 						res.target_header,
 						res.target_body,
 						res.target_justifications,
-						false,
 						self.peer_best_blocks
 							.iter()
 							.map(|(peer_id, (_, best_number))| (*peer_id, *best_number)),
-						self.config.state_request_protocol_name.clone(),
 					);
 
 					self.warp = None;
@@ -452,6 +507,8 @@ where
 			} else {
 				error!(target: LOG_TARGET, "State sync failed. Falling back to full sync.");
 			}
+			// Neither the imported state nor the failed one are to be resumed.
+			state_sync::discard_progress::<B>(&*self.client);
 			let chain_sync = match ChainSync::new(
 				chain_sync_mode(self.config.mode),
 				self.client.clone(),
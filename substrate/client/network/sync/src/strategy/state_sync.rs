@@ -23,8 +23,8 @@ use crate::{
 	LOG_TARGET,
 };
 use codec::{Decode, Encode};
-use log::debug;
-use sc_client_api::{CompactProof, KeyValueStates, ProofProvider};
+use log::{debug, info, warn};
+use sc_client_api::{AuxStore, CompactProof, KeyValueStates, ProofProvider};
 use sc_consensus::ImportedState;
 use smallvec::SmallVec;
 use sp_core::storage::well_known_keys;
@@ -32,7 +32,110 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor},
 	Justifications,
 };
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+	collections::HashMap,
+	fmt,
+	sync::Arc,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Aux storage key of the state sync checkpoint.
+const CHECKPOINT_KEY: &[u8] = b"state_sync_checkpoint";
+
+/// Aux storage key prefix of the downloaded state chunks, followed by the chunk index.
+const CHUNK_KEY_PREFIX: &[u8] = b"state_sync_chunk";
+
+/// Checkpoints older than this are discarded.
+///
+/// Peers only keep the state of recent blocks, so the state of an older target is unlikely to be
+/// served anymore.
+const MAX_CHECKPOINT_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// Storage used to persist the progress of the state sync.
+///
+/// Implemented for every [`AuxStore`], the progress is then kept in the auxiliary data of the
+/// database.
+pub trait StateSyncJournal: Send + Sync {
+	/// Returns the value stored under `key`.
+	fn read(&self, key: &[u8]) -> Option<Vec<u8>>;
+	/// Atomically inserts and deletes the given values.
+	fn write(&self, insert: &[(&[u8], &[u8])], delete: &[&[u8]]);
+}
+
+impl<T: AuxStore + Send + Sync> StateSyncJournal for T {
+	fn read(&self, key: &[u8]) -> Option<Vec<u8>> {
+		self.get_aux(key)
+			.map_err(|e| warn!(target: LOG_TARGET, "Failed to read state sync progress: {e}"))
+			.ok()
+			.flatten()
+	}
+
+	fn write(&self, insert: &[(&[u8], &[u8])], delete: &[&[u8]]) {
+		if let Err(e) = self.insert_aux(insert, delete) {
+			warn!(target: LOG_TARGET, "Failed to persist state sync progress: {e}");
+		}
+	}
+}
+
+/// Progress of the state sync, persisted after every verified chunk.
+#[derive(Encode, Decode)]
+struct Checkpoint<B: BlockT> {
+	target_header: B::Header,
+	target_body: Option<Vec<B::Extrinsic>>,
+	target_justifications: Option<Justifications>,
+	last_key: Vec<Vec<u8>>,
+	imported_bytes: u64,
+	chunks: u32,
+	/// Unix time in seconds.
+	saved_at: u64,
+}
+
+/// Key values of a single verified state response, for each state root.
+type Chunk = Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)>;
+
+fn chunk_key(index: u32) -> Vec<u8> {
+	(CHUNK_KEY_PREFIX, index).encode()
+}
+
+fn unix_time() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs())
+}
+
+fn read_checkpoint<B: BlockT>(journal: &dyn StateSyncJournal) -> Option<Checkpoint<B>> {
+	let encoded = journal.read(CHECKPOINT_KEY)?;
+	Checkpoint::decode(&mut &encoded[..])
+		.map_err(|e| warn!(target: LOG_TARGET, "Failed to decode state sync checkpoint: {e}"))
+		.ok()
+}
+
+/// Returns the target of an interrupted state sync which can be resumed.
+///
+/// The returned target header, body and justifications are those the interrupted state sync was
+/// started with.
+pub fn resumable_target<B: BlockT>(
+	journal: &dyn StateSyncJournal,
+) -> Option<(B::Header, Option<Vec<B::Extrinsic>>, Option<Justifications>)> {
+	let checkpoint = read_checkpoint::<B>(journal)?;
+	if unix_time().saturating_sub(checkpoint.saved_at) > MAX_CHECKPOINT_AGE.as_secs() {
+		debug!(
+			target: LOG_TARGET,
+			"Not resuming state sync of #{}, the progress is too old",
+			checkpoint.target_header.number(),
+		);
+		return None
+	}
+	Some((checkpoint.target_header, checkpoint.target_body, checkpoint.target_justifications))
+}
+
+/// Removes the persisted progress of the state sync.
+pub fn discard_progress<B: BlockT>(journal: &dyn StateSyncJournal) {
+	let chunks = read_checkpoint::<B>(journal).map_or(0, |checkpoint| checkpoint.chunks);
+	let keys = (0..chunks).map(chunk_key).collect::<Vec<_>>();
+	let delete = std::iter::once(CHECKPOINT_KEY)
+		.chain(keys.iter().map(|key| key.as_slice()))
+		.collect::<Vec<_>>();
+	journal.write(&[], &delete);
+}
 
 /// Generic state sync provider. Used for mocking in tests.
 pub trait StateSyncProvider<B: BlockT>: Send + Sync {
@@ -143,6 +246,8 @@ pub struct StateSync<B: BlockT, Client> {
 	metadata: StateSyncMetadata<B>,
 	state: HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>,
 	client: Arc<Client>,
+	journal: Option<Arc<dyn StateSyncJournal>>,
+	chunks: u32,
 }
 
 impl<B, Client> StateSync<B, Client>
@@ -170,7 +275,85 @@ where
 				skip_proof,
 			},
 			state: HashMap::default(),
+			journal: None,
+			chunks: 0,
+		}
+	}
+
+	/// Persist the progress to `journal` and resume from the progress already stored there.
+	///
+	/// The stored progress is only resumed if it was made towards the same target block, it is
+	/// discarded otherwise. Only verified chunks are persisted, so this has no effect if proofs are
+	/// skipped.
+	pub fn with_journal(mut self, journal: Arc<dyn StateSyncJournal>) -> Self {
+		if self.metadata.skip_proof {
+			return self
 		}
+
+		match read_checkpoint::<B>(&*journal) {
+			Some(checkpoint) if checkpoint.target_header == self.metadata.target_header => {
+				let chunks = (0..checkpoint.chunks)
+					.map(|index| {
+						let encoded = journal.read(&chunk_key(index))?;
+						Chunk::decode(&mut &encoded[..]).ok()
+					})
+					.collect::<Option<Vec<_>>>();
+
+				match chunks {
+					Some(chunks) => {
+						for chunk in chunks {
+							for (state_root, key_values) in chunk {
+								self.process_state_key_values(state_root, key_values);
+							}
+						}
+						self.metadata.last_key = checkpoint.last_key.into();
+						self.metadata.imported_bytes = checkpoint.imported_bytes;
+						self.chunks = checkpoint.chunks;
+						info!(
+							target: LOG_TARGET,
+							"Resuming state sync of #{} after {} downloaded chunks",
+							self.metadata.target_number(),
+							self.chunks,
+						);
+					},
+					None => {
+						warn!(target: LOG_TARGET, "State sync progress is corrupted, starting over");
+						discard_progress::<B>(&*journal);
+					},
+				}
+			},
+			Some(_) => discard_progress::<B>(&*journal),
+			None => {},
+		}
+
+		self.journal = Some(journal);
+		self
+	}
+
+	/// Persist the verified chunk together with the updated key cursor.
+	fn persist_chunk(&mut self, values: &KeyValueStates) {
+		let Some(journal) = &self.journal else { return };
+
+		let chunk = values
+			.0
+			.iter()
+			.map(|level| (&level.state_root, &level.key_values))
+			.collect::<Vec<_>>()
+			.encode();
+		let checkpoint = Checkpoint::<B> {
+			target_header: self.metadata.target_header.clone(),
+			target_body: self.metadata.target_body.clone(),
+			target_justifications: self.metadata.target_justifications.clone(),
+			last_key: self.metadata.last_key.to_vec(),
+			imported_bytes: self.metadata.imported_bytes,
+			chunks: self.chunks + 1,
+			saved_at: unix_time(),
+		}
+		.encode();
+
+		let key = chunk_key(self.chunks);
+		journal.write(&[(&key[..], &chunk[..]), (CHECKPOINT_KEY, &checkpoint[..])], &[]);
+		self.chunks += 1;
 	}
 
 	fn process_state_key_values(
@@ -295,8 +478,13 @@ where
 				debug!(target: LOG_TARGET, "Error updating key cursor, depth: {}", completed);
 			};
 
-			self.process_state_verified(values);
 			self.metadata.imported_bytes += proof_size;
+			// The last chunk is not persisted, the progress is discarded once the state is
+			// imported.
+			if !complete {
+				self.persist_chunk(&values);
+			}
+			self.process_state_verified(values);
 			complete
 		} else {
 			self.process_state_unverified(response)
@@ -341,3 +529,122 @@ where
 		self.metadata.progress()
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::schema::v1::{StateRequest, StateResponse};
+	use sp_blockchain::HeaderBackend;
+	use std::sync::Mutex;
+	use substrate_test_runtime_client::{
+		runtime::{Block, Hash},
+		DefaultTestClientBuilderExt, TestClient, TestClientBuilder, TestClientBuilderExt,
+	};
+
+	#[derive(Default)]
+	struct Journal(Mutex<HashMap<Vec<u8>, Vec<u8>>>);
+
+	impl AuxStore for Journal {
+		fn insert_aux<
+			'a,
+			'b: 'a,
+			'c: 'a,
+			I: IntoIterator<Item = &'a (&'c [u8], &'c [u8])>,
+			D: IntoIterator<Item = &'a &'b [u8]>,
+		>(
+			&self,
+			insert: I,
+			delete: D,
+		) -> sp_blockchain::Result<()> {
+			let mut values = self.0.lock().unwrap();
+			for (key, value) in insert {
+				values.insert(key.to_vec(), value.to_vec());
+			}
+			for key in delete {
+				values.remove(*key);
+			}
+			Ok(())
+		}
+
+		fn get_aux(&self, key: &[u8]) -> sp_blockchain::Result<Option<Vec<u8>>> {
+			Ok(self.0.lock().unwrap().get(key).cloned())
+		}
+	}
+
+	fn respond(client: &TestClient, request: &StateRequest) -> StateResponse {
+		let hash = Hash::decode(&mut &request.block[..]).unwrap();
+		let (proof, _) = client.read_proof_collection(hash, &request.start, 256).unwrap();
+		StateResponse { entries: Vec::new(), proof: proof.encode() }
+	}
+
+	fn import_all(
+		client: &TestClient,
+		state_sync: &mut StateSync<Block, TestClient>,
+	) -> Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)> {
+		loop {
+			match state_sync.import(respond(client, &state_sync.next_request())) {
+				ImportResult::Import(_, _, state, _, _) => {
+					let mut levels = state
+						.state
+						.0
+						.into_iter()
+						.map(|level| (level.state_root, level.key_values))
+						.collect::<Vec<_>>();
+					levels.sort();
+					return levels
+				},
+				ImportResult::Continue => {},
+				ImportResult::BadResponse => panic!("Bad state response"),
+			}
+		}
+	}
+
+	#[test]
+	fn state_sync_is_resumed_from_journal() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let target_header = client.header(client.info().genesis_hash).unwrap().unwrap();
+		let journal = Arc::new(Journal::default());
+		let new_state_sync =
+			|| StateSync::new(client.clone(), target_header.clone(), None, None, false);
+
+		let mut state_sync = new_state_sync().with_journal(journal.clone());
+		for _ in 0..2 {
+			let response = respond(&client, &state_sync.next_request());
+			assert!(matches!(state_sync.import(response), ImportResult::Continue));
+		}
+		assert_eq!(
+			resumable_target::<Block>(&*journal).map(|(header, _, _)| header),
+			Some(target_header.clone())
+		);
+
+		// restart
+		let mut resumed = new_state_sync().with_journal(journal.clone());
+		assert_eq!(resumed.chunks, 2);
+		assert_eq!(resumed.next_request(), state_sync.next_request());
+		assert_eq!(resumed.progress(), state_sync.progress());
+
+		assert_eq!(import_all(&client, &mut resumed), import_all(&client, &mut new_state_sync()));
+	}
+
+	#[test]
+	fn journal_of_other_target_is_discarded() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let target_header = client.header(client.info().genesis_hash).unwrap().unwrap();
+		let journal = Arc::new(Journal::default());
+
+		let mut state_sync =
+			StateSync::new(client.clone(), target_header.clone(), None, None, false)
+				.with_journal(journal.clone());
+		let response = respond(&client, &state_sync.next_request());
+		assert!(matches!(state_sync.import(response), ImportResult::Continue));
+		assert!(!journal.0.lock().unwrap().is_empty());
+
+		let mut other_header = target_header;
+		other_header.number += 1;
+		let state_sync = StateSync::new(client.clone(), other_header, None, None, false)
+			.with_journal(journal.clone());
+
+		assert_eq!(state_sync.chunks, 0);
+		assert!(journal.0.lock().unwrap().is_empty());
+	}
+}
@@ -48,6 +48,9 @@ pub enum Error {
 	/// Invalid session keys encoding.
 	#[error("Session keys are not encoded correctly")]
 	InvalidSessionKeys,
+	/// No watched extrinsic with the given hash was restored, or it is watched already.
+	#[error("No restored extrinsic to watch with the given hash")]
+	NotRestored,
 	/// Call to an unsafe RPC was denied.
 	#[error(transparent)]
 	UnsafeRpcCalled(#[from] crate::policy::UnsafeRpcError),
@@ -76,4 +76,15 @@ pub trait AuthorApi<Hash, BlockHash> {
 		item = TransactionStatus<Hash, BlockHash>,
 	)]
 	fn watch_extrinsic(&self, bytes: Bytes);
+
+	/// Re-attach to the status updates of an extrinsic submitted to watch before the node
+	/// restarted, restored by the transaction pool from its journal.
+	///
+	/// The updates of an extrinsic can be watched only once.
+	#[subscription(
+		name = "author_watchRestoredExtrinsic" => "author_extrinsicUpdate",
+		unsubscribe = "author_unwatchRestoredExtrinsic",
+		item = TransactionStatus<Hash, BlockHash>,
+	)]
+	fn watch_restored_extrinsic(&self, hash: Hash);
 }
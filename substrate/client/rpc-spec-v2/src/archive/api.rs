@@ -21,7 +21,8 @@
 use crate::{
 	archive::error::{Error, Infallible},
 	common::events::{
-		ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageEvent, StorageQuery,
+		ArchiveBlockRangeEvent, ArchiveStorageDiffEvent, ArchiveStorageDiffItem,
+		ArchiveStorageEvent, StorageQuery,
 	},
 	MethodResult,
 };
@@ -128,4 +129,21 @@ pub trait ArchiveApi<Hash> {
 		items: Vec<ArchiveStorageDiffItem<String>>,
 		previous_hash: Option<Hash>,
 	);
+
+	/// Streams the finalized blocks from the given height range.
+	///
+	/// For every block of the inclusive `[start_height, end_height]` range a `block` event is
+	/// generated, containing the header, the body and the `System::Events` storage of the block.
+	/// The number of blocks reported by a single subscription is limited. If the range exceeds
+	/// the limit, the `blockRangeDone` event contains the height the next page starts at.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and can change in minor or patch releases.
+	#[subscription(
+		name = "archive_v1_blockRange" => "archive_v1_blockRangeEvent",
+		unsubscribe = "archive_v1_stopBlockRange",
+		item = ArchiveBlockRangeEvent,
+	)]
+	fn archive_v1_block_range(&self, start_height: u64, end_height: u64);
 }
@@ -26,7 +26,8 @@ use crate::{
 	},
 	common::{
 		events::{
-			ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageEvent, StorageQuery,
+			ArchiveBlock, ArchiveBlockRangeEvent, ArchiveStorageDiffEvent, ArchiveStorageDiffItem,
+			ArchiveStorageEvent, StorageQuery,
 		},
 		storage::{QueryResult, StorageSubscriptionClient},
 	},
@@ -45,7 +46,7 @@ use sp_api::{CallApiAt, CallContext};
 use sp_blockchain::{
 	Backend as BlockChainBackend, Error as BlockChainError, HeaderBackend, HeaderMetadata,
 };
-use sp_core::{Bytes, U256};
+use sp_core::{twox_128, Bytes, U256};
 use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT, NumberFor},
 	SaturatedConversion,
@@ -62,6 +63,9 @@ pub(crate) const LOG_TARGET: &str = "rpc-spec-v2::archive";
 /// its down buffer capacity per connection as well.
 const STORAGE_QUERY_BUF: usize = 16;
 
+/// The maximum number of blocks reported by a single `archive_v1_blockRange` subscription.
+const MAX_BLOCK_RANGE_PAGE_SIZE: u64 = 256;
+
 /// An API for archive RPC calls.
 pub struct Archive<BE: Backend<Block>, Block: BlockT, Client> {
 	/// Substrate client.
@@ -101,6 +105,49 @@ fn parse_hex_param(param: String) -> Result<Vec<u8>, ArchiveError> {
 	array_bytes::hex2bytes(&param).map_err(|_| ArchiveError::InvalidParam(param))
 }
 
+/// The storage key of the `System::Events` storage item.
+fn system_events_key() -> StorageKey {
+	StorageKey([twox_128(b"System"), twox_128(b"Events")].concat())
+}
+
+/// Fetches the finalized block at the given height, together with its `System::Events` storage.
+fn fetch_archive_block<BE, Block, Client>(
+	client: &Client,
+	height: u64,
+	events_key: &StorageKey,
+) -> Result<ArchiveBlock, String>
+where
+	Block: BlockT,
+	BE: Backend<Block>,
+	Client: BlockBackend<Block> + HeaderBackend<Block> + StorageProvider<Block, BE>,
+{
+	let number: NumberFor<Block> = U256::from(height)
+		.try_into()
+		.map_err(|_| format!("Invalid block height: {height}"))?;
+	let hash = client
+		.block_hash(number)
+		.map_err(|error| error.to_string())?
+		.ok_or_else(|| format!("Block at height {height} was not found"))?;
+	let block = client
+		.block(hash)
+		.map_err(|error| error.to_string())?
+		.ok_or_else(|| format!("Block {hash} was not found"))?
+		.block;
+	let events = client.storage(hash, events_key).map_err(|error| error.to_string())?;
+
+	Ok(ArchiveBlock {
+		number: height,
+		hash: hex_string(&hash.as_ref()),
+		header: hex_string(&block.header().encode()),
+		body: block
+			.extrinsics()
+			.iter()
+			.map(|extrinsic| hex_string(&extrinsic.encode()))
+			.collect(),
+		events: events.map(|events| hex_string(&events.0)),
+	})
+}
+
 #[async_trait]
 impl<BE, Block, Client> ArchiveApiServer<Block::Hash> for Archive<BE, Block, Client>
 where
@@ -305,6 +352,59 @@ where
 
 		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
 	}
+
+	fn archive_v1_block_range(
+		&self,
+		pending: PendingSubscriptionSink,
+		start_height: u64,
+		end_height: u64,
+	) {
+		let client = self.client.clone();
+
+		log::trace!(
+			target: LOG_TARGET,
+			"Block range subscription started: {start_height}..={end_height}"
+		);
+
+		let fut = async move {
+			let Ok(mut sink) = pending.accept().await.map(Subscription::from) else { return };
+
+			let finalized_height: u64 = client.info().finalized_number.saturated_into();
+			if start_height > end_height || end_height > finalized_height {
+				let message = format!(
+					"Invalid block range {start_height}..={end_height}, finalized height is \
+					 {finalized_height}"
+				);
+				let _ = sink.send(&ArchiveBlockRangeEvent::err(message)).await;
+				return
+			}
+
+			let last_height =
+				end_height.min(start_height.saturating_add(MAX_BLOCK_RANGE_PAGE_SIZE - 1));
+			let events_key = system_events_key();
+
+			for height in start_height..=last_height {
+				let event = match fetch_archive_block::<BE, _, _>(&*client, height, &events_key) {
+					Ok(block) => ArchiveBlockRangeEvent::Block(block),
+					Err(error) => {
+						let _ = sink.send(&ArchiveBlockRangeEvent::err(error)).await;
+						return
+					},
+				};
+
+				// The sink applies backpressure: the next block is fetched only once the
+				// previous one was accepted by the connection buffer.
+				if sink.send(&event).await.is_err() {
+					return
+				}
+			}
+
+			let next_height = (last_height < end_height).then(|| last_height + 1);
+			let _ = sink.send(&ArchiveBlockRangeEvent::done(next_height)).await;
+		};
+
+		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+	}
 }
 
 /// Sends all the events of the storage_diff method to the sink.
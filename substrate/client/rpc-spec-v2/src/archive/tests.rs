@@ -18,9 +18,9 @@
 
 use crate::{
 	common::events::{
-		ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageDiffOperationType,
-		ArchiveStorageDiffResult, ArchiveStorageDiffType, ArchiveStorageEvent, StorageQuery,
-		StorageQueryType, StorageResult, StorageResultType,
+		ArchiveBlockRangeEvent, ArchiveStorageDiffEvent, ArchiveStorageDiffItem,
+		ArchiveStorageDiffOperationType, ArchiveStorageDiffResult, ArchiveStorageDiffType,
+		ArchiveStorageEvent, StorageQuery, StorageQueryType, StorageResult, StorageResultType,
 	},
 	hex_string, MethodResult,
 };
@@ -35,11 +35,11 @@ use jsonrpsee::{
 };
 
 use sc_block_builder::BlockBuilderBuilder;
-use sc_client_api::ChildInfo;
+use sc_client_api::{ChildInfo, StorageKey, StorageProvider};
 use sc_rpc::testing::TokioTestExecutor;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::BlockOrigin;
-use sp_core::{Blake2Hasher, Hasher};
+use sp_core::{twox_128, Blake2Hasher, Hasher};
 use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT},
 	SaturatedConversion,
@@ -998,3 +998,79 @@ async fn archive_storage_diff_invalid_params() {
 		ArchiveStorageDiffEvent::StorageDiffError(ref err) if err.error.contains("Header was not found")
 	);
 }
+
+#[tokio::test]
+async fn archive_block_range() {
+	let (client, api) = setup_api();
+
+	// Block tree:
+	// genesis -> block 1 -> block 2 (finalized) -> block 3
+	let mut parent_hash = client.chain_info().genesis_hash;
+	let mut blocks = Vec::new();
+	for number in 0..3 {
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(parent_hash)
+			.with_parent_block_number(number)
+			.build()
+			.unwrap();
+		builder
+			.push_transfer(runtime::Transfer {
+				from: Sr25519Keyring::Alice.into(),
+				to: Sr25519Keyring::Ferdie.into(),
+				amount: 42,
+				nonce: number,
+			})
+			.unwrap();
+		let block = builder.build().unwrap().block;
+		parent_hash = block.header.hash();
+		client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+		blocks.push(block);
+	}
+	client.finalize_block(blocks[1].header.hash(), None).unwrap();
+
+	let mut sub = api
+		.subscribe_unbounded("archive_v1_blockRange", rpc_params![1, 2])
+		.await
+		.unwrap();
+
+	let events_key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+	for (number, block) in (1..).zip(&blocks[..2]) {
+		let hash = block.header.hash();
+		let expected_events = client.storage(hash, &events_key).unwrap().map(|e| hex_string(&e.0));
+
+		let event = get_next_event::<ArchiveBlockRangeEvent>(&mut sub).await;
+		assert_matches!(event,
+			ArchiveBlockRangeEvent::Block(ref archive_block) if archive_block.number == number &&
+				archive_block.hash == format!("{:?}", hash) &&
+				archive_block.header == hex_string(&block.header.encode()) &&
+				archive_block.body == vec![hex_string(&block.extrinsics[0].encode())] &&
+				archive_block.events == expected_events
+		);
+	}
+
+	let event = get_next_event::<ArchiveBlockRangeEvent>(&mut sub).await;
+	assert_eq!(event, ArchiveBlockRangeEvent::done(None));
+}
+
+#[tokio::test]
+async fn archive_block_range_invalid_params() {
+	let (_, api) = setup_api();
+
+	// The end of the range is above the finalized height.
+	let mut sub = api
+		.subscribe_unbounded("archive_v1_blockRange", rpc_params![0, 1])
+		.await
+		.unwrap();
+	let event = get_next_event::<ArchiveBlockRangeEvent>(&mut sub).await;
+	assert_matches!(event,
+		ArchiveBlockRangeEvent::BlockRangeError(ref err) if err.error.contains("Invalid block range")
+	);
+
+	// The start of the range is above its end.
+	let mut sub = api
+		.subscribe_unbounded("archive_v1_blockRange", rpc_params![1, 0])
+		.await
+		.unwrap();
+	let event = get_next_event::<ArchiveBlockRangeEvent>(&mut sub).await;
+	assert!(event.is_err());
+}
@@ -262,6 +262,74 @@ impl ArchiveStorageDiffEvent {
 	}
 }
 
+/// A finalized block reported by the `archive_blockRange` method.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveBlock {
+	/// The height of the block.
+	pub number: u64,
+	/// The hex-encoded hash of the block.
+	pub hash: String,
+	/// The hex-encoded SCALE-encoded header of the block.
+	pub header: String,
+	/// The hex-encoded SCALE-encoded extrinsics of the block.
+	pub body: Vec<String>,
+	/// The hex-encoded value of the `System::Events` storage item at the block.
+	///
+	/// `None` if the storage item is not present.
+	pub events: Option<String>,
+}
+
+/// The `blockRangeDone` event of the `archive_blockRange` method.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveBlockRangeDone {
+	/// The height the next page of the requested range starts at.
+	///
+	/// `None` if all the blocks of the requested range were reported.
+	pub next_height: Option<u64>,
+}
+
+/// The event generated by the `archive_blockRange` method.
+///
+/// The `archive_blockRange` can generate the following events:
+///  - `block` event - generated for every block of the requested range, in ascending order.
+///  - `blockRangeError` event - generated when an error is produced.
+///  - `blockRangeDone` event - generated when the `archive_blockRange` method completed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum ArchiveBlockRangeEvent {
+	/// The `block` event.
+	Block(ArchiveBlock),
+	/// The `blockRangeError` event.
+	BlockRangeError(ArchiveStorageMethodErr),
+	/// The `blockRangeDone` event.
+	BlockRangeDone(ArchiveBlockRangeDone),
+}
+
+impl ArchiveBlockRangeEvent {
+	/// Create a new `ArchiveBlockRangeEvent::BlockRangeError` event.
+	pub fn err(error: String) -> Self {
+		Self::BlockRangeError(ArchiveStorageMethodErr { error })
+	}
+
+	/// Create a new `ArchiveBlockRangeEvent::BlockRangeDone` event.
+	pub fn done(next_height: Option<u64>) -> Self {
+		Self::BlockRangeDone(ArchiveBlockRangeDone { next_height })
+	}
+
+	/// Checks if the event is a `BlockRangeDone` event.
+	pub fn is_done(&self) -> bool {
+		matches!(self, Self::BlockRangeDone(_))
+	}
+
+	/// Checks if the event is a `BlockRangeError` event.
+	pub fn is_err(&self) -> bool {
+		matches!(self, Self::BlockRangeError(_))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -503,4 +571,31 @@ mod tests {
 		let dec: PaginatedStorageQuery<&str> = serde_json::from_str(exp).unwrap();
 		assert_eq!(dec, item);
 	}
+
+	#[test]
+	fn archive_block_range_event() {
+		let event = ArchiveBlockRangeEvent::Block(ArchiveBlock {
+			number: 1,
+			hash: "0x1".into(),
+			header: "0x2".into(),
+			body: vec!["0x3".into()],
+			events: None,
+		});
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"block","number":1,"hash":"0x1","header":"0x2","body":["0x3"],"events":null}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveBlockRangeEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+
+		let event = ArchiveBlockRangeEvent::done(Some(10));
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"blockRangeDone","nextHeight":10}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveBlockRangeEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+	}
 }
@@ -212,4 +212,15 @@ where
 
 		spawn_subscription_task(&self.executor, fut);
 	}
+
+	fn watch_restored_extrinsic(&self, pending: PendingSubscriptionSink, hash: TxHash<P>) {
+		let Some(stream) = self.pool.watch_restored_transaction(&hash) else {
+			spawn_subscription_task(&self.executor, pending.reject(Error::NotRestored));
+			return
+		};
+
+		let fut =
+			PendingSubscription::from(pending).pipe_from_stream(stream, BoundedVecDeque::default());
+		spawn_subscription_task(&self.executor, fut);
+	}
 }
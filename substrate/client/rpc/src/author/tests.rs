@@ -168,6 +168,19 @@ async fn author_should_return_watch_validation_error() {
 	);
 }
 
+#[tokio::test]
+async fn author_should_not_watch_unknown_restored_extrinsic() {
+	let api = TestSetup::into_rpc();
+	let failed_sub = api
+		.subscribe_unbounded("author_watchRestoredExtrinsic", [H256::repeat_byte(1)])
+		.await;
+
+	assert_matches!(
+		failed_sub,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("No restored extrinsic")
+	);
+}
+
 #[tokio::test]
 async fn author_should_return_pending_extrinsics() {
 	let api = TestSetup::into_rpc();
@@ -31,7 +31,7 @@ use log::info;
 use prometheus_endpoint::Registry;
 use sc_chain_spec::{get_extension, ChainSpec};
 use sc_client_api::{
-	execution_extensions::ExecutionExtensions, proof_provider::ProofProvider, BadBlocks,
+	execution_extensions::ExecutionExtensions, proof_provider::ProofProvider, AuxStore, BadBlocks,
 	BlockBackend, BlockchainEvents, ExecutorProvider, ForkBlocks, StorageProvider, UsageProvider,
 };
 use sc_client_db::{Backend, BlocksPruning, DatabaseSettings, PruningMode};
@@ -859,6 +859,7 @@ where
 		+ ProofProvider<Block>
 		+ HeaderBackend<Block>
 		+ BlockchainEvents<Block>
+		+ AuxStore
 		+ 'static,
 	TxPool: TransactionPool<Block = Block, Hash = <Block as BlockT>::Hash> + 'static,
 	IQ: ImportQueue<Block> + 'static,
@@ -1020,6 +1021,7 @@ where
 		+ ProofProvider<Block>
 		+ HeaderBackend<Block>
 		+ BlockchainEvents<Block>
+		+ AuxStore
 		+ 'static,
 	TxPool: TransactionPool<Block = Block, Hash = <Block as BlockT>::Hash> + 'static,
 	IQ: ImportQueue<Block> + 'static,
@@ -1203,6 +1205,7 @@ where
 		+ BlockBackend<Block>
 		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
 		+ ProofProvider<Block>
+		+ AuxStore
 		+ Send
 		+ Sync
 		+ 'static,
@@ -1316,6 +1319,7 @@ where
 		+ BlockBackend<Block>
 		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
 		+ ProofProvider<Block>
+		+ AuxStore
 		+ Send
 		+ Sync
 		+ 'static,
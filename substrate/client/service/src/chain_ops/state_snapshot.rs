@@ -0,0 +1,710 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Binary snapshots of the state of a block.
+//!
+//! A snapshot file starts with [`SNAPSHOT_MAGIC`] followed by a sequence of records. Every record
+//! is the little-endian `u32` length of its payload, the SCALE encoded payload and the
+//! `blake2_256` checksum of the payload.
+//!
+//! The first record is a [`SnapshotHeader`] holding the header and the justifications of the
+//! snapshot block. Every following record is a [`SnapshotChunk`] of key-value pairs read from the
+//! state in trie order, together with the cursor the next chunk starts from. The last chunk of a
+//! finished snapshot is marked as complete.
+//!
+//! As every chunk is self-contained, an interrupted export is resumed by dropping the truncated or
+//! corrupted tail of the file and continuing from the cursor of the last valid chunk.
+
+use crate::error::Error;
+use codec::{Decode, Encode};
+use futures::future;
+use log::{info, warn};
+use parking_lot::Mutex;
+use sc_client_api::{BlockBackend, HeaderBackend, ProofProvider};
+use sc_consensus::{
+	import_queue::{BlockImportError, BlockImportStatus, ImportQueue, IncomingBlock, Link},
+	ImportedState,
+};
+use sp_consensus::BlockOrigin;
+use sp_core::{
+	hashing::blake2_256,
+	storage::{well_known_keys, ChildInfo, ChildType, PrefixedStorageKey, Storage, StorageChild},
+};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, NumberFor},
+	Justifications,
+};
+use sp_state_machine::KeyValueStorageLevel;
+use std::{
+	collections::HashMap,
+	fs::{File, OpenOptions},
+	io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+	path::Path,
+	sync::Arc,
+	task::Poll,
+};
+
+/// Bytes every snapshot file starts with.
+const SNAPSHOT_MAGIC: [u8; 8] = *b"sc-snap\0";
+
+/// Version of the snapshot format.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Size of the record length prefix.
+const RECORD_LEN_SIZE: u64 = 4;
+
+/// Size of the record checksum.
+const RECORD_CHECKSUM_SIZE: u64 = 32;
+
+/// The first record of a snapshot, describing the snapshot block.
+#[derive(Encode, Decode)]
+struct SnapshotHeader<B: BlockT> {
+	/// Version of the snapshot format.
+	version: u32,
+	/// Genesis hash of the chain the snapshot was taken from.
+	genesis_hash: B::Hash,
+	/// Header of the snapshot block.
+	header: B::Header,
+	/// Justifications of the snapshot block, if any.
+	justifications: Option<Justifications>,
+}
+
+/// A chunk of the snapshot state.
+#[derive(Encode, Decode)]
+struct SnapshotChunk {
+	/// Position of the chunk in the snapshot, starting from zero.
+	index: u32,
+	/// Key-value pairs of the chunk, by state root. The root of the top trie is empty.
+	entries: Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)>,
+	/// Keys the next chunk starts after.
+	cursor: Vec<Vec<u8>>,
+	/// Whether this is the last chunk of the snapshot.
+	complete: bool,
+}
+
+/// Write a record with the given payload.
+fn write_record(writer: &mut impl Write, payload: &[u8]) -> io::Result<()> {
+	let len = u32::try_from(payload.len())
+		.map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Snapshot record is too large"))?;
+	writer.write_all(&len.to_le_bytes())?;
+	writer.write_all(payload)?;
+	writer.write_all(&blake2_256(payload))
+}
+
+/// Read the payload of the next record.
+///
+/// Returns `None` at the end of the input. A truncated record or a checksum mismatch is reported
+/// as an [`io::ErrorKind::InvalidData`] error.
+fn read_record(reader: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+	let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+	let mut len = [0u8; RECORD_LEN_SIZE as usize];
+	match reader.read(&mut len[..1])? {
+		0 => return Ok(None),
+		_ => reader
+			.read_exact(&mut len[1..])
+			.map_err(|_| invalid("Truncated record length"))?,
+	}
+
+	let len = u32::from_le_bytes(len) as u64;
+	let mut payload = Vec::new();
+	if reader.by_ref().take(len).read_to_end(&mut payload)? as u64 != len {
+		return Err(invalid("Truncated record payload"))
+	}
+
+	let mut checksum = [0u8; RECORD_CHECKSUM_SIZE as usize];
+	reader
+		.read_exact(&mut checksum)
+		.map_err(|_| invalid("Truncated record checksum"))?;
+	if checksum != blake2_256(&payload) {
+		return Err(invalid("Record checksum mismatch"))
+	}
+
+	Ok(Some(payload))
+}
+
+/// Read and decode the next record.
+fn read_decoded<T: Decode>(reader: &mut impl Read) -> io::Result<Option<T>> {
+	read_record(reader)?
+		.map(|payload| {
+			T::decode(&mut &payload[..])
+				.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+		})
+		.transpose()
+}
+
+/// Read the magic bytes and the header of a snapshot.
+fn read_header<B: BlockT>(reader: &mut impl Read) -> Result<SnapshotHeader<B>, Error> {
+	let mut magic = [0u8; SNAPSHOT_MAGIC.len()];
+	reader.read_exact(&mut magic)?;
+	if magic != SNAPSHOT_MAGIC {
+		return Err(Error::Other("Not a state snapshot file".into()))
+	}
+
+	let header = read_decoded::<SnapshotHeader<B>>(reader)?
+		.ok_or_else(|| Error::Other("Missing state snapshot header".into()))?;
+	if header.version != SNAPSHOT_VERSION {
+		return Err(Error::Other(format!(
+			"Unsupported state snapshot version {}, expected {}",
+			header.version, SNAPSHOT_VERSION
+		)))
+	}
+
+	Ok(header)
+}
+
+/// Update the cursor with the levels returned by [`ProofProvider::storage_collection`].
+///
+/// Returns `true` if the whole state has been read.
+fn advance_cursor(cursor: &mut Vec<Vec<u8>>, levels: &[(KeyValueStorageLevel, bool)]) -> bool {
+	// The position in the top trie stays valid when the chunk only contains child trie entries.
+	if cursor.len() == 2 && levels.first().map_or(false, |(top, _)| top.key_values.is_empty()) {
+		cursor.pop();
+	} else {
+		cursor.clear();
+	}
+
+	let mut complete = true;
+	for (level, level_complete) in levels {
+		if !level_complete {
+			if let Some((key, _)) = level.key_values.last() {
+				cursor.push(key.clone());
+			}
+			complete = false;
+		}
+	}
+	complete
+}
+
+/// Position to continue an export from.
+#[derive(Default)]
+struct ExportProgress {
+	/// Index of the next chunk.
+	index: u32,
+	/// Keys the next chunk starts after.
+	cursor: Vec<Vec<u8>>,
+	/// Whether the snapshot is already complete.
+	complete: bool,
+}
+
+/// Open a partially exported snapshot and truncate it after its last valid chunk.
+fn open_partial_snapshot<B: BlockT>(path: &Path) -> Result<(File, B::Hash, ExportProgress), Error> {
+	let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+	let mut reader = BufReader::new(&mut file);
+	let header = read_header::<B>(&mut reader)?;
+	let mut valid_len = reader.stream_position()?;
+
+	let mut progress = ExportProgress::default();
+	loop {
+		match read_decoded::<SnapshotChunk>(&mut reader) {
+			Ok(Some(chunk)) if chunk.index == progress.index && !progress.complete => {
+				progress = ExportProgress {
+					index: chunk.index + 1,
+					cursor: chunk.cursor,
+					complete: chunk.complete,
+				};
+				valid_len = reader.stream_position()?;
+			},
+			Ok(Some(_)) => {
+				warn!(
+					"Unexpected chunk #{} in the state snapshot, dropping the tail",
+					progress.index
+				);
+				break
+			},
+			Ok(None) => break,
+			Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+				warn!(
+					"Invalid chunk #{} in the state snapshot, dropping the tail: {}",
+					progress.index, e
+				);
+				break
+			},
+			Err(e) => return Err(e.into()),
+		}
+	}
+	drop(reader);
+
+	file.set_len(valid_len)?;
+	file.seek(SeekFrom::End(0))?;
+	Ok((file, header.header.hash(), progress))
+}
+
+/// Export a binary snapshot of the state at the given block into the file at `path`.
+///
+/// The state is written in chunks of about `chunk_size` bytes. If `resume` is set and `path`
+/// already holds a partial snapshot, the export continues after its last valid chunk. In that case
+/// `hash` defaults to the block of the partial snapshot, otherwise it defaults to the finalized
+/// block.
+pub fn export_state_snapshot<B, C>(
+	client: Arc<C>,
+	hash: Option<B::Hash>,
+	path: &Path,
+	chunk_size: usize,
+	resume: bool,
+) -> Result<(), Error>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + BlockBackend<B> + ProofProvider<B>,
+{
+	let (file, hash, mut progress) = if resume && path.exists() {
+		let (file, snapshot_hash, progress) = open_partial_snapshot::<B>(path)?;
+		if hash.map_or(false, |hash| hash != snapshot_hash) {
+			return Err(Error::Other(format!(
+				"Cannot resume the state snapshot of block {:?} at a different block",
+				snapshot_hash
+			)))
+		}
+		info!(
+			"Resuming state snapshot of block {:?} from chunk #{}",
+			snapshot_hash, progress.index
+		);
+		(file, snapshot_hash, progress)
+	} else {
+		let hash = hash.unwrap_or_else(|| client.info().finalized_hash);
+		let header = client
+			.header(hash)?
+			.ok_or_else(|| Error::Other(format!("Unknown block {:?}", hash)))?;
+		let justifications = client.justifications(hash)?;
+		if justifications.is_none() {
+			warn!("Block {:?} has no justifications, the snapshot won't prove its finality", hash);
+		}
+
+		let mut file = File::create(path)?;
+		file.write_all(&SNAPSHOT_MAGIC)?;
+		let header = SnapshotHeader::<B> {
+			version: SNAPSHOT_VERSION,
+			genesis_hash: client.info().genesis_hash,
+			header,
+			justifications,
+		};
+		write_record(&mut file, &header.encode())?;
+		(file, hash, ExportProgress::default())
+	};
+
+	let mut writer = BufWriter::new(file);
+	let mut exported_keys = 0;
+	while !progress.complete {
+		let levels = client.storage_collection(hash, &progress.cursor, chunk_size)?;
+		progress.complete = advance_cursor(&mut progress.cursor, &levels);
+
+		let chunk = SnapshotChunk {
+			index: progress.index,
+			entries: levels
+				.into_iter()
+				.map(|(level, _)| (level.state_root, level.key_values))
+				.collect(),
+			cursor: progress.cursor.clone(),
+			complete: progress.complete,
+		};
+		exported_keys +=
+			chunk.entries.iter().map(|(_, key_values)| key_values.len()).sum::<usize>();
+
+		// Every chunk is flushed, so an interrupted export only loses the chunk in progress.
+		write_record(&mut writer, &chunk.encode())?;
+		writer.flush()?;
+
+		if progress.index % 64 == 0 {
+			info!("Exported chunk #{} ({} keys so far)", progress.index, exported_keys);
+		}
+		progress.index += 1;
+	}
+	writer.get_ref().sync_all()?;
+
+	info!(
+		"🎉 Exported state snapshot of block {:?} in {} chunks ({} keys in this run)",
+		hash, progress.index, exported_keys
+	);
+	Ok(())
+}
+
+/// Read a complete snapshot from the file at `path`.
+///
+/// Returns the snapshot header and the state, grouped by trie the same way state sync does.
+fn read_snapshot<B: BlockT>(
+	path: &Path,
+) -> Result<(SnapshotHeader<B>, HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>), Error> {
+	let mut reader = BufReader::new(File::open(path)?);
+	let header = read_header::<B>(&mut reader)?;
+
+	let mut state: HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)> = HashMap::new();
+	let mut index = 0;
+	loop {
+		let chunk = read_decoded::<SnapshotChunk>(&mut reader)?.ok_or_else(|| {
+			Error::Other(format!(
+				"State snapshot is incomplete after {} chunks, resume the export to finish it",
+				index
+			))
+		})?;
+		if chunk.index != index {
+			return Err(Error::Other(format!(
+				"Unexpected chunk #{} in the state snapshot, expected #{}",
+				chunk.index, index
+			)))
+		}
+
+		for (state_root, key_values) in chunk.entries {
+			let is_top = state_root.is_empty();
+			let entry = state.entry(state_root).or_default();
+			if entry.0.len() > 0 && entry.1.len() > 1 {
+				// Child trie with the same root was already read.
+				continue
+			}
+
+			let mut child_storage_roots = Vec::new();
+			for (key, value) in key_values {
+				// Child trie roots are recalculated on import.
+				if is_top && well_known_keys::is_child_storage_key(&key) {
+					child_storage_roots.push((value, key));
+				} else {
+					entry.0.push((key, value));
+				}
+			}
+			for (root, storage_key) in child_storage_roots {
+				state.entry(root).or_default().1.push(storage_key);
+			}
+		}
+
+		if chunk.complete {
+			break
+		}
+		index += 1;
+	}
+
+	if read_record(&mut reader)?.is_some() {
+		return Err(Error::Other("Unexpected data after the last state snapshot chunk".into()))
+	}
+
+	Ok((header, state))
+}
+
+/// Read the state snapshot from the file at `path`.
+///
+/// Returns the header of the snapshot block and its state.
+pub fn read_state_snapshot<B: BlockT>(path: &Path) -> Result<(B::Header, Storage), Error> {
+	let (snapshot, state) = read_snapshot::<B>(path)?;
+
+	let mut storage = Storage::default();
+	for (state_root, (key_values, parent_storage_keys)) in state {
+		if state_root.is_empty() {
+			storage.top.extend(key_values);
+			continue
+		}
+
+		for prefixed_key in parent_storage_keys {
+			let Some((ChildType::ParentKeyId, storage_key)) =
+				ChildType::from_prefixed_key(PrefixedStorageKey::new_ref(&prefixed_key))
+			else {
+				return Err(Error::Other("Invalid child storage key in the state snapshot".into()))
+			};
+			storage.children_default.insert(
+				storage_key.to_vec(),
+				StorageChild {
+					data: key_values.iter().cloned().collect(),
+					child_info: ChildInfo::new_default(storage_key),
+				},
+			);
+		}
+	}
+
+	Ok((snapshot.header, storage))
+}
+
+/// Import the state snapshot from the file at `path`.
+///
+/// The snapshot block is imported with its state and justifications through the given import
+/// queue, the same way state sync imports its target block. The whole state is kept in memory
+/// until the import is done.
+pub async fn import_state_snapshot<B, C, IQ>(
+	client: Arc<C>,
+	mut import_queue: IQ,
+	path: &Path,
+) -> Result<(), Error>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + Send + Sync + 'static,
+	IQ: ImportQueue<B> + 'static,
+{
+	/// Link waiting for the import of the snapshot block.
+	#[derive(Default)]
+	struct WaitLink {
+		result: Mutex<Option<Result<(), Error>>>,
+	}
+
+	impl<B: BlockT> Link<B> for WaitLink {
+		fn blocks_processed(
+			&self,
+			_imported: usize,
+			_num_expected_blocks: usize,
+			results: Vec<(Result<BlockImportStatus<NumberFor<B>>, BlockImportError>, B::Hash)>,
+		) {
+			let result = match results.into_iter().next() {
+				Some((Ok(_), _)) => Ok(()),
+				Some((Err(err), hash)) => Err(Error::Other(format!(
+					"Error importing the state snapshot block {:?}: {}",
+					hash, err
+				))),
+				None => return,
+			};
+			*self.result.lock() = Some(result);
+		}
+	}
+
+	info!("Reading state snapshot...");
+	let (snapshot, state) = read_snapshot::<B>(path)?;
+	let genesis_hash = client.info().genesis_hash;
+	if snapshot.genesis_hash != genesis_hash {
+		return Err(Error::Other(format!(
+			"State snapshot was taken from chain with genesis {:?}, expected {:?}",
+			snapshot.genesis_hash, genesis_hash
+		)))
+	}
+
+	let hash = snapshot.header.hash();
+	let number = *snapshot.header.number();
+	let state = sp_state_machine::KeyValueStates::from(state);
+	info!("Importing state snapshot of block #{} ({:?}) with {} keys", number, hash, state.len());
+
+	import_queue.service_ref().import_blocks(
+		BlockOrigin::NetworkInitialSync,
+		vec![IncomingBlock {
+			hash,
+			header: Some(snapshot.header),
+			body: None,
+			indexed_body: None,
+			justifications: snapshot.justifications,
+			origin: None,
+			allow_missing_state: true,
+			import_existing: true,
+			skip_execution: true,
+			state: Some(ImportedState { block: hash, state }),
+		}],
+	);
+
+	let link = WaitLink::default();
+	future::poll_fn(|cx| {
+		import_queue.poll_actions(cx, &link);
+		match link.result.lock().take() {
+			Some(result) => Poll::Ready(result),
+			None => {
+				cx.waker().wake_by_ref();
+				Poll::Pending
+			},
+		}
+	})
+	.await?;
+
+	info!("🎉 Imported state snapshot of block #{} ({:?})", number, hash);
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+	use sc_block_builder::BlockBuilderBuilder;
+	use sc_client_api::StorageProvider;
+	use sc_consensus::{BasicQueue, BlockImportParams, ForkChoiceStrategy, Verifier};
+	use sp_core::storage::StorageKey;
+	use std::collections::BTreeMap;
+	use substrate_test_runtime_client::{prelude::*, runtime::Block, TestClient};
+
+	/// Engine id of the GRANDPA justifications.
+	const GRANDPA_ENGINE_ID: [u8; 4] = *b"FRNK";
+
+	/// Verifier finalizing the blocks imported with a justification, like the GRANDPA block
+	/// import does for the justifications it accepts.
+	struct JustificationVerifier;
+
+	#[async_trait::async_trait]
+	impl Verifier<Block> for JustificationVerifier {
+		async fn verify(
+			&self,
+			mut block: BlockImportParams<Block>,
+		) -> Result<BlockImportParams<Block>, String> {
+			block.finalized = block.justifications.is_some();
+			block.fork_choice = Some(ForkChoiceStrategy::LongestChain);
+			Ok(block)
+		}
+	}
+
+	fn exported_pairs(path: &Path) -> BTreeMap<Vec<u8>, Vec<u8>> {
+		read_state_snapshot::<Block>(path).unwrap().1.top
+	}
+
+	fn client_with_child_storage() -> Arc<TestClient> {
+		Arc::new(
+			TestClientBuilder::new()
+				.add_extra_child_storage(&ChildInfo::new_default(b"child"), b"key", b"value")
+				.build(),
+		)
+	}
+
+	/// Build and import blocks on top of genesis, each setting `key` to its number, and finalize
+	/// the last one with a GRANDPA justification.
+	fn build_finalized_chain(client: &TestClient, key: &[u8], blocks: u64) -> Justifications {
+		let mut parent = client.info().genesis_hash;
+		for number in 0..blocks {
+			let mut builder = BlockBuilderBuilder::new(client)
+				.on_parent_block(parent)
+				.with_parent_block_number(number)
+				.build()
+				.unwrap();
+			builder.push_storage_change(key.to_vec(), Some((number + 1).encode())).unwrap();
+			let block = builder.build().unwrap().block;
+			parent = block.hash();
+			block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		}
+
+		let justification = (GRANDPA_ENGINE_ID, vec![1, 2, 3]);
+		ClientExt::finalize_block(client, parent, Some(justification.clone())).unwrap();
+		Justifications::from(justification)
+	}
+
+	fn import_queue(client: Arc<TestClient>) -> BasicQueue<Block> {
+		BasicQueue::new(
+			JustificationVerifier,
+			Box::new(client),
+			None,
+			&sp_core::testing::TaskExecutor::new(),
+			None,
+		)
+	}
+
+	#[test]
+	fn export_contains_the_whole_state() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let genesis_hash = client.info().genesis_hash;
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("snapshot");
+
+		export_state_snapshot(client.clone(), Some(genesis_hash), &path, 1024, false).unwrap();
+
+		let expected = client
+			.storage_pairs(genesis_hash, None, None)
+			.unwrap()
+			.map(|(key, value)| (key.0, value.0))
+			.collect::<BTreeMap<_, _>>();
+		assert_eq!(exported_pairs(&path), expected);
+
+		let (header, _) = read_snapshot::<Block>(&path).unwrap();
+		assert_eq!(header.header.hash(), genesis_hash);
+		assert_eq!(header.genesis_hash, genesis_hash);
+	}
+
+	#[test]
+	fn interrupted_export_is_resumed() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let genesis_hash = client.info().genesis_hash;
+		let dir = tempfile::tempdir().unwrap();
+		let full_path = dir.path().join("full");
+		let path = dir.path().join("partial");
+
+		export_state_snapshot(client.clone(), None, &full_path, 1024, false).unwrap();
+		let full = std::fs::read(&full_path).unwrap();
+
+		// Cut the snapshot in the middle of a chunk.
+		std::fs::write(&path, &full[..full.len() / 2]).unwrap();
+		assert!(read_snapshot::<Block>(&path).is_err());
+
+		export_state_snapshot(client.clone(), None, &path, 1024, true).unwrap();
+		assert_eq!(std::fs::read(&path).unwrap(), full);
+
+		// Resuming a complete snapshot does not change it.
+		export_state_snapshot(client.clone(), Some(genesis_hash), &path, 1024, true).unwrap();
+		assert_eq!(std::fs::read(&path).unwrap(), full);
+	}
+
+	#[test]
+	fn corrupted_chunk_is_detected() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("snapshot");
+
+		export_state_snapshot(client, None, &path, 1024, false).unwrap();
+		let mut data = std::fs::read(&path).unwrap();
+		let last = data.len() - 1;
+		data[last] ^= 0xff;
+		std::fs::write(&path, data).unwrap();
+
+		assert!(read_snapshot::<Block>(&path).is_err());
+	}
+
+	#[test]
+	fn import_bootstraps_the_database_at_the_justified_block() {
+		let source = client_with_child_storage();
+		let justifications = build_finalized_chain(&source, b"number", 3);
+		let hash = source.info().finalized_hash;
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("snapshot");
+		export_state_snapshot(source.clone(), None, &path, 1024, false).unwrap();
+
+		let client = client_with_child_storage();
+		block_on(import_state_snapshot(client.clone(), import_queue(client.clone()), &path))
+			.unwrap();
+
+		// The snapshot block is anchored by its GRANDPA justification.
+		let info = client.info();
+		assert_eq!(info.finalized_hash, hash);
+		assert_eq!(info.finalized_number, 3);
+		assert_eq!(info.best_hash, hash);
+		assert_eq!(client.justifications(hash).unwrap(), Some(justifications));
+
+		// The state of the snapshot block is available, including the child tries.
+		assert_eq!(
+			client.storage(hash, &StorageKey(b"number".to_vec())).unwrap().map(|v| v.0),
+			Some(3u64.encode()),
+		);
+		assert_eq!(
+			client
+				.child_storage(
+					hash,
+					&ChildInfo::new_default(b"child"),
+					&StorageKey(b"key".to_vec())
+				)
+				.unwrap()
+				.map(|v| v.0),
+			Some(b"value".to_vec()),
+		);
+		assert_eq!(
+			client.header(hash).unwrap().map(|header| *header.state_root()),
+			source.header(hash).unwrap().map(|header| *header.state_root()),
+		);
+	}
+
+	#[test]
+	fn import_rejects_snapshots_of_other_chains() {
+		let source = Arc::new(
+			TestClientBuilder::new()
+				.add_extra_storage(b"other".to_vec(), b"chain".to_vec())
+				.build(),
+		);
+		build_finalized_chain(&source, b"number", 1);
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("snapshot");
+		export_state_snapshot(source, None, &path, 1024, false).unwrap();
+
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let err =
+			block_on(import_state_snapshot(client.clone(), import_queue(client.clone()), &path))
+				.unwrap_err();
+		assert!(err.to_string().contains("genesis"), "{}", err);
+		assert_eq!(client.info().best_number, 0);
+		assert_eq!(client.info().finalized_number, 0);
+	}
+}
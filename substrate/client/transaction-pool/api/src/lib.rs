@@ -280,6 +280,19 @@ pub trait TransactionPool: Send + Sync {
 		xt: TransactionFor<Self>,
 	) -> Result<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error>;
 
+	/// Re-attaches to the status stream of a watched transaction restored by the pool, e.g. from
+	/// a journal persisted across restarts.
+	///
+	/// The stream can be claimed only once. Returns `None` if the pool holds no restored watched
+	/// transaction with the given hash, or if its stream was claimed already. Pools which don't
+	/// restore transactions always return `None`.
+	fn watch_restored_transaction(
+		&self,
+		_hash: &TxHash<Self>,
+	) -> Option<Pin<Box<TransactionStatusStreamFor<Self>>>> {
+		None
+	}
+
 	// *** Block production / Networking
 	/// Get an iterator for ready transactions ordered by priority.
 	///
@@ -21,7 +21,10 @@
 use crate::{
 	common::api::FullChainApi,
 	fork_aware_txpool::ForkAwareTxPool as ForkAwareFullPool,
-	graph::{base_pool::Transaction, ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, Options},
+	graph::{
+		base_pool::{Quotas, Transaction, TransactionClassifier},
+		ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, Options,
+	},
 	single_state_txpool::BasicPool as SingleStateFullPool,
 	TransactionPoolWrapper, LOG_TARGET,
 };
@@ -29,7 +32,7 @@ use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{LocalTransactionPool, MaintainedTransactionPool};
 use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::traits::Block as BlockT;
-use std::{marker::PhantomData, sync::Arc, time::Duration};
+use std::{marker::PhantomData, path::PathBuf, sync::Arc, time::Duration};
 
 /// The type of transaction pool.
 #[derive(Debug, Clone)]
@@ -45,11 +48,16 @@ pub enum TransactionPoolType {
 pub struct TransactionPoolOptions {
 	txpool_type: TransactionPoolType,
 	options: Options,
+	journal_path: Option<PathBuf>,
 }
 
 impl Default for TransactionPoolOptions {
 	fn default() -> Self {
-		Self { txpool_type: TransactionPoolType::SingleState, options: Default::default() }
+		Self {
+			txpool_type: TransactionPoolType::SingleState,
+			options: Default::default(),
+			journal_path: None,
+		}
 	}
 }
 
@@ -81,7 +89,31 @@ impl TransactionPoolOptions {
 			Duration::from_secs(30 * 60)
 		};
 
-		TransactionPoolOptions { options, txpool_type }
+		TransactionPoolOptions { options, txpool_type, journal_path: None }
+	}
+
+	/// Sets the path of the on-disk journal of the pending transactions.
+	///
+	/// If set, pending transactions are persisted and restored after the node restart. Only
+	/// supported by the fork-aware transaction pool.
+	pub fn with_journal_path(mut self, journal_path: Option<PathBuf>) -> Self {
+		self.journal_path = journal_path;
+		self
+	}
+
+	/// Sets the per-sender quotas and the lanes with reserved capacity.
+	///
+	/// The classifier of the current quotas is kept if the given quotas have none.
+	pub fn with_quotas(mut self, quotas: Quotas) -> Self {
+		let classifier = quotas.classifier.or(self.options.quotas.classifier.take());
+		self.options.quotas = Quotas { classifier, ..quotas };
+		self
+	}
+
+	/// Sets the classifier assigning transactions to the classes lanes can be matched on.
+	pub fn with_classifier(mut self, classifier: TransactionClassifier) -> Self {
+		self.options.quotas.classifier = Some(classifier);
+		self
 	}
 
 	/// Creates predefined options for benchmarking
@@ -98,8 +130,10 @@ impl TransactionPoolOptions {
 				},
 				reject_future_transactions: false,
 				ban_time: Duration::from_secs(30 * 60),
+				quotas: Default::default(),
 			},
 			txpool_type: TransactionPoolType::SingleState,
+			journal_path: None,
 		}
 	}
 }
@@ -226,19 +260,28 @@ where
 	pub fn build(self) -> TransactionPoolHandle<Block, Client> {
 		log::info!(target:LOG_TARGET, " creating {:?} txpool {:?}/{:?}.", self.options.txpool_type, self.options.options.ready, self.options.options.future);
 		TransactionPoolWrapper::<Block, Client>(match self.options.txpool_type {
-			TransactionPoolType::SingleState => Box::new(SingleStateFullPool::new_full(
-				self.options.options,
-				self.is_validator,
-				self.prometheus,
-				self.spawner,
-				self.client,
-			)),
+			TransactionPoolType::SingleState => {
+				if let Some(ref journal_path) = self.options.journal_path {
+					log::warn!(
+						target: LOG_TARGET,
+						"Transaction journal {journal_path:?} is not supported by single-state txpool, ignoring."
+					);
+				}
+				Box::new(SingleStateFullPool::new_full(
+					self.options.options,
+					self.is_validator,
+					self.prometheus,
+					self.spawner,
+					self.client,
+				))
+			},
 			TransactionPoolType::ForkAware => Box::new(ForkAwareFullPool::new_full(
 				self.options.options,
 				self.is_validator,
 				self.prometheus,
 				self.spawner,
 				self.client,
+				self.options.journal_path,
 			)),
 		})
 	}
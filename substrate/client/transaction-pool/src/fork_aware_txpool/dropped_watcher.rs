@@ -115,6 +115,11 @@ where
 	///
 	/// Intended to ba called when transactions were finalized or their finality timed out.
 	RemoveTransactions(Vec<ExtrinsicHash<ChainApi>>),
+	/// Sends out given transactions as dropped, regardless of the views referencing them.
+	///
+	/// Intended to be called for transactions that were never submitted to any view, e.g.
+	/// transactions restored from the mempool journal that turned out to be invalid.
+	DropTransactions(Vec<DroppedTransaction<ExtrinsicHash<ChainApi>>>),
 }
 
 impl<ChainApi> Debug for Command<ChainApi>
@@ -126,6 +131,7 @@ where
 			Command::AddView(..) => write!(f, "AddView"),
 			Command::RemoveView(..) => write!(f, "RemoveView"),
 			Command::RemoveTransactions(..) => write!(f, "RemoveTransactions"),
+			Command::DropTransactions(..) => write!(f, "DropTransactions"),
 		}
 	}
 }
@@ -164,6 +170,11 @@ where
 
 	/// Transactions that need to be notified as dropped.
 	pending_dropped_transactions: Vec<ExtrinsicHash<ChainApi>>,
+
+	/// Transactions explicitly requested to be notified as dropped.
+	///
+	/// See [`Command::DropTransactions`].
+	requested_dropped_transactions: Vec<DroppedTransaction<ExtrinsicHash<ChainApi>>>,
 }
 
 impl<C> MultiViewDropWatcherContext<C>
@@ -240,6 +251,18 @@ where
 					self.future_transaction_views.remove(xt);
 				});
 			},
+			Command::DropTransactions(dropped) => {
+				log_xt_trace!(
+					target: LOG_TARGET,
+					dropped.iter().map(|d| d.tx_hash),
+					"dropped_watcher: drop requested"
+				);
+				dropped.iter().for_each(|d| {
+					self.ready_transaction_views.remove(&d.tx_hash);
+					self.future_transaction_views.remove(&d.tx_hash);
+				});
+				self.requested_dropped_transactions.extend(dropped);
+			},
 		}
 	}
 
@@ -316,6 +339,10 @@ where
 
 	/// Gets pending dropped transactions if any.
 	fn get_pending_dropped_transaction(&mut self) -> Option<DroppedTransaction<ExtrinsicHash<C>>> {
+		if let Some(dropped) = self.requested_dropped_transactions.pop() {
+			return Some(dropped)
+		}
+
 		while let Some(tx_hash) = self.pending_dropped_transactions.pop() {
 			// never drop transaction that was seen as ready. It may not have a referencing
 			// view now, but such fork can appear.
@@ -352,6 +379,7 @@ where
 			ready_transaction_views: Default::default(),
 			future_transaction_views: Default::default(),
 			pending_dropped_transactions: Default::default(),
+			requested_dropped_transactions: Default::default(),
 		};
 
 		let stream_map = futures::stream::unfold(ctx, |mut ctx| async move {
@@ -434,6 +462,19 @@ where
 				trace!(target: LOG_TARGET, "dropped_watcher: remove_transactions send message failed: {e}");
 			});
 	}
+
+	/// Requests the [`StreamOfDropped`] to send out given transactions as dropped.
+	pub fn drop_transactions(
+		&self,
+		dropped: impl IntoIterator<Item = DroppedTransaction<ExtrinsicHash<ChainApi>>>,
+	) {
+		let _ = self
+			.controller
+			.unbounded_send(Command::DropTransactions(dropped.into_iter().collect()))
+			.map_err(|e| {
+				trace!(target: LOG_TARGET, "dropped_watcher: drop_transactions send message failed: {e}");
+			});
+	}
 }
 
 #[cfg(test)]
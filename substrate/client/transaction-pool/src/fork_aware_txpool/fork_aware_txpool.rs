@@ -21,11 +21,12 @@
 use super::{
 	dropped_watcher::{MultiViewDroppedWatcherController, StreamOfDropped},
 	import_notification_sink::MultiViewImportNotificationSink,
+	mempool_journal::{MempoolJournal, RestoredTransaction},
 	metrics::{EventsMetricsCollector, MetricsLink as PrometheusMetrics},
-	multi_view_listener::MultiViewListener,
+	multi_view_listener::{MultiViewListener, TxStatusStream},
 	tx_mem_pool::{InsertionInfo, TxMemPool, TXMEMPOOL_TRANSACTION_LIMIT_MULTIPLIER},
 	view::View,
-	view_store::ViewStore,
+	view_store::{ViewStore, ViewStoreSubmitOutcome},
 };
 use crate::{
 	api::FullChainApi,
@@ -37,7 +38,7 @@ use crate::{
 	},
 	graph::{
 		self,
-		base_pool::{TimedTransactionSource, Transaction},
+		base_pool::{EvictionReason, TimedTransactionSource, Transaction},
 		BlockHash, ExtrinsicFor, ExtrinsicHash, IsValidator, Options,
 	},
 	ReadyIteratorFor, LOG_TARGET,
@@ -66,6 +67,7 @@ use sp_runtime::{
 };
 use std::{
 	collections::{BTreeMap, HashMap, HashSet},
+	path::PathBuf,
 	pin::Pin,
 	sync::Arc,
 	time::Instant,
@@ -183,6 +185,12 @@ where
 	/// Intended to be used in the finality stall cleanups and also as a cache for all in-block
 	/// transactions.
 	included_transactions: Mutex<BTreeMap<HashAndNumber<Block>, Vec<ExtrinsicHash<ChainApi>>>>,
+
+	/// Status streams of watched transactions restored from the mempool journal.
+	///
+	/// The stream is kept here until the client re-attaches to it, see
+	/// [`Self::watch_restored_transaction`].
+	restored_watchers: Mutex<HashMap<ExtrinsicHash<ChainApi>, TxStatusStream<ChainApi>>>,
 }
 
 impl<ChainApi, Block> ForkAwareTxPool<ChainApi, Block>
@@ -207,6 +215,7 @@ where
 			Options::default().future,
 			usize::MAX,
 			finality_timeout_threshold,
+			None,
 		)
 	}
 
@@ -220,6 +229,7 @@ where
 		future_limits: crate::PoolLimit,
 		mempool_max_transactions_count: usize,
 		finality_timeout_threshold: Option<usize>,
+		journal_path: Option<PathBuf>,
 	) -> (Self, ForkAwareTxPoolTask) {
 		let (listener, listener_task) = MultiViewListener::new_with_worker(Default::default());
 		let listener = Arc::new(listener);
@@ -227,13 +237,17 @@ where
 		let (import_notification_sink, import_notification_sink_task) =
 			MultiViewImportNotificationSink::new_with_worker();
 
+		let (journal, restored) = Self::open_mempool_journal(&pool_api, journal_path);
 		let mempool = Arc::from(TxMemPool::new(
 			pool_api.clone(),
 			listener.clone(),
 			Default::default(),
 			mempool_max_transactions_count,
 			ready_limits.total_bytes + future_limits.total_bytes,
+			journal,
+			None,
 		));
+		let restored_watchers = Self::restore_mempool(&mempool, &listener, restored);
 
 		let (dropped_stream_controller, dropped_stream) =
 			MultiViewDroppedWatcherController::<ChainApi>::new();
@@ -278,11 +292,67 @@ where
 				finality_timeout_threshold: finality_timeout_threshold
 					.unwrap_or(FINALITY_TIMEOUT_THRESHOLD),
 				included_transactions: Default::default(),
+				restored_watchers: Mutex::new(restored_watchers),
 			},
 			combined_tasks,
 		)
 	}
 
+	/// Opens the mempool journal at the given path.
+	///
+	/// Returns the opened journal and the transactions restored from it. Failure to open the
+	/// journal is not fatal, the pool continues to work without the journal.
+	fn open_mempool_journal(
+		pool_api: &ChainApi,
+		journal_path: Option<PathBuf>,
+	) -> (Option<MempoolJournal<Block>>, Vec<RestoredTransaction<Block::Extrinsic>>) {
+		let Some(journal_path) = journal_path else { return (None, Vec::new()) };
+		match MempoolJournal::open(&journal_path, |xt| pool_api.hash_and_length(xt).0) {
+			Ok((journal, restored)) => {
+				info!(
+					target: LOG_TARGET,
+					?journal_path,
+					restored = restored.len(),
+					"fatp::mempool journal opened"
+				);
+				(Some(journal), restored)
+			},
+			Err(error) => {
+				warn!(
+					target: LOG_TARGET,
+					?journal_path,
+					%error,
+					"fatp::mempool journal could not be opened, transactions will not be persisted"
+				);
+				(None, Vec::new())
+			},
+		}
+	}
+
+	/// Inserts the transactions restored from the journal into the mempool.
+	///
+	/// The status streams for restored watched transactions are created immediately, so no event is
+	/// lost before the client re-attaches to them. Returned streams shall be kept in
+	/// [`Self::restored_watchers`].
+	fn restore_mempool(
+		mempool: &TxMemPool<ChainApi, Block>,
+		listener: &MultiViewListener<ChainApi>,
+		restored: Vec<RestoredTransaction<Block::Extrinsic>>,
+	) -> HashMap<ExtrinsicHash<ChainApi>, TxStatusStream<ChainApi>> {
+		if restored.is_empty() {
+			return Default::default()
+		}
+		mempool
+			.restore(restored)
+			.into_iter()
+			.filter_map(|tx_hash| {
+				listener
+					.create_external_watcher_for_tx(tx_hash)
+					.map(|watcher| (tx_hash, watcher))
+			})
+			.collect()
+	}
+
 	/// Monitors the stream of dropped transactions and removes them from the mempool and
 	/// view_store.
 	///
@@ -346,6 +416,7 @@ where
 		spawner: impl SpawnEssentialNamed,
 		best_block_hash: Block::Hash,
 		finalized_hash: Block::Hash,
+		journal_path: Option<PathBuf>,
 	) -> Self {
 		let metrics = PrometheusMetrics::new(prometheus);
 		let (events_metrics_collector, event_metrics_task) =
@@ -361,13 +432,17 @@ where
 		let (import_notification_sink, import_notification_sink_task) =
 			MultiViewImportNotificationSink::new_with_worker();
 
+		let (journal, restored) = Self::open_mempool_journal(&pool_api, journal_path);
 		let mempool = Arc::from(TxMemPool::new(
 			pool_api.clone(),
 			listener.clone(),
 			metrics.clone(),
 			TXMEMPOOL_TRANSACTION_LIMIT_MULTIPLIER * options.total_count(),
 			options.ready.total_bytes + options.future.total_bytes,
+			journal,
+			options.quotas.max_per_sender,
 		));
+		let restored_watchers = Self::restore_mempool(&mempool, &listener, restored);
 
 		let (dropped_stream_controller, dropped_stream) =
 			MultiViewDroppedWatcherController::<ChainApi>::new();
@@ -411,6 +486,7 @@ where
 			is_validator,
 			finality_timeout_threshold: FINALITY_TIMEOUT_THRESHOLD,
 			included_transactions: Default::default(),
+			restored_watchers: Mutex::new(restored_watchers),
 		}
 	}
 
@@ -791,7 +867,7 @@ where
 		// view_store submission error.
 		//
 		// - If the view_store submission is successful, the transaction priority is updated in the
-		// mempool.
+		// mempool, and the per-sender quota of the mempool is enforced.
 		//
 		// Finally, it collects the hashes of updated transactions or submission errors (either
 		// from the mempool or view_store) into a returned vector.
@@ -807,9 +883,10 @@ where
 				})
 			})
 			.map(|r| {
-				r.map(|r| {
+				r.and_then(|r| {
 					mempool.update_transaction_priority(&r);
-					r.hash()
+					self.enforce_sender_quota(&r)?;
+					Ok(r.hash())
 				})
 			})
 			.collect::<Vec<_>>())
@@ -859,6 +936,9 @@ where
 			Ok(result) => result,
 			Err(TxPoolApiError::ImmediatelyDropped) =>
 				self.attempt_transaction_replacement(source, true, xt.clone()).await?,
+			// Resubmission of the transaction restored from the journal re-attaches to its stream.
+			Err(e @ TxPoolApiError::AlreadyImported(_)) =>
+				return self.watch_restored_transaction(&self.tx_hash(&xt)).ok_or_else(|| e.into()),
 			Err(e) => return Err(e.into()),
 		};
 
@@ -871,12 +951,31 @@ where
 			.inspect_err(|_| {
 				self.mempool.remove_transactions(&[insertion.hash]);
 			})
-			.map(|mut outcome| {
+			.and_then(|mut outcome| {
 				self.mempool.update_transaction_priority(&outcome);
-				outcome.expect_watcher()
+				self.enforce_sender_quota(&outcome)?;
+				Ok(outcome.expect_watcher())
 			})
 	}
 
+	/// Re-attaches to the status stream of a watched transaction restored from the mempool journal.
+	///
+	/// The stream can be claimed only once. Returns `None` if there is no watched transaction with
+	/// given hash restored from the journal, or if the stream was already claimed.
+	fn watch_restored_transaction(
+		&self,
+		tx_hash: &TxHash<Self>,
+	) -> Option<Pin<Box<TransactionStatusStreamFor<Self>>>> {
+		let watcher = self.restored_watchers.lock().remove(tx_hash);
+		trace!(
+			target: LOG_TARGET,
+			?tx_hash,
+			found = watcher.is_some(),
+			"fatp::watch_restored_transaction"
+		);
+		watcher
+	}
+
 	/// Reports invalid transactions to the transaction pool.
 	///
 	/// This function takes an array of tuples, each consisting of a transaction hash and the
@@ -1030,16 +1129,17 @@ where
 			_ => result,
 		}?;
 
-		self.view_store
-			.submit_local(xt)
-			.inspect_err(|_| {
-				self.mempool.remove_transactions(&[insertion.hash]);
-			})
-			.map(|outcome| {
+		match self.view_store.submit_local(xt) {
+			Ok(outcome) => {
 				self.mempool.update_transaction_priority(&outcome);
-				outcome.hash()
-			})
-			.or_else(|_| Ok(insertion.hash))
+				self.enforce_sender_quota(&outcome)?;
+				Ok(outcome.hash())
+			},
+			Err(_) => {
+				self.mempool.remove_transactions(&[insertion.hash]);
+				Ok(insertion.hash)
+			},
+		}
 	}
 }
 
@@ -1516,7 +1616,11 @@ where
 		let finalized_xts = self.view_store.handle_finalized(finalized_hash, tree_route).await;
 
 		self.mempool.purge_finalized_transactions(&finalized_xts).await;
+		self.mempool.compact_journal();
 		self.import_notification_sink.clean_notified_items(&finalized_xts);
+		self.restored_watchers
+			.lock()
+			.retain(|tx_hash, _| self.mempool.get_by_hash(*tx_hash).is_some());
 
 		self.metrics
 			.report(|metrics| metrics.finalized_txs.inc_by(finalized_xts.len() as _));
@@ -1648,12 +1752,49 @@ where
 
 			self.view_store
 				.remove_transaction_subtree(*worst_hash, |listener, removed_tx_hash| {
-					listener.limits_enforced(&removed_tx_hash);
+					listener.limits_enforced(&removed_tx_hash, EvictionReason::MempoolLimit);
 				});
 		}
 
 		return Ok(insertion_info)
 	}
+
+	/// Enforces the per-sender quota of the mempool on the submitted transaction.
+	///
+	/// Views only limit the transactions of a sender they contain, so a sender could fill the
+	/// mempool by spreading its transactions over the forks. If the quota is exceeded, the
+	/// transaction is removed from the mempool and the views and
+	/// [`TxPoolApiError::ImmediatelyDropped`] is returned.
+	fn enforce_sender_quota(
+		&self,
+		outcome: &ViewStoreSubmitOutcome<ChainApi>,
+	) -> Result<(), ChainApi::Error> {
+		if self.mempool.check_sender_quota(outcome) {
+			return Ok(())
+		}
+
+		let tx_hash = outcome.hash();
+		self.view_store
+			.listener
+			.transaction_dropped(DroppedTransaction::new_enforced_by_limts(tx_hash));
+		self.view_store
+			.remove_transaction_subtree(tx_hash, |listener, removed_tx_hash| {
+				listener.limits_enforced(&removed_tx_hash, EvictionReason::SenderQuota);
+			});
+		Err(TxPoolApiError::ImmediatelyDropped.into())
+	}
+}
+
+impl<ChainApi, Block> Drop for ForkAwareTxPool<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: graph::ChainApi<Block = Block> + 'static,
+{
+	fn drop(&mut self) {
+		// The mempool may outlive the pool in the background tasks, make sure the journal is
+		// persisted when the pool is shut down.
+		self.mempool.sync_journal();
+	}
 }
 
 #[async_trait]
@@ -1780,6 +1921,7 @@ where
 		prometheus: Option<&PrometheusRegistry>,
 		spawner: impl SpawnEssentialNamed,
 		client: Arc<Client>,
+		journal_path: Option<PathBuf>,
 	) -> Self {
 		let pool_api = Arc::new(FullChainApi::new(client.clone(), prometheus, &spawner));
 		let pool = Self::new_with_background_worker(
@@ -1790,6 +1932,7 @@ where
 			spawner,
 			client.usage_info().chain.best_hash,
 			client.usage_info().chain.finalized_hash,
+			journal_path,
 		);
 
 		pool
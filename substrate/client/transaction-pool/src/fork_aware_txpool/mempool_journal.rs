@@ -0,0 +1,452 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! On-disk journal of the [`TxMemPool`] content.
+//!
+//! The journal is an append-only file of SCALE-encoded records. Every transaction inserted into
+//! the mempool is appended as an insertion record, every removal is appended as a removal record.
+//! On startup the journal is replayed to rebuild the set of transactions that were pending when
+//! the node was stopped, and then compacted so it only contains those transactions.
+//!
+//! A truncated or corrupted tail of the journal (e.g. caused by a crash in the middle of a write)
+//! is ignored, all the records preceding it are restored.
+//!
+//! The file is written by a dedicated thread, so the mempool only encodes the records while
+//! holding its locks. Records are written in the order they were submitted and flushed once the
+//! writer has no more pending records.
+//!
+//! [`TxMemPool`]: super::tx_mem_pool::TxMemPool
+
+use std::{
+	fs::{self, File, OpenOptions},
+	io::{self, BufWriter, Write},
+	marker::PhantomData,
+	path::{Path, PathBuf},
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		mpsc, Arc,
+	},
+	thread,
+};
+
+use codec::{Decode, Encode};
+use indexmap::IndexMap;
+use sc_transaction_pool_api::TransactionSource;
+use sp_runtime::traits::Block as BlockT;
+use tracing::{debug, warn};
+
+use crate::LOG_TARGET;
+
+/// The minimal number of records in the journal file before compaction is considered.
+const JOURNAL_COMPACTION_THRESHOLD: usize = 10_000;
+
+/// Single entry of the journal file.
+#[derive(Encode, Decode)]
+enum JournalRecord<Hash, Extrinsic> {
+	/// Transaction was inserted into the mempool.
+	Insert { watched: bool, source: TransactionSource, xt: Extrinsic },
+	/// Transactions with given hashes were removed from the mempool.
+	Remove(Vec<Hash>),
+}
+
+/// Transaction restored from the journal.
+#[derive(Debug)]
+pub(super) struct RestoredTransaction<Extrinsic> {
+	/// Was transaction sent with `submit_and_watch`.
+	pub(super) watched: bool,
+	/// The original source of the transaction.
+	pub(super) source: TransactionSource,
+	/// Extrinsic actual body.
+	pub(super) xt: Extrinsic,
+}
+
+/// The opened journal file.
+struct JournalFile {
+	/// Buffered writer appending to the journal file.
+	writer: BufWriter<File>,
+	/// Number of records stored in the journal file.
+	records: usize,
+}
+
+/// Command sent to the journal writer thread.
+enum WriterCommand {
+	/// Append an encoded record.
+	Append(Vec<u8>),
+	/// Replace the content of the journal with the given encoded insertion records.
+	Compact(Vec<Vec<u8>>),
+	/// Flush the journal file and notify the sender.
+	Sync(mpsc::Sender<()>),
+}
+
+/// The append-only journal of the transactions kept in the [`TxMemPool`].
+///
+/// [`TxMemPool`]: super::tx_mem_pool::TxMemPool
+pub(super) struct MempoolJournal<Block: BlockT> {
+	/// Commands for the writer thread, `None` once the journal is being dropped.
+	commands: Option<mpsc::Sender<WriterCommand>>,
+	/// The writer thread, joined when the journal is dropped.
+	writer: Option<thread::JoinHandle<()>>,
+	/// Number of records stored in the journal file, updated by the writer thread.
+	records: Arc<AtomicUsize>,
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block: BlockT> MempoolJournal<Block> {
+	/// Opens the journal at given path and replays its content.
+	///
+	/// `hash` is used to compute the hashes of restored extrinsics, so removal records can be
+	/// matched against them. Returns the opened journal and the transactions which were not removed
+	/// from the mempool, in the insertion order. The journal is compacted to contain only the
+	/// returned transactions.
+	pub(super) fn open(
+		path: &Path,
+		hash: impl Fn(&Block::Extrinsic) -> Block::Hash,
+	) -> io::Result<(Self, Vec<RestoredTransaction<Block::Extrinsic>>)> {
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+
+		let data = match fs::read(path) {
+			Ok(data) => data,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+			Err(e) => return Err(e),
+		};
+
+		let mut input = &data[..];
+		let mut records = 0usize;
+		let mut restored = IndexMap::new();
+		while !input.is_empty() {
+			match JournalRecord::<Block::Hash, Block::Extrinsic>::decode(&mut input) {
+				Ok(JournalRecord::Insert { watched, source, xt }) => {
+					restored.insert(hash(&xt), RestoredTransaction { watched, source, xt });
+				},
+				Ok(JournalRecord::Remove(hashes)) =>
+					for tx_hash in hashes {
+						restored.shift_remove(&tx_hash);
+					},
+				Err(error) => {
+					warn!(
+						target: LOG_TARGET,
+						?path,
+						records,
+						%error,
+						"mempool_journal: corrupted tail, ignoring remaining content"
+					);
+					break
+				},
+			}
+			records += 1;
+		}
+
+		let restored = restored.into_values().collect::<Vec<_>>();
+		debug!(
+			target: LOG_TARGET,
+			?path,
+			records,
+			restored = restored.len(),
+			"mempool_journal: replayed"
+		);
+
+		let file = rewrite(
+			path,
+			restored.iter().map(|tx| {
+				JournalRecord::<Block::Hash, _>::Insert {
+					watched: tx.watched,
+					source: tx.source,
+					xt: &tx.xt,
+				}
+				.encode()
+			}),
+		)?;
+
+		let records = Arc::new(AtomicUsize::new(file.records));
+		let (commands, commands_rx) = mpsc::channel();
+		let writer = JournalWriter { path: path.to_path_buf(), file, records: records.clone() };
+		let writer = thread::Builder::new()
+			.name("mempool-journal".into())
+			.spawn(move || writer.run(commands_rx))?;
+
+		let journal =
+			Self { commands: Some(commands), writer: Some(writer), records, _phantom: PhantomData };
+
+		Ok((journal, restored))
+	}
+
+	/// Sends a command to the writer thread.
+	fn send(&self, command: WriterCommand) {
+		if let Some(commands) = &self.commands {
+			if commands.send(command).is_err() {
+				warn!(target: LOG_TARGET, "mempool_journal: writer thread terminated");
+			}
+		}
+	}
+
+	/// Queues a record for appending to the journal file.
+	///
+	/// Journal failures are not fatal for the pool, they are only logged by the writer.
+	fn append(&self, record: JournalRecord<&Block::Hash, &Block::Extrinsic>) {
+		self.send(WriterCommand::Append(record.encode()));
+	}
+
+	/// Records the insertion of the transaction into the mempool.
+	pub(super) fn insert(&self, watched: bool, source: TransactionSource, xt: &Block::Extrinsic) {
+		self.append(JournalRecord::Insert { watched, source, xt });
+	}
+
+	/// Records the removal of the transactions from the mempool.
+	pub(super) fn remove<'a>(&self, tx_hashes: impl IntoIterator<Item = &'a Block::Hash>) {
+		let tx_hashes = tx_hashes.into_iter().collect::<Vec<_>>();
+		if !tx_hashes.is_empty() {
+			self.append(JournalRecord::Remove(tx_hashes));
+		}
+	}
+
+	/// Blocks until all the records queued so far are written and flushed to the journal file.
+	pub(super) fn sync(&self) {
+		let (tx, rx) = mpsc::channel();
+		self.send(WriterCommand::Sync(tx));
+		let _ = rx.recv();
+	}
+
+	/// Returns true if the journal contains significantly more records than the given number of
+	/// transactions alive in the mempool.
+	pub(super) fn needs_compaction(&self, alive: usize) -> bool {
+		let records = self.records.load(Ordering::Relaxed);
+		records > JOURNAL_COMPACTION_THRESHOLD && records > 2 * alive
+	}
+
+	/// Replaces the content of the journal with the given set of transactions alive in the mempool.
+	pub(super) fn compact<'a>(
+		&self,
+		transactions: impl Iterator<Item = (bool, TransactionSource, &'a Block::Extrinsic)>,
+	) {
+		let records = transactions
+			.map(|(watched, source, xt)| {
+				JournalRecord::<Block::Hash, _>::Insert { watched, source, xt }.encode()
+			})
+			.collect();
+		self.send(WriterCommand::Compact(records));
+	}
+}
+
+impl<Block: BlockT> Drop for MempoolJournal<Block> {
+	fn drop(&mut self) {
+		// Closing the channel makes the writer flush the pending records and terminate.
+		self.commands.take();
+		if let Some(writer) = self.writer.take() {
+			if writer.join().is_err() {
+				warn!(target: LOG_TARGET, "mempool_journal: writer thread panicked");
+			}
+		}
+	}
+}
+
+/// Atomically replaces the content of the journal file with the given encoded records and opens it
+/// for appending.
+fn rewrite(path: &Path, records: impl Iterator<Item = Vec<u8>>) -> io::Result<JournalFile> {
+	let tmp_path = path.with_extension("tmp");
+	let mut count = 0usize;
+	{
+		let mut writer = BufWriter::new(File::create(&tmp_path)?);
+		for record in records {
+			writer.write_all(&record)?;
+			count += 1;
+		}
+		writer.flush()?;
+		writer.get_ref().sync_all()?;
+	}
+	fs::rename(&tmp_path, path)?;
+
+	let file = OpenOptions::new().append(true).open(path)?;
+	Ok(JournalFile { writer: BufWriter::new(file), records: count })
+}
+
+/// The writer thread owning the journal file.
+struct JournalWriter {
+	/// Path to the journal file.
+	path: PathBuf,
+	/// Opened journal file.
+	file: JournalFile,
+	/// Number of records stored in the journal file, shared with the [`MempoolJournal`].
+	records: Arc<AtomicUsize>,
+}
+
+impl JournalWriter {
+	/// Executes the commands until the channel is closed.
+	///
+	/// The file is flushed whenever there are no more pending commands.
+	fn run(mut self, commands: mpsc::Receiver<WriterCommand>) {
+		while let Ok(command) = commands.recv() {
+			self.execute(command);
+			while let Ok(command) = commands.try_recv() {
+				self.execute(command);
+			}
+			if let Err(error) = self.file.writer.flush() {
+				warn!(target: LOG_TARGET, path = ?self.path, %error, "mempool_journal: flush failed");
+			}
+		}
+	}
+
+	fn execute(&mut self, command: WriterCommand) {
+		match command {
+			WriterCommand::Append(record) => match self.file.writer.write_all(&record) {
+				Ok(()) => self.file.records += 1,
+				Err(error) => warn!(
+					target: LOG_TARGET,
+					path = ?self.path,
+					%error,
+					"mempool_journal: append failed"
+				),
+			},
+			WriterCommand::Compact(records) => {
+				// Records appended before the compaction are superseded by the new content.
+				match rewrite(&self.path, records.into_iter()) {
+					Ok(new_file) => {
+						debug!(
+							target: LOG_TARGET,
+							path = ?self.path,
+							before = self.file.records,
+							after = new_file.records,
+							"mempool_journal: compacted"
+						);
+						self.file = new_file;
+					},
+					Err(error) => warn!(
+						target: LOG_TARGET,
+						path = ?self.path,
+						%error,
+						"mempool_journal: compaction failed"
+					),
+				}
+			},
+			WriterCommand::Sync(done) => {
+				if let Err(error) = self.file.writer.flush() {
+					warn!(
+						target: LOG_TARGET,
+						path = ?self.path,
+						%error,
+						"mempool_journal: flush failed"
+					);
+				}
+				let _ = done.send(());
+			},
+		}
+		self.records.store(self.file.records, Ordering::Relaxed);
+	}
+}
+
+#[cfg(test)]
+mod mempool_journal_tests {
+	use super::*;
+	use substrate_test_runtime::{AccountId, Block, Extrinsic, Transfer, H256};
+	use substrate_test_runtime_client::Sr25519Keyring::*;
+
+	fn uxt(nonce: u64) -> Extrinsic {
+		crate::common::tests::uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce,
+		})
+	}
+
+	fn hash(xt: &Extrinsic) -> H256 {
+		sp_crypto_hashing::blake2_256(&xt.encode()).into()
+	}
+
+	#[test]
+	fn replays_inserted_and_skips_removed() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("journal");
+
+		let (journal, restored) = MempoolJournal::<Block>::open(&path, hash).unwrap();
+		assert!(restored.is_empty());
+
+		let xts = (0..4).map(uxt).collect::<Vec<_>>();
+		journal.insert(false, TransactionSource::External, &xts[0]);
+		journal.insert(true, TransactionSource::External, &xts[1]);
+		journal.insert(false, TransactionSource::Local, &xts[2]);
+		journal.remove([&hash(&xts[0])]);
+		journal.insert(false, TransactionSource::External, &xts[3]);
+		drop(journal);
+
+		let (_, restored) = MempoolJournal::<Block>::open(&path, hash).unwrap();
+		assert_eq!(
+			restored.iter().map(|tx| (tx.watched, tx.source, &tx.xt)).collect::<Vec<_>>(),
+			vec![
+				(true, TransactionSource::External, &xts[1]),
+				(false, TransactionSource::Local, &xts[2]),
+				(false, TransactionSource::External, &xts[3]),
+			]
+		);
+	}
+
+	#[test]
+	fn corrupted_tail_is_ignored() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("journal");
+
+		let (journal, _) = MempoolJournal::<Block>::open(&path, hash).unwrap();
+		journal.insert(true, TransactionSource::External, &uxt(0));
+		drop(journal);
+
+		let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+		file.write_all(&[0x00, 0xff]).unwrap();
+		drop(file);
+
+		let (_, restored) = MempoolJournal::<Block>::open(&path, hash).unwrap();
+		assert_eq!(restored.len(), 1);
+		assert_eq!(restored[0].xt, uxt(0));
+	}
+
+	#[test]
+	fn sync_persists_queued_records() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("journal");
+
+		let (journal, _) = MempoolJournal::<Block>::open(&path, hash).unwrap();
+		journal.insert(true, TransactionSource::External, &uxt(0));
+		journal.sync();
+
+		let data = fs::read(&path).unwrap();
+		assert!(matches!(
+			JournalRecord::<H256, Extrinsic>::decode(&mut &data[..]),
+			Ok(JournalRecord::Insert { watched: true, xt, .. }) if xt == uxt(0)
+		));
+	}
+
+	#[test]
+	fn compaction_rewrites_journal() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("journal");
+
+		let (journal, _) = MempoolJournal::<Block>::open(&path, hash).unwrap();
+		let xts = (0..3).map(uxt).collect::<Vec<_>>();
+		xts.iter().for_each(|xt| journal.insert(false, TransactionSource::External, xt));
+		journal.remove(xts.iter().take(2).map(hash).collect::<Vec<_>>().iter());
+
+		journal.compact(std::iter::once((false, TransactionSource::External, &xts[2])));
+		let records = journal.records.clone();
+		drop(journal);
+		assert_eq!(records.load(Ordering::Relaxed), 1);
+
+		let (_, restored) = MempoolJournal::<Block>::open(&path, hash).unwrap();
+		assert_eq!(restored.len(), 1);
+		assert_eq!(restored[0].xt, xts[2]);
+	}
+}
@@ -83,6 +83,17 @@ pub struct Metrics {
 	pub view_revalidation_duration: Histogram,
 	/// Total number of the views created w/o cloning existing view.
 	pub non_cloned_views: Counter<U64>,
+	/// Total number of transactions evicted from views because the ready queue was full.
+	///
+	/// Evictions are counted per view, so a transaction evicted from several views is counted
+	/// several times. The same applies to the other eviction counters.
+	pub ready_limit_evicted_txs: Counter<U64>,
+	/// Total number of transactions evicted from views because the future queue was full.
+	pub future_limit_evicted_txs: Counter<U64>,
+	/// Total number of transactions evicted from views because their sender exceeded its quota.
+	pub sender_quota_evicted_txs: Counter<U64>,
+	/// Total number of transactions evicted from views to make room in the full mempool.
+	pub mempool_limit_evicted_txs: Counter<U64>,
 	/// Histograms to track the timing distribution of individual transaction pool events.
 	pub events_histograms: EventsHistograms,
 }
@@ -348,6 +359,34 @@ impl MetricsRegistrant for Metrics {
 				)?,
 				registry,
 			)?,
+			ready_limit_evicted_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_ready_limit_evicted_txs_total",
+					"Total number of transactions evicted from views because the ready queue was full.",
+				)?,
+				registry,
+			)?,
+			future_limit_evicted_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_future_limit_evicted_txs_total",
+					"Total number of transactions evicted from views because the future queue was full.",
+				)?,
+				registry,
+			)?,
+			sender_quota_evicted_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_sender_quota_evicted_txs_total",
+					"Total number of transactions evicted from views because their sender exceeded its quota.",
+				)?,
+				registry,
+			)?,
+			mempool_limit_evicted_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_mempool_limit_evicted_txs_total",
+					"Total number of transactions evicted from views to make room in the full mempool.",
+				)?,
+				registry,
+			)?,
 			events_histograms: EventsHistograms::register(registry)?,
 		}))
 	}
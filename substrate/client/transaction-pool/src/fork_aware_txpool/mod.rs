@@ -366,6 +366,7 @@
 mod dropped_watcher;
 pub(crate) mod fork_aware_txpool;
 mod import_notification_sink;
+mod mempool_journal;
 mod metrics;
 mod multi_view_listener;
 mod revalidation_worker;
@@ -45,7 +45,7 @@ use sc_transaction_pool_api::{TransactionPriority, TransactionSource};
 use sp_blockchain::HashAndNumber;
 use sp_runtime::{
 	traits::Block as BlockT,
-	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	transaction_validity::{InvalidTransaction, TransactionTag as Tag, TransactionValidityError},
 };
 
 use crate::{
@@ -56,6 +56,8 @@ use crate::{
 };
 
 use super::{
+	dropped_watcher::DroppedTransaction,
+	mempool_journal::{MempoolJournal, RestoredTransaction},
 	metrics::MetricsLink as PrometheusMetrics,
 	multi_view_listener::MultiViewListener,
 	view_store::{ViewStore, ViewStoreSubmitOutcome},
@@ -94,6 +96,11 @@ where
 	/// Priority of transaction at some block. It is assumed it will not be changed often. None if
 	/// not known.
 	priority: RwLock<Option<TransactionPriority>>,
+	/// The key of the sender the per-sender quota is enforced on. None if not known or if the
+	/// quota does not apply to the transaction.
+	sender: RwLock<Option<Tag>>,
+	/// Indicates if transaction was restored from the journal after the node restart.
+	restored: bool,
 }
 
 impl<ChainApi, Block> TxInMemPool<ChainApi, Block>
@@ -154,9 +161,26 @@ where
 			validated_at: AtomicU64::new(0),
 			bytes,
 			priority: priority.into(),
+			sender: None.into(),
+			restored: false,
 		}
 	}
 
+	/// Creates a new instance of wrapper for a transaction restored from the journal.
+	fn new_restored(
+		watched: bool,
+		source: TransactionSource,
+		tx: ExtrinsicFor<ChainApi>,
+		bytes: usize,
+	) -> Self {
+		Self { restored: true, ..Self::new(watched, source, tx, bytes) }
+	}
+
+	/// Was transaction restored from the journal and is still waiting for the first revalidation.
+	fn is_pending_restored_revalidation(&self) -> bool {
+		self.restored && self.validated_at.load(atomic::Ordering::Relaxed) == 0
+	}
+
 	/// Provides a clone of actual transaction body.
 	///
 	/// Operation is cheap, as the body is `Arc`.
@@ -224,6 +248,17 @@ where
 
 	/// Maximal size of encodings of all transactions in the memory pool.
 	max_transactions_total_bytes: usize,
+
+	/// Optional on-disk journal of the memory pool content.
+	///
+	/// Allows to restore the pending transactions after the node restart.
+	journal: Option<MempoolJournal<Block>>,
+
+	/// Maximal number of transactions of a single sender in the memory pool.
+	///
+	/// The views only enforce the quota on their own transactions, the memory pool enforces it
+	/// across all the forks.
+	max_per_sender: Option<usize>,
 }
 
 /// Helper structure to encapsulate a result of [`TxMemPool::try_insert`].
@@ -257,6 +292,8 @@ where
 		metrics: PrometheusMetrics,
 		max_transactions_count: usize,
 		max_transactions_total_bytes: usize,
+		journal: Option<MempoolJournal<Block>>,
+		max_per_sender: Option<usize>,
 	) -> Self {
 		Self {
 			api,
@@ -265,6 +302,8 @@ where
 			metrics,
 			max_transactions_count,
 			max_transactions_total_bytes,
+			journal,
+			max_per_sender,
 		}
 	}
 
@@ -282,6 +321,8 @@ where
 			metrics: Default::default(),
 			max_transactions_count,
 			max_transactions_total_bytes,
+			journal: None,
+			max_per_sender: None,
 		}
 	}
 
@@ -334,6 +375,9 @@ where
 		) {
 			(false, false) => {
 				let source = tx.source();
+				if let Some(journal) = &self.journal {
+					journal.insert(tx.watched, source.source, &tx.tx);
+				}
 				transactions.insert(tx_hash, Arc::from(tx));
 				Ok(InsertionInfo::new(tx_hash, source))
 			},
@@ -421,6 +465,10 @@ where
 		}
 
 		let source = new_tx.source();
+		if let Some(journal) = &self.journal {
+			journal.insert(new_tx.watched, source.source, &new_tx.tx);
+			journal.remove(&to_be_removed);
+		}
 		transactions.insert(hash, Arc::from(new_tx));
 		for worst_hash in &to_be_removed {
 			transactions.remove(worst_hash);
@@ -475,6 +523,67 @@ where
 		for tx_hash in tx_hashes {
 			transactions.remove(tx_hash);
 		}
+		if let Some(journal) = &self.journal {
+			journal.remove(tx_hashes);
+		}
+	}
+
+	/// Inserts the transactions restored from the journal into the memory pool.
+	///
+	/// Restored transactions are not journaled again, as the journal already contains them. They
+	/// are revalidated with the first mempool revalidation, see [`Self::revalidate`].
+	///
+	/// Returns the hashes of restored transactions that were sent with `submit_and_watch`.
+	pub(super) fn restore(
+		&self,
+		restored: Vec<RestoredTransaction<<Block as BlockT>::Extrinsic>>,
+	) -> Vec<ExtrinsicHash<ChainApi>> {
+		let mut transactions = self.transactions.write();
+		let mut watched = vec![];
+		let mut rejected = vec![];
+		for RestoredTransaction { watched: is_watched, source, xt } in restored {
+			let xt = Arc::from(xt);
+			let (tx_hash, length) = self.api.hash_and_length(&xt);
+			if self.is_limit_exceeded(transactions.len() + 1, self.transactions.bytes() + length) {
+				rejected.push(tx_hash);
+				continue
+			}
+			transactions.insert(
+				tx_hash,
+				Arc::from(TxInMemPool::new_restored(is_watched, source, xt, length)),
+			);
+			if is_watched {
+				watched.push(tx_hash);
+			}
+		}
+		if let Some(journal) = &self.journal {
+			journal.remove(&rejected);
+		}
+
+		debug!(
+			target: LOG_TARGET,
+			mempool_len = transactions.len(),
+			rejected = rejected.len(),
+			"mempool::restore"
+		);
+		watched
+	}
+
+	/// Waits until the records queued for the journal are written to disk.
+	pub(super) fn sync_journal(&self) {
+		if let Some(journal) = &self.journal {
+			journal.sync();
+		}
+	}
+
+	/// Compacts the journal if it contains too many stale records.
+	pub(super) fn compact_journal(&self) {
+		let Some(journal) = self.journal.as_ref() else { return };
+		let transactions = self.transactions.read();
+		if journal.needs_compaction(transactions.len()) {
+			journal
+				.compact(transactions.values().map(|tx| (tx.watched, tx.source.source, &*tx.tx)));
+		}
 	}
 
 	/// Revalidates a batch of transactions against the provided finalized block.
@@ -497,9 +606,10 @@ where
 					.into_iter()
 					.filter(|xt| {
 						let finalized_block_number = finalized_block.number.into().as_u64();
-						xt.1.validated_at.load(atomic::Ordering::Relaxed) +
-							TXMEMPOOL_REVALIDATION_PERIOD <
-							finalized_block_number
+						xt.1.is_pending_restored_revalidation() ||
+							xt.1.validated_at.load(atomic::Ordering::Relaxed) +
+								TXMEMPOOL_REVALIDATION_PERIOD <
+								finalized_block_number
 					})
 					.sorted_by_key(|tx| tx.1.validated_at.load(atomic::Ordering::Relaxed))
 					.take(TXMEMPOOL_MAX_REVALIDATION_BATCH_SIZE),
@@ -567,16 +677,46 @@ where
 		finalized_xts.iter().for_each(|t| {
 			transactions.remove(t);
 		});
+		if let Some(journal) = &self.journal {
+			journal.remove(finalized_xts);
+		}
 	}
 
 	/// Revalidates transactions in the memory pool against a given finalized block and removes
 	/// invalid ones.
+	///
+	/// Invalid transactions restored from the journal are sent to the dropped transactions stream
+	/// and removed from the pool by its monitor, see [`DroppedReason::Invalid`].
+	///
+	/// [`DroppedReason::Invalid`]: super::dropped_watcher::DroppedReason::Invalid
 	pub(super) async fn revalidate(
 		&self,
 		view_store: Arc<ViewStore<ChainApi, Block>>,
 		finalized_block: HashAndNumber<Block>,
 	) {
-		let revalidated_invalid_hashes = self.revalidate_inner(finalized_block.clone()).await;
+		let (revalidated_invalid_restored, revalidated_invalid_hashes): (Vec<_>, Vec<_>) = self
+			.revalidate_inner(finalized_block.clone())
+			.await
+			.into_iter()
+			.partition(|tx_hash| self.get_by_hash(*tx_hash).map_or(false, |tx| tx.restored));
+
+		if !revalidated_invalid_restored.is_empty() {
+			debug!(
+				target: LOG_TARGET,
+				count = revalidated_invalid_restored.len(),
+				"mempool::revalidate: dropping invalid restored transactions"
+			);
+			view_store.dropped_stream_controller.drop_transactions(
+				revalidated_invalid_restored
+					.iter()
+					.map(|tx_hash| DroppedTransaction::new_invalid(*tx_hash)),
+			);
+			self.metrics.report(|metrics| {
+				metrics
+					.mempool_revalidation_invalid_txs
+					.inc_by(revalidated_invalid_restored.len() as _)
+			});
+		}
 
 		let mut invalid_hashes_subtrees =
 			revalidated_invalid_hashes.clone().into_iter().collect::<HashSet<_>>();
@@ -594,6 +734,9 @@ where
 			invalid_hashes_subtrees.iter().for_each(|tx_hash| {
 				transactions.remove(&tx_hash);
 			});
+			if let Some(journal) = &self.journal {
+				journal.remove(&invalid_hashes_subtrees);
+			}
 		};
 
 		self.metrics.report(|metrics| {
@@ -617,15 +760,49 @@ where
 		);
 	}
 
-	/// Updates the priority of transaction stored in mempool using provided view_store submission
-	/// outcome.
+	/// Updates the priority and the sender of transaction stored in mempool using provided
+	/// view_store submission outcome.
 	pub(super) fn update_transaction_priority(&self, outcome: &ViewStoreSubmitOutcome<ChainApi>) {
-		outcome.priority().map(|priority| {
-			self.transactions
-				.write()
-				.get_mut(&outcome.hash())
-				.map(|p| *p.priority.write() = Some(priority))
-		});
+		let transactions = self.transactions.read();
+		let Some(tx) = transactions.get(&outcome.hash()) else { return };
+		if let Some(priority) = outcome.priority() {
+			*tx.priority.write() = Some(priority);
+		}
+		if let Some(sender) = outcome.sender() {
+			*tx.sender.write() = Some(sender.clone());
+		}
+	}
+
+	/// Checks if the transaction of the provided view_store submission outcome exceeds the
+	/// per-sender quota of the memory pool.
+	///
+	/// The transaction is removed from the memory pool if it exceeds the quota. Returns false in
+	/// this case.
+	pub(super) fn check_sender_quota(&self, outcome: &ViewStoreSubmitOutcome<ChainApi>) -> bool {
+		let (Some(max_per_sender), Some(sender)) = (self.max_per_sender, outcome.sender()) else {
+			return true
+		};
+		self.update_transaction_priority(outcome);
+
+		let count = self
+			.transactions
+			.read()
+			.values()
+			.filter(|tx| tx.sender.read().as_ref() == Some(sender))
+			.count();
+		if count <= max_per_sender {
+			return true
+		}
+
+		debug!(
+			target: LOG_TARGET,
+			tx_hash = ?outcome.hash(),
+			count,
+			max_per_sender,
+			"mempool::check_sender_quota: quota exceeded"
+		);
+		self.remove_transactions(&[outcome.hash()]);
+		false
 	}
 
 	/// Counts the number of transactions in the provided iterator of hashes
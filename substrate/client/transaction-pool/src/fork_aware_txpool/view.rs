@@ -27,9 +27,10 @@ use super::metrics::MetricsLink as PrometheusMetrics;
 use crate::{
 	common::tracing_log_xt::log_xt_trace,
 	graph::{
-		self, base_pool::TimedTransactionSource, BlockHash, ExtrinsicFor, ExtrinsicHash,
-		IsValidator, TransactionFor, ValidatedPoolSubmitOutcome, ValidatedTransaction,
-		ValidatedTransactionFor,
+		self,
+		base_pool::{EvictionReason, TimedTransactionSource},
+		BlockHash, ExtrinsicFor, ExtrinsicHash, IsValidator, TransactionFor,
+		ValidatedPoolSubmitOutcome, ValidatedTransaction, ValidatedTransactionFor,
 	},
 	LOG_TARGET,
 };
@@ -145,6 +146,9 @@ pub(super) struct ViewPoolObserver<ChainApi: graph::ChainApi> {
 	aggregated_stream_sink: TracingUnboundedSender<
 		TransactionStatusEvent<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
 	>,
+
+	/// Prometheus's metrics endpoint, used to count the evicted transactions.
+	metrics: PrometheusMetrics,
 }
 
 impl<C: graph::ChainApi> graph::EventHandler<C> for ViewPoolObserver<C> {
@@ -168,7 +172,13 @@ impl<C: graph::ChainApi> graph::EventHandler<C> for ViewPoolObserver<C> {
 		self.send_to_aggregated_stream_sink(tx, status);
 	}
 
-	fn limits_enforced(&self, tx: ExtrinsicHash<C>) {
+	fn limits_enforced(&self, tx: ExtrinsicHash<C>, reason: EvictionReason) {
+		self.metrics.report(|metrics| match reason {
+			EvictionReason::ReadyLimit => metrics.ready_limit_evicted_txs.inc(),
+			EvictionReason::FutureLimit => metrics.future_limit_evicted_txs.inc(),
+			EvictionReason::SenderQuota => metrics.sender_quota_evicted_txs.inc(),
+			EvictionReason::MempoolLimit => metrics.mempool_limit_evicted_txs.inc(),
+		});
 		self.send_to_dropped_stream_sink(tx, TransactionStatus::Dropped);
 	}
 
@@ -201,7 +211,9 @@ impl<ChainApi: graph::ChainApi> ViewPoolObserver<ChainApi> {
 	/// to subscribe to life-cycle events of all extrinsics in the pool. For fork-aware
 	/// pool implementation this approach seems to be more efficient than using individual
 	/// streams for every transaction.
-	fn new() -> (
+	fn new(
+		metrics: PrometheusMetrics,
+	) -> (
 		Self,
 		DroppedMonitoringStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
 		AggregatedStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
@@ -211,7 +223,11 @@ impl<ChainApi: graph::ChainApi> ViewPoolObserver<ChainApi> {
 		let (aggregated_stream_sink, aggregated_stream) =
 			tracing_unbounded("mpsc_txpool_aggregated_stream", VIEW_STREAM_WARN_THRESHOLD);
 
-		(Self { dropped_stream_sink, aggregated_stream_sink }, dropped_stream, aggregated_stream)
+		(
+			Self { dropped_stream_sink, aggregated_stream_sink, metrics },
+			dropped_stream,
+			aggregated_stream,
+		)
 	}
 
 	/// Sends given event to the `dropped_stream_sink`.
@@ -270,7 +286,8 @@ where
 		AggregatedStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
 	) {
 		metrics.report(|metrics| metrics.non_cloned_views.inc());
-		let (event_handler, dropped_stream, aggregated_stream) = ViewPoolObserver::new();
+		let (event_handler, dropped_stream, aggregated_stream) =
+			ViewPoolObserver::new(metrics.clone());
 		(
 			Self {
 				pool: graph::Pool::new_with_event_handler(
@@ -297,7 +314,8 @@ where
 		DroppedMonitoringStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
 		AggregatedStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
 	) {
-		let (event_handler, dropped_stream, aggregated_stream) = ViewPoolObserver::new();
+		let (event_handler, dropped_stream, aggregated_stream) =
+			ViewPoolObserver::new(self.metrics.clone());
 		(
 			View {
 				at: at.clone(),
@@ -185,7 +185,7 @@ impl<ChainApi: graph::ChainApi> From<ValidatedPoolSubmitOutcome<ChainApi>>
 	for ViewStoreSubmitOutcome<ChainApi>
 {
 	fn from(value: ValidatedPoolSubmitOutcome<ChainApi>) -> Self {
-		Self::new(value.hash(), value.priority())
+		Self::new(value.hash(), value.priority()).with_sender(value.sender().cloned())
 	}
 }
 
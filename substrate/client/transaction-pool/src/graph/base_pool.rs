@@ -23,6 +23,7 @@
 use std::{cmp::Ordering, collections::HashSet, fmt, hash, sync::Arc, time::Instant};
 
 use crate::LOG_TARGET;
+use codec::Encode;
 use log::{trace, warn};
 use sc_transaction_pool_api::{error, InPoolTransaction, PoolStatus};
 use serde::Serialize;
@@ -37,6 +38,7 @@ use sp_runtime::{
 
 use super::{
 	future::{FutureTransactions, WaitingTransaction},
+	quota_tracker::QuotaTracker,
 	ready::{BestIterator, ReadyTransactions, TransactionRef},
 };
 
@@ -256,23 +258,33 @@ pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 	/// transactions to future in case they were just stuck in verification.
 	recently_pruned: [HashSet<Tag>; RECENTLY_PRUNED_TAGS],
 	recently_pruned_index: usize,
+	/// Number of transactions of every sender and usage of the lanes.
+	quota_tracker: QuotaTracker<Hash>,
 }
 
-impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for BasePool<Hash, Ex> {
+impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug + Encode> Default
+	for BasePool<Hash, Ex>
+{
 	fn default() -> Self {
 		Self::new(false)
 	}
 }
 
-impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash, Ex> {
+impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug + Encode> BasePool<Hash, Ex> {
 	/// Create new pool given reject_future_transactions flag.
 	pub fn new(reject_future_transactions: bool) -> Self {
+		Self::new_with_quotas(reject_future_transactions, Default::default())
+	}
+
+	/// Create new pool given reject_future_transactions flag and the quotas to enforce.
+	pub fn new_with_quotas(reject_future_transactions: bool, quotas: Quotas) -> Self {
 		Self {
 			reject_future_transactions,
 			future: Default::default(),
 			ready: Default::default(),
 			recently_pruned: Default::default(),
 			recently_pruned_index: 0,
+			quota_tracker: QuotaTracker::new(quotas),
 		}
 	}
 
@@ -331,6 +343,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			}
 
 			let hash = tx.transaction.hash.clone();
+			self.quota_tracker.insert(&tx.transaction, false);
 			self.future.import(tx);
 			return Ok(Imported::Future { hash })
 		}
@@ -363,6 +376,10 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			let current_tx = tx.transaction.clone();
 			match self.ready.import(tx) {
 				Ok(mut replaced) => {
+					self.quota_tracker.insert(&current_tx, true);
+					for tx in &replaced {
+						self.quota_tracker.remove(&tx.hash);
+					}
 					if !first {
 						promoted.push(current_hash.clone());
 					}
@@ -373,7 +390,8 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 					// re-import them.
 					removed.append(&mut replaced);
 				},
-				Err(e @ error::Error::TooLowPriority { .. }) =>
+				Err(e @ error::Error::TooLowPriority { .. }) => {
+					self.quota_tracker.remove(&current_hash);
 					if first {
 						trace!(target: LOG_TARGET, "[{:?}] Error importing {first}: {:?}", current_tx.hash, e);
 						return Err(e)
@@ -381,16 +399,19 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 						trace!(target: LOG_TARGET, "[{:?}] Error importing {first}: {:?}", current_tx.hash, e);
 						removed.push(current_tx);
 						promoted.retain(|hash| *hash != current_hash);
-					},
+					}
+				},
 				// transaction failed to be imported.
-				Err(e) =>
+				Err(e) => {
+					self.quota_tracker.remove(&current_hash);
 					if first {
 						trace!(target: LOG_TARGET, "[{:?}] Error importing {first}: {:?}", current_tx.hash, e);
 						return Err(e)
 					} else {
 						trace!(target: LOG_TARGET, "[{:?}] Error importing {first}: {:?}", current_tx.hash, e);
 						failed.push(current_tx.hash.clone());
-					},
+					}
+				},
 			}
 			first = false;
 		}
@@ -403,7 +424,9 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		if removed.iter().any(|tx| tx.hash == hash) {
 			// We still need to remove all transactions that we promoted
 			// since they depend on each other and will never get to the best iterator.
-			self.ready.remove_subtree(&promoted);
+			for tx in self.ready.remove_subtree(&promoted) {
+				self.quota_tracker.remove(&tx.hash);
+			}
 
 			trace!(target: LOG_TARGET, "[{:?}] Cycle detected, bailing.", hash);
 			return Err(error::Error::CycleDetected)
@@ -438,47 +461,45 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		self.ready.by_hash(hash)
 	}
 
-	/// Makes sure that the transactions in the queues stay within provided limits.
+	/// Makes sure that the transactions in the queues stay within provided limits and quotas.
 	///
 	/// Removes and returns worst transactions from the queues and all transactions that depend on
-	/// them. Technically the worst transaction should be evaluated by computing the entire pending
-	/// set. We use a simplified approach to remove transactions with the lowest priority first or
-	/// those that occupy the pool for the longest time in case priority is the same.
+	/// them, together with the reason of the removal. Technically the worst transaction should be
+	/// evaluated by computing the entire pending set. We use a simplified approach to remove
+	/// transactions with the lowest priority first or those that occupy the pool for the longest
+	/// time in case priority is the same.
+	///
+	/// Senders exceeding their quota lose their most recently imported transactions first. When the
+	/// ready queue is full, transactions of a lane are only evicted if the lane uses more than its
+	/// reserved capacity, or if there is nothing else left to evict.
 	pub fn enforce_limits(
 		&mut self,
 		ready: &Limit,
 		future: &Limit,
-	) -> Vec<Arc<Transaction<Hash, Ex>>> {
+	) -> Vec<(Arc<Transaction<Hash, Ex>>, EvictionReason)> {
 		let mut removed = vec![];
 
+		let over_quota = self.quota_tracker.take_over_quota();
+		if !over_quota.is_empty() {
+			removed.extend(
+				self.remove_subtree(&over_quota)
+					.into_iter()
+					.map(|tx| (tx, EvictionReason::SenderQuota)),
+			);
+		}
+
 		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) {
-			// find the worst transaction
-			let worst =
-				self.ready.fold::<Option<TransactionRef<Hash, Ex>>, _>(None, |worst, current| {
-					let transaction = &current.transaction;
-					worst
-						.map(|worst| {
-							// Here we don't use `TransactionRef`'s ordering implementation because
-							// while it prefers priority like need here, it also prefers older
-							// transactions for inclusion purposes and limit enforcement needs to
-							// prefer newer transactions instead and drop the older ones.
-							match worst.transaction.priority.cmp(&transaction.transaction.priority)
-							{
-								Ordering::Less => worst,
-								Ordering::Equal =>
-									if worst.insertion_id > transaction.insertion_id {
-										transaction.clone()
-									} else {
-										worst
-									},
-								Ordering::Greater => transaction.clone(),
-							}
-						})
-						.or_else(|| Some(transaction.clone()))
-				});
+			// find the worst transaction, sparing the reserved capacity of the lanes if possible
+			let worst = self
+				.worst_ready(|tx| self.quota_tracker.is_evictable(&tx.hash))
+				.or_else(|| self.worst_ready(|_| true));
 
 			if let Some(worst) = worst {
-				removed.append(&mut self.remove_subtree(&[worst.transaction.hash.clone()]))
+				removed.extend(
+					self.remove_subtree(&[worst.transaction.hash.clone()])
+						.into_iter()
+						.map(|tx| (tx, EvictionReason::ReadyLimit)),
+				)
 			} else {
 				break
 			}
@@ -509,7 +530,11 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			});
 
 			if let Some(worst) = worst {
-				removed.append(&mut self.remove_subtree(&[worst.transaction.hash.clone()]))
+				removed.extend(
+					self.remove_subtree(&[worst.transaction.hash.clone()])
+						.into_iter()
+						.map(|tx| (tx, EvictionReason::FutureLimit)),
+				)
 			} else {
 				break
 			}
@@ -518,6 +543,43 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		removed
 	}
 
+	/// Returns the worst ready transaction among those accepted by `filter`.
+	fn worst_ready(
+		&self,
+		filter: impl Fn(&Transaction<Hash, Ex>) -> bool,
+	) -> Option<TransactionRef<Hash, Ex>> {
+		self.ready.fold::<Option<TransactionRef<Hash, Ex>>, _>(None, |worst, current| {
+			let transaction = &current.transaction;
+			if !filter(&transaction.transaction) {
+				return worst
+			}
+			worst
+				.map(|worst| {
+					// Here we don't use `TransactionRef`'s ordering implementation because
+					// while it prefers priority like need here, it also prefers older
+					// transactions for inclusion purposes and limit enforcement needs to
+					// prefer newer transactions instead and drop the older ones.
+					match worst.transaction.priority.cmp(&transaction.transaction.priority) {
+						Ordering::Less => worst,
+						Ordering::Equal =>
+							if worst.insertion_id > transaction.insertion_id {
+								transaction.clone()
+							} else {
+								worst
+							},
+						Ordering::Greater => transaction.clone(),
+					}
+				})
+				.or_else(|| Some(transaction.clone()))
+		})
+	}
+
+	/// Returns true if a sender may exceed its quota, in which case [`Self::enforce_limits`]
+	/// has to be called even if the queues are within their limits.
+	pub fn exceeds_sender_quota(&self) -> bool {
+		self.quota_tracker.exceeds_quota()
+	}
+
 	/// Removes all transactions represented by the hashes and all other transactions
 	/// that depend on them.
 	///
@@ -529,12 +591,19 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	pub fn remove_subtree(&mut self, hashes: &[Hash]) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		let mut removed = self.ready.remove_subtree(hashes);
 		removed.extend(self.future.remove(hashes));
+		for tx in &removed {
+			self.quota_tracker.remove(&tx.hash);
+		}
 		removed
 	}
 
 	/// Removes and returns all transactions from the future queue.
 	pub fn clear_future(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
-		self.future.clear()
+		let removed = self.future.clear();
+		for tx in &removed {
+			self.quota_tracker.remove(&tx.hash);
+		}
+		removed
 	}
 
 	/// Prunes transactions that provide given list of tags.
@@ -557,7 +626,10 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			// make sure to promote any future transactions that could be unlocked
 			to_import.append(&mut self.future.satisfy_tags(std::iter::once(&tag)));
 			// and actually prune transactions in ready queue
-			pruned.append(&mut self.ready.prune_tags(tag.clone()));
+			for tx in self.ready.prune_tags(tag.clone()) {
+				self.quota_tracker.remove(&tx.hash);
+				pruned.push(tx);
+			}
 			// store the tags for next submission
 			recently_pruned.insert(tag);
 		}
@@ -565,6 +637,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		let mut promoted = vec![];
 		let mut failed = vec![];
 		for tx in futures_removed {
+			self.quota_tracker.remove(&tx.hash);
 			failed.push(tx.hash.clone());
 		}
 
@@ -612,6 +685,117 @@ impl Limit {
 	}
 }
 
+/// The reason of a transaction being evicted when enforcing the limits of the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionReason {
+	/// The ready queue exceeded its limit.
+	ReadyLimit,
+	/// The future queue exceeded its limit.
+	FutureLimit,
+	/// The sender of the transaction exceeded its quota.
+	SenderQuota,
+	/// The mempool of the fork-aware pool was full and the transaction gave way to one with a
+	/// higher priority.
+	MempoolLimit,
+}
+
+/// A share of the ready queue reserved for the matching transactions.
+///
+/// A transaction belongs to the lane if any of the criteria matches.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lane {
+	/// Name of the lane.
+	pub name: String,
+	/// Number of ready transactions which are not evicted in favour of transactions outside of
+	/// the lane.
+	pub reserved: usize,
+	/// Transactions with at least this priority belong to the lane.
+	pub min_priority: Option<Priority>,
+	/// Transactions submitted from any of these sources belong to the lane.
+	pub sources: Vec<TransactionSource>,
+	/// Transactions providing a tag which starts with any of these prefixes belong to the lane.
+	///
+	/// Nonce based transactions provide the encoded account of the sender followed by the nonce,
+	/// so this can be used to whitelist senders.
+	pub tag_prefixes: Vec<Tag>,
+	/// Transactions assigned to any of these classes by the [`TransactionClassifier`] belong to
+	/// the lane.
+	pub classes: Vec<String>,
+}
+
+impl Lane {
+	/// Returns true if the transaction, assigned to the given classes, belongs to the lane.
+	pub fn matches<Hash, Ex>(&self, tx: &Transaction<Hash, Ex>, classes: &[String]) -> bool {
+		self.sources.contains(&tx.source.source) ||
+			self.classes.iter().any(|class| classes.contains(class)) ||
+			self.min_priority.map_or(false, |min_priority| tx.priority >= min_priority) ||
+			tx.provides
+				.iter()
+				.any(|tag| self.tag_prefixes.iter().any(|prefix| tag.starts_with(prefix)))
+	}
+}
+
+/// Assigns transactions to classes, like the dispatch class or the origin of their call, which
+/// lanes can be matched on.
+///
+/// The pool does not know the types of the runtime, so the classifier is given the encoded
+/// transaction and has to be provided by the node, e.g. by decoding the extrinsic of its runtime.
+#[derive(Clone)]
+pub struct TransactionClassifier(Arc<dyn Fn(&[u8]) -> Vec<String> + Send + Sync>);
+
+impl TransactionClassifier {
+	/// Creates a classifier returning the classes of an encoded transaction.
+	pub fn new(classify: impl Fn(&[u8]) -> Vec<String> + Send + Sync + 'static) -> Self {
+		Self(Arc::new(classify))
+	}
+
+	/// Returns the classes of the encoded transaction.
+	pub fn classify(&self, encoded: &[u8]) -> Vec<String> {
+		(self.0)(encoded)
+	}
+}
+
+impl fmt::Debug for TransactionClassifier {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		write!(fmt, "TransactionClassifier")
+	}
+}
+
+impl PartialEq for TransactionClassifier {
+	fn eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.0, &other.0)
+	}
+}
+
+impl Eq for TransactionClassifier {}
+
+/// Per-sender quotas and reserved lanes of the pool.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Quotas {
+	/// Maximal number of ready and future transactions of a single sender.
+	///
+	/// Transactions of a lane are exempt from the quota.
+	pub max_per_sender: Option<usize>,
+	/// Lanes with capacity reserved in the ready queue.
+	///
+	/// A transaction belongs to the first lane it matches.
+	pub lanes: Vec<Lane>,
+	/// Assigns transactions to the classes of [`Lane::classes`].
+	pub classifier: Option<TransactionClassifier>,
+}
+
+impl Quotas {
+	/// Returns the index of the lane the transaction belongs to.
+	pub fn lane_of<Hash, Ex: Encode>(&self, tx: &Transaction<Hash, Ex>) -> Option<usize> {
+		let classes = match &self.classifier {
+			Some(classifier) if self.lanes.iter().any(|lane| !lane.classes.is_empty()) =>
+				classifier.classify(&tx.data.encode()),
+			_ => vec![],
+		};
+		self.lanes.iter().position(|lane| lane.matches(tx, &classes))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1256,4 +1440,240 @@ source: TimedTransactionSource { source: TransactionSource::External, timestamp:
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	#[test]
+	fn should_enforce_sender_quota() {
+		// given
+		let pool_with = |quotas| {
+			let mut pool = BasePool::new_with_quotas(false, quotas);
+			for nonce in 0..4u8 {
+				pool.import(Transaction {
+					data: vec![nonce].into(),
+					hash: nonce as u64 + 1,
+					requires: if nonce == 0 { vec![] } else { vec![vec![1, nonce - 1]] },
+					provides: vec![vec![1, nonce]],
+					..default_tx().clone()
+				})
+				.unwrap();
+			}
+			pool.import(Transaction {
+				data: vec![10u8].into(),
+				hash: 10,
+				provides: vec![vec![2, 0]],
+				..default_tx().clone()
+			})
+			.unwrap();
+			pool
+		};
+		let limit = Limit { count: 100, total_bytes: 1_000 };
+
+		// when a lane covers the sender
+		let lane =
+			Lane { name: "whitelist".into(), tag_prefixes: vec![vec![1]], ..Default::default() };
+		let mut pool =
+			pool_with(Quotas { max_per_sender: Some(2), lanes: vec![lane], classifier: None });
+
+		// then
+		assert!(!pool.exceeds_sender_quota());
+		assert!(pool.enforce_limits(&limit, &limit).is_empty());
+
+		// when
+		let mut pool =
+			pool_with(Quotas { max_per_sender: Some(2), lanes: vec![], classifier: None });
+		assert!(pool.exceeds_sender_quota());
+		let removed = pool.enforce_limits(&limit, &limit);
+
+		// then
+		let mut removed =
+			removed.into_iter().map(|(tx, reason)| (tx.hash, reason)).collect::<Vec<_>>();
+		removed.sort();
+		assert_eq!(
+			removed,
+			vec![(3, EvictionReason::SenderQuota), (4, EvictionReason::SenderQuota)]
+		);
+		let mut ready = pool.ready().map(|tx| tx.hash).collect::<Vec<_>>();
+		ready.sort();
+		assert_eq!(ready, vec![1, 2, 10]);
+		assert!(!pool.exceeds_sender_quota());
+	}
+
+	#[test]
+	fn should_count_sender_transactions_linked_out_of_order() {
+		// given
+		let mut pool = BasePool::new_with_quotas(
+			false,
+			Quotas { max_per_sender: Some(2), lanes: vec![], classifier: None },
+		);
+		let nonce = |nonce: u8| Transaction {
+			data: vec![nonce].into(),
+			hash: nonce as u64 + 1,
+			requires: if nonce == 0 { vec![] } else { vec![vec![1, nonce - 1]] },
+			provides: vec![vec![1, nonce]],
+			..default_tx().clone()
+		};
+
+		// when the nonce linking the future transaction to the ready one arrives last
+		pool.import(nonce(0)).unwrap();
+		pool.import(nonce(2)).unwrap();
+		assert!(!pool.exceeds_sender_quota());
+		pool.import(nonce(1)).unwrap();
+
+		// then
+		assert!(pool.exceeds_sender_quota());
+
+		// when the transactions leave the pool
+		let pruned = pool.prune_tags(vec![vec![1, 0]]);
+		assert_eq!(pruned.pruned.len(), 1);
+
+		// then they no longer count towards the quota
+		let limit = Limit { count: 100, total_bytes: 1_000 };
+		assert!(pool.enforce_limits(&limit, &limit).is_empty());
+		pool.import(nonce(3)).unwrap();
+		assert!(pool.exceeds_sender_quota());
+		let removed = pool.enforce_limits(&limit, &limit);
+		assert_eq!(removed.len(), 1);
+		assert_eq!(removed[0].0.hash, 4);
+	}
+
+	#[test]
+	fn should_count_gapped_future_nonces_of_one_sender() {
+		// given
+		let mut pool = BasePool::new_with_quotas(
+			false,
+			Quotas { max_per_sender: Some(2), lanes: vec![], classifier: None },
+		);
+		let nonce = |nonce: u8| Transaction {
+			data: vec![nonce].into(),
+			hash: nonce as u64 + 1,
+			requires: vec![vec![1, nonce - 1]],
+			provides: vec![vec![1, nonce]],
+			..default_tx().clone()
+		};
+
+		// when none of the future nonces depend on each other
+		pool.import(nonce(2)).unwrap();
+		pool.import(nonce(4)).unwrap();
+		assert!(!pool.exceeds_sender_quota());
+		pool.import(nonce(6)).unwrap();
+
+		// then they are still attributed to the same sender
+		assert!(pool.exceeds_sender_quota());
+		let limit = Limit { count: 100, total_bytes: 1_000 };
+		let removed = pool.enforce_limits(&limit, &limit);
+		assert_eq!(removed.len(), 1);
+		assert_eq!(removed[0].1, EvictionReason::SenderQuota);
+		assert_eq!(pool.future.len(), 2);
+		assert!(!pool.exceeds_sender_quota());
+	}
+
+	#[test]
+	fn should_match_lanes_on_transaction_classes() {
+		// given
+		let classifier = TransactionClassifier::new(|encoded: &[u8]| {
+			// the encoding of the `Vec<u8>` starts with its compact encoded length
+			if encoded.get(1) == Some(&0xff) {
+				vec!["operational".into()]
+			} else {
+				vec![]
+			}
+		});
+		let lane = Lane {
+			name: "operational".into(),
+			classes: vec!["operational".into()],
+			..Default::default()
+		};
+		let quotas =
+			Quotas { max_per_sender: Some(1), lanes: vec![lane], classifier: Some(classifier) };
+		let tx = |nonce: u8, class: u8| Transaction {
+			data: vec![class, nonce],
+			hash: nonce as u64 + 1,
+			requires: if nonce == 0 { vec![] } else { vec![vec![1, nonce - 1]] },
+			provides: vec![vec![1, nonce]],
+			..default_tx().clone()
+		};
+
+		// then
+		assert_eq!(quotas.lane_of(&tx(0, 0xff)), Some(0));
+		assert_eq!(quotas.lane_of(&tx(0, 0)), None);
+		assert_eq!(Quotas { classifier: None, ..quotas.clone() }.lane_of(&tx(0, 0xff)), None);
+
+		// when
+		let mut pool = BasePool::new_with_quotas(false, quotas);
+		pool.import(tx(0, 0xff)).unwrap();
+		pool.import(tx(1, 0xff)).unwrap();
+		pool.import(tx(2, 0)).unwrap();
+
+		// then the transactions of the lane are not counted
+		assert!(!pool.exceeds_sender_quota());
+		pool.import(tx(3, 0)).unwrap();
+		assert!(pool.exceeds_sender_quota());
+	}
+
+	#[test]
+	fn should_spare_reserved_lane_capacity() {
+		// given
+		let lane = Lane {
+			name: "local".into(),
+			reserved: 1,
+			sources: vec![TransactionSource::Local],
+			..Default::default()
+		};
+		let pool_with = |quotas| {
+			let mut pool = BasePool::new_with_quotas(false, quotas);
+			pool.import(Transaction {
+				data: vec![1u8].into(),
+				hash: 1,
+				priority: 1,
+				provides: vec![vec![1]],
+				source: TimedTransactionSource::new_local(false),
+				..default_tx().clone()
+			})
+			.unwrap();
+			for hash in 2..4u64 {
+				pool.import(Transaction {
+					data: vec![hash as u8].into(),
+					hash,
+					provides: vec![vec![hash as u8]],
+					..default_tx().clone()
+				})
+				.unwrap();
+			}
+			pool
+		};
+		let mut pool =
+			pool_with(Quotas { max_per_sender: None, lanes: vec![lane], classifier: None });
+
+		// when
+		let removed = pool.enforce_limits(
+			&Limit { count: 2, total_bytes: 1_000 },
+			&Limit { count: 100, total_bytes: 1_000 },
+		);
+
+		// then
+		assert_eq!(removed.len(), 1);
+		assert_eq!(removed[0].0.hash, 3);
+		assert_eq!(removed[0].1, EvictionReason::ReadyLimit);
+		assert!(pool.ready.contains(&1));
+
+		// when the reserved capacity is used up
+		let removed = pool.enforce_limits(
+			&Limit { count: 1, total_bytes: 1_000 },
+			&Limit { count: 100, total_bytes: 1_000 },
+		);
+
+		// then the lane is spared
+		assert_eq!(removed.len(), 1);
+		assert_eq!(removed[0].0.hash, 2);
+
+		// when there are no lanes
+		let mut pool = pool_with(Quotas::default());
+		let removed = pool.enforce_limits(
+			&Limit { count: 2, total_bytes: 1_000 },
+			&Limit { count: 100, total_bytes: 1_000 },
+		);
+
+		// then the lowest priority transaction goes first
+		assert_eq!(removed.len(), 1);
+		assert_eq!(removed[0].0.hash, 1);
+	}
 }
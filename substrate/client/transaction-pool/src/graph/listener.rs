@@ -21,7 +21,7 @@ use std::{collections::HashMap, fmt::Debug, hash};
 use linked_hash_map::LinkedHashMap;
 use log::trace;
 
-use super::{watcher, BlockHash, ChainApi, ExtrinsicHash};
+use super::{base_pool::EvictionReason, watcher, BlockHash, ChainApi, ExtrinsicHash};
 
 static LOG_TARGET: &str = "txpool::watcher";
 
@@ -42,7 +42,7 @@ pub trait EventHandler<C: ChainApi> {
 	fn future(&self, _tx: ExtrinsicHash<C>) {}
 
 	/// Called when transaction pool limits result in a transaction being affected.
-	fn limits_enforced(&self, _tx: ExtrinsicHash<C>) {}
+	fn limits_enforced(&self, _tx: ExtrinsicHash<C>, _reason: EvictionReason) {}
 
 	/// Called when a transaction is replaced by another.
 	fn usurped(&self, _tx: ExtrinsicHash<C>, _by: ExtrinsicHash<C>) {}
@@ -156,11 +156,11 @@ impl<C: ChainApi, L: EventHandler<C>> EventDispatcher<ExtrinsicHash<C>, C, L> {
 	}
 
 	/// Transaction was dropped from the pool because of enforcing the limit.
-	pub fn limits_enforced(&mut self, tx: &ExtrinsicHash<C>) {
-		trace!(target: LOG_TARGET, "[{:?}] Dropped (limits enforced)", tx);
+	pub fn limits_enforced(&mut self, tx: &ExtrinsicHash<C>, reason: EvictionReason) {
+		trace!(target: LOG_TARGET, "[{:?}] Dropped (limits enforced: {:?})", tx, reason);
 		self.fire(tx, |watcher| watcher.limit_enforced());
 
-		self.event_handler.as_ref().map(|l| l.limits_enforced(*tx));
+		self.event_handler.as_ref().map(|l| l.limits_enforced(*tx, reason));
 	}
 
 	/// Transaction was replaced with other extrinsic.
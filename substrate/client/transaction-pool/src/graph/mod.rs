@@ -29,6 +29,7 @@
 mod future;
 mod listener;
 mod pool;
+mod quota_tracker;
 mod ready;
 mod rotator;
 pub(crate) mod tracked_map;
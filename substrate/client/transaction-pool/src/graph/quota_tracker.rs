@@ -0,0 +1,342 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Accounting of the per-sender quotas and of the lanes usage of the pool.
+
+use std::{
+	cmp::Reverse,
+	collections::{HashMap, HashSet},
+	hash,
+};
+
+use crate::LOG_TARGET;
+use codec::Encode;
+use log::trace;
+use sp_runtime::transaction_validity::TransactionTag as Tag;
+
+use super::base_pool::{Quotas, Transaction};
+
+/// Identifier of the transactions attributed to the same sender.
+type SenderId = u64;
+
+/// The maximal length of the encoded nonce at the end of the tags of nonce based transactions.
+const MAX_NONCE_LEN: usize = 16;
+
+/// Returns the part of the tags of the transaction identifying its sender.
+///
+/// Nonce based transactions require the encoded account of their sender followed by the previous
+/// nonce and provide the account followed by their nonce. Subsequent nonces always differ in
+/// their first encoded byte, so the common prefix of such a pair of tags is the encoded account.
+/// Transactions which do not require a tag of this shape, like the current nonce of an account,
+/// have no sender key on their own.
+pub(crate) fn sender_key(requires: &[Tag], provides: &[Tag]) -> Option<Tag> {
+	requires.iter().find_map(|required| {
+		provides
+			.iter()
+			.filter(|provided| provided.len() == required.len())
+			.find_map(|provided| {
+				let common = required.iter().zip(provided).take_while(|(a, b)| a == b).count();
+				(common > 0 && common < provided.len() && provided.len() - common <= MAX_NONCE_LEN)
+					.then(|| required[..common].to_vec())
+			})
+	})
+}
+
+/// A transaction of the pool, as seen by the [`QuotaTracker`].
+#[derive(Debug, Clone)]
+struct Entry {
+	/// The sender the transaction is attributed to.
+	sender: SenderId,
+	/// The lane the transaction belongs to.
+	lane: Option<usize>,
+	/// Whether the transaction is in the ready queue.
+	ready: bool,
+	/// Position of the transaction in the order of import.
+	seq: u64,
+	/// Tags required by the transaction.
+	requires: Vec<Tag>,
+	/// Tags provided by the transaction.
+	provides: Vec<Tag>,
+}
+
+/// The transactions attributed to the same sender.
+#[derive(Debug, Clone)]
+struct Sender<Hash> {
+	/// All the transactions of the sender.
+	members: HashSet<Hash>,
+	/// Number of transactions counting towards the quota, i.e. not belonging to a lane.
+	counted: usize,
+	/// The sender keys of the members.
+	keys: HashSet<Tag>,
+}
+
+impl<Hash> Default for Sender<Hash> {
+	fn default() -> Self {
+		Self { members: Default::default(), counted: 0, keys: Default::default() }
+	}
+}
+
+/// Keeps the number of transactions of every sender and the usage of the lanes up to date as
+/// transactions enter and leave the pool.
+///
+/// The pool does not know the sender of a transaction, so transactions sharing the same
+/// [`sender_key`] or linked through their `requires` and `provides` tags are attributed to the
+/// same sender. For nonce based transactions this groups the nonces of an account, including
+/// future nonces with gaps between them. Two senders are merged as soon as a transaction links
+/// them, they are not split again when that transaction leaves the pool.
+#[derive(Debug, Clone)]
+pub(crate) struct QuotaTracker<Hash: hash::Hash + Eq> {
+	quotas: Quotas,
+	entries: HashMap<Hash, Entry>,
+	senders: HashMap<SenderId, Sender<Hash>>,
+	/// The transactions providing a tag.
+	providers: HashMap<Tag, Vec<Hash>>,
+	/// The transactions requiring a tag.
+	requirers: HashMap<Tag, Vec<Hash>>,
+	/// The sender of every sender key.
+	sender_keys: HashMap<Tag, SenderId>,
+	/// Number of ready transactions of every lane.
+	lanes_usage: Vec<usize>,
+	/// Senders which exceeded the quota since the last [`Self::take_over_quota`].
+	over_quota: HashSet<SenderId>,
+	next_sender: SenderId,
+	next_seq: u64,
+}
+
+impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug> QuotaTracker<Hash> {
+	/// Create a new tracker enforcing the given quotas.
+	pub fn new(quotas: Quotas) -> Self {
+		Self {
+			lanes_usage: vec![0; quotas.lanes.len()],
+			quotas,
+			entries: Default::default(),
+			senders: Default::default(),
+			providers: Default::default(),
+			requirers: Default::default(),
+			sender_keys: Default::default(),
+			over_quota: Default::default(),
+			next_sender: 0,
+			next_seq: 0,
+		}
+	}
+
+	/// Records that the transaction is in the ready or in the future queue.
+	///
+	/// Transactions moving from the future to the ready queue are recorded again.
+	pub fn insert<Ex: Encode>(&mut self, tx: &Transaction<Hash, Ex>, ready: bool) {
+		if let Some(entry) = self.entries.get_mut(&tx.hash) {
+			if entry.ready != ready {
+				if let Some(lane) = entry.lane {
+					let usage = &mut self.lanes_usage[lane];
+					*usage = if ready { *usage + 1 } else { usage.saturating_sub(1) };
+				}
+				entry.ready = ready;
+			}
+			return
+		}
+
+		let lane = self.quotas.lane_of(tx);
+		if let Some(lane) = lane.filter(|_| ready) {
+			self.lanes_usage[lane] += 1;
+		}
+
+		let key =
+			sender_key(&tx.requires, &tx.provides).or_else(|| self.known_sender_key(&tx.provides));
+		let linked = tx
+			.requires
+			.iter()
+			.filter_map(|tag| self.providers.get(tag))
+			.chain(tx.provides.iter().filter_map(|tag| self.requirers.get(tag)))
+			.flatten()
+			.filter_map(|hash| self.entries.get(hash).map(|entry| entry.sender))
+			.chain(key.as_ref().and_then(|key| self.sender_keys.get(key)).copied())
+			.collect::<HashSet<_>>();
+		let sender_id = self.merge(linked);
+
+		for tag in &tx.requires {
+			self.requirers.entry(tag.clone()).or_default().push(tx.hash.clone());
+		}
+		for tag in &tx.provides {
+			self.providers.entry(tag.clone()).or_default().push(tx.hash.clone());
+		}
+
+		let sender = self.senders.entry(sender_id).or_default();
+		sender.members.insert(tx.hash.clone());
+		if let Some(key) = &key {
+			sender.keys.insert(key.clone());
+			self.sender_keys.insert(key.clone(), sender_id);
+		}
+		if lane.is_none() {
+			sender.counted += 1;
+			if self.quotas.max_per_sender.is_some_and(|max| sender.counted > max) {
+				self.over_quota.insert(sender_id);
+			}
+		}
+
+		self.entries.insert(
+			tx.hash.clone(),
+			Entry {
+				sender: sender_id,
+				lane,
+				ready,
+				seq: self.next_seq,
+				requires: tx.requires.clone(),
+				provides: tx.provides.clone(),
+			},
+		);
+		self.next_seq += 1;
+	}
+
+	/// Records that the transaction left the pool.
+	pub fn remove(&mut self, hash: &Hash) {
+		let Some(entry) = self.entries.remove(hash) else { return };
+
+		if let Some(lane) = entry.lane.filter(|_| entry.ready) {
+			self.lanes_usage[lane] = self.lanes_usage[lane].saturating_sub(1);
+		}
+
+		for (tags, index) in
+			[(&entry.requires, &mut self.requirers), (&entry.provides, &mut self.providers)]
+		{
+			for tag in tags {
+				if let Some(hashes) = index.get_mut(tag) {
+					hashes.retain(|h| h != hash);
+					if hashes.is_empty() {
+						index.remove(tag);
+					}
+				}
+			}
+		}
+
+		if let Some(sender) = self.senders.get_mut(&entry.sender) {
+			sender.members.remove(hash);
+			if entry.lane.is_none() {
+				sender.counted = sender.counted.saturating_sub(1);
+			}
+			if sender.members.is_empty() {
+				for key in &sender.keys {
+					self.sender_keys.remove(key);
+				}
+				self.senders.remove(&entry.sender);
+				self.over_quota.remove(&entry.sender);
+			}
+		}
+	}
+
+	/// Merge the given senders into one, returning its id.
+	///
+	/// A new sender is created if none is given.
+	/// Returns the sender key of the pool a provided tag starts with, if any.
+	///
+	/// Attributes the current nonce of an account to the sender of its future nonces. The current
+	/// nonce entering the pool before gapped future nonces stays a sender of its own.
+	fn known_sender_key(&self, provides: &[Tag]) -> Option<Tag> {
+		provides.iter().find_map(|provided| {
+			(provided.len().saturating_sub(MAX_NONCE_LEN).max(1)..provided.len())
+				.map(|len| &provided[..len])
+				.find(|prefix| self.sender_keys.contains_key(*prefix))
+				.map(|prefix| prefix.to_vec())
+		})
+	}
+
+	fn merge(&mut self, senders: HashSet<SenderId>) -> SenderId {
+		let mut senders = senders.into_iter();
+		let Some(first) = senders.next() else {
+			let id = self.next_sender;
+			self.next_sender += 1;
+			return id
+		};
+
+		senders.fold(first, |a, b| {
+			let len = |id| self.senders.get(&id).map_or(0, |sender| sender.members.len());
+			// Move the members of the smaller sender over to the bigger one.
+			let (into, from) = if len(a) >= len(b) { (a, b) } else { (b, a) };
+			let Some(from_sender) = self.senders.remove(&from) else { return into };
+			self.over_quota.remove(&from);
+			for hash in &from_sender.members {
+				if let Some(entry) = self.entries.get_mut(hash) {
+					entry.sender = into;
+				}
+			}
+
+			for key in &from_sender.keys {
+				self.sender_keys.insert(key.clone(), into);
+			}
+
+			let sender = self.senders.entry(into).or_default();
+			sender.members.extend(from_sender.members);
+			sender.counted += from_sender.counted;
+			sender.keys.extend(from_sender.keys);
+			if self.quotas.max_per_sender.is_some_and(|max| sender.counted > max) {
+				self.over_quota.insert(into);
+			}
+			into
+		})
+	}
+
+	/// Returns true if a sender may exceed its quota.
+	pub fn exceeds_quota(&self) -> bool {
+		!self.over_quota.is_empty()
+	}
+
+	/// Returns the hashes of the transactions exceeding the per-sender quota.
+	///
+	/// Transactions of a lane do not count towards the quota. Future transactions are given up
+	/// first, then the most recently imported ready ones.
+	pub fn take_over_quota(&mut self) -> Vec<Hash> {
+		let Some(max_per_sender) = self.quotas.max_per_sender else {
+			self.over_quota.clear();
+			return vec![]
+		};
+
+		let mut over_quota = vec![];
+		for id in self.over_quota.drain() {
+			let Some(sender) = self.senders.get(&id) else { continue };
+			if sender.counted <= max_per_sender {
+				continue
+			}
+
+			let mut counted = sender
+				.members
+				.iter()
+				.filter_map(|hash| Some((hash, self.entries.get(hash)?)))
+				.filter(|(_, entry)| entry.lane.is_none())
+				.collect::<Vec<_>>();
+			counted.sort_unstable_by_key(|(_, entry)| Reverse((!entry.ready, entry.seq)));
+			let excess = sender.counted - max_per_sender;
+			trace!(
+				target: LOG_TARGET,
+				"[{:?}] Sender exceeds its quota by {} transactions",
+				counted.first().map(|(hash, _)| hash),
+				excess,
+			);
+			over_quota.extend(counted.into_iter().take(excess).map(|(hash, _)| hash.clone()));
+		}
+		over_quota
+	}
+
+	/// Returns true if the transaction may be evicted when the ready queue is full.
+	///
+	/// Transactions of a lane are only evicted if the lane uses more than its reserved capacity.
+	pub fn is_evictable(&self, hash: &Hash) -> bool {
+		self.entries
+			.get(hash)
+			.and_then(|entry| entry.lane)
+			.map_or(true, |lane| self.lanes_usage[lane] > self.quotas.lanes[lane].reserved)
+	}
+}
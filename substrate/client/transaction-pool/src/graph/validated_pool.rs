@@ -39,6 +39,7 @@ use super::{
 	pool::{
 		BlockHash, ChainApi, EventStream, ExtrinsicFor, ExtrinsicHash, Options, TransactionFor,
 	},
+	quota_tracker::sender_key,
 	rotator::PoolRotator,
 	watcher::Watcher,
 };
@@ -120,6 +121,10 @@ pub struct BaseSubmitOutcome<B: ChainApi, W> {
 
 	/// The priority of the transaction. Defaults to None if unknown.
 	priority: Option<TransactionPriority>,
+
+	/// The key of the sender the per-sender quota is enforced on. None if the quota does not
+	/// apply to the transaction or its sender is unknown.
+	sender: Option<Tag>,
 }
 
 /// Type alias to outcome of submission to `ValidatedPool`.
@@ -129,7 +134,18 @@ pub type ValidatedPoolSubmitOutcome<B> =
 impl<B: ChainApi, W> BaseSubmitOutcome<B, W> {
 	/// Creates a new instance with given hash and priority.
 	pub fn new(hash: ExtrinsicHash<B>, priority: Option<TransactionPriority>) -> Self {
-		Self { hash, priority, watcher: None }
+		Self { hash, priority, watcher: None, sender: None }
+	}
+
+	/// Sets the key of the sender the per-sender quota is enforced on.
+	pub fn with_sender(mut self, sender: Option<Tag>) -> Self {
+		self.sender = sender;
+		self
+	}
+
+	/// Provides the key of the sender the per-sender quota is enforced on.
+	pub fn sender(&self) -> Option<&Tag> {
+		self.sender.as_ref()
 	}
 
 	/// Sets the transaction watcher.
@@ -238,7 +254,10 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		rotator: PoolRotator<ExtrinsicHash<B>>,
 		event_handler: Option<L>,
 	) -> Self {
-		let base_pool = base::BasePool::new(options.reject_future_transactions);
+		let base_pool = base::BasePool::new_with_quotas(
+			options.reject_future_transactions,
+			options.quotas.clone(),
+		);
 		Self {
 			is_validator,
 			options,
@@ -319,6 +338,10 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 					return Err(error::Error::Unactionable.into())
 				}
 
+				let quotas = &self.options.quotas;
+				let sender = (quotas.max_per_sender.is_some() && quotas.lane_of(&tx).is_none())
+					.then(|| sender_key(&tx.requires, &tx.provides))
+					.flatten();
 				let imported = self.pool.write().import(tx)?;
 
 				if let base::Imported::Ready { ref hash, .. } = imported {
@@ -341,7 +364,8 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 
 				let mut event_dispatcher = self.event_dispatcher.write();
 				fire_events(&mut *event_dispatcher, &imported);
-				Ok(ValidatedPoolSubmitOutcome::new(*imported.hash(), Some(priority)))
+				Ok(ValidatedPoolSubmitOutcome::new(*imported.hash(), Some(priority))
+					.with_sender(sender))
 			},
 			ValidatedTransaction::Invalid(hash, err) => {
 				log::trace!(target: LOG_TARGET, "[{:?}] ValidatedPool::submit_one invalid: {:?}", hash, err);
@@ -357,12 +381,17 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 	}
 
 	fn enforce_limits(&self) -> HashSet<ExtrinsicHash<B>> {
-		let status = self.pool.read().status();
+		let (status, exceeds_sender_quota) = {
+			let pool = self.pool.read();
+			(pool.status(), pool.exceeds_sender_quota())
+		};
 		let ready_limit = &self.options.ready;
 		let future_limit = &self.options.future;
 
+		// the per-sender quota may be exceeded while the queues are within their limits
 		if ready_limit.is_exceeded(status.ready, status.ready_bytes) ||
-			future_limit.is_exceeded(status.future, status.future_bytes)
+			future_limit.is_exceeded(status.future, status.future_bytes) ||
+			exceeds_sender_quota
 		{
 			log::debug!(
 				target: LOG_TARGET,
@@ -379,10 +408,10 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 				let removed = pool
 					.enforce_limits(ready_limit, future_limit)
 					.into_iter()
-					.map(|x| x.hash)
-					.collect::<HashSet<_>>();
+					.map(|(x, reason)| (x.hash, reason))
+					.collect::<Vec<_>>();
 				// ban all removed transactions
-				self.rotator.ban(&Instant::now(), removed.iter().copied());
+				self.rotator.ban(&Instant::now(), removed.iter().map(|(h, _)| *h));
 				removed
 			};
 			if !removed.is_empty() {
@@ -391,11 +420,11 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 
 			// run notifications
 			let mut event_dispatcher = self.event_dispatcher.write();
-			for h in &removed {
-				event_dispatcher.limits_enforced(h);
+			for (h, reason) in &removed {
+				event_dispatcher.limits_enforced(h, *reason);
 			}
 
-			removed
+			removed.into_iter().map(|(h, _)| h).collect()
 		} else {
 			Default::default()
 		}
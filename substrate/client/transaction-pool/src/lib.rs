@@ -37,7 +37,10 @@ pub use builder::{Builder, TransactionPoolHandle, TransactionPoolOptions, Transa
 pub use common::notification_future;
 pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolTask};
 pub use graph::{
-	base_pool::{Limit as PoolLimit, TimedTransactionSource},
+	base_pool::{
+		EvictionReason, Lane as PoolLane, Limit as PoolLimit, Quotas as PoolQuotas,
+		TimedTransactionSource, TransactionClassifier,
+	},
 	ChainApi, Options, Pool,
 };
 use single_state_txpool::prune_known_txs_for_block;
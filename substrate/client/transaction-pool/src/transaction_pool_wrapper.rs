@@ -96,6 +96,13 @@ where
 		self.0.submit_and_watch(at, source, xt).await
 	}
 
+	fn watch_restored_transaction(
+		&self,
+		hash: &TxHash<Self>,
+	) -> Option<Pin<Box<TransactionStatusStreamFor<Self>>>> {
+		self.0.watch_restored_transaction(hash)
+	}
+
 	async fn ready_at(
 		&self,
 		at: <Self::Block as BlockT>::Hash,
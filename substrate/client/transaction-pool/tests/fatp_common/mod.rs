@@ -21,7 +21,7 @@
 use sc_transaction_pool::{ChainApi, PoolLimit};
 use sc_transaction_pool_api::ChainEvent;
 use sp_runtime::transaction_validity::TransactionSource;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use substrate_test_runtime_client::{
 	runtime::{Block, Hash, Header},
 	Sr25519Keyring::*,
@@ -71,6 +71,7 @@ pub struct TestPoolBuilder {
 	future_limits: sc_transaction_pool::PoolLimit,
 	mempool_max_transactions_count: usize,
 	finality_timeout_threshold: Option<usize>,
+	journal_path: Option<PathBuf>,
 }
 
 impl Default for TestPoolBuilder {
@@ -82,6 +83,7 @@ impl Default for TestPoolBuilder {
 			future_limits: PoolLimit { count: 512, total_bytes: 1 * 1024 * 1024 },
 			mempool_max_transactions_count: usize::MAX,
 			finality_timeout_threshold: None,
+			journal_path: None,
 		}
 	}
 }
@@ -131,6 +133,12 @@ impl TestPoolBuilder {
 		self
 	}
 
+	pub fn with_journal_path(mut self, journal_path: PathBuf) -> Self {
+		self.journal_path = Some(journal_path);
+		self.use_default_limits = false;
+		self
+	}
+
 	pub fn build(
 		self,
 	) -> (ForkAwareTxPool<TestApi, Block>, Arc<TestApi>, futures::executor::ThreadPool) {
@@ -162,6 +170,7 @@ impl TestPoolBuilder {
 				self.future_limits,
 				self.mempool_max_transactions_count,
 				self.finality_timeout_threshold,
+				self.journal_path,
 			)
 		};
 
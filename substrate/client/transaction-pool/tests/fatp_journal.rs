@@ -0,0 +1,166 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests of the mempool journal of the fork-aware transaction pool.
+
+pub mod fatp_common;
+
+use fatp_common::{
+	finalized_block_event, invalid_hash, new_best_block_event, TestPoolBuilder, LOG_TARGET, SOURCE,
+};
+use futures::{executor::block_on, FutureExt};
+use sc_transaction_pool::ChainApi;
+use sc_transaction_pool_api::{
+	error::Error as TxPoolError, MaintainedTransactionPool, TransactionPool, TransactionStatus,
+};
+use substrate_test_runtime_client::Sr25519Keyring::*;
+use substrate_test_runtime_transaction_pool::uxt;
+
+#[test]
+fn fatp_journal_restores_pending_transactions() {
+	sp_tracing::try_init_simple();
+
+	let journal_dir = tempfile::tempdir().unwrap();
+	let journal_path = journal_dir.path().join("txpool.journal");
+
+	let (pool, api, _) = TestPoolBuilder::new().with_journal_path(journal_path.clone()).build();
+
+	let header01 = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&pool, None, header01.hash())));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	block_on(pool.submit_one(invalid_hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(invalid_hash(), SOURCE, xt1.clone())).unwrap();
+	drop(pool);
+
+	let (pool, _, _) = TestPoolBuilder::new()
+		.with_api(api.clone())
+		.with_journal_path(journal_path)
+		.build();
+	assert_eq!(pool.mempool_len(), (2, 0));
+
+	block_on(pool.maintain(new_best_block_event(&pool, None, header01.hash())));
+	assert_pool_status!(header01.hash(), &pool, 2, 0);
+	assert_ready_iterator!(header01.hash(), pool, [xt0, xt1]);
+}
+
+#[test]
+fn fatp_journal_finalized_transactions_are_not_restored() {
+	sp_tracing::try_init_simple();
+
+	let journal_dir = tempfile::tempdir().unwrap();
+	let journal_path = journal_dir.path().join("txpool.journal");
+
+	let (pool, api, _) = TestPoolBuilder::new().with_journal_path(journal_path.clone()).build();
+
+	let header01 = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&pool, None, header01.hash())));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	block_on(pool.submit_one(invalid_hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(invalid_hash(), SOURCE, xt1.clone())).unwrap();
+
+	let header02 = api.push_block(2, vec![xt0.clone()], true);
+	block_on(pool.maintain(new_best_block_event(&pool, Some(header01.hash()), header02.hash())));
+	block_on(pool.maintain(finalized_block_event(&pool, api.genesis_hash(), header02.hash())));
+	assert_eq!(pool.mempool_len(), (1, 0));
+	drop(pool);
+
+	let (pool, _, _) = TestPoolBuilder::new()
+		.with_api(api.clone())
+		.with_journal_path(journal_path)
+		.build();
+	assert_eq!(pool.mempool_len(), (1, 0));
+
+	block_on(pool.maintain(new_best_block_event(&pool, None, header02.hash())));
+	assert_ready_iterator!(header02.hash(), pool, [xt1]);
+}
+
+#[test]
+fn fatp_journal_watcher_can_be_reattached() {
+	sp_tracing::try_init_simple();
+
+	let journal_dir = tempfile::tempdir().unwrap();
+	let journal_path = journal_dir.path().join("txpool.journal");
+
+	let (pool, api, _) = TestPoolBuilder::new().with_journal_path(journal_path.clone()).build();
+
+	let header01 = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&pool, None, header01.hash())));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let _ = block_on(pool.submit_and_watch(invalid_hash(), SOURCE, xt0.clone())).unwrap();
+	let _ = block_on(pool.submit_and_watch(invalid_hash(), SOURCE, xt1.clone())).unwrap();
+	drop(pool);
+
+	let (pool, _, _) = TestPoolBuilder::new()
+		.with_api(api.clone())
+		.with_journal_path(journal_path)
+		.build();
+	assert_eq!(pool.mempool_len(), (0, 2));
+
+	// re-attach by re-submission:
+	let xt0_watcher = block_on(pool.submit_and_watch(invalid_hash(), SOURCE, xt0.clone())).unwrap();
+	let result = block_on(pool.submit_and_watch(invalid_hash(), SOURCE, xt0.clone())).map(|_| ());
+	assert!(matches!(result.unwrap_err().0, TxPoolError::AlreadyImported(_)));
+
+	// re-attach by hash:
+	let xt1_hash = pool.api().hash_and_length(&xt1).0;
+	let xt1_watcher = pool.watch_restored_transaction(&xt1_hash).unwrap();
+	assert!(pool.watch_restored_transaction(&xt1_hash).is_none());
+
+	block_on(pool.maintain(new_best_block_event(&pool, None, header01.hash())));
+	assert_pool_status!(header01.hash(), &pool, 2, 0);
+
+	assert_watcher_stream!(xt0_watcher, [TransactionStatus::Ready]);
+	assert_watcher_stream!(xt1_watcher, [TransactionStatus::Ready]);
+}
+
+#[test]
+fn fatp_journal_stale_restored_transaction_is_dropped() {
+	sp_tracing::try_init_simple();
+
+	let journal_dir = tempfile::tempdir().unwrap();
+	let journal_path = journal_dir.path().join("txpool.journal");
+
+	let (pool, api, _) = TestPoolBuilder::new().with_journal_path(journal_path.clone()).build();
+
+	let header01 = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&pool, None, header01.hash())));
+
+	let xt0 = uxt(Alice, 200);
+	let _ = block_on(pool.submit_and_watch(invalid_hash(), SOURCE, xt0.clone())).unwrap();
+	drop(pool);
+
+	let header02 = api.push_block(2, vec![], true);
+	api.set_nonce(header02.hash(), Alice.into(), 201);
+
+	let (pool, _, _) = TestPoolBuilder::new()
+		.with_api(api.clone())
+		.with_journal_path(journal_path)
+		.build();
+	let xt0_watcher = pool.watch_restored_transaction(&pool.api().hash_and_length(&xt0).0).unwrap();
+
+	block_on(pool.maintain(finalized_block_event(&pool, api.genesis_hash(), header02.hash())));
+
+	assert_watcher_stream!(xt0_watcher, [TransactionStatus::Invalid]);
+	assert_eq!(pool.mempool_len(), (0, 0));
+}
@@ -1038,6 +1038,28 @@ impl<T: Config> Pallet<T> {
 		BookStateFor::<T>::insert(origin, book_state);
 	}
 
+	/// The messages in the queue of `origin` that were not processed yet, including the
+	/// overweight ones.
+	///
+	/// Reads all pages of the queue and is therefore not meant to be called on-chain, but for
+	/// example from a runtime API.
+	pub fn queued_messages(origin: &MessageOriginOf<T>) -> Vec<Vec<u8>> {
+		let mut pages = Pages::<T>::iter_prefix(origin).collect::<Vec<_>>();
+		pages.sort_by_key(|(page_index, _)| *page_index);
+
+		let mut messages = Vec::new();
+		for (_, page) in pages {
+			let mut index = 0;
+			while let Some((_, processed, message)) = page.peek_index(index) {
+				if !processed {
+					messages.push(message.to_vec());
+				}
+				index.saturating_inc();
+			}
+		}
+		messages
+	}
+
 	/// Try to execute a single message that was marked as overweight.
 	///
 	/// The `weight_limit` is the weight that can be consumed to execute the message. The base
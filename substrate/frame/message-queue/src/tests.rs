@@ -1494,6 +1494,30 @@ fn enqueue_messages_works() {
 	});
 }
 
+#[test]
+fn queued_messages_works() {
+	use MessageOrigin::*;
+	build_and_execute::<Test>(|| {
+		assert!(MessageQueue::queued_messages(&Here).is_empty());
+
+		MessageQueue::enqueue_messages(
+			vec![msg("a"), msg("weight=200"), msg("c")].into_iter(),
+			Here,
+		);
+		MessageQueue::enqueue_message(msg("x"), There);
+		assert_eq!(
+			MessageQueue::queued_messages(&Here),
+			vec![b"a".to_vec(), b"weight=200".to_vec(), b"c".to_vec()]
+		);
+		assert_eq!(MessageQueue::queued_messages(&There), vec![b"x".to_vec()]);
+
+		// The permanently overweight message stays in the queue.
+		assert_eq!(MessageQueue::service_queues(2.into_weight()), 2.into_weight());
+		assert_eq!(MessagesProcessed::take(), vec![(vmsg("a"), Here), (vmsg("c"), Here)]);
+		assert_eq!(MessageQueue::queued_messages(&Here), vec![b"weight=200".to_vec()]);
+	});
+}
+
 #[test]
 fn service_queues_suspend_works() {
 	use MessageOrigin::*;
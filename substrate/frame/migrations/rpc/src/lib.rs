@@ -0,0 +1,187 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the multi-block migrations pallet.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{ErrorObject, ErrorObjectOwned},
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+
+pub use pallet_migrations::runtime_api::MigrationsApi as MigrationsRuntimeApi;
+
+#[rpc(client, server)]
+pub trait MigrationsApi<BlockHash> {
+	/// The status of the multi-block migrations at the given block, or at the best block.
+	#[method(name = "migrations_status")]
+	fn status(&self, at: Option<BlockHash>) -> RpcResult<MigrationsStatus>;
+}
+
+/// The progress of the currently active migration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveMigration {
+	/// The index of the migration within the migrations of the runtime.
+	pub index: u32,
+	/// The number of migrations of the runtime.
+	pub total: u32,
+	/// The identifier of the migration.
+	pub identifier: Option<Bytes>,
+	/// The inner cursor of the migration.
+	pub inner_cursor: Option<Bytes>,
+	/// The block number that the migration started at.
+	pub started_at: u64,
+	/// The number of steps that the migration has taken so far.
+	pub steps: u32,
+	/// The maximal number of steps that the migration can take.
+	pub max_steps: Option<u32>,
+	/// Upper bound of the number of blocks until all migrations are done, if known.
+	pub estimated_blocks_remaining: Option<u32>,
+}
+
+/// A migration that failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedMigration {
+	/// The index of the migration within the migrations of the runtime, if any was active.
+	pub index: Option<u32>,
+	/// The identifier of the migration, if known.
+	pub identifier: Option<Bytes>,
+	/// The block number in which the migration failed.
+	pub failed_at: u64,
+}
+
+/// The status of the multi-block migrations at a given block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationsStatus {
+	/// The number of the block that was queried.
+	pub block_number: u64,
+	/// The currently active migration, if any.
+	pub active: Option<ActiveMigration>,
+	/// Whether the migrations are stuck and need governance intervention.
+	pub stuck: bool,
+	/// The most recent failures, newest first.
+	pub failures: Vec<FailedMigration>,
+}
+
+impl MigrationsStatus {
+	fn new<N: UniqueSaturatedInto<u64>>(
+		block_number: N,
+		status: pallet_migrations::MigrationStatus<N>,
+	) -> Self {
+		let active = status.active.map(|active| ActiveMigration {
+			index: active.index,
+			total: active.total,
+			identifier: active.identifier.map(Into::into),
+			inner_cursor: active.inner_cursor.map(Into::into),
+			started_at: active.started_at.unique_saturated_into(),
+			steps: active.steps,
+			max_steps: active.max_steps,
+			estimated_blocks_remaining: active.estimated_blocks_remaining,
+		});
+		let failures = status
+			.failures
+			.into_iter()
+			.map(|failure| FailedMigration {
+				index: failure.index,
+				identifier: failure.identifier.map(Into::into),
+				failed_at: failure.failed_at.unique_saturated_into(),
+			})
+			.collect();
+
+		Self {
+			block_number: block_number.unique_saturated_into(),
+			active,
+			stuck: status.stuck,
+			failures,
+		}
+	}
+}
+
+/// Provides RPC methods to query the progress of the multi-block migrations.
+pub struct Migrations<C, B> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Migrations<C, B> {
+	/// Creates a new instance of the Migrations Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The requested block is not known.
+	UnknownBlock,
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::UnknownBlock => 2,
+		}
+	}
+}
+
+impl<C, Block> MigrationsApiServer<<Block as BlockT>::Hash> for Migrations<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: MigrationsRuntimeApi<Block>,
+{
+	fn status(&self, at: Option<Block::Hash>) -> RpcResult<MigrationsStatus> {
+		fn map_err(error: impl ToString, desc: &'static str) -> ErrorObjectOwned {
+			ErrorObject::owned(Error::RuntimeError.into(), desc, Some(error.to_string()))
+		}
+
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let number = self
+			.client
+			.number(at_hash)
+			.map_err(|e| map_err(e, "Unable to query the block number."))?
+			.ok_or_else(|| {
+				ErrorObject::owned(
+					Error::UnknownBlock.into(),
+					"Unknown block.",
+					Some(format!("{at_hash:?}")),
+				)
+			})?;
+
+		let status = self
+			.client
+			.runtime_api()
+			.status(at_hash)
+			.map_err(|e| map_err(e, "Unable to query the migrations status."))?;
+
+		Ok(MigrationsStatus::new(number, status))
+	}
+}
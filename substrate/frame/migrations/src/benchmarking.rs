@@ -153,6 +153,7 @@ mod benches {
 		}
 
 		assert_last_event::<T>(Event::UpgradeFailed {}.into());
+		assert_eq!(Failures::<T>::get().len(), 1);
 
 		Ok(())
 	}
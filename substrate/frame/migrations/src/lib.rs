@@ -148,6 +148,7 @@ mod benchmarking;
 pub mod migrations;
 mod mock;
 pub mod mock_helpers;
+pub mod runtime_api;
 mod tests;
 pub mod weights;
 
@@ -281,6 +282,77 @@ impl<Id> HistoricCleanupSelector<Id> {
 	}
 }
 
+/// The maximal number of entries kept in [`Failures`].
+pub const MAX_FAILURE_RECORDS: u32 = 16;
+
+/// A migration that failed and caused its runtime upgrade to fail.
+#[derive(
+	Debug,
+	Clone,
+	Eq,
+	PartialEq,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	scale_info::TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct FailureRecord<Id, BlockNumber> {
+	/// The index of the migration within the [`Config::Migrations`] list.
+	///
+	/// `None` if the upgrade failed without any migration being active, for example because it
+	/// was interrupted by another runtime upgrade while the cursor was stuck.
+	pub index: Option<u32>,
+	/// The identifier of the failed migration, if it could be determined.
+	pub identifier: Option<Id>,
+	/// The block in which the failure happened.
+	pub failed_at: BlockNumber,
+}
+
+/// The progress of the currently active migration.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode, scale_info::TypeInfo)]
+pub struct ActiveMigrationStatus<BlockNumber> {
+	/// The index of the migration within the [`Config::Migrations`] list.
+	pub index: u32,
+	/// The number of migrations in the [`Config::Migrations`] list.
+	pub total: u32,
+	/// The identifier of the migration.
+	pub identifier: Option<Vec<u8>>,
+	/// The inner cursor of the migration.
+	pub inner_cursor: Option<Vec<u8>>,
+	/// The block number that the migration started at.
+	pub started_at: BlockNumber,
+	/// The number of steps that the migration has taken so far.
+	pub steps: u32,
+	/// The [`SteppedMigration::max_steps`] of the migration.
+	pub max_steps: Option<u32>,
+	/// Estimate of the number of blocks until all migrations are done.
+	///
+	/// Derived from the `max_steps` of the active and all following migrations that did not run
+	/// yet, so this is an upper bound. `None` if any of them is unbounded.
+	pub estimated_blocks_remaining: Option<u32>,
+}
+
+/// The status of the multi-block migrations as reported by the [`runtime_api::MigrationsApi`].
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode, scale_info::TypeInfo)]
+pub struct MigrationStatus<BlockNumber> {
+	/// The currently active migration, if any.
+	pub active: Option<ActiveMigrationStatus<BlockNumber>>,
+	/// Whether the migrations are stuck.
+	pub stuck: bool,
+	/// The most recent failures, newest first.
+	pub failures: Vec<FailureRecord<Vec<u8>, BlockNumber>>,
+}
+
+/// Convenience alias for [`FailureRecord`].
+pub type FailureRecordOf<T> = FailureRecord<IdentifierOf<T>, BlockNumberFor<T>>;
+
+/// Convenience alias for [`MigrationStatus`].
+pub type MigrationStatusOf<T> = MigrationStatus<BlockNumberFor<T>>;
+
+/// Convenience alias for the value of [`Failures`].
+pub type FailuresOf<T> = BoundedVec<FailureRecordOf<T>, ConstU32<MAX_FAILURE_RECORDS>>;
+
 /// Convenience alias for [`MigrationCursor`].
 pub type CursorOf<T> = MigrationCursor<RawCursorOf<T>, BlockNumberFor<T>>;
 
@@ -430,6 +502,18 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type Historic<T: Config> = StorageMap<_, Twox64Concat, IdentifierOf<T>, (), OptionQuery>;
 
+	/// The number of steps that the currently active migration has taken.
+	///
+	/// Reset whenever the cursor moves on to the next migration.
+	#[pallet::storage]
+	pub type ActiveSteps<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The most recent migration failures, newest first.
+	///
+	/// Only the last [`MAX_FAILURE_RECORDS`] failures are kept.
+	#[pallet::storage]
+	pub type Failures<T: Config> = StorageValue<_, FailuresOf<T>, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -559,6 +643,7 @@ pub mod pallet {
 			ensure_root(origin)?;
 
 			Cursor::<T>::set(cursor);
+			ActiveSteps::<T>::kill();
 
 			Ok(())
 		}
@@ -586,6 +671,7 @@ pub mod pallet {
 				inner_cursor,
 				started_at,
 			}));
+			ActiveSteps::<T>::kill();
 
 			Ok(())
 		}
@@ -645,7 +731,7 @@ impl<T: Config> Pallet<T> {
 
 			let maybe_index = cursor.as_active().map(|c| c.index);
 			Self::upgrade_failed(maybe_index);
-			return T::WeightInfo::onboard_new_mbms()
+			return T::WeightInfo::onboard_new_mbms().saturating_add(Self::record_failure_weight())
 		}
 
 		let migrations = T::Migrations::len();
@@ -733,6 +819,7 @@ impl<T: Config> Pallet<T> {
 			defensive_assert!(cursor.index == T::Migrations::len(), "Inconsistent MBMs tuple");
 			Self::deposit_event(Event::UpgradeCompleted);
 			Cursor::<T>::kill();
+			ActiveSteps::<T>::kill();
 			T::MigrationStatusHandler::completed();
 			return None;
 		};
@@ -772,6 +859,10 @@ impl<T: Config> Pallet<T> {
 			return None
 		};
 
+		if next_cursor.is_ok() {
+			ActiveSteps::<T>::mutate(|steps| steps.saturating_inc());
+		}
+
 		let took = System::<T>::block_number().saturating_sub(cursor.started_at);
 		match next_cursor {
 			Ok(Some(next_cursor)) => {
@@ -807,6 +898,7 @@ impl<T: Config> Pallet<T> {
 
 				Self::deposit_event(Event::MigrationCompleted { index: cursor.index, took });
 				Historic::<T>::insert(&bounded_id, ());
+				ActiveSteps::<T>::kill();
 				cursor.goto_next_migration(System::<T>::block_number());
 				Some(ControlFlow::Continue(cursor))
 			},
@@ -836,6 +928,17 @@ impl<T: Config> Pallet<T> {
 		use FailedMigrationHandling::*;
 		Self::deposit_event(Event::UpgradeFailed);
 
+		let identifier = migration
+			.and_then(T::Migrations::nth_id)
+			.and_then(|id| IdentifierOf::<T>::try_from(id).ok());
+		let record =
+			FailureRecord { index: migration, identifier, failed_at: System::<T>::block_number() };
+		Failures::<T>::mutate(|failures| {
+			// Drops the oldest record if full.
+			let _ = failures.force_insert_keep_left(0, record);
+		});
+		ActiveSteps::<T>::kill();
+
 		if cfg!(feature = "try-runtime") {
 			panic!("Migration with index {:?} failed.", migration);
 		} else {
@@ -853,7 +956,67 @@ impl<T: Config> Pallet<T> {
 			.max(T::WeightInfo::exec_migration_completed())
 			.max(T::WeightInfo::exec_migration_skipped_historic())
 			.max(T::WeightInfo::exec_migration_advance())
-			.max(T::WeightInfo::exec_migration_fail())
+			.max(T::WeightInfo::exec_migration_fail().saturating_add(Self::record_failure_weight()))
+			// Counting the steps in `ActiveSteps`.
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+
+	/// The weight of recording a failure in [`Failures`] and resetting [`ActiveSteps`] in
+	/// `Self::upgrade_failed`.
+	fn record_failure_weight() -> Weight {
+		T::DbWeight::get()
+			.reads_writes(1, 2)
+			.saturating_add(Weight::from_parts(0, FailuresOf::<T>::max_encoded_len() as u64))
+	}
+
+	/// The status of the multi-block migrations.
+	///
+	/// Exposed to the node through the [`runtime_api::MigrationsApi`].
+	pub fn status() -> MigrationStatusOf<T> {
+		let cursor = Cursor::<T>::get();
+
+		MigrationStatus {
+			active: cursor.as_ref().and_then(|c| c.as_active()).cloned().map(Self::active_status),
+			stuck: matches!(cursor, Some(MigrationCursor::Stuck)),
+			failures: Failures::<T>::get()
+				.into_iter()
+				.map(|failure| FailureRecord {
+					index: failure.index,
+					identifier: failure.identifier.map(|id| id.into_inner()),
+					failed_at: failure.failed_at,
+				})
+				.collect(),
+		}
+	}
+
+	/// The progress of the active migration that `cursor` points to.
+	fn active_status(cursor: ActiveCursorOf<T>) -> ActiveMigrationStatus<BlockNumberFor<T>> {
+		let steps = ActiveSteps::<T>::get();
+		let max_steps = T::Migrations::nth_max_steps(cursor.index).flatten();
+
+		// Migrations that already ran in the past are skipped and do not take any blocks.
+		let estimated_blocks_remaining =
+			max_steps.map(|max| max.saturating_sub(steps)).and_then(|current| {
+				(cursor.index.saturating_add(1)..T::Migrations::len())
+					.filter(|n| {
+						let id = T::Migrations::nth_id(*n)
+							.and_then(|id| IdentifierOf::<T>::try_from(id).ok());
+						!id.is_some_and(|id| Historic::<T>::contains_key(id))
+					})
+					.map(|n| T::Migrations::nth_max_steps(n).flatten())
+					.try_fold(current, |acc, max| max.map(|max| acc.saturating_add(max)))
+			});
+
+		ActiveMigrationStatus {
+			index: cursor.index,
+			total: T::Migrations::len(),
+			identifier: T::Migrations::nth_id(cursor.index),
+			inner_cursor: cursor.inner_cursor.map(|c| c.into_inner()),
+			started_at: cursor.started_at,
+			steps,
+			max_steps,
+			estimated_blocks_remaining,
+		}
 	}
 }
 
@@ -22,7 +22,7 @@ use frame_support::{pallet_prelude::Weight, traits::OnRuntimeUpgrade};
 use crate::{
 	mock::{Test as T, *},
 	mock_helpers::{MockedMigrationKind::*, *},
-	Cursor, Event, FailedMigrationHandling, MigrationCursor,
+	Cursor, Event, FailedMigrationHandling, FailureRecord, MigrationCursor, MigrationStatus,
 };
 
 #[docify::export]
@@ -454,3 +454,63 @@ fn try_runtime_no_migrations() {
 		assert_eq!(System::events().len(), 0);
 	});
 }
+
+#[test]
+fn status_reports_progress() {
+	test_closure(|| {
+		MockedMigrations::set(vec![(SucceedAfter, 1), (SucceedAfter, 2)]);
+		let idle = MigrationStatus { active: None, stuck: false, failures: vec![] };
+		assert_eq!(Migrations::status(), idle);
+
+		System::set_block_number(1);
+		Migrations::on_runtime_upgrade();
+		run_to_block(2);
+
+		let active = Migrations::status().active.unwrap();
+		assert_eq!((active.index, active.total, active.steps), (0, 2, 1));
+		assert_eq!(active.identifier, Some(mocked_id(SucceedAfter, 1).into_inner()));
+		assert_eq!(active.max_steps, Some(1));
+		assert_eq!(active.estimated_blocks_remaining, Some(2));
+
+		run_to_block(3);
+
+		// The first migration completed and the second one took its first step.
+		let active = Migrations::status().active.unwrap();
+		assert_eq!((active.index, active.started_at, active.steps), (1, 3, 1));
+		assert_eq!(active.estimated_blocks_remaining, Some(1));
+
+		run_to_block(10);
+		assert_eq!(Migrations::status(), idle);
+	});
+}
+
+#[test]
+#[cfg_attr(feature = "try-runtime", should_panic)]
+fn status_reports_failures() {
+	test_closure(|| {
+		FailedUpgradeResponse::set(FailedMigrationHandling::KeepStuck);
+		MockedMigrations::set(vec![(SucceedAfter, 0), (FailAfter, 2)]);
+
+		System::set_block_number(1);
+		Migrations::on_runtime_upgrade();
+		run_to_block(10);
+
+		let status = Migrations::status();
+		assert!(status.stuck);
+		assert!(status.active.is_none());
+		assert_eq!(
+			status.failures,
+			vec![FailureRecord {
+				index: Some(1),
+				identifier: Some(mocked_id(FailAfter, 2).into_inner()),
+				failed_at: 4,
+			}]
+		);
+
+		// Another upgrade while stuck fails without an active migration.
+		Migrations::on_runtime_upgrade();
+		let failures = Migrations::status().failures;
+		assert_eq!(failures.len(), 2);
+		assert_eq!(failures[0], FailureRecord { index: None, identifier: None, failed_at: 10 });
+	});
+}
@@ -481,9 +481,19 @@ fn expand_functions(def: &EnvDef) -> TokenStream2 {
 			let trace_fmt_str = format!("{}({}) = {{:?}}\n", name, params_fmt_str);
 
 			quote! {
+				crate::tracing::if_tracing(|tracer| {
+					tracer.enter_syscall(#name, self.ext.gas_meter().gas_left())
+				});
 				// wrap body in closure to make sure the tracing is always executed
 				let result = (|| #body)();
 				::log::trace!(target: "runtime::revive::strace", #trace_fmt_str, #( #trace_fmt_args, )* result);
+				crate::tracing::if_tracing(|tracer| {
+					let error = match &result {
+						Err(TrapReason::SupervisorError(error)) => Some(*error),
+						_ => None,
+					};
+					tracer.exit_syscall(self.ext.gas_meter().gas_left(), error)
+				});
 				result
 			}
 		};
@@ -52,6 +52,16 @@ pub trait EthRpc {
 		block: Option<BlockNumberOrTag>,
 	) -> RpcResult<U256>;
 
+	/// Returns transaction base fee per gas and effective priority fee per gas for the requested
+	/// block range.
+	#[method(name = "eth_feeHistory")]
+	async fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> RpcResult<FeeHistoryResult>;
+
 	/// Returns the current price per gas in wei.
 	#[method(name = "eth_gasPrice")]
 	async fn gas_price(&self) -> RpcResult<U256>;
@@ -77,6 +87,13 @@ pub trait EthRpc {
 		hydrated_transactions: bool,
 	) -> RpcResult<Option<Block>>;
 
+	/// Returns the receipts of a block by number, tag or hash.
+	#[method(name = "eth_getBlockReceipts")]
+	async fn get_block_receipts(
+		&self,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<Option<Vec<ReceiptInfo>>>;
+
 	/// Returns the number of transactions in a block from a block matching the given block hash.
 	#[method(name = "eth_getBlockTransactionCountByHash")]
 	async fn get_block_transaction_count_by_hash(
@@ -95,6 +112,11 @@ pub trait EthRpc {
 	#[method(name = "eth_getCode")]
 	async fn get_code(&self, address: Address, block: BlockNumberOrTagOrHash) -> RpcResult<Bytes>;
 
+	/// Polling method for a filter, which returns an array of logs or block hashes which occurred
+	/// since last poll.
+	#[method(name = "eth_getFilterChanges")]
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults>;
+
 	/// Returns an array of all logs matching filter with given id.
 	#[method(name = "eth_getLogs")]
 	async fn get_logs(&self, filter: Option<Filter>) -> RpcResult<FilterResults>;
@@ -150,6 +172,16 @@ pub trait EthRpc {
 	#[method(name = "eth_maxPriorityFeePerGas")]
 	async fn max_priority_fee_per_gas(&self) -> RpcResult<U256>;
 
+	/// Creates a filter in the node, to notify when a new block arrives.
+	#[method(name = "eth_newBlockFilter")]
+	async fn new_block_filter(&self) -> RpcResult<U256>;
+
+	/// Creates a filter object, based on filter options, to notify when the state changes (logs).
+	///
+	/// Filters with a `blockHash` are rejected, use `eth_getLogs` to query the logs of a block.
+	#[method(name = "eth_newFilter")]
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256>;
+
 	/// Submits a raw transaction. For EIP-4844 transactions, the raw form must be the network form.
 	/// This means it includes the blobs, KZG commitments, and KZG proofs.
 	#[method(name = "eth_sendRawTransaction")]
@@ -163,6 +195,10 @@ pub trait EthRpc {
 	#[method(name = "eth_syncing")]
 	async fn syncing(&self) -> RpcResult<SyncingStatus>;
 
+	/// Uninstalls a filter with given id.
+	#[method(name = "eth_uninstallFilter")]
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool>;
+
 	/// The string value of current network id
 	#[method(name = "net_version")]
 	async fn net_version(&self) -> RpcResult<String>;
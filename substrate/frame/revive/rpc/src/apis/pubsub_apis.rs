@@ -0,0 +1,152 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::*;
+use futures::{stream, StreamExt};
+use jsonrpsee::{core::SubscriptionResult, proc_macros::rpc, PendingSubscriptionSink};
+use sc_rpc::utils::{BoundedVecDeque, PendingSubscription};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of notifications buffered for a subscriber before the subscription is
+/// dropped.
+const SUBSCRIPTION_BUFFER_SIZE: usize = 1024;
+
+/// The kind of notifications requested with `eth_subscribe`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+	/// New block headers.
+	NewHeads,
+	/// Logs of the new blocks matching the given filter.
+	Logs,
+	/// Hashes of the transactions submitted to the pool.
+	NewPendingTransactions,
+}
+
+/// A notification sent to the `eth_subscribe` subscribers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SubscriptionItem {
+	/// The hash of a pending transaction.
+	TransactionHash(H256),
+	/// A log of a new block.
+	Log(Log),
+	/// The header of a new block.
+	Header(Box<Block>),
+}
+
+/// Ethereum publish-subscribe JSON-RPC apis.
+#[rpc(server, client)]
+pub trait EthPubSub {
+	/// Subscribes to the given kind of notifications.
+	///
+	/// The `logs` subscription accepts an optional filter. Its `address` and `topics` are applied
+	/// to the logs of every new block, the block range fields are ignored.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/pubsub>
+	#[subscription(
+		name = "eth_subscribe" => "eth_subscription",
+		unsubscribe = "eth_unsubscribe",
+		item = SubscriptionItem
+	)]
+	async fn subscribe_notifications(&self, kind: SubscriptionKind, filter: Option<Filter>);
+}
+
+pub struct EthPubSubServerImpl {
+	client: client::Client,
+}
+
+impl EthPubSubServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+#[async_trait]
+impl EthPubSubServer for EthPubSubServerImpl {
+	async fn subscribe_notifications(
+		&self,
+		pending: PendingSubscriptionSink,
+		kind: SubscriptionKind,
+		filter: Option<Filter>,
+	) -> SubscriptionResult {
+		log::debug!(target: LOG_TARGET, "eth_subscribe: {kind:?} filter: {filter:?}");
+		if filter.is_some() && kind != SubscriptionKind::Logs {
+			let err = ErrorObjectOwned::owned::<String>(
+				ErrorCode::InvalidParams.code(),
+				format!("{kind:?} subscription does not accept a filter"),
+				None,
+			);
+			pending.reject(err).await;
+			return Ok(());
+		}
+
+		let client = self.client.clone();
+		let pending = PendingSubscription::from(pending);
+		let buffer = || BoundedVecDeque::new(SUBSCRIPTION_BUFFER_SIZE);
+
+		match kind {
+			SubscriptionKind::NewHeads => {
+				let heads = client
+					.block_notifications()
+					.filter_map(move |hash| {
+						let client = client.clone();
+						async move {
+							let block = client.block_by_hash(&hash).await.ok()??;
+							let block = client.evm_block(block, false).await;
+							Some(SubscriptionItem::Header(Box::new(block)))
+						}
+					})
+					.boxed();
+				pending.pipe_from_stream(heads, buffer()).await;
+			},
+			SubscriptionKind::Logs => {
+				let filter = filter.unwrap_or_default();
+				let logs = client
+					.block_notifications()
+					.then(move |hash| {
+						let client = client.clone();
+						let filter = Filter {
+							block_hash: Some(hash),
+							from_block: None,
+							to_block: None,
+							..filter.clone()
+						};
+						async move {
+							client.logs(Some(filter)).await.unwrap_or_else(|err| {
+								log::debug!(target: LOG_TARGET, "Failed to fetch logs of {hash:?}: {err:?}");
+								Vec::new()
+							})
+						}
+					})
+					.flat_map(|logs| stream::iter(logs.into_iter().map(SubscriptionItem::Log)))
+					.boxed();
+				pending.pipe_from_stream(logs, buffer()).await;
+			},
+			SubscriptionKind::NewPendingTransactions => {
+				let hashes = client
+					.pending_transaction_notifications()
+					.map(SubscriptionItem::TransactionHash)
+					.boxed();
+				pending.pipe_from_stream(hashes, buffer()).await;
+			},
+		}
+
+		Ok(())
+	}
+}
@@ -23,12 +23,13 @@ use crate::{
 	BlockInfoProvider, ReceiptExtractor, ReceiptProvider, TransactionInfo, LOG_TARGET,
 };
 use codec::{Decode, Encode};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use jsonrpsee::types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObjectOwned};
 use pallet_revive::{
 	evm::{
-		decode_revert_reason, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, CallTrace, Filter,
-		GenericTransaction, Log, ReceiptInfo, SyncingProgress, SyncingStatus, TracerConfig,
-		TransactionSigned, TransactionTrace, H160, H256, U256,
+		decode_revert_reason, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, CallTrace,
+		FeeHistoryResult, Filter, GenericTransaction, Log, ReceiptInfo, SyncingProgress,
+		SyncingStatus, Trace, TracerConfig, TransactionSigned, TransactionTrace, H160, H256, U256,
 	},
 	EthTransactError, EthTransactInfo,
 };
@@ -49,7 +50,7 @@ use subxt::{
 	Config, OnlineClient,
 };
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 
 use crate::subxt_client::{self, SrcChainConfig};
 
@@ -68,6 +69,16 @@ pub type Shared<T> = Arc<RwLock<T>>;
 /// The runtime balance type.
 pub type Balance = u128;
 
+/// The capacity of the channels used to notify subscribers about new blocks and pending
+/// transactions.
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 1024;
+
+/// The maximum number of blocks that can be queried with a single `eth_feeHistory` call.
+const MAX_FEE_HISTORY_BLOCK_COUNT: u32 = 128;
+
+/// The maximum number of blocks whose fees are fetched concurrently by `eth_feeHistory`.
+const FEE_HISTORY_CONCURRENCY: usize = 16;
+
 /// The subscription type used to listen to new blocks.
 pub enum SubscriptionType {
 	/// Subscribe to the best blocks.
@@ -137,9 +148,19 @@ pub enum ClientError {
 	/// Failed to filter logs.
 	#[error("Failed to filter logs")]
 	LogFilterFailed(#[from] anyhow::Error),
+	/// The reward percentiles are not monotonically increasing values in the [0, 100] range.
+	#[error("invalid reward percentiles")]
+	InvalidRewardPercentiles,
+	/// The tracer is not supported by the runtime.
+	#[error("tracer not supported by the runtime")]
+	TracerNotSupported,
 }
 
 const REVERT_CODE: i32 = 3;
+
+/// The `ReviveApi` version from which the trace methods support all the tracers and return a
+/// [`Trace`]. Older versions only support the call tracer and return a [`CallTrace`].
+const TRACE_API_VERSION: u32 = 2;
 impl From<ClientError> for ErrorObjectOwned {
 	fn from(err: ClientError) -> Self {
 		match err {
@@ -181,6 +202,28 @@ pub struct Client {
 	receipt_extractor: ReceiptExtractor,
 	chain_id: u64,
 	max_block_weight: Weight,
+	/// Notifies the hashes of the blocks cached by [`Client::subscribe_and_cache_new_blocks`].
+	block_notifier: broadcast::Sender<SubstrateBlockHash>,
+	/// Notifies the hashes of the Ethereum transactions submitted through this client.
+	pending_tx_notifier: broadcast::Sender<H256>,
+}
+
+/// Convert a broadcast receiver into a stream, skipping the notifications missed by a lagging
+/// receiver.
+fn broadcast_stream<T: Clone + Send + 'static>(
+	receiver: broadcast::Receiver<T>,
+) -> impl Stream<Item = T> + Send {
+	stream::unfold(receiver, |mut receiver| async move {
+		loop {
+			match receiver.recv().await {
+				Ok(item) => return Some((item, receiver)),
+				Err(broadcast::error::RecvError::Lagged(skipped)) => {
+					log::debug!(target: LOG_TARGET, "Subscriber lagged, skipped {skipped} notifications");
+				},
+				Err(broadcast::error::RecvError::Closed) => return None,
+			}
+		}
+	})
 }
 
 /// Fetch the chain ID from the substrate chain.
@@ -203,6 +246,37 @@ async fn max_block_weight(api: &OnlineClient<SrcChainConfig>) -> Result<Weight,
 	Ok(max_block.0)
 }
 
+/// Compute the effective priority fees per gas paid at the given percentiles of the block's gas
+/// used.
+fn block_rewards(
+	receipts: &[ReceiptInfo],
+	base_fee: U256,
+	gas_used: U256,
+	percentiles: &[f64],
+) -> Vec<U256> {
+	let mut fees = receipts
+		.iter()
+		.map(|receipt| (receipt.effective_gas_price.saturating_sub(base_fee), receipt.gas_used))
+		.collect::<Vec<_>>();
+	fees.sort_by_key(|(priority_fee, _)| *priority_fee);
+
+	percentiles
+		.iter()
+		.map(|percentile| {
+			let threshold = gas_used.low_u128() as f64 * percentile / 100.0;
+			let mut cumulative_gas = 0f64;
+			fees.iter()
+				.find(|(_, gas)| {
+					cumulative_gas += gas.low_u128() as f64;
+					cumulative_gas >= threshold
+				})
+				.or(fees.last())
+				.map(|(priority_fee, _)| *priority_fee)
+				.unwrap_or_default()
+		})
+		.collect()
+}
+
 /// Extract the block timestamp.
 async fn extract_block_timestamp(block: &SubstrateBlock) -> Option<u64> {
 	let extrinsics = block.extrinsics().await.ok()?;
@@ -246,6 +320,9 @@ impl Client {
 		let (chain_id, max_block_weight) =
 			tokio::try_join!(chain_id(&api), max_block_weight(&api))?;
 
+		let (block_notifier, _) = broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY);
+		let (pending_tx_notifier, _) = broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY);
+
 		Ok(Self {
 			api,
 			rpc_client,
@@ -255,6 +332,8 @@ impl Client {
 			receipt_extractor,
 			chain_id,
 			max_block_weight,
+			block_notifier,
+			pending_tx_notifier,
 		})
 	}
 
@@ -353,11 +432,14 @@ impl Client {
 			.subscribe_new_blocks(subscription_type, |block| async {
 				let receipts = self.receipt_extractor.extract_from_block(&block).await?;
 
-				self.receipt_provider.insert(&block.hash(), &receipts).await;
+				let block_hash = block.hash();
+				self.receipt_provider.insert(&block_hash, &receipts).await;
 				if let Some(pruned) = self.block_provider.cache_block(block).await {
 					self.receipt_provider.remove(&pruned).await;
 				}
 
+				// Sending only fails when there are no subscribers.
+				let _ = self.block_notifier.send(block_hash);
 				Ok(())
 			})
 			.await;
@@ -454,6 +536,21 @@ impl Client {
 		Ok(hash)
 	}
 
+	/// Notify the subscribers that the Ethereum transaction with the given hash was submitted.
+	pub fn notify_pending_transaction(&self, tx_hash: H256) {
+		let _ = self.pending_tx_notifier.send(tx_hash);
+	}
+
+	/// Stream the hashes of the new blocks, once they are cached and their receipts are indexed.
+	pub fn block_notifications(&self) -> impl Stream<Item = SubstrateBlockHash> + Send {
+		broadcast_stream(self.block_notifier.subscribe())
+	}
+
+	/// Stream the hashes of the Ethereum transactions submitted through this client.
+	pub fn pending_transaction_notifications(&self) -> impl Stream<Item = H256> + Send {
+		broadcast_stream(self.pending_tx_notifier.subscribe())
+	}
+
 	/// Get an EVM transaction receipt by hash.
 	pub async fn receipt(&self, tx_hash: &H256) -> Option<ReceiptInfo> {
 		self.receipt_provider.receipt_by_hash(tx_hash).await
@@ -629,6 +726,24 @@ impl Client {
 		}
 	}
 
+	/// Get a block for the specified number, tag or hash.
+	pub async fn block_by_number_or_tag_or_hash(
+		&self,
+		block: &BlockNumberOrTagOrHash,
+	) -> Result<Option<Arc<SubstrateBlock>>, ClientError> {
+		match block {
+			BlockNumberOrTagOrHash::H256(hash) => self.block_by_hash(hash).await,
+			BlockNumberOrTagOrHash::U256(n) => {
+				let n = (*n).try_into().map_err(|_| ClientError::ConversionFailed)?;
+				self.block_by_number(n).await
+			},
+			BlockNumberOrTagOrHash::BlockTag(_) => {
+				let block = self.block_provider.latest_block().await;
+				Ok(block)
+			},
+		}
+	}
+
 	/// Get a block by hash
 	pub async fn block_by_hash(
 		&self,
@@ -682,6 +797,7 @@ impl Client {
 			.filter_map(|e| OpaqueExtrinsic::decode(&mut &e[..]).ok())
 			.collect::<Vec<_>>();
 
+		let returns_traces = self.returns_traces(Some(parent_hash), &tracer_config).await?;
 		let params = ((header, exts), tracer_config).encode();
 
 		let bytes = self
@@ -692,7 +808,14 @@ impl Client {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
-		let traces = Vec::<(u32, CallTrace)>::decode(&mut &bytes[..])?;
+		let traces = if returns_traces {
+			Vec::<(u32, Trace)>::decode(&mut &bytes[..])?
+		} else {
+			Vec::<(u32, CallTrace)>::decode(&mut &bytes[..])?
+				.into_iter()
+				.map(|(index, trace)| (index, Trace::Call(trace)))
+				.collect()
+		};
 
 		let mut hashes = self
 			.receipt_provider
@@ -715,7 +838,7 @@ impl Client {
 		&self,
 		transaction_hash: H256,
 		tracer_config: TracerConfig,
-	) -> Result<CallTrace, ClientError> {
+	) -> Result<Trace, ClientError> {
 		let ReceiptInfo { block_hash, transaction_index, .. } = self
 			.receipt_provider
 			.receipt_by_hash(&transaction_hash)
@@ -740,6 +863,7 @@ impl Client {
 			.filter_map(|e| OpaqueExtrinsic::decode(&mut &e[..]).ok())
 			.collect::<Vec<_>>();
 
+		let returns_traces = self.returns_traces(Some(parent_hash), &tracer_config).await?;
 		let params = ((header, exts), transaction_index.as_u32(), tracer_config).encode();
 		let bytes = self
 			.rpc
@@ -749,7 +873,11 @@ impl Client {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
-		let trace = Option::<CallTrace>::decode(&mut &bytes[..])?;
+		let trace = if returns_traces {
+			Option::<Trace>::decode(&mut &bytes[..])?
+		} else {
+			Option::<CallTrace>::decode(&mut &bytes[..])?.map(Trace::Call)
+		};
 		trace.ok_or(ClientError::EthExtrinsicNotFound)
 	}
 
@@ -759,7 +887,7 @@ impl Client {
 		transaction: GenericTransaction,
 		block: BlockNumberOrTag,
 		tracer_config: TracerConfig,
-	) -> Result<CallTrace, ClientError> {
+	) -> Result<Trace, ClientError> {
 		let block_hash = match block {
 			BlockNumberOrTag::U256(n) => {
 				let block_number: SubstrateBlockNumber =
@@ -769,6 +897,7 @@ impl Client {
 			BlockNumberOrTag::BlockTag(_) => self.latest_block().await.map(|b| b.hash()),
 		};
 
+		let returns_traces = self.returns_traces(block_hash, &tracer_config).await?;
 		let params = (transaction, tracer_config).encode();
 		let bytes = self
 			.rpc
@@ -778,8 +907,39 @@ impl Client {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
-		Result::<CallTrace, EthTransactError>::decode(&mut &bytes[..])?
-			.map_err(ClientError::TransactError)
+		if returns_traces {
+			Result::<Trace, EthTransactError>::decode(&mut &bytes[..])?
+		} else {
+			Result::<CallTrace, EthTransactError>::decode(&mut &bytes[..])?.map(Trace::Call)
+		}
+		.map_err(ClientError::TransactError)
+	}
+
+	/// Get the version of the `ReviveApi` runtime API at the given block.
+	async fn revive_api_version(&self, at: Option<SubstrateBlockHash>) -> Result<u32, ClientError> {
+		let bytes = self.rpc.state_call("Core_version", None, at).await?;
+		let version = sp_version::RuntimeVersion::decode(&mut &bytes[..])?;
+		let api_id = sp_crypto_hashing::blake2_64(b"ReviveApi");
+		Ok(version.api_version(&api_id).unwrap_or_default())
+	}
+
+	/// Whether the trace methods of the `ReviveApi` at the given block return a [`Trace`] rather
+	/// than a [`CallTrace`].
+	///
+	/// Returns an error if the given tracer is not supported by the runtime.
+	async fn returns_traces(
+		&self,
+		at: Option<SubstrateBlockHash>,
+		tracer_config: &TracerConfig,
+	) -> Result<bool, ClientError> {
+		if self.revive_api_version(at).await? >= TRACE_API_VERSION {
+			return Ok(true);
+		}
+
+		match tracer_config {
+			TracerConfig::CallTracer { .. } => Ok(false),
+			_ => Err(ClientError::TracerNotSupported),
+		}
 	}
 	/// Get the EVM block for the given hash.
 	pub async fn evm_block(
@@ -851,6 +1011,97 @@ impl Client {
 		self.max_block_weight
 	}
 
+	/// Get the receipts of all the Ethereum transactions of the given block.
+	pub async fn block_receipts(
+		&self,
+		block: &SubstrateBlock,
+	) -> Result<Vec<ReceiptInfo>, ClientError> {
+		if let Some(receipts) = self.receipt_provider.receipts_by_block_hash(&block.hash()).await {
+			return Ok(receipts);
+		}
+
+		// The block is not indexed by the receipt provider.
+		let receipts = self.receipt_extractor.extract_from_block(block).await?;
+		Ok(receipts.into_iter().map(|(_, receipt)| receipt).collect())
+	}
+
+	/// Get the fee history of the `block_count` blocks ending with `newest_block`.
+	///
+	/// The rewards are the effective priority fees per gas paid at the given percentiles of the
+	/// gas used in each block.
+	pub async fn fee_history(
+		&self,
+		block_count: u32,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> Result<FeeHistoryResult, ClientError> {
+		let reward_percentiles = reward_percentiles.unwrap_or_default();
+		let valid_percentiles = reward_percentiles.iter().all(|p| (0.0..=100.0).contains(p)) &&
+			reward_percentiles.windows(2).all(|w| w[0] <= w[1]);
+		if !valid_percentiles {
+			return Err(ClientError::InvalidRewardPercentiles);
+		}
+
+		let newest_block = self
+			.block_by_number_or_tag(&newest_block)
+			.await?
+			.ok_or(ClientError::BlockNotFound)?;
+		let newest_number = newest_block.number();
+		let block_count = block_count.min(MAX_FEE_HISTORY_BLOCK_COUNT).min(newest_number + 1);
+		if block_count == 0 {
+			return Ok(Default::default());
+		}
+
+		let oldest_number = newest_number + 1 - block_count;
+		let mut result =
+			FeeHistoryResult { oldest_block: oldest_number.into(), ..Default::default() };
+
+		let reward_percentiles = &reward_percentiles;
+		let newest_block = &newest_block;
+		let blocks = stream::iter(oldest_number..=newest_number)
+			.map(|number| async move {
+				let block = if number == newest_number {
+					newest_block.clone()
+				} else {
+					self.block_by_number(number).await?.ok_or(ClientError::BlockNotFound)?
+				};
+
+				let base_fee = self.gas_price(&block.hash().into()).await?;
+				let runtime_api = self.api.runtime_api().at(block.hash());
+				let gas_limit = Self::block_gas_limit(&runtime_api).await?;
+				let receipts = self.block_receipts(&block).await?;
+				let gas_used =
+					receipts.iter().fold(U256::zero(), |acc, receipt| acc + receipt.gas_used);
+
+				let gas_used_ratio = if gas_limit.is_zero() {
+					0.0
+				} else {
+					gas_used.low_u128() as f64 / gas_limit.low_u128() as f64
+				};
+				let rewards = (!reward_percentiles.is_empty())
+					.then(|| block_rewards(&receipts, base_fee, gas_used, reward_percentiles));
+
+				Ok::<_, ClientError>((base_fee, gas_used_ratio, rewards))
+			})
+			// `buffered` keeps the blocks in order, from the oldest to the newest.
+			.buffered(FEE_HISTORY_CONCURRENCY)
+			.try_collect::<Vec<_>>()
+			.await?;
+
+		for (base_fee, gas_used_ratio, rewards) in blocks {
+			result.base_fee_per_gas.push(base_fee);
+			result.gas_used_ratio.push(gas_used_ratio);
+			result.reward.extend(rewards);
+		}
+
+		// The base fee of the next block is not known yet, assume it stays the same.
+		if let Some(base_fee) = result.base_fee_per_gas.last().copied() {
+			result.base_fee_per_gas.push(base_fee);
+		}
+
+		Ok(result)
+	}
+
 	/// Get the logs matching the given filter.
 	pub async fn logs(&self, filter: Option<Filter>) -> Result<Vec<Log>, ClientError> {
 		let logs =
@@ -858,3 +1109,28 @@ impl Client {
 		Ok(logs)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn receipt(effective_gas_price: u64, gas_used: u64) -> ReceiptInfo {
+		ReceiptInfo {
+			effective_gas_price: effective_gas_price.into(),
+			gas_used: gas_used.into(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn block_rewards_works() {
+		let receipts = [receipt(130, 300), receipt(110, 100), receipt(120, 600)];
+		let percentiles = [0.0, 10.0, 50.0, 100.0];
+		let rewards = block_rewards(&receipts, U256::from(100), U256::from(1_000), &percentiles);
+		assert_eq!(rewards, [10u64, 10, 20, 30].map(U256::from).to_vec());
+
+		// Empty blocks have no rewards.
+		let rewards = block_rewards(&[], U256::from(100), U256::zero(), &[50.0]);
+		assert_eq!(rewards, vec![U256::zero()]);
+	}
+}
@@ -0,0 +1,173 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	client::{Client, SubstrateBlockNumber},
+	EthRpcError, LOG_TARGET,
+};
+use pallet_revive::evm::{BlockNumberOrTag, Filter, FilterResults, U256};
+use std::{
+	collections::HashMap,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+/// Filters that were not polled for this duration are uninstalled.
+const FILTER_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The maximum number of filters that can be installed at the same time.
+const MAX_FILTERS: usize = 1024;
+
+/// The kind of an installed filter.
+#[derive(Clone, Debug)]
+pub enum FilterKind {
+	/// Notifies the hashes of the new blocks.
+	Block,
+	/// Notifies the new logs matching the filter.
+	Log(Filter),
+}
+
+/// A filter installed with `eth_newFilter` or `eth_newBlockFilter`.
+struct InstalledFilter {
+	/// The kind of the filter.
+	kind: FilterKind,
+	/// The latest block reported by the filter.
+	last_polled_block: SubstrateBlockNumber,
+	/// The last time the filter was polled.
+	last_poll: Instant,
+}
+
+/// The set of installed filters.
+#[derive(Default)]
+struct Filters {
+	/// The installed filters, by id.
+	filters: HashMap<U256, InstalledFilter>,
+}
+
+/// Keeps track of the filters polled with `eth_getFilterChanges`.
+#[derive(Clone, Default)]
+pub struct FilterManager {
+	filters: Arc<Mutex<Filters>>,
+}
+
+impl FilterManager {
+	/// Install a new filter, and return its id.
+	///
+	/// Only the changes happening after the current latest block are reported by the filter, so
+	/// log filters pinned to a block with `blockHash` are rejected. The id is a random 128-bit
+	/// number, so that the filters of other clients can not be guessed.
+	pub async fn install(&self, client: &Client, kind: FilterKind) -> Result<U256, EthRpcError> {
+		if let FilterKind::Log(Filter { block_hash: Some(_), .. }) = kind {
+			return Err(EthRpcError::InvalidParams(
+				"blockHash is not supported by eth_newFilter".to_string(),
+			));
+		}
+
+		let latest_block = client.block_number().await?;
+
+		let mut filters = self.filters.lock().await;
+		filters.filters.retain(|_, filter| filter.last_poll.elapsed() < FILTER_TIMEOUT);
+		if filters.filters.len() >= MAX_FILTERS {
+			return Err(EthRpcError::TooManyFilters);
+		}
+
+		let id = loop {
+			let id = U256::from(rand::random::<u128>());
+			if !id.is_zero() && !filters.filters.contains_key(&id) {
+				break id;
+			}
+		};
+		filters.filters.insert(
+			id,
+			InstalledFilter { kind, last_polled_block: latest_block, last_poll: Instant::now() },
+		);
+
+		log::debug!(target: LOG_TARGET, "Installed filter {id:?} at block #{latest_block}");
+		Ok(id)
+	}
+
+	/// Uninstall the filter with the given id, returns `false` if it was not installed.
+	pub async fn uninstall(&self, id: U256) -> bool {
+		self.filters.lock().await.filters.remove(&id).is_some()
+	}
+
+	/// Get the changes of the filter with the given id since it was last polled.
+	pub async fn changes(&self, client: &Client, id: U256) -> Result<FilterResults, EthRpcError> {
+		let (kind, last_polled_block) = {
+			let mut filters = self.filters.lock().await;
+			let filter = filters
+				.filters
+				.get_mut(&id)
+				.filter(|filter| filter.last_poll.elapsed() < FILTER_TIMEOUT)
+				.ok_or(EthRpcError::FilterNotFound(id))?;
+			filter.last_poll = Instant::now();
+			(filter.kind.clone(), filter.last_polled_block)
+		};
+
+		let latest_block = client.block_number().await?;
+		if latest_block <= last_polled_block {
+			return Ok(match kind {
+				FilterKind::Block => FilterResults::Hashes(Vec::new()),
+				FilterKind::Log(_) => FilterResults::Logs(Vec::new()),
+			});
+		}
+
+		let results = match kind {
+			FilterKind::Block => {
+				let mut hashes = Vec::new();
+				for number in last_polled_block + 1..=latest_block {
+					if let Some(hash) = client.get_block_hash(number).await? {
+						hashes.push(hash);
+					}
+				}
+				FilterResults::Hashes(hashes)
+			},
+			FilterKind::Log(filter) => {
+				let to_block = match filter.to_block {
+					Some(BlockNumberOrTag::U256(to_block)) =>
+						to_block.min(U256::from(latest_block)),
+					_ => U256::from(latest_block),
+				};
+				let from_block = match filter.from_block {
+					Some(BlockNumberOrTag::U256(from_block)) =>
+						from_block.max(U256::from(last_polled_block + 1)),
+					_ => U256::from(last_polled_block + 1),
+				};
+
+				let logs = if from_block > to_block {
+					Vec::new()
+				} else {
+					let filter = Filter {
+						from_block: Some(from_block.into()),
+						to_block: Some(to_block.into()),
+						block_hash: None,
+						..filter
+					};
+					client.logs(Some(filter)).await?
+				};
+				FilterResults::Logs(logs)
+			},
+		};
+
+		if let Some(filter) = self.filters.lock().await.filters.get_mut(&id) {
+			filter.last_polled_block = filter.last_polled_block.max(latest_block);
+		}
+
+		Ok(results)
+	}
+}
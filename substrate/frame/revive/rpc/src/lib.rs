@@ -38,6 +38,9 @@ mod tests;
 mod block_info_provider;
 pub use block_info_provider::*;
 
+mod filter_manager;
+pub use filter_manager::*;
+
 mod receipt_provider;
 pub use receipt_provider::*;
 
@@ -56,12 +59,15 @@ pub struct EthRpcServerImpl {
 
 	/// The accounts managed by the server.
 	accounts: Vec<Account>,
+
+	/// The filters installed with `eth_newFilter` and `eth_newBlockFilter`.
+	filters: FilterManager,
 }
 
 impl EthRpcServerImpl {
 	/// Creates a new [`EthRpcServerImpl`].
 	pub fn new(client: client::Client) -> Self {
-		Self { client, accounts: vec![] }
+		Self { client, accounts: vec![], filters: Default::default() }
 	}
 
 	/// Sets the accounts managed by the server.
@@ -95,6 +101,15 @@ pub enum EthRpcError {
 	/// Received an invalid transaction
 	#[error("Invalid transaction {0:?}")]
 	TransactionTypeNotSupported(Byte),
+	/// The filter was not found, or it expired.
+	#[error("Filter not found {0:?}")]
+	FilterNotFound(U256),
+	/// The maximum number of installed filters was reached.
+	#[error("Too many filters")]
+	TooManyFilters,
+	/// The parameters of the request are not supported.
+	#[error("Invalid params: {0}")]
+	InvalidParams(String),
 }
 
 // TODO use https://eips.ethereum.org/EIPS/eip-1474#error-codes
@@ -102,6 +117,8 @@ impl From<EthRpcError> for ErrorObjectOwned {
 	fn from(value: EthRpcError) -> Self {
 		match value {
 			EthRpcError::ClientError(err) => Self::from(err),
+			EthRpcError::InvalidParams(_) =>
+				Self::owned::<String>(ErrorCode::InvalidParams.code(), value.to_string(), None),
 			_ => Self::owned::<String>(ErrorCode::InvalidRequest.code(), value.to_string(), None),
 		}
 	}
@@ -166,6 +183,7 @@ impl EthRpcServer for EthRpcServerImpl {
 		})?;
 
 		log::debug!(target: LOG_TARGET, "send_raw_transaction hash: {hash:?}");
+		self.client.notify_pending_transaction(hash);
 		Ok(hash)
 	}
 
@@ -284,6 +302,44 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(self.client.receipts_count_per_block(&block.hash).await.map(U256::from))
 	}
 
+	async fn get_block_receipts(
+		&self,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<Option<Vec<ReceiptInfo>>> {
+		let Some(block) = self.client.block_by_number_or_tag_or_hash(&block).await? else {
+			return Ok(None);
+		};
+		let receipts = self.client.block_receipts(&block).await?;
+		Ok(Some(receipts))
+	}
+
+	async fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> RpcResult<FeeHistoryResult> {
+		let block_count = block_count.try_into().unwrap_or(u32::MAX);
+		let result = self.client.fee_history(block_count, newest_block, reward_percentiles).await?;
+		Ok(result)
+	}
+
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256> {
+		Ok(self.filters.install(&self.client, FilterKind::Log(filter)).await?)
+	}
+
+	async fn new_block_filter(&self) -> RpcResult<U256> {
+		Ok(self.filters.install(&self.client, FilterKind::Block).await?)
+	}
+
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		Ok(self.filters.changes(&self.client, filter_id).await?)
+	}
+
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool> {
+		Ok(self.filters.uninstall(filter_id).await)
+	}
+
 	async fn get_logs(&self, filter: Option<Filter>) -> RpcResult<FilterResults> {
 		let logs = self.client.logs(filter).await?;
 		Ok(FilterResults::Logs(logs))
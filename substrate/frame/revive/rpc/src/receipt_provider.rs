@@ -54,6 +54,11 @@ pub trait ReceiptProvider: Send + Sync {
 	/// Get the number of receipts per block.
 	async fn receipts_count_per_block(&self, block_hash: &H256) -> Option<usize>;
 
+	/// Get the receipts of the given block hash, ordered by transaction index.
+	///
+	/// Returns `None` if the receipts of the block are not known to the provider.
+	async fn receipts_by_block_hash(&self, block_hash: &H256) -> Option<Vec<ReceiptInfo>>;
+
 	/// Get the receipt for the given transaction hash.
 	async fn receipt_by_hash(&self, transaction_hash: &H256) -> Option<ReceiptInfo>;
 
@@ -96,6 +101,13 @@ impl<Cache: ReceiptProvider, Archive: ReceiptProvider> ReceiptProvider for (Cach
 		self.1.receipts_count_per_block(block_hash).await
 	}
 
+	async fn receipts_by_block_hash(&self, block_hash: &H256) -> Option<Vec<ReceiptInfo>> {
+		if let Some(receipts) = self.0.receipts_by_block_hash(block_hash).await {
+			return Some(receipts);
+		}
+		self.1.receipts_by_block_hash(block_hash).await
+	}
+
 	async fn block_transaction_hashes(&self, block_hash: &H256) -> Option<HashMap<usize, H256>> {
 		if let Some(hashes) = self.0.block_transaction_hashes(block_hash).await {
 			return Some(hashes);
@@ -70,6 +70,18 @@ impl ReceiptProvider for CacheReceiptProvider {
 		cache.transaction_hashes_by_block_and_index.get(block_hash).map(|v| v.len())
 	}
 
+	async fn receipts_by_block_hash(&self, block_hash: &H256) -> Option<Vec<ReceiptInfo>> {
+		let cache = self.cache().await;
+		let mut receipts = cache
+			.transaction_hashes_by_block_and_index
+			.get(block_hash)?
+			.iter()
+			.map(|(index, hash)| Some((*index, cache.receipts_by_hash.get(hash)?.clone())))
+			.collect::<Option<Vec<_>>>()?;
+		receipts.sort_by_key(|(index, _)| *index);
+		Some(receipts.into_iter().map(|(_, receipt)| receipt).collect())
+	}
+
 	async fn block_transaction_hashes(&self, block_hash: &H256) -> Option<HashMap<usize, H256>> {
 		let cache = self.cache().await;
 		cache.transaction_hashes_by_block_and_index.get(block_hash).cloned()
@@ -158,4 +170,34 @@ mod test {
 		assert_eq!(cache.receipts_by_hash.len(), 2);
 		assert_eq!(cache.signed_tx_by_hash.len(), 2);
 	}
+
+	#[tokio::test]
+	async fn receipts_by_block_hash_are_ordered_by_index() {
+		let provider = CacheReceiptProvider::default();
+		let block_hash = H256::from([1u8; 32]);
+		let receipts = (0u8..3)
+			.rev()
+			.map(|i| {
+				(
+					TransactionSigned::default(),
+					ReceiptInfo {
+						transaction_hash: H256::from([i + 1; 32]),
+						transaction_index: i.into(),
+						..Default::default()
+					},
+				)
+			})
+			.collect::<Vec<_>>();
+		provider.insert(&block_hash, &receipts).await;
+
+		let indexes = provider
+			.receipts_by_block_hash(&block_hash)
+			.await
+			.unwrap()
+			.into_iter()
+			.map(|receipt| receipt.transaction_index.as_usize())
+			.collect::<Vec<_>>();
+		assert_eq!(indexes, vec![0, 1, 2]);
+		assert_eq!(provider.receipts_by_block_hash(&H256::zero()).await, None);
+	}
 }
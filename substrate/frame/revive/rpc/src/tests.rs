@@ -21,13 +21,13 @@
 use crate::{
 	cli::{self, CliCommand},
 	example::TransactionBuilder,
-	EthRpcClient,
+	EthPubSubClient, EthRpcClient, SubscriptionItem, SubscriptionKind,
 };
 use clap::Parser;
 use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use pallet_revive::{
 	create1,
-	evm::{Account, BlockTag, U256},
+	evm::{Account, BlockTag, Filter, FilterResults, U256},
 };
 use static_init::dynamic;
 use std::{sync::Arc, thread};
@@ -219,3 +219,128 @@ async fn invalid_transaction() -> anyhow::Result<()> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn subscriptions() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let mut pending_txs = client
+		.subscribe_notifications(SubscriptionKind::NewPendingTransactions, None)
+		.await?;
+	let mut heads = client.subscribe_notifications(SubscriptionKind::NewHeads, None).await?;
+
+	let tx = TransactionBuilder::new(&client)
+		.value(U256::from(1_000_000_000_000u128))
+		.to(ethan.address())
+		.send()
+		.await?;
+
+	let item = pending_txs.next().await.expect("Subscription should not be closed")?;
+	assert_eq!(item, SubscriptionItem::TransactionHash(tx.hash()));
+
+	let receipt = tx.wait_for_receipt().await?;
+	loop {
+		let item = heads.next().await.expect("Subscription should not be closed")?;
+		let SubscriptionItem::Header(block) = item else { panic!("Expected a block header") };
+		if block.hash == receipt.block_hash {
+			break
+		}
+	}
+
+	// Only the `logs` subscription accepts a filter.
+	let err = client
+		.subscribe_notifications(SubscriptionKind::NewHeads, Some(Default::default()))
+		.await
+		.unwrap_err();
+	assert!(matches!(err, jsonrpsee::core::client::Error::Call(_)));
+	Ok(())
+}
+
+#[tokio::test]
+async fn block_receipts_and_fee_history() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let tx = TransactionBuilder::new(&client)
+		.value(U256::from(1_000_000_000_000u128))
+		.to(ethan.address())
+		.send()
+		.await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let receipts = client
+		.get_block_receipts(receipt.block_hash.into())
+		.await?
+		.expect("Block should exist");
+	assert!(receipts.contains(&receipt), "Block receipts should contain the transaction receipt.");
+
+	let fee_history = client
+		.fee_history(4u32.into(), receipt.block_number.into(), Some(vec![25.0, 75.0]))
+		.await?;
+	let block_count = fee_history.gas_used_ratio.len();
+	assert!(block_count > 0 && block_count <= 4);
+	assert_eq!(fee_history.oldest_block + block_count - 1, receipt.block_number);
+	assert_eq!(fee_history.base_fee_per_gas.len(), block_count + 1);
+	assert_eq!(fee_history.reward.len(), block_count);
+	assert!(fee_history.reward.iter().all(|rewards| rewards.len() == 2));
+	assert!(fee_history.gas_used_ratio.last().is_some_and(|ratio| *ratio > 0.0));
+
+	// Percentiles must be increasing.
+	let err = client
+		.fee_history(4u32.into(), BlockTag::Latest.into(), Some(vec![75.0, 25.0]))
+		.await
+		.unwrap_err();
+	assert!(matches!(err, jsonrpsee::core::client::Error::Call(_)));
+	Ok(())
+}
+
+#[tokio::test]
+async fn filters() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let block_filter = client.new_block_filter().await?;
+	let log_filter = client.new_filter(Default::default()).await?;
+	assert_ne!(block_filter, log_filter);
+
+	let tx = TransactionBuilder::new(&client)
+		.value(U256::from(1_000_000_000_000u128))
+		.to(ethan.address())
+		.send()
+		.await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let FilterResults::Hashes(hashes) = client.get_filter_changes(block_filter).await? else {
+		panic!("Expected block hashes");
+	};
+	assert!(hashes.contains(&receipt.block_hash), "Block filter should report the new block.");
+
+	// Changes are only reported once.
+	let FilterResults::Hashes(hashes) = client.get_filter_changes(block_filter).await? else {
+		panic!("Expected block hashes");
+	};
+	assert!(!hashes.contains(&receipt.block_hash));
+
+	// A transfer does not emit any log, an empty result decodes as the default variant.
+	let changes = client.get_filter_changes(log_filter).await?;
+	assert_eq!(changes, FilterResults::default());
+
+	assert!(client.uninstall_filter(block_filter).await?);
+	assert!(client.uninstall_filter(log_filter).await?);
+	assert!(!client.uninstall_filter(log_filter).await?);
+	assert!(client.get_filter_changes(log_filter).await.is_err());
+
+	// Filters pinned to a block never report any change.
+	let filter = Filter { block_hash: Some(receipt.block_hash), ..Default::default() };
+	let err = client.new_filter(filter).await.unwrap_err();
+	assert!(matches!(
+		err,
+		jsonrpsee::core::client::Error::Call(ref err)
+			if err.code() == jsonrpsee::types::ErrorCode::InvalidParams.code()
+	));
+	Ok(())
+}
@@ -31,6 +31,7 @@ mod debug_rpc_types;
 pub use debug_rpc_types::*;
 
 mod rpc_types;
+pub use rpc_types::*;
 mod rpc_types_gen;
 pub use rpc_types_gen::*;
 
@@ -15,8 +15,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::evm::{Bytes, CallTracer};
-use alloc::{fmt, string::String, vec::Vec};
+use crate::evm::Bytes;
+use alloc::{collections::BTreeMap, fmt, string::String, vec::Vec};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use serde::{
@@ -36,15 +36,19 @@ pub enum TracerConfig {
 		#[serde(rename = "withLog")]
 		with_logs: bool,
 	},
-}
 
-impl TracerConfig {
-	/// Build the tracer associated to this config.
-	pub fn build<G>(self, gas_mapper: G) -> CallTracer<U256, G> {
-		match self {
-			Self::CallTracer { with_logs } => CallTracer::new(with_logs, gas_mapper),
-		}
-	}
+	/// A tracer that captures the state of the accounts touched by the execution.
+	#[serde(rename = "prestateTracer")]
+	PrestateTracer {
+		/// Whether or not to only report the state modified by the execution, before and after
+		/// it.
+		#[serde(rename = "diffMode")]
+		diff_mode: bool,
+	},
+
+	/// A tracer that captures every host function executed by the contracts.
+	#[serde(rename = "stepTracer")]
+	StepTracer,
 }
 
 /// Custom deserializer to support the following JSON format:
@@ -56,6 +60,14 @@ impl TracerConfig {
 /// ```json
 /// { "tracer": "callTracer" }
 /// ```
+///
+/// ```json
+/// { "tracer": "prestateTracer", "tracerConfig": { "diffMode": true } }
+/// ```
+///
+/// ```json
+/// { "tracer": "stepTracer" }
+/// ```
 impl<'de> Deserialize<'de> for TracerConfig {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
@@ -76,6 +88,7 @@ impl<'de> Deserialize<'de> for TracerConfig {
 			{
 				let mut tracer_type: Option<String> = None;
 				let mut with_logs = None;
+				let mut diff_mode = None;
 
 				while let Some(key) = map.next_key::<String>()? {
 					match key.as_str() {
@@ -84,12 +97,15 @@ impl<'de> Deserialize<'de> for TracerConfig {
 						},
 						"tracerConfig" => {
 							#[derive(Deserialize)]
-							struct CallTracerConfig {
+							struct InnerTracerConfig {
 								#[serde(rename = "withLogs")]
 								with_logs: Option<bool>,
+								#[serde(rename = "diffMode")]
+								diff_mode: Option<bool>,
 							}
-							let inner: CallTracerConfig = map.next_value()?;
+							let inner: InnerTracerConfig = map.next_value()?;
 							with_logs = inner.with_logs;
+							diff_mode = inner.diff_mode;
 						},
 						_ => {},
 					}
@@ -98,6 +114,9 @@ impl<'de> Deserialize<'de> for TracerConfig {
 				match tracer_type.as_deref() {
 					Some("callTracer") =>
 						Ok(TracerConfig::CallTracer { with_logs: with_logs.unwrap_or(true) }),
+					Some("prestateTracer") =>
+						Ok(TracerConfig::PrestateTracer { diff_mode: diff_mode.unwrap_or(false) }),
+					Some("stepTracer") => Ok(TracerConfig::StepTracer),
 					_ => Err(de::Error::custom("Unsupported or missing tracer type")),
 				}
 			}
@@ -119,6 +138,12 @@ fn test_tracer_config_serialization() {
 			r#"{"tracer": "callTracer", "tracerConfig": { "withLogs": false }}"#,
 			TracerConfig::CallTracer { with_logs: false },
 		),
+		(r#"{"tracer": "prestateTracer"}"#, TracerConfig::PrestateTracer { diff_mode: false }),
+		(
+			r#"{"tracer": "prestateTracer", "tracerConfig": { "diffMode": true }}"#,
+			TracerConfig::PrestateTracer { diff_mode: true },
+		),
+		(r#"{"tracer": "stepTracer"}"#, TracerConfig::StepTracer),
 	];
 
 	for (json_data, expected) in tracers {
@@ -206,6 +231,88 @@ pub struct CallLog {
 	pub position: u32,
 }
 
+/// The trace produced by one of the tracers selected with a [`TracerConfig`].
+#[derive(TypeInfo, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum Trace {
+	/// A call trace, see [`TracerConfig::CallTracer`].
+	Call(CallTrace),
+	/// A step trace, see [`TracerConfig::StepTracer`].
+	Step(StepTrace),
+	/// A prestate trace, see [`TracerConfig::PrestateTracer`].
+	Prestate(PrestateTrace),
+}
+
+/// The state of the accounts touched by a transaction.
+#[derive(TypeInfo, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+	/// The state of the touched accounts before the execution.
+	Prestate(BTreeMap<H160, PrestateTraceInfo>),
+	/// The state modified by the execution.
+	DiffMode {
+		/// The state of the modified accounts before the execution.
+		pre: BTreeMap<H160, PrestateTraceInfo>,
+		/// The fields of the modified accounts that changed during the execution.
+		post: BTreeMap<H160, PrestateTraceInfo>,
+	},
+}
+
+/// The state of an account reported by the prestate tracer.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct PrestateTraceInfo {
+	/// The balance of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	/// The nonce of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<u32>,
+	/// The code of the account, if it is a contract.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	/// The touched storage slots of the account.
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub storage: BTreeMap<Bytes, Option<Bytes>>,
+}
+
+/// A trace of the host functions executed by a transaction.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct StepTrace<Gas = U256> {
+	/// Amount of gas used by the transaction.
+	pub gas: Gas,
+	/// Whether the transaction failed or reverted.
+	pub failed: bool,
+	/// Return data of the transaction.
+	#[serde(rename = "returnValue")]
+	pub return_value: Bytes,
+	/// The host functions executed by the transaction, in execution order.
+	#[serde(rename = "structLogs")]
+	pub struct_logs: Vec<StepLog<Gas>>,
+}
+
+/// A host function executed by a contract.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct StepLog<Gas = U256> {
+	/// Name of the host function.
+	pub op: String,
+	/// Amount of gas left before the host function was executed.
+	pub gas: Gas,
+	/// Amount of gas consumed by the host function.
+	#[serde(rename = "gasCost")]
+	pub gas_cost: Gas,
+	/// Call depth of the contract executing the host function, starting at 1.
+	pub depth: u32,
+	/// The error message if the host function trapped the contract.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
 /// A transaction trace
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TransactionTrace {
@@ -214,5 +321,5 @@ pub struct TransactionTrace {
 	pub tx_hash: H256,
 	/// The trace of the transaction.
 	#[serde(rename = "result")]
-	pub trace: CallTrace,
+	pub trace: Trace,
 }
@@ -17,6 +17,7 @@
 //! Utility impl for the RPC types.
 use super::*;
 use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
 use sp_core::{H160, U256};
 
 impl From<BlockNumberOrTag> for BlockNumberOrTagOrHash {
@@ -320,3 +321,22 @@ impl GenericTransaction {
 		}
 	}
 }
+
+/// Fee history results
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeeHistoryResult {
+	/// Lowest number block of returned range.
+	#[serde(rename = "oldestBlock")]
+	pub oldest_block: U256,
+	/// An array of block base fees per gas, including the next block after the newest of the
+	/// returned range.
+	#[serde(rename = "baseFeePerGas")]
+	pub base_fee_per_gas: Vec<U256>,
+	/// An array of block gas used ratios, computed as the ratio of `gasUsed` and `gasLimit`.
+	#[serde(rename = "gasUsedRatio")]
+	pub gas_used_ratio: Vec<f64>,
+	/// A two-dimensional array of effective priority fees per gas at the requested block
+	/// percentiles.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub reward: Vec<Vec<U256>>,
+}
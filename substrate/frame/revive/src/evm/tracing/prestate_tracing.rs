@@ -0,0 +1,219 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	evm::{Bytes, PrestateTrace, PrestateTraceInfo},
+	primitives::ExecReturnValue,
+	tracing::Tracer,
+	AddressMapper, BalanceOf, Config, ContractInfoOf, DispatchError, Key, MomentOf, Pallet,
+	PristineCode, Weight,
+};
+use alloc::collections::BTreeMap;
+use core::marker::PhantomData;
+use frame_support::traits::IsType;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::Bounded, SaturatedConversion};
+
+/// The state of an account, as seen by the [`PrestateTracer`].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+struct AccountState {
+	balance: U256,
+	nonce: u32,
+	code: Option<Bytes>,
+	storage: BTreeMap<Key, Option<Bytes>>,
+}
+
+impl AccountState {
+	/// Whether the account did not exist.
+	fn is_empty(&self) -> bool {
+		self.balance.is_zero() &&
+			self.nonce == 0 &&
+			self.code.is_none() &&
+			self.storage.values().all(Option::is_none)
+	}
+
+	/// Convert into a [`PrestateTraceInfo`], reporting all the fields.
+	fn into_info(self) -> PrestateTraceInfo {
+		PrestateTraceInfo {
+			balance: Some(self.balance),
+			nonce: Some(self.nonce),
+			code: self.code,
+			storage: self
+				.storage
+				.into_iter()
+				.map(|(key, value)| (key.unhashed().to_vec().into(), value))
+				.collect(),
+		}
+	}
+}
+
+/// A Tracer that reports the state of the accounts touched by a transaction.
+///
+/// The state of an account is captured the first time it is touched by the execution, either as
+/// the caller or the callee of a call, or when one of its storage slots is accessed. In diff mode,
+/// the state of the touched accounts is read again when the trace is collected, and only the
+/// accounts and fields modified by the execution are reported.
+pub struct PrestateTracer<T> {
+	/// Whether or not to only report the modified state.
+	diff_mode: bool,
+	/// The state of the touched accounts before they were first touched.
+	pre: BTreeMap<H160, AccountState>,
+	_phantom: PhantomData<T>,
+}
+
+impl<T> PrestateTracer<T> {
+	/// Create a new [`PrestateTracer`] instance.
+	pub fn new(diff_mode: bool) -> Self {
+		Self { diff_mode, pre: BTreeMap::new(), _phantom: PhantomData }
+	}
+
+	/// Whether or not the tracer only reports the modified state.
+	pub fn diff_mode(&self) -> bool {
+		self.diff_mode
+	}
+}
+
+impl<T: Config> PrestateTracer<T>
+where
+	BalanceOf<T>: Into<U256> + TryFrom<U256> + Bounded,
+	MomentOf<T>: Into<U256>,
+	T::Hash: IsType<H256>,
+{
+	/// Collect the state of the accounts touched since the last call to this function.
+	///
+	/// In diff mode, this must be called once the traced execution is over, as the current state
+	/// is used as the post state.
+	pub fn collect_trace(&mut self) -> Option<PrestateTrace> {
+		let pre = core::mem::take(&mut self.pre);
+		if pre.is_empty() {
+			return None;
+		}
+
+		if !self.diff_mode {
+			return Some(PrestateTrace::Prestate(
+				pre.into_iter().map(|(address, state)| (address, state.into_info())).collect(),
+			));
+		}
+
+		let mut pre_info = BTreeMap::new();
+		let mut post_info = BTreeMap::new();
+		for (address, mut pre_state) in pre {
+			let mut post_state = Self::account_state(&address, pre_state.storage.keys());
+			pre_state
+				.storage
+				.retain(|key, value| post_state.storage.get(key) != Some(&*value));
+			post_state.storage.retain(|key, _| pre_state.storage.contains_key(key));
+
+			let info = PrestateTraceInfo {
+				balance: (pre_state.balance != post_state.balance).then_some(post_state.balance),
+				nonce: (pre_state.nonce != post_state.nonce).then_some(post_state.nonce),
+				code: if pre_state.code != post_state.code {
+					post_state.code.clone()
+				} else {
+					None
+				},
+				storage: post_state.into_info().storage,
+			};
+			if info == PrestateTraceInfo::default() {
+				continue;
+			}
+
+			post_info.insert(address, info);
+			if !pre_state.is_empty() {
+				pre_info.insert(address, pre_state.into_info());
+			}
+		}
+
+		Some(PrestateTrace::DiffMode { pre: pre_info, post: post_info })
+	}
+
+	/// Read the current state of the account, including the given storage slots.
+	fn account_state<'a>(address: &H160, keys: impl Iterator<Item = &'a Key>) -> AccountState {
+		let account_id = T::AddressMapper::to_account_id(address);
+		let contract_info = ContractInfoOf::<T>::get(address);
+		AccountState {
+			balance: Pallet::<T>::evm_balance(address),
+			nonce: frame_system::Pallet::<T>::account_nonce(&account_id).saturated_into(),
+			code: contract_info
+				.as_ref()
+				.and_then(|info| PristineCode::<T>::get(info.code_hash))
+				.map(|code| code.to_vec().into()),
+			storage: keys
+				.map(|key| {
+					let value = contract_info.as_ref().and_then(|info| info.read(key));
+					(key.clone(), value.map(Into::into))
+				})
+				.collect(),
+		}
+	}
+
+	/// Record the state of the account if this is the first time it is touched.
+	fn touch(&mut self, address: H160) -> &mut AccountState {
+		self.pre
+			.entry(address)
+			.or_insert_with(|| Self::account_state(&address, core::iter::empty()))
+	}
+
+	/// Record the value of the storage slot if this is the first time it is accessed.
+	fn touch_storage(&mut self, address: H160, key: &Key, value: Option<&[u8]>) {
+		self.touch(address)
+			.storage
+			.entry(key.clone())
+			.or_insert_with(|| value.map(|value| value.to_vec().into()));
+	}
+}
+
+impl<T: Config> Tracer for PrestateTracer<T>
+where
+	BalanceOf<T>: Into<U256> + TryFrom<U256> + Bounded,
+	MomentOf<T>: Into<U256>,
+	T::Hash: IsType<H256>,
+{
+	fn enter_child_span(
+		&mut self,
+		from: H160,
+		to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas: Weight,
+	) {
+		self.touch(from);
+		self.touch(to);
+	}
+
+	fn log_event(&mut self, _event: H160, _topics: &[H256], _data: &[u8]) {}
+
+	fn exit_child_span(&mut self, _output: &ExecReturnValue, _gas_used: Weight) {}
+
+	fn exit_child_span_with_error(&mut self, _error: DispatchError, _gas_used: Weight) {}
+
+	fn storage_read(&mut self, address: H160, key: &Key, value: Option<&[u8]>) {
+		self.touch_storage(address, key, value);
+	}
+
+	fn storage_write(
+		&mut self,
+		address: H160,
+		key: &Key,
+		old_value: Option<&[u8]>,
+		_new_value: Option<&[u8]>,
+	) {
+		self.touch_storage(address, key, old_value);
+	}
+}
@@ -0,0 +1,114 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	evm::{StepLog, StepTrace},
+	primitives::ExecReturnValue,
+	tracing::Tracer,
+	DispatchError, Weight,
+};
+use alloc::{format, string::ToString, vec::Vec};
+use sp_core::{H160, H256, U256};
+
+/// A Tracer that reports every host function executed by the contracts of a transaction.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct StepTracer<Gas, GasMapper> {
+	/// Map Weight to Gas equivalent.
+	gas_mapper: GasMapper,
+	/// The steps recorded since the top level call was entered.
+	steps: Vec<StepLog<Gas>>,
+	/// Stack of the steps whose host function is executing, with the weight left before it.
+	pending: Vec<(usize, Weight)>,
+	/// The current call depth.
+	depth: u32,
+	/// The trace of the last top level call.
+	trace: Option<StepTrace<Gas>>,
+}
+
+impl<Gas, GasMapper> StepTracer<Gas, GasMapper> {
+	/// Create a new [`StepTracer`] instance.
+	pub fn new(gas_mapper: GasMapper) -> Self {
+		Self { gas_mapper, steps: Vec::new(), pending: Vec::new(), depth: 0, trace: None }
+	}
+
+	/// Collect the trace of the last top level call.
+	pub fn collect_trace(&mut self) -> Option<StepTrace<Gas>> {
+		self.trace.take()
+	}
+}
+
+impl<Gas: Default, GasMapper: Fn(Weight) -> Gas> StepTracer<Gas, GasMapper> {
+	/// Leave the current call, and build the trace if it was the top level call.
+	fn exit(&mut self, gas_used: Weight, failed: bool, return_value: &[u8]) {
+		self.depth = self.depth.saturating_sub(1);
+		if self.depth == 0 {
+			self.pending.clear();
+			self.trace = Some(StepTrace {
+				gas: (self.gas_mapper)(gas_used),
+				failed,
+				return_value: return_value.to_vec().into(),
+				struct_logs: core::mem::take(&mut self.steps),
+			});
+		}
+	}
+}
+
+impl<Gas: Default, GasMapper: Fn(Weight) -> Gas> Tracer for StepTracer<Gas, GasMapper> {
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		_to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas: Weight,
+	) {
+		self.depth += 1;
+	}
+
+	fn log_event(&mut self, _event: H160, _topics: &[H256], _data: &[u8]) {}
+
+	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: Weight) {
+		self.exit(gas_used, output.did_revert(), &output.data);
+	}
+
+	fn exit_child_span_with_error(&mut self, _error: DispatchError, gas_used: Weight) {
+		self.exit(gas_used, true, &[]);
+	}
+
+	fn enter_syscall(&mut self, name: &str, gas_left: Weight) {
+		self.steps.push(StepLog {
+			op: name.to_string(),
+			gas: (self.gas_mapper)(gas_left),
+			depth: self.depth,
+			..Default::default()
+		});
+		self.pending.push((self.steps.len() - 1, gas_left));
+	}
+
+	fn exit_syscall(&mut self, gas_left: Weight, error: Option<DispatchError>) {
+		let Some((index, gas_before)) = self.pending.pop() else { return };
+		let step = &mut self.steps[index];
+		step.gas_cost = (self.gas_mapper)(gas_before.saturating_sub(gas_left));
+		step.error = error.map(|error| match error {
+			DispatchError::Module(sp_runtime::ModuleError { message, .. }) =>
+				message.unwrap_or_default().to_string(),
+			_ => format!("{:?}", error),
+		});
+	}
+}
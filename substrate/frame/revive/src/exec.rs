@@ -74,6 +74,7 @@ pub const EMPTY_CODE_HASH: H256 =
 	H256(sp_core::hex2array!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"));
 
 /// Combined key type for both fixed and variable sized storage keys.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Key {
 	/// Variant for fixed sized keys.
 	Fix([u8; 32]),
@@ -83,11 +84,6 @@ pub enum Key {
 
 impl Key {
 	/// Reference to the raw unhashed key.
-	///
-	/// # Note
-	///
-	/// Only used by benchmarking in order to generate storage collisions on purpose.
-	#[cfg(feature = "runtime-benchmarks")]
 	pub fn unhashed(&self) -> &[u8] {
 		match self {
 			Key::Fix(v) => v.as_ref(),
@@ -103,10 +99,12 @@ impl Key {
 		}
 	}
 
+	/// Create a fixed sized key.
 	pub fn from_fixed(v: [u8; 32]) -> Self {
 		Self::Fix(v)
 	}
 
+	/// Create a variable sized key, fails if `v` exceeds the maximum key size.
 	pub fn try_from_var(v: Vec<u8>) -> Result<Self, ()> {
 		VarSizedKey::try_from(v).map(Self::Var).map_err(|_| ())
 	}
@@ -740,7 +738,6 @@ where
 		)? {
 			stack.run(executable, input_data).map(|_| stack.first_frame.last_frame_output)
 		} else {
-			let result = Self::transfer_from_origin(&origin, &origin, &dest, value);
 			if_tracing(|t| {
 				t.enter_child_span(
 					origin.account_id().map(T::AddressMapper::to_address).unwrap_or_default(),
@@ -751,10 +748,11 @@ where
 					&input_data,
 					Weight::zero(),
 				);
-				match result {
-					Ok(ref output) => t.exit_child_span(&output, Weight::zero()),
-					Err(e) => t.exit_child_span_with_error(e.error.into(), Weight::zero()),
-				}
+			});
+			let result = Self::transfer_from_origin(&origin, &origin, &dest, value);
+			if_tracing(|t| match result {
+				Ok(ref output) => t.exit_child_span(&output, Weight::zero()),
+				Err(e) => t.exit_child_span_with_error(e.error.into(), Weight::zero()),
 			});
 
 			result
@@ -1513,6 +1511,18 @@ where
 			)? {
 				self.run(executable, input_data)
 			} else {
+				if_tracing(|t| {
+					t.enter_child_span(
+						T::AddressMapper::to_address(self.account_id()),
+						T::AddressMapper::to_address(&dest),
+						false,
+						is_read_only,
+						value,
+						&input_data,
+						Weight::zero(),
+					);
+				});
+
 				let result = if is_read_only && value.is_zero() {
 					Ok(Default::default())
 				} else if is_read_only {
@@ -1526,20 +1536,9 @@ where
 					)
 				};
 
-				if_tracing(|t| {
-					t.enter_child_span(
-						T::AddressMapper::to_address(self.account_id()),
-						T::AddressMapper::to_address(&dest),
-						false,
-						is_read_only,
-						value,
-						&input_data,
-						Weight::zero(),
-					);
-					match result {
-						Ok(ref output) => t.exit_child_span(&output, Weight::zero()),
-						Err(e) => t.exit_child_span_with_error(e.error.into(), Weight::zero()),
-					}
+				if_tracing(|t| match result {
+					Ok(ref output) => t.exit_child_span(&output, Weight::zero()),
+					Err(e) => t.exit_child_span_with_error(e.error.into(), Weight::zero()),
 				});
 				result.map(|_| ())
 			}
@@ -1654,7 +1653,15 @@ where
 	}
 
 	fn get_storage(&mut self, key: &Key) -> Option<Vec<u8>> {
-		self.top_frame_mut().contract_info().read(key)
+		let value = self.top_frame_mut().contract_info().read(key);
+		if_tracing(|tracer| {
+			tracer.storage_read(
+				T::AddressMapper::to_address(self.account_id()),
+				key,
+				value.as_deref(),
+			);
+		});
+		value
 	}
 
 	fn get_storage_size(&mut self, key: &Key) -> Option<u32> {
@@ -1668,6 +1675,15 @@ where
 		take_old: bool,
 	) -> Result<WriteOutcome, DispatchError> {
 		let frame = self.top_frame_mut();
+		if_tracing(|tracer| {
+			let old_value = frame.contract_info().read(key);
+			tracer.storage_write(
+				T::AddressMapper::to_address(&frame.account_id),
+				key,
+				old_value.as_deref(),
+				value.as_deref(),
+			);
+		});
 		frame.contract_info.get(&frame.account_id).write(
 			key.into(),
 			value,
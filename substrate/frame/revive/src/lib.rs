@@ -42,8 +42,11 @@ pub mod tracing;
 pub mod weights;
 
 use crate::{
-	evm::{runtime::GAS_PRICE, CallTrace, GasEncoder, GenericTransaction, TracerConfig},
-	exec::{AccountIdOf, ExecError, Executable, Key, Stack as ExecStack},
+	evm::{
+		runtime::GAS_PRICE, CallTrace, CallTracer, EvmTracer, GasEncoder, GenericTransaction,
+		PrestateTracer, StepTracer, Trace, TracerConfig,
+	},
+	exec::{AccountIdOf, ExecError, Executable, Stack as ExecStack},
 	gas::GasMeter,
 	storage::{meter::Meter as StorageMeter, ContractInfo, DeletionQueueManager},
 	wasm::{CodeInfo, RuntimeCosts, WasmBlob},
@@ -80,7 +83,7 @@ use sp_runtime::{
 
 pub use crate::{
 	address::{create1, create2, AccountId32Mapper, AddressMapper},
-	exec::{MomentOf, Origin},
+	exec::{Key, MomentOf, Origin},
 	pallet::*,
 };
 pub use primitives::*;
@@ -1340,6 +1343,18 @@ where
 		Self::evm_fee_to_gas(fee)
 	}
 
+	/// Build the tracer selected by the given tracer config.
+	pub fn evm_tracer(config: TracerConfig) -> EvmTracer<T> {
+		match config {
+			TracerConfig::CallTracer { with_logs } =>
+				EvmTracer::CallTracer(CallTracer::new(with_logs, Self::evm_gas_from_weight)),
+			TracerConfig::PrestateTracer { diff_mode } =>
+				EvmTracer::PrestateTracer(PrestateTracer::new(diff_mode)),
+			TracerConfig::StepTracer =>
+				EvmTracer::StepTracer(StepTracer::new(Self::evm_gas_from_weight)),
+		}
+	}
+
 	/// Get the block gas limit.
 	pub fn evm_block_gas_limit() -> U256 {
 		let max_block_weight = T::BlockWeights::get()
@@ -1452,7 +1467,7 @@ environmental!(executing_contract: bool);
 
 sp_api::decl_runtime_apis! {
 	/// The API used to dry-run contract interactions.
-	#[api_version(1)]
+	#[api_version(2)]
 	pub trait ReviveApi<AccountId, Balance, Nonce, BlockNumber> where
 		AccountId: Codec,
 		Balance: Codec,
@@ -1522,33 +1537,64 @@ sp_api::decl_runtime_apis! {
 		) -> GetStorageResult;
 
 
-		/// Traces the execution of an entire block and returns call traces.
+		/// Traces the execution of an entire block and returns the traces of its transactions.
 		///
 		/// This is intended to be called through `state_call` to replay the block from the
 		/// parent block.
 		///
 		/// See eth-rpc `debug_traceBlockByNumber` for usage.
+		#[changed_in(2)]
 		fn trace_block(
 			block: Block,
 			config: TracerConfig
 		) -> Vec<(u32, CallTrace)>;
 
+		/// Traces the execution of an entire block and returns the traces of its transactions.
+		///
+		/// This is intended to be called through `state_call` to replay the block from the
+		/// parent block.
+		///
+		/// See eth-rpc `debug_traceBlockByNumber` for usage.
+		fn trace_block(
+			block: Block,
+			config: TracerConfig
+		) -> Vec<(u32, Trace)>;
+
 		/// Traces the execution of a specific transaction within a block.
 		///
 		/// This is intended to be called through `state_call` to replay the block from the
 		/// parent hash up to the transaction.
 		///
 		/// See eth-rpc `debug_traceTransaction` for usage.
+		#[changed_in(2)]
 		fn trace_tx(
 			block: Block,
 			tx_index: u32,
 			config: TracerConfig
 		) -> Option<CallTrace>;
 
+		/// Traces the execution of a specific transaction within a block.
+		///
+		/// This is intended to be called through `state_call` to replay the block from the
+		/// parent hash up to the transaction.
+		///
+		/// See eth-rpc `debug_traceTransaction` for usage.
+		fn trace_tx(
+			block: Block,
+			tx_index: u32,
+			config: TracerConfig
+		) -> Option<Trace>;
+
 		/// Dry run and return the trace of the given call.
 		///
 		/// See eth-rpc `debug_traceCall` for usage.
+		#[changed_in(2)]
 		fn trace_call(tx: GenericTransaction, config: TracerConfig) -> Result<CallTrace, EthTransactError>;
 
+		/// Dry run and return the trace of the given call.
+		///
+		/// See eth-rpc `debug_traceCall` for usage.
+		fn trace_call(tx: GenericTransaction, config: TracerConfig) -> Result<Trace, EthTransactError>;
+
 	}
 }
@@ -4578,6 +4578,121 @@ fn tracing_works() {
 	});
 }
 
+#[test]
+fn prestate_tracing_works() {
+	use crate::evm::*;
+	use alloc::collections::BTreeMap;
+	let (code, code_hash) = compile_module("set_empty_storage").unwrap();
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let account_info = |address: &H160, storage: BTreeMap<Bytes, Option<Bytes>>| {
+			let account_id = <Test as Config>::AddressMapper::to_account_id(address);
+			PrestateTraceInfo {
+				balance: Some(Pallet::<Test>::evm_balance(address)),
+				nonce: Some(System::account_nonce(&account_id) as u32),
+				code: ContractInfoOf::<Test>::get(address)
+					.map(|_| PristineCode::<Test>::get(code_hash).unwrap().to_vec().into()),
+				storage,
+			}
+		};
+		let key = Bytes::from(vec![0u8; 32]);
+		let value = Bytes::from(vec![0u8; 4]);
+
+		// The first call creates the storage item, and charges a storage deposit to the caller.
+		let pre = BTreeMap::from([
+			(ALICE_ADDR, account_info(&ALICE_ADDR, Default::default())),
+			(addr, account_info(&addr, BTreeMap::from([(key.clone(), None)]))),
+		]);
+		let mut tracer = PrestateTracer::<Test>::new(true);
+		trace(&mut tracer, || builder::bare_call(addr).build_and_unwrap_result());
+
+		let post = BTreeMap::from([
+			(
+				ALICE_ADDR,
+				PrestateTraceInfo {
+					balance: Some(Pallet::<Test>::evm_balance(&ALICE_ADDR)),
+					..Default::default()
+				},
+			),
+			(
+				addr,
+				PrestateTraceInfo {
+					storage: BTreeMap::from([(key.clone(), Some(value.clone()))]),
+					..Default::default()
+				},
+			),
+		]);
+		assert_eq!(tracer.collect_trace(), Some(PrestateTrace::DiffMode { pre, post }));
+
+		// The second call overwrites the storage item with the same value.
+		let pre = BTreeMap::from([
+			(ALICE_ADDR, account_info(&ALICE_ADDR, Default::default())),
+			(addr, account_info(&addr, BTreeMap::from([(key.clone(), Some(value.clone()))]))),
+		]);
+		let mut tracer = PrestateTracer::<Test>::new(false);
+		trace(&mut tracer, || builder::bare_call(addr).build_and_unwrap_result());
+		assert_eq!(tracer.collect_trace(), Some(PrestateTrace::Prestate(pre.clone())));
+
+		// Nothing is modified, so nothing is reported in diff mode.
+		let mut tracer = PrestateTracer::<Test>::new(true);
+		trace(&mut tracer, || builder::bare_call(addr).build_and_unwrap_result());
+		assert_eq!(
+			tracer.collect_trace(),
+			Some(PrestateTrace::DiffMode { pre: Default::default(), post: Default::default() })
+		);
+		assert_eq!(tracer.collect_trace(), None);
+	});
+}
+
+#[test]
+fn step_tracing_works() {
+	use crate::evm::*;
+	let (code_caller, _) = compile_module("call").unwrap();
+	let (code_callee, _) = compile_module("set_empty_storage").unwrap();
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr: addr_caller, .. } =
+			builder::bare_instantiate(Code::Upload(code_caller)).build_and_unwrap_contract();
+		let Contract { addr: addr_callee, .. } =
+			builder::bare_instantiate(Code::Upload(code_callee)).build_and_unwrap_contract();
+
+		let mut tracer = StepTracer::new(|w: Weight| w.ref_time());
+		let gas_consumed = trace(&mut tracer, || {
+			builder::bare_call(addr_caller)
+				.data((0u32, addr_callee).encode())
+				.build()
+				.gas_consumed
+		});
+
+		let step_trace = tracer.collect_trace().unwrap();
+		assert_eq!(step_trace.gas, gas_consumed.ref_time());
+		assert!(!step_trace.failed);
+		assert_eq!(
+			step_trace
+				.struct_logs
+				.iter()
+				.map(|step| (step.op.as_str(), step.depth, step.error.is_none()))
+				.collect::<Vec<_>>(),
+			vec![
+				("call_data_size", 1, true),
+				("call_data_copy", 1, true),
+				("call", 1, true),
+				("set_storage", 2, true),
+			]
+		);
+		assert!(step_trace
+			.struct_logs
+			.iter()
+			.all(|step| step.gas_cost > 0 && step.gas >= step.gas_cost));
+		// The cost of the call includes the cost of the callee execution.
+		assert!(step_trace.struct_logs[2].gas_cost > step_trace.struct_logs[3].gas_cost);
+		assert_eq!(tracer.collect_trace(), None);
+	});
+}
+
 #[test]
 fn unknown_precompiles_revert() {
 	let (code, _code_hash) = compile_module("read_only_call").unwrap();
@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{primitives::ExecReturnValue, DispatchError, Weight};
+use crate::{exec::Key, primitives::ExecReturnValue, DispatchError, Weight};
 use environmental::environmental;
 use sp_core::{H160, H256, U256};
 
@@ -61,4 +61,25 @@ pub trait Tracer {
 
 	/// Called when a contract call terminates with an error
 	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_left: Weight);
+
+	/// Called when a storage slot of the contract at `address` is read.
+	fn storage_read(&mut self, _address: H160, _key: &Key, _value: Option<&[u8]>) {}
+
+	/// Called before a storage slot of the contract at `address` is written.
+	fn storage_write(
+		&mut self,
+		_address: H160,
+		_key: &Key,
+		_old_value: Option<&[u8]>,
+		_new_value: Option<&[u8]>,
+	) {
+	}
+
+	/// Called before a host function is executed.
+	fn enter_syscall(&mut self, _name: &str, _gas_left: Weight) {}
+
+	/// Called after a host function is executed.
+	///
+	/// `error` is set if the host function trapped the contract because of an error.
+	fn exit_syscall(&mut self, _gas_left: Weight, _error: Option<DispatchError>) {}
 }
@@ -21,7 +21,7 @@ use cumulus_client_consensus_proposer::Proposer;
 use cumulus_client_service::{
 	build_network, build_relay_chain_interface, prepare_node_config, start_relay_chain_tasks,
 	BuildNetworkParams, CollatorSybilResistance, DARecoveryProfile, ParachainHostFunctions,
-	StartRelayChainTasksParams,
+	PeerBlockFetcher, StartRelayChainTasksParams,
 };
 #[docify::export(cumulus_primitives)]
 use cumulus_primitives_core::{
@@ -33,7 +33,7 @@ use cumulus_relay_chain_interface::{OverseerHandle, RelayChainInterface};
 // Substrate Imports
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
 use prometheus_endpoint::Registry;
-use sc_client_api::Backend;
+use sc_client_api::{Backend, HeaderBackend};
 use sc_consensus::ImportQueue;
 use sc_executor::{HeapAllocStrategy, WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY};
 use sc_network::NetworkBlock;
@@ -285,6 +285,13 @@ pub async fn start_parachain_node(
 		})
 		.await?;
 
+	let block_fetcher = PeerBlockFetcher::new(
+		network.clone(),
+		sync_service.clone(),
+		client.chain_info().genesis_hash,
+		parachain_config.chain_spec.fork_id(),
+	);
+
 	if parachain_config.offchain_worker.enabled {
 		use futures::FutureExt;
 
@@ -386,6 +393,7 @@ pub async fn start_parachain_node(
 		relay_chain_slot_duration,
 		recovery_handle: Box::new(overseer_handle.clone()),
 		sync_service: sync_service.clone(),
+		block_fetcher: Some(Box::new(block_fetcher)),
 	})?;
 
 	if validator {
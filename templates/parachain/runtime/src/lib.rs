@@ -98,7 +98,7 @@ pub type UncheckedExtrinsic =
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (pallet_collator_selection::migration::v3::MigrationToV3<Runtime, Balances>,);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
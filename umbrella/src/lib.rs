@@ -503,6 +503,10 @@ pub use pallet_meta_tx;
 #[cfg(feature = "pallet-migrations")]
 pub use pallet_migrations;
 
+/// RPC interface for the multi-block migrations pallet.
+#[cfg(feature = "pallet-migrations-rpc")]
+pub use pallet_migrations_rpc;
+
 /// FRAME's mixnet pallet.
 #[cfg(feature = "pallet-mixnet")]
 pub use pallet_mixnet;